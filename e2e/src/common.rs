@@ -2,6 +2,7 @@
 #![allow(unused_variables)]
 
 use futures::{StreamExt, TryStreamExt};
+use k8s_openapi::api::core::v1::Pod;
 use kube::{
     api::{
         Api, AttachedProcess, DeleteParams, DynamicObject, ListParams, Patch, PatchParams,
@@ -56,6 +57,9 @@ pub enum Error {
 
     #[error("Pods are inconsistent with replica set!")]
     VReplicaSetFailed,
+
+    #[error("Current state does not match the desired state after a crash!")]
+    CrashEnumerationFailed,
 }
 
 pub async fn apply_file(
@@ -137,3 +141,49 @@ pub fn run_command(program: &str, args: Vec<&str>, err_msg: &str) -> (String, St
         String::from_utf8_lossy(&cmd.stderr).to_string(),
     )
 }
+
+// The port of the metrics server of the controllers (see METRICS_ADDR in deploy/*/deploy_*.yaml)
+const METRICS_PORT: u16 = 8080;
+
+// controller_state_matches asks the running pods of the controller (labeled app.kubernetes.io/name=<controller>
+// in controller_namespace) whether the current state matches the custom resource namespace/name.
+// The shim layer answers at /state-matches/<namespace>/<name> with the executable current_state_matches of
+// the liveness theorem of the controller, which is verified to be the predicate the proofs are about.
+// Only the leader writes the objects, and the answer of a replica depends on the objects it wrote
+// (see WrittenObjects in src/shim_layer/cluster_snapshot.rs), so the state matches if any replica says so.
+pub async fn controller_state_matches(
+    client: Client,
+    controller_namespace: &str,
+    controller: &str,
+    namespace: &str,
+    name: &str,
+) -> Result<bool, Error> {
+    let pod_api: Api<Pod> = Api::namespaced(client, controller_namespace);
+    let pods = pod_api
+        .list(&ListParams::default().labels(&format!("app.kubernetes.io/name={}", controller)))
+        .await?;
+    for pod in pods.items.iter().filter(|pod| {
+        pod.metadata.deletion_timestamp.is_none()
+            && pod.status.as_ref().and_then(|status| status.phase.clone())
+                == Some("Running".to_string())
+    }) {
+        let path = format!(
+            "/api/v1/namespaces/{}/pods/{}:{}/proxy/state-matches/{}/{}",
+            controller_namespace,
+            pod.name_any(),
+            METRICS_PORT,
+            namespace,
+            name
+        );
+        // kubectl prints the body of a 200 response and fails with the status of any other one
+        let (out, _) = run_command(
+            "kubectl",
+            vec!["get", "--raw", path.as_str()],
+            "failed to get state-matches",
+        );
+        if out.trim() == "matches" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,239 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{Api, DeleteParams, PostParams},
+    Client,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::*;
+
+use crate::common::*;
+
+// The crash-point enumeration driver tests the liveness property the proofs claim for a controller,
+// always(desired_state_is(cr)) ~> always(current_state_matches(cr)), under every crash the shim layer can inject:
+//
+// 1. It runs the workload once with a fault injection config that has no faults and only counts the Write requests,
+//    and reads from the progress of the config the number N of requests that change the cluster state
+//    or call the external API (the "Write" requests; see FaultRequestKind in src/shim_layer/fault_injection.rs).
+// 2. For each crash point i in 0..=N, it runs the workload again from scratch with the controller crashing
+//    once after the first i write requests are sent: before sending the (i + 1)-th one, or after sending the last one.
+//    After each step of the workload it waits until current_state_matches holds, which has to happen
+//    after the controller restarts (the controller Deployment restarts the crashed pod).
+//
+// The controller has to run with fault injection enabled (the "crash" argument) and serve the health probe
+// that current_state_matches asks (METRICS_ADDR); see deploy_crash.yaml.
+
+const CONFIG_MAP_NAME: &str = "fault-injection-config";
+const CONFIG_MAP_NAMESPACE: &str = "default";
+
+// How long a step of the workload has to reach current_state_matches, including a restart of the controller
+// that may be delayed by the back-off of a crash-looping pod
+const STEP_TIMEOUT: Duration = Duration::from_secs(600);
+const CLEAN_UP_TIMEOUT: Duration = Duration::from_secs(300);
+const POLL_PERIOD: Duration = Duration::from_secs(5);
+// The progress is settled if it does not change for this long after the last step
+const SETTLE_PERIOD: Duration = Duration::from_secs(30);
+
+// CrashWorkload is a workload the driver runs for a custom resource, step by step.
+#[allow(async_fn_in_trait)]
+pub trait CrashWorkload {
+    // The key (kind/namespace/name) of the custom resource, which the shim layer uses as cr_key
+    fn cr_key(&self) -> String;
+
+    fn steps(&self) -> usize;
+
+    // run_step changes the desired state, e.g., by creating or updating the custom resource
+    async fn run_step(&self, client: Client, step: usize) -> Result<(), Error>;
+
+    // current_state_matches checks the current_state_matches predicate in the liveness theorem of the controller,
+    // which should be asked to the verified checker of the controller (see controller_state_matches)
+    // instead of being ported by hand; Ok(false) means the state does not match yet
+    async fn current_state_matches(&self, client: Client) -> Result<bool, Error>;
+
+    // clean_up deletes the custom resource and everything it owns so that the next run starts from scratch
+    async fn clean_up(&self, client: Client) -> Result<(), Error>;
+
+    // is_cleaned_up tells whether clean_up has taken effect
+    async fn is_cleaned_up(&self, client: Client) -> Result<bool, Error>;
+}
+
+// The part of the progress entry of the fault injection config that the driver reads
+#[derive(Clone, Debug, Default, Deserialize)]
+struct FaultProgress {
+    #[serde(default)]
+    counts: BTreeMap<String, u64>,
+    #[serde(default)]
+    fired: BTreeSet<usize>,
+}
+
+// crash_point_fault returns the fault that crashes the controller after the first i of the n write requests are sent
+fn crash_point_fault(i: u64, n: u64) -> serde_json::Value {
+    if i < n {
+        json!({"request": "Write", "index": i + 1, "fault": "CrashBeforeSend"})
+    } else {
+        json!({"request": "Write", "index": n, "fault": "CrashAfterSend"})
+    }
+}
+
+// reset_fault_injection_config replaces the fault injection config with a new spec and no progress
+async fn reset_fault_injection_config(
+    client: Client,
+    cr_key: &String,
+    faults: Vec<serde_json::Value>,
+) -> Result<(), Error> {
+    let config_map_api = Api::<ConfigMap>::namespaced(client, CONFIG_MAP_NAMESPACE);
+    match config_map_api
+        .delete(CONFIG_MAP_NAME, &DeleteParams::default())
+        .await
+    {
+        Err(kube::Error::Api(err)) if err.code == 404 => {}
+        Err(err) => return Err(err.into()),
+        Ok(_) => {}
+    }
+    // The shim layer only counts (and records) the request kinds the config asks for, so count Write for the driver
+    let spec = json!({"crKey": cr_key, "faults": faults, "count": ["Write"]});
+    let mut config_map = ConfigMap::default();
+    config_map.metadata.name = Some(CONFIG_MAP_NAME.to_string());
+    config_map.data = Some(BTreeMap::from([(
+        "spec".to_string(),
+        serde_json::to_string(&spec)?,
+    )]));
+    config_map_api
+        .create(&PostParams::default(), &config_map)
+        .await?;
+    info!("Fault injection config: {}", spec);
+    Ok(())
+}
+
+async fn get_fault_progress(client: Client) -> Result<FaultProgress, Error> {
+    let config_map_api = Api::<ConfigMap>::namespaced(client, CONFIG_MAP_NAMESPACE);
+    let config_map = config_map_api.get(CONFIG_MAP_NAME).await?;
+    match config_map
+        .data
+        .as_ref()
+        .and_then(|data| data.get("progress"))
+    {
+        Some(progress) => Ok(serde_json::from_str(progress)?),
+        None => Ok(FaultProgress::default()),
+    }
+}
+
+async fn wait_for_current_state_matches<W: CrashWorkload>(
+    workload: &W,
+    client: Client,
+) -> Result<(), Error> {
+    let start = Instant::now();
+    loop {
+        match workload.current_state_matches(client.clone()).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => info!("Current state does not match yet."),
+            Err(e) => info!("Check current state failed with error {}.", e),
+        }
+        if start.elapsed() > STEP_TIMEOUT {
+            error!("Time out on current_state_matches");
+            return Err(Error::Timeout);
+        }
+        sleep(POLL_PERIOD).await;
+    }
+}
+
+async fn clean_up<W: CrashWorkload>(workload: &W, client: Client) -> Result<(), Error> {
+    workload.clean_up(client.clone()).await?;
+    let start = Instant::now();
+    loop {
+        match workload.is_cleaned_up(client.clone()).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => info!("Still cleaning up."),
+            Err(e) => info!("Check clean up failed with error {}.", e),
+        }
+        if start.elapsed() > CLEAN_UP_TIMEOUT {
+            error!("Time out on clean up");
+            return Err(Error::Timeout);
+        }
+        sleep(POLL_PERIOD).await;
+    }
+}
+
+// run_workload runs the workload from scratch under the faults and returns the progress after the last step
+async fn run_workload<W: CrashWorkload>(
+    workload: &W,
+    client: Client,
+    faults: Vec<serde_json::Value>,
+) -> Result<FaultProgress, Error> {
+    clean_up(workload, client.clone()).await?;
+    reset_fault_injection_config(client.clone(), &workload.cr_key(), faults).await?;
+    for step in 0..workload.steps() {
+        info!("Running step {} of the workload", step);
+        workload.run_step(client.clone(), step).await?;
+        wait_for_current_state_matches(workload, client.clone()).await?;
+    }
+    // The controller may issue more requests after the state matches, so wait until the progress settles
+    let mut progress = get_fault_progress(client.clone()).await?;
+    let mut settled_since = Instant::now();
+    while settled_since.elapsed() < SETTLE_PERIOD {
+        sleep(POLL_PERIOD).await;
+        let new_progress = get_fault_progress(client.clone()).await?;
+        if new_progress.counts != progress.counts {
+            progress = new_progress;
+            settled_since = Instant::now();
+        }
+    }
+    // Check that the state still matches, i.e., the controller does not break it after reaching it
+    if !workload.current_state_matches(client.clone()).await? {
+        error!("Current state does not match after the progress settles");
+        return Err(Error::CrashEnumerationFailed);
+    }
+    Ok(progress)
+}
+
+pub async fn crash_enumeration_test<W: CrashWorkload>(workload: W) -> Result<(), Error> {
+    let client = Client::try_default().await?;
+
+    info!(
+        "Counting the write requests of the workload for {}",
+        workload.cr_key()
+    );
+    let progress = run_workload(&workload, client.clone(), vec![]).await?;
+    let n = *progress.counts.get("Write").unwrap_or(&0);
+    info!(
+        "The workload issues {} write requests: {:?}",
+        n, progress.counts
+    );
+    if n == 0 {
+        error!("The workload issues no write requests; is the controller running with fault injection?");
+        return Err(Error::CrashEnumerationFailed);
+    }
+
+    // A run may issue fewer write requests than the counting run (e.g., when it reads a state the crash changes),
+    // in which case its crash never fires; such crash points are reported but do not fail the test
+    let mut missed = vec![];
+    for i in 0..=n {
+        let fault = crash_point_fault(i, n);
+        info!("Crash point {}/{}: {}", i, n, fault);
+        let progress = run_workload(&workload, client.clone(), vec![fault]).await?;
+        if !progress.fired.contains(&0) {
+            warn!("Crash point {} is not reached: {:?}", i, progress.counts);
+            missed.push(i);
+        }
+    }
+
+    clean_up(&workload, client.clone()).await?;
+    if missed.is_empty() {
+        info!(
+            "Crash enumeration test passed for all {} crash points.",
+            n + 1
+        );
+    } else {
+        info!(
+            "Crash enumeration test passed; crash points {:?} of {} are not reached.",
+            missed,
+            n + 1
+        );
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 pub mod common;
+pub mod crash_enumeration;
 pub mod fluent_e2e;
 pub mod rabbitmq_e2e;
 pub mod vreplicaset_e2e;
@@ -12,7 +13,9 @@ use rabbitmq_e2e::{rabbitmq_e2e_test, rabbitmq_ephemeral_e2e_test, rabbitmq_scal
 use std::str::FromStr;
 use std::{env, sync::Arc};
 use tracing::*;
-use vreplicaset_e2e::vreplicaset_e2e_test;
+use vreplicaset_e2e::{
+    vreplicaset_crash_enumeration_test, vreplicaset_e2e_test, vreplicaset_leader_election_e2e_test,
+};
 use zookeeper_e2e::{zookeeper_e2e_test, zookeeper_ephemeral_e2e_test, zookeeper_scaling_e2e_test};
 
 #[tokio::main]
@@ -54,6 +57,14 @@ async fn main() -> Result<(), Error> {
             info!("Running vreplicaset end-to-end test");
             return vreplicaset_e2e_test().await;
         }
+        "vreplicaset-leader-election" => {
+            info!("Running vreplicaset end-to-end test with two replicas of the controller");
+            return vreplicaset_leader_election_e2e_test().await;
+        }
+        "vreplicaset-crash" => {
+            info!("Running vreplicaset crash enumeration test");
+            return vreplicaset_crash_enumeration_test().await;
+        }
         _ => {
             error!("Wrong command. Please specify the correct e2e test workload.");
             Ok(())
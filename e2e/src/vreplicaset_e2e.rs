@@ -1,5 +1,6 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
+use k8s_openapi::api::coordination::v1::Lease;
 use k8s_openapi::api::core::v1::{Pod, Service, ServiceAccount};
 use k8s_openapi::api::rbac::v1::RoleBinding;
 use k8s_openapi::api::{apps::v1::DaemonSet, rbac::v1::Role};
@@ -9,7 +10,7 @@ use kube::{
         Api, AttachParams, AttachedProcess, DeleteParams, ListParams, Patch, PatchParams,
         PostParams, ResourceExt,
     },
-    core::crd::CustomResourceExt,
+    core::{crd::CustomResourceExt, DynamicObject, GroupVersionKind},
     discovery::{ApiCapabilities, ApiResource, Discovery, Scope},
     Client, CustomResource,
 };
@@ -23,6 +24,7 @@ use tokio::time::sleep;
 use tracing::*;
 
 use crate::common::*;
+use crate::crash_enumeration::*;
 
 pub fn v_replica_set() -> String {
     "
@@ -266,3 +268,204 @@ pub async fn vreplicaset_e2e_test() -> Result<(), Error> {
     info!("E2e test passed.");
     Ok(())
 }
+
+// lease_holder returns the holder of the lease that the replicas of the controller compete for
+// (see deploy_leader_election.yaml), if the lease exists.
+async fn lease_holder(client: Client) -> Result<Option<String>, Error> {
+    let lease_api: Api<Lease> = Api::namespaced(client, "vreplicaset");
+    let lease = lease_api.get_opt("vreplicasets.anvil.dev").await?;
+    Ok(lease
+        .and_then(|lease| lease.spec)
+        .and_then(|spec| spec.holder_identity))
+}
+
+// wait_for_leader waits until one of the running controller pods other than old_leader holds the lease.
+async fn wait_for_leader(client: Client, old_leader: Option<String>) -> Result<String, Error> {
+    let timeout = Duration::from_secs(120);
+    let start = Instant::now();
+    let controller_pod_api: Api<Pod> = Api::namespaced(client.clone(), "vreplicaset");
+    loop {
+        sleep(Duration::from_secs(5)).await;
+        if start.elapsed() > timeout {
+            error!("Time out on waiting for a new leader");
+            return Err(Error::Timeout);
+        }
+        let controller_pods = controller_pod_api
+            .list(&ListParams::default().labels("app.kubernetes.io/name=vreplicaset-controller"))
+            .await?;
+        let running = controller_pods
+            .items
+            .iter()
+            .filter(|pod| {
+                pod.metadata.deletion_timestamp.is_none()
+                    && pod.status.as_ref().and_then(|status| status.phase.clone())
+                        == Some("Running".to_string())
+            })
+            .map(|pod| pod.name_any())
+            .collect::<Vec<_>>();
+        match lease_holder(client.clone()).await? {
+            Some(holder) if Some(&holder) != old_leader.as_ref() && running.contains(&holder) => {
+                info!("{} holds the lease.", holder);
+                return Ok(holder);
+            }
+            holder => info!(
+                "The lease is held by {:?}; waiting for a new leader.",
+                holder
+            ),
+        }
+    }
+}
+
+// wait_for_current_state_matches waits until the controller reports that the pods match the VReplicaSet,
+// i.e., current_state_matches of the liveness theorem holds (see controller_state_matches).
+async fn wait_for_current_state_matches(client: Client, vrs_name: &str) -> Result<(), Error> {
+    let timeout = Duration::from_secs(360);
+    let start = Instant::now();
+    loop {
+        sleep(Duration::from_secs(5)).await;
+        if start.elapsed() > timeout {
+            error!("Time out on waiting for the current state to match");
+            return Err(Error::Timeout);
+        }
+        if controller_state_matches(
+            client.clone(),
+            "vreplicaset",
+            "vreplicaset-controller",
+            "default",
+            vrs_name,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        info!("Current state does not match {} yet.", vrs_name);
+    }
+}
+
+// vreplicaset_leader_election_e2e_test runs against two replicas of the controller (see deploy_leader_election.yaml).
+// It kills the leader in the middle of scaling, and checks that the other replica takes over the lease
+// and brings the pods to the desired state.
+pub async fn vreplicaset_leader_election_e2e_test() -> Result<(), Error> {
+    let client = Client::try_default().await?;
+    let discovery = Discovery::new(client.clone()).run().await?;
+    let leader = wait_for_leader(client.clone(), None).await?;
+
+    let vrs_name = apply(v_replica_set(), client.clone(), &discovery).await?;
+    wait_for_current_state_matches(client.clone(), &vrs_name).await?;
+
+    // Kill the leader without a grace period, as if it crashes, and scale the VReplicaSet
+    let controller_pod_api: Api<Pod> = Api::namespaced(client.clone(), "vreplicaset");
+    controller_pod_api
+        .delete(&leader, &DeleteParams::default().grace_period(0))
+        .await?;
+    v_replica_set_api(client.clone())
+        .patch(
+            &vrs_name,
+            &PatchParams::default(),
+            &Patch::Merge(json!({"spec": {"replicas": 5}})),
+        )
+        .await?;
+
+    wait_for_leader(client.clone(), Some(leader)).await?;
+    wait_for_current_state_matches(client.clone(), &vrs_name).await?;
+
+    info!("Leader election e2e test passed.");
+    Ok(())
+}
+
+fn v_replica_set_api(client: Client) -> Api<DynamicObject> {
+    let gvk = GroupVersionKind::gvk("anvil.dev", "v1", "VReplicaSet");
+    Api::default_namespaced_with(client, &ApiResource::from_gvk(&gvk))
+}
+
+// VReplicaSetCrashWorkload creates the frontend VReplicaSet, scales it up and then scales it down.
+pub struct VReplicaSetCrashWorkload {}
+
+impl VReplicaSetCrashWorkload {
+    fn scale(&self, replicas: i32) {
+        let patch = format!(
+            "[{{\"op\": \"replace\", \"path\": \"/spec/replicas\", \"value\": {}}}]",
+            replicas
+        );
+        run_command(
+            "kubectl",
+            vec![
+                "patch",
+                "vrs",
+                "frontend",
+                "--type=json",
+                "-p",
+                patch.as_str(),
+            ],
+            "failed to scale VReplicaSet",
+        );
+    }
+}
+
+impl CrashWorkload for VReplicaSetCrashWorkload {
+    fn cr_key(&self) -> String {
+        "VReplicaSet/default/frontend".to_string()
+    }
+
+    fn steps(&self) -> usize {
+        3
+    }
+
+    async fn run_step(&self, client: Client, step: usize) -> Result<(), Error> {
+        match step {
+            0 => {
+                let discovery = Discovery::new(client.clone()).run().await?;
+                apply(v_replica_set(), client, &discovery).await?;
+            }
+            1 => self.scale(5),
+            _ => self.scale(2),
+        }
+        Ok(())
+    }
+
+    async fn current_state_matches(&self, client: Client) -> Result<bool, Error> {
+        controller_state_matches(
+            client,
+            "vreplicaset",
+            "vreplicaset-controller",
+            "default",
+            "frontend",
+        )
+        .await
+    }
+
+    async fn clean_up(&self, client: Client) -> Result<(), Error> {
+        match v_replica_set_api(client)
+            .delete("frontend", &DeleteParams::default())
+            .await
+        {
+            Err(kube::Error::Api(err)) if err.code == 404 => Ok(()),
+            Err(err) => Err(err.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    // The pods are deleted by the garbage collector after the VReplicaSet is deleted
+    async fn is_cleaned_up(&self, client: Client) -> Result<bool, Error> {
+        if v_replica_set_api(client.clone())
+            .get_opt("frontend")
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+        let pod_api: Api<Pod> = Api::default_namespaced(client);
+        let pods = pod_api.list(&ListParams::default()).await?;
+        Ok(!pods.items.iter().any(|pod| {
+            pod.metadata
+                .owner_references
+                .iter()
+                .flatten()
+                .any(|owner_ref| owner_ref.kind == "VReplicaSet" && owner_ref.name == "frontend")
+        }))
+    }
+}
+
+pub async fn vreplicaset_crash_enumeration_test() -> Result<(), Error> {
+    crash_enumeration_test(VReplicaSetCrashWorkload {}).await
+}
@@ -103,11 +103,12 @@ pub fn zookeeper_cluster_ephemeral() -> String {
     .to_string()
 }
 
+// desired_state_test waits until the controller reports that the current state matches the ZookeeperCluster,
+// i.e., current_state_matches of the liveness theorem holds (see controller_state_matches),
+// and then until the zookeeper servers are ready, which the other tests need to talk to them.
 pub async fn desired_state_test(client: Client, zk_name: String) -> Result<(), Error> {
     let timeout = Duration::from_secs(600);
     let start = Instant::now();
-    let svc_api: Api<Service> = Api::default_namespaced(client.clone());
-    let cm_api: Api<ConfigMap> = Api::default_namespaced(client.clone());
     let sts_api: Api<StatefulSet> = Api::default_namespaced(client.clone());
     loop {
         sleep(Duration::from_secs(5)).await;
@@ -116,83 +117,30 @@ pub async fn desired_state_test(client: Client, zk_name: String) -> Result<(), E
             return Err(Error::Timeout);
         }
 
-        let svc = svc_api.get(&(zk_name.clone() + "-headless")).await;
-        match svc {
-            Err(e) => {
-                info!("Get headless service failed with error {}.", e);
-                continue;
-            }
-            _ => {}
-        };
-
-        let svc = svc_api.get(&(zk_name.clone() + "-client")).await;
-        match svc {
-            Err(e) => {
-                info!("Get client service failed with error {}.", e);
-                continue;
-            }
-            _ => {}
-        };
-
-        let svc = svc_api.get(&(zk_name.clone() + "-admin-server")).await;
-        match svc {
-            Err(e) => {
-                info!("Get admin server service failed with error {}.", e);
-                continue;
-            }
-            _ => {}
-        };
-
-        let cm = cm_api.get(&(zk_name.clone() + "-configmap")).await;
-        match cm {
-            Err(e) => {
-                info!("Get config map failed with error {}.", e);
-                continue;
-            }
-            _ => {}
-        };
+        if !controller_state_matches(
+            client.clone(),
+            "zookeeper",
+            "zookeeper-controller",
+            "default",
+            &zk_name,
+        )
+        .await?
+        {
+            info!("Current state does not match {} yet.", zk_name);
+            continue;
+        }
 
-        // Check stateful set
-        let sts = sts_api.get(&zk_name).await;
-        match sts {
-            Err(e) => {
-                info!("Get stateful set failed with error {}.", e);
-                continue;
-            }
-            Ok(sts) => {
-                if sts.spec.unwrap().replicas != Some(3) {
-                    error!("Stateful set spec is not consistent with zookeeper cluster spec. E2e test failed.");
-                    return Err(Error::ZookeeperStsFailed);
-                }
-                info!("Stateful set is found as expected.");
-                if sts.status.as_ref().unwrap().ready_replicas.is_none() {
-                    info!("No stateful set pod is ready.");
-                    continue;
-                } else if *sts
-                    .status
-                    .as_ref()
-                    .unwrap()
-                    .ready_replicas
-                    .as_ref()
-                    .unwrap()
-                    == 3
-                {
-                    info!("All stateful set pods are ready.");
-                    break;
-                } else {
-                    info!(
-                        "Only {} pods are ready now.",
-                        sts.status
-                            .as_ref()
-                            .unwrap()
-                            .ready_replicas
-                            .as_ref()
-                            .unwrap()
-                    );
-                    continue;
-                }
-            }
-        };
+        let sts = sts_api.get(&zk_name).await?;
+        let ready_replicas = sts
+            .status
+            .as_ref()
+            .and_then(|status| status.ready_replicas)
+            .unwrap_or(0);
+        if ready_replicas == 3 {
+            info!("All stateful set pods are ready.");
+            break;
+        }
+        info!("Only {} pods are ready now.", ready_replicas);
     }
     info!("Desired state test passed.");
     Ok(())
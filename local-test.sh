@@ -3,10 +3,12 @@
 ## Test the controller locally in a kind cluster.
 ##
 ## Requires kind to be installed and the prerequisites of deploy.sh.
+## The optional second argument picks the deployment (deploy/<app>/deploy_<deployment>.yaml), local by default.
 
 set -xeu
 
 app=$1
+deployment=${2:-local}
 
 # Copy the Dockerfile and build the docker image of the controller
 cp docker/controller/Dockerfile .
@@ -16,4 +18,4 @@ rm Dockerfile
 kind create cluster --config deploy/kind.yaml
 kind load docker-image local/$app-controller:v0.1.0
 # Deploy the controller as a pod to the kind cluster, using the image just loaded
-./deploy.sh $app local
+./deploy.sh $app $deployment
@@ -1,86 +1,1437 @@
-use crate::executable_model::prelude::*;
+use crate::executable_model::{apply, prelude::*};
 use crate::kubernetes_api_objects::{
     error::*,
-    exec::{api_resource::*, prelude::*},
-    spec::prelude::Kind,
+    exec::{api_resource::*, label_selector::LabelSelector, prelude::*},
+    spec::prelude::PatchType,
 };
+use crate::shim_layer::controller_runtime::kube_error_to_ghost;
+use crate::vstd_ext::string_map::StringMap;
 use deps_hack::kube::{
-    api::{Api, DeleteParams, ListParams, ObjectMeta, PostParams},
+    api::{Api, DeleteParams, ObjectMeta, Patch, PatchParams, PostParams},
     Client,
 };
 use deps_hack::proptest::prelude::*;
+use deps_hack::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use deps_hack::serde_json::Value;
 use deps_hack::tokio::runtime::Runtime;
 use rand::Rng;
+use std::io::Write;
 use std::process::Command;
 use vstd::prelude::*;
 use vstd::string::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
 enum GeneratedRequest {
     Get {
-        kind: Kind,
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
         name: std::string::String,
     },
+    List {
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
+        // The match_labels of the label selector, if the request has one
+        #[serde(default)]
+        label_selector: Option<std::collections::BTreeMap<std::string::String, std::string::String>>,
+        // The match_expressions of the label selector, if the request has one
+        #[serde(default)]
+        match_expressions: Option<Vec<GeneratedRequirement>>,
+        // The name selected by the field selector, if the request has one
+        #[serde(default)]
+        field_selector: Option<std::string::String>,
+    },
     Create {
-        kind: Kind,
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
+        name: std::string::String,
+    },
+    // CreateBoundPod is not generated, see test_model_with_repeated_pod_deletion
+    CreateBoundPod {
+        name: std::string::String,
+    },
+    Patch {
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
+        name: std::string::String,
+        change: PatchChange,
+        #[serde(with = "patch_type_format")]
+        patch_type: PatchType,
+    },
+    Update {
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
+        name: std::string::String,
+        change: UpdateChange,
+        resource_version: ResourceVersionChoice,
+    },
+    UpdateStatus {
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
         name: std::string::String,
+        message: std::string::String,
+        resource_version: ResourceVersionChoice,
+    },
+    Delete {
+        #[serde(with = "kube_kind_format")]
+        kind: KubeKind,
+        name: std::string::String,
+        // The grace period is set for pods (see delete_grace_period_seconds)
+        #[serde(default)]
+        grace_period_seconds: Option<u32>,
+    },
+    // Apply is only generated for ConfigMaps, whose data is a map that server-side apply merges key by key
+    Apply {
+        name: std::string::String,
+        field_manager: std::string::String,
+        labels: std::collections::BTreeMap<std::string::String, std::string::String>,
+        // Finalizers are merged as a set, so the managers can share or keep each other's finalizers
+        #[serde(default)]
+        finalizers: std::collections::BTreeSet<std::string::String>,
+        data: std::collections::BTreeMap<std::string::String, std::string::String>,
+        force: bool,
+    },
+}
+
+// UpdateChange is what an update request changes in the object that the client gets from the API server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum UpdateChange {
+    AddLabel {
+        key: std::string::String,
+        value: std::string::String,
+    },
+    AddFinalizer {
+        finalizer: std::string::String,
     },
+    RemoveFinalizer {
+        finalizer: std::string::String,
+    },
+}
+
+// PatchChange is what a patch request carries (see patch_object)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum PatchChange {
+    Label {
+        key: std::string::String,
+        value: std::string::String,
+    },
+    Annotation {
+        key: std::string::String,
+        value: std::string::String,
+    },
+    Finalizer {
+        finalizer: std::string::String,
+    },
+    // A list (or map) in the object body with at most one item, see body_patch
+    Body {
+        item: Option<std::string::String>,
+    },
+}
+
+// ResourceVersionChoice is the resource version that an update request carries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum ResourceVersionChoice {
+    // The resource version of the object that the client gets
+    Current,
+    // An older resource version, as if the object has been changed after the client gets it
+    Stale,
+    // No resource version, which makes the update unconditional
+    Absent,
 }
 
-impl Kind {
+impl KubeKind {
     fn to_api_resource(&self) -> ApiResource {
         match self {
-            Kind::ConfigMapKind => {
-                ApiResource::from_kube(deps_hack::kube::api::ApiResource::erase::<
-                    deps_hack::k8s_openapi::api::core::v1::ConfigMap,
-                >(&()))
-            }
-            Kind::SecretKind => ApiResource::from_kube(deps_hack::kube::api::ApiResource::erase::<
-                deps_hack::k8s_openapi::api::core::v1::Secret,
-            >(&())),
+            KubeKind::ConfigMapKind => ConfigMap::api_resource(),
+            KubeKind::RoleKind => Role::api_resource(),
+            KubeKind::RoleBindingKind => RoleBinding::api_resource(),
+            KubeKind::SecretKind => Secret::api_resource(),
+            KubeKind::ServiceKind => Service::api_resource(),
+            KubeKind::ServiceAccountKind => ServiceAccount::api_resource(),
+            KubeKind::PodKind => Pod::api_resource(),
+            KubeKind::CustomResourceKind(kind) if kind == "SimpleCR" => simple_cr_api_resource(),
             _ => panic!(),
         }
     }
 
+    // The default object of each kind is the smallest object that passes the validation of Kubernetes.
     fn to_default_dynamic_object(&self) -> DynamicObject {
         match self {
-            Kind::ConfigMapKind => ConfigMap::default().marshal(),
-            Kind::SecretKind => Secret::default().marshal(),
+            KubeKind::ConfigMapKind => ConfigMap::default().marshal(),
+            KubeKind::RoleKind => Role::default().marshal(),
+            KubeKind::RoleBindingKind => {
+                let mut role_binding = RoleBinding::default();
+                role_binding.set_role_ref({
+                    let mut role_ref = RoleRef::default();
+                    role_ref.set_api_group("rbac.authorization.k8s.io".to_string());
+                    role_ref.set_kind("Role".to_string());
+                    role_ref.set_name("conformance-test".to_string());
+                    role_ref
+                });
+                role_binding.marshal()
+            }
+            KubeKind::SecretKind => Secret::default().marshal(),
+            KubeKind::ServiceKind => {
+                let mut service = Service::default();
+                service.set_spec({
+                    let mut service_spec = ServiceSpec::default();
+                    service_spec.set_ports(vec![ServicePort::new_with("http".to_string(), 80)]);
+                    service_spec
+                });
+                service.marshal()
+            }
+            KubeKind::ServiceAccountKind => ServiceAccount::default().marshal(),
+            // The pod names a scheduler that does not exist, so it is never scheduled (and never run by a kubelet),
+            // and it does not mount the service account token, which Kubernetes would otherwise add as a volume.
+            KubeKind::PodKind => DynamicObject::from_kube(deps_hack::serde_json::from_value(deps_hack::serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "Pod",
+                "metadata": {},
+                "spec": {
+                    "containers": [{"name": "app", "image": "nginx"}],
+                    "schedulerName": "conformance-test",
+                    "automountServiceAccountToken": false,
+                },
+            })).unwrap()),
+            KubeKind::CustomResourceKind(kind) if kind == "SimpleCR" => DynamicObject::from_kube(
+                deps_hack::kube::api::DynamicObject::new("", simple_cr_api_resource().as_kube_ref())
+                    .data(deps_hack::serde_json::json!({"spec": {}})),
+            ),
             _ => panic!(),
         }
     }
+
+    // Kubernetes deletes a pod that is not scheduled to any node right away, regardless of the grace period,
+    // while the model follows the grace period of the request, so the delete requests of pods set it to zero.
+    fn delete_grace_period_seconds(&self) -> Option<u32> {
+        match self {
+            KubeKind::PodKind => Some(0),
+            _ => None,
+        }
+    }
+
+    // For these kinds, Kubernetes creates the object if an update request targets an object that does not exist,
+    // while the model always rejects such requests with ObjectNotFound.
+    fn allows_create_on_update(&self) -> bool {
+        match self {
+            KubeKind::RoleKind | KubeKind::RoleBindingKind | KubeKind::ServiceKind => true,
+            _ => false,
+        }
+    }
+
+    // Kubernetes creates the kube-root-ca.crt ConfigMap and the default ServiceAccount in every namespace,
+    // so they are not in the model.
+    fn is_created_by_kubernetes(&self, name: &str) -> bool {
+        match self {
+            KubeKind::ConfigMapKind => name == "kube-root-ca.crt",
+            KubeKind::ServiceAccountKind => name == "default",
+            _ => false,
+        }
+    }
+}
+
+// The bound pod is the default pod bound to a node that does not exist.
+// Kubernetes deletes a bound pod gracefully, and since no kubelet runs the pod,
+// it stays terminating until the grace period is shortened to zero.
+fn bound_pod() -> DynamicObject {
+    let mut obj = KubeKind::PodKind.to_default_dynamic_object().into_kube();
+    obj.data["spec"]["nodeName"] = Value::String("conformance-test".to_string());
+    DynamicObject::from_kube(obj)
+}
+
+// SimpleCR is the custom resource that the model is instantiated with (see deploy/conformance/simplecr_crd.yaml).
+fn simple_cr_api_resource() -> ApiResource {
+    ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+        group: "anvil.dev".to_string(),
+        version: "v1".to_string(),
+        api_version: "anvil.dev/v1".to_string(),
+        kind: "SimpleCR".to_string(),
+        plural: "simplecrs".to_string(),
+    })
+}
+
+// KubeKind and PatchType are defined in verus! and do not implement the serde traits,
+// so they are written to traces by their names.
+mod kube_kind_format {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(kind: &KubeKind, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&kind.to_api_resource().as_kube_ref().kind)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KubeKind, D::Error> {
+        let kind = std::string::String::deserialize(deserializer)?;
+        match kind.as_str() {
+            "ConfigMap" => Ok(KubeKind::ConfigMapKind),
+            "Role" => Ok(KubeKind::RoleKind),
+            "RoleBinding" => Ok(KubeKind::RoleBindingKind),
+            "Secret" => Ok(KubeKind::SecretKind),
+            "Service" => Ok(KubeKind::ServiceKind),
+            "ServiceAccount" => Ok(KubeKind::ServiceAccountKind),
+            "Pod" => Ok(KubeKind::PodKind),
+            "SimpleCR" => Ok(KubeKind::CustomResourceKind("SimpleCR".to_string())),
+            _ => Err(de::Error::custom(format!("unsupported kind {}", kind))),
+        }
+    }
+}
+
+mod patch_type_format {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        patch_type: &PatchType,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match patch_type {
+            PatchType::MergePatch => serializer.serialize_str("MergePatch"),
+            PatchType::StrategicMergePatch => serializer.serialize_str("StrategicMergePatch"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PatchType, D::Error> {
+        let patch_type = std::string::String::deserialize(deserializer)?;
+        match patch_type.as_str() {
+            "MergePatch" => Ok(PatchType::MergePatch),
+            "StrategicMergePatch" => Ok(PatchType::StrategicMergePatch),
+            _ => Err(de::Error::custom(format!(
+                "unsupported patch type {}",
+                patch_type
+            ))),
+        }
+    }
+}
+
+// The pods are never scheduled (see to_default_dynamic_object), so neither the scheduler nor a kubelet touches them.
+// The custom resource is SimpleCR, whose CRD is installed in the kind cluster before the tests run.
+// We do not generate requests for the following kinds because the built-in controllers of the kind cluster
+// write these objects concurrently with the test, and the model does not, so the results would race:
+// (1) StatefulSet: the controller replaces the whole status (dropping the conditions written by update_status)
+//     on every change of the object, even with zero replicas.
+// (2) DaemonSet: the controller writes the status after each change of the spec, which races with the updates
+//     that carry the current resource version.
+// (3) PersistentVolumeClaim: the admission plugin adds the kubernetes.io/pvc-protection finalizer,
+//     which the pvc-protection controller removes asynchronously after deletion.
+fn kind_strategy() -> BoxedStrategy<KubeKind> {
+    prop_oneof![
+        Just(KubeKind::ConfigMapKind),
+        Just(KubeKind::RoleKind),
+        Just(KubeKind::RoleBindingKind),
+        Just(KubeKind::SecretKind),
+        Just(KubeKind::ServiceKind),
+        Just(KubeKind::ServiceAccountKind),
+        Just(KubeKind::PodKind),
+        Just(KubeKind::CustomResourceKind("SimpleCR".to_string())),
+    ]
+    .boxed()
+}
+
+// The names are drawn from a small set so that the generated requests often hit the same object.
+fn name_strategy() -> BoxedStrategy<std::string::String> {
+    "obj-[a-c]".boxed()
 }
 
-fn kind_strategy() -> BoxedStrategy<Kind> {
-    prop_oneof![Just(Kind::ConfigMapKind), Just(Kind::SecretKind),].boxed()
+// GeneratedRequirement is a requirement in the match_expressions of a label selector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct GeneratedRequirement {
+    key: std::string::String,
+    operator: std::string::String,
+    values: Option<Vec<std::string::String>>,
 }
 
 prop_compose! {
   fn generated_request_get_case()(
       kind in kind_strategy(),
-      name in "[a-z0-9]([-a-z0-9]*[a-z0-9])?",
+      name in name_strategy(),
   ) -> GeneratedRequest {
       GeneratedRequest::Get { kind, name }
   }
 }
 
+// The labels are also drawn from small sets so that the label selectors of the generated list requests
+// often select some (but not all) of the objects labeled by the generated patch and update requests.
+fn label_key_strategy() -> BoxedStrategy<std::string::String> {
+    "label-[ab]".boxed()
+}
+
+fn label_value_strategy() -> BoxedStrategy<std::string::String> {
+    "value-[xy]".boxed()
+}
+
+fn label_selector_strategy() -> BoxedStrategy<Option<std::collections::BTreeMap<std::string::String, std::string::String>>> {
+    prop::option::of(prop::collection::btree_map(label_key_strategy(), label_value_strategy(), 0..=2)).boxed()
+}
+
+// The operators include an unknown one, and the values can be missing for any operator,
+// so that some generated requirements are invalid and the list request is rejected.
+prop_compose! {
+  fn generated_requirement()(
+      key in label_key_strategy(),
+      operator in prop_oneof!["In", "NotIn", "Exists", "DoesNotExist", "Equals"],
+      values in prop::option::of(prop::collection::vec(label_value_strategy(), 0..=2)),
+  ) -> GeneratedRequirement {
+      GeneratedRequirement { key, operator: operator.to_string(), values }
+  }
+}
+
+fn match_expressions_strategy() -> BoxedStrategy<Option<Vec<GeneratedRequirement>>> {
+    prop::option::of(prop::collection::vec(generated_requirement(), 0..=2)).boxed()
+}
+
+prop_compose! {
+  fn generated_request_list_case()(
+      kind in kind_strategy(),
+      label_selector in label_selector_strategy(),
+      match_expressions in match_expressions_strategy(),
+      field_selector in prop::option::of(name_strategy()),
+  ) -> GeneratedRequest {
+      GeneratedRequest::List { kind, label_selector, match_expressions, field_selector }
+  }
+}
+
 prop_compose! {
   fn generated_request_create_case()(
       kind in kind_strategy(),
-      name in "[a-z0-9]([-a-z0-9]*[a-z0-9])?",
+      name in name_strategy(),
   ) -> GeneratedRequest {
       GeneratedRequest::Create { kind, name }
   }
 }
 
+fn patch_type_strategy() -> BoxedStrategy<PatchType> {
+    prop_oneof![Just(PatchType::MergePatch), Just(PatchType::StrategicMergePatch),].boxed()
+}
+
+fn patch_change_strategy() -> BoxedStrategy<PatchChange> {
+    prop_oneof![
+        (label_key_strategy(), label_value_strategy())
+            .prop_map(|(key, value)| PatchChange::Label { key, value }),
+        (label_key_strategy(), label_value_strategy())
+            .prop_map(|(key, value)| PatchChange::Annotation { key, value }),
+        finalizer_strategy().prop_map(|finalizer| PatchChange::Finalizer { finalizer }),
+        prop::option::of("[ab]").prop_map(|item| PatchChange::Body { item }),
+    ]
+    .boxed()
+}
+
+prop_compose! {
+  fn generated_request_patch_case()(
+      kind in kind_strategy(),
+      name in name_strategy(),
+      change in patch_change_strategy(),
+      patch_type in patch_type_strategy(),
+  ) -> GeneratedRequest {
+      GeneratedRequest::Patch { kind, name, change, patch_type }
+  }
+}
+
+// Two finalizers are enough to cover adding a finalizer to an object that already has one
+// and removing the last finalizer of an object that is being deleted.
+fn finalizer_strategy() -> BoxedStrategy<std::string::String> {
+    "conformance\\.anvil\\.dev/[ab]".boxed()
+}
+
+fn update_change_strategy() -> BoxedStrategy<UpdateChange> {
+    prop_oneof![
+        (label_key_strategy(), label_value_strategy())
+            .prop_map(|(key, value)| UpdateChange::AddLabel { key, value }),
+        finalizer_strategy().prop_map(|finalizer| UpdateChange::AddFinalizer { finalizer }),
+        finalizer_strategy().prop_map(|finalizer| UpdateChange::RemoveFinalizer { finalizer }),
+    ]
+    .boxed()
+}
+
+fn resource_version_strategy() -> BoxedStrategy<ResourceVersionChoice> {
+    prop_oneof![
+        Just(ResourceVersionChoice::Current),
+        Just(ResourceVersionChoice::Stale),
+        Just(ResourceVersionChoice::Absent),
+    ]
+    .boxed()
+}
+
+prop_compose! {
+  fn generated_request_update_case()(
+      kind in kind_strategy(),
+      name in name_strategy(),
+      change in update_change_strategy(),
+      resource_version in resource_version_strategy(),
+  ) -> GeneratedRequest {
+      GeneratedRequest::Update { kind, name, change, resource_version }
+  }
+}
+
+prop_compose! {
+  fn generated_request_update_status_case()(
+      kind in kind_strategy(),
+      name in name_strategy(),
+      message in "[a-z0-9]{1,20}",
+      resource_version in resource_version_strategy(),
+  ) -> GeneratedRequest {
+      GeneratedRequest::UpdateStatus { kind, name, message, resource_version }
+  }
+}
+
+prop_compose! {
+  fn generated_request_delete_case()(
+      kind in kind_strategy(),
+      name in name_strategy(),
+  ) -> GeneratedRequest {
+      let grace_period_seconds = kind.delete_grace_period_seconds();
+      GeneratedRequest::Delete { kind, name, grace_period_seconds }
+  }
+}
+
+// The objects written by apply have their own names because the model only tracks the fields owned by apply,
+// while Kubernetes also records the fields set by the other requests under their own managers,
+// which would make apply conflict with them in Kubernetes but not in the model.
+fn apply_name_strategy() -> BoxedStrategy<std::string::String> {
+    "applied-[ab]".boxed()
+}
+
+// Two managers with overlapping labels and data keys cover shared ownership, conflicts and forced takeovers.
+fn field_manager_strategy() -> BoxedStrategy<std::string::String> {
+    "manager-[ab]".boxed()
+}
+
+fn data_key_strategy() -> BoxedStrategy<std::string::String> {
+    "key-[ab]".boxed()
+}
+
+prop_compose! {
+  fn generated_request_apply_case()(
+      name in apply_name_strategy(),
+      field_manager in field_manager_strategy(),
+      labels in prop::collection::btree_map(label_key_strategy(), label_value_strategy(), 0..=2),
+      finalizers in prop::collection::btree_set(finalizer_strategy(), 0..=2),
+      data in prop::collection::btree_map(data_key_strategy(), label_value_strategy(), 1..=2),
+      force in any::<bool>(),
+  ) -> GeneratedRequest {
+      GeneratedRequest::Apply { name, field_manager, labels, finalizers, data, force }
+  }
+}
+
 fn generated_request_strategy() -> BoxedStrategy<GeneratedRequest> {
     prop_oneof![
         generated_request_get_case(),
+        generated_request_list_case(),
         generated_request_create_case(),
+        generated_request_patch_case(),
+        generated_request_update_case(),
+        generated_request_update_status_case(),
+        generated_request_delete_case(),
+        generated_request_apply_case(),
     ]
     .boxed()
 }
 
+// The client (in both the model and Kubernetes) first gets the object, then changes it and sends it back.
+// If the object does not exist, the client sends the default object with the given name.
+fn object_to_update(
+    kind: &KubeKind,
+    name: &std::string::String,
+    current_obj: Option<deps_hack::kube::api::DynamicObject>,
+    resource_version: &ResourceVersionChoice,
+) -> deps_hack::kube::api::DynamicObject {
+    let mut obj = match current_obj {
+        Some(obj) => obj,
+        None => {
+            let mut obj = kind.to_default_dynamic_object();
+            obj.set_name(name.clone());
+            obj.into_kube()
+        }
+    };
+    match resource_version {
+        ResourceVersionChoice::Current => {}
+        ResourceVersionChoice::Stale => {
+            // Both the model and Kubernetes use increasing integers as resource versions
+            let stale_rv = match &obj.metadata.resource_version {
+                Some(rv) => rv.parse::<i64>().ok().map(|rv| (rv - 1).to_string()),
+                None => None,
+            };
+            if stale_rv.is_some() {
+                obj.metadata.resource_version = stale_rv;
+            }
+        }
+        ResourceVersionChoice::Absent => {
+            obj.metadata.resource_version = None;
+        }
+    }
+    obj
+}
+
+fn apply_update_change(obj: &mut deps_hack::kube::api::DynamicObject, change: &UpdateChange) {
+    match change {
+        UpdateChange::AddLabel { key, value } => {
+            obj.metadata
+                .labels
+                .get_or_insert_with(Default::default)
+                .insert(key.clone(), value.clone());
+        }
+        UpdateChange::AddFinalizer { finalizer } => {
+            let finalizers = obj.metadata.finalizers.get_or_insert_with(Vec::new);
+            if !finalizers.contains(finalizer) {
+                finalizers.push(finalizer.clone());
+            }
+        }
+        UpdateChange::RemoveFinalizer { finalizer } => {
+            if let Some(finalizers) = obj.metadata.finalizers.as_mut() {
+                finalizers.retain(|f| f != finalizer);
+            }
+        }
+    }
+}
+
+// The status written by the generated update status requests.
+// The condition carries all the fields required by the validation of Kubernetes.
+fn status_to_write(message: &std::string::String) -> Value {
+    deps_hack::serde_json::json!({
+        "conditions": [{
+            "type": "ConformanceTest",
+            "status": "True",
+            "reason": "ConformanceTest",
+            "message": message,
+            "lastTransitionTime": "2024-01-01T00:00:00Z",
+        }]
+    })
+}
+
+// json_subsumes checks whether every field set in the model's json is set to the same value in the json from Kubernetes.
+// Kubernetes fills in default values for some fields that the model leaves unset (e.g., the type of a Secret
+// or the cluster ip of a Service), so we cannot require the two to be equal.
+fn json_subsumes(kind_json: &Value, model_json: &Value) -> bool {
+    match (kind_json, model_json) {
+        (_, Value::Null) => true,
+        (Value::Object(kind_fields), Value::Object(model_fields)) => {
+            model_fields
+                .iter()
+                .all(|(key, model_value)| match kind_fields.get(key) {
+                    Some(kind_value) => json_subsumes(kind_value, model_value),
+                    None => model_value.is_null(),
+                })
+        }
+        (Value::Array(kind_items), Value::Array(model_items)) => {
+            kind_items.len() == model_items.len()
+                && kind_items
+                    .iter()
+                    .zip(model_items.iter())
+                    .all(|(kind_item, model_item)| json_subsumes(kind_item, model_item))
+        }
+        _ => kind_json == model_json,
+    }
+}
+
+// object_diff compares the object returned (or stored) by the model and by Kubernetes.
+// It skips the metadata fields whose values are decided differently by design,
+// i.e., uid, resource_version, creation_timestamp, managed_fields and the exact deletion_timestamp.
+fn object_diff(
+    model_obj: &deps_hack::kube::api::DynamicObject,
+    kind_obj: &deps_hack::kube::api::DynamicObject,
+) -> Option<std::string::String> {
+    let model_meta = &model_obj.metadata;
+    let kind_meta = &kind_obj.metadata;
+    if model_meta.name != kind_meta.name {
+        Some(format!(
+            "name: model {:?}, kubernetes {:?}",
+            model_meta.name, kind_meta.name
+        ))
+    } else if model_meta.namespace != kind_meta.namespace {
+        Some(format!(
+            "namespace: model {:?}, kubernetes {:?}",
+            model_meta.namespace, kind_meta.namespace
+        ))
+    } else if model_meta.labels.clone().unwrap_or_default()
+        != kind_meta.labels.clone().unwrap_or_default()
+    {
+        Some(format!(
+            "labels: model {:?}, kubernetes {:?}",
+            model_meta.labels, kind_meta.labels
+        ))
+    } else if model_meta.annotations.clone().unwrap_or_default()
+        != kind_meta.annotations.clone().unwrap_or_default()
+    {
+        Some(format!(
+            "annotations: model {:?}, kubernetes {:?}",
+            model_meta.annotations, kind_meta.annotations
+        ))
+    } else if model_meta.finalizers.clone().unwrap_or_default()
+        != kind_meta.finalizers.clone().unwrap_or_default()
+    {
+        Some(format!(
+            "finalizers: model {:?}, kubernetes {:?}",
+            model_meta.finalizers, kind_meta.finalizers
+        ))
+    } else if model_meta.owner_references.clone().unwrap_or_default()
+        != kind_meta.owner_references.clone().unwrap_or_default()
+    {
+        Some(format!(
+            "owner_references: model {:?}, kubernetes {:?}",
+            model_meta.owner_references, kind_meta.owner_references
+        ))
+    } else if model_meta.deletion_timestamp.is_some() != kind_meta.deletion_timestamp.is_some() {
+        Some(format!(
+            "deletion_timestamp: model {:?}, kubernetes {:?}",
+            model_meta.deletion_timestamp, kind_meta.deletion_timestamp
+        ))
+    } else if model_meta.deletion_grace_period_seconds != kind_meta.deletion_grace_period_seconds {
+        Some(format!(
+            "deletion_grace_period_seconds: model {:?}, kubernetes {:?}",
+            model_meta.deletion_grace_period_seconds, kind_meta.deletion_grace_period_seconds
+        ))
+    } else if !json_subsumes(&kind_obj.data, &model_obj.data) {
+        // The data holds the spec and the status (or the counterparts such as the data of a ConfigMap)
+        Some(format!(
+            "data: model {}, kubernetes {}",
+            model_obj.data, kind_obj.data
+        ))
+    } else {
+        None
+    }
+}
+
+// KindError is an error returned by Kubernetes in the form that can be written to a trace.
+// api_error is the APIError that the error maps to (by kube_error_to_ghost), and message is the original error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct KindError {
+    api_error: std::string::String,
+    message: std::string::String,
+}
+
+impl KindError {
+    fn from_kube_error(err: &deps_hack::kube::Error) -> KindError {
+        KindError {
+            api_error: format!("{:?}", kube_error_to_ghost(err)),
+            message: err.to_string(),
+        }
+    }
+}
+
+type KindResult<T> = Result<T, KindError>;
+
+// KindResponse is what Kubernetes returns for a generated request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum KindResponse {
+    // The request is not sent, see allows_create_on_update and has_invalid_selector
+    Skipped,
+    Object(KindResult<deps_hack::kube::api::DynamicObject>),
+    List(KindResult<Vec<deps_hack::kube::api::DynamicObject>>),
+    Delete(KindResult<()>),
+}
+
+// RecordedStep is one generated request together with the behavior of Kubernetes on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct RecordedStep {
+    request: GeneratedRequest,
+    response: KindResponse,
+    // The object stored in Kubernetes after the request, for the requests that can change it
+    stored: Option<KindResult<deps_hack::kube::api::DynamicObject>>,
+}
+
+// RecordedCase is one test case, i.e., a sequence of steps in a fresh namespace.
+// Each line of a trace file is a RecordedCase in json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct RecordedCase {
+    namespace: std::string::String,
+    steps: Vec<RecordedStep>,
+}
+
+// error_diff compares whether the model and Kubernetes both succeed, or both fail with the same APIError.
+fn error_diff<T, U>(
+    model_res: &Result<T, APIError>,
+    kind_res: &KindResult<U>,
+) -> Option<std::string::String> {
+    match (model_res, kind_res) {
+        (Ok(_), Ok(_)) => None,
+        (Ok(_), Err(kind_err)) => Some(format!(
+            "model succeeds, kubernetes fails with {}",
+            kind_err.message
+        )),
+        (Err(model_err), Ok(_)) => Some(format!(
+            "model fails with {:?}, kubernetes succeeds",
+            model_err
+        )),
+        (Err(model_err), Err(kind_err)) => {
+            // APIError does not implement PartialEq outside verus, so we compare the variants by their names
+            if format!("{:?}", model_err) == kind_err.api_error {
+                None
+            } else {
+                Some(format!(
+                    "model fails with {:?}, kubernetes fails with {} ({})",
+                    model_err, kind_err.api_error, kind_err.message
+                ))
+            }
+        }
+    }
+}
+
+fn response_diff(
+    model_res: &Result<DynamicObject, APIError>,
+    kind_res: &KindResult<deps_hack::kube::api::DynamicObject>,
+) -> Option<std::string::String> {
+    match (model_res, kind_res) {
+        (Ok(model_obj), Ok(kind_obj)) => object_diff(model_obj.as_kube_ref(), kind_obj),
+        _ => error_diff(model_res, kind_res),
+    }
+}
+
+// block_on_cluster runs f with an Api that sends requests of the given kind to the kind cluster.
+fn block_on_cluster<F, Fut, T>(
+    kind: &KubeKind,
+    namespace: &std::string::String,
+    f: F,
+) -> KindResult<T>
+where
+    F: FnOnce(Api<deps_hack::kube::api::DynamicObject>) -> Fut,
+    Fut: std::future::Future<Output = Result<T, deps_hack::kube::Error>>,
+{
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let client = Client::try_default().await.unwrap();
+        let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
+            client,
+            namespace,
+            kind.to_api_resource().as_kube_ref(),
+        );
+        f(api).await
+    })
+    .map_err(|err| KindError::from_kube_error(&err))
+}
+
+fn model_get(
+    kind: &KubeKind,
+    namespace: &std::string::String,
+    name: &std::string::String,
+    api_server_state: &ApiServerState,
+) -> Result<DynamicObject, APIError> {
+    let get_request = KubeGetRequest {
+        api_resource: kind.to_api_resource(),
+        namespace: namespace.clone(),
+        name: name.clone(),
+    };
+    SimpleExecutableApiServerModel::handle_get_request(&get_request, api_server_state).res
+}
+
+fn kind_get(
+    kind: &KubeKind,
+    namespace: &std::string::String,
+    name: &std::string::String,
+) -> KindResult<deps_hack::kube::api::DynamicObject> {
+    let name = name.clone();
+    block_on_cluster(kind, namespace, |api| async move { api.get(&name).await })
+}
+
+// kind_step sends the generated request to Kubernetes and records what Kubernetes returns,
+// and what Kubernetes stores after the request if the request can change the object.
+fn kind_step(request: &GeneratedRequest, namespace: &std::string::String) -> RecordedStep {
+    let (response, stored) = match request.clone() {
+        GeneratedRequest::Get { kind, name } => (
+            KindResponse::Object(kind_get(&kind, namespace, &name)),
+            None,
+        ),
+        GeneratedRequest::List {
+            kind,
+            label_selector,
+            match_expressions,
+            field_selector,
+        } => {
+            let list_req = list_request(
+                &kind,
+                namespace,
+                &label_selector,
+                &match_expressions,
+                &field_selector,
+            );
+            // The shim layer rejects a list request with an invalid label selector without sending it
+            // (see KubeListRequest::has_invalid_selector), so it is not sent here either
+            if list_req.has_invalid_selector() {
+                (KindResponse::Skipped, None)
+            } else {
+                // The selectors are translated by the same list_params used by the shim layer
+                let lp = list_req.list_params();
+                let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                    api.list(&lp).await
+                });
+                (KindResponse::List(kind_res.map(|list| list.items)), None)
+            }
+        }
+        GeneratedRequest::Create { kind, name } => {
+            let obj = {
+                let mut obj = kind.to_default_dynamic_object();
+                obj.set_name(name.clone());
+                obj
+            };
+            let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                api.create(&PostParams::default(), &obj.into_kube()).await
+            });
+            (
+                KindResponse::Object(kind_res),
+                Some(kind_get(&kind, namespace, &name)),
+            )
+        }
+        GeneratedRequest::CreateBoundPod { name } => {
+            let obj = {
+                let mut obj = bound_pod();
+                obj.set_name(name.clone());
+                obj
+            };
+            let kind_res = block_on_cluster(&KubeKind::PodKind, namespace, |api| async move {
+                api.create(&PostParams::default(), &obj.into_kube()).await
+            });
+            (
+                KindResponse::Object(kind_res),
+                Some(kind_get(&KubeKind::PodKind, namespace, &name)),
+            )
+        }
+        GeneratedRequest::Patch {
+            kind,
+            name,
+            change,
+            patch_type,
+        } => {
+            let patch_obj = patch_object(&kind, &change, namespace).into_kube();
+            let patch_name = name.clone();
+            let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                let patch = match patch_type {
+                    PatchType::MergePatch => Patch::Merge(&patch_obj),
+                    PatchType::StrategicMergePatch => Patch::Strategic(&patch_obj),
+                };
+                api.patch(&patch_name, &PatchParams::default(), &patch)
+                    .await
+            });
+            (
+                KindResponse::Object(kind_res),
+                Some(kind_get(&kind, namespace, &name)),
+            )
+        }
+        GeneratedRequest::Update {
+            kind,
+            name,
+            change,
+            resource_version,
+        } => {
+            let kind_current = kind_get(&kind, namespace, &name).ok();
+            if kind.allows_create_on_update() && kind_current.is_none() {
+                (KindResponse::Skipped, None)
+            } else {
+                let kind_obj = {
+                    let mut obj = object_to_update(&kind, &name, kind_current, &resource_version);
+                    apply_update_change(&mut obj, &change);
+                    obj
+                };
+                let update_name = name.clone();
+                let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                    api.replace(&update_name, &PostParams::default(), &kind_obj)
+                        .await
+                });
+                (
+                    KindResponse::Object(kind_res),
+                    Some(kind_get(&kind, namespace, &name)),
+                )
+            }
+        }
+        GeneratedRequest::UpdateStatus {
+            kind,
+            name,
+            message,
+            resource_version,
+        } => {
+            let kind_current = kind_get(&kind, namespace, &name).ok();
+            if kind.allows_create_on_update() && kind_current.is_none() {
+                (KindResponse::Skipped, None)
+            } else {
+                let kind_obj = {
+                    let mut obj = object_to_update(&kind, &name, kind_current, &resource_version);
+                    obj.data["status"] = status_to_write(&message);
+                    obj
+                };
+                let update_status_name = name.clone();
+                let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                    let data = deps_hack::serde_json::to_vec(&kind_obj).unwrap();
+                    api.replace_status(&update_status_name, &PostParams::default(), data)
+                        .await
+                });
+                (
+                    KindResponse::Object(kind_res),
+                    Some(kind_get(&kind, namespace, &name)),
+                )
+            }
+        }
+        GeneratedRequest::Delete {
+            kind,
+            name,
+            grace_period_seconds,
+        } => {
+            let delete_name = name.clone();
+            let dp = DeleteParams {
+                grace_period_seconds: grace_period_seconds,
+                ..DeleteParams::default()
+            };
+            let kind_res = block_on_cluster(&kind, namespace, |api| async move {
+                api.delete(&delete_name, &dp).await
+            });
+            (
+                KindResponse::Delete(kind_res.map(|_| ())),
+                Some(kind_get(&kind, namespace, &name)),
+            )
+        }
+        GeneratedRequest::Apply {
+            name,
+            field_manager,
+            labels,
+            finalizers,
+            data,
+            force,
+        } => {
+            // The patch params are built by the same patch_params used by the shim layer
+            let apply_req = apply_request(namespace, &name, &field_manager, &labels, &finalizers, &data, force);
+            let pp = apply_req.patch_params();
+            let obj = apply_req.obj.into_kube();
+            let apply_name = name.clone();
+            let kind_res = block_on_cluster(&KubeKind::ConfigMapKind, namespace, |api| async move {
+                api.patch(&apply_name, &pp, &Patch::Apply(&obj)).await
+            });
+            (
+                KindResponse::Object(kind_res),
+                Some(kind_get(&KubeKind::ConfigMapKind, namespace, &name)),
+            )
+        }
+    };
+    RecordedStep {
+        request: request.clone(),
+        response: response,
+        stored: stored,
+    }
+}
+
+// model_step sends the generated request of the step to the model
+// and compares what the model returns and stores with what Kubernetes does in the step.
+fn model_step(
+    step: &RecordedStep,
+    namespace: &std::string::String,
+    api_server_state: &mut ApiServerState,
+) -> Result<(), TestCaseError> {
+    let kind_res = match &step.response {
+        KindResponse::Skipped => return Ok(()),
+        KindResponse::Object(kind_res) => kind_res,
+        KindResponse::List(kind_res) => {
+            let (kind, label_selector, match_expressions, field_selector) = match &step.request {
+                GeneratedRequest::List {
+                    kind,
+                    label_selector,
+                    match_expressions,
+                    field_selector,
+                } => (kind, label_selector, match_expressions, field_selector),
+                _ => panic!(),
+            };
+            let model_resp = SimpleExecutableApiServerModel::handle_list_request(
+                &list_request(kind, namespace, label_selector, match_expressions, field_selector),
+                api_server_state,
+            );
+            prop_assert_eq!(error_diff(&model_resp.res, kind_res), None);
+            if let (Ok(model_objs), Ok(kind_objs)) = (model_resp.res, kind_res) {
+                // Both list the objects in the order of their names.
+                let kind_objs: Vec<&deps_hack::kube::api::DynamicObject> = kind_objs
+                    .iter()
+                    .filter(|obj| {
+                        !kind.is_created_by_kubernetes(obj.metadata.name.as_ref().unwrap())
+                    })
+                    .collect();
+                prop_assert_eq!(model_objs.len(), kind_objs.len());
+                for (model_obj, kind_obj) in model_objs.iter().zip(kind_objs.into_iter()) {
+                    prop_assert_eq!(object_diff(model_obj.as_kube_ref(), kind_obj), None);
+                }
+            }
+            return Ok(());
+        }
+        KindResponse::Delete(kind_res) => {
+            let (kind, name, grace_period_seconds) = match &step.request {
+                GeneratedRequest::Delete {
+                    kind,
+                    name,
+                    grace_period_seconds,
+                } => (kind, name, grace_period_seconds),
+                _ => panic!(),
+            };
+            let delete_request = KubeDeleteRequest {
+                api_resource: kind.to_api_resource(),
+                name: name.clone(),
+                namespace: namespace.clone(),
+                preconditions: None,
+                propagation_policy: None,
+                grace_period_seconds: *grace_period_seconds,
+            };
+            let model_resp = SimpleExecutableApiServerModel::handle_delete_request(
+                &delete_request,
+                api_server_state,
+            );
+            prop_assert_eq!(error_diff(&model_resp.res, kind_res), None);
+            return stored_object_check(step, kind, namespace, name, api_server_state);
+        }
+    };
+    let model_res = match step.request.clone() {
+        GeneratedRequest::Get { kind, name } => {
+            model_get(&kind, namespace, &name, api_server_state)
+        }
+        GeneratedRequest::Create { kind, name } => {
+            let obj = {
+                let mut obj = kind.to_default_dynamic_object();
+                obj.set_name(name);
+                obj
+            };
+            let create_request = KubeCreateRequest {
+                api_resource: kind.to_api_resource(),
+                namespace: namespace.clone(),
+                obj: obj,
+            };
+            SimpleExecutableApiServerModel::handle_create_request(&create_request, api_server_state)
+                .res
+        }
+        GeneratedRequest::CreateBoundPod { name } => {
+            let obj = {
+                let mut obj = bound_pod();
+                obj.set_name(name);
+                obj
+            };
+            let create_request = KubeCreateRequest {
+                api_resource: KubeKind::PodKind.to_api_resource(),
+                namespace: namespace.clone(),
+                obj: obj,
+            };
+            SimpleExecutableApiServerModel::handle_create_request(&create_request, api_server_state)
+                .res
+        }
+        GeneratedRequest::Patch {
+            kind,
+            name,
+            change,
+            patch_type,
+        } => {
+            let patch_request = KubePatchRequest {
+                api_resource: kind.to_api_resource(),
+                name: name,
+                namespace: namespace.clone(),
+                patch: patch_object(&kind, &change, namespace),
+                patch_type: patch_type,
+            };
+            SimpleExecutableApiServerModel::handle_patch_request(&patch_request, api_server_state)
+                .res
+        }
+        GeneratedRequest::Update {
+            kind,
+            name,
+            change,
+            resource_version,
+        } => {
+            let model_current = model_get(&kind, namespace, &name, api_server_state)
+                .ok()
+                .map(|obj| obj.into_kube());
+            let model_obj = {
+                let mut obj = object_to_update(&kind, &name, model_current, &resource_version);
+                apply_update_change(&mut obj, &change);
+                obj
+            };
+            let update_request = KubeUpdateRequest {
+                api_resource: kind.to_api_resource(),
+                name: name,
+                namespace: namespace.clone(),
+                obj: DynamicObject::from_kube(model_obj),
+            };
+            SimpleExecutableApiServerModel::handle_update_request(&update_request, api_server_state)
+                .res
+        }
+        GeneratedRequest::UpdateStatus {
+            kind,
+            name,
+            message,
+            resource_version,
+        } => {
+            let model_current = model_get(&kind, namespace, &name, api_server_state)
+                .ok()
+                .map(|obj| obj.into_kube());
+            let model_obj = {
+                let mut obj = object_to_update(&kind, &name, model_current, &resource_version);
+                obj.data["status"] = status_to_write(&message);
+                obj
+            };
+            let update_status_request = KubeUpdateStatusRequest {
+                api_resource: kind.to_api_resource(),
+                name: name,
+                namespace: namespace.clone(),
+                obj: DynamicObject::from_kube(model_obj),
+            };
+            let model_res = SimpleExecutableApiServerModel::handle_update_status_request(
+                &update_status_request,
+                api_server_state,
+            )
+            .res;
+            // Since json_subsumes allows Kubernetes to have more fields than the model,
+            // we also check that the written status is in the model exactly when it is in Kubernetes.
+            if let (Ok(model_obj), Ok(kind_obj)) = (&model_res, kind_res) {
+                let written = status_to_write(&message);
+                prop_assert_eq!(
+                    json_subsumes(&model_obj.as_kube_ref().data["status"], &written),
+                    json_subsumes(&kind_obj.data["status"], &written)
+                );
+            }
+            model_res
+        }
+        GeneratedRequest::Apply {
+            name,
+            field_manager,
+            labels,
+            finalizers,
+            data,
+            force,
+        } => {
+            let apply_req = apply_request(namespace, &name, &field_manager, &labels, &finalizers, &data, force);
+            let model_res = SimpleExecutableApiServerModel::handle_apply_request(&apply_req, api_server_state).res;
+            // object_diff skips the managed fields, so the ownership recorded by apply is compared here
+            if let (Ok(model_obj), Ok(kind_obj)) = (&model_res, kind_res) {
+                prop_assert_eq!(
+                    applied_fields_by_manager(model_obj.as_kube_ref()),
+                    applied_fields_by_manager(kind_obj)
+                );
+            }
+            model_res
+        }
+        _ => panic!(),
+    };
+    prop_assert_eq!(response_diff(&model_res, kind_res), None);
+    match &step.request {
+        GeneratedRequest::Create { kind, name }
+        | GeneratedRequest::Patch { kind, name, .. }
+        | GeneratedRequest::Update { kind, name, .. }
+        | GeneratedRequest::UpdateStatus { kind, name, .. } => {
+            stored_object_check(step, kind, namespace, name, api_server_state)
+        }
+        GeneratedRequest::CreateBoundPod { name } => {
+            stored_object_check(step, &KubeKind::PodKind, namespace, name, api_server_state)
+        }
+        GeneratedRequest::Apply { name, .. } => {
+            stored_object_check(step, &KubeKind::ConfigMapKind, namespace, name, api_server_state)
+        }
+        _ => Ok(()),
+    }
+}
+
+// stored_object_check compares the object stored in the model and in Kubernetes after a request,
+// which catches divergences that the response does not show, e.g., whether a delete request removes the object.
+fn stored_object_check(
+    step: &RecordedStep,
+    kind: &KubeKind,
+    namespace: &std::string::String,
+    name: &std::string::String,
+    api_server_state: &ApiServerState,
+) -> Result<(), TestCaseError> {
+    if let Some(kind_stored) = &step.stored {
+        let model_stored = model_get(kind, namespace, name, api_server_state);
+        prop_assert_eq!(
+            response_diff(&model_stored, kind_stored)
+                .map(|diff| format!("stored object differs after the request: {}", diff)),
+            None
+        );
+    }
+    Ok(())
+}
+
+// list_request builds the list request sent to both the model and Kubernetes.
+fn list_request(
+    kind: &KubeKind,
+    namespace: &std::string::String,
+    label_selector: &Option<std::collections::BTreeMap<std::string::String, std::string::String>>,
+    match_expressions: &Option<Vec<GeneratedRequirement>>,
+    field_selector: &Option<std::string::String>,
+) -> KubeListRequest {
+    KubeListRequest {
+        api_resource: kind.to_api_resource(),
+        namespace: namespace.clone(),
+        label_selector: if label_selector.is_none() && match_expressions.is_none() {
+            None
+        } else {
+            Some(LabelSelector::from_kube(
+                deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
+                    match_labels: label_selector.clone(),
+                    match_expressions: match_expressions.as_ref().map(|exprs| {
+                        exprs
+                            .iter()
+                            .map(|expr| deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelectorRequirement {
+                                key: expr.key.clone(),
+                                operator: expr.operator.clone(),
+                                values: expr.values.clone(),
+                            })
+                            .collect()
+                    }),
+                },
+            ))
+        },
+        field_selector: field_selector.as_ref().map(|name| {
+            let mut selector = FieldSelector::default();
+            selector.set_name(name.clone());
+            selector
+        }),
+    }
+}
+
+// patch_object builds the patch sent to both the model and Kubernetes.
+// Each patch changes one field so that the response shows how exactly the patch is merged.
+fn patch_object(kind: &KubeKind, change: &PatchChange, namespace: &std::string::String) -> DynamicObject {
+    let mut metadata = ObjectMeta::default();
+    let mut data = deps_hack::serde_json::json!({});
+    match change {
+        PatchChange::Label { key, value } => {
+            metadata.labels = Some(vec![(key.clone(), value.clone())].into_iter().collect());
+        }
+        PatchChange::Annotation { key, value } => {
+            metadata.annotations = Some(vec![(key.clone(), value.clone())].into_iter().collect());
+        }
+        PatchChange::Finalizer { finalizer } => {
+            metadata.finalizers = Some(vec![finalizer.clone()]);
+        }
+        PatchChange::Body { item } => {
+            data = body_patch(kind, item, namespace);
+        }
+    }
+    DynamicObject::from_kube(deps_hack::kube::api::DynamicObject {
+        types: None,
+        metadata: metadata,
+        data: data,
+    })
+}
+
+// body_patch patches the main list (or map) of each kind with the given item, or with an empty list if there is no item.
+// The lists cover both strategic merge by key (ports of a Service by port and secrets of a ServiceAccount by name)
+// and replacement (rules of a Role, and subjects of a RoleBinding, which are named but have no merge key).
+// A Service needs at least one port and a Pod at least one container, so their patches always carry one;
+// the container is merged by name and only its image changes.
+// A SimpleCR accepts any spec, and strategic merge patches of it are rejected by both Kubernetes and the model.
+fn body_patch(kind: &KubeKind, item: &Option<std::string::String>, namespace: &std::string::String) -> Value {
+    let items = |f: &dyn Fn(&std::string::String) -> Value| -> Value {
+        Value::Array(item.iter().map(|item| f(item)).collect())
+    };
+    match kind {
+        KubeKind::ConfigMapKind => deps_hack::serde_json::json!({
+            "data": item.iter().map(|item| (format!("key-{}", item), Value::String("value".to_string()))).collect::<deps_hack::serde_json::Map<_, _>>()
+        }),
+        KubeKind::SecretKind => deps_hack::serde_json::json!({
+            // "dmFsdWU=" is "value" in base64
+            "data": item.iter().map(|item| (format!("key-{}", item), Value::String("dmFsdWU=".to_string()))).collect::<deps_hack::serde_json::Map<_, _>>()
+        }),
+        KubeKind::ServiceKind => {
+            let port = if item.as_deref() == Some("b") { 8080 } else { 80 };
+            deps_hack::serde_json::json!({
+                "spec": {"ports": [{"name": format!("port-{}", port), "port": port}]}
+            })
+        }
+        KubeKind::ServiceAccountKind => deps_hack::serde_json::json!({
+            "secrets": items(&|item| deps_hack::serde_json::json!({"name": format!("secret-{}", item)}))
+        }),
+        KubeKind::RoleKind => deps_hack::serde_json::json!({
+            "rules": items(&|item| deps_hack::serde_json::json!({
+                "apiGroups": [""],
+                "resources": ["pods"],
+                "verbs": [if item == "a" { "get" } else { "list" }],
+            }))
+        }),
+        KubeKind::RoleBindingKind => deps_hack::serde_json::json!({
+            "subjects": items(&|item| deps_hack::serde_json::json!({
+                "kind": "ServiceAccount",
+                "name": format!("sa-{}", item),
+                "namespace": namespace,
+            }))
+        }),
+        KubeKind::PodKind => {
+            let image = if item.as_deref() == Some("b") { "nginx:1.25" } else { "nginx" };
+            deps_hack::serde_json::json!({
+                "spec": {"containers": [{"name": "app", "image": image}]}
+            })
+        }
+        KubeKind::CustomResourceKind(kind) if kind == "SimpleCR" => deps_hack::serde_json::json!({
+            "spec": {"items": items(&|item| Value::String(item.clone()))}
+        }),
+        _ => panic!(),
+    }
+}
+
+// apply_request builds the apply request sent to both the model and Kubernetes.
+// The applied ConfigMap carries apiVersion and kind since server-side apply requires them.
+fn apply_request(
+    namespace: &std::string::String,
+    name: &std::string::String,
+    field_manager: &std::string::String,
+    labels: &std::collections::BTreeMap<std::string::String, std::string::String>,
+    finalizers: &std::collections::BTreeSet<std::string::String>,
+    data: &std::collections::BTreeMap<std::string::String, std::string::String>,
+    force: bool,
+) -> KubeApplyRequest {
+    let labels = if labels.is_empty() {
+        Value::Null
+    } else {
+        deps_hack::serde_json::to_value(labels).unwrap()
+    };
+    let finalizers = if finalizers.is_empty() {
+        Value::Null
+    } else {
+        deps_hack::serde_json::to_value(finalizers).unwrap()
+    };
+    let obj: deps_hack::kube::api::DynamicObject = deps_hack::serde_json::from_value(deps_hack::serde_json::json!({
+        "apiVersion": "v1",
+        "kind": "ConfigMap",
+        "metadata": {
+            "name": name,
+            "labels": labels,
+            "finalizers": finalizers,
+        },
+        "data": data,
+    }))
+    .unwrap();
+    KubeApplyRequest {
+        api_resource: KubeKind::ConfigMapKind.to_api_resource(),
+        name: name.clone(),
+        namespace: namespace.clone(),
+        obj: DynamicObject::from_kube(obj),
+        field_manager: field_manager.clone(),
+        force: force,
+    }
+}
+
+// The fields owned by each manager through apply.
+// Kubernetes also records the fields set by the other requests, which the model does not track.
+fn applied_fields_by_manager(
+    obj: &deps_hack::kube::api::DynamicObject,
+) -> std::collections::BTreeMap<Option<std::string::String>, std::collections::BTreeSet<apply::FieldPath>> {
+    let mut by_manager = std::collections::BTreeMap::new();
+    for entry in obj.metadata.managed_fields.clone().unwrap_or_default() {
+        if entry.operation.as_deref() == Some("Apply") {
+            by_manager
+                .entry(entry.manager.clone())
+                .or_insert_with(std::collections::BTreeSet::new)
+                .extend(apply::entry_fields(&entry));
+        }
+    }
+    by_manager
+}
+
+// Setting CONFORMANCE_TRACE_RECORD to a file path makes test_model append every test case,
+// together with the behavior of Kubernetes in it, to that file.
+// The file can be replayed later by test_model_with_trace without a cluster.
+fn record_case(case: &RecordedCase) {
+    if let Ok(path) = std::env::var("CONFORMANCE_TRACE_RECORD") {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{}", deps_hack::serde_json::to_string(case).unwrap()).unwrap();
+    }
+}
+
 fn create_new_testing_namespace(len: usize) -> Option<std::string::String> {
     let mut rng = rand::thread_rng();
     let random_number: i32 = rng.gen_range(0..=10000);
@@ -123,54 +1474,80 @@ proptest! {
         prop_assume!(namespace_opt.is_some());
         let namespace = namespace_opt.unwrap();
         println!("Running with {} generated requests in namespace {}", generated_request_sequence.len(), namespace);
+        // We first run the whole sequence against Kubernetes and record it, so that the case is recorded
+        // even if the model diverges from Kubernetes in the middle.
+        let case = RecordedCase {
+            namespace: namespace.clone(),
+            steps: generated_request_sequence.iter().map(|request| kind_step(request, &namespace)).collect(),
+        };
+        record_case(&case);
         let mut api_server_state = ApiServerState::new();
-        for generated_request in generated_request_sequence {
-            match generated_request {
-                // Testing get request handler
-                GeneratedRequest::Get{kind, name} => {
-                    let get_request = KubeGetRequest {
-                        api_resource: kind.to_api_resource(),
-                        namespace: namespace.clone(),
-                        name: name.clone(),
-                    };
-                    let model_resp = SimpleExecutableApiServerModel::handle_get_request(&get_request, &api_server_state);
-
-                    let rt = Runtime::new().unwrap();
-                    let kind_resp = rt.block_on(async {
-                        let client = Client::try_default().await.unwrap();
-                        let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
-                            client, &namespace, kind.to_api_resource().as_kube_ref()
-                        );
-                        api.get(&name).await
-                    });
-
-                    prop_assert_eq!(model_resp.res.is_ok(), kind_resp.is_ok());
-                }
-                // Testing create request handler
-                GeneratedRequest::Create{kind, name} => {
-                    let obj = {
-                        let mut obj = kind.to_default_dynamic_object();
-                        obj.set_name(name);
-                        obj
-                    };
-                    let create_request = KubeCreateRequest {
-                        api_resource: kind.to_api_resource(),
-                        namespace: namespace.clone(),
-                        obj: obj.clone(),
-                    };
-                    let model_resp = SimpleExecutableApiServerModel::handle_create_request(&create_request, &mut api_server_state);
-
-                    let rt = Runtime::new().unwrap();
-                    let kind_resp = rt.block_on(async {
-                        let client = Client::try_default().await.unwrap();
-                        let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
-                            client, &namespace, kind.to_api_resource().as_kube_ref()
-                        );
-                        api.create(&PostParams::default(), &obj.into_kube()).await
-                    });
-
-                    prop_assert_eq!(model_resp.res.is_ok(), kind_resp.is_ok());
-                }
+        for step in case.steps.iter() {
+            model_step(step, &namespace, &mut api_server_state)?;
+        }
+    }
+}
+
+// test_model_with_repeated_pod_deletion deletes a terminating pod again, which the generated requests never do
+// since Kubernetes deletes their pods right away (see delete_grace_period_seconds).
+// A delete request that does not shorten the grace period leaves the pod unchanged,
+// and the one that shortens it to zero removes the pod.
+#[test]
+fn test_model_with_repeated_pod_deletion() {
+    let requests = vec![
+        GeneratedRequest::CreateBoundPod { name: "pod-a".to_string() },
+        GeneratedRequest::Delete { kind: KubeKind::PodKind, name: "pod-a".to_string(), grace_period_seconds: Some(30) },
+        GeneratedRequest::Delete { kind: KubeKind::PodKind, name: "pod-a".to_string(), grace_period_seconds: Some(30) },
+        GeneratedRequest::Delete { kind: KubeKind::PodKind, name: "pod-a".to_string(), grace_period_seconds: Some(60) },
+        GeneratedRequest::Delete { kind: KubeKind::PodKind, name: "pod-a".to_string(), grace_period_seconds: None },
+        GeneratedRequest::Delete { kind: KubeKind::PodKind, name: "pod-a".to_string(), grace_period_seconds: Some(0) },
+    ];
+    let namespace = create_new_testing_namespace(requests.len()).unwrap();
+    let case = RecordedCase {
+        namespace: namespace.clone(),
+        steps: requests.iter().map(|request| kind_step(request, &namespace)).collect(),
+    };
+    record_case(&case);
+    let mut api_server_state = ApiServerState::new();
+    for (j, step) in case.steps.iter().enumerate() {
+        if let Err(err) = model_step(step, &namespace, &mut api_server_state) {
+            panic!("Diverges at step {} ({:?}): {}", j, step.request, err);
+        }
+    }
+}
+
+// test_model_with_trace replays the test cases recorded by test_model (see record_case)
+// from the file at CONFORMANCE_TRACE_REPLAY, comparing the model with the recorded behavior of Kubernetes.
+// It needs no cluster, so the conformance-trace-replay job in CI replays the trace recorded
+// by the conformance-tests job against kind without access to the cluster.
+// It does nothing if CONFORMANCE_TRACE_REPLAY is not set, and fails if the trace is missing or empty.
+#[test]
+fn test_model_with_trace() {
+    let path = match std::env::var("CONFORMANCE_TRACE_REPLAY") {
+        Ok(path) => path,
+        Err(_) => {
+            println!("CONFORMANCE_TRACE_REPLAY is not set, skip replaying");
+            return;
+        }
+    };
+    let trace = match std::fs::read_to_string(&path) {
+        Ok(trace) => trace,
+        Err(err) => panic!("Fail to read the trace {}: {}", path, err),
+    };
+    let cases: Vec<RecordedCase> = trace
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| deps_hack::serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(!cases.is_empty(), "The trace {} has no test case", path);
+    for (i, case) in cases.iter().enumerate() {
+        let mut api_server_state = ApiServerState::new();
+        for (j, step) in case.steps.iter().enumerate() {
+            if let Err(err) = model_step(step, &case.namespace, &mut api_server_state) {
+                panic!(
+                    "Case {} diverges at step {} ({:?}): {}",
+                    i, j, step.request, err
+                );
             }
         }
     }
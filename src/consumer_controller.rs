@@ -4,6 +4,7 @@
 
 #[path = "controller_examples/composition_example/consumer_controller/mod.rs"]
 pub mod consumer_controller;
+pub mod executable_model;
 pub mod external_api;
 pub mod kubernetes_api_objects;
 pub mod kubernetes_cluster;
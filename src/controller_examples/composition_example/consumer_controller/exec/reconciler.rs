@@ -15,6 +15,9 @@ use crate::kubernetes_api_objects::spec::prelude::PodView;
 use crate::kubernetes_api_objects::spec::resource::ResourceView;
 use crate::producer_controller::trusted::exec_types::*;
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use crate::shim_layer::requeue::RequeueHintReconciler;
 use crate::vstd_ext::{string_map::StringMap, string_view::*};
 use vstd::prelude::*;
 use vstd::seq_lib::*;
@@ -46,6 +49,12 @@ impl Reconciler for ConsumerReconciler {
     fn reconcile_error(state: &ConsumerReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Producer::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: ConsumerReconcileState)
@@ -246,3 +255,11 @@ fn make_producer(consumer: &Consumer) -> (producer: Producer)
 }
 
 }
+
+// There is no executable model of the spec reconcile_core yet, so the refinement monitor skips this reconciler.
+impl ExecutableModelReconciler for ConsumerReconciler {}
+
+impl RequeueHintReconciler for ConsumerReconciler {}
+
+// There is no exec checker of current_state_matches yet, so the health probe answers that the state is unchecked.
+impl StateMatchesReconciler for ConsumerReconciler {}
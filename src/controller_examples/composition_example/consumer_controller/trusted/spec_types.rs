@@ -62,7 +62,7 @@ impl ResourceView for ConsumerView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("Consumer"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
@@ -14,6 +14,8 @@ use crate::producer_controller::trusted::exec_types::*;
 use crate::producer_controller::trusted::spec_types;
 use crate::producer_controller::trusted::step::*;
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::requeue::RequeueHintReconciler;
 use crate::vstd_ext::{string_map::StringMap, string_view::*};
 use vstd::prelude::*;
 use vstd::seq_lib::*;
@@ -45,6 +47,12 @@ impl Reconciler for ProducerReconciler {
     fn reconcile_error(state: &ProducerReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Pod::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: ProducerReconcileState)
@@ -163,3 +171,8 @@ fn make_pod(producer: &Producer) -> (pod: Pod)
 }
 
 }
+
+impl RequeueHintReconciler for ProducerReconciler {}
+
+// There is no exec checker of current_state_matches yet, so the health probe answers that the state is unchecked.
+impl StateMatchesReconciler for ProducerReconciler {}
@@ -0,0 +1,111 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::producer_controller::exec::reconciler::ProducerReconciler;
+use crate::shim_layer::reconcile_trace::{kube_api_resource_to_json, ReconcileTraceOutcome};
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use deps_hack::k8s_openapi::api::core::v1::{Container, ContainerPort, Pod, PodSpec};
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use deps_hack::kube::api::{ApiResource, Resource};
+use deps_hack::serde::{Deserialize, Serialize};
+use deps_hack::serde_json::{json, Value};
+use deps_hack::Producer;
+
+// This is the executable model of model::reconciler::reconcile_core used by the refinement monitor
+// (see crate::shim_layer::refinement_monitor). Each function below transcribes the spec function of the same name
+// to kube-rs types, so keep them in sync when changing the spec.
+// The model works on the objects as served by Kubernetes, so it never goes through the exec types or their views.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum ProducerReconcileStepModel {
+    Init,
+    Done,
+    Error,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct ProducerReconcileStateModel {
+    reconcile_step: ProducerReconcileStepModel,
+}
+
+impl ExecutableModelReconciler for ProducerReconciler {
+    fn model_init_state() -> Option<Value> {
+        deps_hack::serde_json::to_value(ProducerReconcileStateModel {
+            reconcile_step: ProducerReconcileStepModel::Init,
+        })
+        .ok()
+    }
+
+    fn model_reconcile_core(
+        cr: &Value,
+        _resp_o: Option<&Value>,
+        state: &Value,
+    ) -> Option<(Value, Option<Value>)> {
+        let producer = deps_hack::serde_json::from_value::<Producer>(cr.clone()).ok()?;
+        let state =
+            deps_hack::serde_json::from_value::<ProducerReconcileStateModel>(state.clone()).ok()?;
+        let (state_prime, req_o) = reconcile_core(&producer, state)?;
+        Some((deps_hack::serde_json::to_value(state_prime).ok()?, req_o))
+    }
+
+    fn model_outcome(state: &Value) -> Option<ReconcileTraceOutcome> {
+        let state =
+            deps_hack::serde_json::from_value::<ProducerReconcileStateModel>(state.clone()).ok()?;
+        match state.reconcile_step {
+            ProducerReconcileStepModel::Done => Some(ReconcileTraceOutcome::Done),
+            ProducerReconcileStepModel::Error => Some(ReconcileTraceOutcome::Error),
+            _ => None,
+        }
+    }
+}
+
+// The spec reconcile_core never looks at the response
+fn reconcile_core(
+    producer: &Producer,
+    state: ProducerReconcileStateModel,
+) -> Option<(ProducerReconcileStateModel, Option<Value>)> {
+    let namespace = producer.metadata.namespace.clone()?;
+    match state.reconcile_step {
+        ProducerReconcileStepModel::Init => {
+            let req = json!({ "KRequest": { "Create": {
+                "api_resource": kube_api_resource_to_json(&ApiResource::erase::<Pod>(&())),
+                "namespace": namespace,
+                "obj": deps_hack::serde_json::to_value(make_pod(producer)?).ok()?,
+            }}});
+            let state_prime = ProducerReconcileStateModel {
+                reconcile_step: ProducerReconcileStepModel::Done,
+            };
+            Some((state_prime, Some(req)))
+        }
+        _ => Some((state, None)),
+    }
+}
+
+fn make_pod(producer: &Producer) -> Option<Pod> {
+    Some(Pod {
+        metadata: ObjectMeta {
+            name: Some(producer.metadata.name.clone()?),
+            owner_references: Some(vec![producer.controller_owner_ref(&())?]),
+            labels: Some(
+                vec![("producer_message".to_string(), producer.spec.message.clone())]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..ObjectMeta::default()
+        },
+        spec: Some(PodSpec {
+            containers: vec![Container {
+                name: "nginx".to_string(),
+                image: Some("nginx:1.14.2".to_string()),
+                ports: Some(vec![ContainerPort {
+                    container_port: 80,
+                    ..ContainerPort::default()
+                }]),
+                ..Container::default()
+            }],
+            ..PodSpec::default()
+        }),
+        ..Pod::default()
+    })
+}
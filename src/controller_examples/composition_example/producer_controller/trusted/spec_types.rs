@@ -76,7 +76,7 @@ impl ResourceView for ProducerView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("Producer"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
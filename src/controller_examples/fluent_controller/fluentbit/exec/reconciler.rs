@@ -1,8 +1,10 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
+use crate::executable_model::object_map::ObjectMap;
 use crate::external_api::exec::*;
 use crate::fluent_controller::fluentbit::exec::resource::*;
+use crate::fluent_controller::fluentbit::exec::state_matches;
 use crate::fluent_controller::fluentbit::model::reconciler as model_reconciler;
 use crate::fluent_controller::fluentbit::model::resource as model_resource;
 use crate::fluent_controller::fluentbit::trusted::{exec_types::*, spec_types, step::*};
@@ -10,6 +12,9 @@ use crate::kubernetes_api_objects::exec::prelude::*;
 use crate::kubernetes_api_objects::exec::resource::ResourceWrapper;
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
 use crate::reconciler::spec::resource_builder::ResourceBuilder as SpecResourceBuilder;
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use crate::shim_layer::requeue::RequeueHintReconciler;
 use crate::vstd_ext::{string_map::StringMap, string_view::*};
 use vstd::{prelude::*, string::*};
 
@@ -40,6 +45,16 @@ impl Reconciler for FluentBitReconciler {
     fn reconcile_error(state: &FluentBitReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Role::api_resource());
+        resources.push(ServiceAccount::api_resource());
+        resources.push(RoleBinding::api_resource());
+        resources.push(Service::api_resource());
+        resources.push(DaemonSet::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: FluentBitReconcileState)
@@ -248,3 +263,14 @@ pub fn reconcile_helper<
 }
 
 }
+
+// There is no executable model of the spec reconcile_core yet, so the refinement monitor skips this reconciler.
+impl ExecutableModelReconciler for FluentBitReconciler {}
+
+impl RequeueHintReconciler for FluentBitReconciler {}
+
+impl StateMatchesReconciler for FluentBitReconciler {
+    fn state_matches(fb: &FluentBit, snapshot: &ObjectMap) -> Option<bool> {
+        Some(state_matches::current_state_matches(fb, snapshot))
+    }
+}
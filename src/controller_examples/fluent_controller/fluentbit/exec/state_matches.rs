@@ -0,0 +1,86 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::ObjectMap, state_matches::*};
+use crate::fluent_controller::fluentbit::exec::resource::*;
+use crate::fluent_controller::fluentbit::model::reconciler::FluentBitMaker;
+use crate::fluent_controller::fluentbit::trusted::{exec_types::*, liveness_theorem, step::*};
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use vstd::{prelude::*, string::*};
+
+verus! {
+
+// resource_state_matches is the exec version of liveness_theorem::resource_state_matches instantiated with
+// FluentBitMaker (see crate::zookeeper_controller::exec::state_matches for how it is used).
+pub fn resource_state_matches(sub_resource: SubResource, fb: &FluentBit, resources: &ObjectMap) -> (res: bool)
+    requires fb@.well_formed(),
+    ensures res == liveness_theorem::resource_state_matches::<FluentBitMaker>(sub_resource, fb@, resources@),
+{
+    match sub_resource {
+        SubResource::ServiceAccount => {
+            let key = make_key(KubeKind::ServiceAccountKind, make_service_account_name(fb), fb);
+            let made = make_service_account(fb);
+            service_account_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::Role => {
+            let key = make_key(KubeKind::RoleKind, make_role_name(fb), fb);
+            let made = make_role(fb);
+            role_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::RoleBinding => {
+            let key = make_key(KubeKind::RoleBindingKind, make_role_binding_name(fb), fb);
+            let made = make_role_binding(fb);
+            role_binding_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::Service => {
+            let key = make_key(KubeKind::ServiceKind, make_service_name(fb), fb);
+            let made = make_service(fb);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::DaemonSet => {
+            let key = make_key(KubeKind::DaemonSetKind, make_daemon_set_name(fb), fb);
+            let made = make_daemon_set(fb);
+            daemon_set_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+    }
+}
+
+// current_state_matches checks liveness_theorem::current_state_matches on the objects stored in a cluster.
+pub fn current_state_matches(fb: &FluentBit, resources: &ObjectMap) -> (res: bool)
+    requires fb@.well_formed(),
+    ensures res == forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<FluentBitMaker>(sub_resource, fb@, resources@),
+{
+    let res = resource_state_matches(SubResource::ServiceAccount, fb, resources)
+        && resource_state_matches(SubResource::Role, fb, resources)
+        && resource_state_matches(SubResource::RoleBinding, fb, resources)
+        && resource_state_matches(SubResource::Service, fb, resources)
+        && resource_state_matches(SubResource::DaemonSet, fb, resources);
+    proof {
+        if res {
+            assert forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<FluentBitMaker>(sub_resource, fb@, resources@) by {
+                match sub_resource {
+                    SubResource::ServiceAccount => {},
+                    SubResource::Role => {},
+                    SubResource::RoleBinding => {},
+                    SubResource::Service => {},
+                    SubResource::DaemonSet => {},
+                }
+            }
+        }
+    }
+    res
+}
+
+fn make_key(kind: KubeKind, name: String, fb: &FluentBit) -> (key: KubeObjectRef)
+    requires fb@.well_formed(),
+    ensures key@ == (ObjectRef { kind: kind@, name: name@, namespace: fb@.metadata.namespace.get_Some_0() }),
+{
+    KubeObjectRef {
+        kind: kind,
+        name: name,
+        namespace: fb.metadata().namespace().unwrap(),
+    }
+}
+
+}
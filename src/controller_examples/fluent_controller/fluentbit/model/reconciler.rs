@@ -6,6 +6,7 @@ use crate::fluent_controller::fluentbit::model::resource::*;
 use crate::fluent_controller::fluentbit::trusted::{
     liveness_theorem::desired_secret_key, maker::*, spec_types::*, step::*,
 };
+use crate::fluent_controller::fluentbit_config::trusted::spec_types::FluentBitConfigView;
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::reconciler::spec::{io::*, reconciler::*, resource_builder::*};
 use vstd::{prelude::*, string::*};
@@ -32,7 +33,11 @@ impl Reconciler<FluentBitView, EmptyAPI> for FluentBitReconciler {
         reconcile_error(state)
     }
 
-    open spec fn expect_from_user(obj: DynamicObjectView) -> bool { obj.kind == SecretView::kind() /* expect the user might create some secret object */ }
+    // Expect the user might create some secret object, and the fluentbit config controller, which runs in the same
+    // cluster (see fluent_controller::multi_cluster), to create the fluentbit config objects and their secrets.
+    open spec fn expect_from_user(obj: DynamicObjectView) -> bool {
+        obj.kind == SecretView::kind() || obj.kind == FluentBitConfigView::kind()
+    }
 }
 
 pub open spec fn reconcile_init_state() -> FluentBitReconcileState { FluentBitReconcileState { reconcile_step: FluentBitReconcileStep::Init } }
@@ -67,13 +67,16 @@ pub proof fn lemma_always_object_in_every_resource_create_or_update_request_msg_
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::triggering_cr_has_lower_uid_than_uid_counter()(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
     };
     FBCluster::lemma_always_triggering_cr_has_lower_uid_than_uid_counter(spec);
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(FBCluster::next()),
         lift_state(FBCluster::triggering_cr_has_lower_uid_than_uid_counter()),
-        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion())
     );
     let resource_key = get_request(sub_resource, fb).key;
     let create_valid = |msg: FBMessage, s: FBCluster| {
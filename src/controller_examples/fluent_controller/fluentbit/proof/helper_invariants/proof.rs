@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use super::predicate::*;
+use crate::external_api::spec::EmptyTypeView;
 use crate::fluent_controller::fluentbit::{
     model::resource::*,
     proof::{
@@ -13,11 +14,13 @@ use crate::kubernetes_api_objects::spec::{
     api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
 };
 use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::request_uses_propagation_policy,
     cluster::*,
     cluster_state_machine::Step,
     controller::types::{ControllerActionInput, ControllerStep},
     message::*,
 };
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
 use crate::temporal_logic::{defs::*, rules::*};
 use crate::vstd_ext::{multiset_lib, seq_lib, string_view::*};
 use vstd::{multiset::*, prelude::*, string::*};
@@ -150,7 +153,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
         spec.entails(always(lift_state(FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -192,7 +197,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
         spec.entails(always(lift_state(FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -230,7 +237,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)(s)
         &&& FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
@@ -271,7 +280,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCluster::next()), lift_state(FBCluster::crash_disabled()), lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)),
         lift_state(FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()),
@@ -300,7 +311,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
     ensures spec.entails(true_pred().leads_to(always(tla_forall(|sub_resource: SubResource| lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, fb)))))),
@@ -320,7 +333,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, fb))))),
@@ -338,7 +353,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)(s)
     };
@@ -360,7 +377,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCluster::next()), lift_state(FBCluster::crash_disabled()), lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb))
     );
@@ -380,7 +399,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
         spec.entails(always(lift_state(fb_is_well_formed(fb)))),
@@ -401,7 +422,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)))),
         spec.entails(always(lift_state(fb_is_well_formed(fb)))),
@@ -422,7 +445,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)(s)
         &&& fb_is_well_formed(fb)(s)
@@ -446,7 +471,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCluster::next()), lift_state(FBCluster::crash_disabled()), lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::the_object_in_reconcile_has_spec_and_uid_as(fb)),
         lift_state(fb_is_well_formed(fb))
@@ -578,7 +605,7 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
@@ -591,6 +618,11 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
+                    Step::ServeReadFromCacheStep(_) => {
+                        assert(!msg.content.is_APIRequest());
+                        assert(!msg.content.is_update_status_request());
+                        assert(false);
+                    },
                     _ => {
                         assert(!s_prime.in_flight().contains(msg));
                         assert(false);
@@ -612,6 +644,42 @@ spec fn make_owner_references_with_name_and_uid(name: StringView, uid: Uid) -> O
     }
 }
 
+/// The fluent bit controller never deletes any object, and the objects it creates or updates are built by make and update,
+/// which set no finalizers. So the controller does not use the orphan or foreground propagation policy.
+pub proof fn lemma_reconciler_does_not_use_propagation_policy()
+    ensures FBCluster::reconciler_does_not_use_propagation_policy(),
+{
+    assert forall |fb: FluentBitView, resp_o: Option<ResponseView<EmptyTypeView>>, state: FluentBitReconcileState|
+        #![trigger FluentBitReconciler::reconcile_core(fb, resp_o, state)]
+        FluentBitReconciler::reconcile_core(fb, resp_o, state).1.is_Some()
+        && FluentBitReconciler::reconcile_core(fb, resp_o, state).1.get_Some_0().is_KRequest()
+    implies !request_uses_propagation_policy(FluentBitReconciler::reconcile_core(fb, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                if update(sub_resource, fb, state, get_resp.get_Ok_0()).is_Ok() {
+                    assert(update(sub_resource, fb, state, get_resp.get_Ok_0()).get_Ok_0().metadata.finalizers.is_None());
+                }
+            } else if make(sub_resource, fb, state).is_Ok() {
+                assert(make(sub_resource, fb, state).get_Ok_0().metadata.finalizers.is_None());
+            }
+        }
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<FBCluster>)
+    requires
+        spec.entails(lift_state(FBCluster::init())),
+        spec.entails(always(lift_action(FBCluster::next()))),
+    ensures spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
+{
+    lemma_reconciler_does_not_use_propagation_policy();
+    FBCluster::lemma_always_no_orphan_or_foreground_deletion(spec);
+}
+
 pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(spec: TempPred<FBCluster>, sub_resource: SubResource, fb: FluentBitView)
     requires
         spec.entails(lift_state(FBCluster::init())),
@@ -682,14 +750,17 @@ proof fn lemma_always_resource_object_create_or_update_request_msg_has_one_contr
     let stronger_next = |s, s_prime| {
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
     };
     let key = fb.object_ref();
     let resource_key = get_request(sub_resource, fb).key;
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(FBCluster::next()),
-        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion())
     );
     let create_msg_pred = |msg: FBMessage| {
         resource_create_request_msg(resource_key)(msg)
@@ -752,6 +823,7 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
         !s.in_flight().contains(msg), s_prime.in_flight().contains(msg),
         FBCluster::next_step(s, s_prime, step),
         FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        FBCluster::no_orphan_or_foreground_deletion()(s),
     ensures
         resource_create_request_msg(get_request(sub_resource, fb).key)(msg)
         ==> step.is_ControllerStep() && step.get_ControllerStep_0().1.get_Some_0() == fb.object_ref()
@@ -773,6 +845,10 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
     let cr = s.ongoing_reconciles()[key].triggering_cr;
     let resource_key = get_request(sub_resource, fb).key;
     if resource_create_request_msg(get_request(sub_resource, fb).key)(msg) || resource_update_request_msg(get_request(sub_resource, fb).key)(msg) {
+        if step.is_BuiltinControllersStep() {
+            // The builtin controllers do not orphan the dependents, so they only send delete and update status requests.
+            FBCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, step.get_BuiltinControllersStep_0());
+        }
         assert(step.is_ControllerStep());
         assert(s.ongoing_reconciles().contains_key(cr_key));
         let local_step = s.ongoing_reconciles()[cr_key].local_state.reconcile_step;
@@ -808,6 +884,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight_fo
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(FBCluster::next()))),
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCluster::external_api_next().weak_fairness(i))),
@@ -847,6 +925,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(FBCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(FBCluster::next()))),
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCluster::external_api_next().weak_fairness(i))),
@@ -864,6 +944,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     let stronger_next = |s: FBCluster, s_prime: FBCluster| {
         &&& FBCluster::next()(s, s_prime)
         &&& desired_state_is(fb)(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, fb)(s)
         &&& resource_well_formed(s)
     };
@@ -876,7 +957,12 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
             } else {
                 let step = choose |step| FBCluster::next_step(s, s_prime, step);
                 match step {
-                    Step::BuiltinControllersStep(_) => {
+                    Step::BuiltinControllersStep(input) => {
+                        FBCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                        if input.0.is_Kubelet() {
+                            // The kubelet only deletes pods, and none of the sub resources is a pod.
+                            assert(resource_key.kind != Kind::PodKind);
+                        }
                         if s.resources().contains_key(resource_key) {
                             let owner_refs = s.resources()[resource_key].metadata.owner_references;
                             assert(owner_refs == Some(seq![fb.controller_owner_ref()]));
@@ -890,7 +976,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
                             assert(!s_prime.ongoing_reconciles()[cr_key].pending_req_msg.get_Some_0().content.is_delete_request());
                         }
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         if msg.content.is_delete_request() {
                             assert(msg.content.get_delete_request().key.kind != resource_key.kind);
                         }
@@ -905,6 +991,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCluster::next()), lift_state(desired_state_is(fb)),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, fb)),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed())
     );
@@ -919,6 +1006,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(spec: TempPred<FBCluster>, fb: FluentBitView)
     requires
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(FBCluster::next()))),
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCluster::builtin_controllers_next().weak_fairness(i))),
@@ -943,6 +1031,7 @@ pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_po
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr(spec: TempPred<FBCluster>, sub_resource: SubResource, fb: FluentBitView)
     requires
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(FBCluster::next()))),
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCluster::builtin_controllers_next().weak_fairness(i))),
@@ -69,13 +69,16 @@ proof fn lemma_always_object_in_every_create_request_msg_satisfies_unchangeable(
     let next = |s, s_prime| {
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
     };
     FBCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next),
         lift_action(FBCluster::next()),
-        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion())
     );
     let resource_key = get_request(sub_resource, fb).key;
     assert forall |s: FBCluster, s_prime: FBCluster| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
@@ -119,6 +122,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<F
     let next = |s: FBCluster, s_prime: FBCluster| {
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& FBCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(resource_key)(s)
@@ -128,6 +132,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<F
         &&& no_create_resource_request_msg_without_name_in_flight(sub_resource, fb)(s)
     };
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     FBCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     always_to_always_later(spec, lift_state(FBCluster::each_object_in_etcd_is_well_formed()));
     FBCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, resource_key);
@@ -138,6 +143,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<F
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(FBCluster::next()),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
         later(lift_state(FBCluster::each_object_in_etcd_is_well_formed())),
         lift_state(FBCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(resource_key)),
@@ -205,6 +211,7 @@ pub proof fn object_in_every_update_request_msg_satisfies_unchangeable_induction
         object_in_every_update_request_msg_satisfies_unchangeable(sub_resource, fb)(s),
         FBCluster::next()(s, s_prime),
         FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        FBCluster::no_orphan_or_foreground_deletion()(s),
         FBCluster::each_object_in_etcd_is_well_formed()(s),
         FBCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(get_request(sub_resource, fb).key)(s),
         response_at_after_get_resource_step_is_resource_get_response(sub_resource, fb)(s),
@@ -154,6 +154,7 @@ pub proof fn lemma_always_daemon_set_update_request_msg_does_not_change_owner_re
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(SubResource::DaemonSet, fb)(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(ds_key)(s)
         &&& object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::DaemonSet, fb)(s)
     };
@@ -161,6 +162,7 @@ pub proof fn lemma_always_daemon_set_update_request_msg_does_not_change_owner_re
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, SubResource::DaemonSet, fb);
     always_to_always_later(spec, lift_state(FBCluster::each_object_in_etcd_is_well_formed()));
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     FBCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, ds_key);
     lemma_always_object_in_resource_update_request_msg_has_smaller_rv_than_etcd(spec, SubResource::DaemonSet, fb);
     combine_spec_entails_always_n!(
@@ -169,6 +171,7 @@ pub proof fn lemma_always_daemon_set_update_request_msg_does_not_change_owner_re
         later(lift_state(FBCluster::each_object_in_etcd_is_well_formed())),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(SubResource::DaemonSet, fb)),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(ds_key)),
         lift_state(object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::DaemonSet, fb))
     );
@@ -222,12 +225,14 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(sub_resource, fb)(s)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
     };
     FBCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, sub_resource, fb);
     always_to_always_later(spec, lift_state(FBCluster::each_object_in_etcd_is_well_formed()));
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     FBCluster::lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(FBCluster::next()),
@@ -235,6 +240,7 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
         later(lift_state(FBCluster::each_object_in_etcd_is_well_formed())),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(sub_resource, fb)),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::object_in_ok_get_response_has_smaller_rv_than_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
@@ -279,12 +285,14 @@ proof fn lemma_always_daemon_set_in_create_request_msg_satisfies_unchangeable(sp
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(fb)(s)
         &&& object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(ds_res, fb)(s)
     };
     FBCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     always_to_always_later(spec, lift_state(FBCluster::each_object_in_etcd_is_well_formed()));
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     FBCluster::lemma_always_transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(spec, fb);
     lemma_always_object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(spec, ds_res, fb);
     combine_spec_entails_always_n!(
@@ -293,6 +301,7 @@ proof fn lemma_always_daemon_set_in_create_request_msg_satisfies_unchangeable(sp
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
         later(lift_state(FBCluster::each_object_in_etcd_is_well_formed())),
         lift_state(FBCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(fb)),
         lift_state(object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(ds_res, fb))
     );
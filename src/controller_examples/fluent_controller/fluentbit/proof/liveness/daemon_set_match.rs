@@ -30,6 +30,7 @@ pub proof fn lemma_from_after_get_daemon_set_step_to_daemon_set_matches(spec: Te
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
@@ -71,6 +72,7 @@ proof fn lemma_from_after_get_daemon_set_step_and_key_exists_to_daemon_set_match
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
@@ -188,6 +190,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_daemon_set_step(
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(SubResource::DaemonSet, fb)))),
         spec.entails(always(lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(SubResource::DaemonSet, fb)))),
@@ -215,6 +218,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_daemon_set_step(
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(SubResource::DaemonSet, fb)(s)
         &&& helper_invariants::no_delete_resource_request_msg_in_flight(SubResource::DaemonSet, fb)(s)
@@ -225,6 +229,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_daemon_set_step(
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(SubResource::DaemonSet, fb)),
         lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(SubResource::DaemonSet, fb)),
@@ -277,6 +282,7 @@ proof fn lemma_from_after_get_daemon_set_step_to_after_update_daemon_set_step(sp
         spec.entails(tla_forall(|i| FBCluster::controller_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
@@ -309,6 +315,7 @@ proof fn lemma_from_after_get_daemon_set_step_to_after_update_daemon_set_step(sp
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
@@ -325,6 +332,7 @@ proof fn lemma_from_after_get_daemon_set_step_to_after_update_daemon_set_step(sp
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
@@ -359,6 +367,7 @@ proof fn lemma_daemon_set_state_matches_at_after_update_daemon_set_step(spec: Te
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(desired_state_is(fb)))),
@@ -391,6 +400,7 @@ proof fn lemma_daemon_set_state_matches_at_after_update_daemon_set_step(spec: Te
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& resource_well_formed(s)
         &&& desired_state_is(fb)(s)
@@ -408,6 +418,7 @@ proof fn lemma_daemon_set_state_matches_at_after_update_daemon_set_step(spec: Te
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(resource_well_formed),
         lift_state(desired_state_is(fb)),
@@ -37,7 +37,7 @@ use vstd::{prelude::*, string::*};
 verus! {
 
 // We prove init /\ []next /\ []wf |= []desired_state_is(fb) ~> []current_state_matches(fb) holds for each fb.
-proof fn liveness_proof_forall_fb()
+pub proof fn liveness_proof_forall_fb()
     ensures liveness_theorem::<FluentBitMaker>(),
 {
     assert forall |fb: FluentBitView| #[trigger] cluster_spec().entails(liveness::<FluentBitMaker>(fb)) by {
@@ -255,6 +255,7 @@ proof fn lemma_from_init_step_to_after_get_service_account_step(spec: TempPred<F
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(desired_state_is(fb)))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
@@ -353,6 +354,7 @@ proof fn lemma_from_send_get_secret_req_to_receive_ok_resp_at_after_get_secret_s
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(desired_state_is(fb)))),
     ensures spec.entails(lift_state(req_msg_is_the_in_flight_pending_req_at_after_get_secret_step(fb, req_msg)).leads_to(lift_state(at_after_get_secret_step_and_exists_ok_resp_in_flight(fb)))),
@@ -365,6 +367,7 @@ proof fn lemma_from_send_get_secret_req_to_receive_ok_resp_at_after_get_secret_s
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& desired_state_is(fb)(s)
     };
@@ -373,6 +376,7 @@ proof fn lemma_from_send_get_secret_req_to_receive_ok_resp_at_after_get_secret_s
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(desired_state_is(fb))
     );
@@ -416,6 +420,7 @@ proof fn lemma_from_after_get_secret_step_to_after_get_service_account_step(spec
         spec.entails(tla_forall(|i| FBCluster::controller_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
@@ -428,6 +433,7 @@ proof fn lemma_from_after_get_secret_step_to_after_get_service_account_step(spec
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())(s)
@@ -438,6 +444,7 @@ proof fn lemma_from_after_get_secret_step_to_after_get_service_account_step(spec
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref()))
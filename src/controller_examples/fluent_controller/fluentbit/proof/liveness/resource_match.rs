@@ -38,6 +38,7 @@ pub proof fn lemma_from_after_get_resource_step_to_resource_matches(
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
@@ -82,6 +83,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
@@ -191,6 +193,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
                     &&& FBCluster::next()(s, s_prime)
                     &&& FBCluster::crash_disabled()(s)
                     &&& FBCluster::busy_disabled()(s)
+                    &&& FBCluster::stale_read_disabled()(s)
                     &&& FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())(s)
                     &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
                 };
@@ -199,6 +202,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
                     lift_action(FBCluster::next()),
                     lift_state(FBCluster::crash_disabled()),
                     lift_state(FBCluster::busy_disabled()),
+                    lift_state(FBCluster::stale_read_disabled()),
                     lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())),
                     lift_state(FBCluster::every_in_flight_msg_has_unique_id())
                 );
@@ -253,6 +257,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
@@ -354,6 +359,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
                     &&& FBCluster::next()(s, s_prime)
                     &&& FBCluster::crash_disabled()(s)
                     &&& FBCluster::busy_disabled()(s)
+                    &&& FBCluster::stale_read_disabled()(s)
                     &&& FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())(s)
                     &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
                 };
@@ -363,6 +369,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
                     lift_action(FBCluster::next()),
                     lift_state(FBCluster::crash_disabled()),
                     lift_state(FBCluster::busy_disabled()),
+                    lift_state(FBCluster::stale_read_disabled()),
                     lift_state(FBCluster::pending_req_of_key_is_unique_with_unique_id(fb.object_ref())),
                     lift_state(FBCluster::every_in_flight_msg_has_unique_id())
                 );
@@ -415,6 +422,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fb)))),
         spec.entails(always(lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fb)))),
@@ -441,6 +449,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fb)(s)
         &&& helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fb)(s)
@@ -450,6 +459,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fb)),
         lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fb))
@@ -572,6 +582,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())))),
@@ -594,6 +605,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
         &&& helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())(s)
@@ -605,6 +617,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
         lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())),
@@ -658,6 +671,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fb)))),
         spec.entails(always(lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fb)))),
@@ -672,6 +686,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fb)(s)
         &&& helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fb)(s)
@@ -682,6 +697,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fb)),
         lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fb)),
@@ -734,6 +750,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         spec.entails(tla_forall(|i| FBCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())))),
@@ -754,6 +771,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         &&& FBCluster::next()(s, s_prime)
         &&& FBCluster::crash_disabled()(s)
         &&& FBCluster::busy_disabled()(s)
+        &&& FBCluster::stale_read_disabled()(s)
         &&& FBCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCluster::each_object_in_etcd_is_well_formed()(s)
         &&& helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())(s)
@@ -769,6 +787,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         lift_action(FBCluster::next()),
         lift_state(FBCluster::crash_disabled()),
         lift_state(FBCluster::busy_disabled()),
+        lift_state(FBCluster::stale_read_disabled()),
         lift_state(FBCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCluster::each_object_in_etcd_is_well_formed()),
         lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fb.object_ref())),
@@ -222,6 +222,8 @@ pub open spec fn derived_invariants_since_beginning(fb: FluentBitView) -> TempPr
     .and(always(lift_state(helper_invariants::daemon_set_in_etcd_satisfies_unchangeable(fb))))
     .and(always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::object_in_etcd_satisfies_unchangeable(sub_resource, fb)))))
     .and(always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fb)))))
+    .and(always(lift_state(FBCluster::stale_read_disabled())))
+    .and(always(lift_state(FBCluster::no_orphan_or_foreground_deletion())))
 }
 
 pub proof fn derived_invariants_since_beginning_is_stable(fb: FluentBitView)
@@ -257,7 +259,9 @@ pub proof fn derived_invariants_since_beginning_is_stable(fb: FluentBitView)
         tla_forall(a_to_p_5),
         lift_state(helper_invariants::daemon_set_in_etcd_satisfies_unchangeable(fb)),
         tla_forall(a_to_p_6),
-        tla_forall(a_to_p_7)
+        tla_forall(a_to_p_7),
+        lift_state(FBCluster::stale_read_disabled()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -396,6 +400,8 @@ pub proof fn sm_spec_entails_all_invariants(fb: FluentBitView)
     FBCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     FBCluster::lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec);
     FBCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    FBCluster::lemma_always_stale_read_disabled(spec);
+    helper_invariants::lemma_always_no_orphan_or_foreground_deletion(spec);
     let a_to_p_1 = |sub_resource: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, fb));
     assert_by(spec.entails(always(tla_forall(a_to_p_1))), {
         assert forall |sub_resource: SubResource| spec.entails(always(#[trigger] a_to_p_1(sub_resource))) by {
@@ -474,7 +480,9 @@ pub proof fn sm_spec_entails_all_invariants(fb: FluentBitView)
         tla_forall(a_to_p_5),
         lift_state(helper_invariants::daemon_set_in_etcd_satisfies_unchangeable(fb)),
         tla_forall(a_to_p_6),
-        tla_forall(a_to_p_7)
+        tla_forall(a_to_p_7),
+        lift_state(FBCluster::stale_read_disabled()),
+        lift_state(FBCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -28,7 +28,7 @@ pub open spec fn sub_resource_state_matches(sub_resource: SubResource, fb: Fluen
 
 pub open spec fn at_fb_step(key: ObjectRef, step: FluentBitReconcileStep) -> StatePred<FBCluster>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == FluentBitView::kind()
 {
     |s: FBCluster| {
         &&& s.ongoing_reconciles().contains_key(key)
@@ -66,7 +66,7 @@ impl ResourceView for FluentBitView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("FluentBit"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
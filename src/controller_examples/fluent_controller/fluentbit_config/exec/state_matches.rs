@@ -0,0 +1,50 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::ObjectMap, state_matches::*};
+use crate::fluent_controller::fluentbit_config::exec::resource::*;
+use crate::fluent_controller::fluentbit_config::model::reconciler::FluentBitConfigMaker;
+use crate::fluent_controller::fluentbit_config::trusted::{exec_types::*, liveness_theorem, step::*};
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use vstd::{prelude::*, string::*};
+
+verus! {
+
+// resource_state_matches is the exec version of liveness_theorem::resource_state_matches instantiated with
+// FluentBitConfigMaker (see crate::zookeeper_controller::exec::state_matches for how it is used).
+pub fn resource_state_matches(sub_resource: SubResource, fbc: &FluentBitConfig, resources: &ObjectMap) -> (res: bool)
+    requires fbc@.well_formed(),
+    ensures res == liveness_theorem::resource_state_matches::<FluentBitConfigMaker>(sub_resource, fbc@, resources@),
+{
+    match sub_resource {
+        SubResource::Secret => {
+            let key = KubeObjectRef {
+                kind: KubeKind::SecretKind,
+                name: make_secret_name(fbc),
+                namespace: fbc.metadata().namespace().unwrap(),
+            };
+            secret_data_matches(&key, &make_secret(fbc), resources)
+        },
+    }
+}
+
+// current_state_matches checks liveness_theorem::current_state_matches on the objects stored in a cluster.
+pub fn current_state_matches(fbc: &FluentBitConfig, resources: &ObjectMap) -> (res: bool)
+    requires fbc@.well_formed(),
+    ensures res == forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<FluentBitConfigMaker>(sub_resource, fbc@, resources@),
+{
+    let res = resource_state_matches(SubResource::Secret, fbc, resources);
+    proof {
+        if res {
+            assert forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<FluentBitConfigMaker>(sub_resource, fbc@, resources@) by {
+                match sub_resource {
+                    SubResource::Secret => {},
+                }
+            }
+        }
+    }
+    res
+}
+
+}
@@ -3,6 +3,7 @@
 #![allow(unused_imports)]
 use crate::external_api::spec::*;
 use crate::fluent_controller::fluentbit_config::model::resource::*;
+use crate::fluent_controller::fluentbit::trusted::spec_types::FluentBitView;
 use crate::fluent_controller::fluentbit_config::trusted::{maker::*, spec_types::*, step::*};
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::reconciler::spec::{io::*, reconciler::*, resource_builder::*};
@@ -30,8 +31,15 @@ impl Reconciler<FluentBitConfigView, EmptyAPI> for FluentBitConfigReconciler {
         reconcile_error(state)
     }
 
+    // Expect the fluentbit controller, which runs in the same cluster (see fluent_controller::multi_cluster),
+    // to create the fluentbit objects and the objects it manages for them.
     open spec fn expect_from_user(obj: DynamicObjectView) -> bool {
-        false /* Don't expect anything from the user except the cr object */
+        ||| obj.kind == FluentBitView::kind()
+        ||| obj.kind == ServiceAccountView::kind()
+        ||| obj.kind == RoleView::kind()
+        ||| obj.kind == RoleBindingView::kind()
+        ||| obj.kind == ServiceView::kind()
+        ||| obj.kind == DaemonSetView::kind()
     }
 }
 
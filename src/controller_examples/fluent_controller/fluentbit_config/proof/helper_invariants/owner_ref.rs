@@ -65,13 +65,16 @@ pub proof fn lemma_always_object_in_every_resource_create_or_update_request_msg_
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::triggering_cr_has_lower_uid_than_uid_counter()(s)
         &&& FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
     };
     FBCCluster::lemma_always_triggering_cr_has_lower_uid_than_uid_counter(spec);
     FBCCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(FBCCluster::next()),
         lift_state(FBCCluster::triggering_cr_has_lower_uid_than_uid_counter()),
-        lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion())
     );
     let resource_key = get_request(sub_resource, fb).key;
     let create_valid = |msg: FBCMessage, s: FBCCluster| {
@@ -40,7 +40,7 @@ pub open spec fn the_object_in_schedule_satisfies_state_validation() -> StatePre
     |s: FBCCluster| {
         forall |key: ObjectRef|
         #[trigger] s.scheduled_reconciles().contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == FluentBitConfigView::kind()
         ==> s.scheduled_reconciles()[key].state_validation()
     }
 }
@@ -50,7 +50,7 @@ pub open spec fn cr_objects_in_etcd_satisfy_state_validation() -> StatePred<FBCC
     |s: FBCCluster| {
         forall |key: ObjectRef|
         #[trigger] s.resources().contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == FluentBitConfigView::kind()
         ==> FluentBitConfigView::unmarshal(s.resources()[key]).is_Ok()
             && FluentBitConfigView::unmarshal(s.resources()[key]).get_Ok_0().state_validation()
     }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use super::predicate::*;
+use crate::external_api::spec::EmptyTypeView;
 use crate::fluent_controller::fluentbit_config::{
     model::resource::*,
     proof::{predicate::*, resource::*},
@@ -11,11 +12,13 @@ use crate::kubernetes_api_objects::spec::{
     api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
 };
 use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::request_uses_propagation_policy,
     cluster::*,
     cluster_state_machine::Step,
     controller::types::{ControllerActionInput, ControllerStep},
     message::*,
 };
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
 use crate::temporal_logic::{defs::*, rules::*};
 use crate::vstd_ext::{multiset_lib, seq_lib, string_view::*};
 use vstd::{multiset::*, prelude::*, string::*};
@@ -148,7 +151,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
         spec.entails(always(lift_state(FBCCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -178,7 +183,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
         spec.entails(always(lift_state(FBCCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -210,7 +217,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)(s)
         &&& FBCCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
@@ -248,7 +257,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCCluster::next()), lift_state(FBCCluster::crash_disabled()), lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)),
         lift_state(FBCCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()),
@@ -275,7 +286,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
     ensures
@@ -297,7 +310,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, fbc))))),
@@ -315,7 +330,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)(s)
     };
@@ -337,7 +354,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCCluster::next()), lift_state(FBCCluster::crash_disabled()), lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc))
     );
@@ -357,7 +376,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
         spec.entails(always(lift_state(fbc_is_well_formed(fbc)))),
@@ -380,7 +401,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)))),
         spec.entails(always(lift_state(fbc_is_well_formed(fbc)))),
@@ -400,7 +423,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)(s)
         &&& fbc_is_well_formed(fbc)(s)
@@ -423,7 +448,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCCluster::next()), lift_state(FBCCluster::crash_disabled()), lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::the_object_in_reconcile_has_spec_and_uid_as(fbc)),
         lift_state(fbc_is_well_formed(fbc))
@@ -487,7 +514,7 @@ pub proof fn lemma_always_no_update_status_request_msg_in_flight(spec: TempPred<
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
@@ -501,6 +528,11 @@ pub proof fn lemma_always_no_update_status_request_msg_in_flight(spec: TempPred<
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
+                    Step::ServeReadFromCacheStep(_) => {
+                        assert(!msg.content.is_APIRequest());
+                        assert(!msg.content.is_update_status_request());
+                        assert(false);
+                    },
                     _ => {
                         assert(!s_prime.in_flight().contains(msg));
                         assert(false);
@@ -522,6 +554,42 @@ spec fn make_owner_references_with_name_and_uid(name: StringView, uid: Uid) -> O
     }
 }
 
+/// The fluent bit config controller never deletes any object, and the objects it creates or updates are built by make and update,
+/// which set no finalizers. So the controller does not use the orphan or foreground propagation policy.
+pub proof fn lemma_reconciler_does_not_use_propagation_policy()
+    ensures FBCCluster::reconciler_does_not_use_propagation_policy(),
+{
+    assert forall |fbc: FluentBitConfigView, resp_o: Option<ResponseView<EmptyTypeView>>, state: FluentBitConfigReconcileState|
+        #![trigger FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state)]
+        FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.is_Some()
+        && FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.get_Some_0().is_KRequest()
+    implies !request_uses_propagation_policy(FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                if update(sub_resource, fbc, state, get_resp.get_Ok_0()).is_Ok() {
+                    assert(update(sub_resource, fbc, state, get_resp.get_Ok_0()).get_Ok_0().metadata.finalizers.is_None());
+                }
+            } else if make(sub_resource, fbc, state).is_Ok() {
+                assert(make(sub_resource, fbc, state).get_Ok_0().metadata.finalizers.is_None());
+            }
+        }
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<FBCCluster>)
+    requires
+        spec.entails(lift_state(FBCCluster::init())),
+        spec.entails(always(lift_action(FBCCluster::next()))),
+    ensures spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
+{
+    lemma_reconciler_does_not_use_propagation_policy();
+    FBCCluster::lemma_always_no_orphan_or_foreground_deletion(spec);
+}
+
 pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(spec: TempPred<FBCCluster>, sub_resource: SubResource, fbc: FluentBitConfigView)
     requires
         spec.entails(lift_state(FBCCluster::init())),
@@ -592,14 +660,17 @@ proof fn lemma_always_resource_object_create_or_update_request_msg_has_one_contr
     let stronger_next = |s, s_prime| {
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
     };
     let key = fbc.object_ref();
     let resource_key = get_request(sub_resource, fbc).key;
     FBCCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(FBCCluster::next()),
-        lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion())
     );
     let create_msg_pred = |msg: FBCMessage| {
         resource_create_request_msg(resource_key)(msg)
@@ -662,6 +733,7 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
         !s.in_flight().contains(msg), s_prime.in_flight().contains(msg),
         FBCCluster::next_step(s, s_prime, step),
         FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        FBCCluster::no_orphan_or_foreground_deletion()(s),
     ensures
         resource_create_request_msg(get_request(sub_resource, fbc).key)(msg)
         ==> step.is_ControllerStep() && step.get_ControllerStep_0().1.get_Some_0() == fbc.object_ref()
@@ -682,6 +754,10 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
     let cr = s.ongoing_reconciles()[key].triggering_cr;
     let resource_key = get_request(sub_resource, fbc).key;
     if resource_create_request_msg(get_request(sub_resource, fbc).key)(msg) || resource_update_request_msg(get_request(sub_resource, fbc).key)(msg) {
+        if step.is_BuiltinControllersStep() {
+            // The builtin controllers do not orphan the dependents, so they only send delete and update status requests.
+            FBCCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, step.get_BuiltinControllersStep_0());
+        }
         assert(step.is_ControllerStep());
         assert(s.ongoing_reconciles().contains_key(cr_key));
         let local_step = s.ongoing_reconciles()[cr_key].local_state.reconcile_step;
@@ -711,6 +787,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight_fo
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(FBCCluster::next()))),
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCCluster::external_api_next().weak_fairness(i))),
@@ -731,6 +809,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(FBCCluster::next()))),
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCCluster::external_api_next().weak_fairness(i))),
@@ -749,6 +829,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     let stronger_next = |s: FBCCluster, s_prime: FBCCluster| {
         &&& FBCCluster::next()(s, s_prime)
         &&& desired_state_is(fbc)(s)
+        &&& FBCCluster::no_orphan_or_foreground_deletion()(s)
         &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, fbc)(s)
         &&& FBCCluster::each_object_in_etcd_is_well_formed()(s)
     };
@@ -761,7 +842,12 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
             } else {
                 let step = choose |step| FBCCluster::next_step(s, s_prime, step);
                 match step {
-                    Step::BuiltinControllersStep(_) => {
+                    Step::BuiltinControllersStep(input) => {
+                        FBCCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                        if input.0.is_Kubelet() {
+                            // The kubelet only deletes pods, and none of the sub resources is a pod.
+                            assert(resource_key.kind != Kind::PodKind);
+                        }
                         if s.resources().contains_key(resource_key) {
                             let owner_refs = s.resources()[resource_key].metadata.owner_references;
                             assert(owner_refs == Some(seq![fbc.controller_owner_ref()]));
@@ -775,7 +861,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
                             assert(!s_prime.ongoing_reconciles()[cr_key].pending_req_msg.get_Some_0().content.is_delete_request());
                         }
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         if msg.content.is_delete_request() {
                             assert(msg.content.get_delete_request().key.kind != resource_key.kind);
                         }
@@ -790,6 +876,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(FBCCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(FBCCluster::next()), lift_state(desired_state_is(fbc)),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion()),
         lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, fbc)),
         lift_state(FBCCluster::each_object_in_etcd_is_well_formed())
     );
@@ -804,6 +891,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(spec: TempPred<FBCCluster>, fbc: FluentBitConfigView)
     requires
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(FBCCluster::next()))),
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCCluster::builtin_controllers_next().weak_fairness(i))),
@@ -828,6 +916,7 @@ pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_po
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr(spec: TempPred<FBCCluster>, sub_resource: SubResource, fbc: FluentBitConfigView)
     requires
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(FBCCluster::next()))),
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| FBCCluster::builtin_controllers_next().weak_fairness(i))),
@@ -29,7 +29,7 @@ use vstd::{prelude::*, string::*};
 verus! {
 
 // We prove init /\ []next /\ []wf |= []desired_state_is(fbc) ~> []current_state_matches(fbc) holds for each fbc.
-proof fn liveness_proof_forall_fbc()
+pub proof fn liveness_proof_forall_fbc()
     ensures liveness_theorem::<FluentBitConfigMaker>(),
 {
     assert forall |fbc: FluentBitConfigView| #[trigger] cluster_spec().entails(liveness::<FluentBitConfigMaker>(fbc)) by {
@@ -30,6 +30,7 @@ pub proof fn lemma_from_after_get_resource_step_to_resource_matches(spec: TempPr
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::pending_req_of_key_is_unique_with_unique_id(fbc.object_ref())))),
@@ -66,6 +67,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
@@ -167,6 +169,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::pending_req_of_key_is_unique_with_unique_id(fbc.object_ref())))),
@@ -255,6 +258,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fbc)))),
         spec.entails(always(lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fbc)))),
@@ -281,6 +285,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fbc)(s)
         &&& helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fbc)(s)
@@ -290,6 +295,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         lift_action(FBCCluster::next()),
         lift_state(FBCCluster::crash_disabled()),
         lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, fbc)),
         lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, fbc))
@@ -408,6 +414,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())))),
@@ -430,6 +437,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::each_object_in_etcd_is_well_formed()(s)
         &&& helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())(s)
@@ -441,6 +449,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(spec: TempPr
         lift_action(FBCCluster::next()),
         lift_state(FBCCluster::crash_disabled()),
         lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::each_object_in_etcd_is_well_formed()),
         lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())),
@@ -489,6 +498,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fbc)))),
         spec.entails(always(lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fbc)))),
@@ -505,6 +515,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fbc)(s)
         &&& helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fbc)(s)
@@ -515,6 +526,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         lift_action(FBCCluster::next()),
         lift_state(FBCCluster::crash_disabled()),
         lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, fbc)),
         lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, fbc)),
@@ -562,6 +574,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         spec.entails(tla_forall(|i| FBCCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())))),
@@ -580,6 +593,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::each_object_in_etcd_is_well_formed()(s)
         &&& helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())(s)
@@ -593,6 +607,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         lift_action(FBCCluster::next()),
         lift_state(FBCCluster::crash_disabled()),
         lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::each_object_in_etcd_is_well_formed()),
         lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(fbc.object_ref())),
@@ -632,6 +647,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(
         spec.entails(tla_forall(|i| FBCCluster::controller_next().weak_fairness(i))),
         spec.entails(always(lift_state(FBCCluster::crash_disabled()))),
         spec.entails(always(lift_state(FBCCluster::busy_disabled()))),
+        spec.entails(always(lift_state(FBCCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(FBCCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(FBCCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(FBCCluster::pending_req_of_key_is_unique_with_unique_id(fbc.object_ref())))),
@@ -651,6 +667,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(
         &&& FBCCluster::next()(s, s_prime)
         &&& FBCCluster::crash_disabled()(s)
         &&& FBCCluster::busy_disabled()(s)
+        &&& FBCCluster::stale_read_disabled()(s)
         &&& FBCCluster::each_object_in_etcd_is_well_formed()(s)
         &&& FBCCluster::every_in_flight_msg_has_unique_id()(s)
         &&& FBCCluster::pending_req_of_key_is_unique_with_unique_id(fbc.object_ref())(s)
@@ -664,6 +681,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(
         lift_action(FBCCluster::next()),
         lift_state(FBCCluster::crash_disabled()),
         lift_state(FBCCluster::busy_disabled()),
+        lift_state(FBCCluster::stale_read_disabled()),
         lift_state(FBCCluster::each_object_in_etcd_is_well_formed()),
         lift_state(FBCCluster::every_in_flight_msg_has_unique_id()),
         lift_state(FBCCluster::pending_req_of_key_is_unique_with_unique_id(fbc.object_ref())),
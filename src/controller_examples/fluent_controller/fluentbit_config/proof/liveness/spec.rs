@@ -204,6 +204,8 @@ pub open spec fn derived_invariants_since_beginning(fbc: FluentBitConfigView) ->
     .and(always(tla_forall(|res: SubResource| lift_state(helper_invariants::response_at_after_get_resource_step_is_resource_get_response(res, fbc)))))
     .and(always(tla_forall(|res: SubResource| lift_state(FBCCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(get_request(res, fbc).key)))))
     .and(always(tla_forall(|res: SubResource| lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(res, fbc)))))
+    .and(always(lift_state(FBCCluster::stale_read_disabled())))
+    .and(always(lift_state(FBCCluster::no_orphan_or_foreground_deletion())))
 }
 
 pub proof fn derived_invariants_since_beginning_is_stable(fbc: FluentBitConfigView)
@@ -234,7 +236,9 @@ pub proof fn derived_invariants_since_beginning_is_stable(fbc: FluentBitConfigVi
         lift_state(FBCCluster::key_of_object_in_matched_ok_update_resp_message_is_same_as_key_of_pending_req(fbc.object_ref())),
         tla_forall(a_to_p_4),
         tla_forall(a_to_p_5),
-        tla_forall(a_to_p_6)
+        tla_forall(a_to_p_6),
+        lift_state(FBCCluster::stale_read_disabled()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -358,6 +362,8 @@ pub proof fn sm_spec_entails_all_invariants(fbc: FluentBitConfigView)
     FBCCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     FBCCluster::lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec);
     FBCCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    FBCCluster::lemma_always_stale_read_disabled(spec);
+    helper_invariants::lemma_always_no_orphan_or_foreground_deletion(spec);
     let a_to_p_1 = |sub_resource: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, fbc));
     assert_by(spec.entails(always(tla_forall(a_to_p_1))), {
         assert forall |sub_resource: SubResource| spec.entails(always(#[trigger] a_to_p_1(sub_resource))) by {
@@ -423,7 +429,9 @@ pub proof fn sm_spec_entails_all_invariants(fbc: FluentBitConfigView)
         lift_state(FBCCluster::key_of_object_in_matched_ok_update_resp_message_is_same_as_key_of_pending_req(fbc.object_ref())),
         tla_forall(a_to_p_4),
         tla_forall(a_to_p_5),
-        tla_forall(a_to_p_6)
+        tla_forall(a_to_p_6),
+        lift_state(FBCCluster::stale_read_disabled()),
+        lift_state(FBCCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -30,7 +30,7 @@ pub open spec fn sub_resource_state_matches(sub_resource: SubResource, fbc: Flue
 
 pub open spec fn at_fbc_step(key: ObjectRef, step: FluentBitConfigReconcileStep) -> StatePred<FBCCluster>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == FluentBitConfigView::kind()
 {
     |s: FBCCluster| {
         &&& s.ongoing_reconciles().contains_key(key)
@@ -65,7 +65,7 @@ impl ResourceView for FluentBitConfigView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("FluentBitConfig"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
@@ -2,3 +2,4 @@
 // SPDX-License-Identifier: MIT
 pub mod fluentbit;
 pub mod fluentbit_config;
+pub mod multi_cluster;
@@ -0,0 +1,185 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::fluent_controller::fluentbit::{
+    model::reconciler::{FluentBitMaker, FluentBitReconciler},
+    proof::{
+        helper_invariants::lemma_reconciler_does_not_use_propagation_policy as lemma_fb_reconciler_does_not_use_propagation_policy,
+        liveness::proof::liveness_proof_forall_fb,
+        resource as fb_resource,
+    },
+    trusted::{
+        liveness_theorem as fb_liveness_theorem,
+        spec_types::{FBCluster, FluentBitReconcileState, FluentBitView},
+        step as fb_step,
+    },
+};
+use crate::fluent_controller::fluentbit_config::{
+    model::reconciler::{FluentBitConfigMaker, FluentBitConfigReconciler},
+    proof::{
+        helper_invariants::lemma_reconciler_does_not_use_propagation_policy as lemma_fbc_reconciler_does_not_use_propagation_policy,
+        liveness::proof::liveness_proof_forall_fbc,
+        resource as fbc_resource,
+    },
+    trusted::{
+        liveness_theorem as fbc_liveness_theorem,
+        spec_types::{FBCCluster, FluentBitConfigReconcileState, FluentBitConfigView},
+        step as fbc_step,
+    },
+};
+use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, prelude::*, resource::*};
+use crate::kubernetes_cluster::{
+    proof::multi_cluster::*,
+    spec::{
+        builtin_controllers::garbage_collector::request_uses_propagation_policy,
+        client::state_machine::client_can_send_request,
+        multi_cluster::{state_machine::*, types::*},
+    },
+};
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
+use crate::temporal_logic::defs::*;
+use vstd::prelude::*;
+
+verus! {
+
+// The fluentbit controller and the fluentbit config controller are deployed together:
+// the users create the fluentbit and fluentbit config objects, the fluentbit config controller
+// creates the secret read by the fluentbit controller, and the fluentbit controller creates the objects
+// (e.g., the daemon set) that the fluentbit config controller expects from its users.
+// Here we show that both liveness theorems still hold when the two controllers run in the same cluster.
+
+pub open spec fn fb_id() -> ControllerId { 0 }
+
+pub open spec fn fbc_id() -> ControllerId { 1 }
+
+pub open spec fn fluent_model() -> MultiClusterModel<EmptyAPI> {
+    MultiClusterModel {
+        kubernetes_api: FBCluster::kubernetes_api(),
+        builtin_controllers: FBCluster::builtin_controllers(),
+        controllers: map![fb_id() => FBCluster::controller_model(), fbc_id() => FBCCluster::controller_model()],
+        external_api: FBCluster::external_api(),
+        network: FBCluster::network(),
+        client_expects: |obj: DynamicObjectView| obj.kind == FluentBitView::kind() || obj.kind == FluentBitConfigView::kind(),
+    }
+}
+
+pub open spec fn fluent_spec() -> TempPred<MultiCluster<EmptyAPI>> {
+    fluent_model().sm_spec()
+}
+
+pub proof fn fluent_liveness_theorem()
+    requires
+        custom_resource_validation_agrees_with::<FluentBitView>(),
+        custom_resource_validation_agrees_with::<FluentBitConfigView>(),
+    ensures
+        fluent_spec().entails(FBCluster::lift_to_multi_cluster(tla_forall(|fb: FluentBitView| fb_liveness_theorem::liveness::<FluentBitMaker>(fb)), fb_id())),
+        fluent_spec().entails(FBCCluster::lift_to_multi_cluster(tla_forall(|fbc: FluentBitConfigView| fbc_liveness_theorem::liveness::<FluentBitConfigMaker>(fbc)), fbc_id())),
+{
+    let model = fluent_model();
+    FBCluster::lemma_shared_components_do_not_depend_on_controller::<FluentBitConfigView, FluentBitConfigReconciler>();
+    assert(model.controllers.contains_key(fb_id()) && model.controllers[fb_id()] == FBCluster::controller_model());
+    assert(model.controllers.contains_key(fbc_id()) && model.controllers[fbc_id()] == FBCCluster::controller_model());
+    assert(FBCluster::is_hosted_by(model, fb_id()));
+    assert(FBCCluster::is_hosted_by(model, fbc_id()));
+
+    lemma_fbc_is_client_like_to_fb();
+    assert forall |other_id: ControllerId| #[trigger] model.controllers.contains_key(other_id) && other_id != fb_id()
+    implies FBCluster::is_client_like(model.controllers[other_id]) by {
+        assert(other_id == fbc_id());
+    }
+    assert(FBCluster::other_controllers_do_not_interfere(model, fb_id()));
+    lemma_fb_is_client_like_to_fbc();
+    assert forall |other_id: ControllerId| #[trigger] model.controllers.contains_key(other_id) && other_id != fbc_id()
+    implies FBCCluster::is_client_like(model.controllers[other_id]) by {
+        assert(other_id == fb_id());
+    }
+    assert(FBCCluster::other_controllers_do_not_interfere(model, fbc_id()));
+
+    liveness_proof_forall_fb();
+    FBCluster::lemma_lift_to_multi_cluster(
+        model, fb_id(), fluent_spec(), tla_forall(|fb: FluentBitView| fb_liveness_theorem::liveness::<FluentBitMaker>(fb))
+    );
+    liveness_proof_forall_fbc();
+    FBCCluster::lemma_lift_to_multi_cluster(
+        model, fbc_id(), fluent_spec(), tla_forall(|fbc: FluentBitConfigView| fbc_liveness_theorem::liveness::<FluentBitConfigMaker>(fbc))
+    );
+}
+
+// The fluentbit config controller only reads, creates and updates the secret of the fluentbit config,
+// which is expected from the user by the fluentbit controller.
+proof fn lemma_fbc_is_client_like_to_fb()
+    ensures FBCluster::is_client_like(FBCCluster::controller_model()),
+{
+    lemma_fbc_reconciler_does_not_use_propagation_policy();
+    assert forall |fbc: FluentBitConfigView, resp_o: Option<ResponseView<EmptyTypeView>>, state: FluentBitConfigReconcileState|
+        #![trigger FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state)]
+        FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.is_Some()
+    implies FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.get_Some_0().is_KRequest()
+        && client_can_send_request(|obj: DynamicObjectView| FBCluster::client_expects(obj), FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let req = FluentBitConfigReconciler::reconcile_core(fbc, resp_o, state).1.get_Some_0().get_KRequest_0();
+        assert(!request_uses_propagation_policy(req));
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == fbc_step::ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                if fbc_resource::update(sub_resource, fbc, state, get_resp.get_Ok_0()).is_Ok() {
+                    assert(fbc_resource::update(sub_resource, fbc, state, get_resp.get_Ok_0()).get_Ok_0().kind == SecretView::kind());
+                }
+            } else if fbc_resource::make(sub_resource, fbc, state).is_Ok() {
+                assert(fbc_resource::make(sub_resource, fbc, state).get_Ok_0().kind == SecretView::kind());
+            }
+        }
+    }
+    FBCluster::lemma_controller_model_is_client_like::<FluentBitConfigView, FluentBitConfigReconciler>();
+}
+
+// The fluentbit controller only reads the secret, and reads, creates and updates the service account, role,
+// role binding, service and daemon set of the fluentbit, which are expected from the user by the fluentbit config controller.
+proof fn lemma_fb_is_client_like_to_fbc()
+    ensures FBCCluster::is_client_like(FBCluster::controller_model()),
+{
+    lemma_fb_reconciler_does_not_use_propagation_policy();
+    assert forall |fb: FluentBitView, resp_o: Option<ResponseView<EmptyTypeView>>, state: FluentBitReconcileState|
+        #![trigger FluentBitReconciler::reconcile_core(fb, resp_o, state)]
+        FluentBitReconciler::reconcile_core(fb, resp_o, state).1.is_Some()
+    implies FluentBitReconciler::reconcile_core(fb, resp_o, state).1.get_Some_0().is_KRequest()
+        && client_can_send_request(|obj: DynamicObjectView| FBCCluster::client_expects(obj), FluentBitReconciler::reconcile_core(fb, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let req = FluentBitReconciler::reconcile_core(fb, resp_o, state).1.get_Some_0().get_KRequest_0();
+        assert(!request_uses_propagation_policy(req));
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == fb_step::ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                let updated = fb_resource::update(sub_resource, fb, state, get_resp.get_Ok_0());
+                if updated.is_Ok() {
+                    match sub_resource {
+                        fb_step::SubResource::ServiceAccount => { assert(updated.get_Ok_0().kind == ServiceAccountView::kind()); },
+                        fb_step::SubResource::Role => { assert(updated.get_Ok_0().kind == RoleView::kind()); },
+                        fb_step::SubResource::RoleBinding => { assert(updated.get_Ok_0().kind == RoleBindingView::kind()); },
+                        fb_step::SubResource::Service => { assert(updated.get_Ok_0().kind == ServiceView::kind()); },
+                        fb_step::SubResource::DaemonSet => { assert(updated.get_Ok_0().kind == DaemonSetView::kind()); },
+                    }
+                }
+            } else {
+                let made = fb_resource::make(sub_resource, fb, state);
+                if made.is_Ok() {
+                    match sub_resource {
+                        fb_step::SubResource::ServiceAccount => { assert(made.get_Ok_0().kind == ServiceAccountView::kind()); },
+                        fb_step::SubResource::Role => { assert(made.get_Ok_0().kind == RoleView::kind()); },
+                        fb_step::SubResource::RoleBinding => { assert(made.get_Ok_0().kind == RoleBindingView::kind()); },
+                        fb_step::SubResource::Service => { assert(made.get_Ok_0().kind == ServiceView::kind()); },
+                        fb_step::SubResource::DaemonSet => { assert(made.get_Ok_0().kind == DaemonSetView::kind()); },
+                    }
+                }
+            }
+        }
+    }
+    FBCCluster::lemma_controller_model_is_client_like::<FluentBitView, FluentBitReconciler>();
+}
+
+}
@@ -1,6 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
+use crate::executable_model::object_map::ObjectMap;
 use crate::external_api::exec::*;
 use crate::kubernetes_api_objects::exec::resource::ResourceWrapper;
 use crate::kubernetes_api_objects::exec::{
@@ -8,6 +9,7 @@ use crate::kubernetes_api_objects::exec::{
     volume::*,
 };
 use crate::rabbitmq_controller::exec::resource::*;
+use crate::rabbitmq_controller::exec::state_matches;
 use crate::rabbitmq_controller::model::reconciler as model_reconciler;
 use crate::rabbitmq_controller::model::resource as model_resource;
 use crate::rabbitmq_controller::trusted::exec_types::*;
@@ -15,6 +17,9 @@ use crate::rabbitmq_controller::trusted::spec_types;
 use crate::rabbitmq_controller::trusted::step::*;
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
 use crate::reconciler::spec::resource_builder::ResourceBuilder as SpecResourceBuilder;
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use crate::shim_layer::requeue::RequeueHintReconciler;
 use crate::vstd_ext::{string_map::StringMap, string_view::*};
 use vstd::prelude::*;
 use vstd::seq_lib::*;
@@ -46,6 +51,18 @@ impl Reconciler for RabbitmqReconciler {
     fn reconcile_error(state: &RabbitmqReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Service::api_resource());
+        resources.push(Secret::api_resource());
+        resources.push(ConfigMap::api_resource());
+        resources.push(ServiceAccount::api_resource());
+        resources.push(Role::api_resource());
+        resources.push(RoleBinding::api_resource());
+        resources.push(StatefulSet::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: RabbitmqReconcileState)
@@ -226,3 +243,14 @@ pub fn reconcile_helper<
 }
 
 }
+
+// There is no executable model of the spec reconcile_core yet, so the refinement monitor skips this reconciler.
+impl ExecutableModelReconciler for RabbitmqReconciler {}
+
+impl RequeueHintReconciler for RabbitmqReconciler {}
+
+impl StateMatchesReconciler for RabbitmqReconciler {
+    fn state_matches(rabbitmq: &RabbitmqCluster, snapshot: &ObjectMap) -> Option<bool> {
+        Some(state_matches::current_state_matches(rabbitmq, snapshot))
+    }
+}
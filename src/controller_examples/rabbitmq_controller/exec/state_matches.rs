@@ -0,0 +1,133 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::ObjectMap, state_matches::*};
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::rabbitmq_controller::exec::resource::*;
+use crate::rabbitmq_controller::model::reconciler::RabbitmqMaker;
+use crate::rabbitmq_controller::trusted::{exec_types::*, liveness_theorem, step::*};
+use vstd::prelude::*;
+use vstd::string::*;
+
+verus! {
+
+// resource_state_matches is the exec version of liveness_theorem::resource_state_matches instantiated with
+// RabbitmqMaker (see crate::zookeeper_controller::exec::state_matches for how it is used).
+pub fn resource_state_matches(sub_resource: SubResource, rabbitmq: &RabbitmqCluster, resources: &ObjectMap) -> (res: bool)
+    requires rabbitmq@.well_formed(),
+    ensures res == liveness_theorem::resource_state_matches::<RabbitmqMaker>(sub_resource, rabbitmq@, resources@),
+{
+    match sub_resource {
+        SubResource::HeadlessService => {
+            let key = make_key(KubeKind::ServiceKind, make_headless_service_name(rabbitmq), rabbitmq);
+            let made = make_headless_service(rabbitmq);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::Service => {
+            let key = make_key(KubeKind::ServiceKind, make_main_service_name(rabbitmq), rabbitmq);
+            let made = make_main_service(rabbitmq);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::ErlangCookieSecret => {
+            let key = make_key(KubeKind::SecretKind, make_erlang_secret_name(rabbitmq), rabbitmq);
+            let made = make_erlang_secret(rabbitmq);
+            secret_data_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::DefaultUserSecret => {
+            let key = make_key(KubeKind::SecretKind, make_default_user_secret_name(rabbitmq), rabbitmq);
+            let made = make_default_user_secret(rabbitmq);
+            secret_data_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::PluginsConfigMap => {
+            let key = make_key(KubeKind::ConfigMapKind, make_plugins_config_map_name(rabbitmq), rabbitmq);
+            let made = make_plugins_config_map(rabbitmq);
+            config_map_data_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::ServerConfigMap => {
+            let key = make_key(KubeKind::ConfigMapKind, make_server_config_map_name(rabbitmq), rabbitmq);
+            let made = make_server_config_map(rabbitmq);
+            config_map_data_matches(&key, &made, resources)
+            && object_spec_matches(&key, &made.clone().marshal(), resources)
+            && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::ServiceAccount => {
+            let key = make_key(KubeKind::ServiceAccountKind, make_service_account_name(rabbitmq), rabbitmq);
+            let made = make_service_account(rabbitmq);
+            service_account_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::Role => {
+            let key = make_key(KubeKind::RoleKind, make_role_name(rabbitmq), rabbitmq);
+            let made = make_role(rabbitmq);
+            role_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::RoleBinding => {
+            let key = make_key(KubeKind::RoleBindingKind, make_role_binding_name(rabbitmq), rabbitmq);
+            let made = make_role_binding(rabbitmq);
+            role_binding_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::StatefulSet => {
+            let key = make_key(KubeKind::StatefulSetKind, make_stateful_set_name(rabbitmq), rabbitmq);
+            let cm_key = make_key(KubeKind::ConfigMapKind, make_server_config_map_name(rabbitmq), rabbitmq);
+            let cm_obj = resources.get(&cm_key);
+            if cm_obj.is_none() {
+                return false;
+            }
+            let rv = cm_obj.unwrap().metadata().resource_version();
+            if rv.is_none() {
+                return false;
+            }
+            let made = make_stateful_set(rabbitmq, &rv.unwrap());
+            stateful_set_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+    }
+}
+
+// current_state_matches checks liveness_theorem::current_state_matches on the objects stored in a cluster.
+pub fn current_state_matches(rabbitmq: &RabbitmqCluster, resources: &ObjectMap) -> (res: bool)
+    requires rabbitmq@.well_formed(),
+    ensures res == forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<RabbitmqMaker>(sub_resource, rabbitmq@, resources@),
+{
+    let res = resource_state_matches(SubResource::HeadlessService, rabbitmq, resources)
+        && resource_state_matches(SubResource::Service, rabbitmq, resources)
+        && resource_state_matches(SubResource::ErlangCookieSecret, rabbitmq, resources)
+        && resource_state_matches(SubResource::DefaultUserSecret, rabbitmq, resources)
+        && resource_state_matches(SubResource::PluginsConfigMap, rabbitmq, resources)
+        && resource_state_matches(SubResource::ServerConfigMap, rabbitmq, resources)
+        && resource_state_matches(SubResource::ServiceAccount, rabbitmq, resources)
+        && resource_state_matches(SubResource::Role, rabbitmq, resources)
+        && resource_state_matches(SubResource::RoleBinding, rabbitmq, resources)
+        && resource_state_matches(SubResource::StatefulSet, rabbitmq, resources);
+    proof {
+        if res {
+            assert forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<RabbitmqMaker>(sub_resource, rabbitmq@, resources@) by {
+                match sub_resource {
+                    SubResource::HeadlessService => {},
+                    SubResource::Service => {},
+                    SubResource::ErlangCookieSecret => {},
+                    SubResource::DefaultUserSecret => {},
+                    SubResource::PluginsConfigMap => {},
+                    SubResource::ServerConfigMap => {},
+                    SubResource::ServiceAccount => {},
+                    SubResource::Role => {},
+                    SubResource::RoleBinding => {},
+                    SubResource::StatefulSet => {},
+                }
+            }
+        }
+    }
+    res
+}
+
+fn make_key(kind: KubeKind, name: String, rabbitmq: &RabbitmqCluster) -> (key: KubeObjectRef)
+    requires rabbitmq@.well_formed(),
+    ensures key@ == (ObjectRef { kind: kind@, name: name@, namespace: rabbitmq@.metadata.namespace.get_Some_0() }),
+{
+    KubeObjectRef {
+        kind: kind,
+        name: name,
+        namespace: rabbitmq.metadata().namespace().unwrap(),
+    }
+}
+
+}
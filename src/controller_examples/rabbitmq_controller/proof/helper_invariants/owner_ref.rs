@@ -67,13 +67,16 @@ pub proof fn lemma_always_object_in_every_resource_create_or_update_request_msg_
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::triggering_cr_has_lower_uid_than_uid_counter()(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
     };
     RMQCluster::lemma_always_triggering_cr_has_lower_uid_than_uid_counter(spec);
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(RMQCluster::next()),
         lift_state(RMQCluster::triggering_cr_has_lower_uid_than_uid_counter()),
-        lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion())
     );
     let resource_key = get_request(sub_resource, rabbitmq).key;
     let create_valid = |msg: RMQMessage, s: RMQCluster| {
@@ -102,6 +105,7 @@ proof fn object_in_every_resource_create_or_update_request_msg_only_has_valid_ow
         RMQCluster::next()(s, s_prime),
         RMQCluster::triggering_cr_has_lower_uid_than_uid_counter()(s),
         RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        RMQCluster::no_orphan_or_foreground_deletion()(s),
     ensures
         resource_create_request_msg(get_request(sub_resource, rabbitmq).key)(msg) ==> owner_references_is_valid(msg.content.get_create_request().obj, s_prime),
         resource_update_request_msg(get_request(sub_resource, rabbitmq).key)(msg) ==> owner_references_is_valid(msg.content.get_update_request().obj, s_prime),
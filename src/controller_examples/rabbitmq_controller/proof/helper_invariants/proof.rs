@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use super::predicate::*;
+use crate::external_api::spec::EmptyTypeView;
 use crate::kubernetes_api_objects::spec::{
     api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
 };
 use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::request_uses_propagation_policy,
     cluster::*,
     cluster_state_machine::Step,
     controller::types::{ControllerActionInput, ControllerStep},
@@ -18,6 +20,7 @@ use crate::rabbitmq_controller::{
     },
     trusted::{liveness_theorem::desired_state_is, spec_types::*, step::*},
 };
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
 use crate::temporal_logic::{defs::*, rules::*};
 use crate::vstd_ext::{multiset_lib, seq_lib, string_view::*};
 use vstd::{multiset::*, prelude::*, string::*};
@@ -626,7 +629,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
         spec.entails(always(lift_state(RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -660,7 +665,9 @@ proof fn lemma_eventually_always_every_resource_update_request_implies_at_after_
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
         spec.entails(always(lift_state(RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -694,7 +701,9 @@ proof fn lemma_eventually_always_every_resource_update_request_implies_at_after_
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::crash_disabled()(s)
         &&& RMQCluster::busy_disabled()(s)
+        &&& RMQCluster::stale_read_disabled()(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::every_in_flight_msg_has_unique_id()(s)
         &&& RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)(s)
         &&& RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
@@ -739,7 +748,9 @@ proof fn lemma_eventually_always_every_resource_update_request_implies_at_after_
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(RMQCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(RMQCluster::next()), lift_state(RMQCluster::crash_disabled()), lift_state(RMQCluster::busy_disabled()),
+        lift_state(RMQCluster::stale_read_disabled()),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::every_in_flight_msg_has_unique_id()),
         lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)),
         lift_state(RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()),
@@ -770,7 +781,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
     ensures spec.entails(true_pred().leads_to(always(tla_forall(|sub_resource: SubResource| lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, rabbitmq)))))),
@@ -792,7 +805,9 @@ proof fn lemma_eventually_always_object_in_every_resource_update_request_only_ha
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, rabbitmq))))),
@@ -810,7 +825,9 @@ proof fn lemma_eventually_always_object_in_every_resource_update_request_only_ha
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::crash_disabled()(s)
         &&& RMQCluster::busy_disabled()(s)
+        &&& RMQCluster::stale_read_disabled()(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::every_in_flight_msg_has_unique_id()(s)
         &&& RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)(s)
     };
@@ -832,7 +849,9 @@ proof fn lemma_eventually_always_object_in_every_resource_update_request_only_ha
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(RMQCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(RMQCluster::next()), lift_state(RMQCluster::crash_disabled()), lift_state(RMQCluster::busy_disabled()),
+        lift_state(RMQCluster::stale_read_disabled()),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::every_in_flight_msg_has_unique_id()),
         lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq))
     );
@@ -854,6 +873,7 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
@@ -877,6 +897,7 @@ proof fn lemma_eventually_always_every_resource_create_request_implies_at_after_
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::crash_disabled()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)))),
@@ -897,6 +918,7 @@ proof fn lemma_eventually_always_every_resource_create_request_implies_at_after_
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::crash_disabled()(s)
         &&& RMQCluster::busy_disabled()(s)
+        &&& RMQCluster::stale_read_disabled()(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
         &&& RMQCluster::every_in_flight_msg_has_unique_id()(s)
         &&& RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)(s)
@@ -920,6 +942,7 @@ proof fn lemma_eventually_always_every_resource_create_request_implies_at_after_
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(RMQCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(RMQCluster::next()), lift_state(RMQCluster::crash_disabled()), lift_state(RMQCluster::busy_disabled()),
+        lift_state(RMQCluster::stale_read_disabled()),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
         lift_state(RMQCluster::every_in_flight_msg_has_unique_id()),
         lift_state(RMQCluster::the_object_in_reconcile_has_spec_and_uid_as(rabbitmq)),
@@ -1052,7 +1075,7 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
@@ -1065,6 +1088,11 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
+                    Step::ServeReadFromCacheStep(_) => {
+                        assert(!msg.content.is_APIRequest());
+                        assert(!msg.content.is_update_status_request());
+                        assert(false);
+                    },
                     _ => {
                         assert(!s_prime.in_flight().contains(msg));
                         assert(false);
@@ -1086,6 +1114,42 @@ spec fn make_owner_references_with_name_and_uid(name: StringView, uid: Uid) -> O
     }
 }
 
+/// The rabbitmq controller never deletes any object, and the objects it creates or updates are built by make and update,
+/// which set no finalizers. So the controller does not use the orphan or foreground propagation policy.
+pub proof fn lemma_reconciler_does_not_use_propagation_policy()
+    ensures RMQCluster::reconciler_does_not_use_propagation_policy(),
+{
+    assert forall |rabbitmq: RabbitmqClusterView, resp_o: Option<ResponseView<EmptyTypeView>>, state: RabbitmqReconcileState|
+        #![trigger RabbitmqReconciler::reconcile_core(rabbitmq, resp_o, state)]
+        RabbitmqReconciler::reconcile_core(rabbitmq, resp_o, state).1.is_Some()
+        && RabbitmqReconciler::reconcile_core(rabbitmq, resp_o, state).1.get_Some_0().is_KRequest()
+    implies !request_uses_propagation_policy(RabbitmqReconciler::reconcile_core(rabbitmq, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                if update(sub_resource, rabbitmq, state, get_resp.get_Ok_0()).is_Ok() {
+                    assert(update(sub_resource, rabbitmq, state, get_resp.get_Ok_0()).get_Ok_0().metadata.finalizers.is_None());
+                }
+            } else if make(sub_resource, rabbitmq, state).is_Ok() {
+                assert(make(sub_resource, rabbitmq, state).get_Ok_0().metadata.finalizers.is_None());
+            }
+        }
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<RMQCluster>)
+    requires
+        spec.entails(lift_state(RMQCluster::init())),
+        spec.entails(always(lift_action(RMQCluster::next()))),
+    ensures spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
+{
+    lemma_reconciler_does_not_use_propagation_policy();
+    RMQCluster::lemma_always_no_orphan_or_foreground_deletion(spec);
+}
+
 pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(spec: TempPred<RMQCluster>, sub_resource: SubResource, rabbitmq: RabbitmqClusterView)
     requires
         spec.entails(lift_state(RMQCluster::init())),
@@ -1156,14 +1220,17 @@ proof fn lemma_always_resource_object_create_or_update_request_msg_has_one_contr
     let stronger_next = |s, s_prime| {
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
     };
     let key = rabbitmq.object_ref();
     let resource_key = get_request(sub_resource, rabbitmq).key;
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(RMQCluster::next()),
-        lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion())
     );
     let create_msg_pred = |msg: RMQMessage| {
         resource_create_request_msg(resource_key)(msg)
@@ -1228,6 +1295,7 @@ pub proof fn lemma_resource_update_request_msg_implies_key_in_reconcile_equals(s
         !s.in_flight().contains(msg), s_prime.in_flight().contains(msg),
         RMQCluster::next_step(s, s_prime, step),
         RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        RMQCluster::no_orphan_or_foreground_deletion()(s),
         resource_update_request_msg(get_request(sub_resource, rabbitmq).key)(msg),
     ensures
         step.is_ControllerStep(),
@@ -1248,6 +1316,10 @@ pub proof fn lemma_resource_update_request_msg_implies_key_in_reconcile_equals(s
     let key = rabbitmq.object_ref();
     let cr = s.ongoing_reconciles()[key].triggering_cr;
     let resource_key = get_request(sub_resource, rabbitmq).key;
+    if step.is_BuiltinControllersStep() {
+        // The builtin controllers do not orphan the dependents, so they only send delete and update status requests.
+        RMQCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, step.get_BuiltinControllersStep_0());
+    }
     assert(step.is_ControllerStep());
     assert(s.ongoing_reconciles().contains_key(cr_key));
     let local_step = s.ongoing_reconciles()[cr_key].local_state.reconcile_step;
@@ -1486,6 +1558,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight_fo
         spec.entails(always(lift_state(RMQCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(RMQCluster::next()))),
         spec.entails(tla_forall(|i| RMQCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| RMQCluster::external_api_next().weak_fairness(i))),
@@ -1525,6 +1599,8 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
         spec.entails(always(lift_state(RMQCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(RMQCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(RMQCluster::next()))),
         spec.entails(tla_forall(|i| RMQCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| RMQCluster::external_api_next().weak_fairness(i))),
@@ -1543,6 +1619,7 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
     let stronger_next = |s: RMQCluster, s_prime: RMQCluster| {
         &&& RMQCluster::next()(s, s_prime)
         &&& RMQCluster::desired_state_is(rabbitmq)(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)(s)
         &&& resource_well_formed(s)
     };
@@ -1556,7 +1633,12 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
             } else {
                 let step = choose |step| RMQCluster::next_step(s, s_prime, step);
                 match step {
-                    Step::BuiltinControllersStep(_) => {
+                    Step::BuiltinControllersStep(input) => {
+                        RMQCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                        if input.0.is_Kubelet() {
+                            // The kubelet only deletes pods, and none of the sub resources is a pod.
+                            assert(resource_key.kind != Kind::PodKind);
+                        }
                         if s.resources().contains_key(resource_key) {
                             assert(RMQCluster::etcd_object_is_well_formed(resource_key)(s));
                             assert(RMQCluster::etcd_object_is_well_formed(key)(s));
@@ -1573,7 +1655,7 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
                         }
                         assert(requirements(msg, s_prime));
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         if msg.content.is_delete_request() {
                             assert(msg.content.get_delete_request().key.kind != resource_key.kind);
                         }
@@ -1589,6 +1671,7 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(RMQCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(RMQCluster::next()), lift_state(RMQCluster::desired_state_is(rabbitmq)),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, rabbitmq)),
         lift_state(resource_well_formed)
     );
@@ -1603,6 +1686,7 @@ proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(spec:
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(spec: TempPred<RMQCluster>, rabbitmq: RabbitmqClusterView)
     requires
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(RMQCluster::next()))),
         spec.entails(tla_forall(|i| RMQCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| RMQCluster::builtin_controllers_next().weak_fairness(i))),
@@ -1627,6 +1711,7 @@ pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_po
 proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr(spec: TempPred<RMQCluster>, sub_resource: SubResource, rabbitmq: RabbitmqClusterView)
     requires
         spec.entails(always(lift_state(RMQCluster::busy_disabled()))),
+        spec.entails(always(lift_state(RMQCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(RMQCluster::next()))),
         spec.entails(tla_forall(|i| RMQCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| RMQCluster::builtin_controllers_next().weak_fairness(i))),
@@ -1850,7 +1935,7 @@ pub proof fn lemma_always_no_create_resource_request_msg_without_name_in_flight(
                         assert(!resource_create_request_msg_without_name(resource_key.kind, resource_key.namespace)(msg));
                     }
                 },
-                // Step::ClientStep() => {
+                // Step::ClientStep(_) => {
                 //     if !s.in_flight().contains(msg) && s_prime.in_flight().contains(msg) {
                 //         assert(!resource_create_request_msg_without_name(resource_key.kind, resource_key.namespace)(msg));
                 //     }
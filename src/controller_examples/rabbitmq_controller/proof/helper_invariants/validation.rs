@@ -157,6 +157,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
         &&& RMQCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, rabbitmq)(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)(s)
         &&& object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, rabbitmq)(s)
     };
@@ -164,6 +165,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, SubResource::StatefulSet, rabbitmq);
     always_to_always_later(spec, lift_state(RMQCluster::each_object_in_etcd_is_well_formed()));
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     RMQCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, sts_key);
     lemma_always_object_in_resource_update_request_msg_has_smaller_rv_than_etcd(spec, SubResource::StatefulSet, rabbitmq);
     combine_spec_entails_always_n!(
@@ -172,6 +174,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
         later(lift_state(RMQCluster::each_object_in_etcd_is_well_formed())),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, rabbitmq)),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)),
         lift_state(object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, rabbitmq))
     );
@@ -226,6 +229,7 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
         &&& resource_well_formed(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(sub_resource, rabbitmq)(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
     };
     RMQCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
@@ -233,12 +237,14 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
     always_weaken_temp(spec, lift_state(RMQCluster::each_object_in_etcd_is_well_formed()), lift_state(resource_well_formed));
     always_to_always_later(spec, lift_state(resource_well_formed));
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     RMQCluster::lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(RMQCluster::next()),
         lift_state(resource_well_formed), later(lift_state(resource_well_formed)),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(sub_resource, rabbitmq)),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::object_in_ok_get_response_has_smaller_rv_than_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
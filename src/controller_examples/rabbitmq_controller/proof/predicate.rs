@@ -29,7 +29,7 @@ pub open spec fn sub_resource_state_matches(sub_resource: SubResource, rabbitmq:
 
 pub open spec fn at_rabbitmq_step(key: ObjectRef, step: RabbitmqReconcileStep) -> StatePred<RMQCluster>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == RabbitmqClusterView::kind()
 {
     |s: RMQCluster| {
         &&& s.ongoing_reconciles().contains_key(key)
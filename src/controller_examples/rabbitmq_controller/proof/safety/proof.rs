@@ -112,6 +112,7 @@ proof fn lemma_always_replicas_of_stateful_set_update_request_msg_is_no_smaller_
         &&& replicas_of_etcd_stateful_set_satisfies_order(rabbitmq)(s_prime)
         &&& object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, rabbitmq)(s)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)(s)
         &&& response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, rabbitmq)(s)
     };
@@ -121,6 +122,7 @@ proof fn lemma_always_replicas_of_stateful_set_update_request_msg_is_no_smaller_
     always_to_always_later(spec, lift_state(replicas_of_etcd_stateful_set_satisfies_order(rabbitmq)));
     lemma_always_object_in_resource_update_request_msg_has_smaller_rv_than_etcd(spec, SubResource::StatefulSet, rabbitmq);
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     RMQCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, sts_key);
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, SubResource::StatefulSet, rabbitmq);
     combine_spec_entails_always_n!(
@@ -129,6 +131,7 @@ proof fn lemma_always_replicas_of_stateful_set_update_request_msg_is_no_smaller_
         later(lift_state(replicas_of_etcd_stateful_set_satisfies_order(rabbitmq))),
         lift_state(object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, rabbitmq)),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, rabbitmq))
     );
@@ -184,7 +187,7 @@ spec fn replicas_satisfies_order(obj: DynamicObjectView, rabbitmq: RabbitmqClust
 
 spec fn replicas_of_rabbitmq(obj: DynamicObjectView) -> int
     recommends
-        obj.kind.is_CustomResourceKind(),
+        obj.kind == RabbitmqClusterView::kind(),
 {
     RabbitmqClusterView::unmarshal(obj).get_Ok_0().spec.replicas
 }
@@ -284,12 +287,14 @@ proof fn lemma_always_replicas_of_stateful_set_create_or_update_request_msg_sati
         &&& RMQCluster::each_object_in_etcd_is_well_formed()(s)
         &&& RMQCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& RMQCluster::no_orphan_or_foreground_deletion()(s)
         &&& RMQCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(rabbitmq)(s)
         &&& object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(SubResource::StatefulSet, rabbitmq)(s)
     };
     RMQCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     always_to_always_later(spec, lift_state(RMQCluster::each_object_in_etcd_is_well_formed()));
     RMQCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     RMQCluster::lemma_always_transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(spec, rabbitmq);
     lemma_always_object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(spec, SubResource::StatefulSet, rabbitmq);
     combine_spec_entails_always_n!(
@@ -298,6 +303,7 @@ proof fn lemma_always_replicas_of_stateful_set_create_or_update_request_msg_sati
         lift_state(RMQCluster::each_object_in_etcd_is_well_formed()),
         later(lift_state(RMQCluster::each_object_in_etcd_is_well_formed())),
         lift_state(RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(RMQCluster::no_orphan_or_foreground_deletion()),
         lift_state(RMQCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(rabbitmq)),
         lift_state(object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(SubResource::StatefulSet, rabbitmq))
     );
@@ -375,6 +381,7 @@ proof fn replicas_of_stateful_set_update_request_msg_satisfies_order_induction(
         RMQCluster::each_object_in_etcd_is_well_formed()(s),
         RMQCluster::each_object_in_etcd_is_well_formed()(s_prime),
         RMQCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        RMQCluster::no_orphan_or_foreground_deletion()(s),
         RMQCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(rabbitmq)(s),
         object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(SubResource::StatefulSet, rabbitmq)(s),
         // no_create_resource_request_msg_without_name_in_flight(SubResource::StatefulSet, rabbitmq)(s),
@@ -0,0 +1,138 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, config_map::ConfigMap, dynamic::*, resource::*,
+    secret::Secret, service::Service, stateful_set::StatefulSet,
+};
+use crate::kubernetes_api_objects::spec::prelude::PatchType;
+use crate::rabbitmq_controller::exec::{reconciler::RabbitmqReconciler, state_matches};
+use crate::rabbitmq_controller::trusted::exec_types;
+use crate::shim_layer::reconcile_trace::*;
+use crate::shim_layer::refinement_monitor::*;
+use crate::shim_layer::simulation::*;
+use deps_hack::{default_persistence, RabbitmqCluster, RabbitmqClusterSpec};
+use std::collections::BTreeMap;
+
+fn make_rabbitmq_cluster(replicas: i32) -> RabbitmqCluster {
+    let mut rabbitmq = RabbitmqCluster::new(
+        "test",
+        RabbitmqClusterSpec {
+            replicas: replicas,
+            image: "rabbitmq:3.11.10-management".to_string(),
+            persistence: default_persistence(),
+            rabbitmq_config: None,
+            affinity: None,
+            tolerations: None,
+            labels: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+            resources: None,
+            pod_management_policy: "Parallel".to_string(),
+            persistent_volume_claim_retention_policy: None,
+        },
+    );
+    rabbitmq.metadata.namespace = Some("default".to_string());
+    rabbitmq
+}
+
+fn scale(cluster: &mut SimulatedCluster<RabbitmqReconciler>, replicas: i32) {
+    let patch = DynamicObject::from_kube(
+        deps_hack::serde_json::from_value(deps_hack::serde_json::json!({
+            "apiVersion": "anvil.dev/v1",
+            "kind": "RabbitmqCluster",
+            "metadata": {},
+            "spec": { "replicas": replicas },
+        }))
+        .unwrap(),
+    );
+    let resp = cluster.handle_request(KubeAPIRequest::PatchRequest(KubePatchRequest {
+        api_resource: exec_types::RabbitmqCluster::api_resource(),
+        name: "test".to_string(),
+        namespace: "default".to_string(),
+        patch: patch,
+        patch_type: PatchType::MergePatch,
+    }));
+    assert!(resp.into_patch_response().res.is_ok());
+}
+
+// current_state_matches runs the exec checker of the liveness theorem on the state of the simulated cluster
+fn current_state_matches(cluster: &SimulatedCluster<RabbitmqReconciler>) -> bool {
+    let obj = cluster
+        .get(exec_types::RabbitmqCluster::api_resource(), "default", "test")
+        .unwrap();
+    let rabbitmq = match exec_types::RabbitmqCluster::unmarshal(obj) {
+        Ok(rabbitmq) => rabbitmq,
+        Err(_) => panic!("Fail to unmarshal the RabbitmqCluster"),
+    };
+    state_matches::current_state_matches(&rabbitmq, &cluster.api_server_state.resources)
+}
+
+fn stateful_set_replicas(cluster: &SimulatedCluster<RabbitmqReconciler>) -> Option<i32> {
+    let stateful_sets = cluster.list(StatefulSet::api_resource(), "default");
+    assert_eq!(stateful_sets.len(), 1);
+    match StatefulSet::unmarshal(stateful_sets[0].clone()) {
+        Ok(sts) => sts.spec().unwrap().replicas(),
+        Err(_) => panic!("Fail to unmarshal the StatefulSet"),
+    }
+}
+
+#[test]
+pub fn test_reconcile_creates_objects() {
+    let mut cluster = SimulatedCluster::<RabbitmqReconciler>::new();
+    cluster.create(&make_rabbitmq_cluster(3)).unwrap();
+    assert!(!current_state_matches(&cluster));
+    let outcome = cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    assert!(matches!(outcome, SimulatedReconcileOutcome::Done { .. }));
+    // The headless and client services, the erlang cookie and default user secrets,
+    // and the plugins and server config maps
+    assert_eq!(cluster.list(Service::api_resource(), "default").len(), 2);
+    assert_eq!(cluster.list(Secret::api_resource(), "default").len(), 2);
+    assert_eq!(cluster.list(ConfigMap::api_resource(), "default").len(), 2);
+    assert_eq!(stateful_set_replicas(&cluster), Some(3));
+    assert!(current_state_matches(&cluster));
+
+    // Reconciling again only updates the objects to what they already are
+    cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    assert_eq!(stateful_set_replicas(&cluster), Some(3));
+    assert!(current_state_matches(&cluster));
+}
+
+#[test]
+pub fn test_reconcile_scales_up() {
+    let mut cluster = SimulatedCluster::<RabbitmqReconciler>::new();
+    cluster.create(&make_rabbitmq_cluster(1)).unwrap();
+    cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    scale(&mut cluster, 3);
+    assert!(!current_state_matches(&cluster));
+    cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    assert_eq!(stateful_set_replicas(&cluster), Some(3));
+    assert!(current_state_matches(&cluster));
+}
+
+#[test]
+pub fn test_reconcile_cr_not_found() {
+    let mut cluster = SimulatedCluster::<RabbitmqReconciler>::new();
+    let outcome = cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    assert_eq!(outcome, SimulatedReconcileOutcome::CrNotFound);
+    assert_eq!(cluster.list(StatefulSet::api_resource(), "default").len(), 0);
+}
+
+#[test]
+pub fn test_reconciles_refine_executable_model() {
+    let mut cluster = SimulatedCluster::<RabbitmqReconciler>::new();
+    cluster.create(&make_rabbitmq_cluster(1)).unwrap();
+    cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    scale(&mut cluster, 3);
+    cluster.reconcile::<RabbitmqCluster>("default", "test").unwrap();
+    assert_eq!(cluster.traces.len(), 2);
+    for trace in cluster.traces.iter() {
+        assert_eq!(trace.outcome, ReconcileTraceOutcome::Done);
+        assert_eq!(
+            replay_reconcile::<RabbitmqCluster, RabbitmqReconciler>(trace),
+            ReplayResult::Consistent
+        );
+        assert_eq!(
+            check_reconcile_trace::<RabbitmqCluster, RabbitmqReconciler>(trace),
+            Ok(())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+use crate::executable_model::object_map::ObjectMap;
 use crate::external_api::exec::*;
 use crate::kubernetes_api_objects::exec::resource::ResourceWrapper;
 use crate::kubernetes_api_objects::exec::{
@@ -10,11 +11,14 @@ use crate::kubernetes_api_objects::spec::prelude::DynamicObjectView;
 use crate::kubernetes_api_objects::spec::prelude::PodView;
 use crate::kubernetes_api_objects::spec::resource::ResourceView;
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::requeue::{RequeueHint, RequeueHintReconciler};
+use crate::v_replica_set_controller::exec::state_matches;
 use crate::v_replica_set_controller::model::reconciler as model_reconciler;
 use crate::v_replica_set_controller::trusted::exec_types::*;
 use crate::v_replica_set_controller::trusted::spec_types;
 use crate::v_replica_set_controller::trusted::step::*;
-use crate::vstd_ext::{string_map::StringMap, string_view::*};
+use crate::vstd_ext::{seq_lib::*, string_map::StringMap, string_view::*};
 use vstd::prelude::*;
 use vstd::seq_lib::*;
 use vstd::string::*;
@@ -45,6 +49,12 @@ impl Reconciler for VReplicaSetReconciler {
     fn reconcile_error(state: &VReplicaSetReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Pod::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: VReplicaSetReconcileState)
@@ -84,6 +94,8 @@ pub fn reconcile_core(v_replica_set: &VReplicaSet, resp_o: Option<Response<Empty
             let req = KubeAPIRequest::ListRequest(KubeListRequest {
                 api_resource: Pod::api_resource(),
                 namespace: namespace,
+                label_selector: Some(v_replica_set.spec().selector()),
+                field_selector: None,
             });
             let state_prime = VReplicaSetReconcileState {
                 reconcile_step: VReplicaSetReconcileStep::AfterListPods,
@@ -134,10 +146,15 @@ pub fn reconcile_core(v_replica_set: &VReplicaSet, resp_o: Option<Response<Empty
                 if pod_name_or_none.is_none() {
                     return (error_state(state), None);
                 }
+                let mut preconditions = Preconditions::default();
+                preconditions.set_uid_from_object_meta(&filtered_pods[diff - 1].metadata());
                 let req = KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
                     api_resource: Pod::api_resource(),
                     name: pod_name_or_none.unwrap(),
                     namespace: namespace,
+                    preconditions: Some(preconditions),
+                    propagation_policy: None,
+                    grace_period_seconds: None,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetReconcileStep::AfterDeletePod(diff - 1),
@@ -198,10 +215,15 @@ pub fn reconcile_core(v_replica_set: &VReplicaSet, resp_o: Option<Response<Empty
                 if pod_name_or_none.is_none() {
                     return (error_state(state), None);
                 }
+                let mut preconditions = Preconditions::default();
+                preconditions.set_uid_from_object_meta(&state.filtered_pods.as_ref().unwrap()[diff - 1].metadata());
                 let req = KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
                     api_resource: Pod::api_resource(),
                     name: pod_name_or_none.unwrap(),
                     namespace: namespace,
+                    preconditions: Some(preconditions),
+                    propagation_policy: None,
+                    grace_period_seconds: None,
                 });
                 let state_prime = VReplicaSetReconcileState {
                     reconcile_step: VReplicaSetReconcileStep::AfterDeletePod(diff - 1),
@@ -411,18 +433,6 @@ fn filter_pods(pods: Vec<Pod>, v_replica_set: &VReplicaSet) -> (filtered_pods: V
     filtered_pods
 }
 
-proof fn lemma_filter_maintained_after_add<A>(s: Seq<A>, pred: spec_fn(A) -> bool, filtered_s: Seq<A>, new_elt: A)
-    requires filtered_s == s.filter(pred),
-    ensures
-        (pred(new_elt) ==> filtered_s.push(new_elt) == s.push(new_elt).filter(pred)),
-        (!pred(new_elt) ==> filtered_s == s.push(new_elt).filter(pred)),
-{
-    // Lemma follows from body of Seq::filter.
-    reveal(Seq::filter);
-    // For some reason, this law needs to be explicitly asserted.
-    assert(s.push(new_elt).drop_last() == s);
-}
-
 fn make_pod(v_replica_set: &VReplicaSet) -> (pod: Pod)
     requires v_replica_set@.well_formed(),
     ensures pod@ == model_reconciler::make_pod(v_replica_set@),
@@ -452,3 +462,18 @@ fn make_pod(v_replica_set: &VReplicaSet) -> (pod: Pod)
 }
 
 }
+
+// Every change to the pods of a VReplicaSet, including the pods created or deleted by the reconcile itself,
+// triggers another reconcile (see owned_resources), so a done reconcile need not be requeued until something changes.
+impl RequeueHintReconciler for VReplicaSetReconciler {
+    fn requeue_hint(_state: &VReplicaSetReconcileState) -> RequeueHint {
+        RequeueHint::AwaitChange
+    }
+}
+
+// The snapshot stores each object under its own key (see take_snapshot), as current_state_matches requires.
+impl StateMatchesReconciler for VReplicaSetReconciler {
+    fn state_matches(vrs: &VReplicaSet, snapshot: &ObjectMap) -> Option<bool> {
+        Some(state_matches::current_state_matches(vrs, snapshot))
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::*};
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::v_replica_set_controller::trusted::{exec_types::*, liveness_theorem};
+use crate::vstd_ext::{map_lib::*, seq_lib::*, string_map::StringMap};
+use vstd::prelude::*;
+
+verus! {
+
+// resource_state_matches is the exec version of liveness_theorem::resource_state_matches.
+// It counts the pods in the namespace of the VReplicaSet that it owns and selects and that are not being deleted
+// (the same pods as filter_pods in exec::reconciler), and compares the count with the replicas.
+// See crate::zookeeper_controller::exec::state_matches for how the checkers are used.
+pub fn resource_state_matches(vrs: &VReplicaSet, resources: &ObjectMap) -> (res: bool)
+    requires
+        vrs@.well_formed(),
+        each_object_is_stored_under_its_key(resources@),
+    ensures res == liveness_theorem::resource_state_matches(vrs@, resources@),
+{
+    let objs = resources.values();
+    let ghost matches = |obj: DynamicObjectView|
+        obj.kind == PodView::kind()
+        && obj.metadata.namespace == vrs@.metadata.namespace
+        && obj.metadata.owner_references_contains(vrs@.controller_owner_ref())
+        && vrs@.spec.selector.matches(obj.metadata.labels.unwrap_or(Map::empty()))
+        && obj.metadata.deletion_timestamp.is_None();
+    let ghost objs_view = objs@.map_values(|o: DynamicObject| o@);
+    proof {
+        lemma_values_are_stored(resources@, objs_view);
+        assert forall |i| 0 <= i < objs.len() implies #[trigger] resources@.values().contains(objs[i]@) by {
+            assert(objs_view[i] == objs[i]@);
+        }
+    }
+    let mut count: usize = 0;
+    let mut idx = 0;
+    while idx < objs.len()
+        invariant
+            idx <= objs.len(),
+            count <= idx,
+            objs_view == objs@.map_values(|o: DynamicObject| o@),
+            count == objs_view.take(idx as int).filter(matches).len(),
+            each_object_is_stored_under_its_key(resources@),
+            forall |i| 0 <= i < objs.len() ==> #[trigger] resources@.values().contains(objs[i]@),
+    {
+        let obj = &objs[idx];
+        let ghost old_filtered = objs_view.take(idx as int).filter(matches);
+        proof {
+            // obj is stored in resources, so its namespace is Some
+            let key = choose |key: ObjectRef| resources@.contains_key(key) && #[trigger] resources@[key] == obj@;
+            assert(resources@.contains_key(key));
+        }
+        if obj.kind().eq(&KubeKind::PodKind)
+        && obj.metadata().namespace().unwrap().eq(&vrs.metadata().namespace().unwrap())
+        && obj.metadata().owner_references_contains(vrs.controller_owner_ref())
+        && vrs.spec().selector().matches(obj.metadata().labels().unwrap_or(StringMap::new()))
+        && !obj.metadata().has_deletion_timestamp() {
+            count = count + 1;
+        }
+        proof {
+            lemma_filter_maintained_after_add(objs_view.take(idx as int), matches, old_filtered, obj@);
+            assert(objs_view.take(idx as int).push(obj@) == objs_view.take((idx + 1) as int));
+        }
+        idx = idx + 1;
+    }
+    proof {
+        assert(objs_view.take(objs.len() as int) == objs_view);
+        lemma_object_ref_lt_is_strict_total_order();
+        lemma_map_to_seq_len_is_filtered_keys_len(resources@, object_ref_order(), matches);
+        assert(Set::new(|k: ObjectRef| liveness_theorem::owned_selector_match_is(vrs@, resources@, k))
+            =~= Set::new(|k: ObjectRef| resources@.contains_key(k) && matches(resources@[k])));
+    }
+    let replicas = vrs.spec().replicas().unwrap_or(0);
+    if replicas < 0 {
+        return false;
+    }
+    count == replicas as usize
+}
+
+// current_state_matches checks liveness_theorem::current_state_matches on the objects stored in a cluster.
+pub fn current_state_matches(vrs: &VReplicaSet, resources: &ObjectMap) -> (res: bool)
+    requires
+        vrs@.well_formed(),
+        each_object_is_stored_under_its_key(resources@),
+    ensures res == liveness_theorem::resource_state_matches(vrs@, resources@),
+{
+    resource_state_matches(vrs, resources)
+}
+
+}
@@ -62,6 +62,8 @@ pub open spec fn reconcile_core(
             let req = APIRequest::ListRequest(ListRequest {
                 kind: PodView::kind(),
                 namespace: namespace,
+                label_selector: Some(v_replica_set.spec.selector),
+                field_selector: None,
             });
             let state_prime = VReplicaSetReconcileState {
                 reconcile_step: VReplicaSetReconcileStepView::AfterListPods,
@@ -116,7 +118,12 @@ pub open spec fn reconcile_core(
                                         kind: PodView::kind(),
                                         name: pod_name_or_none.unwrap(),
                                         namespace: namespace,
-                                    }
+                                    },
+                                    // The uid precondition makes sure we do not delete a pod
+                                    // that is recreated with the same name after we list the pods.
+                                    preconditions: Some(PreconditionsView::default().set_uid_from_object_meta(filtered_pods[diff - 1].metadata)),
+                                    propagation_policy: None,
+                                    grace_period_seconds: None,
                                 });
                                 let state_prime = VReplicaSetReconcileState {
                                     reconcile_step: VReplicaSetReconcileStepView::AfterDeletePod((diff - 1) as nat),
@@ -182,7 +189,10 @@ pub open spec fn reconcile_core(
                                 kind: PodView::kind(),
                                 name: pod_name_or_none.unwrap(),
                                 namespace: namespace,
-                            }
+                            },
+                            preconditions: Some(PreconditionsView::default().set_uid_from_object_meta(state.filtered_pods.unwrap()[diff - 1].metadata)),
+                            propagation_policy: None,
+                            grace_period_seconds: None,
                         });
                         let state_prime = VReplicaSetReconcileState {
                             reconcile_step: VReplicaSetReconcileStepView::AfterDeletePod((diff - 1) as nat),
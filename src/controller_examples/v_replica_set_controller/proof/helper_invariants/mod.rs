@@ -0,0 +1,7 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+pub mod predicate;
+pub mod proof;
+
+pub use predicate::*;
+pub use proof::*;
@@ -0,0 +1,265 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::{EmptyAPI, EmptyTypeView};
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::generated_names_are_not_taken,
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::predicate::*,
+    trusted::{spec_types::*, step::*},
+};
+use crate::vstd_ext::string_view::*;
+use vstd::prelude::*;
+
+verus! {
+
+pub open spec fn vrs_is_well_formed(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& vrs.well_formed()
+        &&& vrs.state_validation()
+    }
+}
+
+pub open spec fn the_object_in_reconcile_satisfies_state_validation(key: ObjectRef) -> StatePred<VRSCluster>
+{
+    |s: VRSCluster| {
+        s.ongoing_reconciles().contains_key(key)
+        ==> s.ongoing_reconciles()[key].triggering_cr.state_validation()
+    }
+}
+
+pub open spec fn the_object_in_schedule_satisfies_state_validation() -> StatePred<VRSCluster>
+{
+    |s: VRSCluster| {
+        forall |key: ObjectRef|
+        #[trigger] s.scheduled_reconciles().contains_key(key)
+        && key.kind == VReplicaSetView::kind()
+        ==> s.scheduled_reconciles()[key].state_validation()
+    }
+}
+
+pub open spec fn cr_objects_in_etcd_satisfy_state_validation() -> StatePred<VRSCluster>
+{
+    |s: VRSCluster| {
+        forall |key: ObjectRef|
+        #[trigger] s.resources().contains_key(key)
+        && key.kind == VReplicaSetView::kind()
+        ==> VReplicaSetView::unmarshal(s.resources()[key]).is_Ok()
+            && VReplicaSetView::unmarshal(s.resources()[key]).get_Ok_0().state_validation()
+    }
+}
+
+/// No pod in the namespace of vrs takes a name that the API server may generate for make_pod(vrs) later,
+/// so creating make_pod(vrs) never fails with ObjectAlreadyExists.
+pub open spec fn generated_pod_names_are_not_taken(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        generated_names_are_not_taken(
+            s.kubernetes_api_state, PodView::kind(), vrs.metadata.namespace.get_Some_0(), vrs.metadata.name.get_Some_0() + "-"@
+        )
+    }
+}
+
+/// The API server generates the name of a pod from the generate_name made by a reconciler, i.e., the name of the owner
+/// followed by "-", and the uid counter, which never decreases, so no pod takes a name that may be generated later.
+pub open spec fn no_pod_takes_a_name_generated_later() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& s.kubernetes_api_state.uid_counter >= 0
+        &&& forall |namespace: StringView, owner_name: StringView|
+            #[trigger] generated_names_are_not_taken(s.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@)
+    }
+}
+
+/// The pod is created by a reconciler from make_pod: it has no name, exactly one owner reference,
+/// and a generate_name made of the name of the owner followed by "-".
+pub open spec fn pod_is_made_by_reconciler(obj: DynamicObjectView) -> bool {
+    let owner_refs = obj.metadata.owner_references;
+    &&& obj.metadata.name.is_None()
+    &&& owner_refs.is_Some()
+    &&& owner_refs.get_Some_0().len() == 1
+    &&& obj.metadata.generate_name == Some(owner_refs.get_Some_0()[0].name + "-"@)
+}
+
+/// The requests sent by the client (which only manages VReplicaSet objects), the reconcilers (which list, create
+/// and delete pods) and the built-in controllers (which only delete objects and update the status when no object is
+/// orphaning its dependents): nobody applies or patches objects, updates a pod, or creates a pod in any other way.
+pub open spec fn req_msg_is_expected(msg: VRSMessage) -> bool {
+    &&& !msg.content.is_apply_request()
+    &&& !msg.content.is_patch_request()
+    &&& msg.content.is_update_request() ==> msg.content.get_update_request().obj.kind == VReplicaSetView::kind()
+    &&& msg.content.is_create_request() && msg.content.get_create_request().obj.kind == PodView::kind()
+        ==> pod_is_made_by_reconciler(msg.content.get_create_request().obj)
+}
+
+pub open spec fn every_in_flight_req_msg_is_expected() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        forall |msg: VRSMessage|
+            #[trigger] s.in_flight().contains(msg) && msg.content.is_APIRequest()
+            ==> req_msg_is_expected(msg)
+    }
+}
+
+/// The API server gives each new object the uid counter and then increments it, so no two objects share a uid.
+pub open spec fn each_object_in_etcd_has_unique_uid() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        forall |key: ObjectRef, other_key: ObjectRef|
+            #[trigger] s.resources().contains_key(key) && #[trigger] s.resources().contains_key(other_key)
+            && s.resources()[key].metadata.uid == s.resources()[other_key].metadata.uid
+            ==> key == other_key
+    }
+}
+
+/// The uid has been allocated, and the object with this uid (if it is still in etcd) is in the namespace.
+pub open spec fn uid_is_allocated_in_namespace(uid: Uid, namespace: StringView, s: VRSCluster) -> bool {
+    &&& uid < s.kubernetes_api_state.uid_counter
+    &&& forall |key: ObjectRef|
+        #[trigger] s.resources().contains_key(key) && s.resources()[key].metadata.uid == Some(uid)
+        ==> key.namespace == namespace
+}
+
+/// The VReplicaSet objects in the schedule and in the reconciles are copied from etcd, so each of them has a uid that
+/// is allocated in its namespace.
+pub open spec fn each_cr_has_uid_allocated_in_its_namespace() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& forall |key: ObjectRef|
+            #[trigger] s.scheduled_reconciles().contains_key(key)
+            ==> uid_is_allocated_in_namespace(s.scheduled_reconciles()[key].metadata.uid.get_Some_0(), key.namespace, s)
+        &&& forall |key: ObjectRef|
+            #[trigger] s.ongoing_reconciles().contains_key(key)
+            ==> uid_is_allocated_in_namespace(s.ongoing_reconciles()[key].triggering_cr.metadata.uid.get_Some_0(), key.namespace, s)
+    }
+}
+
+/// A pod is created by the reconciler of its only owner in the namespace of the owner. Since a uid is never reused,
+/// the owner of a pod, if it is still in etcd, is in the same namespace as the pod.
+pub open spec fn each_pod_is_owned_by_one_object_in_its_namespace() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& forall |key: ObjectRef|
+            #[trigger] s.resources().contains_key(key) && key.kind == PodView::kind()
+            ==> {
+                let owner_refs = s.resources()[key].metadata.owner_references;
+                &&& owner_refs.is_Some()
+                &&& owner_refs.get_Some_0().len() == 1
+                &&& uid_is_allocated_in_namespace(owner_refs.get_Some_0()[0].uid, key.namespace, s)
+            }
+        &&& forall |msg: VRSMessage|
+            #[trigger] s.in_flight().contains(msg) && msg.content.is_create_request()
+            && msg.content.get_create_request().obj.kind == PodView::kind()
+            ==> {
+                let req = msg.content.get_create_request();
+                uid_is_allocated_in_namespace(req.obj.metadata.owner_references.get_Some_0()[0].uid, req.namespace, s)
+            }
+    }
+}
+
+/// The pod is a copy of the pod with the same uid in etcd (if it is still there): nobody updates pods,
+/// so the pod in etcd keeps the owner references.
+pub open spec fn pod_is_a_copy_of_pod_in_etcd(metadata: ObjectMetaView, s: VRSCluster) -> bool {
+    &&& metadata.uid.is_Some()
+    &&& metadata.uid.get_Some_0() < s.kubernetes_api_state.uid_counter
+    &&& forall |key: ObjectRef|
+        #[trigger] s.resources().contains_key(key) && s.resources()[key].metadata.uid == metadata.uid
+        ==> {
+            &&& s.resources()[key].kind == PodView::kind()
+            &&& s.resources()[key].metadata.owner_references == metadata.owner_references
+        }
+}
+
+/// The pods in the list responses and the pods filtered by the reconcilers are copies of the pods in etcd,
+/// and the filtered pods are owned by the triggering cr, so a reconciler only deletes (with a uid precondition)
+/// the pods owned by its triggering cr.
+pub open spec fn each_listed_pod_is_a_copy_of_pod_in_etcd() -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& forall |msg: VRSMessage|
+            #[trigger] s.in_flight().contains(msg) && msg.content.is_list_response() && msg.content.get_list_response().res.is_Ok()
+            ==> {
+                let objs = msg.content.get_list_response().res.get_Ok_0();
+                forall |i: int| #![trigger objs[i]] 0 <= i < objs.len() && objs[i].kind == PodView::kind()
+                    ==> pod_is_a_copy_of_pod_in_etcd(objs[i].metadata, s)
+            }
+        &&& forall |key: ObjectRef|
+            #[trigger] s.ongoing_reconciles().contains_key(key) && s.ongoing_reconciles()[key].local_state.filtered_pods.is_Some()
+            ==> {
+                let pods = s.ongoing_reconciles()[key].local_state.filtered_pods.get_Some_0();
+                forall |i: int| #![trigger pods[i]] 0 <= i < pods.len() ==> {
+                    &&& pods[i].metadata.owner_references_contains(s.ongoing_reconciles()[key].triggering_cr.controller_owner_ref())
+                    &&& pod_is_a_copy_of_pod_in_etcd(pods[i].metadata, s)
+                }
+            }
+    }
+}
+
+/// A request creating a pod that would be owned by vrs once created.
+pub open spec fn create_matching_pod_request_msg(vrs: VReplicaSetView) -> spec_fn(VRSMessage) -> bool {
+    |msg: VRSMessage| {
+        &&& msg.dst.is_ApiServer()
+        &&& msg.content.is_create_request()
+        &&& msg.content.get_create_request().namespace == vrs.metadata.namespace.get_Some_0()
+        &&& msg.content.get_create_request().obj.kind == PodView::kind()
+        &&& msg.content.get_create_request().obj.metadata.owner_references_contains(vrs.controller_owner_ref())
+    }
+}
+
+/// A request deleting some pod in the namespace of vrs.
+pub open spec fn delete_pod_request_msg_in_namespace(vrs: VReplicaSetView) -> spec_fn(VRSMessage) -> bool {
+    |msg: VRSMessage| {
+        &&& msg.dst.is_ApiServer()
+        &&& msg.content.is_delete_request()
+        &&& msg.content.get_delete_request().key.kind == PodView::kind()
+        &&& msg.content.get_delete_request().key.namespace == vrs.metadata.namespace.get_Some_0()
+    }
+}
+
+/// Only the reconciler of vrs creates pods owned by vrs, and it creates them one at a time at AfterCreatePod.
+pub open spec fn every_create_matching_pod_request_implies_at_after_create_pod_step(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let key = vrs.object_ref();
+        forall |msg: VRSMessage| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& create_matching_pod_request_msg(vrs)(msg)
+        } ==> {
+            &&& s.ongoing_reconciles().contains_key(key)
+            &&& s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterCreatePod()
+            &&& VRSCluster::pending_req_msg_is(s, key, msg)
+            &&& msg.content.get_create_request().obj == make_pod(vrs).marshal()
+        }
+    }
+}
+
+/// Except the pending request of the reconciler of vrs at AfterDeletePod, a request deleting a pod in the namespace
+/// of vrs carries a uid precondition that no pod owned by vrs satisfies, so it never deletes such a pod.
+/// The uid in the precondition is allocated, so no pod created later satisfies it either.
+pub open spec fn no_other_delete_request_can_delete_matching_pods(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let key = vrs.object_ref();
+        forall |msg: VRSMessage| {
+            &&& #[trigger] s.in_flight().contains(msg)
+            &&& delete_pod_request_msg_in_namespace(vrs)(msg)
+        } ==> {
+            ||| {
+                &&& s.ongoing_reconciles().contains_key(key)
+                &&& s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterDeletePod()
+                &&& VRSCluster::pending_req_msg_is(s, key, msg)
+            }
+            ||| {
+                let req = msg.content.get_delete_request();
+                &&& req.preconditions.is_Some()
+                &&& req.preconditions.get_Some_0().uid.is_Some()
+                &&& req.preconditions.get_Some_0().uid.get_Some_0() < s.kubernetes_api_state.uid_counter
+                &&& matching_pods(vrs, s.resources()).contains(req.key)
+                    ==> s.resources()[req.key].metadata.uid != req.preconditions.get_Some_0().uid
+            }
+        }
+    }
+}
+
+}
@@ -0,0 +1,1061 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use super::predicate::*;
+use crate::external_api::spec::{EmptyAPI, EmptyTypeView};
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::{generated_name, generated_names_are_not_taken, handle_list_request},
+    builtin_controllers::garbage_collector::request_uses_propagation_policy,
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::reconciler::spec::{io::*, reconciler::*};
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::predicate::*,
+    trusted::{liveness_theorem::*, spec_types::*, step::*},
+};
+use crate::vstd_ext::{map_lib::*, seq_lib::*, string_view::*};
+use vstd::prelude::*;
+
+verus! {
+
+pub proof fn lemma_always_vrs_is_well_formed(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_state(desired_state_is(vrs)))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_etcd_is_well_formed()))),
+        spec.entails(always(lift_state(cr_objects_in_etcd_satisfy_state_validation()))),
+    ensures spec.entails(always(lift_state(vrs_is_well_formed(vrs)))),
+{
+    let stronger_inv = |s: VRSCluster| {
+        &&& desired_state_is(vrs)(s)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& cr_objects_in_etcd_satisfy_state_validation()(s)
+    };
+
+    invariant_n!(
+        spec, lift_state(stronger_inv),
+        lift_state(vrs_is_well_formed(vrs)),
+        lift_state(desired_state_is(vrs)),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(cr_objects_in_etcd_satisfy_state_validation())
+    );
+}
+
+pub proof fn lemma_always_cr_objects_in_etcd_satisfy_state_validation(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(cr_objects_in_etcd_satisfy_state_validation()))),
+{
+    let inv = cr_objects_in_etcd_satisfy_state_validation();
+    VReplicaSetView::marshal_status_preserves_integrity();
+    init_invariant(spec, VRSCluster::init(), VRSCluster::next(), inv);
+}
+
+pub proof fn lemma_always_the_object_in_schedule_satisfies_state_validation(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(the_object_in_schedule_satisfies_state_validation()))),
+{
+    let inv = the_object_in_schedule_satisfies_state_validation();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& cr_objects_in_etcd_satisfy_state_validation()(s)
+    };
+    lemma_always_cr_objects_in_etcd_satisfy_state_validation(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(cr_objects_in_etcd_satisfy_state_validation())
+    );
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+pub proof fn lemma_always_the_object_in_reconcile_satisfies_state_validation(spec: TempPred<VRSCluster>, key: ObjectRef)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(the_object_in_reconcile_satisfies_state_validation(key)))),
+{
+    let inv = the_object_in_reconcile_satisfies_state_validation(key);
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& the_object_in_schedule_satisfies_state_validation()(s)
+    };
+    lemma_always_the_object_in_schedule_satisfies_state_validation(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(the_object_in_schedule_satisfies_state_validation())
+    );
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+pub proof fn lemma_always_each_object_in_reconcile_satisfies_state_validation(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(VRSCluster::each_object_in_reconcile_satisfies_state_validation()))),
+{
+    let inv = VRSCluster::each_object_in_reconcile_satisfies_state_validation();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& the_object_in_schedule_satisfies_state_validation()(s)
+    };
+    lemma_always_the_object_in_schedule_satisfies_state_validation(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(the_object_in_schedule_satisfies_state_validation())
+    );
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+/// The reconciler lists pods, deletes pods without a propagation policy, and creates the pods made by make_pod,
+/// which copy the finalizers of the pod template. state_validation rules out the finalizers of the orphan and
+/// foreground propagation policies in the template, so the reconcile of a valid cr does not use either policy.
+pub proof fn lemma_reconciler_does_not_use_propagation_policy_for_valid_cr()
+    ensures VRSCluster::reconciler_does_not_use_propagation_policy_for_valid_cr(),
+{
+    assert forall |vrs: VReplicaSetView, resp_o: Option<ResponseView<EmptyTypeView>>, state: VReplicaSetReconcileState|
+        #![trigger VReplicaSetReconciler::reconcile_core(vrs, resp_o, state)]
+        vrs.state_validation()
+        && VReplicaSetReconciler::reconcile_core(vrs, resp_o, state).1.is_Some()
+        && VReplicaSetReconciler::reconcile_core(vrs, resp_o, state).1.get_Some_0().is_KRequest()
+    implies !request_uses_propagation_policy(VReplicaSetReconciler::reconcile_core(vrs, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        assert(make_pod(vrs).marshal().metadata.finalizers == vrs.spec.template.get_Some_0().metadata.get_Some_0().finalizers);
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(VRSCluster::no_orphan_or_foreground_deletion()))),
+{
+    lemma_reconciler_does_not_use_propagation_policy_for_valid_cr();
+    lemma_always_each_object_in_reconcile_satisfies_state_validation(spec);
+    VRSCluster::lemma_always_no_orphan_or_foreground_deletion_for_valid_cr(spec);
+}
+
+/// The pod made by the reconciler of any cr has no name, the cr as the only owner, and a generate_name made of the name
+/// of the cr followed by "-". Unlike lemma_make_pod_is_owned_and_selected, this holds for any cr.
+pub proof fn lemma_make_pod_is_made_by_reconciler(cr: VReplicaSetView)
+    ensures
+        make_pod(cr).marshal().kind == PodView::kind(),
+        make_pod(cr).marshal().metadata == make_pod(cr).metadata,
+        make_pod(cr).metadata.owner_references == Some(make_owner_references(cr)),
+        make_owner_references(cr)[0] == cr.controller_owner_ref(),
+        pod_is_made_by_reconciler(make_pod(cr).marshal()),
+{
+    let owner_refs = make_owner_references(cr);
+    assert(owner_refs.len() == 1);
+    assert(owner_refs[0] == cr.controller_owner_ref());
+    assert(make_pod(cr).metadata.generate_name == Some(cr.metadata.name.get_Some_0() + "-"@));
+}
+
+pub proof fn lemma_always_every_in_flight_req_msg_is_expected(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(every_in_flight_req_msg_is_expected()))),
+{
+    let inv = every_in_flight_req_msg_is_expected();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::no_orphan_or_foreground_deletion()(s)
+    };
+    lemma_always_no_orphan_or_foreground_deletion(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::no_orphan_or_foreground_deletion())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        assert forall |msg: VRSMessage| #[trigger] s_prime.in_flight().contains(msg) && msg.content.is_APIRequest()
+        implies req_msg_is_expected(msg) by {
+            if !s.in_flight().contains(msg) {
+                let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+                match step {
+                    Step::ControllerStep(input) => {
+                        // The reconciler lists pods, deletes pods, or creates the pods made by make_pod.
+                        let cr_key = input.1.get_Some_0();
+                        lemma_make_pod_is_made_by_reconciler(s.ongoing_reconciles()[cr_key].triggering_cr);
+                    },
+                    Step::BuiltinControllersStep(input) => {
+                        VRSCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+/// Only the API server changes etcd, and nobody applies or patches objects or updates pods (every_in_flight_req_msg_is_expected).
+/// So a new object is created by a create request with the current uid counter, and an object already in etcd keeps its kind
+/// and uid; a pod also keeps its owner references and labels, and never loses its deletion timestamp.
+#[verifier(spinoff_prover)]
+pub proof fn lemma_each_object_in_etcd_is_new_or_keeps_uid(s: VRSCluster, s_prime: VRSCluster, step: VRSStep)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        every_in_flight_req_msg_is_expected()(s),
+    ensures
+        s.kubernetes_api_state.uid_counter <= s_prime.kubernetes_api_state.uid_counter,
+        forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && !s.resources().contains_key(key)
+        ==> {
+            let req_msg = step.get_ApiServerStep_0().get_Some_0();
+            let req = req_msg.content.get_create_request();
+            &&& step.is_ApiServerStep()
+            &&& s.in_flight().contains(req_msg)
+            &&& req_msg.content.is_create_request()
+            &&& s_prime.resources() == s.resources().insert(key, s_prime.resources()[key])
+            &&& s_prime.kubernetes_api_state.uid_counter == s.kubernetes_api_state.uid_counter + 1
+            &&& s_prime.resources()[key].metadata.uid == Some(s.kubernetes_api_state.uid_counter)
+            &&& s_prime.resources()[key].kind == req.obj.kind
+            &&& s_prime.resources()[key].metadata.owner_references == req.obj.metadata.owner_references
+            &&& s_prime.resources()[key].metadata.labels == req.obj.metadata.labels
+            &&& s_prime.resources()[key].metadata.deletion_timestamp.is_None()
+            &&& key.kind == req.obj.kind
+            &&& key.namespace == req.namespace
+            &&& req.obj.metadata.name.is_None()
+                ==> key.name == generated_name(s.kubernetes_api_state, req.obj.metadata.generate_name.get_Some_0())
+        },
+        forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s.resources().contains_key(key)
+        ==> {
+            &&& s_prime.resources()[key].kind == s.resources()[key].kind
+            &&& s_prime.resources()[key].metadata.uid == s.resources()[key].metadata.uid
+            &&& key.kind == PodView::kind() ==> {
+                &&& s_prime.resources()[key].metadata.owner_references == s.resources()[key].metadata.owner_references
+                &&& s_prime.resources()[key].metadata.labels == s.resources()[key].metadata.labels
+                &&& s.resources()[key].metadata.deletion_timestamp.is_Some()
+                    ==> s_prime.resources()[key].metadata.deletion_timestamp.is_Some()
+            }
+        },
+{
+    match step {
+        Step::ApiServerStep(input) => {
+            let req_msg = input.get_Some_0();
+            assert(s.in_flight().contains(req_msg));
+            assert(req_msg_is_expected(req_msg));
+            match req_msg.content.get_APIRequest_0() {
+                APIRequest::CreateRequest(req) => {
+                    // The created object is the only new object, and its key comes from the request.
+                    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && !s.resources().contains_key(key)
+                    implies s_prime.resources() == s.resources().insert(key, s_prime.resources()[key]) by {
+                        assert(s_prime.resources()[key].object_ref() == key);
+                    }
+                },
+                APIRequest::UpdateRequest(req) => {
+                    // Only VReplicaSet objects are updated, and the updated object keeps its uid.
+                    assert(req.obj.kind == VReplicaSetView::kind());
+                    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s.resources().contains_key(key)
+                    implies s_prime.resources()[key].kind == s.resources()[key].kind by {
+                        if key == req.key() {
+                            assert(s.resources()[key].object_ref() == key);
+                        }
+                    }
+                },
+                APIRequest::UpdateStatusRequest(req) => {
+                    // The object with the new status keeps its metadata.
+                    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s.resources().contains_key(key)
+                    implies s_prime.resources()[key].kind == s.resources()[key].kind by {
+                        if key == req.key() {
+                            assert(s.resources()[key].object_ref() == key);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        },
+        _ => {}
+    }
+}
+
+pub proof fn lemma_always_each_object_in_etcd_has_unique_uid(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(each_object_in_etcd_has_unique_uid()))),
+{
+    let inv = each_object_in_etcd_has_unique_uid();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+    };
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    lemma_always_every_in_flight_req_msg_is_expected(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(every_in_flight_req_msg_is_expected())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+        assert forall |key: ObjectRef, other_key: ObjectRef|
+            #[trigger] s_prime.resources().contains_key(key) && #[trigger] s_prime.resources().contains_key(other_key)
+            && s_prime.resources()[key].metadata.uid == s_prime.resources()[other_key].metadata.uid
+        implies key == other_key by {
+            // The new object gets the uid counter, which is larger than the uid of any object already in etcd.
+            if s.resources().contains_key(key) && !s.resources().contains_key(other_key) {
+                assert(s.resources()[key].metadata.uid.get_Some_0() < s.kubernetes_api_state.uid_counter);
+            } else if !s.resources().contains_key(key) && s.resources().contains_key(other_key) {
+                assert(s.resources()[other_key].metadata.uid.get_Some_0() < s.kubernetes_api_state.uid_counter);
+            }
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+/// A uid allocated in a namespace stays so: each new object gets a larger uid, and the other objects keep their uids.
+pub proof fn lemma_uid_is_allocated_in_namespace_is_preserved(
+    s: VRSCluster, s_prime: VRSCluster, step: VRSStep, uid: Uid, namespace: StringView
+)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        every_in_flight_req_msg_is_expected()(s),
+        uid_is_allocated_in_namespace(uid, namespace, s),
+    ensures uid_is_allocated_in_namespace(uid, namespace, s_prime),
+{
+    lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s_prime.resources()[key].metadata.uid == Some(uid)
+    implies key.namespace == namespace by {
+        if !s.resources().contains_key(key) {
+            assert(s_prime.resources()[key].metadata.uid == Some(s.kubernetes_api_state.uid_counter));
+            assert(false);
+        }
+    }
+}
+
+pub proof fn lemma_always_each_cr_has_uid_allocated_in_its_namespace(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(each_cr_has_uid_allocated_in_its_namespace()))),
+{
+    let inv = each_cr_has_uid_allocated_in_its_namespace();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+        &&& each_object_in_etcd_has_unique_uid()(s)
+    };
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    lemma_always_every_in_flight_req_msg_is_expected(spec);
+    lemma_always_each_object_in_etcd_has_unique_uid(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(every_in_flight_req_msg_is_expected()),
+        lift_state(each_object_in_etcd_has_unique_uid())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        VReplicaSetView::marshal_preserves_metadata();
+        assert forall |key: ObjectRef| #[trigger] s_prime.scheduled_reconciles().contains_key(key)
+        implies uid_is_allocated_in_namespace(s_prime.scheduled_reconciles()[key].metadata.uid.get_Some_0(), key.namespace, s_prime) by {
+            let uid = s_prime.scheduled_reconciles()[key].metadata.uid.get_Some_0();
+            if !s.scheduled_reconciles().contains_key(key) || s_prime.scheduled_reconciles()[key] != s.scheduled_reconciles()[key] {
+                // The cr is just scheduled from etcd with the key, which is the only object with its uid.
+                assert(step == Step::<VRSMessage>::ScheduleControllerReconcileStep(key));
+                assert(s.resources().contains_key(key));
+                assert(s.resources()[key].metadata.uid == Some(uid));
+                assert forall |other_key: ObjectRef| #[trigger] s.resources().contains_key(other_key) && s.resources()[other_key].metadata.uid == Some(uid)
+                implies other_key.namespace == key.namespace by {
+                    assert(other_key == key);
+                }
+            }
+            lemma_uid_is_allocated_in_namespace_is_preserved(s, s_prime, step, uid, key.namespace);
+        }
+        assert forall |key: ObjectRef| #[trigger] s_prime.ongoing_reconciles().contains_key(key)
+        implies uid_is_allocated_in_namespace(s_prime.ongoing_reconciles()[key].triggering_cr.metadata.uid.get_Some_0(), key.namespace, s_prime) by {
+            let uid = s_prime.ongoing_reconciles()[key].triggering_cr.metadata.uid.get_Some_0();
+            if !s.ongoing_reconciles().contains_key(key) || s_prime.ongoing_reconciles()[key].triggering_cr != s.ongoing_reconciles()[key].triggering_cr {
+                // The reconcile just starts with the scheduled cr.
+                assert(s.scheduled_reconciles().contains_key(key));
+                assert(s_prime.ongoing_reconciles()[key].triggering_cr == s.scheduled_reconciles()[key]);
+            }
+            lemma_uid_is_allocated_in_namespace_is_preserved(s, s_prime, step, uid, key.namespace);
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+pub proof fn lemma_always_each_pod_is_owned_by_one_object_in_its_namespace(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(each_pod_is_owned_by_one_object_in_its_namespace()))),
+{
+    let inv = each_pod_is_owned_by_one_object_in_its_namespace();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+        &&& each_cr_has_uid_allocated_in_its_namespace()(s)
+    };
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    VRSCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_every_in_flight_req_msg_is_expected(spec);
+    lemma_always_each_cr_has_uid_allocated_in_its_namespace(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(every_in_flight_req_msg_is_expected()),
+        lift_state(each_cr_has_uid_allocated_in_its_namespace())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+        assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && key.kind == PodView::kind()
+        implies {
+            let owner_refs = s_prime.resources()[key].metadata.owner_references;
+            &&& owner_refs.is_Some()
+            &&& owner_refs.get_Some_0().len() == 1
+            &&& uid_is_allocated_in_namespace(owner_refs.get_Some_0()[0].uid, key.namespace, s_prime)
+        } by {
+            let owner_refs = s_prime.resources()[key].metadata.owner_references;
+            if !s.resources().contains_key(key) {
+                // The pod is created by a request that makes it owned by one object in the namespace of the request.
+                let req_msg = step.get_ApiServerStep_0().get_Some_0();
+                assert(req_msg_is_expected(req_msg));
+                assert(pod_is_made_by_reconciler(req_msg.content.get_create_request().obj));
+            }
+            lemma_uid_is_allocated_in_namespace_is_preserved(s, s_prime, step, owner_refs.get_Some_0()[0].uid, key.namespace);
+        }
+        assert forall |msg: VRSMessage| #[trigger] s_prime.in_flight().contains(msg) && msg.content.is_create_request()
+            && msg.content.get_create_request().obj.kind == PodView::kind()
+        implies {
+            let req = msg.content.get_create_request();
+            uid_is_allocated_in_namespace(req.obj.metadata.owner_references.get_Some_0()[0].uid, req.namespace, s_prime)
+        } by {
+            let req = msg.content.get_create_request();
+            if !s.in_flight().contains(msg) {
+                // Only the reconcilers create pods, which are owned by the triggering cr and in the namespace of the cr.
+                let cr_key = step.get_ControllerStep_0().1.get_Some_0();
+                let cr = s.ongoing_reconciles()[cr_key].triggering_cr;
+                assert(step.is_ControllerStep());
+                assert(s.ongoing_reconciles().contains_key(cr_key));
+                lemma_make_pod_is_made_by_reconciler(cr);
+                assert(req.obj == make_pod(cr).marshal());
+                assert(req.namespace == cr_key.namespace);
+                assert(req.obj.metadata.owner_references.get_Some_0()[0].uid == cr.metadata.uid.get_Some_0());
+            }
+            lemma_uid_is_allocated_in_namespace_is_preserved(s, s_prime, step, req.obj.metadata.owner_references.get_Some_0()[0].uid, req.namespace);
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+/// Each pod is owned by one object in its namespace, so a pod owned by vrs is in the namespace of vrs.
+pub proof fn lemma_pod_owned_by_vrs_is_in_namespace_of_vrs(vrs: VReplicaSetView, s: VRSCluster, key: ObjectRef)
+    requires
+        desired_state_is(vrs)(s),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        each_pod_is_owned_by_one_object_in_its_namespace()(s),
+        s.resources().contains_key(key),
+        key.kind == PodView::kind(),
+        s.resources()[key].metadata.owner_references_contains(vrs.controller_owner_ref()),
+    ensures
+        key.namespace == vrs.metadata.namespace.get_Some_0(),
+        s.resources()[key].metadata.owner_references == Some(seq![vrs.controller_owner_ref()]),
+{
+    let owner_refs = s.resources()[key].metadata.owner_references.get_Some_0();
+    assert(owner_refs.contains(vrs.controller_owner_ref()));
+    assert(owner_refs[0] == vrs.controller_owner_ref());
+    assert(owner_refs =~= seq![vrs.controller_owner_ref()]);
+    VReplicaSetView::marshal_preserves_metadata();
+    // vrs is in etcd with its uid, which is allocated in the namespace of the pod.
+    assert(s.resources().contains_key(vrs.object_ref()));
+    assert(s.resources()[vrs.object_ref()].metadata.uid == vrs.metadata.uid);
+    assert(s.resources()[vrs.object_ref()].metadata.uid == Some(owner_refs[0].uid));
+    assert(vrs.object_ref().namespace == key.namespace);
+}
+
+/// The API server generates the name of a new pod from the generate_name set by a reconciler and the uid counter,
+/// which then increases. By lemma_dash_int_suffix_is_unique, the generated name is never one that may be generated
+/// later with a larger uid counter.
+pub proof fn lemma_always_no_pod_takes_a_name_generated_later(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(no_pod_takes_a_name_generated_later()))),
+{
+    let inv = no_pod_takes_a_name_generated_later();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+    };
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    lemma_always_every_in_flight_req_msg_is_expected(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(every_in_flight_req_msg_is_expected())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+        assert forall |namespace: StringView, owner_name: StringView|
+            #[trigger] generated_names_are_not_taken(s.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@)
+        implies generated_names_are_not_taken(s_prime.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@) by {
+            assert forall |key: ObjectRef, uid: Uid|
+                #[trigger] s_prime.resources().contains_key(key) && key.kind == PodView::kind() && key.namespace == namespace
+                && s_prime.kubernetes_api_state.uid_counter <= uid
+            implies key.name != owner_name + "-"@ + #[trigger] int_to_string_view(uid) by {
+                if !s.resources().contains_key(key) {
+                    // The new pod is named after its owner and the uid counter before the creation,
+                    // which is smaller than uid.
+                    let req = step.get_ApiServerStep_0().get_Some_0().content.get_create_request();
+                    let owner_name_of_pod = req.obj.metadata.owner_references.get_Some_0()[0].name;
+                    assert(req_msg_is_expected(step.get_ApiServerStep_0().get_Some_0()));
+                    assert(key.name == owner_name_of_pod + "-"@ + int_to_string_view(s.kubernetes_api_state.uid_counter));
+                    if key.name == owner_name + "-"@ + int_to_string_view(uid) {
+                        lemma_dash_int_suffix_is_unique(owner_name_of_pod, s.kubernetes_api_state.uid_counter, owner_name, uid);
+                    }
+                } else {
+                    assert(generated_names_are_not_taken(s.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@));
+                }
+            }
+        }
+        assert forall |namespace: StringView, owner_name: StringView|
+        implies #[trigger] generated_names_are_not_taken(s_prime.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@) by {
+            assert(generated_names_are_not_taken(s.kubernetes_api_state, PodView::kind(), namespace, owner_name + "-"@));
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+pub proof fn lemma_always_generated_pod_names_are_not_taken(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(generated_pod_names_are_not_taken(vrs)))),
+{
+    lemma_always_no_pod_takes_a_name_generated_later(spec);
+    always_weaken_temp(spec, lift_state(no_pod_takes_a_name_generated_later()), lift_state(generated_pod_names_are_not_taken(vrs)));
+}
+
+/// The pods created by the reconciler are owned by vrs and selected by its selector, which relies on state_validation.
+pub proof fn lemma_make_pod_is_owned_and_selected(vrs: VReplicaSetView)
+    requires
+        vrs.well_formed(),
+        vrs.state_validation(),
+    ensures
+        make_pod(vrs).marshal().kind == PodView::kind(),
+        make_pod(vrs).marshal().metadata == make_pod(vrs).metadata,
+        make_pod(vrs).metadata.owner_references_contains(vrs.controller_owner_ref()),
+        make_pod(vrs).metadata.owner_references == Some(make_owner_references(vrs)),
+        vrs.spec.selector.matches(make_pod(vrs).metadata.labels.unwrap_or(Map::empty())),
+        make_pod(vrs).metadata.name.is_None(),
+        make_pod(vrs).metadata.namespace.is_None(),
+        make_pod(vrs).metadata.generate_name.is_Some(),
+        make_pod(vrs).metadata.deletion_timestamp.is_None(),
+{
+    let owner_refs = make_owner_references(vrs);
+    assert(owner_refs[0] == vrs.controller_owner_ref());
+    assert(owner_refs.contains(vrs.controller_owner_ref()));
+    assert(make_pod(vrs).metadata.labels == vrs.spec.template.get_Some_0().metadata.get_Some_0().labels);
+}
+
+#[verifier(spinoff_prover)]
+pub proof fn lemma_eventually_always_every_create_matching_pod_request_implies_at_after_create_pod_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::external_api_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)))),
+        spec.entails(always(lift_state(vrs_is_well_formed(vrs)))),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(every_create_matching_pod_request_implies_at_after_create_pod_step(vrs))))),
+{
+    let key = vrs.object_ref();
+    let requirements = |msg: VRSMessage, s: VRSCluster| {
+        create_matching_pod_request_msg(vrs)(msg) ==> {
+            &&& s.ongoing_reconciles().contains_key(key)
+            &&& s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterCreatePod()
+            &&& VRSCluster::pending_req_msg_is(s, key, msg)
+            &&& msg.content.get_create_request().obj == make_pod(vrs).marshal()
+        }
+    };
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::crash_disabled()(s)
+        &&& VRSCluster::every_in_flight_msg_has_unique_id()(s)
+        &&& VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)(s)
+        &&& vrs_is_well_formed(vrs)(s)
+    };
+    assert forall |s, s_prime| #[trigger] stronger_next(s, s_prime)
+    implies VRSCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)(s, s_prime) by {
+        assert forall |msg: VRSMessage| (!s.in_flight().contains(msg) || requirements(msg, s)) && #[trigger] s_prime.in_flight().contains(msg)
+        implies requirements(msg, s_prime) by {
+            if create_matching_pod_request_msg(vrs)(msg) {
+                let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+                if !s.in_flight().contains(msg) {
+                    lemma_create_matching_pod_request_msg_implies_key_in_reconcile_equals(vrs, s, s_prime, msg, step);
+                } else {
+                    assert(requirements(msg, s));
+                    assert(s.ongoing_reconciles()[key] == s_prime.ongoing_reconciles()[key]);
+                }
+            }
+        }
+    }
+    invariant_n!(
+        spec, lift_action(stronger_next), lift_action(VRSCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
+        lift_action(VRSCluster::next()), lift_state(VRSCluster::crash_disabled()),
+        lift_state(VRSCluster::every_in_flight_msg_has_unique_id()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)),
+        lift_state(vrs_is_well_formed(vrs))
+    );
+
+    VRSCluster::lemma_true_leads_to_always_every_in_flight_req_msg_satisfies(spec, requirements);
+
+    temp_pred_equality(
+        lift_state(every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)),
+        lift_state(VRSCluster::every_in_flight_req_msg_satisfies(requirements))
+    );
+}
+
+pub proof fn lemma_create_matching_pod_request_msg_implies_key_in_reconcile_equals(vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, msg: VRSMessage, step: VRSStep)
+    requires
+        !s.in_flight().contains(msg), s_prime.in_flight().contains(msg),
+        VRSCluster::next_step(s, s_prime, step),
+        VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)(s),
+        vrs_is_well_formed(vrs)(s),
+        create_matching_pod_request_msg(vrs)(msg),
+    ensures
+        step.is_ControllerStep(),
+        step.get_ControllerStep_0().1.get_Some_0() == vrs.object_ref(),
+        s_prime.ongoing_reconciles().contains_key(vrs.object_ref()),
+        s_prime.ongoing_reconciles()[vrs.object_ref()].local_state.reconcile_step.is_AfterCreatePod(),
+        VRSCluster::pending_req_msg_is(s_prime, vrs.object_ref(), msg),
+        msg.content.get_create_request().obj == make_pod(vrs).marshal(),
+{
+    // The client only creates VReplicaSet objects and the built-in controllers never create objects,
+    // so the create request must come from the reconciler, which creates pods owned by the triggering cr.
+    // Since the owner reference carries the name of vrs and the request carries the namespace of vrs,
+    // the triggering cr must be vrs itself.
+    let cr_key = step.get_ControllerStep_0().1.get_Some_0();
+    let cr = s.ongoing_reconciles()[cr_key].triggering_cr;
+    assert(step.is_ControllerStep());
+    assert(s.ongoing_reconciles().contains_key(cr_key));
+    let local_step_prime = s_prime.ongoing_reconciles()[cr_key].local_state.reconcile_step;
+    assert(local_step_prime.is_AfterCreatePod());
+    assert(msg.content.get_create_request().obj == make_pod(cr).marshal());
+    let owner_refs = make_owner_references(cr);
+    assert(make_pod(cr).metadata.owner_references == Some(owner_refs));
+    assert(owner_refs.contains(vrs.controller_owner_ref()));
+    let i = choose |i: int| 0 <= i < owner_refs.len() && owner_refs[i] == vrs.controller_owner_ref();
+    assert(owner_refs[i] == cr.controller_owner_ref());
+    assert(cr.metadata.name.get_Some_0() == vrs.metadata.name.get_Some_0());
+    assert(cr.metadata.namespace.get_Some_0() == vrs.metadata.namespace.get_Some_0());
+    assert(cr_key == cr.object_ref());
+    assert(cr_key == vrs.object_ref());
+    assert(cr.metadata.name == vrs.metadata.name);
+    assert(cr.metadata.uid == vrs.metadata.uid);
+    assert(cr.spec == vrs.spec);
+    assert(make_pod(cr) == make_pod(vrs));
+}
+
+/// Each pod filtered by the reconciler of cr is unmarshalled from a pod in the list response, and is owned by cr.
+pub proof fn lemma_filtered_pods_are_from_list_resp(objs: Seq<DynamicObjectView>, cr: VReplicaSetView, i: int) -> (j: int)
+    requires
+        objects_to_pods(objs).is_Some(),
+        0 <= i < filter_pods(objects_to_pods(objs).get_Some_0(), cr).len(),
+    ensures
+        0 <= j < objs.len(),
+        objs[j].kind == PodView::kind(),
+        objs[j].metadata == filter_pods(objects_to_pods(objs).get_Some_0(), cr)[i].metadata,
+        filter_pods(objects_to_pods(objs).get_Some_0(), cr)[i].metadata.owner_references_contains(cr.controller_owner_ref()),
+{
+    let pods = objects_to_pods(objs).get_Some_0();
+    let pod = filter_pods(pods, cr)[i];
+    lemma_filter_auto::<PodView>();
+    lemma_filter_auto::<DynamicObjectView>();
+    assert(pods.contains(pod));
+    let j = choose |j: int| 0 <= j < pods.len() && pods[j] == pod;
+    // objects_to_pods returns Some only if no object fails to unmarshal.
+    assert(PodView::unmarshal(objs[j]).is_Ok());
+    j
+}
+
+/// A pod in etcd keeps its uid, kind and owner references, and a new pod gets a larger uid than any copy,
+/// so a copy of a pod in etcd stays a copy.
+pub proof fn lemma_pod_is_a_copy_of_pod_in_etcd_is_preserved(s: VRSCluster, s_prime: VRSCluster, step: VRSStep, metadata: ObjectMetaView)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        every_in_flight_req_msg_is_expected()(s),
+        pod_is_a_copy_of_pod_in_etcd(metadata, s),
+    ensures pod_is_a_copy_of_pod_in_etcd(metadata, s_prime),
+{
+    lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s_prime.resources()[key].metadata.uid == metadata.uid
+    implies {
+        &&& s_prime.resources()[key].kind == PodView::kind()
+        &&& s_prime.resources()[key].metadata.owner_references == metadata.owner_references
+    } by {
+        if s.resources().contains_key(key) {
+            assert(s.resources()[key].object_ref() == key);
+        }
+    }
+}
+
+pub proof fn lemma_always_each_listed_pod_is_a_copy_of_pod_in_etcd(spec: TempPred<VRSCluster>)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+    ensures spec.entails(always(lift_state(each_listed_pod_is_a_copy_of_pod_in_etcd()))),
+{
+    let inv = each_listed_pod_is_a_copy_of_pod_in_etcd();
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::stale_read_disabled()(s)
+        &&& VRSCluster::etcd_is_finite()(s)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+        &&& each_object_in_etcd_has_unique_uid()(s)
+    };
+    VRSCluster::lemma_always_stale_read_disabled(spec);
+    VRSCluster::lemma_always_etcd_is_finite(spec);
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    lemma_always_every_in_flight_req_msg_is_expected(spec);
+    lemma_always_each_object_in_etcd_has_unique_uid(spec);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::stale_read_disabled()),
+        lift_state(VRSCluster::etcd_is_finite()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(every_in_flight_req_msg_is_expected()),
+        lift_state(each_object_in_etcd_has_unique_uid())
+    );
+    assert forall |s, s_prime| inv(s) && #[trigger] stronger_next(s, s_prime) implies inv(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        assert forall |msg: VRSMessage|
+            #[trigger] s_prime.in_flight().contains(msg) && msg.content.is_list_response() && msg.content.get_list_response().res.is_Ok()
+        implies {
+            let objs = msg.content.get_list_response().res.get_Ok_0();
+            forall |i: int| #![trigger objs[i]] 0 <= i < objs.len() && objs[i].kind == PodView::kind()
+                ==> pod_is_a_copy_of_pod_in_etcd(objs[i].metadata, s_prime)
+        } by {
+            let objs = msg.content.get_list_response().res.get_Ok_0();
+            assert forall |i: int| #![trigger objs[i]] 0 <= i < objs.len() && objs[i].kind == PodView::kind()
+            implies pod_is_a_copy_of_pod_in_etcd(objs[i].metadata, s_prime) by {
+                if !s.in_flight().contains(msg) {
+                    // The list response is made by the API server from etcd, which the list request does not change,
+                    // and the listed object is the only object with its uid.
+                    let req = step.get_ApiServerStep_0().get_Some_0().content.get_list_request();
+                    assert(step.is_ApiServerStep());
+                    assert(s_prime.resources() == s.resources());
+                    assert(objs == handle_list_request(req, s.kubernetes_api_state).res.get_Ok_0());
+                    lemma_object_ref_lt_is_strict_total_order();
+                    let k = lemma_map_to_seq_contains_implies_contains_value(
+                        s.resources(), object_ref_order(), |o: DynamicObjectView| req.selects(o), i
+                    );
+                    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key) && s_prime.resources()[key].metadata.uid == objs[i].metadata.uid
+                    implies key == k by {
+                        assert(s.resources().contains_key(k));
+                    }
+                } else {
+                    lemma_pod_is_a_copy_of_pod_in_etcd_is_preserved(s, s_prime, step, objs[i].metadata);
+                }
+            }
+        }
+        assert forall |key: ObjectRef|
+            #[trigger] s_prime.ongoing_reconciles().contains_key(key) && s_prime.ongoing_reconciles()[key].local_state.filtered_pods.is_Some()
+        implies {
+            let pods = s_prime.ongoing_reconciles()[key].local_state.filtered_pods.get_Some_0();
+            forall |i: int| #![trigger pods[i]] 0 <= i < pods.len() ==> {
+                &&& pods[i].metadata.owner_references_contains(s_prime.ongoing_reconciles()[key].triggering_cr.controller_owner_ref())
+                &&& pod_is_a_copy_of_pod_in_etcd(pods[i].metadata, s_prime)
+            }
+        } by {
+            let pods = s_prime.ongoing_reconciles()[key].local_state.filtered_pods.get_Some_0();
+            let cr = s_prime.ongoing_reconciles()[key].triggering_cr;
+            assert forall |i: int| #![trigger pods[i]] 0 <= i < pods.len() implies {
+                &&& pods[i].metadata.owner_references_contains(cr.controller_owner_ref())
+                &&& pod_is_a_copy_of_pod_in_etcd(pods[i].metadata, s_prime)
+            } by {
+                if !(s.ongoing_reconciles().contains_key(key)
+                    && s.ongoing_reconciles()[key].local_state.filtered_pods == Some(pods)
+                    && s.ongoing_reconciles()[key].triggering_cr == cr) {
+                    // The reconcile filters the pods from the list response it receives at AfterListPods.
+                    let resp_msg = step.get_ControllerStep_0().0.get_Some_0();
+                    let objs = resp_msg.content.get_list_response().res.get_Ok_0();
+                    assert(step.is_ControllerStep());
+                    assert(step.get_ControllerStep_0().1 == Some(key));
+                    assert(s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterListPods());
+                    assert(s.in_flight().contains(resp_msg));
+                    assert(pods == filter_pods(objects_to_pods(objs).get_Some_0(), cr));
+                    let j = lemma_filtered_pods_are_from_list_resp(objs, cr, i);
+                    assert(pod_is_a_copy_of_pod_in_etcd(objs[j].metadata, s));
+                }
+                lemma_pod_is_a_copy_of_pod_in_etcd_is_preserved(s, s_prime, step, pods[i].metadata);
+            }
+        }
+    }
+    init_invariant(spec, VRSCluster::init(), stronger_next, inv);
+}
+
+/// A reconciler only deletes a pod that it filters from a list response (at AfterListPods) or keeps in its local state
+/// (at AfterDeletePod), with the uid of the pod as the precondition, and then waits for the response at AfterDeletePod.
+/// Returns the metadata of the pod.
+pub proof fn lemma_delete_request_from_reconciler_carries_uid_of_owned_pod(s: VRSCluster, s_prime: VRSCluster, step: VRSStep, msg: VRSMessage) -> (metadata: ObjectMetaView)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        each_listed_pod_is_a_copy_of_pod_in_etcd()(s),
+        !s.in_flight().contains(msg),
+        s_prime.in_flight().contains(msg),
+        step.is_ControllerStep(),
+        msg.content.is_delete_request(),
+    ensures
+        ({
+            let cr_key = step.get_ControllerStep_0().1.get_Some_0();
+            let cr = s.ongoing_reconciles()[cr_key].triggering_cr;
+            &&& s.ongoing_reconciles().contains_key(cr_key)
+            &&& s_prime.ongoing_reconciles().contains_key(cr_key)
+            &&& s_prime.ongoing_reconciles()[cr_key].triggering_cr == cr
+            &&& s_prime.ongoing_reconciles()[cr_key].local_state.reconcile_step.is_AfterDeletePod()
+            &&& VRSCluster::pending_req_msg_is(s_prime, cr_key, msg)
+            &&& metadata.owner_references_contains(cr.controller_owner_ref())
+            &&& pod_is_a_copy_of_pod_in_etcd(metadata, s)
+            &&& msg.content.get_delete_request().preconditions == Some(PreconditionsView::default().set_uid_from_object_meta(metadata))
+        }),
+{
+    let cr_key = step.get_ControllerStep_0().1.get_Some_0();
+    let cr = s.ongoing_reconciles()[cr_key].triggering_cr;
+    let local_state = s.ongoing_reconciles()[cr_key].local_state;
+    assert(s.ongoing_reconciles().contains_key(cr_key));
+    match local_state.reconcile_step {
+        VReplicaSetReconcileStepView::AfterListPods => {
+            let resp_msg = step.get_ControllerStep_0().0.get_Some_0();
+            let objs = resp_msg.content.get_list_response().res.get_Ok_0();
+            let filtered = filter_pods(objects_to_pods(objs).get_Some_0(), cr);
+            let i = filtered.len() - cr.spec.replicas.unwrap_or(0) - 1;
+            assert(s.in_flight().contains(resp_msg));
+            let j = lemma_filtered_pods_are_from_list_resp(objs, cr, i);
+            assert(pod_is_a_copy_of_pod_in_etcd(objs[j].metadata, s));
+            filtered[i].metadata
+        },
+        VReplicaSetReconcileStepView::AfterDeletePod(diff) => {
+            let pods = local_state.filtered_pods.get_Some_0();
+            let i = diff - 1;
+            assert(pod_is_a_copy_of_pod_in_etcd(pods[i].metadata, s));
+            pods[i].metadata
+        },
+        _ => {
+            // The reconciler does not delete pods at the other steps.
+            assert(false);
+            arbitrary()
+        },
+    }
+}
+
+/// A delete request newly sent for a pod in the namespace of vrs is either the pending request of the reconciler of vrs
+/// at AfterDeletePod, or carries the uid of an object other than the pods owned by vrs:
+/// - The client never deletes objects.
+/// - The garbage collector only deletes an object when none of its owners is in etcd with the same uid, while vrs,
+///   the only owner of the pods it owns, stays in etcd (desired_state_is).
+/// - The kubelet only deletes the pods that already have a deletion timestamp.
+/// - The reconciler of another cr only deletes the pods owned by that cr, while a pod has only one owner.
+proof fn lemma_new_delete_pod_request_msg_cannot_delete_matching_pods(
+    vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, step: VRSStep, msg: VRSMessage
+)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        VRSCluster::no_orphan_or_foreground_deletion()(s),
+        each_cr_has_uid_allocated_in_its_namespace()(s),
+        each_pod_is_owned_by_one_object_in_its_namespace()(s),
+        each_listed_pod_is_a_copy_of_pod_in_etcd()(s),
+        desired_state_is(vrs)(s),
+        !s.in_flight().contains(msg),
+        s_prime.in_flight().contains(msg),
+        delete_pod_request_msg_in_namespace(vrs)(msg),
+    ensures
+        ({
+            let key = vrs.object_ref();
+            let req = msg.content.get_delete_request();
+            ||| {
+                &&& s_prime.ongoing_reconciles().contains_key(key)
+                &&& s_prime.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterDeletePod()
+                &&& VRSCluster::pending_req_msg_is(s_prime, key, msg)
+            }
+            ||| {
+                &&& req.preconditions.is_Some()
+                &&& req.preconditions.get_Some_0().uid.is_Some()
+                &&& req.preconditions.get_Some_0().uid.get_Some_0() < s_prime.kubernetes_api_state.uid_counter
+                &&& matching_pods(vrs, s_prime.resources()).contains(req.key)
+                    ==> s_prime.resources()[req.key].metadata.uid != req.preconditions.get_Some_0().uid
+            }
+        }),
+{
+    let key = vrs.object_ref();
+    let req = msg.content.get_delete_request();
+    VReplicaSetView::marshal_preserves_metadata();
+    match step {
+        Step::ControllerStep(input) => {
+            let cr_key = input.1.get_Some_0();
+            let cr = s.ongoing_reconciles()[cr_key].triggering_cr;
+            let metadata = lemma_delete_request_from_reconciler_carries_uid_of_owned_pod(s, s_prime, step, msg);
+            assert(s_prime.resources() == s.resources());
+            if cr_key != key && matching_pods(vrs, s.resources()).contains(req.key)
+                && s.resources()[req.key].metadata.uid == req.preconditions.get_Some_0().uid {
+                // The pod is owned by both cr and vrs while it has only one owner, so cr has the name and uid of vrs.
+                // The uid of cr is allocated in the namespace of cr_key, where vrs is, so cr_key is the key of vrs.
+                lemma_pod_owned_by_vrs_is_in_namespace_of_vrs(vrs, s, req.key);
+                assert(s.resources()[req.key].metadata.owner_references == metadata.owner_references);
+                assert(metadata.owner_references.get_Some_0().contains(cr.controller_owner_ref()));
+                assert(cr.controller_owner_ref() == vrs.controller_owner_ref());
+                assert(s.resources().contains_key(key));
+                assert(s.resources()[key].metadata.uid == Some(cr.metadata.uid.get_Some_0()));
+                assert(cr_key.namespace == key.namespace);
+                assert(cr_key == key);
+            }
+        },
+        Step::BuiltinControllersStep(input) => {
+            VRSCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+            assert(s_prime.resources() == s.resources());
+            if matching_pods(vrs, s.resources()).contains(req.key) && input.0.is_GarbageCollector() {
+                // vrs, the only owner of the pod, is in etcd with the same uid.
+                lemma_pod_owned_by_vrs_is_in_namespace_of_vrs(vrs, s, req.key);
+                let owner_refs = s.resources()[req.key].metadata.owner_references.get_Some_0();
+                assert(owner_reference_to_object_reference(owner_refs[0], req.key.namespace) == key);
+                assert(s.resources()[key].metadata.uid == Some(owner_refs[0].uid));
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Besides the reconciler of vrs, the other hosts never delete a pod owned by vrs (see
+/// lemma_new_delete_pod_request_msg_cannot_delete_matching_pods). A newly created pod gets a fresh uid from uid_counter,
+/// so it never satisfies the uid precondition of a delete request already in flight, and a pod never becomes owned and
+/// selected by vrs again after it gets a deletion timestamp. The delete requests sent earlier by the reconcile of an
+/// older vrs are eventually handled, which is why the invariant holds eventually.
+#[verifier(spinoff_prover)]
+pub proof fn lemma_eventually_always_no_other_delete_request_can_delete_matching_pods(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::external_api_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_etcd_is_well_formed()))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(VRSCluster::no_orphan_or_foreground_deletion()))),
+        spec.entails(always(lift_state(every_in_flight_req_msg_is_expected()))),
+        spec.entails(always(lift_state(each_cr_has_uid_allocated_in_its_namespace()))),
+        spec.entails(always(lift_state(each_pod_is_owned_by_one_object_in_its_namespace()))),
+        spec.entails(always(lift_state(each_listed_pod_is_a_copy_of_pod_in_etcd()))),
+        spec.entails(always(lift_state(desired_state_is(vrs)))),
+    ensures spec.entails(true_pred().leads_to(always(lift_state(no_other_delete_request_can_delete_matching_pods(vrs))))),
+{
+    let key = vrs.object_ref();
+    let requirements = |msg: VRSMessage, s: VRSCluster| {
+        delete_pod_request_msg_in_namespace(vrs)(msg) ==> {
+            ||| {
+                &&& s.ongoing_reconciles().contains_key(key)
+                &&& s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterDeletePod()
+                &&& VRSCluster::pending_req_msg_is(s, key, msg)
+            }
+            ||| {
+                let req = msg.content.get_delete_request();
+                &&& req.preconditions.is_Some()
+                &&& req.preconditions.get_Some_0().uid.is_Some()
+                &&& req.preconditions.get_Some_0().uid.get_Some_0() < s.kubernetes_api_state.uid_counter
+                &&& matching_pods(vrs, s.resources()).contains(req.key)
+                    ==> s.resources()[req.key].metadata.uid != req.preconditions.get_Some_0().uid
+            }
+        }
+    };
+    let stronger_next = |s: VRSCluster, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::crash_disabled()(s)
+        &&& VRSCluster::every_in_flight_msg_has_unique_id()(s)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& VRSCluster::no_orphan_or_foreground_deletion()(s)
+        &&& every_in_flight_req_msg_is_expected()(s)
+        &&& each_cr_has_uid_allocated_in_its_namespace()(s)
+        &&& each_pod_is_owned_by_one_object_in_its_namespace()(s)
+        &&& each_listed_pod_is_a_copy_of_pod_in_etcd()(s)
+        &&& desired_state_is(vrs)(s)
+    };
+    assert forall |s, s_prime| #[trigger] stronger_next(s, s_prime)
+    implies VRSCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)(s, s_prime) by {
+        assert forall |msg: VRSMessage| (!s.in_flight().contains(msg) || requirements(msg, s)) && #[trigger] s_prime.in_flight().contains(msg)
+        implies requirements(msg, s_prime) by {
+            if delete_pod_request_msg_in_namespace(vrs)(msg) {
+                let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+                let req = msg.content.get_delete_request();
+                if !s.in_flight().contains(msg) {
+                    lemma_new_delete_pod_request_msg_cannot_delete_matching_pods(vrs, s, s_prime, step, msg);
+                } else if s.ongoing_reconciles().contains_key(key)
+                    && s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterDeletePod()
+                    && VRSCluster::pending_req_msg_is(s, key, msg) {
+                    // The pending request is still in flight, so its response is not (unique ids) and the reconcile stays.
+                    assert(s.ongoing_reconciles()[key] == s_prime.ongoing_reconciles()[key]);
+                } else {
+                    // A new pod gets the uid counter as its uid, and a pod in etcd keeps its uid, owner references and
+                    // labels, and never loses its deletion timestamp.
+                    lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+                    if matching_pods(vrs, s_prime.resources()).contains(req.key) && s.resources().contains_key(req.key) {
+                        assert(matching_pods(vrs, s.resources()).contains(req.key));
+                    }
+                }
+            }
+        }
+    }
+    invariant_n!(
+        spec, lift_action(stronger_next), lift_action(VRSCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
+        lift_action(VRSCluster::next()), lift_state(VRSCluster::crash_disabled()),
+        lift_state(VRSCluster::every_in_flight_msg_has_unique_id()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::no_orphan_or_foreground_deletion()),
+        lift_state(every_in_flight_req_msg_is_expected()),
+        lift_state(each_cr_has_uid_allocated_in_its_namespace()),
+        lift_state(each_pod_is_owned_by_one_object_in_its_namespace()),
+        lift_state(each_listed_pod_is_a_copy_of_pod_in_etcd()),
+        lift_state(desired_state_is(vrs))
+    );
+
+    VRSCluster::lemma_true_leads_to_always_every_in_flight_req_msg_satisfies(spec, requirements);
+
+    temp_pred_equality(
+        lift_state(no_other_delete_request_can_delete_matching_pods(vrs)),
+        lift_state(VRSCluster::every_in_flight_req_msg_satisfies(requirements))
+    );
+}
+
+}
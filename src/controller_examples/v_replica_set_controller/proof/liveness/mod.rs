@@ -0,0 +1,6 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+pub mod proof;
+pub mod resource_match;
+pub mod spec;
+pub mod terminate;
@@ -0,0 +1,210 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, dynamic::*, owner_reference::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::{
+        helper_invariants,
+        liveness::{resource_match::*, spec::*, terminate},
+        predicate::*,
+    },
+    trusted::{liveness_theorem::*, spec_types::*, step::*},
+};
+use vstd::prelude::*;
+
+verus! {
+
+// We prove init /\ []next /\ []wf |= []desired_state_is(vrs) ~> []current_state_matches(vrs) holds for each vrs.
+proof fn liveness_proof_forall_vrs()
+    ensures liveness_theorem(),
+{
+    assert forall |vrs: VReplicaSetView| #[trigger] cluster_spec().entails(liveness(vrs)) by {
+        liveness_proof(vrs);
+    };
+    spec_entails_tla_forall(cluster_spec(), |vrs: VReplicaSetView| liveness(vrs));
+}
+
+proof fn liveness_proof(vrs: VReplicaSetView)
+    ensures cluster_spec().entails(liveness(vrs)),
+{
+    assumption_and_invariants_of_all_phases_is_stable(vrs);
+    lemma_true_leads_to_always_current_state_matches(vrs);
+    reveal_with_fuel(spec_before_phase_n, 5);
+    spec_before_phase_n_entails_true_leads_to_current_state_matches(4, vrs);
+    spec_before_phase_n_entails_true_leads_to_current_state_matches(3, vrs);
+    spec_before_phase_n_entails_true_leads_to_current_state_matches(2, vrs);
+    spec_before_phase_n_entails_true_leads_to_current_state_matches(1, vrs);
+
+    let assumption = always(lift_state(desired_state_is(vrs)));
+    unpack_conditions_from_spec(invariants(vrs), assumption, true_pred(), always(lift_state(current_state_matches(vrs))));
+    temp_pred_equality(true_pred().and(assumption), assumption);
+
+    valid_implies_trans(
+        cluster_spec().and(derived_invariants_since_beginning(vrs)), invariants(vrs),
+        always(lift_state(desired_state_is(vrs))).leads_to(always(lift_state(current_state_matches(vrs))))
+    );
+    sm_spec_entails_all_invariants(vrs);
+    simplify_predicate(cluster_spec(), derived_invariants_since_beginning(vrs));
+}
+
+proof fn spec_before_phase_n_entails_true_leads_to_current_state_matches(i: nat, vrs: VReplicaSetView)
+    requires
+        1 <= i <= 4,
+        valid(stable(spec_before_phase_n(i, vrs))),
+        spec_before_phase_n(i + 1, vrs).entails(true_pred().leads_to(always(lift_state(current_state_matches(vrs)))))
+    ensures spec_before_phase_n(i, vrs).entails(true_pred().leads_to(always(lift_state(current_state_matches(vrs))))),
+{
+    reveal_with_fuel(spec_before_phase_n, 5);
+    temp_pred_equality(spec_before_phase_n(i + 1, vrs), spec_before_phase_n(i, vrs).and(invariants_since_phase_n(i, vrs)));
+    spec_of_previous_phases_entails_eventually_new_invariants(i, vrs);
+    unpack_conditions_from_spec(spec_before_phase_n(i, vrs), invariants_since_phase_n(i, vrs), true_pred(), always(lift_state(current_state_matches(vrs))));
+    temp_pred_equality(true_pred().and(invariants_since_phase_n(i, vrs)), invariants_since_phase_n(i, vrs));
+    leads_to_trans_temp(spec_before_phase_n(i, vrs), true_pred(), invariants_since_phase_n(i, vrs), always(lift_state(current_state_matches(vrs))));
+}
+
+proof fn lemma_true_leads_to_always_current_state_matches(vrs: VReplicaSetView)
+    ensures assumption_and_invariants_of_all_phases(vrs).entails(true_pred().leads_to(always(lift_state(current_state_matches(vrs))))),
+{
+    let spec = assumption_and_invariants_of_all_phases(vrs);
+
+    // The cluster version of desired_state_is is needed by the lemmas borrowed from the cluster.
+    implies_preserved_by_always_temp(lift_state(desired_state_is(vrs)), lift_state(VRSCluster::desired_state_is(vrs)));
+    valid_implies_trans(spec, always(lift_state(desired_state_is(vrs))), always(lift_state(VRSCluster::desired_state_is(vrs))));
+    helper_invariants::lemma_always_vrs_is_well_formed(spec, vrs);
+
+    // The use of termination property ensures spec |= true ~> reconcile_idle.
+    terminate::reconcile_eventually_terminates(spec, vrs);
+    // Then we can continue to show that spec |= reconcile_idle ~> []current_state_matches(vrs).
+
+    // The following two lemmas show that spec |= reconcile_idle ~> init /\ no_pending_req.
+    lemma_from_reconcile_idle_to_scheduled(spec, vrs);
+    lemma_from_scheduled_to_init_step(spec, vrs);
+
+    // Then the reconciler lists the pods, and creates or deletes pods until the matching pods are as many as the replicas.
+    lemma_from_init_step_to_after_list_pods_step(spec, vrs);
+    lemma_from_after_list_pods_step_to_current_state_matches(spec, vrs);
+
+    leads_to_trans_n!(
+        spec, true_pred(), lift_state(|s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) }),
+        lift_state(|s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) && s.scheduled_reconciles().contains_key(vrs.object_ref())}),
+        lift_state(no_pending_req_at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::Init)),
+        lift_state(pending_req_in_flight_at_after_list_pods_step(vrs)),
+        lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs))
+    );
+
+    // Now we further prove stability: given true ~> current_state_matches_and_reconcile_changes_no_pods(vrs)
+    // we prove true ~> []current_state_matches(vrs).
+    lemma_always_stronger_next_with_invariants(spec, vrs);
+    lemma_current_state_matches_is_stable(spec, vrs, true_pred());
+}
+
+proof fn lemma_from_reconcile_idle_to_scheduled(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::schedule_controller_reconcile().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::desired_state_is(vrs)))),
+    ensures
+        spec.entails(lift_state(|s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) }).leads_to(lift_state(|s: VRSCluster| {
+            &&& !s.ongoing_reconciles().contains_key(vrs.object_ref())
+            &&& s.scheduled_reconciles().contains_key(vrs.object_ref())
+        }))),
+{
+    let pre = |s: VRSCluster| {
+        &&& !s.ongoing_reconciles().contains_key(vrs.object_ref())
+        &&& !s.scheduled_reconciles().contains_key(vrs.object_ref())
+    };
+    let post = |s: VRSCluster| {
+        &&& !s.ongoing_reconciles().contains_key(vrs.object_ref())
+        &&& s.scheduled_reconciles().contains_key(vrs.object_ref())
+    };
+    let input = vrs.object_ref();
+    VRSCluster::lemma_pre_leads_to_post_by_schedule_controller_reconcile_borrow_from_spec(spec, input, VRSCluster::next(), VRSCluster::desired_state_is(vrs), pre, post);
+    valid_implies_implies_leads_to(spec, lift_state(post), lift_state(post));
+    or_leads_to_combine_temp(spec, lift_state(pre), lift_state(post), lift_state(post));
+    temp_pred_equality(lift_state(pre).or(lift_state(post)), lift_state(|s: VRSCluster| {!s.ongoing_reconciles().contains_key(vrs.object_ref())}));
+}
+
+proof fn lemma_from_scheduled_to_init_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs)))),
+    ensures
+        spec.entails(lift_state(|s: VRSCluster| {
+            &&& !s.ongoing_reconciles().contains_key(vrs.object_ref())
+            &&& s.scheduled_reconciles().contains_key(vrs.object_ref())
+        }).leads_to(lift_state(no_pending_req_at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::Init)))),
+{
+    let pre = |s: VRSCluster| {
+        &&& !s.ongoing_reconciles().contains_key(vrs.object_ref())
+        &&& s.scheduled_reconciles().contains_key(vrs.object_ref())
+    };
+    let post = no_pending_req_at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::Init);
+    let input = (None, Some(vrs.object_ref()));
+    let stronger_next = |s, s_prime| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::crash_disabled()(s)
+        &&& VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata()(s)
+        &&& VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs)(s)
+    };
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::crash_disabled()),
+        lift_state(VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs))
+    );
+    VRSCluster::lemma_pre_leads_to_post_by_controller(spec, input, stronger_next, VRSCluster::run_scheduled_reconcile(), pre, post);
+}
+
+proof fn lemma_from_init_step_to_after_list_pods_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+    ensures
+        spec.entails(lift_state(no_pending_req_at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::Init))
+            .leads_to(lift_state(pending_req_in_flight_at_after_list_pods_step(vrs)))),
+{
+    let pre = no_pending_req_at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::Init);
+    let post = pending_req_in_flight_at_after_list_pods_step(vrs);
+    let input = (None, Some(vrs.object_ref()));
+    let stronger_next = |s, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::crash_disabled()(s)
+    };
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next), lift_action(VRSCluster::next()), lift_state(VRSCluster::crash_disabled())
+    );
+    assert forall |s, s_prime| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        match step {
+            Step::ControllerStep(input) => {
+                if input.1.get_Some_0() != vrs.object_ref() {
+                    assert(pre(s_prime));
+                } else {
+                    assert(post(s_prime));
+                }
+            },
+            _ => {
+                assert(pre(s_prime));
+            }
+        }
+    }
+    VRSCluster::lemma_pre_leads_to_post_by_controller(spec, input, stronger_next, VRSCluster::continue_reconcile(), pre, post);
+}
+
+}
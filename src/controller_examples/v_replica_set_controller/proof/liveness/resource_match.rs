@@ -0,0 +1,898 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, dynamic::*, owner_reference::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::{generated_name, lemma_generated_name_is_fresh},
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::{helper_invariants, predicate::*},
+    trusted::{liveness_theorem::*, spec_types::*, step::*},
+};
+use crate::vstd_ext::{map_lib::*, seq_lib::*};
+use vstd::prelude::*;
+
+verus! {
+
+/// All the invariants used by the wf1 lemmas in this file, so that each of them does not have to list the same invariants.
+pub open spec fn stronger_next_with_invariants(vrs: VReplicaSetView) -> ActionPred<VRSCluster> {
+    |s, s_prime: VRSCluster| {
+        &&& VRSCluster::next()(s, s_prime)
+        &&& VRSCluster::crash_disabled()(s)
+        &&& VRSCluster::busy_disabled()(s)
+        &&& VRSCluster::stale_read_disabled()(s)
+        &&& VRSCluster::every_in_flight_msg_has_unique_id()(s)
+        &&& VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()(s)
+        &&& VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())(s)
+        &&& VRSCluster::each_object_in_etcd_is_well_formed()(s)
+        &&& VRSCluster::etcd_is_finite()(s)
+        &&& VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)(s)
+        &&& desired_state_is(vrs)(s)
+        &&& helper_invariants::vrs_is_well_formed(vrs)(s)
+        &&& helper_invariants::generated_pod_names_are_not_taken(vrs)(s)
+        &&& helper_invariants::every_in_flight_req_msg_is_expected()(s)
+        &&& helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()(s)
+        &&& helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)(s)
+        &&& helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)(s)
+    }
+}
+
+pub proof fn lemma_always_stronger_next_with_invariants(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::busy_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_etcd_is_well_formed()))),
+        spec.entails(always(lift_state(VRSCluster::etcd_is_finite()))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)))),
+        spec.entails(always(lift_state(desired_state_is(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::vrs_is_well_formed(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::every_in_flight_req_msg_is_expected()))),
+        spec.entails(always(lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()))),
+        spec.entails(always(lift_state(helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)))),
+    ensures spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+{
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next_with_invariants(vrs)),
+        lift_action(VRSCluster::next()),
+        lift_state(VRSCluster::crash_disabled()),
+        lift_state(VRSCluster::busy_disabled()),
+        lift_state(VRSCluster::stale_read_disabled()),
+        lift_state(VRSCluster::every_in_flight_msg_has_unique_id()),
+        lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()),
+        lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(VRSCluster::etcd_is_finite()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)),
+        lift_state(desired_state_is(vrs)),
+        lift_state(helper_invariants::vrs_is_well_formed(vrs)),
+        lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs)),
+        lift_state(helper_invariants::every_in_flight_req_msg_is_expected()),
+        lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()),
+        lift_state(helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)),
+        lift_state(helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs))
+    );
+}
+
+pub proof fn lemma_from_after_list_pods_step_to_current_state_matches(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::busy_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_etcd_is_well_formed()))),
+        spec.entails(always(lift_state(VRSCluster::etcd_is_finite()))),
+        spec.entails(always(lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)))),
+        spec.entails(always(lift_state(desired_state_is(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::vrs_is_well_formed(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::every_in_flight_req_msg_is_expected()))),
+        spec.entails(always(lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()))),
+        spec.entails(always(lift_state(helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)))),
+        spec.entails(always(lift_state(helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)))),
+    ensures
+        spec.entails(lift_state(pending_req_in_flight_at_after_list_pods_step(vrs))
+            .leads_to(lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs)))),
+{
+    lemma_always_stronger_next_with_invariants(spec, vrs);
+    let pre = lift_state(pending_req_in_flight_at_after_list_pods_step(vrs));
+    let exists_resp_in_flight = lift_state(exists_resp_in_flight_at_after_list_pods_step(vrs));
+    let post = lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs));
+
+    let pre_and_req_in_flight = |req_msg| lift_state(req_msg_is_the_in_flight_list_req_at_after_list_pods_step(vrs, req_msg));
+    assert forall |req_msg| spec.entails(#[trigger] pre_and_req_in_flight(req_msg).leads_to(exists_resp_in_flight)) by {
+        lemma_from_list_req_to_receives_list_resp(spec, vrs, req_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_req_in_flight, exists_resp_in_flight);
+    assert_by(tla_exists(pre_and_req_in_flight) == pre, {
+        assert forall |ex| #[trigger] pre.satisfied_by(ex) implies tla_exists(pre_and_req_in_flight).satisfied_by(ex) by {
+            let req_msg = ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+            assert(pre_and_req_in_flight(req_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_req_in_flight), pre);
+    });
+
+    let pre_and_resp_in_flight = |resp_msg| lift_state(resp_msg_is_the_in_flight_list_resp_at_after_list_pods_step(vrs, resp_msg));
+    assert forall |resp_msg| spec.entails(#[trigger] pre_and_resp_in_flight(resp_msg).leads_to(post)) by {
+        lemma_from_list_resp_to_current_state_matches(spec, vrs, resp_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_resp_in_flight, post);
+    assert_by(tla_exists(pre_and_resp_in_flight) == exists_resp_in_flight, {
+        assert forall |ex| #[trigger] exists_resp_in_flight.satisfied_by(ex) implies tla_exists(pre_and_resp_in_flight).satisfied_by(ex) by {
+            let resp_msg = choose |resp_msg| {
+                &&& #[trigger] ex.head().in_flight().contains(resp_msg)
+                &&& Message::resp_msg_matches_req_msg(resp_msg, ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0())
+                &&& resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, ex.head().resources())
+            };
+            assert(pre_and_resp_in_flight(resp_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_resp_in_flight), exists_resp_in_flight);
+    });
+
+    leads_to_trans_n!(spec, pre, exists_resp_in_flight, post);
+}
+
+proof fn lemma_from_list_resp_to_current_state_matches(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, resp_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(resp_msg_is_the_in_flight_list_resp_at_after_list_pods_step(vrs, resp_msg))
+            .leads_to(lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs)))),
+{
+    let pre = lift_state(resp_msg_is_the_in_flight_list_resp_at_after_list_pods_step(vrs, resp_msg));
+    let post = lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs));
+    let pre_with_m = |m: nat| lift_state(resp_msg_is_the_in_flight_list_resp_with_m_matching_pods(vrs, resp_msg, m));
+
+    // Depending on the number of matching pods m, the reconciler either stops, or creates or deletes
+    // the pods one by one until there are as many matching pods as the replicas.
+    assert forall |m: nat| spec.entails(#[trigger] pre_with_m(m).leads_to(post)) by {
+        lemma_from_list_resp_with_m_matching_pods_to_next_step(spec, vrs, resp_msg, m);
+        if m < replicas(vrs) {
+            let n = (replicas(vrs) - m - 1) as nat;
+            lemma_from_after_create_pod_step_to_current_state_matches(spec, vrs, n);
+            leads_to_trans_n!(spec, pre_with_m(m), lift_state(pending_req_in_flight_at_after_create_pod_step(vrs, n)), post);
+        } else if m > replicas(vrs) {
+            let n = (m - replicas(vrs) - 1) as nat;
+            lemma_from_after_delete_pod_step_to_current_state_matches(spec, vrs, n);
+            leads_to_trans_n!(spec, pre_with_m(m), lift_state(pending_req_in_flight_at_after_delete_pod_step(vrs, n)), post);
+        }
+    }
+    leads_to_exists_intro(spec, pre_with_m, post);
+    assert_by(tla_exists(pre_with_m) == pre, {
+        assert forall |ex| #[trigger] pre.satisfied_by(ex) implies tla_exists(pre_with_m).satisfied_by(ex) by {
+            let m = matching_pods(vrs, ex.head().resources()).len();
+            assert(pre_with_m(m).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_with_m), pre);
+    });
+}
+
+proof fn lemma_from_after_create_pod_step_to_current_state_matches(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(pending_req_in_flight_at_after_create_pod_step(vrs, n))
+            .leads_to(lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs)))),
+    decreases n,
+{
+    let pre = lift_state(pending_req_in_flight_at_after_create_pod_step(vrs, n));
+    let exists_ok_resp_in_flight = lift_state(exists_ok_resp_in_flight_at_after_create_pod_step(vrs, n));
+    let next_step = lift_state(next_step_after_create_pod(vrs, n));
+    let post = lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs));
+
+    let pre_and_req_in_flight = |req_msg| lift_state(req_msg_is_the_in_flight_create_req_at_after_create_pod_step(vrs, n, req_msg));
+    assert forall |req_msg| spec.entails(#[trigger] pre_and_req_in_flight(req_msg).leads_to(exists_ok_resp_in_flight)) by {
+        lemma_from_create_req_to_receives_ok_resp(spec, vrs, n, req_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_req_in_flight, exists_ok_resp_in_flight);
+    assert_by(tla_exists(pre_and_req_in_flight) == pre, {
+        assert forall |ex| #[trigger] pre.satisfied_by(ex) implies tla_exists(pre_and_req_in_flight).satisfied_by(ex) by {
+            let req_msg = ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+            assert(pre_and_req_in_flight(req_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_req_in_flight), pre);
+    });
+
+    let pre_and_resp_in_flight = |resp_msg| lift_state(resp_msg_is_the_in_flight_ok_resp_at_after_create_pod_step(vrs, n, resp_msg));
+    assert forall |resp_msg| spec.entails(#[trigger] pre_and_resp_in_flight(resp_msg).leads_to(next_step)) by {
+        lemma_from_create_resp_to_next_step(spec, vrs, n, resp_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_resp_in_flight, next_step);
+    assert_by(tla_exists(pre_and_resp_in_flight) == exists_ok_resp_in_flight, {
+        assert forall |ex| #[trigger] exists_ok_resp_in_flight.satisfied_by(ex) implies tla_exists(pre_and_resp_in_flight).satisfied_by(ex) by {
+            let resp_msg = choose |resp_msg| {
+                &&& #[trigger] ex.head().in_flight().contains(resp_msg)
+                &&& Message::resp_msg_matches_req_msg(resp_msg, ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0())
+                &&& resp_msg.content.get_create_response().res.is_Ok()
+            };
+            assert(pre_and_resp_in_flight(resp_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_resp_in_flight), exists_ok_resp_in_flight);
+    });
+
+    leads_to_trans_n!(spec, pre, exists_ok_resp_in_flight, next_step);
+    if n > 0 {
+        lemma_from_after_create_pod_step_to_current_state_matches(spec, vrs, (n - 1) as nat);
+        leads_to_trans_n!(spec, pre, next_step, post);
+    }
+}
+
+proof fn lemma_from_after_delete_pod_step_to_current_state_matches(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(pending_req_in_flight_at_after_delete_pod_step(vrs, n))
+            .leads_to(lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs)))),
+    decreases n,
+{
+    let pre = lift_state(pending_req_in_flight_at_after_delete_pod_step(vrs, n));
+    let exists_ok_resp_in_flight = lift_state(exists_ok_resp_in_flight_at_after_delete_pod_step(vrs, n));
+    let next_step = lift_state(next_step_after_delete_pod(vrs, n));
+    let post = lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs));
+
+    let pre_and_req_in_flight = |req_msg| lift_state(req_msg_is_the_in_flight_delete_req_at_after_delete_pod_step(vrs, n, req_msg));
+    assert forall |req_msg| spec.entails(#[trigger] pre_and_req_in_flight(req_msg).leads_to(exists_ok_resp_in_flight)) by {
+        lemma_from_delete_req_to_receives_ok_resp(spec, vrs, n, req_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_req_in_flight, exists_ok_resp_in_flight);
+    assert_by(tla_exists(pre_and_req_in_flight) == pre, {
+        assert forall |ex| #[trigger] pre.satisfied_by(ex) implies tla_exists(pre_and_req_in_flight).satisfied_by(ex) by {
+            let req_msg = ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+            assert(pre_and_req_in_flight(req_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_req_in_flight), pre);
+    });
+
+    let pre_and_resp_in_flight = |resp_msg| lift_state(resp_msg_is_the_in_flight_ok_resp_at_after_delete_pod_step(vrs, n, resp_msg));
+    assert forall |resp_msg| spec.entails(#[trigger] pre_and_resp_in_flight(resp_msg).leads_to(next_step)) by {
+        lemma_from_delete_resp_to_next_step(spec, vrs, n, resp_msg);
+    }
+    leads_to_exists_intro(spec, pre_and_resp_in_flight, next_step);
+    assert_by(tla_exists(pre_and_resp_in_flight) == exists_ok_resp_in_flight, {
+        assert forall |ex| #[trigger] exists_ok_resp_in_flight.satisfied_by(ex) implies tla_exists(pre_and_resp_in_flight).satisfied_by(ex) by {
+            let resp_msg = choose |resp_msg| {
+                &&& #[trigger] ex.head().in_flight().contains(resp_msg)
+                &&& Message::resp_msg_matches_req_msg(resp_msg, ex.head().ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0())
+                &&& resp_msg.content.get_delete_response().res.is_Ok()
+            };
+            assert(pre_and_resp_in_flight(resp_msg).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(pre_and_resp_in_flight), exists_ok_resp_in_flight);
+    });
+
+    leads_to_trans_n!(spec, pre, exists_ok_resp_in_flight, next_step);
+    if n > 0 {
+        lemma_from_after_delete_pod_step_to_current_state_matches(spec, vrs, (n - 1) as nat);
+        leads_to_trans_n!(spec, pre, next_step, post);
+    }
+}
+
+proof fn lemma_from_list_req_to_receives_list_resp(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, req_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(req_msg_is_the_in_flight_list_req_at_after_list_pods_step(vrs, req_msg))
+            .leads_to(lift_state(exists_resp_in_flight_at_after_list_pods_step(vrs)))),
+{
+    let pre = req_msg_is_the_in_flight_list_req_at_after_list_pods_step(vrs, req_msg);
+    let post = exists_resp_in_flight_at_after_list_pods_step(vrs);
+    let input = Some(req_msg);
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        match step {
+            Step::ApiServerStep(input) => {
+                if input.get_Some_0() == req_msg {
+                    let resp_msg = VRSCluster::handle_list_request_msg(req_msg, s.kubernetes_api_state).1;
+                    lemma_list_pods_resp_is_ok_list_resp_of_matching_pods(vrs, s, req_msg);
+                    assert(s_prime.resources() == s.resources());
+                    assert({
+                        &&& s_prime.in_flight().contains(resp_msg)
+                        &&& Message::resp_msg_matches_req_msg(resp_msg, req_msg)
+                        &&& resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, s_prime.resources())
+                    });
+                    assert(post(s_prime));
+                }
+            },
+            _ => {}
+        }
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::kubernetes_api_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        let resp_msg = VRSCluster::handle_list_request_msg(req_msg, s.kubernetes_api_state).1;
+        lemma_list_pods_resp_is_ok_list_resp_of_matching_pods(vrs, s, req_msg);
+        assert(s_prime.resources() == s.resources());
+        assert({
+            &&& s_prime.in_flight().contains(resp_msg)
+            &&& Message::resp_msg_matches_req_msg(resp_msg, req_msg)
+            &&& resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, s_prime.resources())
+        });
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_kubernetes_api(spec, input, stronger_next, VRSCluster::handle_request(), pre, post);
+}
+
+#[verifier(spinoff_prover)]
+proof fn lemma_from_list_resp_with_m_matching_pods_to_next_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, resp_msg: VRSMessage, m: nat)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(resp_msg_is_the_in_flight_list_resp_with_m_matching_pods(vrs, resp_msg, m))
+            .leads_to(lift_state(next_step_after_list_pods(vrs, m)))),
+{
+    let pre = resp_msg_is_the_in_flight_list_resp_with_m_matching_pods(vrs, resp_msg, m);
+    let post = next_step_after_list_pods(vrs, m);
+    let input = (Some(resp_msg), Some(vrs.object_ref()));
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    // The list response keeps reflecting the matching pods until the controller handles it,
+    // because no one else changes the matching pods.
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+        match step {
+            Step::ControllerStep(input) => {
+                if input.1 == Some(vrs.object_ref()) && input.0 == Some(resp_msg) {
+                    lemma_from_list_resp_to_next_step_by_controller(vrs, s, s_prime, resp_msg, m);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::controller_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        lemma_from_list_resp_to_next_step_by_controller(vrs, s, s_prime, resp_msg, m);
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_controller(spec, input, stronger_next, VRSCluster::continue_reconcile(), pre, post);
+}
+
+proof fn lemma_from_list_resp_to_next_step_by_controller(vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, resp_msg: VRSMessage, m: nat)
+    requires
+        resp_msg_is_the_in_flight_list_resp_with_m_matching_pods(vrs, resp_msg, m)(s),
+        stronger_next_with_invariants(vrs)(s, s_prime),
+        VRSCluster::controller_next().forward((Some(resp_msg), Some(vrs.object_ref())))(s, s_prime),
+    ensures next_step_after_list_pods(vrs, m)(s_prime),
+{
+    let triggering_cr = s.ongoing_reconciles()[vrs.object_ref()].triggering_cr;
+    let objs = resp_msg.content.get_list_response().res.get_Ok_0();
+    let filtered = filter_pods(objects_to_pods(objs).get_Some_0(), vrs);
+    // The triggering cr only differs from vrs in the fields that do not affect the pods to create or delete.
+    assert(triggering_cr.controller_owner_ref() == vrs.controller_owner_ref());
+    assert(filter_pods(objects_to_pods(objs).get_Some_0(), triggering_cr) == filtered);
+    assert(make_pod(triggering_cr) == make_pod(vrs));
+    assert(s_prime.resources() == s.resources());
+    assert(filtered.len() == m);
+    if m > replicas(vrs) {
+        let n = (m - replicas(vrs) - 1) as nat;
+        assert forall |i: int| #![trigger filtered[i]] 0 <= i < n + 1 implies filtered[i].metadata.name.is_Some() by {
+            assert(first_n_pods_are_matching(vrs, filtered, filtered.len() as int, s.resources()));
+        }
+    }
+}
+
+proof fn lemma_from_create_req_to_receives_ok_resp(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat, req_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(req_msg_is_the_in_flight_create_req_at_after_create_pod_step(vrs, n, req_msg))
+            .leads_to(lift_state(exists_ok_resp_in_flight_at_after_create_pod_step(vrs, n)))),
+{
+    let pre = req_msg_is_the_in_flight_create_req_at_after_create_pod_step(vrs, n, req_msg);
+    let post = exists_ok_resp_in_flight_at_after_create_pod_step(vrs, n);
+    let input = Some(req_msg);
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        match step {
+            Step::ApiServerStep(input) => {
+                if input.get_Some_0() == req_msg {
+                    lemma_create_matching_pod_adds_one_matching_pod(vrs, s, s_prime, req_msg);
+                    let resp_msg = VRSCluster::handle_create_request_msg(req_msg, s.kubernetes_api_state).1;
+                    assert(s_prime.in_flight().contains(resp_msg));
+                    assert(post(s_prime));
+                } else {
+                    lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+                }
+            },
+            _ => {
+                lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+            }
+        }
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::kubernetes_api_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        lemma_create_matching_pod_adds_one_matching_pod(vrs, s, s_prime, req_msg);
+        let resp_msg = VRSCluster::handle_create_request_msg(req_msg, s.kubernetes_api_state).1;
+        assert(s_prime.in_flight().contains(resp_msg));
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_kubernetes_api(spec, input, stronger_next, VRSCluster::handle_request(), pre, post);
+}
+
+proof fn lemma_from_create_resp_to_next_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat, resp_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(resp_msg_is_the_in_flight_ok_resp_at_after_create_pod_step(vrs, n, resp_msg))
+            .leads_to(lift_state(next_step_after_create_pod(vrs, n)))),
+{
+    let pre = resp_msg_is_the_in_flight_ok_resp_at_after_create_pod_step(vrs, n, resp_msg);
+    let post = next_step_after_create_pod(vrs, n);
+    let input = (Some(resp_msg), Some(vrs.object_ref()));
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        // The pending create request has been handled, so every step keeps the matching pods.
+        lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+        match step {
+            Step::ControllerStep(input) => {
+                if input.1 == Some(vrs.object_ref()) && input.0 == Some(resp_msg) {
+                    let triggering_cr = s.ongoing_reconciles()[vrs.object_ref()].triggering_cr;
+                    assert(make_pod(triggering_cr) == make_pod(vrs));
+                }
+            },
+            _ => {}
+        }
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::controller_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        let triggering_cr = s.ongoing_reconciles()[vrs.object_ref()].triggering_cr;
+        assert(make_pod(triggering_cr) == make_pod(vrs));
+        assert(s_prime.resources() == s.resources());
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_controller(spec, input, stronger_next, VRSCluster::continue_reconcile(), pre, post);
+}
+
+proof fn lemma_from_delete_req_to_receives_ok_resp(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat, req_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(req_msg_is_the_in_flight_delete_req_at_after_delete_pod_step(vrs, n, req_msg))
+            .leads_to(lift_state(exists_ok_resp_in_flight_at_after_delete_pod_step(vrs, n)))),
+{
+    let pre = req_msg_is_the_in_flight_delete_req_at_after_delete_pod_step(vrs, n, req_msg);
+    let post = exists_ok_resp_in_flight_at_after_delete_pod_step(vrs, n);
+    let input = Some(req_msg);
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        match step {
+            Step::ApiServerStep(input) => {
+                if input.get_Some_0() == req_msg {
+                    lemma_delete_matching_pod_removes_one_matching_pod_at_after_delete_pod_step(vrs, s, s_prime, n, req_msg);
+                    assert(post(s_prime));
+                } else {
+                    lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+                }
+            },
+            _ => {
+                lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+            }
+        }
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::kubernetes_api_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        lemma_delete_matching_pod_removes_one_matching_pod_at_after_delete_pod_step(vrs, s, s_prime, n, req_msg);
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_kubernetes_api(spec, input, stronger_next, VRSCluster::handle_request(), pre, post);
+}
+
+proof fn lemma_delete_matching_pod_removes_one_matching_pod_at_after_delete_pod_step(
+    vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, n: nat, req_msg: VRSMessage
+)
+    requires
+        req_msg_is_the_in_flight_delete_req_at_after_delete_pod_step(vrs, n, req_msg)(s),
+        stronger_next_with_invariants(vrs)(s, s_prime),
+        VRSCluster::kubernetes_api_next().forward(Some(req_msg))(s, s_prime),
+    ensures exists_ok_resp_in_flight_at_after_delete_pod_step(vrs, n)(s_prime),
+{
+    let filtered_pods = s.ongoing_reconciles()[vrs.object_ref()].local_state.filtered_pods.get_Some_0();
+    let pod = filtered_pods[n as int];
+    lemma_delete_matching_pod_removes_one_matching_pod(vrs, s, s_prime, req_msg, pod);
+    let resp_msg = VRSCluster::handle_delete_request_msg(req_msg, s.kubernetes_api_state).1;
+    assert(s_prime.in_flight().contains(resp_msg));
+    // The first n pods have different keys from the deleted one, so they are still matching pods with the same uids.
+    assert forall |i: int| #![trigger filtered_pods[i]] 0 <= i < n implies {
+        &&& matching_pods(vrs, s_prime.resources()).contains(pod_key(vrs, filtered_pods[i]))
+        &&& s_prime.resources()[pod_key(vrs, filtered_pods[i])].metadata.uid == filtered_pods[i].metadata.uid
+    } by {
+        assert(pod_key(vrs, filtered_pods[i]) != pod_key(vrs, pod));
+    }
+}
+
+proof fn lemma_from_delete_resp_to_next_step(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat, resp_msg: VRSMessage)
+    requires
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+    ensures
+        spec.entails(lift_state(resp_msg_is_the_in_flight_ok_resp_at_after_delete_pod_step(vrs, n, resp_msg))
+            .leads_to(lift_state(next_step_after_delete_pod(vrs, n)))),
+{
+    let pre = resp_msg_is_the_in_flight_ok_resp_at_after_delete_pod_step(vrs, n, resp_msg);
+    let post = next_step_after_delete_pod(vrs, n);
+    let input = (Some(resp_msg), Some(vrs.object_ref()));
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) implies pre(s_prime) || post(s_prime) by {
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        // The pending delete request has been handled, so every step keeps the matching pods and their uids.
+        lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+    }
+
+    assert forall |s, s_prime: VRSCluster| pre(s) && #[trigger] stronger_next(s, s_prime) && VRSCluster::controller_next().forward(input)(s, s_prime)
+    implies post(s_prime) by {
+        assert(s_prime.resources() == s.resources());
+    }
+
+    VRSCluster::lemma_pre_leads_to_post_by_controller(spec, input, stronger_next, VRSCluster::continue_reconcile(), pre, post);
+}
+
+/// Once the matching pods are as many as the replicas and the reconcile does not change any pod, every later reconcile
+/// lists the same matching pods and stops at Done, so the matching pods stay as many as the replicas.
+pub proof fn lemma_current_state_matches_is_stable(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, p: TempPred<VRSCluster>)
+    requires
+        spec.entails(p.leads_to(lift_state(current_state_matches_and_reconcile_changes_no_pods(vrs)))),
+        spec.entails(always(lift_action(stronger_next_with_invariants(vrs)))),
+        spec.entails(always(lift_state(desired_state_is(vrs)))),
+    ensures spec.entails(p.leads_to(always(lift_state(current_state_matches(vrs))))),
+{
+    let post = current_state_matches_and_reconcile_changes_no_pods(vrs);
+    let stronger_next = stronger_next_with_invariants(vrs);
+
+    assert forall |s, s_prime: VRSCluster| post(s) && #[trigger] stronger_next(s, s_prime) implies post(s_prime) by {
+        let key = vrs.object_ref();
+        let step = choose |step| VRSCluster::next_step(s, s_prime, step);
+        lemma_matching_pods_are_unchanged_by_other_steps(vrs, s, s_prime, step);
+        match step {
+            Step::ApiServerStep(input) => {
+                let req_msg = input.get_Some_0();
+                if s.ongoing_reconciles().contains_key(key) && VRSCluster::pending_req_msg_is(s, key, req_msg) {
+                    lemma_list_pods_resp_is_ok_list_resp_of_matching_pods(vrs, s, req_msg);
+                }
+            },
+            Step::ControllerStep(input) => {
+                if input.1 == Some(key) && s.ongoing_reconciles().contains_key(key) {
+                    let triggering_cr = s.ongoing_reconciles()[key].triggering_cr;
+                    assert(triggering_cr.controller_owner_ref() == vrs.controller_owner_ref());
+                    // A new list request has a fresh id, so no response in flight matches it yet.
+                    if s.ongoing_reconciles()[key].local_state.reconcile_step == VReplicaSetReconcileStepView::Init {
+                        assert(triggering_cr.metadata.namespace == vrs.metadata.namespace);
+                        assert(triggering_cr.spec.selector == vrs.spec.selector);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    leads_to_stable_temp(spec, lift_action(stronger_next), p, lift_state(post));
+    assert_by(lift_state(post).and(lift_state(desired_state_is(vrs))).entails(lift_state(current_state_matches(vrs))), {
+        assert forall |ex| #[trigger] lift_state(post).and(lift_state(desired_state_is(vrs))).satisfied_by(ex)
+        implies lift_state(current_state_matches(vrs)).satisfied_by(ex) by {
+            assert(post(ex.head()));
+        }
+    });
+    leads_to_always_enhance(spec, lift_state(desired_state_is(vrs)), p, lift_state(post), lift_state(current_state_matches(vrs)));
+}
+
+/// The steps that do not handle the pending create or delete request of the reconciler of vrs.
+pub open spec fn step_does_not_handle_pending_create_or_delete_pod_req(vrs: VReplicaSetView, s: VRSCluster, step: VRSStep) -> bool {
+    let key = vrs.object_ref();
+    step.is_ApiServerStep()
+    && s.ongoing_reconciles().contains_key(key)
+    && (s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterCreatePod()
+        || s.ongoing_reconciles()[key].local_state.reconcile_step.is_AfterDeletePod())
+    ==> !VRSCluster::pending_req_msg_is(s, key, step.get_ApiServerStep_0().get_Some_0())
+}
+
+/// Only the API server changes the objects in etcd, and each request touches at most one pod:
+/// (1) a create request makes a matching pod only if it creates a pod owned by vrs in the namespace of vrs, which is the
+/// pending request at AfterCreatePod (every_create_matching_pod_request_implies_at_after_create_pod_step) and is excluded;
+/// (2) a delete request on a matching pod either is the pending request at AfterDeletePod (excluded) or carries a uid
+/// precondition that the pod does not satisfy (no_other_delete_request_can_delete_matching_pods), so it fails;
+/// (3) nobody updates pods or applies or patches objects (every_in_flight_req_msg_is_expected).
+/// A pod keeps its uid, owner references and labels, and never loses its deletion timestamp, so no pod in etcd
+/// becomes a matching pod and the uids of the matching pods are unchanged.
+pub proof fn lemma_matching_pods_are_unchanged_by_other_steps(vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, step: VRSStep)
+    requires
+        VRSCluster::next_step(s, s_prime, step),
+        step_does_not_handle_pending_create_or_delete_pod_req(vrs, s, step),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        desired_state_is(vrs)(s),
+        helper_invariants::every_in_flight_req_msg_is_expected()(s),
+        helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()(s),
+        helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)(s),
+        helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)(s),
+    ensures
+        matching_pods(vrs, s_prime.resources()) == matching_pods(vrs, s.resources()),
+        forall |key: ObjectRef| #[trigger] matching_pods(vrs, s.resources()).contains(key)
+            ==> s_prime.resources()[key].metadata.uid == s.resources()[key].metadata.uid,
+{
+    let key = vrs.object_ref();
+    helper_invariants::lemma_each_object_in_etcd_is_new_or_keeps_uid(s, s_prime, step);
+    VReplicaSetView::marshal_preserves_metadata();
+    assert forall |k: ObjectRef| #[trigger] matching_pods(vrs, s_prime.resources()).contains(k)
+    implies matching_pods(vrs, s.resources()).contains(k) by {
+        if !s.resources().contains_key(k) {
+            // The new pod is created by a request creating a pod owned by vrs. The uid of vrs is allocated in the
+            // namespace of the request, so it is a create_matching_pod_request_msg, which must be the pending request.
+            let req_msg = step.get_ApiServerStep_0().get_Some_0();
+            let req = req_msg.content.get_create_request();
+            let owner_refs = req.obj.metadata.owner_references.get_Some_0();
+            assert(helper_invariants::req_msg_is_expected(req_msg));
+            assert(owner_refs.contains(vrs.controller_owner_ref()));
+            assert(owner_refs[0] == vrs.controller_owner_ref());
+            assert(s.resources().contains_key(key));
+            assert(s.resources()[key].metadata.uid == Some(owner_refs[0].uid));
+            assert(req.namespace == key.namespace);
+            assert(helper_invariants::create_matching_pod_request_msg(vrs)(req_msg));
+            assert(false);
+        }
+    }
+    assert forall |k: ObjectRef| #[trigger] matching_pods(vrs, s.resources()).contains(k)
+    implies matching_pods(vrs, s_prime.resources()).contains(k) by {
+        assert(s.resources()[k].object_ref() == k);
+        match step {
+            Step::ApiServerStep(input) => {
+                let req_msg = input.get_Some_0();
+                assert(helper_invariants::req_msg_is_expected(req_msg));
+                if req_msg.content.is_delete_request() && req_msg.content.get_delete_request().key == k {
+                    // The request is not the pending one, so its uid precondition fails on the matching pod.
+                    helper_invariants::lemma_pod_owned_by_vrs_is_in_namespace_of_vrs(vrs, s, k);
+                    assert(helper_invariants::delete_pod_request_msg_in_namespace(vrs)(req_msg));
+                    assert(s_prime.resources() == s.resources());
+                }
+            },
+            _ => {}
+        }
+    }
+    assert(matching_pods(vrs, s_prime.resources()) =~= matching_pods(vrs, s.resources()));
+}
+
+/// The selector of vrs is valid (state_validation), so handle_list_request returns map_to_seq of the objects in etcd
+/// selected by the request, which are pods in the namespace of vrs and can be unmarshalled
+/// (each_object_in_etcd_is_well_formed). filter_pods keeps exactly the ones owned by vrs and selected by its selector
+/// without a deletion timestamp, i.e., the matching pods, since each pod owned by vrs is in the namespace of vrs
+/// (each_pod_is_owned_by_one_object_in_its_namespace). Since etcd is finite and the objects are listed by their sorted
+/// keys, lemma_map_to_seq_len_is_filtered_keys_len gives the number of filtered pods, and each key is listed once.
+pub proof fn lemma_list_pods_resp_is_ok_list_resp_of_matching_pods(vrs: VReplicaSetView, s: VRSCluster, req_msg: VRSMessage)
+    requires
+        req_msg.content.is_list_request(),
+        req_msg.content.get_list_request() == pods_list_request(vrs),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        VRSCluster::etcd_is_finite()(s),
+        desired_state_is(vrs)(s),
+        helper_invariants::vrs_is_well_formed(vrs)(s),
+        helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()(s),
+    ensures
+        resp_msg_is_ok_list_resp_of_matching_pods(vrs, VRSCluster::handle_list_request_msg(req_msg, s.kubernetes_api_state).1, s.resources()),
+{
+    let req = req_msg.content.get_list_request();
+    let resources = s.resources();
+    let selects = |o: DynamicObjectView| req.selects(o);
+    let unmarshal = |o: DynamicObjectView| PodView::unmarshal(o).unwrap();
+    let owned = |pod: PodView|
+        pod.metadata.owner_references_contains(vrs.controller_owner_ref())
+        && vrs.spec.selector.matches(pod.metadata.labels.unwrap_or(Map::empty()))
+        && pod.metadata.deletion_timestamp.is_None();
+    let owned_obj = |o: DynamicObjectView| owned(unmarshal(o));
+    let listed_and_owned = |o: DynamicObjectView| selects(o) && owned_obj(o);
+    lemma_object_ref_lt_is_strict_total_order();
+    lemma_sorted_keys_exists(resources, object_ref_order());
+    lemma_filter_auto::<DynamicObjectView>();
+    lemma_filter_auto::<PodView>();
+    let keys = sorted_keys(resources, object_ref_order());
+    let values = keys.map_values(|k: ObjectRef| resources[k]);
+    let objs = values.filter(selects);
+    assert(objs == VRSCluster::handle_list_request_msg(req_msg, s.kubernetes_api_state).1.content.get_list_response().res.get_Ok_0());
+
+    // Each listed object is the pod stored under its key in the namespace of vrs, so it can be unmarshalled.
+    assert forall |i: int| #![trigger objs[i]] 0 <= i < objs.len() implies {
+        &&& resources.contains_key(objs[i].object_ref())
+        &&& resources[objs[i].object_ref()] == objs[i]
+        &&& objs[i].kind == PodView::kind()
+        &&& objs[i].object_ref().namespace == vrs.metadata.namespace.get_Some_0()
+        &&& objs[i].metadata.name.is_Some()
+        &&& objs[i].metadata.uid.is_Some()
+        &&& PodView::unmarshal(objs[i]).is_Ok()
+    } by {
+        let k = lemma_map_to_seq_contains_implies_contains_value(resources, object_ref_order(), selects, i);
+        assert(resources[k].object_ref() == k);
+    }
+    let errs = objs.filter(|o: DynamicObjectView| PodView::unmarshal(o).is_err());
+    if errs.len() != 0 {
+        assert(objs.contains(errs[0]));
+    }
+    let pods = objs.map_values(unmarshal);
+    assert(objects_to_pods(objs) == Some(pods));
+    let filtered = pods.filter(owned);
+    assert(filtered == filter_pods(pods, vrs));
+
+    // The filtered pods are as many as the matching pods.
+    lemma_map_values_filter_len(objs, unmarshal, owned, owned_obj);
+    lemma_filter_of_filter(values, selects, owned_obj, listed_and_owned);
+    lemma_map_to_seq_len_is_filtered_keys_len(resources, object_ref_order(), listed_and_owned);
+    assert(Set::new(|k: ObjectRef| resources.contains_key(k) && listed_and_owned(resources[k])) =~= matching_pods(vrs, resources)) by {
+        assert forall |k: ObjectRef| #[trigger] matching_pods(vrs, resources).contains(k)
+        implies resources.contains_key(k) && listed_and_owned(resources[k]) by {
+            assert(resources[k].object_ref() == k);
+            helper_invariants::lemma_pod_owned_by_vrs_is_in_namespace_of_vrs(vrs, s, k);
+        }
+        assert forall |k: ObjectRef| resources.contains_key(k) && #[trigger] listed_and_owned(resources[k])
+        implies matching_pods(vrs, resources).contains(k) by {
+            assert(resources[k].object_ref() == k);
+        }
+    }
+
+    // Each filtered pod is a matching pod with the same uid in etcd.
+    assert forall |i: int| #![trigger filtered[i]] 0 <= i < filtered.len() implies {
+        &&& filtered[i].metadata.name.is_Some()
+        &&& filtered[i].metadata.uid.is_Some()
+        &&& matching_pods(vrs, resources).contains(pod_key(vrs, filtered[i]))
+        &&& resources[pod_key(vrs, filtered[i])].metadata.uid == filtered[i].metadata.uid
+    } by {
+        assert(pods.contains(filtered[i]));
+        let j = choose |j: int| 0 <= j < pods.len() && pods[j] == filtered[i];
+        assert(pod_key(vrs, filtered[i]) == objs[j].object_ref());
+    }
+
+    // Each key is listed once and each listed pod is unmarshalled from the object stored under its key,
+    // so the filtered pods have distinct keys.
+    assert forall |i: int, j: int| 0 <= i < values.len() && 0 <= j < values.len() && i != j implies values[i] != values[j] by {
+        assert(keys.to_set().contains(keys[i]) && keys.to_set().contains(keys[j]));
+        assert(resources[keys[i]].object_ref() == keys[i]);
+        assert(resources[keys[j]].object_ref() == keys[j]);
+    }
+    lemma_filter_preserves_no_duplicates(values, selects);
+    assert forall |i: int, j: int| 0 <= i < pods.len() && 0 <= j < pods.len() && i != j implies pods[i] != pods[j] by {
+        if pods[i] == pods[j] {
+            assert(objs[i].object_ref() == objs[j].object_ref());
+        }
+    }
+    lemma_filter_preserves_no_duplicates(pods, owned);
+    assert forall |i: int, j: int| #![trigger filtered[i], filtered[j]] 0 <= i < filtered.len() && 0 <= j < filtered.len() && i != j
+    implies pod_key(vrs, filtered[i]) != pod_key(vrs, filtered[j]) by {
+        if pod_key(vrs, filtered[i]) == pod_key(vrs, filtered[j]) {
+            assert(pods.contains(filtered[i]) && pods.contains(filtered[j]));
+            let a = choose |a: int| 0 <= a < pods.len() && pods[a] == filtered[i];
+            let b = choose |b: int| 0 <= b < pods.len() && pods[b] == filtered[j];
+            assert(objs[a].object_ref() == objs[b].object_ref());
+            assert(filtered[i] == filtered[j]);
+        }
+    }
+}
+
+/// The pod made by make_pod(vrs) has a generate_name, so the API server assigns it a generated name, which is fresh
+/// since no pod in the namespace takes it (generated_pod_names_are_not_taken and lemma_generated_name_is_fresh),
+/// and a fresh uid, and stores it as a new matching pod (lemma_make_pod_is_owned_and_selected);
+/// the request does not fail transiently since busy is disabled. No other object changes.
+pub proof fn lemma_create_matching_pod_adds_one_matching_pod(vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, req_msg: VRSMessage)
+    requires
+        VRSCluster::kubernetes_api_next().forward(Some(req_msg))(s, s_prime),
+        VRSCluster::busy_disabled()(s),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        VRSCluster::etcd_is_finite()(s),
+        helper_invariants::vrs_is_well_formed(vrs)(s),
+        helper_invariants::generated_pod_names_are_not_taken(vrs)(s),
+        s.in_flight().contains(req_msg),
+        req_msg.content.is_create_request(),
+        req_msg.content.get_create_request().namespace == vrs.metadata.namespace.get_Some_0(),
+        req_msg.content.get_create_request().obj == make_pod(vrs).marshal(),
+        matching_pods(vrs, s.resources()).finite(),
+    ensures
+        s_prime.in_flight().contains(VRSCluster::handle_create_request_msg(req_msg, s.kubernetes_api_state).1),
+        Message::resp_msg_matches_req_msg(VRSCluster::handle_create_request_msg(req_msg, s.kubernetes_api_state).1, req_msg),
+        VRSCluster::handle_create_request_msg(req_msg, s.kubernetes_api_state).1.content.get_create_response().res.is_Ok(),
+        matching_pods(vrs, s_prime.resources()).finite(),
+        matching_pods(vrs, s_prime.resources()).len() == matching_pods(vrs, s.resources()).len() + 1,
+{
+    let req = req_msg.content.get_create_request();
+    let pod = make_pod(vrs);
+    helper_invariants::lemma_make_pod_is_owned_and_selected(vrs);
+    PodView::marshal_spec_preserves_integrity();
+    PodView::marshal_status_preserves_integrity();
+    assert(pod.metadata.generate_name == Some(vrs.metadata.name.get_Some_0() + "-"@));
+    lemma_generated_name_is_fresh(s.kubernetes_api_state, PodView::kind(), req.namespace, vrs.metadata.name.get_Some_0() + "-"@);
+    let created_key = ObjectRef {
+        kind: PodView::kind(),
+        name: generated_name(s.kubernetes_api_state, vrs.metadata.name.get_Some_0() + "-"@),
+        namespace: req.namespace,
+    };
+    // The generated name is fresh, and the pod is valid: it has a spec and only one owner reference.
+    assert(!s.resources().contains_key(created_key));
+    assert(pod.spec.is_Some());
+    assert(pod.metadata.owner_references.get_Some_0().len() == 1);
+    let created_obj = s_prime.resources()[created_key];
+    assert(s_prime.resources() == s.resources().insert(created_key, created_obj));
+    assert(created_obj.metadata.owner_references == pod.metadata.owner_references);
+    assert(created_obj.metadata.labels == pod.metadata.labels);
+    assert(matching_pods(vrs, s_prime.resources()) =~= matching_pods(vrs, s.resources()).insert(created_key));
+}
+
+/// The pod exists with the uid in the precondition, so the API server deletes it, or marks it with a deletion
+/// timestamp if it has finalizers or a positive grace period (the request has no grace period, so the pod's
+/// terminationGracePeriodSeconds is used). Either way it is no longer a matching pod even though it may linger in etcd
+/// until the kubelet deletes it. No other object changes.
+pub proof fn lemma_delete_matching_pod_removes_one_matching_pod(vrs: VReplicaSetView, s: VRSCluster, s_prime: VRSCluster, req_msg: VRSMessage, pod: PodView)
+    requires
+        VRSCluster::kubernetes_api_next().forward(Some(req_msg))(s, s_prime),
+        VRSCluster::busy_disabled()(s),
+        VRSCluster::each_object_in_etcd_is_well_formed()(s),
+        s.in_flight().contains(req_msg),
+        req_msg.content.is_delete_request(),
+        req_msg.content.get_delete_request() == pod_delete_request(vrs, pod),
+        pod.metadata.uid.is_Some(),
+        matching_pods(vrs, s.resources()).contains(pod_key(vrs, pod)),
+        s.resources()[pod_key(vrs, pod)].metadata.uid == pod.metadata.uid,
+        matching_pods(vrs, s.resources()).finite(),
+    ensures
+        s_prime.in_flight().contains(VRSCluster::handle_delete_request_msg(req_msg, s.kubernetes_api_state).1),
+        Message::resp_msg_matches_req_msg(VRSCluster::handle_delete_request_msg(req_msg, s.kubernetes_api_state).1, req_msg),
+        VRSCluster::handle_delete_request_msg(req_msg, s.kubernetes_api_state).1.content.get_delete_response().res.is_Ok(),
+        matching_pods(vrs, s_prime.resources()) == matching_pods(vrs, s.resources()).remove(pod_key(vrs, pod)),
+        matching_pods(vrs, s_prime.resources()).finite(),
+        matching_pods(vrs, s_prime.resources()).len() == matching_pods(vrs, s.resources()).len() - 1,
+        forall |key: ObjectRef| #[trigger] matching_pods(vrs, s_prime.resources()).contains(key)
+            ==> s_prime.resources()[key].metadata.uid == s.resources()[key].metadata.uid,
+{
+    let key = pod_key(vrs, pod);
+    let obj = s.resources()[key];
+    // The uid precondition is satisfied, and the pod has no deletion timestamp, so the deletion is not pending.
+    assert(PreconditionsView::default().set_uid_from_object_meta(pod.metadata).satisfied_by(obj.metadata));
+    assert(obj.metadata.deletion_timestamp.is_None());
+    // The pod is either removed or stamped with a deletion timestamp, and no other object changes.
+    assert(!s_prime.resources().contains_key(key) || s_prime.resources()[key].metadata.deletion_timestamp.is_Some());
+    assert forall |k: ObjectRef| k != key implies
+        (#[trigger] s_prime.resources().contains_key(k) == s.resources().contains_key(k))
+        && (s.resources().contains_key(k) ==> s_prime.resources()[k] == s.resources()[k]) by {}
+    assert(matching_pods(vrs, s_prime.resources()) =~= matching_pods(vrs, s.resources()).remove(key));
+}
+
+}
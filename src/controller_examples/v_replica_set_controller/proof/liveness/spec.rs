@@ -0,0 +1,371 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, dynamic::*, owner_reference::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::{helper_invariants, liveness::terminate, predicate::*},
+    trusted::{liveness_theorem::*, spec_types::*, step::*},
+};
+use vstd::prelude::*;
+
+verus! {
+
+pub open spec fn assumption_and_invariants_of_all_phases(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    invariants(vrs)
+    .and(always(lift_state(desired_state_is(vrs))))
+    .and(invariants_since_phase_i(vrs))
+    .and(invariants_since_phase_ii(vrs))
+    .and(invariants_since_phase_iii(vrs))
+    .and(invariants_since_phase_iv(vrs))
+}
+
+pub open spec fn invariants_since_phase_n(n: nat, vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    if n == 0 {
+        invariants(vrs).and(always(lift_state(desired_state_is(vrs))))
+    } else if n == 1 {
+        invariants_since_phase_i(vrs)
+    } else if n == 2 {
+        invariants_since_phase_ii(vrs)
+    } else if n == 3 {
+        invariants_since_phase_iii(vrs)
+    } else if n == 4 {
+        invariants_since_phase_iv(vrs)
+    } else {
+        true_pred()
+    }
+}
+
+pub open spec fn spec_before_phase_n(n: nat, vrs: VReplicaSetView) -> TempPred<VRSCluster>
+    decreases n,
+{
+    if n == 1 {
+        invariants(vrs).and(always(lift_state(desired_state_is(vrs))))
+    } else if 2 <= n <= 5 {
+        spec_before_phase_n((n-1) as nat, vrs).and(invariants_since_phase_n((n-1) as nat, vrs))
+    } else {
+        true_pred()
+    }
+}
+
+pub proof fn spec_of_previous_phases_entails_eventually_new_invariants(i: nat, vrs: VReplicaSetView)
+    requires 1 <= i <= 4,
+    ensures spec_before_phase_n(i, vrs).entails(true_pred().leads_to(invariants_since_phase_n(i, vrs))),
+{
+    let spec = spec_before_phase_n(i, vrs);
+    reveal_with_fuel(spec_before_phase_n, 5);
+    implies_preserved_by_always_temp(lift_state(desired_state_is(vrs)), lift_state(VRSCluster::desired_state_is(vrs)));
+    valid_implies_trans(spec, always(lift_state(desired_state_is(vrs))), always(lift_state(VRSCluster::desired_state_is(vrs))));
+    if i == 1 {
+        VRSCluster::lemma_true_leads_to_crash_always_disabled(spec);
+        VRSCluster::lemma_true_leads_to_busy_always_disabled(spec);
+        VRSCluster::lemma_true_leads_to_always_the_object_in_schedule_has_spec_and_uid_as(spec, vrs);
+        leads_to_always_combine_n!(
+            spec,
+            true_pred(),
+            lift_state(VRSCluster::crash_disabled()),
+            lift_state(VRSCluster::busy_disabled()),
+            lift_state(VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs))
+        );
+    } else {
+        terminate::reconcile_eventually_terminates(spec, vrs);
+        if i == 2 {
+            VRSCluster::lemma_true_leads_to_always_the_object_in_reconcile_has_spec_and_uid_as(spec, vrs);
+        } else if i == 3 {
+            helper_invariants::lemma_always_vrs_is_well_formed(spec, vrs);
+            helper_invariants::lemma_eventually_always_every_create_matching_pod_request_implies_at_after_create_pod_step(spec, vrs);
+        } else if i == 4 {
+            helper_invariants::lemma_always_vrs_is_well_formed(spec, vrs);
+            helper_invariants::lemma_eventually_always_no_other_delete_request_can_delete_matching_pods(spec, vrs);
+        }
+    }
+}
+
+pub proof fn assumption_and_invariants_of_all_phases_is_stable(vrs: VReplicaSetView)
+    ensures
+        valid(stable(assumption_and_invariants_of_all_phases(vrs))),
+        valid(stable(invariants(vrs))),
+        forall |i: nat|  1 <= i <= 4 ==> valid(stable(#[trigger] spec_before_phase_n(i, vrs))),
+{
+    reveal_with_fuel(spec_before_phase_n, 4);
+    invariants_is_stable(vrs);
+    always_p_is_stable(lift_state(desired_state_is(vrs)));
+    invariants_since_phase_i_is_stable(vrs);
+    invariants_since_phase_ii_is_stable(vrs);
+    invariants_since_phase_iii_is_stable(vrs);
+    invariants_since_phase_iv_is_stable(vrs);
+    stable_and_n!(
+        invariants(vrs), always(lift_state(desired_state_is(vrs))),
+        invariants_since_phase_i(vrs), invariants_since_phase_ii(vrs), invariants_since_phase_iii(vrs),
+        invariants_since_phase_iv(vrs)
+    );
+}
+
+// Next and all the wf conditions.
+pub open spec fn next_with_wf() -> TempPred<VRSCluster> {
+    always(lift_action(VRSCluster::next()))
+    .and(tla_forall(|input| VRSCluster::kubernetes_api_next().weak_fairness(input)))
+    .and(tla_forall(|input| VRSCluster::external_api_next().weak_fairness(input)))
+    .and(tla_forall(|input| VRSCluster::controller_next().weak_fairness(input)))
+    .and(tla_forall(|input| VRSCluster::schedule_controller_reconcile().weak_fairness(input)))
+    .and(tla_forall(|input| VRSCluster::builtin_controllers_next().weak_fairness(input)))
+    .and(VRSCluster::disable_crash().weak_fairness(()))
+    .and(VRSCluster::disable_transient_failure().weak_fairness(()))
+}
+
+pub proof fn next_with_wf_is_stable()
+    ensures valid(stable(next_with_wf())),
+{
+    always_p_is_stable(lift_action(VRSCluster::next()));
+    VRSCluster::tla_forall_action_weak_fairness_is_stable(VRSCluster::kubernetes_api_next());
+    VRSCluster::tla_forall_action_weak_fairness_is_stable(VRSCluster::external_api_next());
+    VRSCluster::tla_forall_action_weak_fairness_is_stable(VRSCluster::controller_next());
+    VRSCluster::tla_forall_action_weak_fairness_is_stable(VRSCluster::schedule_controller_reconcile());
+    VRSCluster::tla_forall_action_weak_fairness_is_stable(VRSCluster::builtin_controllers_next());
+    VRSCluster::action_weak_fairness_is_stable(VRSCluster::disable_crash());
+    VRSCluster::action_weak_fairness_is_stable(VRSCluster::disable_transient_failure());
+    stable_and_n!(
+        always(lift_action(VRSCluster::next())),
+        tla_forall(|input| VRSCluster::kubernetes_api_next().weak_fairness(input)),
+        tla_forall(|input| VRSCluster::external_api_next().weak_fairness(input)),
+        tla_forall(|input| VRSCluster::controller_next().weak_fairness(input)),
+        tla_forall(|input| VRSCluster::schedule_controller_reconcile().weak_fairness(input)),
+        tla_forall(|input| VRSCluster::builtin_controllers_next().weak_fairness(input)),
+        VRSCluster::disable_crash().weak_fairness(()),
+        VRSCluster::disable_transient_failure().weak_fairness(())
+    );
+}
+
+/// This predicate combines all the possible actions (next), weak fairness and invariants that hold throughout the execution.
+/// We name it invariants here because these predicates are never violated, thus they can all be seen as some kind of invariants.
+///
+/// The final goal of our proof is to show init /\ invariants |= []desired_state_is(vrs) ~> []current_state_matches(vrs).
+/// init /\ invariants is equivalent to init /\ next /\ weak_fairness, so we get cluster_spec() |= []desired_state_is(vrs) ~> []current_state_matches(vrs).
+pub open spec fn invariants(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    next_with_wf().and(derived_invariants_since_beginning(vrs))
+}
+
+pub proof fn invariants_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(invariants(vrs))),
+{
+    next_with_wf_is_stable();
+    derived_invariants_since_beginning_is_stable(vrs);
+    stable_and_n!(
+        next_with_wf(),
+        derived_invariants_since_beginning(vrs)
+    );
+}
+
+// The safety invariants that are required to prove liveness.
+pub open spec fn derived_invariants_since_beginning(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))
+    .and(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref()))))
+    .and(always(lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator())))
+    .and(always(lift_state(VRSCluster::each_object_in_etcd_is_well_formed())))
+    .and(always(lift_state(VRSCluster::etcd_is_finite())))
+    .and(always(lift_state(VRSCluster::stale_read_disabled())))
+    .and(always(lift_state(VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata())))
+    .and(always(lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())))
+    .and(always(lift_state(VRSCluster::no_pending_req_msg_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::Init)))))
+    .and(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterListPods)))))
+    .and(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n)))))))
+    .and(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n)))))))
+    .and(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(vrs.object_ref()))))
+    .and(always(lift_state(helper_invariants::cr_objects_in_etcd_satisfy_state_validation())))
+    .and(always(lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs))))
+    .and(always(lift_state(VRSCluster::no_orphan_or_foreground_deletion())))
+    .and(always(lift_state(helper_invariants::every_in_flight_req_msg_is_expected())))
+    .and(always(lift_state(helper_invariants::each_object_in_etcd_has_unique_uid())))
+    .and(always(lift_state(helper_invariants::each_cr_has_uid_allocated_in_its_namespace())))
+    .and(always(lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace())))
+    .and(always(lift_state(helper_invariants::each_listed_pod_is_a_copy_of_pod_in_etcd())))
+}
+
+pub proof fn derived_invariants_since_beginning_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(derived_invariants_since_beginning(vrs))),
+{
+    let a_to_p_1 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))));
+    let a_to_p_2 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))));
+    stable_and_always_n!(
+        lift_state(VRSCluster::every_in_flight_msg_has_unique_id()),
+        lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())),
+        lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(VRSCluster::etcd_is_finite()),
+        lift_state(VRSCluster::stale_read_disabled()),
+        lift_state(VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::no_pending_req_msg_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::Init))),
+        lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterListPods))),
+        tla_forall(a_to_p_1),
+        tla_forall(a_to_p_2),
+        lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(vrs.object_ref())),
+        lift_state(helper_invariants::cr_objects_in_etcd_satisfy_state_validation()),
+        lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs)),
+        lift_state(VRSCluster::no_orphan_or_foreground_deletion()),
+        lift_state(helper_invariants::every_in_flight_req_msg_is_expected()),
+        lift_state(helper_invariants::each_object_in_etcd_has_unique_uid()),
+        lift_state(helper_invariants::each_cr_has_uid_allocated_in_its_namespace()),
+        lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()),
+        lift_state(helper_invariants::each_listed_pod_is_a_copy_of_pod_in_etcd())
+    );
+}
+
+/// The first notable phase comes when crash and k8s busy are always disabled and the object in schedule always has the same
+/// spec and uid as the cr we provide.
+///
+/// Note that don't try to find any connections between those invariants -- they are put together because they don't have to
+/// wait for another of them to first be satisfied.
+pub open spec fn invariants_since_phase_i(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    always(lift_state(VRSCluster::crash_disabled()))
+    .and(always(lift_state(VRSCluster::busy_disabled())))
+    .and(always(lift_state(VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs))))
+}
+
+pub proof fn invariants_since_phase_i_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(invariants_since_phase_i(vrs))),
+{
+    stable_and_always_n!(
+        lift_state(VRSCluster::crash_disabled()),
+        lift_state(VRSCluster::busy_disabled()),
+        lift_state(VRSCluster::the_object_in_schedule_has_spec_and_uid_as(vrs))
+    );
+}
+
+/// For now, phase II only contains one invariant, which is the object in reconcile has the same spec and uid as vrs.
+///
+/// It is alone because it relies on the invariant the_object_in_schedule_has_spec_and_uid_as (in phase I) and every invariant
+/// in phase III relies on it.
+pub open spec fn invariants_since_phase_ii(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    always(lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)))
+}
+
+pub proof fn invariants_since_phase_ii_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(invariants_since_phase_ii(vrs))),
+{
+    always_p_is_stable(lift_state(VRSCluster::the_object_in_reconcile_has_spec_and_uid_as(vrs)));
+}
+
+/// Once every reconcile of vrs is triggered by the current vrs, the only requests creating pods owned by vrs are the
+/// pending requests at AfterCreatePod, which create make_pod(vrs).
+pub open spec fn invariants_since_phase_iii(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    always(lift_state(helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)))
+}
+
+pub proof fn invariants_since_phase_iii_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(invariants_since_phase_iii(vrs))),
+{
+    always_p_is_stable(lift_state(helper_invariants::every_create_matching_pod_request_implies_at_after_create_pod_step(vrs)));
+}
+
+/// Invariants since phase IV say that no one except the reconciler of vrs deletes the pods owned by vrs. The pods that
+/// other requests were about to delete may be created before phase III, so this phase has to come after phase III.
+pub open spec fn invariants_since_phase_iv(vrs: VReplicaSetView) -> TempPred<VRSCluster> {
+    always(lift_state(helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)))
+}
+
+pub proof fn invariants_since_phase_iv_is_stable(vrs: VReplicaSetView)
+    ensures valid(stable(invariants_since_phase_iv(vrs))),
+{
+    always_p_is_stable(lift_state(helper_invariants::no_other_delete_request_can_delete_matching_pods(vrs)));
+}
+
+pub proof fn lemma_always_for_all_n_pending_req_in_flight_or_resp_in_flight_at_after_create_and_delete_pod_step(
+    spec: TempPred<VRSCluster>, vrs: VReplicaSetView
+)
+    requires
+        spec.entails(lift_state(VRSCluster::init())),
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+    ensures
+        spec.entails(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))))))),
+        spec.entails(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))))))),
+{
+    let a_to_p_1 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))));
+    assert_by(spec.entails(always(tla_forall(a_to_p_1))), {
+        assert forall |n: nat| spec.entails(always(#[trigger] a_to_p_1(n))) by {
+            VRSCluster::lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                spec, vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))
+            );
+        }
+        spec_entails_always_tla_forall(spec, a_to_p_1);
+    });
+    let a_to_p_2 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))));
+    assert_by(spec.entails(always(tla_forall(a_to_p_2))), {
+        assert forall |n: nat| spec.entails(always(#[trigger] a_to_p_2(n))) by {
+            VRSCluster::lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                spec, vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))
+            );
+        }
+        spec_entails_always_tla_forall(spec, a_to_p_2);
+    });
+}
+
+pub proof fn sm_spec_entails_all_invariants(vrs: VReplicaSetView)
+    ensures cluster_spec().entails(derived_invariants_since_beginning(vrs)),
+{
+    let spec = cluster_spec();
+    // Adding two assertions to make the verification faster because all the lemmas below require the two preconditions.
+    // And then the verifier doesn't have to infer it every time applying those lemmas.
+    assert(spec.entails(lift_state(VRSCluster::init())));
+    assert(spec.entails(always(lift_action(VRSCluster::next()))));
+    VRSCluster::lemma_always_every_in_flight_msg_has_unique_id(spec);
+    VRSCluster::lemma_always_pending_req_of_key_is_unique_with_unique_id(spec, vrs.object_ref());
+    VRSCluster::lemma_always_every_in_flight_msg_has_lower_id_than_allocator(spec);
+    VRSCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    VRSCluster::lemma_always_etcd_is_finite(spec);
+    VRSCluster::lemma_always_stale_read_disabled(spec);
+    VRSCluster::lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec);
+    VRSCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    VRSCluster::lemma_always_no_pending_req_msg_at_reconcile_state(spec, vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::Init));
+    VRSCluster::lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_state(spec, vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterListPods));
+    let a_to_p_1 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))));
+    let a_to_p_2 = |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))));
+    lemma_always_for_all_n_pending_req_in_flight_or_resp_in_flight_at_after_create_and_delete_pod_step(spec, vrs);
+    helper_invariants::lemma_always_the_object_in_reconcile_satisfies_state_validation(spec, vrs.object_ref());
+    helper_invariants::lemma_always_cr_objects_in_etcd_satisfy_state_validation(spec);
+    helper_invariants::lemma_always_generated_pod_names_are_not_taken(spec, vrs);
+    helper_invariants::lemma_always_no_orphan_or_foreground_deletion(spec);
+    helper_invariants::lemma_always_every_in_flight_req_msg_is_expected(spec);
+    helper_invariants::lemma_always_each_object_in_etcd_has_unique_uid(spec);
+    helper_invariants::lemma_always_each_cr_has_uid_allocated_in_its_namespace(spec);
+    helper_invariants::lemma_always_each_pod_is_owned_by_one_object_in_its_namespace(spec);
+    helper_invariants::lemma_always_each_listed_pod_is_a_copy_of_pod_in_etcd(spec);
+
+    entails_always_and_n!(
+        spec,
+        lift_state(VRSCluster::every_in_flight_msg_has_unique_id()),
+        lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())),
+        lift_state(VRSCluster::every_in_flight_msg_has_lower_id_than_allocator()),
+        lift_state(VRSCluster::each_object_in_etcd_is_well_formed()),
+        lift_state(VRSCluster::etcd_is_finite()),
+        lift_state(VRSCluster::stale_read_disabled()),
+        lift_state(VRSCluster::each_scheduled_object_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(VRSCluster::no_pending_req_msg_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::Init))),
+        lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterListPods))),
+        tla_forall(a_to_p_1),
+        tla_forall(a_to_p_2),
+        lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(vrs.object_ref())),
+        lift_state(helper_invariants::cr_objects_in_etcd_satisfy_state_validation()),
+        lift_state(helper_invariants::generated_pod_names_are_not_taken(vrs)),
+        lift_state(VRSCluster::no_orphan_or_foreground_deletion()),
+        lift_state(helper_invariants::every_in_flight_req_msg_is_expected()),
+        lift_state(helper_invariants::each_object_in_etcd_has_unique_uid()),
+        lift_state(helper_invariants::each_cr_has_uid_allocated_in_its_namespace()),
+        lift_state(helper_invariants::each_pod_is_owned_by_one_object_in_its_namespace()),
+        lift_state(helper_invariants::each_listed_pod_is_a_copy_of_pod_in_etcd())
+    );
+}
+
+}
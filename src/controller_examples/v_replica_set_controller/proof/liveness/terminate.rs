@@ -0,0 +1,236 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::EmptyAPI;
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, dynamic::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    cluster::*,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::reconciler::spec::reconciler::*;
+use crate::temporal_logic::{defs::*, rules::*};
+use crate::v_replica_set_controller::{
+    model::reconciler::*,
+    proof::predicate::*,
+    trusted::{spec_types::*, step::*},
+};
+use vstd::prelude::*;
+
+verus! {
+
+pub proof fn reconcile_eventually_terminates(spec: TempPred<VRSCluster>, vrs: VReplicaSetView)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::external_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::busy_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+        spec.entails(always(lift_state(VRSCluster::no_pending_req_msg_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::Init))))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterListPods))))),
+        spec.entails(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))))))),
+        spec.entails(always(tla_forall(|n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))))))),
+    ensures spec.entails(true_pred().leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+{
+    assert forall |n: nat| #![auto]
+    spec.entails(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+        vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))
+    )))) by {
+        always_tla_forall_apply::<VRSCluster, nat>(
+            spec, |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))
+            )), n
+        );
+    }
+    assert forall |n: nat| #![auto]
+    spec.entails(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+        vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))
+    )))) by {
+        always_tla_forall_apply::<VRSCluster, nat>(
+            spec, |n: nat| lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))
+            )), n
+        );
+    }
+    let reconcile_idle = |s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) };
+
+    // First, prove that reconcile_done \/ reconcile_error \/ reconcile_ide ~> reconcile_idle.
+    // Here we simply apply a cluster lemma which uses the wf1 of end_reconcile action.
+    VRSCluster::lemma_reconcile_error_leads_to_reconcile_idle(spec, vrs.object_ref());
+    VRSCluster::lemma_reconcile_done_leads_to_reconcile_idle(spec, vrs.object_ref());
+    temp_pred_equality(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)), lift_state(VRSCluster::reconciler_reconcile_done(vrs.object_ref())));
+    temp_pred_equality(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)), lift_state(VRSCluster::reconciler_reconcile_error(vrs.object_ref())));
+    valid_implies_implies_leads_to(spec, lift_state(reconcile_idle), lift_state(reconcile_idle));
+
+    // Second, prove that every AfterCreatePod(n) and AfterDeletePod(n) step leads to reconcile idle by induction on n,
+    // since the reconciler always counts n down to zero (or stops with an error) before it reaches Done.
+    let at_after_create_pod_step = |n: nat| lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n)));
+    let at_after_delete_pod_step = |n: nat| lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n)));
+    assert forall |n: nat| spec.entails(#[trigger] at_after_create_pod_step(n).leads_to(lift_state(reconcile_idle))) by {
+        lemma_from_after_create_pod_step_to_reconcile_idle(spec, vrs, n);
+    }
+    assert forall |n: nat| spec.entails(#[trigger] at_after_delete_pod_step(n).leads_to(lift_state(reconcile_idle))) by {
+        lemma_from_after_delete_pod_step_to_reconcile_idle(spec, vrs, n);
+    }
+    leads_to_exists_intro(spec, at_after_create_pod_step, lift_state(reconcile_idle));
+    leads_to_exists_intro(spec, at_after_delete_pod_step, lift_state(reconcile_idle));
+    assert_by(tla_exists(at_after_create_pod_step) == lift_state(at_any_create_pod_step_state_pred(vrs)), {
+        assert forall |ex| #[trigger] lift_state(at_any_create_pod_step_state_pred(vrs)).satisfied_by(ex)
+        implies tla_exists(at_after_create_pod_step).satisfied_by(ex) by {
+            let n = ex.head().ongoing_reconciles()[vrs.object_ref()].local_state.reconcile_step.get_AfterCreatePod_0();
+            assert(at_after_create_pod_step(n).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(at_after_create_pod_step), lift_state(at_any_create_pod_step_state_pred(vrs)));
+    });
+    assert_by(tla_exists(at_after_delete_pod_step) == lift_state(at_any_delete_pod_step_state_pred(vrs)), {
+        assert forall |ex| #[trigger] lift_state(at_any_delete_pod_step_state_pred(vrs)).satisfied_by(ex)
+        implies tla_exists(at_after_delete_pod_step).satisfied_by(ex) by {
+            let n = ex.head().ongoing_reconciles()[vrs.object_ref()].local_state.reconcile_step.get_AfterDeletePod_0();
+            assert(at_after_delete_pod_step(n).satisfied_by(ex));
+        }
+        temp_pred_equality(tla_exists(at_after_delete_pod_step), lift_state(at_any_delete_pod_step_state_pred(vrs)));
+    });
+
+    // Third, prove that AfterListPods leads to reconcile idle since it goes to one of the steps above.
+    let state_after_list_pods = |s: VReplicaSetReconcileState| {
+        s.reconcile_step == VReplicaSetReconcileStepView::Done
+        || s.reconcile_step == VReplicaSetReconcileStepView::Error
+        || s.reconcile_step.is_AfterCreatePod()
+        || s.reconcile_step.is_AfterDeletePod()
+    };
+    or_leads_to_combine_and_equality!(
+        spec, lift_state(VRSCluster::at_expected_reconcile_states(vrs.object_ref(), state_after_list_pods)),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)),
+        lift_state(at_any_create_pod_step_state_pred(vrs)),
+        lift_state(at_any_delete_pod_step_state_pred(vrs));
+        lift_state(reconcile_idle)
+    );
+    VRSCluster::lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(spec, vrs, at_step_closure(VReplicaSetReconcileStepView::AfterListPods), state_after_list_pods);
+
+    // Fourth, prove that reconcile init state can reach AfterListPods.
+    VRSCluster::lemma_from_init_state_to_next_state_to_reconcile_idle(spec, vrs, at_step_closure(VReplicaSetReconcileStepView::Init), at_step_closure(VReplicaSetReconcileStepView::AfterListPods));
+
+    // Finally, combine all cases.
+    or_leads_to_combine_and_equality!(
+        spec,
+        true_pred(),
+        lift_state(reconcile_idle),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Init)),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterListPods)),
+        lift_state(at_any_create_pod_step_state_pred(vrs)),
+        lift_state(at_any_delete_pod_step_state_pred(vrs)),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+        lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error));
+        lift_state(reconcile_idle)
+    );
+}
+
+pub open spec fn at_step_state_pred(vrs: VReplicaSetView, step: VReplicaSetReconcileStepView) -> StatePred<VRSCluster> {
+    VRSCluster::at_expected_reconcile_states(vrs.object_ref(), |s: VReplicaSetReconcileState| s.reconcile_step == step)
+}
+
+pub open spec fn at_any_create_pod_step_state_pred(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    VRSCluster::at_expected_reconcile_states(vrs.object_ref(), |s: VReplicaSetReconcileState| s.reconcile_step.is_AfterCreatePod())
+}
+
+pub open spec fn at_any_delete_pod_step_state_pred(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    VRSCluster::at_expected_reconcile_states(vrs.object_ref(), |s: VReplicaSetReconcileState| s.reconcile_step.is_AfterDeletePod())
+}
+
+proof fn lemma_from_after_create_pod_step_to_reconcile_idle(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::external_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::busy_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+        forall |n: nat| #![auto]
+            spec.entails(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n))
+            )))),
+        spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+        spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+    ensures spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n))).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+    decreases n,
+{
+    let reconcile_idle = |s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) };
+    let state_after_create_pod = |s: VReplicaSetReconcileState| {
+        s.reconcile_step == VReplicaSetReconcileStepView::Done
+        || s.reconcile_step == VReplicaSetReconcileStepView::Error
+        || (n > 0 && s.reconcile_step == VReplicaSetReconcileStepView::AfterCreatePod((n - 1) as nat))
+    };
+    if n > 0 {
+        lemma_from_after_create_pod_step_to_reconcile_idle(spec, vrs, (n - 1) as nat);
+        or_leads_to_combine_and_equality!(
+            spec, lift_state(VRSCluster::at_expected_reconcile_states(vrs.object_ref(), state_after_create_pod)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterCreatePod((n - 1) as nat)));
+            lift_state(reconcile_idle)
+        );
+    } else {
+        or_leads_to_combine_and_equality!(
+            spec, lift_state(VRSCluster::at_expected_reconcile_states(vrs.object_ref(), state_after_create_pod)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error));
+            lift_state(reconcile_idle)
+        );
+    }
+    VRSCluster::lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(spec, vrs, at_step_closure(VReplicaSetReconcileStepView::AfterCreatePod(n)), state_after_create_pod);
+}
+
+proof fn lemma_from_after_delete_pod_step_to_reconcile_idle(spec: TempPred<VRSCluster>, vrs: VReplicaSetView, n: nat)
+    requires
+        spec.entails(always(lift_action(VRSCluster::next()))),
+        spec.entails(tla_forall(|i| VRSCluster::kubernetes_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::external_api_next().weak_fairness(i))),
+        spec.entails(tla_forall(|i| VRSCluster::controller_next().weak_fairness(i))),
+        spec.entails(always(lift_state(VRSCluster::crash_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::busy_disabled()))),
+        spec.entails(always(lift_state(VRSCluster::every_in_flight_msg_has_unique_id()))),
+        spec.entails(always(lift_state(VRSCluster::pending_req_of_key_is_unique_with_unique_id(vrs.object_ref())))),
+        forall |n: nat| #![auto]
+            spec.entails(always(lift_state(VRSCluster::pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
+                vrs.object_ref(), at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n))
+            )))),
+        spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+        spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+    ensures spec.entails(lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n))).leads_to(lift_state(|s: VRSCluster| !s.ongoing_reconciles().contains_key(vrs.object_ref())))),
+    decreases n,
+{
+    let reconcile_idle = |s: VRSCluster| { !s.ongoing_reconciles().contains_key(vrs.object_ref()) };
+    let state_after_delete_pod = |s: VReplicaSetReconcileState| {
+        s.reconcile_step == VReplicaSetReconcileStepView::Done
+        || s.reconcile_step == VReplicaSetReconcileStepView::Error
+        || (n > 0 && s.reconcile_step == VReplicaSetReconcileStepView::AfterDeletePod((n - 1) as nat))
+    };
+    if n > 0 {
+        lemma_from_after_delete_pod_step_to_reconcile_idle(spec, vrs, (n - 1) as nat);
+        or_leads_to_combine_and_equality!(
+            spec, lift_state(VRSCluster::at_expected_reconcile_states(vrs.object_ref(), state_after_delete_pod)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::AfterDeletePod((n - 1) as nat)));
+            lift_state(reconcile_idle)
+        );
+    } else {
+        or_leads_to_combine_and_equality!(
+            spec, lift_state(VRSCluster::at_expected_reconcile_states(vrs.object_ref(), state_after_delete_pod)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Done)),
+            lift_state(at_step_state_pred(vrs, VReplicaSetReconcileStepView::Error));
+            lift_state(reconcile_idle)
+        );
+    }
+    VRSCluster::lemma_from_some_state_to_arbitrary_next_state_to_reconcile_idle(spec, vrs, at_step_closure(VReplicaSetReconcileStepView::AfterDeletePod(n)), state_after_delete_pod);
+}
+
+}
@@ -0,0 +1,5 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+pub mod helper_invariants;
+pub mod liveness;
+pub mod predicate;
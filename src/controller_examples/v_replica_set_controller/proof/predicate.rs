@@ -0,0 +1,392 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::{EmptyAPI, EmptyTypeView};
+use crate::kubernetes_api_objects::spec::{
+    api_method::*, common::*, label_selector::*, prelude::*, resource::*,
+};
+use crate::kubernetes_cluster::spec::{
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerStep},
+    message::*,
+};
+use crate::temporal_logic::defs::*;
+use crate::v_replica_set_controller::model::reconciler::*;
+use crate::v_replica_set_controller::trusted::{liveness_theorem::*, spec_types::*, step::*};
+use vstd::prelude::*;
+
+verus! {
+
+/// The keys of the pods that count towards the replicas of vrs.
+pub open spec fn matching_pods(vrs: VReplicaSetView, resources: StoredState) -> Set<ObjectRef> {
+    Set::new(|key: ObjectRef| owned_selector_match_is(vrs, resources, key))
+}
+
+pub open spec fn num_matching_pods_is(vrs: VReplicaSetView, n: int) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& matching_pods(vrs, s.resources()).finite()
+        &&& matching_pods(vrs, s.resources()).len() == n
+    }
+}
+
+pub open spec fn replicas(vrs: VReplicaSetView) -> int {
+    vrs.spec.replicas.unwrap_or(0)
+}
+
+pub open spec fn at_vrs_step(key: ObjectRef, step: VReplicaSetReconcileStepView) -> StatePred<VRSCluster>
+    recommends
+        key.kind == VReplicaSetView::kind()
+{
+    |s: VRSCluster| {
+        &&& s.ongoing_reconciles().contains_key(key)
+        &&& s.ongoing_reconciles()[key].local_state.reconcile_step == step
+    }
+}
+
+pub open spec fn at_vrs_step_with_vrs(vrs: VReplicaSetView, step: VReplicaSetReconcileStepView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& s.ongoing_reconciles().contains_key(vrs.object_ref())
+        &&& s.ongoing_reconciles()[vrs.object_ref()].triggering_cr.object_ref() == vrs.object_ref()
+        &&& s.ongoing_reconciles()[vrs.object_ref()].triggering_cr.spec() == vrs.spec()
+        &&& s.ongoing_reconciles()[vrs.object_ref()].triggering_cr.metadata().uid == vrs.metadata().uid
+        &&& s.ongoing_reconciles()[vrs.object_ref()].local_state.reconcile_step == step
+    }
+}
+
+pub open spec fn no_pending_req_at_vrs_step_with_vrs(vrs: VReplicaSetView, step: VReplicaSetReconcileStepView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& at_vrs_step_with_vrs(vrs, step)(s)
+        &&& VRSCluster::no_pending_req_msg(s, vrs.object_ref())
+    }
+}
+
+pub open spec fn at_step_closure(step: VReplicaSetReconcileStepView) -> spec_fn(VReplicaSetReconcileState) -> bool {
+    |s: VReplicaSetReconcileState| s.reconcile_step == step
+}
+
+pub open spec fn pods_list_request(vrs: VReplicaSetView) -> ListRequest {
+    ListRequest {
+        kind: PodView::kind(),
+        namespace: vrs.metadata.namespace.get_Some_0(),
+        label_selector: Some(vrs.spec.selector),
+        field_selector: None,
+    }
+}
+
+pub open spec fn pod_key(vrs: VReplicaSetView, pod: PodView) -> ObjectRef {
+    ObjectRef {
+        kind: PodView::kind(),
+        name: pod.metadata.name.get_Some_0(),
+        namespace: vrs.metadata.namespace.get_Some_0(),
+    }
+}
+
+pub open spec fn pod_delete_request(vrs: VReplicaSetView, pod: PodView) -> DeleteRequest {
+    DeleteRequest {
+        key: pod_key(vrs, pod),
+        preconditions: Some(PreconditionsView::default().set_uid_from_object_meta(pod.metadata)),
+        propagation_policy: None,
+        grace_period_seconds: None,
+    }
+}
+
+/// The first n pods are still matching pods in etcd (with the same uids) and have different keys,
+/// so deleting them one by one removes n matching pods.
+pub open spec fn first_n_pods_are_matching(vrs: VReplicaSetView, pods: Seq<PodView>, n: int, resources: StoredState) -> bool {
+    &&& 0 <= n <= pods.len()
+    &&& forall |i: int| #![trigger pods[i]] 0 <= i < n ==> {
+        &&& pods[i].metadata.name.is_Some()
+        &&& pods[i].metadata.uid.is_Some()
+        &&& matching_pods(vrs, resources).contains(pod_key(vrs, pods[i]))
+        &&& resources[pod_key(vrs, pods[i])].metadata.uid == pods[i].metadata.uid
+    }
+    &&& forall |i: int, j: int| #![trigger pods[i], pods[j]] 0 <= i < n && 0 <= j < n && i != j
+        ==> pod_key(vrs, pods[i]) != pod_key(vrs, pods[j])
+}
+
+/// The list response contains exactly the matching pods in etcd: the pods filtered out of it are as many as the
+/// matching pods and each of them is still a matching pod.
+pub open spec fn resp_msg_is_ok_list_resp_of_matching_pods(vrs: VReplicaSetView, resp_msg: VRSMessage, resources: StoredState) -> bool {
+    let objs = resp_msg.content.get_list_response().res.get_Ok_0();
+    let pods_or_none = objects_to_pods(objs);
+    let filtered = filter_pods(pods_or_none.get_Some_0(), vrs);
+    &&& resp_msg.content.is_list_response()
+    &&& resp_msg.content.get_list_response().res.is_Ok()
+    &&& pods_or_none.is_Some()
+    &&& matching_pods(vrs, resources).finite()
+    &&& filtered.len() == matching_pods(vrs, resources).len()
+    &&& first_n_pods_are_matching(vrs, filtered, filtered.len() as int, resources)
+}
+
+pub open spec fn pending_req_in_flight_at_after_list_pods_step(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        let request = msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterListPods)(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& s.in_flight().contains(msg)
+        &&& msg.src == HostId::CustomController
+        &&& msg.dst == HostId::ApiServer
+        &&& msg.content.is_APIRequest()
+        &&& request.is_ListRequest()
+        &&& request.get_ListRequest_0() == pods_list_request(vrs)
+    }
+}
+
+pub open spec fn req_msg_is_the_in_flight_list_req_at_after_list_pods_step(vrs: VReplicaSetView, req_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let request = req_msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterListPods)(s)
+        &&& VRSCluster::pending_req_msg_is(s, vrs.object_ref(), req_msg)
+        &&& s.in_flight().contains(req_msg)
+        &&& req_msg.src == HostId::CustomController
+        &&& req_msg.dst == HostId::ApiServer
+        &&& req_msg.content.is_APIRequest()
+        &&& request.is_ListRequest()
+        &&& request.get_ListRequest_0() == pods_list_request(vrs)
+    }
+}
+
+pub open spec fn exists_resp_in_flight_at_after_list_pods_step(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        let request = msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterListPods)(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& request.is_ListRequest()
+        &&& request.get_ListRequest_0() == pods_list_request(vrs)
+        &&& exists |resp_msg| {
+            &&& #[trigger] s.in_flight().contains(resp_msg)
+            &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+            &&& resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, s.resources())
+        }
+    }
+}
+
+pub open spec fn resp_msg_is_the_in_flight_list_resp_at_after_list_pods_step(vrs: VReplicaSetView, resp_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        let request = msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterListPods)(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& request.is_ListRequest()
+        &&& request.get_ListRequest_0() == pods_list_request(vrs)
+        &&& s.in_flight().contains(resp_msg)
+        &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+        &&& resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, s.resources())
+    }
+}
+
+/// At AfterCreatePod(n), the controller waits for the response of creating one pod and then creates n more pods,
+/// so before the pending create request is handled there are replicas - n - 1 matching pods.
+pub open spec fn pending_req_in_flight_at_after_create_pod_step(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        let request = msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n))(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& s.in_flight().contains(msg)
+        &&& msg.src == HostId::CustomController
+        &&& msg.dst == HostId::ApiServer
+        &&& msg.content.is_APIRequest()
+        &&& request.is_CreateRequest()
+        &&& request.get_CreateRequest_0().namespace == vrs.metadata.namespace.get_Some_0()
+        &&& request.get_CreateRequest_0().obj == make_pod(vrs).marshal()
+        &&& num_matching_pods_is(vrs, replicas(vrs) - n - 1)(s)
+    }
+}
+
+pub open spec fn req_msg_is_the_in_flight_create_req_at_after_create_pod_step(vrs: VReplicaSetView, n: nat, req_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let request = req_msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n))(s)
+        &&& VRSCluster::pending_req_msg_is(s, vrs.object_ref(), req_msg)
+        &&& s.in_flight().contains(req_msg)
+        &&& req_msg.src == HostId::CustomController
+        &&& req_msg.dst == HostId::ApiServer
+        &&& req_msg.content.is_APIRequest()
+        &&& request.is_CreateRequest()
+        &&& request.get_CreateRequest_0().namespace == vrs.metadata.namespace.get_Some_0()
+        &&& request.get_CreateRequest_0().obj == make_pod(vrs).marshal()
+        &&& num_matching_pods_is(vrs, replicas(vrs) - n - 1)(s)
+    }
+}
+
+pub open spec fn exists_ok_resp_in_flight_at_after_create_pod_step(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n))(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& msg.content.is_create_request()
+        &&& exists |resp_msg| {
+            &&& #[trigger] s.in_flight().contains(resp_msg)
+            &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+            &&& resp_msg.content.get_create_response().res.is_Ok()
+        }
+        &&& num_matching_pods_is(vrs, replicas(vrs) - n)(s)
+    }
+}
+
+pub open spec fn resp_msg_is_the_in_flight_ok_resp_at_after_create_pod_step(vrs: VReplicaSetView, n: nat, resp_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterCreatePod(n))(s)
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& msg.content.is_create_request()
+        &&& s.in_flight().contains(resp_msg)
+        &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+        &&& resp_msg.content.get_create_response().res.is_Ok()
+        &&& num_matching_pods_is(vrs, replicas(vrs) - n)(s)
+    }
+}
+
+/// At AfterDeletePod(n), the controller waits for the response of deleting the n-th filtered pod and then deletes
+/// the first n filtered pods, so before the pending delete request is handled there are replicas + n + 1 matching pods.
+pub open spec fn pending_req_in_flight_at_after_delete_pod_step(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let state = s.ongoing_reconciles()[vrs.object_ref()].local_state;
+        let filtered_pods = state.filtered_pods.get_Some_0();
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        let request = msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n))(s)
+        &&& state.filtered_pods.is_Some()
+        &&& n < filtered_pods.len()
+        &&& first_n_pods_are_matching(vrs, filtered_pods, n + 1, s.resources())
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& s.in_flight().contains(msg)
+        &&& msg.src == HostId::CustomController
+        &&& msg.dst == HostId::ApiServer
+        &&& msg.content.is_APIRequest()
+        &&& request.is_DeleteRequest()
+        &&& request.get_DeleteRequest_0() == pod_delete_request(vrs, filtered_pods[n as int])
+        &&& num_matching_pods_is(vrs, replicas(vrs) + n + 1)(s)
+    }
+}
+
+pub open spec fn req_msg_is_the_in_flight_delete_req_at_after_delete_pod_step(vrs: VReplicaSetView, n: nat, req_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let state = s.ongoing_reconciles()[vrs.object_ref()].local_state;
+        let filtered_pods = state.filtered_pods.get_Some_0();
+        let request = req_msg.content.get_APIRequest_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n))(s)
+        &&& state.filtered_pods.is_Some()
+        &&& n < filtered_pods.len()
+        &&& first_n_pods_are_matching(vrs, filtered_pods, n + 1, s.resources())
+        &&& VRSCluster::pending_req_msg_is(s, vrs.object_ref(), req_msg)
+        &&& s.in_flight().contains(req_msg)
+        &&& req_msg.src == HostId::CustomController
+        &&& req_msg.dst == HostId::ApiServer
+        &&& req_msg.content.is_APIRequest()
+        &&& request.is_DeleteRequest()
+        &&& request.get_DeleteRequest_0() == pod_delete_request(vrs, filtered_pods[n as int])
+        &&& num_matching_pods_is(vrs, replicas(vrs) + n + 1)(s)
+    }
+}
+
+pub open spec fn exists_ok_resp_in_flight_at_after_delete_pod_step(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let state = s.ongoing_reconciles()[vrs.object_ref()].local_state;
+        let filtered_pods = state.filtered_pods.get_Some_0();
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n))(s)
+        &&& state.filtered_pods.is_Some()
+        &&& n < filtered_pods.len()
+        &&& first_n_pods_are_matching(vrs, filtered_pods, n as int, s.resources())
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& msg.content.is_delete_request()
+        &&& exists |resp_msg| {
+            &&& #[trigger] s.in_flight().contains(resp_msg)
+            &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+            &&& resp_msg.content.get_delete_response().res.is_Ok()
+        }
+        &&& num_matching_pods_is(vrs, replicas(vrs) + n)(s)
+    }
+}
+
+pub open spec fn resp_msg_is_the_in_flight_ok_resp_at_after_delete_pod_step(vrs: VReplicaSetView, n: nat, resp_msg: VRSMessage) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let state = s.ongoing_reconciles()[vrs.object_ref()].local_state;
+        let filtered_pods = state.filtered_pods.get_Some_0();
+        let msg = s.ongoing_reconciles()[vrs.object_ref()].pending_req_msg.get_Some_0();
+        &&& at_vrs_step_with_vrs(vrs, VReplicaSetReconcileStepView::AfterDeletePod(n))(s)
+        &&& state.filtered_pods.is_Some()
+        &&& n < filtered_pods.len()
+        &&& first_n_pods_are_matching(vrs, filtered_pods, n as int, s.resources())
+        &&& VRSCluster::has_pending_k8s_api_req_msg(s, vrs.object_ref())
+        &&& msg.src == HostId::CustomController
+        &&& msg.content.is_delete_request()
+        &&& s.in_flight().contains(resp_msg)
+        &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+        &&& resp_msg.content.get_delete_response().res.is_Ok()
+        &&& num_matching_pods_is(vrs, replicas(vrs) + n)(s)
+    }
+}
+
+/// The matching pods are as many as the replicas, and the ongoing reconcile of vrs (if any) will not create or delete
+/// any pod: it has not listed the pods yet, it has finished, or every response to its list request reflects the matching
+/// pods in etcd so it goes to Done right after the response.
+pub open spec fn current_state_matches_and_reconcile_changes_no_pods(vrs: VReplicaSetView) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        let key = vrs.object_ref();
+        let step = s.ongoing_reconciles()[key].local_state.reconcile_step;
+        let msg = s.ongoing_reconciles()[key].pending_req_msg.get_Some_0();
+        &&& current_state_matches(vrs)(s)
+        &&& s.ongoing_reconciles().contains_key(key) ==> {
+            ||| step == VReplicaSetReconcileStepView::Init
+            ||| step == VReplicaSetReconcileStepView::Done
+            ||| step == VReplicaSetReconcileStepView::Error
+            ||| {
+                &&& step == VReplicaSetReconcileStepView::AfterListPods
+                &&& VRSCluster::has_pending_k8s_api_req_msg(s, key)
+                &&& msg.content.get_APIRequest_0() == APIRequest::ListRequest(pods_list_request(vrs))
+                &&& forall |resp_msg: VRSMessage| {
+                    &&& #[trigger] s.in_flight().contains(resp_msg)
+                    &&& Message::resp_msg_matches_req_msg(resp_msg, msg)
+                } ==> resp_msg_is_ok_list_resp_of_matching_pods(vrs, resp_msg, s.resources())
+            }
+        }
+    }
+}
+
+pub open spec fn resp_msg_is_the_in_flight_list_resp_with_m_matching_pods(vrs: VReplicaSetView, resp_msg: VRSMessage, m: nat) -> StatePred<VRSCluster> {
+    |s: VRSCluster| {
+        &&& resp_msg_is_the_in_flight_list_resp_at_after_list_pods_step(vrs, resp_msg)(s)
+        &&& num_matching_pods_is(vrs, m as int)(s)
+    }
+}
+
+/// After receiving the list response with m matching pods, the reconciler stops if m is the same as the replicas,
+/// otherwise it starts to create or delete the missing or extra pods.
+pub open spec fn next_step_after_list_pods(vrs: VReplicaSetView, m: nat) -> StatePred<VRSCluster> {
+    if m == replicas(vrs) {
+        current_state_matches_and_reconcile_changes_no_pods(vrs)
+    } else if m < replicas(vrs) {
+        pending_req_in_flight_at_after_create_pod_step(vrs, (replicas(vrs) - m - 1) as nat)
+    } else {
+        pending_req_in_flight_at_after_delete_pod_step(vrs, (m - replicas(vrs) - 1) as nat)
+    }
+}
+
+pub open spec fn next_step_after_create_pod(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    if n == 0 {
+        current_state_matches_and_reconcile_changes_no_pods(vrs)
+    } else {
+        pending_req_in_flight_at_after_create_pod_step(vrs, (n - 1) as nat)
+    }
+}
+
+pub open spec fn next_step_after_delete_pod(vrs: VReplicaSetView, n: nat) -> StatePred<VRSCluster> {
+    if n == 0 {
+        current_state_matches_and_reconcile_changes_no_pods(vrs)
+    } else {
+        pending_req_in_flight_at_after_delete_pod_step(vrs, (n - 1) as nat)
+    }
+}
+
+}
@@ -0,0 +1,391 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::external_api::exec::*;
+use crate::kubernetes_api_objects::error::APIError;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, dynamic::*, pod::Pod, resource::*,
+};
+use crate::kubernetes_api_objects::spec::prelude::PatchType;
+use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::reconcile_trace::*;
+use crate::shim_layer::refinement_monitor::*;
+use crate::shim_layer::simulation::*;
+use crate::v_replica_set_controller::exec::{reconciler::VReplicaSetReconciler, state_matches};
+use crate::v_replica_set_controller::trusted::exec_types;
+use deps_hack::k8s_openapi::api::core::v1::{Container, PodSpec, PodTemplateSpec};
+use deps_hack::kube::api::Resource;
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::{
+    LabelSelector, LabelSelectorRequirement, ObjectMeta,
+};
+use deps_hack::proptest::prelude::*;
+use deps_hack::VReplicaSet;
+use std::collections::BTreeMap;
+
+fn make_v_replica_set(replicas: i32) -> VReplicaSet {
+    let labels: BTreeMap<String, String> = vec![("app".to_string(), "test".to_string())]
+        .into_iter()
+        .collect();
+    let mut v_replica_set = VReplicaSet::new(
+        "test",
+        deps_hack::VReplicaSetSpec {
+            replicas: Some(replicas),
+            selector: LabelSelector {
+                match_labels: Some(labels.clone()),
+                ..LabelSelector::default()
+            },
+            template: Some(PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(labels),
+                    ..ObjectMeta::default()
+                }),
+                spec: Some(PodSpec {
+                    containers: vec![Container {
+                        name: "nginx".to_string(),
+                        image: Some("nginx".to_string()),
+                        ..Container::default()
+                    }],
+                    ..PodSpec::default()
+                }),
+            }),
+        },
+    );
+    v_replica_set.metadata.namespace = Some("default".to_string());
+    v_replica_set
+}
+
+fn scale(cluster: &mut SimulatedCluster<VReplicaSetReconciler>, replicas: i32) {
+    let patch = DynamicObject::from_kube(
+        deps_hack::serde_json::from_value(deps_hack::serde_json::json!({
+            "apiVersion": "anvil.dev/v1",
+            "kind": "VReplicaSet",
+            "metadata": {},
+            "spec": { "replicas": replicas },
+        }))
+        .unwrap(),
+    );
+    let resp = cluster.handle_request(KubeAPIRequest::PatchRequest(KubePatchRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource::erase::<
+            VReplicaSet,
+        >(&())),
+        name: "test".to_string(),
+        namespace: "default".to_string(),
+        patch: patch,
+        patch_type: PatchType::MergePatch,
+    }));
+    assert!(resp.into_patch_response().res.is_ok());
+}
+
+// current_state_matches runs the exec checker of the liveness theorem on the state of the simulated cluster
+fn current_state_matches(cluster: &SimulatedCluster<VReplicaSetReconciler>) -> bool {
+    let obj = cluster
+        .get(exec_types::VReplicaSet::api_resource(), "default", "test")
+        .unwrap();
+    let v_replica_set = match exec_types::VReplicaSet::unmarshal(obj) {
+        Ok(v_replica_set) => v_replica_set,
+        Err(_) => panic!("Fail to unmarshal the VReplicaSet"),
+    };
+    state_matches::current_state_matches(&v_replica_set, &cluster.api_server_state.resources)
+}
+
+#[test]
+pub fn test_reconcile_creates_pods() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    let outcome = cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    // List pods, then create three pods
+    assert_eq!(outcome, SimulatedReconcileOutcome::Done { steps: 5 });
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 3);
+
+    // Nothing to do once the pods match the replicas
+    let outcome = cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert_eq!(outcome, SimulatedReconcileOutcome::Done { steps: 2 });
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 3);
+}
+
+#[test]
+pub fn test_pod_names_are_generated_from_generate_name() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    for pod in cluster.list(Pod::api_resource(), "default") {
+        let meta = pod.into_kube().metadata;
+        assert_eq!(meta.generate_name, Some("test-".to_string()));
+        assert_eq!(meta.name, Some(format!("test-{}", meta.uid.unwrap())));
+    }
+}
+
+#[test]
+pub fn test_create_fails_when_generated_name_is_taken() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    // The first pod created by the reconcile gets the uid right after the one taken by the pod below
+    let mut pod = deps_hack::k8s_openapi::api::core::v1::Pod::default();
+    pod.meta_mut().name = Some(format!("test-{}", cluster.api_server_state.uid_counter + 1));
+    pod.meta_mut().namespace = Some("default".to_string());
+    pod.spec = make_v_replica_set(3).spec.template.unwrap().spec;
+    cluster.create(&pod).unwrap();
+    assert!(cluster.reconcile::<VReplicaSet>("default", "test").is_err());
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 1);
+
+    // The failed create consumes the uid so the next reconcile gets fresh names
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 4);
+    assert!(current_state_matches(&cluster));
+}
+
+#[test]
+pub fn test_reconcile_deletes_pods() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    scale(&mut cluster, 1);
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 1);
+}
+
+#[test]
+pub fn test_current_state_matches_after_reconcile() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    assert!(!current_state_matches(&cluster));
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert!(current_state_matches(&cluster));
+
+    scale(&mut cluster, 1);
+    assert!(!current_state_matches(&cluster));
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert!(current_state_matches(&cluster));
+
+    scale(&mut cluster, 0);
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert!(current_state_matches(&cluster));
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 0);
+}
+
+#[test]
+pub fn test_reconcile_cr_not_found() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    let outcome = cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert_eq!(outcome, SimulatedReconcileOutcome::CrNotFound);
+    assert_eq!(cluster.list(Pod::api_resource(), "default").len(), 0);
+}
+
+#[test]
+pub fn test_replay_recorded_reconciles() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    scale(&mut cluster, 1);
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    assert_eq!(cluster.traces.len(), 2);
+    for trace in cluster.traces.iter() {
+        assert_eq!(trace.outcome, ReconcileTraceOutcome::Done);
+        assert_eq!(
+            replay_reconcile::<VReplicaSet, VReplicaSetReconciler>(trace),
+            ReplayResult::Consistent
+        );
+    }
+}
+
+#[test]
+pub fn test_replay_flags_tampered_response() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    scale(&mut cluster, 1);
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    // Pretend that the list request of the second reconcile returned no pod,
+    // so reconcile_core creates a pod instead of deleting the recorded one
+    let mut trace = cluster.traces[1].clone();
+    let list_step = trace
+        .steps
+        .iter()
+        .position(|step| match &step.request {
+            Some(request) => request["KRequest"].get("List").is_some(),
+            None => false,
+        })
+        .unwrap();
+    trace.steps[list_step].response =
+        Some(deps_hack::serde_json::json!({ "KResponse": { "List": { "Ok": [] } } }));
+    match replay_reconcile::<VReplicaSet, VReplicaSetReconciler>(&trace) {
+        ReplayResult::RequestMismatch { step, .. } => assert_eq!(step, list_step + 1),
+        result => panic!("Unexpected replay result {:?}", result),
+    }
+}
+
+#[test]
+pub fn test_reconciles_refine_executable_model() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    scale(&mut cluster, 1);
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    for trace in cluster.traces.iter() {
+        assert_eq!(
+            check_reconcile_trace::<VReplicaSet, VReplicaSetReconciler>(trace),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+pub fn test_refinement_monitor_flags_unexpected_request() {
+    let mut cluster = SimulatedCluster::<VReplicaSetReconciler>::new();
+    cluster.create(&make_v_replica_set(3)).unwrap();
+    cluster.reconcile::<VReplicaSet>("default", "test").unwrap();
+    let trace = &cluster.traces[0];
+    // The spec lists the pods first, so the recorded list request refines the spec
+    let mut model = ModelReconcile::<VReplicaSetReconciler>::new(&trace.cr).unwrap();
+    assert_eq!(
+        model.check_step(None, trace.steps[0].request.as_ref(), None),
+        RefinementCheck::Refines
+    );
+    // but ending the reconcile right after that does not
+    assert!(matches!(
+        model.check_step(
+            trace.steps[0].response.as_ref(),
+            trace.steps[1].request.as_ref(),
+            Some(ReconcileTraceOutcome::Done)
+        ),
+        RefinementCheck::Violated(_)
+    ));
+    // and neither does creating a pod right away
+    let mut model = ModelReconcile::<VReplicaSetReconciler>::new(&trace.cr).unwrap();
+    match model.check_step(None, trace.steps[1].request.as_ref(), None) {
+        RefinementCheck::Violated(violation) => {
+            assert_eq!(violation.expected_request, trace.steps[0].request)
+        }
+        check => panic!("Unexpected refinement check {:?}", check),
+    }
+    // The state of the model has diverged after the violation, so it stops checking the reconcile
+    assert_eq!(
+        model.check_step(
+            trace.steps[0].response.as_ref(),
+            trace.steps[1].request.as_ref(),
+            None
+        ),
+        RefinementCheck::Unchecked
+    );
+}
+
+fn labels_strategy() -> BoxedStrategy<BTreeMap<String, String>> {
+    prop::collection::btree_map(prop_oneof!["app", "tier"], prop_oneof!["test", "web"], 0..=2).boxed()
+}
+
+prop_compose! {
+    fn requirement_strategy()(
+        key in prop_oneof!["app", "tier"],
+        operator in prop_oneof!["In", "NotIn", "Exists", "DoesNotExist"],
+        values in prop::collection::vec(prop_oneof!["test", "web"], 1..=2),
+    ) -> LabelSelectorRequirement {
+        let values = match operator.as_str() {
+            "In" | "NotIn" => Some(values),
+            _ => None,
+        };
+        LabelSelectorRequirement { key: key, operator: operator, values: values }
+    }
+}
+
+prop_compose! {
+    fn v_replica_set_strategy()(
+        replicas in -1..=3i32,
+        match_labels in labels_strategy(),
+        match_expressions in prop::option::of(prop::collection::vec(requirement_strategy(), 0..=2)),
+    ) -> VReplicaSet {
+        let mut v_replica_set = make_v_replica_set(replicas);
+        v_replica_set.metadata.uid = Some("test-uid".to_string());
+        v_replica_set.spec.selector = LabelSelector {
+            match_labels: Some(match_labels),
+            match_expressions: match_expressions,
+        };
+        v_replica_set
+    }
+}
+
+// pod_strategy generates a pod listed to the reconcile, which may or may not be owned by the VReplicaSet,
+// match its selector, or be deleted
+prop_compose! {
+    fn pod_strategy()(
+        labels in labels_strategy(),
+        owned in any::<bool>(),
+        deleted in any::<bool>(),
+    ) -> deps_hack::serde_json::Value {
+        let mut metadata = deps_hack::serde_json::json!({ "namespace": "default", "labels": labels });
+        if owned {
+            metadata["ownerReferences"] = deps_hack::serde_json::json!([{
+                "apiVersion": "anvil.dev/v1",
+                "kind": "VReplicaSet",
+                "name": "test",
+                "uid": "test-uid",
+                "controller": true,
+                "blockOwnerDeletion": true,
+            }]);
+        }
+        if deleted {
+            metadata["deletionTimestamp"] = deps_hack::serde_json::json!("2024-01-01T00:00:00Z");
+            metadata["finalizers"] = deps_hack::serde_json::json!(["anvil.dev/test"]);
+        }
+        deps_hack::serde_json::json!({ "apiVersion": "v1", "kind": "Pod", "metadata": metadata })
+    }
+}
+
+// respond answers a request of the reconcile as if the namespace has the given pods,
+// or with an error if the request fails
+fn respond(req: &KubeAPIRequest, pods: &Vec<DynamicObject>, fails: bool) -> KubeAPIResponse {
+    match req {
+        KubeAPIRequest::ListRequest(_) => KubeAPIResponse::ListResponse(KubeListResponse {
+            res: if fails { Err(APIError::InternalError) } else { Ok(pods.clone()) },
+        }),
+        KubeAPIRequest::CreateRequest(req) => KubeAPIResponse::CreateResponse(KubeCreateResponse {
+            res: if fails { Err(APIError::InternalError) } else { Ok(req.obj.clone()) },
+        }),
+        KubeAPIRequest::DeleteRequest(_) => KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+            res: if fails { Err(APIError::ObjectNotFound) } else { Ok(()) },
+        }),
+        _ => panic!("Unexpected request of the VReplicaSet reconcile"),
+    }
+}
+
+// run_reconcile_core runs the exec reconcile_core to the end without a cluster,
+// answering its requests by respond, and records the reconcile as a trace
+fn run_reconcile_core(v_replica_set: &VReplicaSet, pods: &Vec<DynamicObject>, failed_step: usize) -> ReconcileTrace {
+    let mut trace = ReconcileTrace::new("VReplicaSet/default/test".to_string(), v_replica_set);
+    let cr = exec_types::VReplicaSet::from_kube(v_replica_set.clone());
+    let mut state = VReplicaSetReconciler::reconcile_init_state();
+    let mut resp_option: Option<Response<EmptyType>> = None;
+    while !VReplicaSetReconciler::reconcile_done(&state) && !VReplicaSetReconciler::reconcile_error(&state) {
+        assert!(trace.steps.len() < 20, "Reconcile is not done after 20 steps");
+        let (state_prime, request_option) = VReplicaSetReconciler::reconcile_core(&cr, resp_option, state);
+        let request_json = request_option.as_ref().map(|req| request_to_json::<EmptyAPIShimLayer>(req));
+        let fails = trace.steps.len() == failed_step;
+        resp_option = match request_option {
+            Some(Request::KRequest(req)) => Some(Response::KResponse(respond(&req, pods, fails))),
+            Some(Request::ExternalRequest(_)) => panic!("The VReplicaSet reconcile has no external request"),
+            None => None,
+        };
+        trace.push_step::<EmptyAPIShimLayer>(request_json, &resp_option, None);
+        state = state_prime;
+    }
+    trace.outcome = outcome_of::<VReplicaSetReconciler>(&state);
+    trace
+}
+
+proptest! {
+    // The exec reconcile_core is proved to refine the spec reconcile_core, so wherever the executable model
+    // used by the refinement monitor agrees with it, the executable model agrees with the spec.
+    #![proptest_config(ProptestConfig::with_cases(500))]
+    #[test]
+    fn test_executable_model_agrees_with_reconcile_core(
+        v_replica_set in v_replica_set_strategy(),
+        pods in prop::collection::vec(pod_strategy(), 0..=4),
+        failed_step in 0..6usize,
+    ) {
+        let pods: Vec<DynamicObject> = pods.into_iter().enumerate().map(|(i, mut pod)| {
+            pod["metadata"]["name"] = deps_hack::serde_json::json!(format!("pod-{}", i));
+            pod["metadata"]["uid"] = deps_hack::serde_json::json!(format!("pod-uid-{}", i));
+            DynamicObject::from_kube(deps_hack::serde_json::from_value(pod).unwrap())
+        }).collect();
+        let trace = run_reconcile_core(&v_replica_set, &pods, failed_step);
+        prop_assert_eq!(check_reconcile_trace::<VReplicaSet, VReplicaSetReconciler>(&trace), Ok(()));
+    }
+}
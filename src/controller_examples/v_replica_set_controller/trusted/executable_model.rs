@@ -0,0 +1,320 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::reconcile_trace::{kube_api_resource_to_json, ReconcileTraceOutcome};
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use crate::v_replica_set_controller::exec::reconciler::VReplicaSetReconciler;
+use deps_hack::k8s_openapi::api::core::v1::Pod;
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+use deps_hack::kube::api::{ApiResource, DeleteParams, Preconditions, Resource};
+use deps_hack::serde::{Deserialize, Serialize};
+use deps_hack::serde_json::{json, Value};
+use deps_hack::VReplicaSet;
+
+// This is the executable model of model::reconciler::reconcile_core used by the refinement monitor
+// (see crate::shim_layer::refinement_monitor). Each function below transcribes the spec function of the same name
+// to kube-rs types, and the branches of reconcile_core follow the spec one by one.
+// The model works on the objects as served by Kubernetes, so it never goes through the exec types or their views.
+//
+// The transcription is trusted. It is tied to the spec by test_executable_model_agrees_with_reconcile_core
+// (see crate::v_replica_set_controller::simulation_tests), which runs it alongside the exec reconcile_core,
+// proved to refine the spec reconcile_core, on generated cr objects and responses.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+enum VReplicaSetReconcileStepModel {
+    Init,
+    AfterListPods,
+    AfterCreatePod(usize),
+    AfterDeletePod(usize),
+    Done,
+    Error,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+struct VReplicaSetReconcileStateModel {
+    reconcile_step: VReplicaSetReconcileStepModel,
+    filtered_pods: Option<Vec<Pod>>,
+}
+
+impl ExecutableModelReconciler for VReplicaSetReconciler {
+    fn model_init_state() -> Option<Value> {
+        deps_hack::serde_json::to_value(VReplicaSetReconcileStateModel {
+            reconcile_step: VReplicaSetReconcileStepModel::Init,
+            filtered_pods: None,
+        })
+        .ok()
+    }
+
+    fn model_reconcile_core(
+        cr: &Value,
+        resp_o: Option<&Value>,
+        state: &Value,
+    ) -> Option<(Value, Option<Value>)> {
+        let v_replica_set = deps_hack::serde_json::from_value::<VReplicaSet>(cr.clone()).ok()?;
+        let state =
+            deps_hack::serde_json::from_value::<VReplicaSetReconcileStateModel>(state.clone())
+                .ok()?;
+        let (state_prime, req_o) = reconcile_core(&v_replica_set, resp_o, state)?;
+        Some((deps_hack::serde_json::to_value(state_prime).ok()?, req_o))
+    }
+
+    fn model_outcome(state: &Value) -> Option<ReconcileTraceOutcome> {
+        let state =
+            deps_hack::serde_json::from_value::<VReplicaSetReconcileStateModel>(state.clone())
+                .ok()?;
+        match state.reconcile_step {
+            VReplicaSetReconcileStepModel::Done => Some(ReconcileTraceOutcome::Done),
+            VReplicaSetReconcileStepModel::Error => Some(ReconcileTraceOutcome::Error),
+            _ => None,
+        }
+    }
+}
+
+fn reconcile_core(
+    v_replica_set: &VReplicaSet,
+    resp_o: Option<&Value>,
+    state: VReplicaSetReconcileStateModel,
+) -> Option<(VReplicaSetReconcileStateModel, Option<Value>)> {
+    let namespace = v_replica_set.metadata.namespace.clone()?;
+    match state.reconcile_step {
+        VReplicaSetReconcileStepModel::Init => {
+            let req = json!({ "KRequest": { "List": {
+                "api_resource": pod_api_resource(),
+                "namespace": namespace,
+                "label_selector": label_selector_string(v_replica_set),
+                "field_selector": null,
+            }}});
+            let state_prime = VReplicaSetReconcileStateModel {
+                reconcile_step: VReplicaSetReconcileStepModel::AfterListPods,
+                ..state
+            };
+            Some((state_prime, Some(req)))
+        }
+        VReplicaSetReconcileStepModel::AfterListPods => {
+            let objs = match ok_response(resp_o, "List") {
+                Some(objs) => objs.as_array()?,
+                None => return Some((error_state(state), None)),
+            };
+            let pods = match objects_to_pods(objs) {
+                Some(pods) => pods,
+                None => return Some((error_state(state), None)),
+            };
+            let filtered_pods = filter_pods(pods, v_replica_set);
+            let replicas = v_replica_set.spec.replicas.unwrap_or(0);
+            if replicas < 0 {
+                return Some((error_state(state), None));
+            }
+            let desired_replicas = replicas as usize;
+            if filtered_pods.len() == desired_replicas {
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::Done,
+                    ..state
+                };
+                Some((state_prime, None))
+            } else if filtered_pods.len() < desired_replicas {
+                let diff = desired_replicas - filtered_pods.len();
+                let req = create_pod_request(v_replica_set, &namespace)?;
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::AfterCreatePod(diff - 1),
+                    ..state
+                };
+                Some((state_prime, Some(req)))
+            } else {
+                let diff = filtered_pods.len() - desired_replicas;
+                let req = match delete_pod_request(&filtered_pods[diff - 1], &namespace) {
+                    Some(req) => req,
+                    None => return Some((error_state(state), None)),
+                };
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::AfterDeletePod(diff - 1),
+                    filtered_pods: Some(filtered_pods),
+                };
+                Some((state_prime, Some(req)))
+            }
+        }
+        VReplicaSetReconcileStepModel::AfterCreatePod(diff) => {
+            if ok_response(resp_o, "Create").is_none() {
+                Some((error_state(state), None))
+            } else if diff == 0 {
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::Done,
+                    ..state
+                };
+                Some((state_prime, None))
+            } else {
+                let req = create_pod_request(v_replica_set, &namespace)?;
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::AfterCreatePod(diff - 1),
+                    ..state
+                };
+                Some((state_prime, Some(req)))
+            }
+        }
+        VReplicaSetReconcileStepModel::AfterDeletePod(diff) => {
+            if ok_response(resp_o, "Delete").is_none() {
+                Some((error_state(state), None))
+            } else if diff == 0 {
+                let state_prime = VReplicaSetReconcileStateModel {
+                    reconcile_step: VReplicaSetReconcileStepModel::Done,
+                    ..state
+                };
+                Some((state_prime, None))
+            } else {
+                let req = match &state.filtered_pods {
+                    Some(filtered_pods) if diff <= filtered_pods.len() => {
+                        delete_pod_request(&filtered_pods[diff - 1], &namespace)
+                    }
+                    _ => None,
+                };
+                match req {
+                    Some(req) => {
+                        let state_prime = VReplicaSetReconcileStateModel {
+                            reconcile_step: VReplicaSetReconcileStepModel::AfterDeletePod(diff - 1),
+                            ..state
+                        };
+                        Some((state_prime, Some(req)))
+                    }
+                    None => Some((error_state(state), None)),
+                }
+            }
+        }
+        _ => Some((state, None)),
+    }
+}
+
+fn error_state(state: VReplicaSetReconcileStateModel) -> VReplicaSetReconcileStateModel {
+    VReplicaSetReconcileStateModel {
+        reconcile_step: VReplicaSetReconcileStepModel::Error,
+        ..state
+    }
+}
+
+// ok_response returns the result of the kube response if it is a successful response of the given method
+fn ok_response<'a>(resp_o: Option<&'a Value>, method: &str) -> Option<&'a Value> {
+    resp_o?.get("KResponse")?.get(method)?.get("Ok")
+}
+
+fn objects_to_pods(objs: &Vec<Value>) -> Option<Vec<Pod>> {
+    objs.iter()
+        .map(|obj| deps_hack::serde_json::from_value::<Pod>(obj.clone()).ok())
+        .collect()
+}
+
+fn filter_pods(pods: Vec<Pod>, v_replica_set: &VReplicaSet) -> Vec<Pod> {
+    pods.into_iter()
+        .filter(|pod| {
+            owner_references_contains_controller_owner_ref(&pod.metadata, v_replica_set)
+                && selector_matches(v_replica_set, &pod.metadata)
+                && pod.metadata.deletion_timestamp.is_none()
+        })
+        .collect()
+}
+
+// The view of an owner reference has no api version, so it is not compared here
+fn owner_references_contains_controller_owner_ref(
+    metadata: &ObjectMeta,
+    v_replica_set: &VReplicaSet,
+) -> bool {
+    metadata.owner_references.iter().flatten().any(|owner_ref| {
+        owner_ref.kind == VReplicaSet::kind(&())
+            && Some(&owner_ref.name) == v_replica_set.metadata.name.as_ref()
+            && Some(&owner_ref.uid) == v_replica_set.metadata.uid.as_ref()
+            && owner_ref.controller == Some(true)
+            && owner_ref.block_owner_deletion == Some(true)
+    })
+}
+
+// selector_matches follows LabelSelectorView::matches, which ANDs match_labels and match_expressions
+fn selector_matches(v_replica_set: &VReplicaSet, metadata: &ObjectMeta) -> bool {
+    let selector = &v_replica_set.spec.selector;
+    let label = |key: &String| metadata.labels.as_ref().and_then(|labels| labels.get(key));
+    selector
+        .match_labels
+        .iter()
+        .flatten()
+        .all(|(k, v)| label(k) == Some(v))
+        && selector.match_expressions.iter().flatten().all(|expr| {
+            let values = expr.values.clone().unwrap_or_default();
+            match expr.operator.as_str() {
+                "In" => label(&expr.key).map_or(false, |v| values.contains(v)),
+                "NotIn" => label(&expr.key).map_or(true, |v| !values.contains(v)),
+                "Exists" => label(&expr.key).is_some(),
+                "DoesNotExist" => label(&expr.key).is_none(),
+                _ => false,
+            }
+        })
+}
+
+fn make_pod(v_replica_set: &VReplicaSet) -> Option<Pod> {
+    let template = v_replica_set.spec.template.clone()?;
+    let tm = template.metadata?;
+    Some(Pod {
+        metadata: ObjectMeta {
+            labels: tm.labels,
+            annotations: tm.annotations,
+            finalizers: tm.finalizers,
+            generate_name: Some(v_replica_set.metadata.name.clone()? + "-"),
+            owner_references: Some(vec![OwnerReference {
+                api_version: VReplicaSet::api_version(&()).to_string(),
+                kind: VReplicaSet::kind(&()).to_string(),
+                name: v_replica_set.metadata.name.clone()?,
+                uid: v_replica_set.metadata.uid.clone()?,
+                controller: Some(true),
+                block_owner_deletion: Some(true),
+            }]),
+            ..ObjectMeta::default()
+        },
+        spec: template.spec,
+        ..Pod::default()
+    })
+}
+
+fn pod_api_resource() -> Value {
+    kube_api_resource_to_json(&ApiResource::erase::<Pod>(&()))
+}
+
+// label_selector_string follows the labelSelector query parameter built from the selector of the list request
+fn label_selector_string(v_replica_set: &VReplicaSet) -> String {
+    let selector = &v_replica_set.spec.selector;
+    let labels = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .map(|(k, v)| format!("{}={}", k, v));
+    let expressions = selector.match_expressions.iter().flatten().map(|expr| {
+        let values = expr.values.clone().unwrap_or_default().join(",");
+        match expr.operator.as_str() {
+            "In" => format!("{} in ({})", expr.key, values),
+            "NotIn" => format!("{} notin ({})", expr.key, values),
+            "DoesNotExist" => format!("!{}", expr.key),
+            _ => expr.key.clone(),
+        }
+    });
+    labels.chain(expressions).collect::<Vec<_>>().join(",")
+}
+
+fn create_pod_request(v_replica_set: &VReplicaSet, namespace: &String) -> Option<Value> {
+    Some(json!({ "KRequest": { "Create": {
+        "api_resource": pod_api_resource(),
+        "namespace": namespace,
+        "obj": deps_hack::serde_json::to_value(make_pod(v_replica_set)?).ok()?,
+    }}}))
+}
+
+// delete_pod_request returns None if the pod has no name
+fn delete_pod_request(pod: &Pod, namespace: &String) -> Option<Value> {
+    let delete_params = DeleteParams {
+        preconditions: Some(Preconditions {
+            uid: pod.metadata.uid.clone(),
+            resource_version: None,
+        }),
+        ..DeleteParams::default()
+    };
+    Some(json!({ "KRequest": { "Delete": {
+        "api_resource": pod_api_resource(),
+        "namespace": namespace,
+        "name": pod.metadata.name.clone()?,
+        "delete_params": deps_hack::serde_json::to_value(&delete_params).ok()?,
+    }}}))
+}
@@ -1,6 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 pub mod exec_types;
+pub mod executable_model;
 pub mod liveness_theorem;
 pub mod spec_types;
 pub mod step;
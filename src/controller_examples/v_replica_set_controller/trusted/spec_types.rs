@@ -5,7 +5,10 @@ use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::{
     api_resource::*, label_selector::*, pod_template_spec::*, prelude::*,
 };
-use crate::kubernetes_cluster::spec::{cluster::*, cluster_state_machine::*, message::*};
+use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::has_propagation_finalizer, cluster::*, cluster_state_machine::*,
+    message::*,
+};
 use crate::v_replica_set_controller::trusted::step::*;
 use crate::vstd_ext::string_view::*;
 use vstd::prelude::*;
@@ -69,7 +72,7 @@ impl ResourceView for VReplicaSetView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("VReplicaSet"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
@@ -135,8 +138,20 @@ impl ResourceView for VReplicaSetView {
 
     proof fn unmarshal_result_determined_by_unmarshal_spec_and_status() {}
 
+    // Similar to k8s's ReplicaSet, the pod template must be present and its labels must match the selector;
+    // otherwise the pods created from the template are never selected and the replica set never converges.
+    // The selector must also be valid, since the API server rejects every list request with an invalid selector.
+    // The template must not carry the finalizers of the orphan or foreground propagation policy either, since the pods
+    // copy them from the template and would make the garbage collector orphan or delete their dependents.
+    // The CEL rules in deploy/vreplicaset/crd.yaml make the API server enforce the same conditions.
     open spec fn state_validation(self) -> bool {
-        self.spec.replicas.is_Some() ==> self.spec.replicas.get_Some_0() >= 0
+        &&& self.spec.replicas.is_Some() ==> self.spec.replicas.get_Some_0() >= 0
+        &&& self.spec.template.is_Some()
+        &&& self.spec.template.get_Some_0().metadata.is_Some()
+        &&& self.spec.template.get_Some_0().spec.is_Some()
+        &&& self.spec.selector.is_valid()
+        &&& self.spec.selector.matches(self.spec.template.get_Some_0().metadata.get_Some_0().labels.unwrap_or(Map::empty()))
+        &&& !has_propagation_finalizer(self.spec.template.get_Some_0().metadata.get_Some_0().finalizers)
     }
 
     open spec fn transition_validation(self, old_obj: VReplicaSetView) -> bool {
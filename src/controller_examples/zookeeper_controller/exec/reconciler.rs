@@ -1,6 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
+use crate::executable_model::object_map::ObjectMap;
 use crate::external_api::exec::*;
 use crate::kubernetes_api_objects::exec::{
     api_method::*, container::*, label_selector::*, object_meta::*, owner_reference::*,
@@ -9,8 +10,12 @@ use crate::kubernetes_api_objects::exec::{
 };
 use crate::reconciler::exec::{io::*, reconciler::*, resource_builder::*};
 use crate::reconciler::spec::resource_builder::ResourceBuilder as SpecResourceBuilder;
+use crate::shim_layer::cluster_snapshot::StateMatchesReconciler;
+use crate::shim_layer::refinement_monitor::ExecutableModelReconciler;
+use crate::shim_layer::requeue::RequeueHintReconciler;
 use crate::vstd_ext::{string_map::*, string_view::*};
 use crate::zookeeper_controller::exec::resource::*;
+use crate::zookeeper_controller::exec::state_matches;
 use crate::zookeeper_controller::model::reconciler as model_reconciler;
 use crate::zookeeper_controller::model::resource as model_resource;
 use crate::zookeeper_controller::trusted::{
@@ -48,6 +53,14 @@ impl Reconciler for ZookeeperReconciler {
     fn reconcile_error(state: &ZookeeperReconcileState) -> bool {
         reconcile_error(state)
     }
+
+    fn owned_resources() -> Vec<ApiResource> {
+        let mut resources = Vec::new();
+        resources.push(Service::api_resource());
+        resources.push(ConfigMap::api_resource());
+        resources.push(StatefulSet::api_resource());
+        resources
+    }
 }
 
 pub fn reconcile_init_state() -> (state: ZookeeperReconcileState)
@@ -469,3 +482,14 @@ impl ZKAPIOutput {
 
 
 }
+
+// There is no executable model of the spec reconcile_core yet, so the refinement monitor skips this reconciler.
+impl ExecutableModelReconciler for ZookeeperReconciler {}
+
+impl RequeueHintReconciler for ZookeeperReconciler {}
+
+impl StateMatchesReconciler for ZookeeperReconciler {
+    fn state_matches(zk: &ZookeeperCluster, snapshot: &ObjectMap) -> Option<bool> {
+        Some(state_matches::current_state_matches(zk, snapshot))
+    }
+}
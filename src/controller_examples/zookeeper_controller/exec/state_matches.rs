@@ -0,0 +1,100 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::ObjectMap, state_matches::*};
+use crate::kubernetes_api_objects::exec::prelude::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::zookeeper_controller::exec::resource::*;
+use crate::zookeeper_controller::model::reconciler::ZookeeperMaker;
+use crate::zookeeper_controller::trusted::{exec_types::*, liveness_theorem, step::*};
+use vstd::prelude::*;
+use vstd::string::*;
+
+verus! {
+
+// resource_state_matches is the exec version of liveness_theorem::resource_state_matches instantiated with
+// ZookeeperMaker, so it checks the same property as the liveness theorem proves. It runs against the objects
+// stored in a cluster, e.g., the state of the exec API server model or a snapshot of a real cluster
+// (see crate::shim_layer::cluster_snapshot).
+pub fn resource_state_matches(sub_resource: SubResource, zk: &ZookeeperCluster, resources: &ObjectMap) -> (res: bool)
+    requires zk@.well_formed(),
+    ensures res == liveness_theorem::resource_state_matches::<ZookeeperMaker>(sub_resource, zk@, resources@),
+{
+    match sub_resource {
+        SubResource::HeadlessService => {
+            let key = make_key(KubeKind::ServiceKind, make_headless_service_name(zk), zk);
+            let made = make_headless_service(zk);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::ClientService => {
+            let key = make_key(KubeKind::ServiceKind, make_client_service_name(zk), zk);
+            let made = make_client_service(zk);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::AdminServerService => {
+            let key = make_key(KubeKind::ServiceKind, make_admin_server_service_name(zk), zk);
+            let made = make_admin_server_service(zk);
+            service_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::ConfigMap => {
+            let key = make_key(KubeKind::ConfigMapKind, make_config_map_name(zk), zk);
+            let made = make_config_map(zk);
+            config_map_data_matches(&key, &made, resources)
+            && object_spec_matches(&key, &made.clone().marshal(), resources)
+            && metadata_matches(&key, &made.metadata(), resources)
+        },
+        SubResource::StatefulSet => {
+            let key = make_key(KubeKind::StatefulSetKind, make_stateful_set_name(zk), zk);
+            let cm_key = make_key(KubeKind::ConfigMapKind, make_config_map_name(zk), zk);
+            let cm_obj = resources.get(&cm_key);
+            if cm_obj.is_none() {
+                return false;
+            }
+            let rv = cm_obj.unwrap().metadata().resource_version();
+            if rv.is_none() {
+                return false;
+            }
+            let made = make_stateful_set(zk, &rv.unwrap());
+            stateful_set_spec_matches(&key, &made, resources) && metadata_matches(&key, &made.metadata(), resources)
+        },
+    }
+}
+
+// current_state_matches checks liveness_theorem::current_state_matches on the objects stored in a cluster.
+pub fn current_state_matches(zk: &ZookeeperCluster, resources: &ObjectMap) -> (res: bool)
+    requires zk@.well_formed(),
+    ensures res == forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<ZookeeperMaker>(sub_resource, zk@, resources@),
+{
+    let res = resource_state_matches(SubResource::HeadlessService, zk, resources)
+        && resource_state_matches(SubResource::ClientService, zk, resources)
+        && resource_state_matches(SubResource::AdminServerService, zk, resources)
+        && resource_state_matches(SubResource::ConfigMap, zk, resources)
+        && resource_state_matches(SubResource::StatefulSet, zk, resources);
+    proof {
+        if res {
+            assert forall |sub_resource: SubResource| #[trigger] liveness_theorem::resource_state_matches::<ZookeeperMaker>(sub_resource, zk@, resources@) by {
+                match sub_resource {
+                    SubResource::HeadlessService => {},
+                    SubResource::ClientService => {},
+                    SubResource::AdminServerService => {},
+                    SubResource::ConfigMap => {},
+                    SubResource::StatefulSet => {},
+                }
+            }
+        }
+    }
+    res
+}
+
+fn make_key(kind: KubeKind, name: String, zk: &ZookeeperCluster) -> (key: KubeObjectRef)
+    requires zk@.well_formed(),
+    ensures key@ == (ObjectRef { kind: kind@, name: name@, namespace: zk@.metadata.namespace.get_Some_0() }),
+{
+    KubeObjectRef {
+        kind: kind,
+        name: name,
+        namespace: zk.metadata().namespace().unwrap(),
+    }
+}
+
+}
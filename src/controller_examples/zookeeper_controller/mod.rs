@@ -4,3 +4,5 @@ pub mod exec;
 pub mod model;
 pub mod proof;
 pub mod trusted;
+#[cfg(test)]
+pub mod simulation_tests;
@@ -67,13 +67,16 @@ pub proof fn lemma_always_object_in_every_resource_create_or_update_request_msg_
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::triggering_cr_has_lower_uid_than_uid_counter()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
     };
     ZKCluster::lemma_always_triggering_cr_has_lower_uid_than_uid_counter(spec);
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(ZKCluster::next()),
         lift_state(ZKCluster::triggering_cr_has_lower_uid_than_uid_counter()),
-        lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion())
     );
     let resource_key = get_request(sub_resource, zookeeper).key;
     let create_valid = |msg: ZKMessage, s: ZKCluster| {
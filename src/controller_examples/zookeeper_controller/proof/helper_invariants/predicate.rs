@@ -41,7 +41,7 @@ pub open spec fn the_object_in_schedule_satisfies_state_validation() -> StatePre
     |s: ZKCluster| {
         forall |key: ObjectRef|
         #[trigger] s.scheduled_reconciles().contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == ZookeeperClusterView::kind()
         ==> s.scheduled_reconciles()[key].state_validation()
     }
 }
@@ -51,7 +51,7 @@ pub open spec fn cr_objects_in_etcd_satisfy_state_validation() -> StatePred<ZKCl
     |s: ZKCluster| {
         forall |key: ObjectRef|
         #[trigger] s.resources().contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == ZookeeperClusterView::kind()
         ==> ZookeeperClusterView::unmarshal(s.resources()[key]).is_Ok()
             && ZookeeperClusterView::unmarshal(s.resources()[key]).get_Ok_0().state_validation()
     }
@@ -6,11 +6,13 @@ use crate::kubernetes_api_objects::spec::{
     api_method::*, common::*, owner_reference::*, prelude::*, resource::*,
 };
 use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::request_uses_propagation_policy,
     cluster::*,
     cluster_state_machine::Step,
     controller::types::{ControllerActionInput, ControllerStep},
     message::*,
 };
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
 use crate::temporal_logic::{defs::*, rules::*};
 use crate::vstd_ext::{multiset_lib, seq_lib, string_view::*};
 use crate::zookeeper_controller::{
@@ -18,7 +20,7 @@ use crate::zookeeper_controller::{
     proof::{
         helper_invariants::stateful_set_in_etcd_satisfies_unchangeable, predicate::*, resource::*,
     },
-    trusted::{liveness_theorem::*, spec_types::*, step::*},
+    trusted::{liveness_theorem::*, spec_types::*, step::*, zookeeper_api_spec::*},
 };
 use vstd::{multiset::*, prelude::*, string::*};
 
@@ -540,7 +542,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
         spec.entails(always(lift_state(ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -576,7 +580,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
         spec.entails(always(lift_state(ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()))),
@@ -610,7 +616,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)(s)
         &&& ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
@@ -655,7 +663,9 @@ pub proof fn lemma_eventually_always_every_resource_update_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::crash_disabled()), lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)),
         lift_state(ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()),
@@ -686,7 +696,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
     ensures
@@ -710,7 +722,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
     ensures spec.entails(true_pred().leads_to(always(lift_state(object_in_every_resource_update_request_only_has_owner_references_pointing_to_current_cr(sub_resource, zookeeper))))),
@@ -728,7 +742,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)(s)
     };
@@ -750,7 +766,9 @@ pub proof fn lemma_eventually_always_object_in_every_resource_update_request_onl
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::crash_disabled()), lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper))
     );
@@ -772,7 +790,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
         spec.entails(always(lift_state(zookeeper_is_well_formed(zookeeper)))),
@@ -795,7 +815,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
         spec.entails(always(lift_state(zookeeper_is_well_formed(zookeeper)))),
@@ -815,7 +837,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)(s)
         &&& zookeeper_is_well_formed(zookeeper)(s)
@@ -838,7 +862,9 @@ pub proof fn lemma_eventually_always_every_resource_create_request_implies_at_af
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::crash_disabled()), lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)),
         lift_state(zookeeper_is_well_formed(zookeeper))
@@ -969,7 +995,7 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
@@ -982,6 +1008,11 @@ pub proof fn lemma_always_no_update_status_request_msg_not_from_bc_in_flight_of_
                         assert(!msg.content.is_update_status_request());
                         assert(false);
                     },
+                    Step::ServeReadFromCacheStep(_) => {
+                        assert(!msg.content.is_APIRequest());
+                        assert(!msg.content.is_update_status_request());
+                        assert(false);
+                    },
                     _ => {
                         assert(!s_prime.in_flight().contains(msg));
                         assert(false);
@@ -1003,6 +1034,42 @@ spec fn make_owner_references_with_name_and_uid(name: StringView, uid: Uid) -> O
     }
 }
 
+/// The zookeeper controller never deletes any object, and the objects it creates or updates are built by make and update,
+/// which set no finalizers. So the controller does not use the orphan or foreground propagation policy.
+pub proof fn lemma_reconciler_does_not_use_propagation_policy()
+    ensures ZKCluster::reconciler_does_not_use_propagation_policy(),
+{
+    assert forall |zookeeper: ZookeeperClusterView, resp_o: Option<ResponseView<ZKAPIOutputView>>, state: ZookeeperReconcileState|
+        #![trigger ZookeeperReconciler::reconcile_core(zookeeper, resp_o, state)]
+        ZookeeperReconciler::reconcile_core(zookeeper, resp_o, state).1.is_Some()
+        && ZookeeperReconciler::reconcile_core(zookeeper, resp_o, state).1.get_Some_0().is_KRequest()
+    implies !request_uses_propagation_policy(ZookeeperReconciler::reconcile_core(zookeeper, resp_o, state).1.get_Some_0().get_KRequest_0()) by {
+        let step = state.reconcile_step;
+        if step.is_AfterKRequestStep() && step.get_AfterKRequestStep_0() == ActionKind::Get
+        && resp_o.is_Some() && resp_o.get_Some_0().is_KResponse() && resp_o.get_Some_0().get_KResponse_0().is_GetResponse() {
+            let sub_resource = step.get_AfterKRequestStep_1();
+            let get_resp = resp_o.get_Some_0().get_KResponse_0().get_GetResponse_0().res;
+            if get_resp.is_Ok() {
+                if update(sub_resource, zookeeper, state, get_resp.get_Ok_0()).is_Ok() {
+                    assert(update(sub_resource, zookeeper, state, get_resp.get_Ok_0()).get_Ok_0().metadata.finalizers.is_None());
+                }
+            } else if make(sub_resource, zookeeper, state).is_Ok() {
+                assert(make(sub_resource, zookeeper, state).get_Ok_0().metadata.finalizers.is_None());
+            }
+        }
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<ZKCluster>)
+    requires
+        spec.entails(lift_state(ZKCluster::init())),
+        spec.entails(always(lift_action(ZKCluster::next()))),
+    ensures spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
+{
+    lemma_reconciler_does_not_use_propagation_policy();
+    ZKCluster::lemma_always_no_orphan_or_foreground_deletion(spec);
+}
+
 pub proof fn lemma_always_resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(spec: TempPred<ZKCluster>, sub_resource: SubResource, zookeeper: ZookeeperClusterView)
     requires
         spec.entails(lift_state(ZKCluster::init())),
@@ -1073,14 +1140,17 @@ proof fn lemma_always_resource_object_create_or_update_request_msg_has_one_contr
     let stronger_next = |s, s_prime| {
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
     };
     let key = zookeeper.object_ref();
     let resource_key = get_request(sub_resource, zookeeper).key;
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(stronger_next),
         lift_action(ZKCluster::next()),
-        lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata())
+        lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion())
     );
     let create_msg_pred = |msg: ZKMessage| {
         resource_create_request_msg(resource_key)(msg)
@@ -1143,6 +1213,7 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
         !s.in_flight().contains(msg), s_prime.in_flight().contains(msg),
         ZKCluster::next_step(s, s_prime, step),
         ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        ZKCluster::no_orphan_or_foreground_deletion()(s),
     ensures
         resource_create_request_msg(get_request(sub_resource, zookeeper).key)(msg)
         ==> step.is_ControllerStep() && step.get_ControllerStep_0().1.get_Some_0() == zookeeper.object_ref()
@@ -1163,6 +1234,10 @@ pub proof fn lemma_resource_create_or_update_request_msg_implies_key_in_reconcil
     let cr = s.ongoing_reconciles()[key].triggering_cr;
     let resource_key = get_request(sub_resource, zookeeper).key;
     if resource_create_request_msg(get_request(sub_resource, zookeeper).key)(msg) || resource_update_request_msg(get_request(sub_resource, zookeeper).key)(msg) {
+        if step.is_BuiltinControllersStep() {
+            // The builtin controllers do not orphan the dependents, so they only send delete and update status requests.
+            ZKCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, step.get_BuiltinControllersStep_0());
+        }
         assert(step.is_ControllerStep());
         assert(s.ongoing_reconciles().contains_key(cr_key));
         let local_step = s.ongoing_reconciles()[cr_key].local_state.reconcile_step;
@@ -1285,6 +1360,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight_fo
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(ZKCluster::next()))),
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| ZKCluster::external_api_next().weak_fairness(i))),
@@ -1324,6 +1401,8 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion()))),
         spec.entails(always(lift_action(ZKCluster::next()))),
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| ZKCluster::external_api_next().weak_fairness(i))),
@@ -1342,6 +1421,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     let stronger_next = |s: ZKCluster, s_prime: ZKCluster| {
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::desired_state_is(zookeeper)(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, zookeeper)(s)
         &&& resource_well_formed(s)
     };
@@ -1355,7 +1435,12 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
             } else {
                 let step = choose |step| ZKCluster::next_step(s, s_prime, step);
                 match step {
-                    Step::BuiltinControllersStep(_) => {
+                    Step::BuiltinControllersStep(input) => {
+                        ZKCluster::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                        if input.0.is_Kubelet() {
+                            // The kubelet only deletes pods, and none of the sub resources is a pod.
+                            assert(resource_key.kind != Kind::PodKind);
+                        }
                         if s.resources().contains_key(resource_key) {
                             let owner_refs = s.resources()[resource_key].metadata.owner_references;
                             assert(owner_refs == Some(seq![zookeeper.controller_owner_ref()]));
@@ -1369,7 +1454,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
                             assert(!s_prime.ongoing_reconciles()[cr_key].pending_req_msg.get_Some_0().content.is_delete_request());
                         }
                     },
-                    Step::ClientStep() => {
+                    Step::ClientStep(_) => {
                         if msg.content.is_delete_request() {
                             assert(msg.content.get_delete_request().key.kind != resource_key.kind);
                         }
@@ -1384,6 +1469,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::desired_state_is(zookeeper)),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(resource_object_only_has_owner_reference_pointing_to_current_cr(sub_resource, zookeeper)),
         lift_state(resource_well_formed)
     );
@@ -1398,6 +1484,7 @@ pub proof fn lemma_eventually_always_no_delete_resource_request_msg_in_flight(sp
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr_forall(spec: TempPred<ZKCluster>, zookeeper: ZookeeperClusterView)
     requires
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(ZKCluster::next()))),
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| ZKCluster::builtin_controllers_next().weak_fairness(i))),
@@ -1422,6 +1509,7 @@ pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_po
 pub proof fn lemma_eventually_always_resource_object_only_has_owner_reference_pointing_to_current_cr(spec: TempPred<ZKCluster>, sub_resource: SubResource, zookeeper: ZookeeperClusterView)
     requires
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_action(ZKCluster::next()))),
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(tla_forall(|i| ZKCluster::builtin_controllers_next().weak_fairness(i))),
@@ -71,15 +71,18 @@ proof fn lemma_always_object_in_every_create_request_msg_satisfies_unchangeable(
     let next = |s, s_prime| {
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& the_object_in_reconcile_satisfies_state_validation(zookeeper.object_ref())(s)
     };
     ZKCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     lemma_always_the_object_in_reconcile_satisfies_state_validation(spec, zookeeper.object_ref());
     combine_spec_entails_always_n!(
         spec, lift_action(next),
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(the_object_in_reconcile_satisfies_state_validation(zookeeper.object_ref()))
     );
     let resource_key = get_request(sub_resource, zookeeper).key;
@@ -121,6 +124,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<Z
     let next = |s: ZKCluster, s_prime: ZKCluster| {
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& ZKCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(resource_key)(s)
@@ -131,6 +135,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<Z
         &&& no_create_resource_request_msg_without_name_in_flight(sub_resource, zookeeper)(s)
     };
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     ZKCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     always_to_always_later(spec, lift_state(ZKCluster::each_object_in_etcd_is_well_formed()));
     ZKCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, resource_key);
@@ -142,6 +147,7 @@ pub proof fn lemma_always_object_in_etcd_satisfies_unchangeable(spec: TempPred<Z
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(ZKCluster::next()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::each_object_in_etcd_is_well_formed()),
         later(lift_state(ZKCluster::each_object_in_etcd_is_well_formed())),
         lift_state(ZKCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(resource_key)),
@@ -203,6 +209,7 @@ pub proof fn object_in_every_update_request_msg_satisfies_unchangeable_induction
         object_in_every_update_request_msg_satisfies_unchangeable(sub_resource, zookeeper)(s),
         ZKCluster::next()(s, s_prime),
         ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s),
+        ZKCluster::no_orphan_or_foreground_deletion()(s),
         ZKCluster::each_object_in_etcd_is_well_formed()(s),
         ZKCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(get_request(sub_resource, zookeeper).key)(s),
         response_at_after_get_resource_step_is_resource_get_response(sub_resource, zookeeper)(s),
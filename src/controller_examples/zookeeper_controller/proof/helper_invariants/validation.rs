@@ -155,6 +155,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, zookeeper)(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)(s)
         &&& object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, zookeeper)(s)
     };
@@ -162,6 +163,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, SubResource::StatefulSet, zookeeper);
     always_to_always_later(spec, lift_state(ZKCluster::each_object_in_etcd_is_well_formed()));
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     ZKCluster::lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spec, sts_key);
     lemma_always_object_in_resource_update_request_msg_has_smaller_rv_than_etcd(spec, SubResource::StatefulSet, zookeeper);
     combine_spec_entails_always_n!(
@@ -170,6 +172,7 @@ pub proof fn lemma_always_stateful_set_update_request_msg_does_not_change_owner_
         later(lift_state(ZKCluster::each_object_in_etcd_is_well_formed())),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(SubResource::StatefulSet, zookeeper)),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::object_in_ok_get_resp_is_same_as_etcd_with_same_rv(sts_key)),
         lift_state(object_in_resource_update_request_msg_has_smaller_rv_than_etcd(SubResource::StatefulSet, zookeeper))
     );
@@ -224,12 +227,14 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& response_at_after_get_resource_step_is_resource_get_response(sub_resource, zookeeper)(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
     };
     ZKCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     lemma_always_response_at_after_get_resource_step_is_resource_get_response(spec, sub_resource, zookeeper);
     always_to_always_later(spec, lift_state(ZKCluster::each_object_in_etcd_is_well_formed()));
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     ZKCluster::lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(ZKCluster::next()),
@@ -237,6 +242,7 @@ pub proof fn lemma_always_object_in_resource_update_request_msg_has_smaller_rv_t
         later(lift_state(ZKCluster::each_object_in_etcd_is_well_formed())),
         lift_state(response_at_after_get_resource_step_is_resource_get_response(sub_resource, zookeeper)),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::object_in_ok_get_response_has_smaller_rv_than_etcd())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
@@ -281,12 +287,14 @@ proof fn lemma_always_stateful_set_in_create_request_msg_satisfies_unchangeable(
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s_prime)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
+        &&& ZKCluster::no_orphan_or_foreground_deletion()(s)
         &&& ZKCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(zookeeper)(s)
         &&& object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(sts_res, zookeeper)(s)
     };
     ZKCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     always_to_always_later(spec, lift_state(ZKCluster::each_object_in_etcd_is_well_formed()));
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    lemma_always_no_orphan_or_foreground_deletion(spec);
     ZKCluster::lemma_always_transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(spec, zookeeper);
     lemma_always_object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(spec, sts_res, zookeeper);
     combine_spec_entails_always_n!(
@@ -295,6 +303,7 @@ proof fn lemma_always_stateful_set_in_create_request_msg_satisfies_unchangeable(
         lift_state(ZKCluster::each_object_in_etcd_is_well_formed()),
         later(lift_state(ZKCluster::each_object_in_etcd_is_well_formed())),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion()),
         lift_state(ZKCluster::transition_rule_applies_to_etcd_and_scheduled_and_triggering_cr(zookeeper)),
         lift_state(object_in_every_resource_create_or_update_request_msg_only_has_valid_owner_references(sts_res, zookeeper))
     );
@@ -49,6 +49,7 @@ pub proof fn lemma_eventually_always_every_zk_set_data_request_implies_at_after_
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
@@ -67,6 +68,7 @@ pub proof fn lemma_eventually_always_every_zk_set_data_request_implies_at_after_
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)(s)
@@ -91,6 +93,7 @@ pub proof fn lemma_eventually_always_every_zk_set_data_request_implies_at_after_
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::crash_disabled()), lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper))
@@ -127,6 +130,7 @@ pub proof fn lemma_eventually_always_every_zk_create_node_request_implies_at_aft
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_lower_id_than_allocator()))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)))),
@@ -145,6 +149,7 @@ pub proof fn lemma_eventually_always_every_zk_create_node_request_implies_at_aft
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper)(s)
@@ -169,6 +174,7 @@ pub proof fn lemma_eventually_always_every_zk_create_node_request_implies_at_aft
     invariant_n!(
         spec, lift_action(stronger_next), lift_action(ZKCluster::every_new_req_msg_if_in_flight_then_satisfies(requirements)),
         lift_action(ZKCluster::next()), lift_state(ZKCluster::crash_disabled()), lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::the_object_in_reconcile_has_spec_and_uid_as(zookeeper))
@@ -33,6 +33,7 @@ pub proof fn lemma_from_after_get_resource_step_to_resource_matches(
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
@@ -94,6 +95,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_reconcile_has_consistent_key_and_valid_metadata()))),
@@ -216,6 +218,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
                     &&& ZKCluster::next()(s, s_prime)
                     &&& ZKCluster::crash_disabled()(s)
                     &&& ZKCluster::busy_disabled()(s)
+                    &&& ZKCluster::stale_read_disabled()(s)
                     &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
                     &&& ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())(s)
                 };
@@ -225,6 +228,7 @@ pub proof fn lemma_from_after_get_resource_step_and_key_not_exists_to_resource_m
                     lift_action(ZKCluster::next()),
                     lift_state(ZKCluster::crash_disabled()),
                     lift_state(ZKCluster::busy_disabled()),
+                    lift_state(ZKCluster::stale_read_disabled()),
                     lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
                     lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref()))
                 );
@@ -278,6 +282,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
@@ -389,6 +394,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
                 &&& ZKCluster::next()(s, s_prime)
                 &&& ZKCluster::crash_disabled()(s)
                 &&& ZKCluster::busy_disabled()(s)
+                &&& ZKCluster::stale_read_disabled()(s)
                 &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
                 &&& ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())(s)
             };
@@ -397,6 +403,7 @@ proof fn lemma_from_after_get_resource_step_and_key_exists_to_resource_matches(
                 lift_action(ZKCluster::next()),
                 lift_state(ZKCluster::crash_disabled()),
                 lift_state(ZKCluster::busy_disabled()),
+                lift_state(ZKCluster::stale_read_disabled()),
                 lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
                 lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref()))
             );
@@ -446,6 +453,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, zookeeper)))),
         spec.entails(always(lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, zookeeper)))),
@@ -478,6 +486,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, zookeeper)(s)
         &&& helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, zookeeper)(s)
@@ -487,6 +496,7 @@ proof fn lemma_from_key_not_exists_to_receives_not_found_resp_at_after_get_resou
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::crash_disabled()),
         lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, zookeeper)),
         lift_state(helper_invariants::every_resource_create_request_implies_at_after_create_resource_step(sub_resource, zookeeper))
@@ -616,6 +626,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(zookeeper.object_ref())))),
@@ -645,6 +656,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s)
         &&& helper_invariants::the_object_in_reconcile_satisfies_state_validation(zookeeper.object_ref())(s)
@@ -657,6 +669,7 @@ proof fn lemma_resource_state_matches_at_after_create_resource_step(
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::crash_disabled()),
         lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::each_object_in_etcd_is_well_formed()),
         lift_state(helper_invariants::the_object_in_reconcile_satisfies_state_validation(zookeeper.object_ref())),
@@ -711,6 +724,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, zookeeper)))),
         spec.entails(always(lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, zookeeper)))),
@@ -727,6 +741,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, zookeeper)(s)
         &&& helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, zookeeper)(s)
@@ -737,6 +752,7 @@ proof fn lemma_from_key_exists_to_receives_ok_resp_at_after_get_resource_step(sp
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::crash_disabled()),
         lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(helper_invariants::every_resource_update_request_implies_at_after_update_resource_step(sub_resource, zookeeper)),
         lift_state(helper_invariants::no_delete_resource_request_msg_in_flight(sub_resource, zookeeper)),
@@ -789,6 +805,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         spec.entails(tla_forall(|i| ZKCluster::kubernetes_api_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::desired_state_is(zookeeper)))),
@@ -811,6 +828,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s)
         &&& ZKCluster::desired_state_is(zookeeper)(s)
@@ -827,6 +845,7 @@ proof fn lemma_resource_state_matches_at_after_update_resource_step(spec: TempPr
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::crash_disabled()),
         lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
         lift_state(ZKCluster::each_object_in_etcd_is_well_formed()),
         lift_state(ZKCluster::desired_state_is(zookeeper)),
@@ -884,6 +903,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(spec:
         spec.entails(tla_forall(|i| ZKCluster::controller_next().weak_fairness(i))),
         spec.entails(always(lift_state(ZKCluster::crash_disabled()))),
         spec.entails(always(lift_state(ZKCluster::busy_disabled()))),
+        spec.entails(always(lift_state(ZKCluster::stale_read_disabled()))),
         spec.entails(always(lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())))),
         spec.entails(always(lift_state(ZKCluster::each_object_in_etcd_is_well_formed()))),
         spec.entails(always(lift_state(ZKCluster::every_in_flight_msg_has_unique_id()))),
@@ -903,6 +923,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(spec:
         &&& ZKCluster::next()(s, s_prime)
         &&& ZKCluster::crash_disabled()(s)
         &&& ZKCluster::busy_disabled()(s)
+        &&& ZKCluster::stale_read_disabled()(s)
         &&& ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())(s)
         &&& ZKCluster::each_object_in_etcd_is_well_formed()(s)
         &&& ZKCluster::every_in_flight_msg_has_unique_id()(s)
@@ -919,6 +940,7 @@ proof fn lemma_from_after_get_resource_step_to_after_update_resource_step(spec:
         lift_action(ZKCluster::next()),
         lift_state(ZKCluster::crash_disabled()),
         lift_state(ZKCluster::busy_disabled()),
+        lift_state(ZKCluster::stale_read_disabled()),
         lift_state(ZKCluster::pending_req_of_key_is_unique_with_unique_id(zookeeper.object_ref())),
         lift_state(ZKCluster::each_object_in_etcd_is_well_formed()),
         lift_state(ZKCluster::every_in_flight_msg_has_unique_id()),
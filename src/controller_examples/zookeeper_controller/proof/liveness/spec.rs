@@ -231,6 +231,8 @@ pub open spec fn derived_invariants_since_beginning(zookeeper: ZookeeperClusterV
     .and(always(lift_state(helper_invariants::stateful_set_in_etcd_satisfies_unchangeable(zookeeper))))
     .and(always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::object_in_etcd_satisfies_unchangeable(sub_resource, zookeeper)))))
     .and(always(tla_forall(|sub_resource: SubResource| lift_state(helper_invariants::no_create_resource_request_msg_without_name_in_flight(sub_resource, zookeeper)))))
+    .and(always(lift_state(ZKCluster::stale_read_disabled())))
+    .and(always(lift_state(ZKCluster::no_orphan_or_foreground_deletion())))
 }
 
 pub proof fn derived_invariants_since_beginning_is_stable(zookeeper: ZookeeperClusterView)
@@ -271,7 +273,9 @@ pub proof fn derived_invariants_since_beginning_is_stable(zookeeper: ZookeeperCl
         tla_forall(a_to_p_5),
         lift_state(helper_invariants::stateful_set_in_etcd_satisfies_unchangeable(zookeeper)),
         tla_forall(a_to_p_6),
-        tla_forall(a_to_p_7)
+        tla_forall(a_to_p_7),
+        lift_state(ZKCluster::stale_read_disabled()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -482,6 +486,8 @@ pub proof fn sm_spec_entails_all_invariants(zookeeper: ZookeeperClusterView)
     ZKCluster::lemma_always_each_object_in_etcd_is_well_formed(spec);
     ZKCluster::lemma_always_each_scheduled_object_has_consistent_key_and_valid_metadata(spec);
     ZKCluster::lemma_always_each_object_in_reconcile_has_consistent_key_and_valid_metadata(spec);
+    ZKCluster::lemma_always_stale_read_disabled(spec);
+    helper_invariants::lemma_always_no_orphan_or_foreground_deletion(spec);
     let a_to_p_1 = |sub_resource: SubResource| lift_state(helper_invariants::resource_object_has_no_finalizers_or_timestamp_and_only_has_controller_owner_ref(sub_resource, zookeeper));
     assert_by(spec.entails(always(tla_forall(a_to_p_1))), {
         assert forall |sub_resource: SubResource| spec.entails(always(#[trigger] a_to_p_1(sub_resource))) by {
@@ -575,7 +581,9 @@ pub proof fn sm_spec_entails_all_invariants(zookeeper: ZookeeperClusterView)
         tla_forall(a_to_p_5),
         lift_state(helper_invariants::stateful_set_in_etcd_satisfies_unchangeable(zookeeper)),
         tla_forall(a_to_p_6),
-        tla_forall(a_to_p_7)
+        tla_forall(a_to_p_7),
+        lift_state(ZKCluster::stale_read_disabled()),
+        lift_state(ZKCluster::no_orphan_or_foreground_deletion())
     );
 }
 
@@ -33,7 +33,7 @@ pub open spec fn at_step_closure(step: ZookeeperReconcileStep) -> spec_fn(Zookee
 
 pub open spec fn at_zk_step(key: ObjectRef, step: ZookeeperReconcileStep) -> StatePred<ZKCluster>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == ZookeeperClusterView::kind()
 {
     |s: ZKCluster| {
         &&& s.ongoing_reconciles().contains_key(key)
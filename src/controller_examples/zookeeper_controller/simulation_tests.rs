@@ -0,0 +1,261 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, config_map::ConfigMap, dynamic::*, resource::*,
+    service::Service, stateful_set::StatefulSet,
+};
+use crate::kubernetes_api_objects::spec::prelude::PatchType;
+use crate::shim_layer::reconcile_trace::*;
+use crate::shim_layer::refinement_monitor::*;
+use crate::shim_layer::simulation::*;
+use crate::zookeeper_controller::exec::{reconciler::ZookeeperReconciler, state_matches};
+use crate::zookeeper_controller::trusted::{
+    exec_types, step::ZKAPIError, zookeeper_api_exec::*,
+};
+use deps_hack::k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use deps_hack::{
+    ZookeeperCluster, ZookeeperClusterSpec, ZookeeperConfig, ZookeeperPersistence, ZookeeperPorts,
+};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+// ZKNodes is an in-memory stand-in for the zookeeper ensemble, mapping each node path to its data and version.
+// It follows the zookeeper semantics that the controller relies on: a node can only be created under an existing
+// parent, creating an existing node fails with ZKNodeCreateAlreadyExists, and setting the data requires the
+// expected version and bumps the version.
+type ZKNodes = Rc<RefCell<BTreeMap<String, (String, i32)>>>;
+
+fn zk_path(path: &Vec<String>) -> String {
+    format!("/{}", path.join("/"))
+}
+
+fn zk_parent_path(path: &Vec<String>) -> String {
+    zk_path(&path[..path.len() - 1].to_vec())
+}
+
+fn handle_zk_request(nodes: &ZKNodes, input: ZKAPIInput) -> ZKAPIOutput {
+    let mut nodes = nodes.borrow_mut();
+    match input {
+        ZKAPIInput::ExistsRequest(_, _, _, path) => ZKAPIOutput::ExistsResponse(ZKAPIExistsResult {
+            res: Ok(nodes.get(&zk_path(&path)).map(|(_, version)| *version)),
+        }),
+        ZKAPIInput::CreateRequest(_, _, _, path, data) => {
+            let res = if nodes.contains_key(&zk_path(&path)) {
+                Err(ZKAPIError::ZKNodeCreateAlreadyExists)
+            } else if path.len() > 1 && !nodes.contains_key(&zk_parent_path(&path)) {
+                Err(ZKAPIError::ZKNodeCreateFailed)
+            } else {
+                nodes.insert(zk_path(&path), (data, 0));
+                Ok(())
+            };
+            ZKAPIOutput::CreateResponse(ZKAPICreateResult { res: res })
+        }
+        ZKAPIInput::SetDataRequest(_, _, _, path, data, version) => {
+            let res = match nodes.get(&zk_path(&path)) {
+                Some((_, current_version)) if *current_version == version => {
+                    nodes.insert(zk_path(&path), (data, version + 1));
+                    Ok(())
+                }
+                _ => Err(ZKAPIError::ZKNodeSetDataFailed),
+            };
+            ZKAPIOutput::SetDataResponse(ZKAPISetDataResult { res: res })
+        }
+    }
+}
+
+fn make_cluster() -> (SimulatedCluster<ZookeeperReconciler>, ZKNodes) {
+    let mut cluster = SimulatedCluster::<ZookeeperReconciler>::new();
+    let nodes: ZKNodes = Rc::new(RefCell::new(BTreeMap::new()));
+    let nodes_for_api = nodes.clone();
+    cluster.set_external_api(move |input| handle_zk_request(&nodes_for_api, input));
+    (cluster, nodes)
+}
+
+fn make_zookeeper_cluster(replicas: i32) -> ZookeeperCluster {
+    let mut zk = ZookeeperCluster::new(
+        "test",
+        ZookeeperClusterSpec {
+            replicas: replicas,
+            image: "pravega/zookeeper:0.2.14".to_string(),
+            ports: ZookeeperPorts {
+                client: 2181,
+                quorum: 2888,
+                leader_election: 3888,
+                metrics: 7000,
+                admin_server: 8080,
+            },
+            conf: ZookeeperConfig {
+                init_limit: 10,
+                tick_time: 2000,
+                sync_limit: 2,
+                global_outstanding_limit: 1000,
+                pre_alloc_size: 65536,
+                snap_count: 10000,
+                commit_log_count: 500,
+                snap_size_limit_in_kb: 4194304,
+                max_cnxns: 0,
+                max_client_cnxns: 60,
+                min_session_timeout: 4000,
+                max_session_timeout: 40000,
+                auto_purge_snap_retain_count: 3,
+                auto_purge_purge_interval: 1,
+                quorum_listen_on_all_ips: false,
+            },
+            persistence: ZookeeperPersistence {
+                enabled: true,
+                storage_size: Quantity("20Gi".to_string()),
+                storage_class_name: "standard".to_string(),
+            },
+            resources: None,
+            affinity: None,
+            tolerations: None,
+            node_selector: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+        },
+    );
+    zk.metadata.namespace = Some("default".to_string());
+    zk
+}
+
+fn scale(cluster: &mut SimulatedCluster<ZookeeperReconciler>, replicas: i32) {
+    let patch = DynamicObject::from_kube(
+        deps_hack::serde_json::from_value(deps_hack::serde_json::json!({
+            "apiVersion": "anvil.dev/v1",
+            "kind": "ZookeeperCluster",
+            "metadata": {},
+            "spec": { "replicas": replicas },
+        }))
+        .unwrap(),
+    );
+    let resp = cluster.handle_request(KubeAPIRequest::PatchRequest(KubePatchRequest {
+        api_resource: exec_types::ZookeeperCluster::api_resource(),
+        name: "test".to_string(),
+        namespace: "default".to_string(),
+        patch: patch,
+        patch_type: PatchType::MergePatch,
+    }));
+    assert!(resp.into_patch_response().res.is_ok());
+}
+
+// current_state_matches runs the exec checker of the liveness theorem on the state of the simulated cluster
+fn current_state_matches(cluster: &SimulatedCluster<ZookeeperReconciler>) -> bool {
+    let obj = cluster
+        .get(exec_types::ZookeeperCluster::api_resource(), "default", "test")
+        .unwrap();
+    let zk = match exec_types::ZookeeperCluster::unmarshal(obj) {
+        Ok(zk) => zk,
+        Err(_) => panic!("Fail to unmarshal the ZookeeperCluster"),
+    };
+    state_matches::current_state_matches(&zk, &cluster.api_server_state.resources)
+}
+
+fn stateful_set_replicas(cluster: &SimulatedCluster<ZookeeperReconciler>) -> Option<i32> {
+    let obj = cluster
+        .get(StatefulSet::api_resource(), "default", "test")
+        .unwrap();
+    match StatefulSet::unmarshal(obj) {
+        Ok(sts) => sts.spec().unwrap().replicas(),
+        Err(_) => panic!("Fail to unmarshal the StatefulSet"),
+    }
+}
+
+#[test]
+pub fn test_reconcile_creates_objects() {
+    let (mut cluster, nodes) = make_cluster();
+    cluster.create(&make_zookeeper_cluster(3)).unwrap();
+    assert!(!current_state_matches(&cluster));
+    let outcome = cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    assert!(matches!(outcome, SimulatedReconcileOutcome::Done { .. }));
+    // The headless, client and admin server services
+    assert_eq!(cluster.list(Service::api_resource(), "default").len(), 3);
+    assert_eq!(cluster.list(ConfigMap::api_resource(), "default").len(), 1);
+    assert_eq!(stateful_set_replicas(&cluster), Some(3));
+    assert!(current_state_matches(&cluster));
+    // The zookeeper node is only written once the stateful set exists
+    assert!(nodes.borrow().is_empty());
+}
+
+#[test]
+pub fn test_reconcile_writes_zk_node() {
+    let (mut cluster, nodes) = make_cluster();
+    cluster.create(&make_zookeeper_cluster(3)).unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    assert!(nodes.borrow().contains_key("/zookeeper-operator"));
+    assert_eq!(
+        nodes.borrow().get("/zookeeper-operator/test"),
+        Some(&("CLUSTER_SIZE=3".to_string(), 0))
+    );
+    assert!(current_state_matches(&cluster));
+}
+
+#[test]
+pub fn test_reconcile_updates_zk_node_before_scaling() {
+    let (mut cluster, nodes) = make_cluster();
+    cluster.create(&make_zookeeper_cluster(3)).unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    scale(&mut cluster, 5);
+    assert!(!current_state_matches(&cluster));
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    assert_eq!(
+        nodes.borrow().get("/zookeeper-operator/test"),
+        Some(&("CLUSTER_SIZE=5".to_string(), 1))
+    );
+    assert_eq!(stateful_set_replicas(&cluster), Some(5));
+    assert!(current_state_matches(&cluster));
+}
+
+#[test]
+pub fn test_reconcile_does_not_scale_when_zk_node_is_not_updated() {
+    let (mut cluster, nodes) = make_cluster();
+    cluster.create(&make_zookeeper_cluster(5)).unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    // Someone else updates the zookeeper node so the version expected by the controller is stale
+    let nodes_for_api = nodes.clone();
+    cluster.set_external_api(move |input| match input {
+        ZKAPIInput::ExistsRequest(..) => ZKAPIOutput::ExistsResponse(ZKAPIExistsResult { res: Ok(Some(0)) }),
+        input => handle_zk_request(&nodes_for_api, input),
+    });
+    nodes.borrow_mut().insert(
+        "/zookeeper-operator/test".to_string(),
+        ("CLUSTER_SIZE=5".to_string(), 1),
+    );
+    scale(&mut cluster, 3);
+    assert!(cluster.reconcile::<ZookeeperCluster>("default", "test").is_err());
+    // The stateful set is not scaled down before the membership change is written to zookeeper
+    assert_eq!(stateful_set_replicas(&cluster), Some(5));
+}
+
+#[test]
+pub fn test_reconcile_cr_not_found() {
+    let (mut cluster, _) = make_cluster();
+    let outcome = cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    assert_eq!(outcome, SimulatedReconcileOutcome::CrNotFound);
+    assert_eq!(cluster.list(StatefulSet::api_resource(), "default").len(), 0);
+}
+
+#[test]
+pub fn test_reconciles_refine_executable_model() {
+    let (mut cluster, _) = make_cluster();
+    cluster.create(&make_zookeeper_cluster(3)).unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    scale(&mut cluster, 5);
+    cluster.reconcile::<ZookeeperCluster>("default", "test").unwrap();
+    assert_eq!(cluster.traces.len(), 3);
+    for trace in cluster.traces.iter() {
+        assert_eq!(trace.outcome, ReconcileTraceOutcome::Done);
+        assert_eq!(
+            replay_reconcile::<ZookeeperCluster, ZookeeperReconciler>(trace),
+            ReplayResult::Consistent
+        );
+        assert_eq!(
+            check_reconcile_trace::<ZookeeperCluster, ZookeeperReconciler>(trace),
+            Ok(())
+        );
+    }
+}
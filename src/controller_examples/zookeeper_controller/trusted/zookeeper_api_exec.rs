@@ -1,6 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 use crate::external_api::exec::*;
+use crate::shim_layer::reconcile_trace::TracedExternalAPI;
 use crate::zookeeper_controller::trusted::{
     step::*,
     zookeeper_api_spec::{
@@ -9,6 +10,8 @@ use crate::zookeeper_controller::trusted::{
     },
 };
 use core::time::Duration;
+use deps_hack::serde::{de::DeserializeOwned, Serialize};
+use deps_hack::serde_json::{json, Value};
 use deps_hack::tracing::info;
 use deps_hack::zookeeper::{Acl, CreateMode, WatchedEvent, Watcher, ZkError, ZkResult, ZooKeeper};
 use vstd::pervasive::unreached;
@@ -119,6 +122,82 @@ impl ExternalAPIShimLayer for ZKAPIShimLayer {
     }
 }
 
+fn zk_error_from_str(err: &str) -> Option<ZKAPIError> {
+    match err {
+        "ZKNodeExistsFailed" => Some(ZKAPIError::ZKNodeExistsFailed),
+        "ZKNodeCreateAlreadyExists" => Some(ZKAPIError::ZKNodeCreateAlreadyExists),
+        "ZKNodeCreateFailed" => Some(ZKAPIError::ZKNodeCreateFailed),
+        "ZKNodeSetDataFailed" => Some(ZKAPIError::ZKNodeSetDataFailed),
+        _ => None,
+    }
+}
+
+// The results are recorded as {"Ok": ...} or {"Err": "<ZKAPIError>"}, in the same way as the results of kube requests.
+fn zk_result_to_json<T: Serialize>(res: &Result<T, ZKAPIError>) -> Value {
+    match res {
+        Ok(value) => json!({ "Ok": value }),
+        Err(err) => json!({ "Err": format!("{:?}", err) }),
+    }
+}
+
+fn zk_result_from_json<T: DeserializeOwned>(res: &Value) -> Option<Result<T, ZKAPIError>> {
+    if let Some(value) = res.get("Ok") {
+        deps_hack::serde_json::from_value(value.clone()).ok().map(|value| Ok(value))
+    } else {
+        zk_error_from_str(res.get("Err")?.as_str()?).map(|err| Err(err))
+    }
+}
+
+impl TracedExternalAPI for ZKAPIShimLayer {
+    fn input_to_json(input: &ZKAPIInput) -> Value {
+        match input {
+            ZKAPIInput::ExistsRequest(zk_name, zk_namespace, port, path) => json!({ "Exists": {
+                "name": zk_name,
+                "namespace": zk_namespace,
+                "port": port,
+                "path": path,
+            }}),
+            ZKAPIInput::CreateRequest(zk_name, zk_namespace, port, path, data) => json!({ "Create": {
+                "name": zk_name,
+                "namespace": zk_namespace,
+                "port": port,
+                "path": path,
+                "data": data,
+            }}),
+            ZKAPIInput::SetDataRequest(zk_name, zk_namespace, port, path, data, version) => {
+                json!({ "SetData": {
+                    "name": zk_name,
+                    "namespace": zk_namespace,
+                    "port": port,
+                    "path": path,
+                    "data": data,
+                    "version": version,
+                }})
+            }
+        }
+    }
+
+    fn output_to_json(output: &ZKAPIOutput) -> Value {
+        match output {
+            ZKAPIOutput::ExistsResponse(result) => json!({ "Exists": zk_result_to_json(&result.res) }),
+            ZKAPIOutput::CreateResponse(result) => json!({ "Create": zk_result_to_json(&result.res) }),
+            ZKAPIOutput::SetDataResponse(result) => json!({ "SetData": zk_result_to_json(&result.res) }),
+        }
+    }
+
+    fn output_from_json(output: &Value) -> Option<ZKAPIOutput> {
+        if let Some(res) = output.get("Exists") {
+            Some(ZKAPIOutput::ExistsResponse(ZKAPIExistsResult { res: zk_result_from_json(res)? }))
+        } else if let Some(res) = output.get("Create") {
+            Some(ZKAPIOutput::CreateResponse(ZKAPICreateResult { res: zk_result_from_json(res)? }))
+        } else if let Some(res) = output.get("SetData") {
+            Some(ZKAPIOutput::SetDataResponse(ZKAPISetDataResult { res: zk_result_from_json(res)? }))
+        } else {
+            None
+        }
+    }
+}
+
 struct NoopWatcher;
 
 impl Watcher for NoopWatcher {
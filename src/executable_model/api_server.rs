@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use crate::executable_model::{
-    api_server_state::ApiServerState, common::*, object_map::ObjectMap,
-    object_ref_set::ObjectRefSet,
+    api_server_state::ApiServerState, apply, common::*, object_map::*, object_ref_set::ObjectRefSet,
+    patch::patched_kube_object,
 };
 use crate::kubernetes_api_objects::{error::*, exec::prelude::*, spec::prelude::*};
 use crate::kubernetes_cluster::spec::{
     api_server::state_machine as model, api_server::types as model_types,
 };
+use crate::vstd_ext::{map_lib::*, seq_lib::*, string_map::StringMap, string_view::*};
 use vstd::{multiset::*, prelude::*};
 
 verus! {
@@ -31,6 +32,14 @@ pub struct ExecutableApiServerState {
 
 pub type SimpleExecutableApiServerModel = ExecutableApiServerModel<SimpleCR>;
 
+// The kinds registered in the exec model: the built-in kinds and the custom resource K.
+// The model leaves the schema of the other custom resources uninterpreted (see model::valid_custom_resource),
+// so the exec model cannot validate their objects; it rejects the requests that write them with ObjectNotFound,
+// as the API server does for a resource whose CRD is not installed, and conforms to the model only for the registered kinds.
+pub open spec fn registered_kind<K: CustomResourceView>(kind: Kind) -> bool {
+    kind.is_CustomResourceKind() ==> kind == K::kind()
+}
+
 impl <K> ExecutableApiServerModel<K> where K: View + CustomResource, K::V: CustomResourceView {
 
 #[verifier(external_body)]
@@ -56,28 +65,39 @@ fn metadata_transition_validity_check(obj: &DynamicObject, old_obj: &DynamicObje
     if old_obj.metadata().has_deletion_timestamp()
     && obj.metadata().finalizers().is_some()
     && !obj.metadata().finalizers_as_set().subset_of(&old_obj.metadata().finalizers_as_set()) {
-        Some(APIError::Forbidden)
+        Some(APIError::Invalid)
     } else {
         None
     }
 }
 
+fn is_registered_kind(kind: &KubeKind) -> (ret: bool)
+    ensures ret == registered_kind::<K::V>(kind@)
+{
+    match kind {
+        KubeKind::CustomResourceKind(_) => kind.eq(&K::kind()),
+        _ => true,
+    }
+}
+
 fn valid_object(obj: &DynamicObject) -> (ret: bool)
-    requires model::unmarshallable_object::<K::V>(obj@)
+    requires
+        registered_kind::<K::V>(obj@.kind),
+        model::unmarshallable_object::<K::V>(obj@),
     ensures ret == model::valid_object::<K::V>(obj@)
 {
     match obj.kind() {
-        Kind::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::PodKind => Pod::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::RoleKind => Role::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::SecretKind => Secret::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::ServiceKind => Service::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().state_validation(),
-        Kind::CustomResourceKind => {
+        KubeKind::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::PodKind => Pod::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::RoleKind => Role::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::SecretKind => Secret::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::ServiceKind => Service::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().state_validation(),
+        KubeKind::CustomResourceKind(_) => {
             proof {
                 K::V::unmarshal_result_determined_by_unmarshal_spec_and_status();
                 K::V::kind_is_custom_resource();
@@ -88,7 +108,9 @@ fn valid_object(obj: &DynamicObject) -> (ret: bool)
 }
 
 fn object_validity_check(obj: &DynamicObject) -> (ret: Option<APIError>)
-    requires model::unmarshallable_object::<K::V>(obj@)
+    requires
+        registered_kind::<K::V>(obj@.kind),
+        model::unmarshallable_object::<K::V>(obj@),
     ensures ret == model::object_validity_check::<K::V>(obj@)
 {
     if !Self::valid_object(obj) {
@@ -100,6 +122,7 @@ fn object_validity_check(obj: &DynamicObject) -> (ret: Option<APIError>)
 
 fn valid_transition(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
     requires
+        registered_kind::<K::V>(obj@.kind),
         model::unmarshallable_object::<K::V>(obj@),
         model::unmarshallable_object::<K::V>(old_obj@),
         old_obj@.kind == obj@.kind,
@@ -108,17 +131,17 @@ fn valid_transition(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
     ensures ret == model::valid_transition::<K::V>(obj@, old_obj@)
 {
     match obj.kind() {
-        Kind::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().transition_validation(&ConfigMap::unmarshal(old_obj.clone()).unwrap()),
-        Kind::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().transition_validation(&DaemonSet::unmarshal(old_obj.clone()).unwrap()),
-        Kind::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().transition_validation(&PersistentVolumeClaim::unmarshal(old_obj.clone()).unwrap()),
-        Kind::PodKind => Pod::unmarshal(obj.clone()).unwrap().transition_validation(&Pod::unmarshal(old_obj.clone()).unwrap()),
-        Kind::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().transition_validation(&RoleBinding::unmarshal(old_obj.clone()).unwrap()),
-        Kind::RoleKind => Role::unmarshal(obj.clone()).unwrap().transition_validation(&Role::unmarshal(old_obj.clone()).unwrap()),
-        Kind::SecretKind => Secret::unmarshal(obj.clone()).unwrap().transition_validation(&Secret::unmarshal(old_obj.clone()).unwrap()),
-        Kind::ServiceKind => Service::unmarshal(obj.clone()).unwrap().transition_validation(&Service::unmarshal(old_obj.clone()).unwrap()),
-        Kind::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().transition_validation(&StatefulSet::unmarshal(old_obj.clone()).unwrap()),
-        Kind::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().transition_validation(&ServiceAccount::unmarshal(old_obj.clone()).unwrap()),
-        Kind::CustomResourceKind => {
+        KubeKind::ConfigMapKind => ConfigMap::unmarshal(obj.clone()).unwrap().transition_validation(&ConfigMap::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::DaemonSetKind => DaemonSet::unmarshal(obj.clone()).unwrap().transition_validation(&DaemonSet::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::PersistentVolumeClaimKind => PersistentVolumeClaim::unmarshal(obj.clone()).unwrap().transition_validation(&PersistentVolumeClaim::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::PodKind => Pod::unmarshal(obj.clone()).unwrap().transition_validation(&Pod::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::RoleBindingKind => RoleBinding::unmarshal(obj.clone()).unwrap().transition_validation(&RoleBinding::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::RoleKind => Role::unmarshal(obj.clone()).unwrap().transition_validation(&Role::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::SecretKind => Secret::unmarshal(obj.clone()).unwrap().transition_validation(&Secret::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::ServiceKind => Service::unmarshal(obj.clone()).unwrap().transition_validation(&Service::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::StatefulSetKind => StatefulSet::unmarshal(obj.clone()).unwrap().transition_validation(&StatefulSet::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::ServiceAccountKind => ServiceAccount::unmarshal(obj.clone()).unwrap().transition_validation(&ServiceAccount::unmarshal(old_obj.clone()).unwrap()),
+        KubeKind::CustomResourceKind(_) => {
             proof {
                 K::V::unmarshal_result_determined_by_unmarshal_spec_and_status();
                 K::V::kind_is_custom_resource();
@@ -130,6 +153,7 @@ fn valid_transition(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: bool)
 
 fn object_transition_validity_check(obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
+        registered_kind::<K::V>(obj@.kind),
         model::unmarshallable_object::<K::V>(obj@),
         model::unmarshallable_object::<K::V>(old_obj@),
         old_obj@.kind == obj@.kind,
@@ -159,10 +183,78 @@ pub fn handle_get_request(req: &KubeGetRequest, s: &ApiServerState) -> (ret: Kub
     }
 }
 
+fn list_request_selects(req: &KubeListRequest, obj: &DynamicObject) -> (ret: bool)
+    requires
+        obj@.metadata.name.is_Some(),
+        obj@.metadata.namespace.is_Some(),
+    ensures ret == req@.selects(obj@)
+{
+    obj.metadata().namespace().unwrap().eq(&req.namespace)
+    && obj.kind().eq(&req.api_resource.kind())
+    && (req.label_selector.is_none()
+        || req.label_selector.as_ref().unwrap().matches(obj.metadata().labels().unwrap_or(StringMap::empty())))
+    && (req.field_selector.is_none()
+        || req.field_selector.as_ref().unwrap().name.is_none()
+        || obj.metadata().name().unwrap().eq(req.field_selector.as_ref().unwrap().name.as_ref().unwrap()))
+}
+
+// The objects are listed in the order of ObjectMap::values, i.e., sorted by their keys as in map_to_seq.
+pub fn handle_list_request(req: &KubeListRequest, s: &ApiServerState) -> (ret: KubeListResponse)
+    requires each_object_is_stored_under_its_key(s@.resources),
+    ensures ret@ == model::handle_list_request(req@, s@)
+{
+    if req.label_selector.is_some() && !req.label_selector.as_ref().unwrap().is_valid() {
+        return KubeListResponse{res: Err(APIError::BadRequest)};
+    }
+    let objs = s.resources.values();
+    let ghost selector = |o: DynamicObjectView| req@.selects(o);
+    let ghost objs_view = objs@.map_values(|o: DynamicObject| o@);
+    proof {
+        lemma_values_are_stored(s@.resources, objs_view);
+        assert forall |i| 0 <= i < objs.len() implies #[trigger] s@.resources.values().contains(objs[i]@) by {
+            assert(objs_view[i] == objs[i]@);
+        }
+    }
+    let mut selected: Vec<DynamicObject> = Vec::new();
+    let mut idx = 0;
+    while idx < objs.len()
+        invariant
+            idx <= objs.len(),
+            each_object_is_stored_under_its_key(s@.resources),
+            objs_view == objs@.map_values(|o: DynamicObject| o@),
+            forall |i| 0 <= i < objs.len() ==> #[trigger] s@.resources.values().contains(objs[i]@),
+            selected@.map_values(|o: DynamicObject| o@) == objs_view.take(idx as int).filter(selector),
+    {
+        let obj = &objs[idx];
+        proof {
+            // obj is stored under some key, so it has a name and a namespace
+            assert(s@.resources.values().contains(obj@));
+        }
+        let ghost old_selected = selected@.map_values(|o: DynamicObject| o@);
+        if Self::list_request_selects(req, obj) {
+            selected.push(obj.clone());
+            proof { assert(selected@.map_values(|o: DynamicObject| o@) == old_selected.push(obj@)); }
+        }
+        proof {
+            lemma_filter_maintained_after_add(objs_view.take(idx as int), selector, old_selected, obj@);
+            assert(objs_view.take(idx as int).push(obj@) == objs_view.take((idx + 1) as int));
+        }
+        idx = idx + 1;
+    }
+    proof {
+        assert(objs_view.take(objs.len() as int) == objs_view);
+    }
+    KubeListResponse{res: Ok(selected)}
+}
+
 fn create_request_admission_check(req: &KubeCreateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::create_request_admission_check::<K::V>(req@, s@),
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> ret == model::create_request_admission_check::<K::V>(req@, s@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> ret == Some(APIError::ObjectNotFound),
 {
-    if req.obj.metadata().name().is_none() && req.obj.metadata().generate_name().is_none() {
+    if !Self::is_registered_kind(&req.obj.kind()) {
+        Some(APIError::ObjectNotFound)
+    } else if req.obj.metadata().name().is_none() && req.obj.metadata().generate_name().is_none() {
         Some(APIError::Invalid)
     } else if req.obj.metadata().namespace().is_some() && !req.namespace.eq(&req.obj.metadata().namespace().unwrap()) {
         Some(APIError::BadRequest)
@@ -180,7 +272,9 @@ fn create_request_admission_check(req: &KubeCreateRequest, s: &ApiServerState) -
 }
 
 fn created_object_validity_check(created_obj: &DynamicObject) -> (ret: Option<APIError>)
-    requires model::unmarshallable_object::<K::V>(created_obj@)
+    requires
+        registered_kind::<K::V>(created_obj@.kind),
+        model::unmarshallable_object::<K::V>(created_obj@),
     ensures ret == model::created_object_validity_check::<K::V>(created_obj@)
 {
     if Self::metadata_validity_check(created_obj).is_some() {
@@ -192,13 +286,10 @@ fn created_object_validity_check(created_obj: &DynamicObject) -> (ret: Option<AP
     }
 }
 
-// No plan to run conformance test on create requests without names
-// so just panic here
-#[verifier(external_body)]
-fn generate_name(s: &ApiServerState) -> (ret: String)
-    ensures ret@ == model::generate_name(s@)
+fn generated_name(s: &ApiServerState, generate_name: &String) -> (ret: String)
+    ensures ret@ == model::generated_name(s@, generate_name@)
 {
-    panic!()
+    generate_name.clone().concat(i64_to_string(s.uid_counter).as_str())
 }
 
 pub fn handle_create_request(req: &KubeCreateRequest, s: &mut ApiServerState) -> (ret: KubeCreateResponse)
@@ -206,7 +297,9 @@ pub fn handle_create_request(req: &KubeCreateRequest, s: &mut ApiServerState) ->
         // No integer overflow
         old(s).resource_version_counter < i64::MAX,
         old(s).uid_counter < i64::MAX,
-    ensures (s@, ret@) == model::handle_create_request::<K::V>(req@, old(s)@)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> (s@, ret@) == model::handle_create_request::<K::V>(req@, old(s)@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> s@ == old(s)@ && ret@.res == Err(APIError::ObjectNotFound),
 {
     // TODO: use if-let?
     let request_check_error = Self::create_request_admission_check(req, s);
@@ -215,15 +308,19 @@ pub fn handle_create_request(req: &KubeCreateRequest, s: &mut ApiServerState) ->
     } else {
         let mut created_obj = req.obj.clone();
         if req.obj.metadata().name().is_none() {
-            created_obj.set_name(Self::generate_name(s));
+            created_obj.set_name(Self::generated_name(s, &req.obj.metadata().generate_name().unwrap()));
         }
         created_obj.set_namespace(req.namespace.clone());
         created_obj.set_resource_version(s.resource_version_counter);
         created_obj.set_uid(s.uid_counter);
         created_obj.unset_deletion_timestamp();
+        created_obj.unset_deletion_grace_period_seconds();
         created_obj.set_default_status::<K::V>();
         let object_check_error = Self::created_object_validity_check(&created_obj);
         if s.resources.contains_key(&created_obj.object_ref()) {
+            if req.obj.metadata().name().is_none() {
+                s.uid_counter = s.uid_counter + 1;
+            }
             KubeCreateResponse{res: Err(APIError::ObjectAlreadyExists)}
         } else if object_check_error.is_some() {
             KubeCreateResponse{res: Err(object_check_error.unwrap())}
@@ -237,6 +334,83 @@ pub fn handle_create_request(req: &KubeCreateRequest, s: &mut ApiServerState) ->
     }
 }
 
+fn delete_request_admission_check(req: &KubeDeleteRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    ensures ret == model::delete_request_admission_check(req@, s@)
+{
+    let req_key = KubeObjectRef {
+        kind: req.api_resource.kind(),
+        name: req.name.clone(),
+        namespace: req.namespace.clone(),
+    };
+    if !s.resources.contains_key(&req_key) {
+        Some(APIError::ObjectNotFound)
+    } else if req.preconditions.is_some()
+    && !req.preconditions.as_ref().unwrap().satisfied_by(&s.resources.get(&req_key).unwrap().metadata()) {
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+fn propagation_finalizer(propagation_policy: &Option<PropagationPolicy>) -> (ret: Option<String>)
+    ensures
+        ret.is_Some() == model::propagation_finalizer(*propagation_policy).is_Some(),
+        ret.is_Some() ==> ret.get_Some_0()@ == model::propagation_finalizer(*propagation_policy).get_Some_0(),
+{
+    match propagation_policy {
+        Some(PropagationPolicy::Orphan) => Some("orphan".to_string()),
+        Some(PropagationPolicy::Foreground) => Some("foregroundDeletion".to_string()),
+        _ => None,
+    }
+}
+
+fn default_pod_deletion_grace_period_seconds(obj: &DynamicObject) -> (ret: i64)
+    ensures ret as int == model::default_pod_deletion_grace_period_seconds(obj@)
+{
+    let pod = Pod::unmarshal(obj.clone());
+    if pod.is_ok() && pod.as_ref().unwrap().spec().is_some()
+    && pod.as_ref().unwrap().spec().unwrap().termination_grace_period_seconds().is_some() {
+        pod.unwrap().spec().unwrap().termination_grace_period_seconds().unwrap()
+    } else {
+        30
+    }
+}
+
+fn deletion_grace_period_seconds(req: &KubeDeleteRequest, obj: &DynamicObject) -> (ret: Option<i64>)
+    ensures
+        ret.is_Some() == model::deletion_grace_period_seconds(req@, obj@).is_Some(),
+        ret.is_Some() ==> ret.get_Some_0() as int == model::deletion_grace_period_seconds(req@, obj@).get_Some_0(),
+{
+    match obj.kind() {
+        KubeKind::PodKind => match req.grace_period_seconds {
+            Some(grace_period_seconds) => Some(grace_period_seconds as i64),
+            None => Some(Self::default_pod_deletion_grace_period_seconds(obj)),
+        },
+        _ => None,
+    }
+}
+
+fn graceful_deletion(req: &KubeDeleteRequest, obj: &DynamicObject) -> (ret: bool)
+    ensures ret == model::graceful_deletion(req@, obj@)
+{
+    let grace_period_seconds = Self::deletion_grace_period_seconds(req, obj);
+    grace_period_seconds.is_some()
+    && grace_period_seconds.unwrap() > 0
+    && (obj.metadata().deletion_grace_period_seconds().is_none()
+        || grace_period_seconds.unwrap() < obj.metadata().deletion_grace_period_seconds().unwrap())
+}
+
+fn pending_graceful_deletion(req: &KubeDeleteRequest, obj: &DynamicObject) -> (ret: bool)
+    ensures ret == model::pending_graceful_deletion(req@, obj@)
+{
+    let grace_period_seconds = Self::deletion_grace_period_seconds(req, obj);
+    obj.metadata().has_deletion_timestamp()
+    && obj.metadata().deletion_grace_period_seconds().is_some()
+    && obj.metadata().deletion_grace_period_seconds().unwrap() > 0
+    && grace_period_seconds.is_some()
+    && grace_period_seconds.unwrap() >= obj.metadata().deletion_grace_period_seconds().unwrap()
+}
+
 pub fn handle_delete_request(req: &KubeDeleteRequest, s: &mut ApiServerState) -> (ret: KubeDeleteResponse)
     requires old(s).resource_version_counter < i64::MAX // No integer overflow
     ensures (s@, ret@) == model::handle_delete_request(req@, old(s)@)
@@ -246,14 +420,47 @@ pub fn handle_delete_request(req: &KubeDeleteRequest, s: &mut ApiServerState) ->
         name: req.name.clone(),
         namespace: req.namespace.clone(),
     };
-    if !s.resources.contains_key(&req_key) {
-        KubeDeleteResponse{res: Err(APIError::ObjectNotFound)}
+    let admission_check_error = Self::delete_request_admission_check(req, s);
+    if admission_check_error.is_some() {
+        KubeDeleteResponse{res: Err(admission_check_error.unwrap())}
     } else {
         let mut obj = s.resources.get(&req_key).unwrap();
-        if obj.metadata().finalizers().is_some() && obj.metadata().finalizers().unwrap().len() > 0 {
+        let grace_period_seconds = Self::deletion_grace_period_seconds(req, &obj);
+        let propagation_finalizer = Self::propagation_finalizer(&req.propagation_policy);
+        if Self::graceful_deletion(req, &obj) {
+            if !obj.metadata().has_deletion_timestamp() && propagation_finalizer.is_some() {
+                obj.add_finalizer(propagation_finalizer.unwrap());
+            }
+            obj.set_deletion_grace_period_seconds(grace_period_seconds.unwrap());
+            obj.set_current_deletion_timestamp();
+            obj.set_resource_version(s.resource_version_counter);
+            let stamped_obj_with_new_rv = obj; // This renaming is just to stay consistent with the model
+            s.resources.insert(req_key, stamped_obj_with_new_rv);
+            s.resource_version_counter = s.resource_version_counter + 1;
+            KubeDeleteResponse{res: Ok(())}
+        } else if Self::pending_graceful_deletion(req, &obj) {
+            KubeDeleteResponse{res: Ok(())}
+        } else if !obj.metadata().has_deletion_timestamp() && propagation_finalizer.is_some() {
+            obj.add_finalizer(propagation_finalizer.unwrap());
+            obj.set_deletion_grace_period_seconds(0);
+            obj.set_current_deletion_timestamp();
+            obj.set_resource_version(s.resource_version_counter);
+            let stamped_obj_with_new_rv = obj; // This renaming is just to stay consistent with the model
+            s.resources.insert(req_key, stamped_obj_with_new_rv);
+            s.resource_version_counter = s.resource_version_counter + 1;
+            KubeDeleteResponse{res: Ok(())}
+        } else if obj.metadata().finalizers().is_some() && obj.metadata().finalizers().unwrap().len() > 0 {
             if obj.metadata().has_deletion_timestamp() {
+                if obj.metadata().deletion_grace_period_seconds().is_some() && obj.metadata().deletion_grace_period_seconds().unwrap() > 0
+                && grace_period_seconds.is_some() && grace_period_seconds.unwrap() == 0 {
+                    obj.set_deletion_grace_period_seconds(0);
+                    obj.set_resource_version(s.resource_version_counter);
+                    s.resources.insert(req_key, obj);
+                    s.resource_version_counter = s.resource_version_counter + 1;
+                }
                 KubeDeleteResponse{res: Ok(())}
             } else {
+                obj.set_deletion_grace_period_seconds(0);
                 obj.set_current_deletion_timestamp();
                 obj.set_resource_version(s.resource_version_counter);
                 let stamped_obj_with_new_rv = obj; // This renaming is just to stay consistent with the model
@@ -269,24 +476,28 @@ pub fn handle_delete_request(req: &KubeDeleteRequest, s: &mut ApiServerState) ->
     }
 }
 
-fn allow_unconditional_update(kind: &Kind) -> (ret: bool)
-    ensures ret == model::allow_unconditional_update(*kind)
+fn allow_unconditional_update(kind: &KubeKind) -> (ret: bool)
+    ensures ret == model::allow_unconditional_update(kind@)
 {
     match kind {
-        Kind::CustomResourceKind => false,
+        KubeKind::CustomResourceKind(_) => false,
         _ => true,
     }
 }
 
 fn update_request_admission_check_helper(name: &String, namespace: &String, obj: &DynamicObject, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_request_admission_check_helper::<K::V>(name@, namespace@, obj@, s@)
+    ensures
+        registered_kind::<K::V>(obj@.kind) ==> ret == model::update_request_admission_check_helper::<K::V>(name@, namespace@, obj@, s@),
+        !registered_kind::<K::V>(obj@.kind) ==> ret == Some(APIError::ObjectNotFound),
 {
     let key = KubeObjectRef {
         kind: obj.kind(),
         namespace: namespace.clone(),
         name: name.clone(),
     };
-    if obj.metadata().name().is_none() {
+    if !Self::is_registered_kind(&key.kind) {
+        Some(APIError::ObjectNotFound)
+    } else if obj.metadata().name().is_none() {
         Some(APIError::BadRequest)
     } else if !name.eq(&obj.metadata().name().unwrap()) {
         Some(APIError::BadRequest)
@@ -312,7 +523,9 @@ fn update_request_admission_check_helper(name: &String, namespace: &String, obj:
 }
 
 fn update_request_admission_check(req: &KubeUpdateRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_request_admission_check::<K::V>(req@, s@)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> ret == model::update_request_admission_check::<K::V>(req@, s@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> ret == Some(APIError::ObjectNotFound),
 {
     Self::update_request_admission_check_helper(&req.name, &req.namespace, &req.obj, s)
 }
@@ -325,12 +538,14 @@ fn updated_object(req: &KubeUpdateRequest, old_obj: &DynamicObject) -> (ret: Dyn
     updated_obj.set_resource_version_from(old_obj);
     updated_obj.set_uid_from(old_obj);
     updated_obj.set_deletion_timestamp_from(old_obj);
+    updated_obj.set_deletion_grace_period_seconds_from(old_obj);
     updated_obj.set_status_from(old_obj);
     updated_obj
 }
 
 fn updated_object_validity_check(updated_obj: &DynamicObject, old_obj: &DynamicObject) -> (ret: Option<APIError>)
     requires
+        registered_kind::<K::V>(updated_obj@.kind),
         model::unmarshallable_object::<K::V>(updated_obj@),
         model::unmarshallable_object::<K::V>(old_obj@),
         old_obj@.kind == updated_obj@.kind,
@@ -361,7 +576,9 @@ pub fn handle_update_request(req: &KubeUpdateRequest, s: &mut ApiServerState) ->
         // The old version has the right key (name, namespace, kind)
         old(s)@.resources.contains_key(req@.key()) ==> old(s)@.resources[req@.key()].object_ref() == req@.key(),
         // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
-    ensures (s@, ret@) == model::handle_update_request::<K::V>(req@, old(s)@)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> (s@, ret@) == model::handle_update_request::<K::V>(req@, old(s)@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> s@ == old(s)@ && ret@.res == Err(APIError::ObjectNotFound),
 {
     let request_check_error = Self::update_request_admission_check(req, s);
     if request_check_error.is_some() {
@@ -401,10 +618,241 @@ pub fn handle_update_request(req: &KubeUpdateRequest, s: &mut ApiServerState) ->
     }
 }
 
+fn strategic_merge_patch_not_supported(patch_type: &PatchType, kind: &KubeKind) -> (ret: bool)
+    ensures ret == (patch_type.is_StrategicMergePatch() && kind@.is_CustomResourceKind())
+{
+    match patch_type {
+        PatchType::StrategicMergePatch => match kind {
+            KubeKind::CustomResourceKind(_) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn patch_request_admission_check(req: &KubePatchRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    ensures
+        registered_kind::<K::V>(req@.key.kind) ==> ret == model::patch_request_admission_check::<K::V>(req@, s@),
+        !registered_kind::<K::V>(req@.key.kind) ==> ret == Some(APIError::ObjectNotFound),
+{
+    let key = KubeObjectRef {
+        kind: req.api_resource.kind(),
+        namespace: req.namespace.clone(),
+        name: req.name.clone(),
+    };
+    if !Self::is_registered_kind(&key.kind) {
+        Some(APIError::ObjectNotFound)
+    } else if req.patch.metadata().name().is_some()
+    && !req.name.eq(&req.patch.metadata().name().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if req.patch.metadata().namespace().is_some()
+    && !req.namespace.eq(&req.patch.metadata().namespace().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if Self::strategic_merge_patch_not_supported(&req.patch_type, &key.kind) {
+        Some(APIError::NotSupported)
+    } else if !s.resources.contains_key(&key) {
+        Some(APIError::ObjectNotFound)
+    } else if req.patch.metadata().has_some_resource_version()
+    && !req.patch.metadata().resource_version_eq(&s.resources.get(&key).unwrap().metadata()) {
+        Some(APIError::Conflict)
+    } else if req.patch.metadata().has_some_uid()
+    && !req.patch.metadata().uid_eq(&s.resources.get(&key).unwrap().metadata()) {
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+#[verifier(external_body)]
+fn patched_object(req: &KubePatchRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
+    ensures ret@ == model::patched_object(req@, old_obj@)
+{
+    DynamicObject::from_kube(patched_kube_object(&req.patch_type, &req.api_resource.as_kube_ref().kind, req.patch.as_kube_ref(), old_obj.as_kube_ref()))
+}
+
+pub fn handle_patch_request(req: &KubePatchRequest, s: &mut ApiServerState) -> (ret: KubePatchResponse)
+    requires
+        // No integer overflow
+        old(s).resource_version_counter < i64::MAX,
+        // The old version is marshallable
+        old(s)@.resources.contains_key(req@.key) ==> model::unmarshallable_object::<K::V>(old(s)@.resources[req@.key]),
+        // The old version passes state validation
+        old(s)@.resources.contains_key(req@.key) ==> model::valid_object::<K::V>(old(s)@.resources[req@.key]),
+        // The old version has the right key (name, namespace, kind)
+        old(s)@.resources.contains_key(req@.key) ==> old(s)@.resources[req@.key].object_ref() == req@.key,
+        // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
+    ensures
+        registered_kind::<K::V>(req@.key.kind) ==> (s@, ret@) == model::handle_patch_request::<K::V>(req@, old(s)@),
+        !registered_kind::<K::V>(req@.key.kind) ==> s@ == old(s)@ && ret@.res == Err(APIError::ObjectNotFound),
+{
+    let request_check_error = Self::patch_request_admission_check(req, s);
+    if request_check_error.is_some() {
+        KubePatchResponse{res: Err(request_check_error.unwrap())}
+    } else {
+        let req_key = KubeObjectRef {
+            kind: req.api_resource.kind(),
+            namespace: req.namespace.clone(),
+            name: req.name.clone(),
+        };
+        let old_obj = s.resources.get(&req_key).unwrap();
+        let patched_obj = Self::patched_object(req, &old_obj);
+        if !Self::unmarshallable_object(&patched_obj) {
+            KubePatchResponse{res: Err(APIError::BadRequest)}
+        } else {
+            // The patched object is handled in the same way as an update request
+            let update_req = KubeUpdateRequest {
+                api_resource: req.api_resource.clone(),
+                name: req.name.clone(),
+                namespace: req.namespace.clone(),
+                obj: patched_obj,
+            };
+            let update_resp = Self::handle_update_request(&update_req, s);
+            KubePatchResponse{res: update_resp.res}
+        }
+    }
+}
+
+#[verifier(external_body)]
+fn field_manager_is_empty(req: &KubeApplyRequest) -> (ret: bool)
+    ensures ret == (req@.field_manager.len() == 0)
+{
+    req.field_manager.is_empty()
+}
+
+fn apply_request_admission_check(req: &KubeApplyRequest, s: &ApiServerState) -> (ret: Option<APIError>)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> ret == model::apply_request_admission_check::<K::V>(req@, s@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> ret == Some(APIError::ObjectNotFound),
+{
+    let key = KubeObjectRef {
+        kind: req.obj.kind(),
+        namespace: req.namespace.clone(),
+        name: req.name.clone(),
+    };
+    if !Self::is_registered_kind(&key.kind) {
+        Some(APIError::ObjectNotFound)
+    } else if Self::field_manager_is_empty(req) {
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata().name().is_none() {
+        Some(APIError::BadRequest)
+    } else if !req.name.eq(&req.obj.metadata().name().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata().namespace().is_some()
+    && !req.namespace.eq(&req.obj.metadata().namespace().unwrap()) {
+        Some(APIError::BadRequest)
+    } else if !Self::unmarshallable_object(&req.obj) {
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata().has_some_resource_version()
+    && (!s.resources.contains_key(&key)
+        || !req.obj.metadata().resource_version_eq(&s.resources.get(&key).unwrap().metadata())) {
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+#[verifier(external_body)]
+fn apply_conflicts(req: &KubeApplyRequest, old_obj: &DynamicObject) -> (ret: bool)
+    ensures ret == model::apply_conflicts(req@, old_obj@)
+{
+    apply::apply_conflicts(&req.field_manager, req.force, req.obj.as_kube_ref(), old_obj.as_kube_ref())
+}
+
+#[verifier(external_body)]
+fn created_object_from_apply(req: &KubeApplyRequest) -> (ret: DynamicObject)
+    ensures ret@ == model::create_request_from_apply(req@).obj
+{
+    DynamicObject::from_kube(apply::created_kube_object_from_apply(&req.field_manager, req.obj.as_kube_ref()))
+}
+
+#[verifier(external_body)]
+fn applied_object(req: &KubeApplyRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
+    ensures ret@ == model::applied_object(req@, old_obj@)
+{
+    DynamicObject::from_kube(apply::applied_kube_object(&req.field_manager, req.obj.as_kube_ref(), old_obj.as_kube_ref()))
+}
+
+pub fn handle_apply_request(req: &KubeApplyRequest, s: &mut ApiServerState) -> (ret: KubeApplyResponse)
+    requires
+        // No integer overflow
+        old(s).resource_version_counter < i64::MAX,
+        old(s).uid_counter < i64::MAX,
+        // The old version is marshallable
+        old(s)@.resources.contains_key(req@.key()) ==> model::unmarshallable_object::<K::V>(old(s)@.resources[req@.key()]),
+        // The old version passes state validation
+        old(s)@.resources.contains_key(req@.key()) ==> model::valid_object::<K::V>(old(s)@.resources[req@.key()]),
+        // The old version has the right key (name, namespace, kind)
+        old(s)@.resources.contains_key(req@.key()) ==> old(s)@.resources[req@.key()].object_ref() == req@.key(),
+        // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> (s@, ret@) == model::handle_apply_request::<K::V>(req@, old(s)@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> s@ == old(s)@ && ret@.res == Err(APIError::ObjectNotFound),
+{
+    let request_check_error = Self::apply_request_admission_check(req, s);
+    if request_check_error.is_some() {
+        KubeApplyResponse{res: Err(request_check_error.unwrap())}
+    } else {
+        let req_key = KubeObjectRef {
+            kind: req.obj.kind(),
+            namespace: req.namespace.clone(),
+            name: req.name.clone(),
+        };
+        if !s.resources.contains_key(&req_key) {
+            // The object does not exist, so apply creates it
+            let create_req = KubeCreateRequest {
+                api_resource: req.api_resource.clone(),
+                namespace: req.namespace.clone(),
+                obj: Self::created_object_from_apply(req),
+            };
+            let create_resp = Self::handle_create_request(&create_req, s);
+            KubeApplyResponse{res: create_resp.res}
+        } else {
+            let old_obj = s.resources.get(&req_key).unwrap();
+            if Self::apply_conflicts(req, &old_obj) {
+                KubeApplyResponse{res: Err(APIError::Conflict)}
+            } else {
+                let applied_obj = Self::applied_object(req, &old_obj);
+                if !Self::unmarshallable_object(&applied_obj) {
+                    KubeApplyResponse{res: Err(APIError::BadRequest)}
+                } else {
+                    // The merged object is handled in the same way as an update request
+                    let update_req = KubeUpdateRequest {
+                        api_resource: req.api_resource.clone(),
+                        name: req.name.clone(),
+                        namespace: req.namespace.clone(),
+                        obj: applied_obj,
+                    };
+                    let update_resp = Self::handle_update_request(&update_req, s);
+                    KubeApplyResponse{res: update_resp.res}
+                }
+            }
+        }
+    }
+}
+
+fn has_status_subresource(kind: &KubeKind) -> (ret: bool)
+    ensures ret == model::has_status_subresource(kind@)
+{
+    match kind {
+        KubeKind::ConfigMapKind => false,
+        KubeKind::RoleKind => false,
+        KubeKind::RoleBindingKind => false,
+        KubeKind::SecretKind => false,
+        KubeKind::ServiceAccountKind => false,
+        _ => true,
+    }
+}
+
 fn update_status_request_admission_check(req: &KubeUpdateStatusRequest, s: &ApiServerState) -> (ret: Option<APIError>)
-    ensures ret == model::update_status_request_admission_check::<K::V>(req@, s@)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> ret == model::update_status_request_admission_check::<K::V>(req@, s@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> ret == Some(APIError::ObjectNotFound),
 {
-    Self::update_request_admission_check_helper(&req.name, &req.namespace, &req.obj, s)
+    if !Self::has_status_subresource(&req.obj.kind()) {
+        Some(APIError::ObjectNotFound)
+    } else {
+        Self::update_request_admission_check_helper(&req.name, &req.namespace, &req.obj, s)
+    }
 }
 
 fn status_updated_object(req: &KubeUpdateStatusRequest, old_obj: &DynamicObject) -> (ret: DynamicObject)
@@ -427,7 +875,9 @@ pub fn handle_update_status_request(req: &KubeUpdateStatusRequest, s: &mut ApiSe
         // The old version has the right key (name, namespace, kind)
         old(s)@.resources.contains_key(req@.key()) ==> old(s)@.resources[req@.key()].object_ref() == req@.key(),
         // All the three preconditions above are proved by the invariant lemma_always_each_object_in_etcd_is_well_formed
-    ensures (s@, ret@) == model::handle_update_status_request::<K::V>(req@, old(s)@)
+    ensures
+        registered_kind::<K::V>(req@.obj.kind) ==> (s@, ret@) == model::handle_update_status_request::<K::V>(req@, old(s)@),
+        !registered_kind::<K::V>(req@.obj.kind) ==> s@ == old(s)@ && ret@.res == Err(APIError::ObjectNotFound),
 {
     let request_check_error = Self::update_status_request_admission_check(req, s);
     if request_check_error.is_some() {
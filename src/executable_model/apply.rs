@@ -0,0 +1,448 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::{FieldsV1, ManagedFieldsEntry, OwnerReference};
+use deps_hack::kube::api::DynamicObject;
+use deps_hack::serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+// This file implements how the executable model merges an applied object into an existing object by field ownership.
+// It is the executable counterpart of model::applied_object and model::create_request_from_apply
+// (including the opaque model::value_fields, model::value_at and model::applied_value),
+// and is validated against the real Kubernetes API by the conformance tests.
+//
+// The owned fields of each manager are stored in the fieldsV1 of its managedFields entry in the same format as Kubernetes,
+// e.g., {"f:metadata": {"f:labels": {"f:app": {}}}, "f:data": {"f:key": {}}}.
+
+// FieldPath is the executable counterpart of FieldPathView.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FieldPath {
+    Label(String),
+    Annotation(String),
+    Finalizer(String),
+    // The uid of the owner reference
+    OwnerReference(String),
+    Spec(Vec<String>),
+}
+
+const APPLY_OPERATION: &str = "Apply";
+
+// value_fields returns the paths to the leaf values set in the object body, skipping status.
+// Nested objects are walked into, and everything else (including lists) is a leaf.
+pub fn value_fields(data: &Value) -> BTreeSet<Vec<String>> {
+    let mut fields = BTreeSet::new();
+    if let Value::Object(data_map) = data {
+        for (key, value) in data_map {
+            if key != "status" {
+                collect_value_fields(vec![key.clone()], value, &mut fields);
+            }
+        }
+    }
+    fields
+}
+
+fn collect_value_fields(path: Vec<String>, value: &Value, fields: &mut BTreeSet<Vec<String>>) {
+    match value {
+        Value::Null => {}
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let mut child_path = path.clone();
+                child_path.push(key.clone());
+                collect_value_fields(child_path, child, fields);
+            }
+        }
+        _ => {
+            fields.insert(path);
+        }
+    }
+}
+
+// value_at returns the value at the path, or null if the path is not set.
+pub fn value_at(data: &Value, path: &Vec<String>) -> Value {
+    let mut value = data;
+    for key in path {
+        match value.get(key) {
+            Some(child) => value = child,
+            None => return Value::Null,
+        }
+    }
+    value.clone()
+}
+
+// applied_value removes the fields in removed_fields from old_value (together with the objects that become empty),
+// and then sets every leaf value of value.
+pub fn applied_value(old_value: Value, value: &Value, removed_fields: &BTreeSet<Vec<String>>) -> Value {
+    let mut result = old_value;
+    for path in removed_fields {
+        remove_value_at(&mut result, path);
+    }
+    for path in value_fields(value) {
+        set_value_at(&mut result, &path, value_at(value, &path));
+    }
+    result
+}
+
+fn remove_value_at(value: &mut Value, path: &[String]) {
+    if let Value::Object(map) = value {
+        if path.len() == 1 {
+            map.remove(&path[0]);
+        } else if let Some(child) = map.get_mut(&path[0]) {
+            remove_value_at(child, &path[1..]);
+            if child.as_object().map_or(false, |child_map| child_map.is_empty()) {
+                map.remove(&path[0]);
+            }
+        }
+    }
+}
+
+fn set_value_at(value: &mut Value, path: &[String], leaf: Value) {
+    if !value.is_object() {
+        *value = Value::Object(Map::new());
+    }
+    let map = value.as_object_mut().unwrap();
+    if path.len() == 1 {
+        // An empty object in the applied object only claims the field, so it does not clear the existing object
+        let claims_existing_object = leaf.as_object().map_or(false, |leaf_map| leaf_map.is_empty())
+            && map.get(&path[0]).map_or(false, |old| old.is_object());
+        if !claims_existing_object {
+            map.insert(path[0].clone(), leaf);
+        }
+    } else {
+        let child = map.entry(path[0].clone()).or_insert(Value::Object(Map::new()));
+        set_value_at(child, &path[1..], leaf);
+    }
+}
+
+// applied_fields returns the fields that obj sets, which the applier owns after applying obj.
+pub fn applied_fields(obj: &DynamicObject) -> BTreeSet<FieldPath> {
+    let mut fields = BTreeSet::new();
+    if let Some(labels) = &obj.metadata.labels {
+        fields.extend(labels.keys().map(|key| FieldPath::Label(key.clone())));
+    }
+    if let Some(annotations) = &obj.metadata.annotations {
+        fields.extend(annotations.keys().map(|key| FieldPath::Annotation(key.clone())));
+    }
+    if let Some(finalizers) = &obj.metadata.finalizers {
+        fields.extend(finalizers.iter().map(|finalizer| FieldPath::Finalizer(finalizer.clone())));
+    }
+    if let Some(owner_references) = &obj.metadata.owner_references {
+        fields.extend(owner_references.iter().map(|owner_reference| FieldPath::OwnerReference(owner_reference.uid.clone())));
+    }
+    fields.extend(value_fields(&obj.data).into_iter().map(FieldPath::Spec));
+    fields
+}
+
+// field_changed checks whether obj sets the field to a different value than old_obj.
+pub fn field_changed(obj: &DynamicObject, old_obj: &DynamicObject, field: &FieldPath) -> bool {
+    match field {
+        FieldPath::Label(key) => {
+            obj.metadata.labels.as_ref().and_then(|labels| labels.get(key))
+                != old_obj.metadata.labels.as_ref().and_then(|labels| labels.get(key))
+        }
+        FieldPath::Annotation(key) => {
+            obj.metadata.annotations.as_ref().and_then(|annotations| annotations.get(key))
+                != old_obj.metadata.annotations.as_ref().and_then(|annotations| annotations.get(key))
+        }
+        // A finalizer is the value of its own item, so applying it never changes it
+        FieldPath::Finalizer(_) => false,
+        FieldPath::OwnerReference(uid) => {
+            owner_references_with_uid(&obj.metadata.owner_references, uid)
+                != owner_references_with_uid(&old_obj.metadata.owner_references, uid)
+        }
+        FieldPath::Spec(path) => value_at(&obj.data, path) != value_at(&old_obj.data, path),
+    }
+}
+
+fn owner_references_with_uid(owner_references: &Option<Vec<OwnerReference>>, uid: &String) -> Vec<OwnerReference> {
+    owner_references
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|owner_reference| &owner_reference.uid == uid)
+        .collect()
+}
+
+// fields_from_fields_v1 parses the fieldsV1 of a managedFields entry.
+// The metadata fields other than labels, annotations, finalizers and owner references are not tracked.
+// Each finalizer is a set item ("v:" followed by the finalizer in json) and each owner reference is a map item
+// ("k:" followed by its uid in json), and a field with only "." or list item children is a leaf.
+pub fn fields_from_fields_v1(fields_v1: &Value) -> BTreeSet<FieldPath> {
+    let mut fields = BTreeSet::new();
+    for (name, child) in field_children(fields_v1) {
+        if name == "metadata" {
+            for (meta_name, meta_child) in field_children(child) {
+                match meta_name.as_str() {
+                    "labels" => fields.extend(field_children(meta_child).into_iter().map(|(key, _)| FieldPath::Label(key))),
+                    "annotations" => fields.extend(field_children(meta_child).into_iter().map(|(key, _)| FieldPath::Annotation(key))),
+                    "finalizers" => fields.extend(
+                        list_item_keys(meta_child, "v:")
+                            .into_iter()
+                            .filter_map(|item| item.as_str().map(|finalizer| FieldPath::Finalizer(finalizer.to_string()))),
+                    ),
+                    "ownerReferences" => fields.extend(
+                        list_item_keys(meta_child, "k:")
+                            .into_iter()
+                            .filter_map(|item| item["uid"].as_str().map(|uid| FieldPath::OwnerReference(uid.to_string()))),
+                    ),
+                    _ => {}
+                }
+            }
+        } else if name != "status" {
+            collect_fields_v1(vec![name], child, &mut fields);
+        }
+    }
+    fields
+}
+
+// list_item_keys parses the keys of the list items with the prefix ("v:" or "k:"), which are json values.
+fn list_item_keys(fields_v1: &Value, prefix: &str) -> Vec<Value> {
+    match fields_v1 {
+        Value::Object(map) => map
+            .keys()
+            .filter_map(|key| key.strip_prefix(prefix))
+            .filter_map(|item| deps_hack::serde_json::from_str(item).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn field_children(fields_v1: &Value) -> Vec<(String, &Value)> {
+    match fields_v1 {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(key, child)| key.strip_prefix("f:").map(|name| (name.to_string(), child)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn collect_fields_v1(path: Vec<String>, fields_v1: &Value, fields: &mut BTreeSet<FieldPath>) {
+    let children = field_children(fields_v1);
+    if children.is_empty() {
+        fields.insert(FieldPath::Spec(path));
+    } else {
+        for (name, child) in children {
+            let mut child_path = path.clone();
+            child_path.push(name);
+            collect_fields_v1(child_path, child, fields);
+        }
+    }
+}
+
+// fields_v1_from_fields is the inverse of fields_from_fields_v1.
+pub fn fields_v1_from_fields(fields: &BTreeSet<FieldPath>) -> Value {
+    let mut fields_v1 = Value::Object(Map::new());
+    for field in fields {
+        let path: Vec<String> = match field {
+            FieldPath::Label(key) => vec!["metadata".to_string(), "labels".to_string(), key.clone()],
+            FieldPath::Annotation(key) => vec!["metadata".to_string(), "annotations".to_string(), key.clone()],
+            FieldPath::Finalizer(finalizer) => {
+                insert_list_item(&mut fields_v1, "finalizers", format!("v:{}", Value::String(finalizer.clone())));
+                continue;
+            }
+            FieldPath::OwnerReference(uid) => {
+                insert_list_item(&mut fields_v1, "ownerReferences", format!("k:{}", deps_hack::serde_json::json!({"uid": uid})));
+                continue;
+            }
+            FieldPath::Spec(path) => path.clone(),
+        };
+        let mut node = &mut fields_v1;
+        for name in path {
+            node = node
+                .as_object_mut()
+                .unwrap()
+                .entry(format!("f:{}", name))
+                .or_insert(Value::Object(Map::new()));
+        }
+    }
+    fields_v1
+}
+
+// insert_list_item marks the list in metadata and the item in it as owned, in the same format as Kubernetes,
+// e.g., {"f:metadata": {"f:finalizers": {".": {}, "v:\"foo\"": {}}}}.
+fn insert_list_item(fields_v1: &mut Value, list: &str, item: String) {
+    let list_node = fields_v1
+        .as_object_mut()
+        .unwrap()
+        .entry("f:metadata".to_string())
+        .or_insert(Value::Object(Map::new()))
+        .as_object_mut()
+        .unwrap()
+        .entry(format!("f:{}", list))
+        .or_insert(Value::Object(Map::new()))
+        .as_object_mut()
+        .unwrap();
+    list_node.insert(".".to_string(), Value::Object(Map::new()));
+    list_node.insert(item, Value::Object(Map::new()));
+}
+
+pub fn entry_fields(entry: &ManagedFieldsEntry) -> BTreeSet<FieldPath> {
+    match &entry.fields_v1 {
+        Some(FieldsV1(fields_v1)) => fields_from_fields_v1(fields_v1),
+        None => BTreeSet::new(),
+    }
+}
+
+fn fields_of(obj: &DynamicObject, is_applier: bool, field_manager: &str) -> BTreeSet<FieldPath> {
+    obj.metadata
+        .managed_fields
+        .clone()
+        .unwrap_or_default()
+        .iter()
+        .filter(|entry| (entry.manager.as_deref() == Some(field_manager)) == is_applier)
+        .flat_map(entry_fields)
+        .collect()
+}
+
+fn applier_entry(field_manager: &str, obj: &DynamicObject) -> ManagedFieldsEntry {
+    ManagedFieldsEntry {
+        api_version: obj.types.as_ref().map(|types| types.api_version.clone()),
+        fields_type: Some("FieldsV1".to_string()),
+        fields_v1: Some(FieldsV1(fields_v1_from_fields(&applied_fields(obj)))),
+        manager: Some(field_manager.to_string()),
+        operation: Some(APPLY_OPERATION.to_string()),
+        subresource: None,
+        time: None,
+    }
+}
+
+// conflicting_fields returns the fields that obj changes but are owned by the managers other than field_manager.
+pub fn conflicting_fields(field_manager: &str, obj: &DynamicObject, old_obj: &DynamicObject) -> BTreeSet<FieldPath> {
+    let fields_of_other_managers = fields_of(old_obj, false, field_manager);
+    applied_fields(obj)
+        .into_iter()
+        .filter(|field| fields_of_other_managers.contains(field) && field_changed(obj, old_obj, field))
+        .collect()
+}
+
+pub fn apply_conflicts(field_manager: &str, force: bool, obj: &DynamicObject, old_obj: &DynamicObject) -> bool {
+    !force && !conflicting_fields(field_manager, obj, old_obj).is_empty()
+}
+
+pub fn created_kube_object_from_apply(field_manager: &str, obj: &DynamicObject) -> DynamicObject {
+    let mut created_obj = obj.clone();
+    created_obj.metadata.managed_fields = Some(vec![applier_entry(field_manager, obj)]);
+    created_obj
+}
+
+pub fn applied_kube_object(field_manager: &str, obj: &DynamicObject, old_obj: &DynamicObject) -> DynamicObject {
+    let applied = applied_fields(obj);
+    let fields_of_other_managers = fields_of(old_obj, false, field_manager);
+    let conflicting = conflicting_fields(field_manager, obj, old_obj);
+    let removed: BTreeSet<FieldPath> = fields_of(old_obj, true, field_manager)
+        .into_iter()
+        .filter(|field| !applied.contains(field) && !fields_of_other_managers.contains(field))
+        .collect();
+
+    let mut result = old_obj.clone();
+    result.types = obj.types.clone();
+    result.metadata.labels = applied_string_map(
+        &old_obj.metadata.labels,
+        &obj.metadata.labels,
+        |key| removed.contains(&FieldPath::Label(key.clone())),
+    );
+    result.metadata.annotations = applied_string_map(
+        &old_obj.metadata.annotations,
+        &obj.metadata.annotations,
+        |key| removed.contains(&FieldPath::Annotation(key.clone())),
+    );
+    result.metadata.finalizers = applied_list(
+        &old_obj.metadata.finalizers,
+        &obj.metadata.finalizers,
+        |finalizer| finalizer.clone(),
+        |finalizer| removed.contains(&FieldPath::Finalizer(finalizer.clone())),
+    );
+    result.metadata.owner_references = applied_list(
+        &old_obj.metadata.owner_references,
+        &obj.metadata.owner_references,
+        |owner_reference| owner_reference.uid.clone(),
+        |uid| removed.contains(&FieldPath::OwnerReference(uid.clone())),
+    );
+
+    let mut managed_fields: Vec<ManagedFieldsEntry> = Vec::new();
+    for entry in old_obj.metadata.managed_fields.clone().unwrap_or_default() {
+        if entry.manager.as_deref() != Some(field_manager) {
+            let kept: BTreeSet<FieldPath> = entry_fields(&entry).difference(&conflicting).cloned().collect();
+            if !kept.is_empty() {
+                managed_fields.push(ManagedFieldsEntry {
+                    fields_v1: Some(FieldsV1(fields_v1_from_fields(&kept))),
+                    ..entry
+                });
+            }
+        }
+    }
+    managed_fields.push(applier_entry(field_manager, obj));
+    result.metadata.managed_fields = Some(managed_fields);
+
+    let removed_spec_fields: BTreeSet<Vec<String>> = removed
+        .iter()
+        .filter_map(|field| match field {
+            FieldPath::Spec(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    result.data = applied_value(old_obj.data.clone(), &obj.data, &removed_spec_fields);
+    result
+}
+
+fn applied_string_map(
+    old_map: &Option<BTreeMap<String, String>>,
+    map: &Option<BTreeMap<String, String>>,
+    removed: impl Fn(&String) -> bool,
+) -> Option<BTreeMap<String, String>> {
+    if old_map.is_none() && map.is_none() {
+        None
+    } else {
+        let mut result: BTreeMap<String, String> = old_map
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| !removed(key))
+            .collect();
+        result.extend(map.clone().unwrap_or_default());
+        Some(result)
+    }
+}
+
+// merged_list merges the applied list into the old list item by item (see model::merged_list):
+// the old items that are not applied stay where they are, and the applied items follow the applied order
+// and replace the old items with the same key.
+pub fn merged_list<T: Clone>(old_list: &[T], list: &[T], key: &impl Fn(&T) -> String) -> Vec<T> {
+    let mut old_list = old_list.to_vec();
+    let mut list = list;
+    let mut result = Vec::new();
+    while !list.is_empty() && !old_list.is_empty() {
+        let old_key = key(&old_list[0]);
+        if !list.iter().any(|item| key(item) == old_key) {
+            result.push(old_list.remove(0));
+        } else {
+            let applied_key = key(&list[0]);
+            result.push(list[0].clone());
+            old_list.retain(|item| key(item) != applied_key);
+            list = &list[1..];
+        }
+    }
+    result.extend(old_list);
+    result.extend(list.iter().cloned());
+    result
+}
+
+// An empty list is omitted from the metadata, same as the Kubernetes API.
+fn applied_list<T: Clone>(
+    old_list: &Option<Vec<T>>,
+    list: &Option<Vec<T>>,
+    key: impl Fn(&T) -> String,
+    removed: impl Fn(&String) -> bool,
+) -> Option<Vec<T>> {
+    let kept: Vec<T> = old_list
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|item| !removed(&key(item)))
+        .collect();
+    let merged = merged_list(&kept, &list.clone().unwrap_or_default(), &key);
+    if merged.is_empty() {
+        None
+    } else {
+        Some(merged)
+    }
+}
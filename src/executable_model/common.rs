@@ -15,7 +15,7 @@ use vstd::string::*;
 // because it internally uses vstd::string::String, which does not implement such traits.
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct ExternalObjectRef {
-    pub kind: Kind,
+    pub kind: KubeKind,
     pub name: std::string::String,
     pub namespace: std::string::String,
 }
@@ -30,10 +30,114 @@ impl KubeObjectRef {
     }
 }
 
+// set_status_field overwrites the "status" field of data, or removes it if status is None.
+fn set_status_field(
+    data: &mut deps_hack::serde_json::Value,
+    status: Option<deps_hack::serde_json::Value>,
+) {
+    if !data.is_object() {
+        *data = deps_hack::serde_json::Value::Object(deps_hack::serde_json::Map::new());
+    }
+    let fields = data.as_object_mut().unwrap();
+    match status {
+        Some(status) => {
+            fields.insert("status".to_string(), status);
+        }
+        None => {
+            fields.remove("status");
+        }
+    }
+}
+
 verus! {
 
+// KubeKind is the exec version of Kind.
+// It is used by the exec model (instead of Kind) because Kind::CustomResourceKind carries a StringView.
+#[is_variant]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum KubeKind {
+    ConfigMapKind,
+    CustomResourceKind(String),
+    DaemonSetKind,
+    PersistentVolumeClaimKind,
+    PodKind,
+    RoleKind,
+    RoleBindingKind,
+    StatefulSetKind,
+    ServiceKind,
+    ServiceAccountKind,
+    SecretKind,
+}
+
+impl View for KubeKind {
+    type V = Kind;
+    open spec fn view(&self) -> Kind {
+        match self {
+            KubeKind::ConfigMapKind => Kind::ConfigMapKind,
+            KubeKind::CustomResourceKind(kind) => Kind::CustomResourceKind(kind@),
+            KubeKind::DaemonSetKind => Kind::DaemonSetKind,
+            KubeKind::PersistentVolumeClaimKind => Kind::PersistentVolumeClaimKind,
+            KubeKind::PodKind => Kind::PodKind,
+            KubeKind::RoleKind => Kind::RoleKind,
+            KubeKind::RoleBindingKind => Kind::RoleBindingKind,
+            KubeKind::StatefulSetKind => Kind::StatefulSetKind,
+            KubeKind::ServiceKind => Kind::ServiceKind,
+            KubeKind::ServiceAccountKind => Kind::ServiceAccountKind,
+            KubeKind::SecretKind => Kind::SecretKind,
+        }
+    }
+}
+
+impl std::clone::Clone for KubeKind {
+    #[verifier(external_body)]
+    fn clone(&self) -> (result: Self)
+        ensures result == self
+    {
+        match self {
+            KubeKind::CustomResourceKind(kind) => KubeKind::CustomResourceKind(kind.clone()),
+            KubeKind::ConfigMapKind => KubeKind::ConfigMapKind,
+            KubeKind::DaemonSetKind => KubeKind::DaemonSetKind,
+            KubeKind::PersistentVolumeClaimKind => KubeKind::PersistentVolumeClaimKind,
+            KubeKind::PodKind => KubeKind::PodKind,
+            KubeKind::RoleKind => KubeKind::RoleKind,
+            KubeKind::RoleBindingKind => KubeKind::RoleBindingKind,
+            KubeKind::StatefulSetKind => KubeKind::StatefulSetKind,
+            KubeKind::ServiceKind => KubeKind::ServiceKind,
+            KubeKind::ServiceAccountKind => KubeKind::ServiceAccountKind,
+            KubeKind::SecretKind => KubeKind::SecretKind,
+        }
+    }
+}
+
+impl KubeKind {
+    #[verifier(external_body)]
+    pub fn eq(&self, other: &KubeKind) -> (b: bool)
+        ensures b == (self@ == other@)
+    {
+        self == other
+    }
+
+    // Builtin kinds are recognized by their names, and all the other kinds are treated as custom resources.
+    #[verifier(external_body)]
+    pub fn from_kind_name(kind: &str) -> KubeKind {
+        match kind {
+            "ConfigMap" => KubeKind::ConfigMapKind,
+            "DaemonSet" => KubeKind::DaemonSetKind,
+            "PersistentVolumeClaim" => KubeKind::PersistentVolumeClaimKind,
+            "Pod" => KubeKind::PodKind,
+            "Role" => KubeKind::RoleKind,
+            "RoleBinding" => KubeKind::RoleBindingKind,
+            "StatefulSet" => KubeKind::StatefulSetKind,
+            "Service" => KubeKind::ServiceKind,
+            "ServiceAccount" => KubeKind::ServiceAccountKind,
+            "Secret" => KubeKind::SecretKind,
+            _ => KubeKind::CustomResourceKind(kind.to_string()),
+        }
+    }
+}
+
 pub struct KubeObjectRef {
-    pub kind: Kind,
+    pub kind: KubeKind,
     pub name: String,
     pub namespace: String,
 }
@@ -42,7 +146,7 @@ impl View for KubeObjectRef {
     type V = ObjectRef;
     open spec fn view(&self) -> ObjectRef {
         ObjectRef {
-            kind: self.kind,
+            kind: self.kind@,
             name: self.name@,
             namespace: self.namespace@,
         }
@@ -62,24 +166,11 @@ impl std::clone::Clone for KubeObjectRef {
 }
 
 impl ApiResource {
-    // This kind() is not a perfect implementation but it is sufficient for conformance tests.
     #[verifier(external_body)]
-    pub fn kind(&self) -> (kind: Kind)
-        ensures kind == self@.kind,
-    {
-        match self.as_kube_ref().kind.as_str() {
-            "ConfigMap" => Kind::ConfigMapKind,
-            "DaemonSet" => Kind::DaemonSetKind,
-            "PersistentVolumeClaim" => Kind::PersistentVolumeClaimKind,
-            "Pod" => Kind::PodKind,
-            "Role" => Kind::RoleKind,
-            "RoleBinding" => Kind::RoleBindingKind,
-            "StatefulSet" => Kind::StatefulSetKind,
-            "Service" => Kind::ServiceKind,
-            "ServiceAccount" => Kind::ServiceAccountKind,
-            "Secret" => Kind::SecretKind,
-            _ => panic!(), // We assume the DynamicObject won't be a custom object
-        }
+    pub fn kind(&self) -> (kind: KubeKind)
+        ensures kind@ == self@.kind,
+    {
+        KubeKind::from_kind_name(self.as_kube_ref().kind.as_str())
     }
 }
 
@@ -116,6 +207,16 @@ impl DynamicObjectView {
         }
     }
 
+    pub open spec fn overwrite_deletion_grace_period_seconds(self, deletion_grace_period_seconds: Option<int>) -> DynamicObjectView {
+        DynamicObjectView {
+            metadata: ObjectMetaView {
+                deletion_grace_period_seconds: deletion_grace_period_seconds,
+                ..self.metadata
+            },
+            ..self
+        }
+    }
+
     pub open spec fn overwrite_uid(self, uid: Option<int>) -> DynamicObjectView {
         DynamicObjectView {
             metadata: ObjectMetaView {
@@ -152,27 +253,14 @@ impl DynamicObjectView {
 }
 
 impl DynamicObject {
-    // This kind() is not a perfect implementation but it is sufficient for conformance tests.
     #[verifier(external_body)]
-    pub fn kind(&self) -> (kind: Kind)
-        ensures kind == self@.kind,
+    pub fn kind(&self) -> (kind: KubeKind)
+        ensures kind@ == self@.kind,
     {
         if self.as_kube_ref().types.is_none() {
             panic!();
         }
-        match self.as_kube_ref().types.as_ref().unwrap().kind.as_str() {
-            "ConfigMap" => Kind::ConfigMapKind,
-            "DaemonSet" => Kind::DaemonSetKind,
-            "PersistentVolumeClaim" => Kind::PersistentVolumeClaimKind,
-            "Pod" => Kind::PodKind,
-            "Role" => Kind::RoleKind,
-            "RoleBinding" => Kind::RoleBindingKind,
-            "StatefulSet" => Kind::StatefulSetKind,
-            "Service" => Kind::ServiceKind,
-            "ServiceAccount" => Kind::ServiceAccountKind,
-            "Secret" => Kind::SecretKind,
-            _ => panic!(), // We assume the DynamicObject won't be a custom object
-        }
+        KubeKind::from_kind_name(self.as_kube_ref().types.as_ref().unwrap().kind.as_str())
     }
 
     // We implement getter and setter functions of the DynamicObject
@@ -248,6 +336,26 @@ impl DynamicObject {
         self.as_kube_mut_ref().metadata.deletion_timestamp = other.as_kube_ref().metadata.deletion_timestamp.clone();
     }
 
+    #[verifier(external_body)]
+    pub fn set_deletion_grace_period_seconds(&mut self, deletion_grace_period_seconds: i64)
+        ensures self@ == old(self)@.overwrite_deletion_grace_period_seconds(Some(deletion_grace_period_seconds as int)),
+    {
+        self.as_kube_mut_ref().metadata.deletion_grace_period_seconds = Some(deletion_grace_period_seconds);
+    }
+
+    #[verifier(external_body)]
+    pub fn unset_deletion_grace_period_seconds(&mut self)
+        ensures self@ == old(self)@.overwrite_deletion_grace_period_seconds(None),
+    {
+        self.as_kube_mut_ref().metadata.deletion_grace_period_seconds = None;
+    }
+
+    #[verifier(external_body)]
+    pub fn set_deletion_grace_period_seconds_from(&mut self, other: &DynamicObject)
+        ensures self@ == old(self)@.overwrite_deletion_grace_period_seconds(other@.metadata.deletion_grace_period_seconds),
+    {
+        self.as_kube_mut_ref().metadata.deletion_grace_period_seconds = other.as_kube_ref().metadata.deletion_grace_period_seconds;
+    }
 
     // This function sets the deletion timestamp to the current time.
     // This seems a bit inconsistent with the model's behavior which
@@ -260,6 +368,16 @@ impl DynamicObject {
         self.as_kube_mut_ref().metadata.deletion_timestamp = Some(deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::Time(deps_hack::chrono::Utc::now()));
     }
 
+    #[verifier(external_body)]
+    pub fn add_finalizer(&mut self, finalizer: String)
+        ensures self@ == old(self)@.set_metadata(old(self)@.metadata.set_finalizers(model::finalizers_with(old(self)@.metadata.finalizers, finalizer@))),
+    {
+        let finalizers = self.as_kube_mut_ref().metadata.finalizers.get_or_insert_with(Vec::new);
+        if !finalizers.contains(&finalizer) {
+            finalizers.push(finalizer);
+        }
+    }
+
     #[verifier(external_body)]
     pub fn eq(&self, other: &DynamicObject) -> (ret: bool)
         ensures ret == (self@ == other@)
@@ -274,23 +392,38 @@ impl DynamicObject {
         self.as_kube_mut_ref().metadata = other.as_kube_ref().metadata.clone()
     }
 
-    // We intentionally leave set_spec_from overly sets the data and
-    // set_status_from does not set any data because they are rather
-    // difficult to implement: we'll have to unmarshal other.inner and extract
-    // the spec/status part from the json representation.
-    // Since these two are left empty, the conformance test should not check
-    // the content of the spec and status.
+    // The data of a kube DynamicObject holds all the fields other than apiVersion, kind and metadata.
+    // set_spec_from and set_status_from split the data by the "status" field:
+    // the status is the "status" field and the spec is everything else in the data
+    // (e.g., "spec" of a Service, or "data" and "type" of a Secret).
     #[verifier(external_body)]
     pub fn set_spec_from(&mut self, other: &DynamicObject)
         ensures self@ == old(self)@.set_spec(other@.spec)
     {
-        self.as_kube_mut_ref().data = other.as_kube_ref().data.clone()
+        let status = self.as_kube_ref().data.get("status").cloned();
+        let mut data = other.as_kube_ref().data.clone();
+        set_status_field(&mut data, status);
+        self.as_kube_mut_ref().data = data;
+    }
+
+    #[verifier(external_body)]
+    pub fn spec_eq(&self, other: &DynamicObject) -> (ret: bool)
+        ensures ret == (self@.spec == other@.spec)
+    {
+        let mut data = self.as_kube_ref().data.clone();
+        let mut other_data = other.as_kube_ref().data.clone();
+        set_status_field(&mut data, None);
+        set_status_field(&mut other_data, None);
+        data == other_data
     }
 
     #[verifier(external_body)]
     pub fn set_status_from(&mut self, other: &DynamicObject)
         ensures self@ == old(self)@.set_status(other@.status)
-    {}
+    {
+        let status = other.as_kube_ref().data.get("status").cloned();
+        set_status_field(&mut self.as_kube_mut_ref().data, status);
+    }
 
     #[verifier(external_body)]
     pub fn set_default_status<K: CustomResourceView>(&mut self)
@@ -510,6 +643,9 @@ where Self::V: CustomResourceView, Self: std::marker::Sized
 
     fn transition_validation(&self, old_obj: &Self) -> (ret: bool)
         ensures ret == self@.transition_validation(old_obj@);
+
+    fn kind() -> (kind: KubeKind)
+        ensures kind@ == Self::V::kind();
 }
 
 // SimpleCRView and SimpleCR are types only used for instantiating the executable API server model,
@@ -539,7 +675,7 @@ impl ResourceView for SimpleCRView {
 
     open spec fn metadata(self) -> ObjectMetaView { self.metadata }
 
-    open spec fn kind() -> Kind { Kind::CustomResourceKind }
+    open spec fn kind() -> Kind { Kind::CustomResourceKind("SimpleCR"@) }
 
     open spec fn object_ref(self) -> ObjectRef {
         ObjectRef {
@@ -624,6 +760,12 @@ impl View for SimpleCR {
 }
 
 impl CustomResource for SimpleCR {
+    fn kind() -> (kind: KubeKind)
+        ensures kind@ == SimpleCRView::kind()
+    {
+        KubeKind::CustomResourceKind("SimpleCR".to_string())
+    }
+
     #[verifier(external_body)]
     fn unmarshal(obj: DynamicObject) -> (res: Result<SimpleCR, ParseDynamicObjectError>)
         ensures
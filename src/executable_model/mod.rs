@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: MIT
 pub mod api_server;
 pub mod api_server_state;
+pub mod apply;
 pub mod common;
 pub mod object_map;
 pub mod object_ref_set;
+pub mod patch;
 pub mod prelude;
+pub mod state_matches;
 pub mod string_set;
@@ -1,14 +1,42 @@
 use crate::executable_model::common::*;
 use crate::kubernetes_api_objects::exec::dynamic::DynamicObject;
 use crate::kubernetes_api_objects::spec::{
-    common::{Kind, ObjectRef},
+    common::{lemma_object_ref_lt_is_strict_total_order, object_ref_order, Kind, ObjectRef},
     dynamic::{DynamicObjectView, StoredState},
 };
+use crate::vstd_ext::map_lib::*;
 use vstd::prelude::*;
 use vstd::string::*;
 
 verus! {
 
+// Each object in m is stored under the key made of its own kind, name and namespace,
+// which is how the API server stores objects.
+pub open spec fn each_object_is_stored_under_its_key(m: StoredState) -> bool {
+    forall |key: ObjectRef| #[trigger] m.contains_key(key)
+        ==> {
+            &&& m[key].metadata.name.is_Some()
+            &&& m[key].metadata.namespace.is_Some()
+            &&& m[key].object_ref() == key
+        }
+}
+
+// Each object listed by ObjectMap::values is stored in the map.
+pub proof fn lemma_values_are_stored(m: StoredState, values: Seq<DynamicObjectView>)
+    requires
+        m.dom().finite(),
+        values == sorted_keys(m, object_ref_order()).map_values(|k: ObjectRef| m[k]),
+    ensures forall |i| 0 <= i < values.len() ==> #[trigger] m.values().contains(values[i]),
+{
+    lemma_object_ref_lt_is_strict_total_order();
+    lemma_sorted_keys_exists(m, object_ref_order());
+    let keys = sorted_keys(m, object_ref_order());
+    assert forall |i| 0 <= i < values.len() implies #[trigger] m.values().contains(values[i]) by {
+        assert(keys.to_set().contains(keys[i]));
+        assert(m.contains_key(keys[i]) && m[keys[i]] == values[i]);
+    }
+}
+
 // This is the exec version of the map used in crate::kubernetes_cluster::spec::api_server::types::ApiServerState
 // for storing the cluster state (i.e., the k8s objects).
 #[verifier(external_body)]
@@ -89,6 +117,17 @@ impl ObjectMap {
         self.inner.contains_key(&key.clone().into_external_object_ref())
     }
 
+    // values returns the objects in the ascending order of their keys, which is how BTreeMap iterates over its values.
+    // The order derived for ExternalObjectRef compares the kind, name and namespace in turn, which is object_ref_lt.
+    #[verifier(external_body)]
+    pub fn values(&self) -> (values: Vec<DynamicObject>)
+        ensures
+            self@.dom().finite(),
+            values@.map_values(|v: DynamicObject| v@) == sorted_keys(self@, object_ref_order()).map_values(|k: ObjectRef| self@[k]),
+    {
+        self.inner.values().cloned().collect()
+    }
+
     #[verifier(external)]
     pub fn from_rust_map(inner: std::collections::BTreeMap<ExternalObjectRef, DynamicObject>) -> ObjectMap { ObjectMap { inner: inner } }
 
@@ -0,0 +1,162 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::spec::api_method::PatchType;
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use deps_hack::kube::api::DynamicObject;
+use deps_hack::serde_json::{Map, Value};
+
+// This file implements how the executable model merges a patch into an existing object.
+// It is the executable counterpart of model::patched_object and model::patched_value,
+// and is validated against the real Kubernetes API by the conformance tests.
+//
+// Only the fields that model::patched_object allows to change are touched here:
+// labels, annotations, finalizers, owner references and everything in the object body except status.
+
+pub fn patched_kube_object(patch_type: &PatchType, kind: &str, patch: &DynamicObject, old_obj: &DynamicObject) -> DynamicObject {
+    let mut obj = old_obj.clone();
+    if let Some(labels) = &patch.metadata.labels {
+        obj.metadata.labels.get_or_insert_with(Default::default).extend(labels.clone());
+    }
+    if let Some(annotations) = &patch.metadata.annotations {
+        obj.metadata.annotations.get_or_insert_with(Default::default).extend(annotations.clone());
+    }
+    if let Some(finalizers) = &patch.metadata.finalizers {
+        obj.metadata.finalizers = Some(match patch_type {
+            PatchType::MergePatch => finalizers.clone(),
+            PatchType::StrategicMergePatch => {
+                let mut merged = obj.metadata.finalizers.clone().unwrap_or_default();
+                let old_len = merged.len();
+                for finalizer in finalizers {
+                    if !merged[..old_len].contains(finalizer) {
+                        merged.push(finalizer.clone());
+                    }
+                }
+                merged
+            }
+        });
+    }
+    if let Some(owner_references) = &patch.metadata.owner_references {
+        obj.metadata.owner_references = Some(match patch_type {
+            PatchType::MergePatch => owner_references.clone(),
+            PatchType::StrategicMergePatch => merge_owner_references(
+                obj.metadata.owner_references.clone().unwrap_or_default(),
+                owner_references,
+            ),
+        });
+    }
+    // The object body (e.g., spec of most kinds and data of ConfigMap and Secret) is in the data field.
+    // Status is skipped because patching the main resource does not change the status.
+    if let Value::Object(patch_data) = &patch.data {
+        for (key, value) in patch_data {
+            if key == "status" {
+                continue;
+            }
+            let old_value = obj.data.get(key).cloned().unwrap_or(Value::Null);
+            let new_value = patched_field_value(patch_type, kind, Some(key), old_value, value);
+            match (&mut obj.data, new_value) {
+                (Value::Object(data), Value::Null) => {
+                    data.remove(key);
+                }
+                (Value::Object(data), new_value) => {
+                    data.insert(key.clone(), new_value);
+                }
+                (data, new_value) => {
+                    let mut new_data = Map::new();
+                    if !new_value.is_null() {
+                        new_data.insert(key.clone(), new_value);
+                    }
+                    *data = Value::Object(new_data);
+                }
+            }
+        }
+    }
+    obj
+}
+
+// Each old owner reference is replaced by the one in the patch with the same uid (if any),
+// and the owner references in the patch with new uids are appended.
+fn merge_owner_references(old_owner_references: Vec<OwnerReference>, patch_owner_references: &Vec<OwnerReference>) -> Vec<OwnerReference> {
+    let mut merged: Vec<OwnerReference> = old_owner_references
+        .iter()
+        .map(|o| {
+            patch_owner_references
+                .iter()
+                .find(|p| p.uid == o.uid)
+                .unwrap_or(o)
+                .clone()
+        })
+        .collect();
+    for p in patch_owner_references {
+        if !old_owner_references.iter().any(|o| o.uid == p.uid) {
+            merged.push(p.clone());
+        }
+    }
+    merged
+}
+
+// patched_value merges patch into old_value following RFC 7386: objects are merged key by key,
+// a null in the patch removes the key, and any other value replaces the old one.
+//
+// For strategic merge patch, the lists declared with patchStrategy=merge in the built-in kinds are additionally
+// merged by their patchMergeKey (see merge_key): each old item is merged with the patch item that has the same key (if any),
+// and the patch items with new keys are appended. So an empty patch list leaves such a list unchanged,
+// as in Kubernetes. All the other lists (including the ones in merge patches) are replaced by the patch list.
+// The patch directives (e.g., $patch and $setElementOrder) are not supported.
+pub fn patched_value(patch_type: &PatchType, kind: &str, old_value: Value, patch: &Value) -> Value {
+    patched_field_value(patch_type, kind, None, old_value, patch)
+}
+
+fn patched_field_value(patch_type: &PatchType, kind: &str, field: Option<&str>, old_value: Value, patch: &Value) -> Value {
+    match (old_value, patch) {
+        (Value::Object(mut old_map), Value::Object(patch_map)) => {
+            for (key, patch_value) in patch_map {
+                if patch_value.is_null() {
+                    old_map.remove(key);
+                } else {
+                    let old_value = old_map.remove(key).unwrap_or(Value::Null);
+                    old_map.insert(key.clone(), patched_field_value(patch_type, kind, Some(key), old_value, patch_value));
+                }
+            }
+            Value::Object(old_map)
+        }
+        (_, Value::Object(patch_map)) => patched_field_value(patch_type, kind, field, Value::Object(Map::new()), &Value::Object(patch_map.clone())),
+        (Value::Array(old_list), Value::Array(patch_list)) if matches!(patch_type, PatchType::StrategicMergePatch) => {
+            match field.and_then(|field| merge_key(kind, field)) {
+                Some(merge_key) => {
+                    let key_of = |item: &Value| item.get(merge_key).cloned();
+                    let mut merged = Vec::new();
+                    for old_item in &old_list {
+                        match patch_list.iter().find(|p| key_of(p).is_some() && key_of(p) == key_of(old_item)) {
+                            Some(patch_item) => merged.push(patched_field_value(patch_type, kind, None, old_item.clone(), patch_item)),
+                            None => merged.push(old_item.clone()),
+                        }
+                    }
+                    for patch_item in patch_list {
+                        if key_of(patch_item).is_none() || !old_list.iter().any(|o| key_of(o) == key_of(patch_item)) {
+                            merged.push(patched_field_value(patch_type, kind, None, Value::Null, patch_item));
+                        }
+                    }
+                    Value::Array(merged)
+                }
+                None => patch.clone(),
+            }
+        }
+        (_, patch) => patch.clone(),
+    }
+}
+
+// merge_key returns the patchMergeKey of the list field if the field has patchStrategy=merge in the built-in kinds.
+// ports is the only such field name that has different merge keys: port for a Service, and containerPort for a container.
+pub fn merge_key(kind: &str, field: &str) -> Option<&'static str> {
+    match field {
+        "containers" | "initContainers" | "ephemeralContainers" | "volumes" | "env" | "imagePullSecrets"
+        | "resourceClaims" | "secrets" => Some("name"),
+        "volumeMounts" => Some("mountPath"),
+        "volumeDevices" => Some("devicePath"),
+        "ports" if kind == "Service" => Some("port"),
+        "ports" => Some("containerPort"),
+        "hostAliases" => Some("ip"),
+        "topologySpreadConstraints" => Some("topologyKey"),
+        _ => None,
+    }
+}
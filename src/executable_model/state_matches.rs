@@ -0,0 +1,231 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::*};
+use crate::kubernetes_api_objects::{exec::prelude::*, spec::prelude::*};
+use vstd::prelude::*;
+
+verus! {
+
+// The functions below are the exec versions of the conjuncts that the liveness theorems of the controllers
+// (e.g., crate::zookeeper_controller::trusted::liveness_theorem::resource_state_matches) check on each sub-resource.
+// Each of them takes the key of a sub-resource, the object (or the part of it) that the reconciler makes
+// and the objects stored in the cluster, and returns whether the stored object matches the made one.
+// The per-controller checkers (e.g., crate::zookeeper_controller::exec::state_matches) assemble them
+// into resource_state_matches and prove the assembly equals the spec-level predicate.
+//
+// The comparisons (e.g., ObjectMeta::labels_eq and StatefulSet::spec_eq) compare the kube objects in full,
+// so they are as precise as the views of the objects, like LabelSelector::eq. This is exact for the objects
+// kept by the exec API server model, while an object read from a real cluster may carry fields that the
+// API server sets by default and the views do not model (e.g., the session affinity of a Service),
+// in which case the check fails unless the snapshot puts back the object the controller wrote
+// (see crate::shim_layer::cluster_snapshot::WrittenObjects).
+
+pub fn metadata_matches(key: &KubeObjectRef, made: &ObjectMeta, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& resources@[key@].metadata.labels == made@.labels
+            &&& resources@[key@].metadata.annotations == made@.annotations
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let metadata = obj.unwrap().metadata();
+    metadata.labels_eq(made) && metadata.annotations_eq(made)
+}
+
+// service_spec_matches ignores the cluster IP, which the API server allocates unless the made Service sets it.
+pub fn service_spec_matches(key: &KubeObjectRef, made: &Service, resources: &ObjectMap) -> (res: bool)
+    requires made@.spec.is_Some(),
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& ServiceView::unmarshal(resources@[key@]).is_Ok()
+            &&& ServiceView::unmarshal(resources@[key@]).get_Ok_0().spec.is_Some()
+            &&& made@.spec.get_Some_0() == ServiceSpecView {
+                cluster_ip: made@.spec.get_Some_0().cluster_ip,
+                ..ServiceView::unmarshal(resources@[key@]).get_Ok_0().spec.get_Some_0()
+            }
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let service = Service::unmarshal(obj.unwrap());
+    if service.is_err() {
+        return false;
+    }
+    let found_spec = service.unwrap().spec();
+    if found_spec.is_none() {
+        return false;
+    }
+    let mut spec = found_spec.unwrap();
+    let mut made_spec = made.spec().unwrap();
+    let ghost found_spec_view = spec@;
+    let ghost made_spec_view = made_spec@;
+    spec.unset_cluster_ip();
+    made_spec.unset_cluster_ip();
+    let res = made_spec.eq(&spec);
+    proof {
+        // Both sides agree on all the fields but the cluster IP
+        assert(res == (made_spec_view == ServiceSpecView { cluster_ip: made_spec_view.cluster_ip, ..found_spec_view }));
+    }
+    res
+}
+
+pub fn config_map_data_matches(key: &KubeObjectRef, made: &ConfigMap, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& ConfigMapView::unmarshal(resources@[key@]).is_Ok()
+            &&& ConfigMapView::unmarshal(resources@[key@]).get_Ok_0().data == made@.data
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let config_map = ConfigMap::unmarshal(obj.unwrap());
+    if config_map.is_err() {
+        return false;
+    }
+    config_map.unwrap().data_eq(made)
+}
+
+pub fn secret_data_matches(key: &KubeObjectRef, made: &Secret, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& SecretView::unmarshal(resources@[key@]).is_Ok()
+            &&& SecretView::unmarshal(resources@[key@]).get_Ok_0().data == made@.data
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let secret = Secret::unmarshal(obj.unwrap());
+    if secret.is_err() {
+        return false;
+    }
+    secret.unwrap().data_eq(made)
+}
+
+// object_spec_matches compares the raw spec of the stored object, e.g., for the liveness theorems
+// that require the spec of a ConfigMap to be exactly the marshalled data of the made one.
+pub fn object_spec_matches(key: &KubeObjectRef, made: &DynamicObject, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& resources@[key@].spec == made@.spec
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    obj.unwrap().spec_eq(made)
+}
+
+pub fn service_account_matches(key: &KubeObjectRef, made: &ServiceAccount, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& ServiceAccountView::unmarshal(resources@[key@]).is_Ok()
+            &&& ServiceAccountView::unmarshal(resources@[key@]).get_Ok_0().automount_service_account_token == made@.automount_service_account_token
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let service_account = ServiceAccount::unmarshal(obj.unwrap());
+    if service_account.is_err() {
+        return false;
+    }
+    service_account.unwrap().automount_service_account_token_eq(made)
+}
+
+pub fn role_matches(key: &KubeObjectRef, made: &Role, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& RoleView::unmarshal(resources@[key@]).is_Ok()
+            &&& RoleView::unmarshal(resources@[key@]).get_Ok_0().policy_rules == made@.policy_rules
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let role = Role::unmarshal(obj.unwrap());
+    if role.is_err() {
+        return false;
+    }
+    role.unwrap().rules_eq(made)
+}
+
+pub fn role_binding_matches(key: &KubeObjectRef, made: &RoleBinding, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& RoleBindingView::unmarshal(resources@[key@]).is_Ok()
+            &&& RoleBindingView::unmarshal(resources@[key@]).get_Ok_0().role_ref == made@.role_ref
+            &&& RoleBindingView::unmarshal(resources@[key@]).get_Ok_0().subjects == made@.subjects
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let role_binding = RoleBinding::unmarshal(obj.unwrap());
+    if role_binding.is_err() {
+        return false;
+    }
+    let role_binding = role_binding.unwrap();
+    role_binding.role_ref().eq(&made.role_ref()) && role_binding.subjects_eq(made)
+}
+
+pub fn stateful_set_spec_matches(key: &KubeObjectRef, made: &StatefulSet, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& StatefulSetView::unmarshal(resources@[key@]).is_Ok()
+            &&& StatefulSetView::unmarshal(resources@[key@]).get_Ok_0().spec == made@.spec
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let stateful_set = StatefulSet::unmarshal(obj.unwrap());
+    if stateful_set.is_err() {
+        return false;
+    }
+    stateful_set.unwrap().spec_eq(made)
+}
+
+pub fn daemon_set_spec_matches(key: &KubeObjectRef, made: &DaemonSet, resources: &ObjectMap) -> (res: bool)
+    ensures
+        res == {
+            &&& resources@.contains_key(key@)
+            &&& DaemonSetView::unmarshal(resources@[key@]).is_Ok()
+            &&& DaemonSetView::unmarshal(resources@[key@]).get_Ok_0().spec == made@.spec
+        },
+{
+    let obj = resources.get(key);
+    if obj.is_none() {
+        return false;
+    }
+    let daemon_set = DaemonSet::unmarshal(obj.unwrap());
+    if daemon_set.is_err() {
+        return false;
+    }
+    daemon_set.unwrap().spec_eq(made)
+}
+
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 
+pub mod executable_model;
 pub mod external_api;
 #[path = "controller_examples/fluent_controller/mod.rs"]
 pub mod fluent_controller;
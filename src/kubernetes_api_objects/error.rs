@@ -1,6 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 use deps_hack::kube;
+use deps_hack::serde::{Deserialize, Serialize};
 use vstd::prelude::*;
 
 verus! {
@@ -18,6 +19,13 @@ pub enum APIError {
     InternalError,
     Timeout,
     ServerTimeout,
+    Unauthorized,
+    TooManyRequests,
+    Gone,
+    Expired,
+    MethodNotAllowed,
+    RequestEntityTooLarge,
+    ServiceUnavailable,
     Other
 }
 
@@ -35,6 +43,13 @@ impl std::fmt::Debug for APIError {
             APIError::InternalError => write!(f, "InternalError"),
             APIError::Timeout => write!(f, "Timeout"),
             APIError::ServerTimeout => write!(f, "ServerTimeout"),
+            APIError::Unauthorized => write!(f, "Unauthorized"),
+            APIError::TooManyRequests => write!(f, "TooManyRequests"),
+            APIError::Gone => write!(f, "Gone"),
+            APIError::Expired => write!(f, "Expired"),
+            APIError::MethodNotAllowed => write!(f, "MethodNotAllowed"),
+            APIError::RequestEntityTooLarge => write!(f, "RequestEntityTooLarge"),
+            APIError::ServiceUnavailable => write!(f, "ServiceUnavailable"),
             APIError::Other => write!(f, "Other"),
         }
     }
@@ -68,6 +83,32 @@ impl APIError {
             _ => false,
         }
     }
+
+    /// is_transient tells whether the error can be returned for any request
+    /// regardless of the request and the cluster state, i.e., the request can succeed if retried later:
+    /// timeouts, resource version conflicts, throttling, an unavailable API server, and expired resource versions.
+    pub open spec fn is_transient(self) -> bool {
+        ||| self.is_Timeout()
+        ||| self.is_ServerTimeout()
+        ||| self.is_Conflict()
+        ||| self.is_TooManyRequests()
+        ||| self.is_ServiceUnavailable()
+        ||| self.is_Gone()
+        ||| self.is_Expired()
+    }
+}
+
 }
 
+/// APIErrorDetails keeps what the exec side knows about a failed request beyond its APIError,
+/// which is all that reconcile_core (and the spec) sees.
+/// code is the HTTP status code returned by the API server, or None if the request never got a response
+/// (e.g., the connection is refused or times out).
+/// Note that kube-rs keeps neither the response headers nor the details of the returned Status in its errors,
+/// so the retry-after hint of a throttled request (429) is not available here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub struct APIErrorDetails {
+    pub code: Option<u16>,
+    pub message: std::string::String,
 }
@@ -1,7 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 use crate::kubernetes_api_objects::error::*;
-use crate::kubernetes_api_objects::exec::{api_resource::*, dynamic::*};
+use crate::kubernetes_api_objects::exec::{api_resource::*, dynamic::*, label_selector::*, preconditions::*, resource::*};
 use crate::kubernetes_api_objects::spec::{
     api_method::*,
     common::{Kind, ObjectRef},
@@ -31,6 +31,8 @@ pub enum KubeAPIRequest {
     DeleteRequest(KubeDeleteRequest),
     UpdateRequest(KubeUpdateRequest),
     UpdateStatusRequest(KubeUpdateStatusRequest),
+    ApplyRequest(KubeApplyRequest),
+    PatchRequest(KubePatchRequest),
 }
 
 /// KubeGetRequest has the name as the parameter of Api.get(), and namespace to instantiate an Api.
@@ -61,11 +63,14 @@ impl View for KubeGetRequest {
     }
 }
 
-/// KubeListRequest has the namespace to instantiate an Api.
+/// KubeListRequest has the namespace to instantiate an Api,
+/// and the label selector and field selector to build the ListParams for Api.list().
 
 pub struct KubeListRequest {
     pub api_resource: ApiResource,
     pub namespace: String,
+    pub label_selector: Option<LabelSelector>,
+    pub field_selector: Option<FieldSelector>,
 }
 
 impl KubeListRequest {
@@ -73,6 +78,29 @@ impl KubeListRequest {
     pub fn key(&self) -> std::string::String {
         format!("{}/{}", self.api_resource.as_kube_ref().kind, self.namespace)
     }
+
+    /// has_invalid_selector checks whether the label selector has a requirement that the Kubernetes API cannot parse,
+    /// which makes the list request fail with BadRequest (see LabelSelectorView::is_valid).
+    #[verifier(external)]
+    pub fn has_invalid_selector(&self) -> bool {
+        match &self.label_selector {
+            Some(label_selector) => !label_selector.is_valid(),
+            None => false,
+        }
+    }
+
+    /// list_params translates the selectors to the ListParams used by Api.list().
+    #[verifier(external)]
+    pub fn list_params(&self) -> deps_hack::kube::api::ListParams {
+        let mut lp = deps_hack::kube::api::ListParams::default();
+        if let Some(label_selector) = &self.label_selector {
+            lp = lp.labels(&label_selector.to_selector_string());
+        }
+        if let Some(field_selector) = &self.field_selector {
+            lp = lp.fields(&field_selector.to_selector_string());
+        }
+        lp
+    }
 }
 
 impl View for KubeListRequest {
@@ -81,6 +109,58 @@ impl View for KubeListRequest {
         ListRequest {
             kind: self.api_resource@.kind,
             namespace: self.namespace@,
+            label_selector: match self.label_selector {
+                Some(label_selector) => Some(label_selector@),
+                None => None,
+            },
+            field_selector: match self.field_selector {
+                Some(field_selector) => Some(field_selector@),
+                None => None,
+            },
+        }
+    }
+}
+
+/// FieldSelector selects objects by the values of their fields.
+/// For now it only supports selecting by metadata.name.
+
+pub struct FieldSelector {
+    pub name: Option<String>,
+}
+
+impl FieldSelector {
+    pub fn default() -> (field_selector: FieldSelector)
+        ensures field_selector@ == FieldSelectorView::default(),
+    {
+        FieldSelector {
+            name: None,
+        }
+    }
+
+    pub fn set_name(&mut self, name: String)
+        ensures self@ == old(self)@.set_name(name@),
+    {
+        self.name = Some(name);
+    }
+
+    #[verifier(external)]
+    pub fn to_selector_string(&self) -> std::string::String {
+        let mut requirements = Vec::new();
+        if let Some(name) = &self.name {
+            requirements.push(format!("metadata.name={}", name));
+        }
+        requirements.join(",")
+    }
+}
+
+impl View for FieldSelector {
+    type V = FieldSelectorView;
+    open spec fn view(&self) -> FieldSelectorView {
+        FieldSelectorView {
+            name: match self.name {
+                Some(name) => Some(name@),
+                None => None,
+            },
         }
     }
 }
@@ -111,11 +191,15 @@ impl View for KubeCreateRequest {
 }
 
 /// KubeDeleteRequest has the name as the parameter of Api.delete(), and namespace to instantiate an Api.
+/// The preconditions, propagation_policy and grace_period_seconds are used to build the DeleteParams.
 
 pub struct KubeDeleteRequest {
     pub api_resource: ApiResource,
     pub name: String,
     pub namespace: String,
+    pub preconditions: Option<Preconditions>,
+    pub propagation_policy: Option<PropagationPolicy>,
+    pub grace_period_seconds: Option<u32>,
 }
 
 impl KubeDeleteRequest {
@@ -123,6 +207,21 @@ impl KubeDeleteRequest {
     pub fn key(&self) -> std::string::String {
         format!("{}/{}/{}", self.api_resource.as_kube_ref().kind, self.namespace, self.name)
     }
+
+    /// delete_params builds the DeleteParams used by Api.delete().
+    #[verifier(external)]
+    pub fn delete_params(&self) -> deps_hack::kube::api::DeleteParams {
+        deps_hack::kube::api::DeleteParams {
+            preconditions: self.preconditions.as_ref().map(|p| p.clone().into_kube()),
+            propagation_policy: self.propagation_policy.map(|p| match p {
+                PropagationPolicy::Orphan => deps_hack::kube::api::PropagationPolicy::Orphan,
+                PropagationPolicy::Background => deps_hack::kube::api::PropagationPolicy::Background,
+                PropagationPolicy::Foreground => deps_hack::kube::api::PropagationPolicy::Foreground,
+            }),
+            grace_period_seconds: self.grace_period_seconds,
+            ..deps_hack::kube::api::DeleteParams::default()
+        }
+    }
 }
 
 impl View for KubeDeleteRequest {
@@ -133,7 +232,16 @@ impl View for KubeDeleteRequest {
                 kind: self.api_resource@.kind,
                 name: self.name@,
                 namespace: self.namespace@,
-            }
+            },
+            preconditions: match self.preconditions {
+                Some(p) => Some(p@),
+                None => None,
+            },
+            propagation_policy: self.propagation_policy,
+            grace_period_seconds: match self.grace_period_seconds {
+                Some(g) => Some(g as int),
+                None => None,
+            },
         }
     }
 }
@@ -192,6 +300,82 @@ impl View for KubeUpdateStatusRequest {
     }
 }
 
+/// KubeApplyRequest has the obj as the parameter of Api.patch() with Patch::Apply,
+/// and the field manager and force flag to build the PatchParams.
+
+pub struct KubeApplyRequest {
+    pub api_resource: ApiResource,
+    pub name: String,
+    pub namespace: String,
+    pub obj: DynamicObject,
+    pub field_manager: String,
+    pub force: bool,
+}
+
+impl KubeApplyRequest {
+    #[verifier(external)]
+    pub fn key(&self) -> std::string::String {
+        format!("{}/{}/{}", self.api_resource.as_kube_ref().kind, self.namespace, self.name)
+    }
+
+    /// patch_params builds the PatchParams used by Api.patch() for server-side apply.
+    #[verifier(external)]
+    pub fn patch_params(&self) -> deps_hack::kube::api::PatchParams {
+        let pp = deps_hack::kube::api::PatchParams::apply(&self.field_manager);
+        if self.force {
+            pp.force()
+        } else {
+            pp
+        }
+    }
+}
+
+impl View for KubeApplyRequest {
+    type V = ApplyRequest;
+    open spec fn view(&self) -> ApplyRequest {
+        ApplyRequest {
+            name: self.name@,
+            namespace: self.namespace@,
+            obj: self.obj@,
+            field_manager: self.field_manager@,
+            force: self.force,
+        }
+    }
+}
+
+/// KubePatchRequest has the name and the patch as the parameters of Api.patch(),
+/// and the patch_type to choose between Patch::Merge and Patch::Strategic.
+
+pub struct KubePatchRequest {
+    pub api_resource: ApiResource,
+    pub name: String,
+    pub namespace: String,
+    pub patch: DynamicObject,
+    pub patch_type: PatchType,
+}
+
+impl KubePatchRequest {
+    #[verifier(external)]
+    pub fn key(&self) -> std::string::String {
+        format!("{}/{}/{}", self.api_resource.as_kube_ref().kind, self.namespace, self.name)
+    }
+}
+
+impl View for KubePatchRequest {
+    type V = PatchRequest;
+    open spec fn view(&self) -> PatchRequest {
+        PatchRequest {
+            key: ObjectRef {
+                kind: self.api_resource@.kind,
+                name: self.name@,
+                namespace: self.namespace@,
+            },
+            patch: self.patch@,
+            patch_type: self.patch_type,
+        }
+    }
+}
+
 impl View for KubeAPIRequest {
     type V = APIRequest;
 
@@ -203,6 +387,8 @@ impl View for KubeAPIRequest {
             KubeAPIRequest::DeleteRequest(delete_req) => APIRequest::DeleteRequest(delete_req@),
             KubeAPIRequest::UpdateRequest(update_req) => APIRequest::UpdateRequest(update_req@),
             KubeAPIRequest::UpdateStatusRequest(update_status_req) => APIRequest::UpdateStatusRequest(update_status_req@),
+            KubeAPIRequest::ApplyRequest(apply_req) => APIRequest::ApplyRequest(apply_req@),
+            KubeAPIRequest::PatchRequest(patch_req) => APIRequest::PatchRequest(patch_req@),
         }
     }
 }
@@ -226,6 +412,8 @@ pub enum KubeAPIResponse {
     DeleteResponse(KubeDeleteResponse),
     UpdateResponse(KubeUpdateResponse),
     UpdateStatusResponse(KubeUpdateStatusResponse),
+    ApplyResponse(KubeApplyResponse),
+    PatchResponse(KubePatchResponse),
 }
 
 /// KubeGetResponse has the object returned by KubeGetRequest.
@@ -324,6 +512,38 @@ impl View for KubeUpdateStatusResponse {
     }
 }
 
+/// KubeApplyResponse has the object created or updated by KubeApplyRequest.
+
+pub struct KubeApplyResponse {
+    pub res: Result<DynamicObject, APIError>,
+}
+
+impl View for KubeApplyResponse {
+    type V = ApplyResponse;
+    open spec fn view(&self) -> ApplyResponse {
+        match self.res {
+            Ok(o) => ApplyResponse { res: Ok(o@) },
+            Err(e) => ApplyResponse { res: Err(e) },
+        }
+    }
+}
+
+/// KubePatchResponse has the object patched by KubePatchRequest.
+
+pub struct KubePatchResponse {
+    pub res: Result<DynamicObject, APIError>,
+}
+
+impl View for KubePatchResponse {
+    type V = PatchResponse;
+    open spec fn view(&self) -> PatchResponse {
+        match self.res {
+            Ok(o) => PatchResponse { res: Ok(o@) },
+            Err(e) => PatchResponse { res: Err(e) },
+        }
+    }
+}
+
 impl View for KubeAPIResponse {
     type V = APIResponse;
     open spec fn view(&self) -> APIResponse {
@@ -334,6 +554,8 @@ impl View for KubeAPIResponse {
             KubeAPIResponse::DeleteResponse(delete_resp) => APIResponse::DeleteResponse(delete_resp@),
             KubeAPIResponse::UpdateResponse(update_resp) => APIResponse::UpdateResponse(update_resp@),
             KubeAPIResponse::UpdateStatusResponse(update_status_resp) => APIResponse::UpdateStatusResponse(update_status_resp@),
+            KubeAPIResponse::ApplyResponse(apply_resp) => APIResponse::ApplyResponse(apply_resp@),
+            KubeAPIResponse::PatchResponse(patch_resp) => APIResponse::PatchResponse(patch_resp@),
         }
     }
 }
@@ -517,6 +739,66 @@ impl KubeAPIResponse {
             _ => unreached(),
         }
     }
+
+    pub fn is_apply_response(&self) -> (res: bool)
+        ensures
+            res == self.is_ApplyResponse(),
+    {
+        match self {
+            KubeAPIResponse::ApplyResponse(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_apply_response_ref(&self) -> (resp: &KubeApplyResponse)
+        requires self.is_ApplyResponse(),
+        ensures resp == self.get_ApplyResponse_0(),
+    {
+        match self {
+            KubeAPIResponse::ApplyResponse(resp) => resp,
+            _ => unreached(),
+        }
+    }
+
+    pub fn into_apply_response(self) -> (resp: KubeApplyResponse)
+        requires self.is_ApplyResponse(),
+        ensures resp == self.get_ApplyResponse_0(),
+    {
+        match self {
+            KubeAPIResponse::ApplyResponse(resp) => resp,
+            _ => unreached(),
+        }
+    }
+
+    pub fn is_patch_response(&self) -> (res: bool)
+        ensures
+            res == self.is_PatchResponse(),
+    {
+        match self {
+            KubeAPIResponse::PatchResponse(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_patch_response_ref(&self) -> (resp: &KubePatchResponse)
+        requires self.is_PatchResponse(),
+        ensures resp == self.get_PatchResponse_0(),
+    {
+        match self {
+            KubeAPIResponse::PatchResponse(resp) => resp,
+            _ => unreached(),
+        }
+    }
+
+    pub fn into_patch_response(self) -> (resp: KubePatchResponse)
+        requires self.is_PatchResponse(),
+        ensures resp == self.get_PatchResponse_0(),
+    {
+        match self {
+            KubeAPIResponse::PatchResponse(resp) => resp,
+            _ => unreached(),
+        }
+    }
 }
 
 pub open spec fn opt_resp_to_view(resp: &Option<KubeAPIResponse>) -> Option<APIResponse> {
@@ -20,6 +20,13 @@ pub struct ApiResource {
 impl ApiResource {
     pub spec fn view(&self) -> ApiResourceView;
 
+    #[verifier(external_body)]
+    pub fn clone(&self) -> (s: Self)
+        ensures s@ == self@,
+    {
+        ApiResource { inner: self.inner.clone() }
+    }
+
     #[verifier(external)]
     pub fn as_kube_ref(&self) -> &deps_hack::kube::api::ApiResource {
         &self.inner
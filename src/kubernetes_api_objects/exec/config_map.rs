@@ -65,6 +65,13 @@ impl ConfigMap {
         }
     }
 
+    #[verifier(external_body)]
+    pub fn data_eq(&self, other: &ConfigMap) -> (b: bool)
+        ensures b == (self@.data == other@.data)
+    {
+        self.inner.data == other.inner.data
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata(&mut self, metadata: ObjectMeta)
         ensures self@ == old(self)@.set_metadata(metadata@),
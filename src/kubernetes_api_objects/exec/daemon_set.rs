@@ -61,6 +61,13 @@ impl DaemonSet {
         if self.inner.spec.is_none() { None } else { Some(DaemonSetSpec::from_kube(self.inner.spec.as_ref().unwrap().clone())) }
     }
 
+    #[verifier(external_body)]
+    pub fn spec_eq(&self, other: &DaemonSet) -> (b: bool)
+        ensures b == (self@.spec == other@.spec)
+    {
+        self.inner.spec == other.inner.spec
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata(&mut self, metadata: ObjectMeta)
         ensures self@ == old(self)@.set_metadata(metadata@),
@@ -67,29 +67,72 @@ impl LabelSelector {
         self.inner.match_labels = Some(match_labels.into_rust_map());
     }
 
+    /// is_valid checks whether each requirement in match_expressions can be parsed by the Kubernetes API.
+    #[verifier(external_body)]
+    pub fn is_valid(&self) -> (res: bool)
+        ensures res == self@.is_valid()
+    {
+        match &self.inner.match_expressions {
+            Some(exprs) => exprs.iter().all(|expr| {
+                let values_len = expr.values.as_ref().map_or(0, |values| values.len());
+                match expr.operator.as_str() {
+                    "In" | "NotIn" => values_len > 0,
+                    "Exists" | "DoesNotExist" => values_len == 0,
+                    _ => false,
+                }
+            }),
+            None => true,
+        }
+    }
+
+    /// to_selector_string translates the label selector to the form used by the labelSelector
+    /// query parameter of the Kubernetes API (e.g., "app=zookeeper,tier in (backend,cache),!canary").
+    /// The caller should check is_valid() first, since an invalid requirement has no such form.
+    #[verifier(external)]
+    pub fn to_selector_string(&self) -> std::string::String {
+        let mut requirements = Vec::new();
+        if let Some(ml) = &self.inner.match_labels {
+            requirements.extend(ml.iter().map(|(k, v)| format!("{}={}", k, v)));
+        }
+        if let Some(exprs) = &self.inner.match_expressions {
+            requirements.extend(exprs.iter().map(|expr| {
+                let values = expr.values.clone().unwrap_or_default().join(",");
+                match expr.operator.as_str() {
+                    "In" => format!("{} in ({})", expr.key, values),
+                    "NotIn" => format!("{} notin ({})", expr.key, values),
+                    "DoesNotExist" => format!("!{}", expr.key),
+                    _ => expr.key.clone(),
+                }
+            }));
+        }
+        requirements.join(",")
+    }
+
     // TODO: prove it and maybe move to a different lib
     #[verifier(external_body)]
     pub fn matches(&self, labels: StringMap) -> (res: bool)
         ensures res == self@.matches(labels@)
     {
-        if self.match_labels().is_none() {
-            true
-        } else {
-            let match_labels = self.match_labels().unwrap();
-            let keys = match_labels.keys();
-            let mut idx = 0;
-            while idx < match_labels.len()
-            {
-                let key = &keys[idx];
-                let val = match_labels.get(key).unwrap();
-                let val_or_not = labels.get(key);
-                if !(val_or_not.is_some() && val_or_not.unwrap().eq(&val)) {
-                    return false;
+        let labels = labels.into_rust_map();
+        let match_labels_match = match &self.inner.match_labels {
+            Some(ml) => ml.iter().all(|(k, v)| labels.get(k) == Some(v)),
+            None => true,
+        };
+        let match_expressions_match = match &self.inner.match_expressions {
+            Some(exprs) => exprs.iter().all(|expr| {
+                let values = expr.values.clone().unwrap_or_default();
+                let value = labels.get(&expr.key);
+                match expr.operator.as_str() {
+                    "In" => value.map_or(false, |v| values.contains(v)),
+                    "NotIn" => value.map_or(true, |v| !values.contains(v)),
+                    "Exists" => value.is_some(),
+                    "DoesNotExist" => value.is_none(),
+                    _ => false,
                 }
-                idx = idx + 1;
-            }
-            true
-        }
+            }),
+            None => true,
+        };
+        match_labels_match && match_expressions_match
     }
 }
 
@@ -180,6 +180,22 @@ impl ObjectMeta {
         self.inner.uid == other.inner.uid
     }
 
+    // labels_eq and annotations_eq let exec code compare the labels (annotations) of two objects,
+    // e.g., to check whether an object in the cluster carries the labels the reconciler makes.
+    #[verifier(external_body)]
+    pub fn labels_eq(&self, other: &ObjectMeta) -> (b: bool)
+        ensures b == (self@.labels == other@.labels)
+    {
+        self.inner.labels == other.inner.labels
+    }
+
+    #[verifier(external_body)]
+    pub fn annotations_eq(&self, other: &ObjectMeta) -> (b: bool)
+        ensures b == (self@.annotations == other@.annotations)
+    {
+        self.inner.annotations == other.inner.annotations
+    }
+
     #[verifier(external_body)]
     pub fn has_deletion_timestamp(&self) -> (b: bool)
         ensures b == self@.deletion_timestamp.is_Some(),
@@ -187,6 +203,15 @@ impl ObjectMeta {
         self.inner.deletion_timestamp.is_some()
     }
 
+    #[verifier(external_body)]
+    pub fn deletion_grace_period_seconds(&self) -> (deletion_grace_period_seconds: Option<i64>)
+        ensures
+            self@.deletion_grace_period_seconds.is_Some() == deletion_grace_period_seconds.is_Some(),
+            deletion_grace_period_seconds.is_Some() ==> deletion_grace_period_seconds.get_Some_0() as int == self@.deletion_grace_period_seconds.get_Some_0(),
+    {
+        self.inner.deletion_grace_period_seconds
+    }
+
     #[verifier(external_body)]
     pub fn set_name(&mut self, name: String)
         ensures self@ == old(self)@.set_name(name@),
@@ -146,6 +146,15 @@ impl PodSpec {
         PodSpec { inner: self.inner.clone() }
     }
 
+    #[verifier(external_body)]
+    pub fn termination_grace_period_seconds(&self) -> (termination_grace_period_seconds: Option<i64>)
+        ensures
+            self@.termination_grace_period_seconds.is_Some() == termination_grace_period_seconds.is_Some(),
+            termination_grace_period_seconds.is_Some() ==> termination_grace_period_seconds.get_Some_0() as int == self@.termination_grace_period_seconds.get_Some_0(),
+    {
+        self.inner.termination_grace_period_seconds
+    }
+
     #[verifier(external_body)]
     pub fn set_affinity(&mut self, affinity: Affinity)
         ensures self@ == old(self)@.set_affinity(affinity@),
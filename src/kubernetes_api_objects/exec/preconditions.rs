@@ -0,0 +1,73 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::{object_meta::*, resource::*};
+use crate::kubernetes_api_objects::spec::preconditions::*;
+use vstd::prelude::*;
+
+verus! {
+
+/// Preconditions must be satisfied by the existing object before a delete request is handled.
+///
+/// This definition is a wrapper of Preconditions defined at
+/// https://github.com/kube-rs/kube/blob/main/kube-core/src/params.rs.
+/// It is supposed to be used in exec controller code.
+///
+
+#[verifier(external_body)]
+pub struct Preconditions {
+    inner: deps_hack::kube::api::Preconditions,
+}
+
+impl Preconditions {
+    pub spec fn view(&self) -> PreconditionsView;
+
+    #[verifier(external_body)]
+    pub fn default() -> (preconditions: Preconditions)
+        ensures preconditions@ == PreconditionsView::default(),
+    {
+        Preconditions {
+            inner: deps_hack::kube::api::Preconditions::default(),
+        }
+    }
+
+    #[verifier(external_body)]
+    pub fn clone(&self) -> (s: Self)
+        ensures s@ == self@,
+    {
+        Preconditions { inner: self.inner.clone() }
+    }
+
+    // Uid and ResourceVersion are int in spec but String in exec, so instead of setting them
+    // from a String we copy them from the ObjectMeta of the object that the request targets.
+    #[verifier(external_body)]
+    pub fn set_uid_from_object_meta(&mut self, meta: &ObjectMeta)
+        ensures self@ == old(self)@.set_uid_from_object_meta(meta@),
+    {
+        self.inner.uid = meta.clone().into_kube().uid;
+    }
+
+    #[verifier(external_body)]
+    pub fn set_resource_version_from_object_meta(&mut self, meta: &ObjectMeta)
+        ensures self@ == old(self)@.set_resource_version_from_object_meta(meta@),
+    {
+        self.inner.resource_version = meta.clone().into_kube().resource_version;
+    }
+
+    #[verifier(external_body)]
+    pub fn satisfied_by(&self, meta: &ObjectMeta) -> (b: bool)
+        ensures b == self@.satisfied_by(meta@),
+    {
+        let meta = meta.clone().into_kube();
+        (self.inner.uid.is_none() || self.inner.uid == meta.uid)
+        && (self.inner.resource_version.is_none() || self.inner.resource_version == meta.resource_version)
+    }
+}
+
+#[verifier(external)]
+impl ResourceWrapper<deps_hack::kube::api::Preconditions> for Preconditions {
+    fn from_kube(inner: deps_hack::kube::api::Preconditions) -> Preconditions { Preconditions { inner: inner } }
+
+    fn into_kube(self) -> deps_hack::kube::api::Preconditions { self.inner }
+}
+
+}
@@ -56,6 +56,13 @@ impl Role {
         }
     }
 
+    #[verifier(external_body)]
+    pub fn rules_eq(&self, other: &Role) -> (b: bool)
+        ensures b == (self@.policy_rules == other@.policy_rules)
+    {
+        self.inner.rules == other.inner.rules
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata(&mut self, metadata: ObjectMeta)
         ensures self@ == old(self)@.set_metadata(metadata@),
@@ -55,6 +55,13 @@ impl RoleBinding {
         RoleRef::from_kube(self.inner.role_ref.clone())
     }
 
+    #[verifier(external_body)]
+    pub fn subjects_eq(&self, other: &RoleBinding) -> (b: bool)
+        ensures b == (self@.subjects == other@.subjects)
+    {
+        self.inner.subjects == other.inner.subjects
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata(&mut self, metadata: ObjectMeta)
         ensures self@ == old(self)@.set_metadata(metadata@),
@@ -143,6 +143,13 @@ impl ServiceSpec {
         ServiceSpec { inner: self.inner.clone() }
     }
 
+    #[verifier(external_body)]
+    pub fn eq(&self, other: &Self) -> (b: bool)
+        ensures b == (self@ == other@)
+    {
+        self.inner == other.inner
+    }
+
     #[verifier(external_body)]
     pub fn set_cluster_ip(&mut self, cluster_ip: String)
         ensures self@ == old(self)@.set_cluster_ip(cluster_ip@),
@@ -150,6 +157,13 @@ impl ServiceSpec {
         self.inner.cluster_ip = Some(cluster_ip)
     }
 
+    #[verifier(external_body)]
+    pub fn unset_cluster_ip(&mut self)
+        ensures self@ == old(self)@.unset_cluster_ip(),
+    {
+        self.inner.cluster_ip = None;
+    }
+
     #[verifier(external_body)]
     pub fn ports(&self) -> (ports: Option<Vec<ServicePort>>)
         ensures
@@ -49,6 +49,13 @@ impl ServiceAccount {
         ObjectMeta::from_kube(self.inner.metadata.clone())
     }
 
+    #[verifier(external_body)]
+    pub fn automount_service_account_token_eq(&self, other: &ServiceAccount) -> (b: bool)
+        ensures b == (self@.automount_service_account_token == other@.automount_service_account_token)
+    {
+        self.inner.automount_service_account_token == other.inner.automount_service_account_token
+    }
+
     #[verifier(external_body)]
     pub fn set_metadata(&mut self, metadata: ObjectMeta)
         ensures self@ == old(self)@.set_metadata(metadata@),
@@ -72,6 +72,13 @@ impl StatefulSet {
         }
     }
 
+    #[verifier(external_body)]
+    pub fn spec_eq(&self, other: &StatefulSet) -> (b: bool)
+        ensures b == (self@.spec == other@.spec)
+    {
+        self.inner.spec == other.inner.spec
+    }
+
     #[verifier(external_body)]
     pub fn status(&self) -> (status: Option<StatefulSetStatus>)
         ensures
@@ -4,6 +4,8 @@ use crate::kubernetes_api_objects::error::APIError;
 use crate::kubernetes_api_objects::spec::{
     common::{Kind, ObjectRef},
     dynamic::*,
+    label_selector::*,
+    preconditions::*,
 };
 use crate::vstd_ext::string_view::*;
 use vstd::string::*;
@@ -19,7 +21,6 @@ verus! {
 /// Each variant in APIRequest represents on type of request.
 /// For now we do not consider Watch.
 
-// TODO: implement Update and Patch request.
 #[is_variant]
 pub enum APIRequest {
     GetRequest(GetRequest),
@@ -28,6 +29,8 @@ pub enum APIRequest {
     DeleteRequest(DeleteRequest),
     UpdateRequest(UpdateRequest),
     UpdateStatusRequest(UpdateStatusRequest),
+    ApplyRequest(ApplyRequest),
+    PatchRequest(PatchRequest),
 }
 
 /// GetRequest gets an object with the key (kind, name and namespace).
@@ -36,11 +39,53 @@ pub struct GetRequest {
     pub key: ObjectRef,
 }
 
-/// ListRequest lists all the objects of kind in namespace.
+/// ListRequest lists all the objects of kind in namespace
+/// that match the label selector and the field selector (if provided).
 
 pub struct ListRequest {
     pub kind: Kind,
     pub namespace: StringView,
+    pub label_selector: Option<LabelSelectorView>,
+    pub field_selector: Option<FieldSelectorView>,
+}
+
+impl ListRequest {
+    pub open spec fn selects(self, obj: DynamicObjectView) -> bool {
+        &&& obj.object_ref().namespace == self.namespace
+        &&& obj.object_ref().kind == self.kind
+        // An object without labels is treated as one with empty labels, same as the Kubernetes API
+        &&& self.label_selector.is_Some() ==> self.label_selector.get_Some_0().matches(obj.metadata.labels.unwrap_or(Map::empty()))
+        &&& self.field_selector.is_Some() ==> self.field_selector.get_Some_0().matches(obj)
+    }
+}
+
+/// FieldSelectorView selects objects by the values of their fields.
+///
+/// For now it only supports selecting by metadata.name (i.e., "metadata.name=<name>"),
+/// which is supported by the Kubernetes API for all kinds including custom resources.
+/// Fields that are not provided do not constrain the selected objects.
+
+pub struct FieldSelectorView {
+    pub name: Option<StringView>,
+}
+
+impl FieldSelectorView {
+    pub open spec fn default() -> FieldSelectorView {
+        FieldSelectorView {
+            name: None,
+        }
+    }
+
+    pub open spec fn set_name(self, name: StringView) -> FieldSelectorView {
+        FieldSelectorView {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub open spec fn matches(self, obj: DynamicObjectView) -> bool {
+        self.name.is_Some() ==> obj.metadata.name == self.name
+    }
 }
 
 /// CreateRequest creates the obj.
@@ -61,9 +106,41 @@ impl CreateRequest {
 }
 
 /// DeleteRequest deletes the object with the key.
+///
+/// If preconditions are provided, the existing object must satisfy them, otherwise the request fails with Conflict.
+/// The propagation_policy decides what happens to the dependents (i.e., objects owned by the deleted one);
+/// if it is not provided, the dependents are deleted in the background by the garbage collector.
+/// The grace_period_seconds overrides the default grace period of the kinds that support graceful deletion (i.e., pods),
+/// and is ignored by the other kinds.
 
 pub struct DeleteRequest {
     pub key: ObjectRef,
+    pub preconditions: Option<PreconditionsView>,
+    pub propagation_policy: Option<PropagationPolicy>,
+    pub grace_period_seconds: Option<int>,
+}
+
+/// PropagationPolicy decides whether and how the dependents are garbage collected after the owner is deleted.
+///
+/// Orphan keeps the dependents and removes the owner's reference from them before the owner is deleted.
+/// Background deletes the owner immediately and lets the garbage collector delete the dependents afterwards.
+/// Foreground keeps the owner (with a deletion timestamp) until the dependents that block the owner's deletion are deleted.
+
+#[is_variant]
+#[derive(PartialEq, Eq, Debug)]
+pub enum PropagationPolicy {
+    Orphan,
+    Background,
+    Foreground,
+}
+
+impl std::marker::Copy for PropagationPolicy {}
+
+impl std::clone::Clone for PropagationPolicy {
+    #[verifier(external_body)]
+    fn clone(&self) -> (result: Self)
+        ensures result == self
+    { *self }
 }
 
 /// UpdateRequest replaces the existing obj with a new one.
@@ -102,6 +179,62 @@ impl UpdateStatusRequest {
     }
 }
 
+/// ApplyRequest applies the obj on behalf of the field manager using server-side apply.
+///
+/// If the object does not exist, it is created.
+/// Otherwise the applied fields are merged into the existing object,
+/// which conflicts with the fields owned by other managers unless force is set.
+
+pub struct ApplyRequest {
+    pub namespace: StringView,
+    pub name: StringView,
+    pub obj: DynamicObjectView,
+    pub field_manager: StringView,
+    pub force: bool,
+}
+
+impl ApplyRequest {
+    pub open spec fn key(self) -> ObjectRef {
+        ObjectRef {
+            kind: self.obj.kind,
+            namespace: self.namespace,
+            name: self.name,
+        }
+    }
+}
+
+/// PatchType is the content type of the patch in PatchRequest.
+///
+/// MergePatch is the JSON merge patch (RFC 7386) supported by all kinds.
+/// StrategicMergePatch is the strategic merge patch supported by built-in kinds only.
+
+#[is_variant]
+#[derive(PartialEq, Eq, Debug)]
+pub enum PatchType {
+    MergePatch,
+    StrategicMergePatch,
+}
+
+impl std::marker::Copy for PatchType {}
+
+impl std::clone::Clone for PatchType {
+    #[verifier(external_body)]
+    fn clone(&self) -> (result: Self)
+        ensures result == self
+    { *self }
+}
+
+/// PatchRequest patches the existing object with the key.
+///
+/// The patch is a partial object: the fields it carries are merged into the existing object
+/// according to the patch_type, and the fields it omits are left unchanged.
+
+pub struct PatchRequest {
+    pub key: ObjectRef,
+    pub patch: DynamicObjectView,
+    pub patch_type: PatchType,
+}
+
 /// APIResponse represents API responses sent from the Kubernetes API for specifications.
 
 #[is_variant]
@@ -112,6 +245,8 @@ pub enum APIResponse {
     DeleteResponse(DeleteResponse),
     UpdateResponse(UpdateResponse),
     UpdateStatusResponse(UpdateStatusResponse),
+    ApplyResponse(ApplyResponse),
+    PatchResponse(PatchResponse),
 }
 
 /// GetResponse has the object returned by GetRequest.
@@ -150,4 +285,16 @@ pub struct UpdateStatusResponse {
     pub res: Result<DynamicObjectView, APIError>,
 }
 
+/// ApplyResponse has the object created or updated by ApplyRequest.
+
+pub struct ApplyResponse {
+    pub res: Result<DynamicObjectView, APIError>,
+}
+
+/// PatchResponse has the object patched by PatchRequest.
+
+pub struct PatchResponse {
+    pub res: Result<DynamicObjectView, APIError>,
+}
+
 }
@@ -1,6 +1,6 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
-use crate::vstd_ext::string_view::*;
+use crate::vstd_ext::{map_lib::strict_total_order, string_view::*};
 use vstd::prelude::*;
 use vstd::string::*;
 
@@ -17,13 +17,12 @@ pub type ResourceVersion = int;
 // make GenerateNameCounter an int, instead of String, so that it is easy to compare in spec/proof
 pub type GenerateNameCounter = int;
 
-// TODO: make CustomResourceKind take a string so that we
-// can differentiate between different custom resources
+// CustomResourceKind carries the kind of the custom resource (e.g., "RabbitmqCluster")
+// so that we can differentiate between different custom resources
 #[is_variant]
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum Kind {
     ConfigMapKind,
-    CustomResourceKind,
+    CustomResourceKind(StringView),
     DaemonSetKind,
     PersistentVolumeClaimKind,
     PodKind,
@@ -35,19 +34,103 @@ pub enum Kind {
     SecretKind,
 }
 
-impl std::marker::Copy for Kind {}
-
-impl std::clone::Clone for Kind {
-    #[verifier(external_body)]
-    fn clone(&self) -> (result: Self)
-        ensures result == self
-    { *self }
-}
-
 pub struct ObjectRef {
     pub kind: Kind,
     pub name: StringView,
     pub namespace: StringView,
 }
 
+// kind_lt and object_ref_lt mirror the orders derived for KubeKind and ExternalObjectRef in the exec model:
+// kinds are ordered by their variants (and custom resource kinds by their names),
+// and object references are ordered by kind, then name, then namespace.
+// It is the order in which the exec model iterates over the stored objects (see ObjectMap::values).
+pub open spec fn kind_index(kind: Kind) -> int {
+    match kind {
+        Kind::ConfigMapKind => 0,
+        Kind::CustomResourceKind(_) => 1,
+        Kind::DaemonSetKind => 2,
+        Kind::PersistentVolumeClaimKind => 3,
+        Kind::PodKind => 4,
+        Kind::RoleKind => 5,
+        Kind::RoleBindingKind => 6,
+        Kind::StatefulSetKind => 7,
+        Kind::ServiceKind => 8,
+        Kind::ServiceAccountKind => 9,
+        Kind::SecretKind => 10,
+    }
+}
+
+pub open spec fn kind_lt(a: Kind, b: Kind) -> bool {
+    if a.is_CustomResourceKind() && b.is_CustomResourceKind() {
+        string_view_lt(a.get_CustomResourceKind_0(), b.get_CustomResourceKind_0())
+    } else {
+        kind_index(a) < kind_index(b)
+    }
+}
+
+pub open spec fn object_ref_lt(a: ObjectRef, b: ObjectRef) -> bool {
+    ||| kind_lt(a.kind, b.kind)
+    ||| a.kind == b.kind && string_view_lt(a.name, b.name)
+    ||| a.kind == b.kind && a.name == b.name && string_view_lt(a.namespace, b.namespace)
+}
+
+pub open spec fn object_ref_order() -> spec_fn(ObjectRef, ObjectRef) -> bool {
+    |a: ObjectRef, b: ObjectRef| object_ref_lt(a, b)
+}
+
+proof fn lemma_kind_lt_is_strict_total_order()
+    ensures strict_total_order(|a: Kind, b: Kind| kind_lt(a, b)),
+{
+    assert forall |a: Kind| !#[trigger] kind_lt(a, a) by {
+        if a.is_CustomResourceKind() {
+            lemma_string_view_lt_is_irreflexive(a.get_CustomResourceKind_0());
+        }
+    }
+    assert forall |a: Kind, b: Kind, c: Kind| #[trigger] kind_lt(a, b) && #[trigger] kind_lt(b, c) implies kind_lt(a, c) by {
+        if a.is_CustomResourceKind() && b.is_CustomResourceKind() && c.is_CustomResourceKind() {
+            lemma_string_view_lt_is_transitive(a.get_CustomResourceKind_0(), b.get_CustomResourceKind_0(), c.get_CustomResourceKind_0());
+        }
+    }
+    assert forall |a: Kind, b: Kind| a != b implies #[trigger] kind_lt(a, b) || kind_lt(b, a) by {
+        if a.is_CustomResourceKind() && b.is_CustomResourceKind() {
+            lemma_string_view_lt_is_total(a.get_CustomResourceKind_0(), b.get_CustomResourceKind_0());
+        }
+    }
+}
+
+pub proof fn lemma_object_ref_lt_is_strict_total_order()
+    ensures strict_total_order(object_ref_order()),
+{
+    lemma_kind_lt_is_strict_total_order();
+    let kind_order = |a: Kind, b: Kind| kind_lt(a, b);
+    assert forall |a: ObjectRef| !#[trigger] object_ref_lt(a, a) by {
+        assert(!kind_order(a.kind, a.kind));
+        lemma_string_view_lt_is_irreflexive(a.name);
+        lemma_string_view_lt_is_irreflexive(a.namespace);
+    }
+    assert forall |a: ObjectRef, b: ObjectRef, c: ObjectRef| #[trigger] object_ref_lt(a, b) && #[trigger] object_ref_lt(b, c)
+    implies object_ref_lt(a, c) by {
+        if a.kind == b.kind && b.kind == c.kind {
+            if a.name == b.name && b.name == c.name {
+                lemma_string_view_lt_is_transitive(a.namespace, b.namespace, c.namespace);
+            } else if string_view_lt(a.name, b.name) && string_view_lt(b.name, c.name) {
+                lemma_string_view_lt_is_transitive(a.name, b.name, c.name);
+            }
+        } else if a.kind != b.kind && b.kind != c.kind {
+            assert(kind_order(a.kind, b.kind) && kind_order(b.kind, c.kind));
+            assert(kind_order(a.kind, c.kind));
+        }
+    }
+    assert forall |a: ObjectRef, b: ObjectRef| a != b implies #[trigger] object_ref_lt(a, b) || object_ref_lt(b, a) by {
+        if a.kind != b.kind {
+            assert(kind_order(a.kind, b.kind) || kind_order(b.kind, a.kind));
+        } else if a.name != b.name {
+            lemma_string_view_lt_is_total(a.name, b.name);
+        } else {
+            lemma_string_view_lt_is_total(a.namespace, b.namespace);
+        }
+    }
+    assert forall |a: ObjectRef, b: ObjectRef| #[trigger] object_ref_order()(a, b) == object_ref_lt(a, b) by {}
+}
+
 }
@@ -14,12 +14,14 @@ verus! {
 
 pub struct LabelSelectorView {
     pub match_labels: Option<Map<StringView, StringView>>,
+    pub match_expressions: Option<Seq<LabelSelectorRequirementView>>,
 }
 
 impl LabelSelectorView {
     pub open spec fn default() -> LabelSelectorView {
         LabelSelectorView {
             match_labels: None,
+            match_expressions: None,
         }
     }
 
@@ -30,14 +32,72 @@ impl LabelSelectorView {
         }
     }
 
-    // TODO: handle match_expressions as well
+    pub open spec fn set_match_expressions(self, match_expressions: Seq<LabelSelectorRequirementView>) -> LabelSelectorView {
+        LabelSelectorView {
+            match_expressions: Some(match_expressions),
+            ..self
+        }
+    }
+
+    // The result of match_labels and match_expressions are ANDed, same as the Kubernetes API.
     pub open spec fn matches(self, labels: Map<StringView, StringView>) -> bool {
-        if self.match_labels.is_None() {
-            true
-        } else {
+        &&& self.match_labels.is_Some() ==> {
             let match_labels = self.match_labels.get_Some_0();
             forall |k, v| match_labels.contains_pair(k, v) ==> labels.contains_pair(k, v)
         }
+        &&& self.match_expressions.is_Some() ==> {
+            let match_expressions = self.match_expressions.get_Some_0();
+            forall |i| 0 <= i < match_expressions.len() ==> #[trigger] match_expressions[i].matches(labels)
+        }
+    }
+
+    // is_valid checks whether each requirement in match_expressions can be parsed by the Kubernetes API;
+    // a list request with an invalid label selector fails with BadRequest.
+    pub open spec fn is_valid(self) -> bool {
+        self.match_expressions.is_Some() ==> {
+            let match_expressions = self.match_expressions.get_Some_0();
+            forall |i| 0 <= i < match_expressions.len() ==> #[trigger] match_expressions[i].is_valid()
+        }
+    }
+}
+
+/// LabelSelectorRequirementView is the ghost type of LabelSelectorRequirement.
+/// It is supposed to be used in spec and proof code.
+
+pub struct LabelSelectorRequirementView {
+    pub key: StringView,
+    pub operator: StringView,
+    pub values: Option<Seq<StringView>>,
+}
+
+impl LabelSelectorRequirementView {
+    pub open spec fn values_or_empty(self) -> Seq<StringView> {
+        self.values.unwrap_or(Seq::empty())
+    }
+
+    // In and NotIn need a non-empty set of values, while Exists and DoesNotExist take no values.
+    pub open spec fn is_valid(self) -> bool {
+        if self.operator == "In"@ || self.operator == "NotIn"@ {
+            self.values_or_empty().len() > 0
+        } else if self.operator == "Exists"@ || self.operator == "DoesNotExist"@ {
+            self.values_or_empty().len() == 0
+        } else {
+            false
+        }
+    }
+
+    pub open spec fn matches(self, labels: Map<StringView, StringView>) -> bool {
+        if self.operator == "In"@ {
+            labels.contains_key(self.key) && self.values_or_empty().contains(labels[self.key])
+        } else if self.operator == "NotIn"@ {
+            !labels.contains_key(self.key) || !self.values_or_empty().contains(labels[self.key])
+        } else if self.operator == "Exists"@ {
+            labels.contains_key(self.key)
+        } else if self.operator == "DoesNotExist"@ {
+            !labels.contains_key(self.key)
+        } else {
+            false
+        }
     }
 }
 
@@ -0,0 +1,71 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::spec::common::*;
+use crate::vstd_ext::string_view::*;
+use vstd::prelude::*;
+use vstd::string::*;
+
+verus! {
+
+/// FieldPathView identifies a field whose ownership is tracked by server-side apply.
+///
+/// Labels and annotations are owned key by key. Finalizers are a set, so each finalizer is owned by itself,
+/// and owner references are a map keyed by uid, so each owner reference is owned by its uid.
+/// Fields in the object body (spec) are identified by their paths from the body to the leaf values,
+/// e.g., ["data", "key"] of a ConfigMap or ["spec", "replicas"] of a StatefulSet.
+/// Lists in the body are leaves, so each list is owned as a whole.
+
+#[is_variant]
+pub enum FieldPathView {
+    Label(StringView),
+    Annotation(StringView),
+    Finalizer(StringView),
+    OwnerReference(Uid),
+    Spec(Seq<StringView>),
+}
+
+/// ManagedFieldsEntryView is the ghost type of ManagedFieldsEntry.
+/// It is supposed to be used in spec and proof code.
+///
+/// Each entry records which fields (fieldsV1) a field manager owns and the operation that set the fields.
+
+pub struct ManagedFieldsEntryView {
+    pub manager: Option<StringView>,
+    pub operation: Option<StringView>,
+    pub fields: Set<FieldPathView>,
+}
+
+impl ManagedFieldsEntryView {
+    pub open spec fn default() -> ManagedFieldsEntryView {
+        ManagedFieldsEntryView {
+            manager: None,
+            operation: None,
+            fields: Set::empty(),
+        }
+    }
+
+    pub open spec fn set_manager(self, manager: StringView) -> ManagedFieldsEntryView {
+        ManagedFieldsEntryView {
+            manager: Some(manager),
+            ..self
+        }
+    }
+
+    pub open spec fn set_operation(self, operation: StringView) -> ManagedFieldsEntryView {
+        ManagedFieldsEntryView {
+            operation: Some(operation),
+            ..self
+        }
+    }
+
+    pub open spec fn set_fields(self, fields: Set<FieldPathView>) -> ManagedFieldsEntryView {
+        ManagedFieldsEntryView {
+            fields: fields,
+            ..self
+        }
+    }
+}
+
+pub open spec fn apply_operation() -> StringView { "Apply"@ }
+
+}
@@ -9,12 +9,14 @@ pub mod container;
 pub mod daemon_set;
 pub mod dynamic;
 pub mod label_selector;
+pub mod managed_fields_entry;
 pub mod marshal;
 pub mod object_meta;
 pub mod owner_reference;
 pub mod persistent_volume_claim;
 pub mod pod;
 pub mod pod_template_spec;
+pub mod preconditions;
 pub mod prelude;
 pub mod resource;
 pub mod resource_requirements;
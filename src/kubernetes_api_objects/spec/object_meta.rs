@@ -1,7 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 use crate::kubernetes_api_objects::error::*;
-use crate::kubernetes_api_objects::spec::{common::*, marshal::*, owner_reference::*, resource::*};
+use crate::kubernetes_api_objects::spec::{common::*, managed_fields_entry::*, marshal::*, owner_reference::*, resource::*};
 use crate::vstd_ext::string_map::*;
 use crate::vstd_ext::string_view::*;
 use vstd::prelude::*;
@@ -23,6 +23,8 @@ pub struct ObjectMetaView {
     pub owner_references: Option<Seq<OwnerReferenceView>>,
     pub finalizers: Option<Seq<StringView>>,
     pub deletion_timestamp: Option<StringView>,
+    pub deletion_grace_period_seconds: Option<int>,
+    pub managed_fields: Option<Seq<ManagedFieldsEntryView>>,
 }
 
 impl ObjectMetaView {
@@ -38,6 +40,8 @@ impl ObjectMetaView {
             owner_references: None,
             finalizers: None,
             deletion_timestamp: None,
+            deletion_grace_period_seconds: None,
+            managed_fields: None,
         }
     }
 
@@ -145,6 +149,20 @@ impl ObjectMetaView {
         }
     }
 
+    pub open spec fn set_deletion_grace_period_seconds(self, deletion_grace_period_seconds: int) -> ObjectMetaView {
+        ObjectMetaView {
+            deletion_grace_period_seconds: Some(deletion_grace_period_seconds),
+            ..self
+        }
+    }
+
+    pub open spec fn set_managed_fields(self, managed_fields: Seq<ManagedFieldsEntryView>) -> ObjectMetaView {
+        ObjectMetaView {
+            managed_fields: Some(managed_fields),
+            ..self
+        }
+    }
+
     pub open spec fn well_formed(self) -> bool {
         &&& self.name.is_Some()
         &&& self.namespace.is_Some()
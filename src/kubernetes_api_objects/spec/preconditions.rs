@@ -0,0 +1,61 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::spec::{common::*, object_meta::*};
+use vstd::prelude::*;
+
+verus! {
+
+/// PreconditionsView is the ghost type of Preconditions.
+/// It is supposed to be used in spec and proof code.
+///
+/// Preconditions must be satisfied by the existing object before the request is handled.
+/// Fields that are not provided do not constrain the existing object.
+
+pub struct PreconditionsView {
+    pub uid: Option<Uid>,
+    pub resource_version: Option<ResourceVersion>,
+}
+
+impl PreconditionsView {
+    pub open spec fn default() -> PreconditionsView {
+        PreconditionsView {
+            uid: None,
+            resource_version: None,
+        }
+    }
+
+    pub open spec fn set_uid(self, uid: Uid) -> PreconditionsView {
+        PreconditionsView {
+            uid: Some(uid),
+            ..self
+        }
+    }
+
+    pub open spec fn set_resource_version(self, resource_version: ResourceVersion) -> PreconditionsView {
+        PreconditionsView {
+            resource_version: Some(resource_version),
+            ..self
+        }
+    }
+
+    pub open spec fn set_uid_from_object_meta(self, meta: ObjectMetaView) -> PreconditionsView {
+        PreconditionsView {
+            uid: meta.uid,
+            ..self
+        }
+    }
+
+    pub open spec fn set_resource_version_from_object_meta(self, meta: ObjectMetaView) -> PreconditionsView {
+        PreconditionsView {
+            resource_version: meta.resource_version,
+            ..self
+        }
+    }
+
+    pub open spec fn satisfied_by(self, meta: ObjectMetaView) -> bool {
+        &&& self.uid.is_Some() ==> meta.uid == self.uid
+        &&& self.resource_version.is_Some() ==> meta.resource_version == self.resource_version
+    }
+}
+
+}
@@ -5,11 +5,13 @@ pub use crate::kubernetes_api_objects::spec::common::*;
 pub use crate::kubernetes_api_objects::spec::config_map::*;
 pub use crate::kubernetes_api_objects::spec::daemon_set::*;
 pub use crate::kubernetes_api_objects::spec::dynamic::*;
+pub use crate::kubernetes_api_objects::spec::managed_fields_entry::*;
 pub use crate::kubernetes_api_objects::spec::marshal::*;
 pub use crate::kubernetes_api_objects::spec::object_meta::*;
 pub use crate::kubernetes_api_objects::spec::owner_reference::*;
 pub use crate::kubernetes_api_objects::spec::persistent_volume_claim::*;
 pub use crate::kubernetes_api_objects::spec::pod::*;
+pub use crate::kubernetes_api_objects::spec::preconditions::*;
 pub use crate::kubernetes_api_objects::spec::resource::*;
 pub use crate::kubernetes_api_objects::spec::role::*;
 pub use crate::kubernetes_api_objects::spec::role_binding::*;
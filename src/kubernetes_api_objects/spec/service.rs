@@ -159,6 +159,13 @@ impl ServiceSpecView {
         }
     }
 
+    pub open spec fn unset_cluster_ip(self) -> ServiceSpecView {
+        ServiceSpecView {
+            cluster_ip: None,
+            ..self
+        }
+    }
+
     pub open spec fn set_ports(self, ports: Seq<ServicePortView>) -> ServiceSpecView {
         ServiceSpecView {
             ports: Some(ports),
@@ -5,7 +5,8 @@ use crate::external_api::spec::ExternalAPI;
 use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, resource::*};
 use crate::kubernetes_cluster::spec::{
-    api_server::types::ApiServerAction, builtin_controllers::types::*, cluster::*,
+    api_server::state_machine::handle_get_request, api_server::types::ApiServerAction,
+    builtin_controllers::types::*, cluster::*,
     cluster_state_machine::Step, message::*,
 };
 use crate::reconciler::spec::reconciler::Reconciler;
@@ -141,6 +142,17 @@ pub proof fn lemma_get_req_leads_to_some_resp(spec: TempPred<Self>, msg: MsgType
                     assert(pre(s_prime));
                 }
             },
+            Step::ServeReadFromCacheStep(input) => {
+                if input == msg {
+                    let cached_api_server_state = s.cache_state.as_api_server_state(s.kubernetes_api_state);
+                    let resp = Message::form_get_resp_msg(msg, handle_get_request(msg.content.get_get_request(), cached_api_server_state));
+                    assert(s_prime.in_flight().contains(resp));
+                    assert(Message::resp_msg_matches_req_msg(resp, msg));
+                    assert(post(s_prime));
+                } else {
+                    assert(pre(s_prime));
+                }
+            },
             _ => assert(pre(s_prime)),
         }
     }
@@ -163,6 +175,7 @@ pub proof fn lemma_get_req_leads_to_some_resp(spec: TempPred<Self>, msg: MsgType
 pub proof fn lemma_get_req_leads_to_ok_or_err_resp(spec: TempPred<Self>, msg: MsgType<E>, key: ObjectRef)
     requires
         spec.entails(always(lift_state(Self::busy_disabled()))),
+        spec.entails(always(lift_state(Self::stale_read_disabled()))),
         spec.entails(always(lift_action(Self::next()))),
         spec.entails(tla_forall(|i| Self::kubernetes_api_next().weak_fairness(i))),
     ensures
@@ -187,10 +200,16 @@ pub proof fn lemma_get_req_leads_to_ok_or_err_resp(spec: TempPred<Self>, msg: Ms
         ||| s.in_flight().contains(Message::form_get_resp_msg(msg, GetResponse{res: Err(APIError::ObjectNotFound)}))
     };
     let stronger_next = |s, s_prime: Self| {
-        Self::next()(s, s_prime)
-        && !s.transient_failure_enabled
+        &&& Self::next()(s, s_prime)
+        &&& Self::busy_disabled()(s)
+        &&& Self::stale_read_disabled()(s)
     };
-    strengthen_next::<Self>(spec, Self::next(), Self::busy_disabled(), stronger_next);
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(Self::next()),
+        lift_state(Self::busy_disabled()),
+        lift_state(Self::stale_read_disabled())
+    );
     Self::lemma_pre_leads_to_post_by_kubernetes_api(spec, Some(msg), stronger_next, Self::handle_request(), pre, post);
     temp_pred_equality::<Self>(
         lift_state(post),
@@ -601,13 +620,20 @@ proof fn pending_requests_num_decreases(spec: TempPred<Self>, rest_id: RestId, m
                     assert(pending_req_multiset =~= pending_req_multiset_prime);
                 }
             },
+            Step::ServeReadFromCacheStep(input) => {
+                if pending_req_multiset.count(input) > 0 {
+                    assert(pending_req_multiset.remove(input) =~= pending_req_multiset_prime);
+                } else {
+                    assert(pending_req_multiset =~= pending_req_multiset_prime);
+                }
+            },
             Step::BuiltinControllersStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
             Step::ControllerStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
-            Step::ClientStep() => {
+            Step::ClientStep(_) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
             Step::ExternalAPIStep(input) => {
@@ -2,10 +2,163 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use crate::external_api::spec::ExternalAPI;
+use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::prelude::*;
-use crate::kubernetes_cluster::spec::{cluster::*, cluster_state_machine::Step, message::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::{state_machine::*, types::*},
+    cluster::*,
+    cluster_state_machine::Step,
+    message::*,
+};
 use crate::reconciler::spec::reconciler::Reconciler;
 use crate::temporal_logic::{defs::*, rules::*};
+use crate::vstd_ext::seq_lib::*;
 use vstd::prelude::*;
 
-verus! {}
+verus! {
+
+// The lemmas below are the field ownership guarantees of server-side apply (see handle_apply_request).
+
+/// An apply that is not forced fails with Conflict, and changes nothing,
+/// if it changes a field owned by another manager.
+pub proof fn lemma_apply_changing_field_of_other_manager_conflicts<K: CustomResourceView>(req: ApplyRequest, s: ApiServerState, f: FieldPathView)
+    requires
+        apply_request_admission_check::<K>(req, s).is_None(),
+        s.resources.contains_key(req.key()),
+        !req.force,
+        applied_fields(req.obj).contains(f),
+        fields_of_other_managers(req, s.resources[req.key()]).contains(f),
+        field_changed(req.obj, s.resources[req.key()], f),
+    ensures handle_apply_request::<K>(req, s) == (s, ApplyResponse{res: Err(APIError::Conflict)}),
+{
+    assert(conflicting_fields(req, s.resources[req.key()]).contains(f));
+}
+
+/// If an apply that is not forced does not conflict, then for each field owned by another manager,
+/// the field is not removed, the applier does not change it, and the other manager still owns it afterwards.
+pub proof fn lemma_apply_without_force_preserves_fields_of_other_managers(req: ApplyRequest, old_obj: DynamicObjectView, f: FieldPathView)
+    requires
+        !req.force,
+        !apply_conflicts(req, old_obj),
+        fields_of_other_managers(req, old_obj).contains(f),
+    ensures
+        !removed_fields(req, old_obj).contains(f),
+        applied_fields(req.obj).contains(f) ==> !field_changed(req.obj, old_obj, f),
+        fields_of_other_managers(req, applied_object(req, old_obj)).contains(f),
+{
+    let conflicting = conflicting_fields(req, old_obj);
+    assert(!conflicting.contains(f));
+
+    let old_entries = managed_fields_of(old_obj);
+    let is_other = |entry: ManagedFieldsEntryView| entry.manager != Some(req.field_manager);
+    let drop_conflicting = |entry: ManagedFieldsEntryView| entry.set_fields(entry.fields.difference(conflicting));
+    let is_not_empty = |entry: ManagedFieldsEntryView| entry.fields != Set::<FieldPathView>::empty();
+    let others = old_entries.filter(is_other);
+    let kept_others = others.map_values(drop_conflicting);
+    let non_empty_others = kept_others.filter(is_not_empty);
+    let new_entries = non_empty_others.push(applier_managed_fields_entry(req));
+    assert(managed_fields_after_apply(req, old_obj) == new_entries);
+    assert(managed_fields_of(applied_object(req, old_obj)) == new_entries);
+
+    // The entry of the other manager that owns f is kept with f in its fields.
+    let i = choose |i| 0 <= i < old_entries.len()
+        && #[trigger] old_entries[i].manager != Some(req.field_manager)
+        && old_entries[i].fields.contains(f);
+    assert(old_entries.contains(old_entries[i]));
+    lemma_filter_contains(old_entries, is_other, old_entries[i]);
+    let j = choose |j| 0 <= j < others.len() && others[j] == old_entries[i];
+    let kept = kept_others[j];
+    assert(kept == drop_conflicting(old_entries[i]));
+    assert(kept.fields.contains(f));
+    assert(!Set::<FieldPathView>::empty().contains(f));
+    assert(is_not_empty(kept));
+    assert(kept_others.contains(kept));
+    lemma_filter_contains(kept_others, is_not_empty, kept);
+    let k = choose |k| 0 <= k < non_empty_others.len() && non_empty_others[k] == kept;
+    assert(new_entries[k] == kept);
+    assert(managed_fields_of(applied_object(req, old_obj))[k].manager != Some(req.field_manager));
+    assert(managed_fields_of(applied_object(req, old_obj))[k].fields.contains(f));
+}
+
+/// An apply that is not forced does not change the value of any label owned by another manager.
+pub proof fn lemma_apply_without_force_preserves_labels_of_other_managers(req: ApplyRequest, old_obj: DynamicObjectView, key: StringView)
+    requires
+        !req.force,
+        !apply_conflicts(req, old_obj),
+        fields_of_other_managers(req, old_obj).contains(FieldPathView::Label(key)),
+        old_obj.metadata.labels.is_Some(),
+        old_obj.metadata.labels.get_Some_0().contains_key(key),
+    ensures
+        applied_object(req, old_obj).metadata.labels.is_Some(),
+        applied_object(req, old_obj).metadata.labels.get_Some_0().contains_key(key),
+        applied_object(req, old_obj).metadata.labels.get_Some_0()[key] == old_obj.metadata.labels.get_Some_0()[key],
+{
+    lemma_apply_without_force_preserves_fields_of_other_managers(req, old_obj, FieldPathView::Label(key));
+    let removed = removed_fields(req, old_obj);
+    let removed_keys = Set::new(|k: StringView| removed.contains(FieldPathView::Label(k)));
+    assert(!removed_keys.contains(key));
+    let old_labels = old_obj.metadata.labels.get_Some_0();
+    let applied_labels = req.obj.metadata.labels.unwrap_or(Map::empty());
+    let labels = applied_string_map(old_obj.metadata.labels, req.obj.metadata.labels, removed_keys);
+    assert(old_labels.remove_keys(removed_keys).contains_key(key));
+    assert(old_labels.remove_keys(removed_keys)[key] == old_labels[key]);
+    if applied_labels.contains_key(key) {
+        // The applier sets the label to the same value since it does not conflict.
+        assert(applied_fields(req.obj).contains(FieldPathView::Label(key)));
+        assert(!field_changed(req.obj, old_obj, FieldPathView::Label(key)));
+        assert(applied_labels[key] == old_labels[key]);
+    }
+    assert(labels.get_Some_0().contains_key(key));
+    assert(labels.get_Some_0()[key] == old_labels[key]);
+}
+
+/// A forced apply takes over every field it changes: the applier owns the field afterwards, and no other manager does.
+pub proof fn lemma_forced_apply_takes_over_changed_fields(req: ApplyRequest, old_obj: DynamicObjectView, f: FieldPathView)
+    requires
+        req.force,
+        applied_fields(req.obj).contains(f),
+        field_changed(req.obj, old_obj, f),
+    ensures
+        fields_of_applier(req, applied_object(req, old_obj)).contains(f),
+        !fields_of_other_managers(req, applied_object(req, old_obj)).contains(f),
+{
+    let conflicting = conflicting_fields(req, old_obj);
+    let old_entries = managed_fields_of(old_obj);
+    let is_other = |entry: ManagedFieldsEntryView| entry.manager != Some(req.field_manager);
+    let drop_conflicting = |entry: ManagedFieldsEntryView| entry.set_fields(entry.fields.difference(conflicting));
+    let is_not_empty = |entry: ManagedFieldsEntryView| entry.fields != Set::<FieldPathView>::empty();
+    let others = old_entries.filter(is_other);
+    let kept_others = others.map_values(drop_conflicting);
+    let non_empty_others = kept_others.filter(is_not_empty);
+    let new_entries = non_empty_others.push(applier_managed_fields_entry(req));
+    assert(managed_fields_after_apply(req, old_obj) == new_entries);
+    assert(managed_fields_of(applied_object(req, old_obj)) == new_entries);
+
+    // The applier's entry is the last one.
+    let last = new_entries.len() - 1;
+    assert(new_entries[last] == applier_managed_fields_entry(req));
+    assert(managed_fields_of(applied_object(req, old_obj))[last].manager == Some(req.field_manager));
+    assert(managed_fields_of(applied_object(req, old_obj))[last].fields.contains(f));
+
+    // No entry of the other managers contains f: either f conflicts and is dropped from these entries,
+    // or no other manager owns f in the first place.
+    assert forall |k| 0 <= k < new_entries.len() && #[trigger] new_entries[k].manager != Some(req.field_manager)
+    implies !new_entries[k].fields.contains(f) by {
+        assert(k != last);
+        assert(new_entries[k] == non_empty_others[k]);
+        assert(non_empty_others.contains(non_empty_others[k]));
+        lemma_filter_contains_implies_contains(kept_others, is_not_empty, non_empty_others[k]);
+        let j = choose |j| 0 <= j < kept_others.len() && kept_others[j] == non_empty_others[k];
+        assert(others.contains(others[j]));
+        lemma_filter_contains_implies_contains(old_entries, is_other, others[j]);
+        let i = choose |i| 0 <= i < old_entries.len() && old_entries[i] == others[j];
+        assert(kept_others[j] == drop_conflicting(others[j]));
+        if old_entries[i].fields.contains(f) {
+            assert(managed_fields_of(old_obj)[i].manager != Some(req.field_manager));
+            assert(fields_of_other_managers(req, old_obj).contains(f));
+            assert(conflicting.contains(f));
+        }
+    }
+}
+
+}
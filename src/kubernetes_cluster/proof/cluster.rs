@@ -9,6 +9,7 @@ use crate::kubernetes_cluster::spec::{
     client,
     client::*,
     cluster::*,
+    cluster_state_machine::Step,
     controller::state_machine::*,
     controller::types::{
         ControllerAction, ControllerActionInput, ControllerState, OngoingReconcile,
@@ -88,6 +89,23 @@ pub proof fn lemma_any_pred_leads_to_crash_always_disabled(
     leads_to_trans_temp::<Self>(spec, any_pred, true_pred(), always(lift_state(Self::crash_disabled())));
 }
 
+/// A leader failover is a controller step followed by a restart of the controller,
+/// so the Cluster already covers the executions where the controller fails over to another replica.
+pub proof fn lemma_failover_controller_is_controller_step_then_restart(
+    input: (Option<MsgType<E>>, Option<ObjectRef>), s: Self, s_prime: Self
+)
+    requires Self::failover_controller().forward(input)(s, s_prime),
+    ensures
+        exists |s_mid: Self| {
+            &&& #[trigger] Self::next_step(s, s_mid, Step::ControllerStep(input))
+            &&& Self::next_step(s_mid, s_prime, Step::RestartController())
+        },
+{
+    let s_mid = (Self::controller_next().transition)(input, s).0;
+    assert(Self::next_step(s, s_mid, Step::ControllerStep(input)));
+    assert(Self::next_step(s_mid, s_prime, Step::RestartController()));
+}
+
 // This desired_state_is specifies the desired state (described in the cr object)
 // Informally, it says that given the cr object, the object's key exists in the etcd,
 // and the corresponding object in etcd has the same spec and uid of the given cr object.
@@ -4,11 +4,15 @@
 use crate::external_api::spec::ExternalAPI;
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
-    api_server::state_machine as api_server_state_machine, cluster::*, cluster_state_machine::Step,
+    api_server::state_machine as api_server_state_machine,
+    builtin_controllers::{garbage_collector::*, types::BuiltinControllerChoice},
+    cluster::*,
+    cluster_state_machine::Step,
     message::*,
 };
-use crate::reconciler::spec::reconciler::Reconciler;
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
 use crate::temporal_logic::{defs::*, rules::*};
+use crate::vstd_ext::{seq_lib::*, string_view::*};
 use vstd::prelude::*;
 
 verus! {
@@ -96,6 +100,8 @@ pub proof fn lemma_always_each_object_in_etcd_is_well_formed(spec: TempPred<Self
                             APIRequest::DeleteRequest(_) => {}
                             APIRequest::UpdateRequest(_) => {}
                             APIRequest::UpdateStatusRequest(_) => {}
+                            APIRequest::ApplyRequest(_) => {}
+                            APIRequest::PatchRequest(_) => {}
                         }
                     }
                     _ => {}
@@ -107,7 +113,324 @@ pub proof fn lemma_always_each_object_in_etcd_is_well_formed(spec: TempPred<Self
     init_invariant(spec, Self::init(), Self::next(), invariant);
 }
 
-pub open spec fn each_scheduled_object_has_consistent_key_and_valid_metadata() -> StatePred<Self> {
+/// etcd stores finitely many objects: it is empty initially and each step inserts or removes at most one object.
+/// This is used for counting the objects that satisfy some property (e.g., the pods owned by a replica set).
+pub open spec fn etcd_is_finite() -> StatePred<Self> {
+    |s: Self| s.resources().dom().finite()
+}
+
+pub proof fn lemma_always_etcd_is_finite(spec: TempPred<Self>)
+    requires
+        spec.entails(lift_state(Self::init())),
+        spec.entails(always(lift_action(Self::next()))),
+    ensures spec.entails(always(lift_state(Self::etcd_is_finite()))),
+{
+    let invariant = Self::etcd_is_finite();
+
+    assert forall |s, s_prime: Self| invariant(s) && #[trigger] Self::next()(s, s_prime)
+    implies invariant(s_prime) by {
+        let step = choose |step| Self::next_step(s, s_prime, step);
+        match step {
+            Step::ApiServerStep(input) => {
+                match input.get_Some_0().content.get_APIRequest_0() {
+                    APIRequest::GetRequest(_) => {}
+                    APIRequest::ListRequest(_) => {}
+                    APIRequest::CreateRequest(_) => {}
+                    APIRequest::DeleteRequest(_) => {}
+                    APIRequest::UpdateRequest(_) => {}
+                    APIRequest::UpdateStatusRequest(_) => {}
+                    APIRequest::ApplyRequest(_) => {}
+                    APIRequest::PatchRequest(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    init_invariant(spec, Self::init(), Self::next(), invariant);
+}
+
+/// Without init_with_stale_read, every get and list request is answered by the Kubernetes API,
+/// since no action enables stale reads.
+pub proof fn lemma_always_stale_read_disabled(spec: TempPred<Self>)
+    requires
+        spec.entails(lift_state(Self::init())),
+        spec.entails(always(lift_action(Self::next()))),
+    ensures spec.entails(always(lift_state(Self::stale_read_disabled()))),
+{
+    init_invariant(spec, Self::init(), Self::next(), Self::stale_read_disabled());
+}
+
+/// No object is orphaning its dependents or being deleted in the foreground: no object in etcd has the finalizer of
+/// either propagation policy, and no request in flight would add one. As a result, the garbage collector never orphans
+/// or deletes the dependents in the foreground, and only deletes the objects whose owners are all gone.
+pub open spec fn no_orphan_or_foreground_deletion() -> StatePred<Self> {
+    |s: Self| {
+        &&& forall |key: ObjectRef|
+            #[trigger] s.resources().contains_key(key)
+                ==> !has_propagation_finalizer(s.resources()[key].metadata.finalizers)
+        &&& forall |msg: MsgType<E>|
+            #[trigger] s.in_flight().contains(msg) && msg.content.is_APIRequest()
+                ==> !request_uses_propagation_policy(msg.content.get_APIRequest_0())
+    }
+}
+
+/// The controller never deletes an object with the orphan or foreground propagation policy,
+/// and never sets the finalizer of either policy by itself.
+/// Each controller proves it by checking the requests returned by its reconcile_core.
+pub open spec fn reconciler_does_not_use_propagation_policy() -> bool {
+    forall |cr: K, resp_o: Option<ResponseView<E::Output>>, state: R::T|
+        #![trigger R::reconcile_core(cr, resp_o, state)]
+        R::reconcile_core(cr, resp_o, state).1.is_Some()
+        && R::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+        ==> !request_uses_propagation_policy(R::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0())
+}
+
+/// Same as reconciler_does_not_use_propagation_policy but only for the reconcile of one cr.
+pub open spec fn reconcile_of_cr_does_not_use_propagation_policy(cr: K) -> bool {
+    forall |resp_o: Option<ResponseView<E::Output>>, state: R::T|
+        #![trigger R::reconcile_core(cr, resp_o, state)]
+        R::reconcile_core(cr, resp_o, state).1.is_Some()
+        && R::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+        ==> !request_uses_propagation_policy(R::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0())
+}
+
+/// A weaker version of reconciler_does_not_use_propagation_policy for the controllers that copy finalizers from the cr
+/// (e.g., from a pod template) into the objects they create: the cr that passes state_validation does not make
+/// the controller use the propagation policies.
+pub open spec fn reconciler_does_not_use_propagation_policy_for_valid_cr() -> bool {
+    forall |cr: K, resp_o: Option<ResponseView<E::Output>>, state: R::T|
+        #![trigger R::reconcile_core(cr, resp_o, state)]
+        cr.state_validation()
+        && R::reconcile_core(cr, resp_o, state).1.is_Some()
+        && R::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+        ==> !request_uses_propagation_policy(R::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0())
+}
+
+pub open spec fn each_object_in_reconcile_satisfies_state_validation() -> StatePred<Self> {
+    |s: Self| {
+        forall |key: ObjectRef|
+            #[trigger] s.ongoing_reconciles().contains_key(key)
+                ==> s.ongoing_reconciles()[key].triggering_cr.state_validation()
+    }
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion(spec: TempPred<Self>)
+    requires
+        spec.entails(lift_state(Self::init())),
+        spec.entails(always(lift_action(Self::next()))),
+        Self::reconciler_does_not_use_propagation_policy(),
+    ensures spec.entails(always(lift_state(Self::no_orphan_or_foreground_deletion()))),
+{
+    let invariant = Self::no_orphan_or_foreground_deletion();
+
+    assert forall |s, s_prime: Self| invariant(s) && #[trigger] Self::next()(s, s_prime)
+    implies invariant(s_prime) by {
+        assert forall |key: ObjectRef| #[trigger] s.ongoing_reconciles().contains_key(key)
+        implies Self::reconcile_of_cr_does_not_use_propagation_policy(s.ongoing_reconciles()[key].triggering_cr) by {
+            let cr = s.ongoing_reconciles()[key].triggering_cr;
+            assert forall |resp_o: Option<ResponseView<E::Output>>, state: R::T|
+                #![trigger R::reconcile_core(cr, resp_o, state)]
+                R::reconcile_core(cr, resp_o, state).1.is_Some()
+                && R::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+            implies !request_uses_propagation_policy(R::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0()) by {}
+        }
+        Self::lemma_no_orphan_or_foreground_deletion_is_preserved(s, s_prime);
+    }
+
+    init_invariant(spec, Self::init(), Self::next(), invariant);
+}
+
+pub proof fn lemma_always_no_orphan_or_foreground_deletion_for_valid_cr(spec: TempPred<Self>)
+    requires
+        spec.entails(lift_state(Self::init())),
+        spec.entails(always(lift_action(Self::next()))),
+        spec.entails(always(lift_state(Self::each_object_in_reconcile_satisfies_state_validation()))),
+        Self::reconciler_does_not_use_propagation_policy_for_valid_cr(),
+    ensures spec.entails(always(lift_state(Self::no_orphan_or_foreground_deletion()))),
+{
+    let invariant = Self::no_orphan_or_foreground_deletion();
+    let stronger_next = |s, s_prime: Self| {
+        &&& Self::next()(s, s_prime)
+        &&& Self::each_object_in_reconcile_satisfies_state_validation()(s)
+    };
+    combine_spec_entails_always_n!(
+        spec, lift_action(stronger_next),
+        lift_action(Self::next()),
+        lift_state(Self::each_object_in_reconcile_satisfies_state_validation())
+    );
+
+    assert forall |s, s_prime: Self| invariant(s) && #[trigger] stronger_next(s, s_prime)
+    implies invariant(s_prime) by {
+        assert forall |key: ObjectRef| #[trigger] s.ongoing_reconciles().contains_key(key)
+        implies Self::reconcile_of_cr_does_not_use_propagation_policy(s.ongoing_reconciles()[key].triggering_cr) by {
+            let cr = s.ongoing_reconciles()[key].triggering_cr;
+            assert(cr.state_validation());
+            assert forall |resp_o: Option<ResponseView<E::Output>>, state: R::T|
+                #![trigger R::reconcile_core(cr, resp_o, state)]
+                R::reconcile_core(cr, resp_o, state).1.is_Some()
+                && R::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+            implies !request_uses_propagation_policy(R::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0()) by {}
+        }
+        Self::lemma_no_orphan_or_foreground_deletion_is_preserved(s, s_prime);
+    }
+
+    init_invariant(spec, Self::init(), stronger_next, invariant);
+}
+
+#[verifier(spinoff_prover)]
+proof fn lemma_no_orphan_or_foreground_deletion_is_preserved(s: Self, s_prime: Self)
+    requires
+        Self::next()(s, s_prime),
+        Self::no_orphan_or_foreground_deletion()(s),
+        forall |key: ObjectRef| #[trigger] s.ongoing_reconciles().contains_key(key)
+            ==> Self::reconcile_of_cr_does_not_use_propagation_policy(s.ongoing_reconciles()[key].triggering_cr),
+    ensures Self::no_orphan_or_foreground_deletion()(s_prime),
+{
+    let step = choose |step| Self::next_step(s, s_prime, step);
+    assert forall |key: ObjectRef| #[trigger] s_prime.resources().contains_key(key)
+    implies !has_propagation_finalizer(s_prime.resources()[key].metadata.finalizers) by {
+        // Only the API server changes etcd, and the request it handles does not use the propagation policies,
+        // so the object it creates or changes has no finalizer of the propagation policies.
+        match step {
+            Step::ApiServerStep(input) => {
+                let req_msg = input.get_Some_0();
+                assert(s.in_flight().contains(req_msg));
+                match req_msg.content.get_APIRequest_0() {
+                    APIRequest::GetRequest(_) => {}
+                    APIRequest::ListRequest(_) => {}
+                    APIRequest::CreateRequest(_) => {}
+                    APIRequest::DeleteRequest(_) => {}
+                    APIRequest::UpdateRequest(_) => {}
+                    APIRequest::UpdateStatusRequest(_) => {}
+                    APIRequest::ApplyRequest(_) => {}
+                    APIRequest::PatchRequest(req) => {
+                        if s.resources().contains_key(req.key) {
+                            Self::lemma_patched_finalizers_have_no_propagation_finalizer(
+                                req.patch_type, s.resources()[req.key].metadata.finalizers, req.patch.metadata.finalizers
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    assert forall |msg: MsgType<E>| #[trigger] s_prime.in_flight().contains(msg) && msg.content.is_APIRequest()
+    implies !request_uses_propagation_policy(msg.content.get_APIRequest_0()) by {
+        if !s.in_flight().contains(msg) {
+            match step {
+                Step::ControllerStep(input) => {
+                    // The request comes from reconcile_core, which does not use the propagation policies.
+                    let cr_key = input.1.get_Some_0();
+                    assert(s.ongoing_reconciles().contains_key(cr_key));
+                }
+                Step::BuiltinControllersStep(input) => {
+                    Self::lemma_builtin_controllers_only_send_delete_or_update_status_requests(s, s_prime, msg, input);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+proof fn lemma_patched_finalizers_have_no_propagation_finalizer(
+    patch_type: PatchType, old_finalizers: Option<Seq<StringView>>, patch_finalizers: Option<Seq<StringView>>
+)
+    requires
+        !has_propagation_finalizer(old_finalizers),
+        !has_propagation_finalizer(patch_finalizers),
+    ensures !has_propagation_finalizer(api_server_state_machine::patched_finalizers(patch_type, old_finalizers, patch_finalizers)),
+{
+    if patch_finalizers.is_Some() && patch_type.is_StrategicMergePatch() {
+        let old_list = old_finalizers.unwrap_or(Seq::empty());
+        let pred = |f: StringView| !old_list.contains(f);
+        let added = patch_finalizers.get_Some_0().filter(pred);
+        // Each finalizer in the merged list comes from either the old list or the patch.
+        assert forall |f: StringView| #[trigger] (old_list + added).contains(f)
+        implies old_list.contains(f) || patch_finalizers.get_Some_0().contains(f) by {
+            let i = choose |i: int| 0 <= i < (old_list + added).len() && (old_list + added)[i] == f;
+            if i < old_list.len() {
+                assert(old_list[i] == f);
+            } else {
+                assert(added[i - old_list.len()] == f);
+                assert(added.contains(f));
+                lemma_filter_contains_implies_contains(patch_finalizers.get_Some_0(), pred, f);
+            }
+        }
+        assert(!(old_list + added).contains(api_server_state_machine::orphan_finalizer()));
+        assert(!(old_list + added).contains(api_server_state_machine::foreground_deletion_finalizer()));
+    }
+}
+
+/// As long as no_orphan_or_foreground_deletion holds, the builtin controllers only send the delete requests of the garbage
+/// collector (for the objects whose owners are all gone) and the kubelet (for the pods being deleted gracefully),
+/// and the update status requests of the stateful set and daemon set controllers.
+/// None of them uses a propagation policy, and each delete request carries the uid of the object it deletes.
+pub proof fn lemma_builtin_controllers_only_send_delete_or_update_status_requests(
+    s: Self, s_prime: Self, msg: MsgType<E>, input: (BuiltinControllerChoice, ObjectRef)
+)
+    requires
+        Self::next_step(s, s_prime, Step::BuiltinControllersStep(input)),
+        Self::no_orphan_or_foreground_deletion()(s),
+        !s.in_flight().contains(msg),
+        s_prime.in_flight().contains(msg),
+    ensures
+        msg.src.is_BuiltinController(),
+        msg.content.is_delete_request() || msg.content.is_update_status_request(),
+        msg.content.is_delete_request() ==> {
+            let key = msg.content.get_delete_request().key;
+            &&& msg.content.get_delete_request().propagation_policy.is_None()
+            &&& key == input.1
+            &&& s.resources().contains_key(key)
+            &&& msg.content.get_delete_request().preconditions
+                == Some(PreconditionsView::default().set_uid_from_object_meta(s.resources()[key].metadata))
+            &&& (
+                (input.0.is_GarbageCollector() && Self::garbage_collector_deletion_enabled(key)(s))
+                || (
+                    input.0.is_Kubelet() && key.kind == Kind::PodKind
+                    && s.resources()[key].metadata.deletion_timestamp.is_Some()
+                )
+            )
+        },
+{
+    let resources = s.resources();
+    let key = input.1;
+    let owner_references = resources[key].metadata.owner_references.get_Some_0();
+    match input.0 {
+        BuiltinControllerChoice::GarbageCollectorOrphan => {
+            // Some owner is orphaning, so it has the orphan finalizer, which is impossible.
+            let i = choose |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_orphaning(resources, owner_references[i], key.namespace);
+            let owner_key = owner_reference_to_object_reference(owner_references[i], key.namespace);
+            assert(resources.contains_key(owner_key));
+            assert(resources[owner_key].metadata.finalizers.get_Some_0().contains(api_server_state_machine::orphan_finalizer()));
+            assert(false);
+        }
+        BuiltinControllerChoice::GarbageCollectorFinalizer => {
+            assert(resources.contains_key(key));
+            assert(resources[key].metadata.finalizers.get_Some_0().contains(api_server_state_machine::orphan_finalizer()));
+            assert(false);
+        }
+        BuiltinControllerChoice::GarbageCollectorForeground => {
+            // Some owner is being deleted in the foreground, so it has the foregroundDeletion finalizer, which is impossible.
+            let i = choose |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_deleting_in_foreground(resources, owner_references[i], key.namespace);
+            let owner_key = owner_reference_to_object_reference(owner_references[i], key.namespace);
+            assert(resources.contains_key(owner_key));
+            assert(resources[owner_key].metadata.finalizers.get_Some_0().contains(api_server_state_machine::foreground_deletion_finalizer()));
+            assert(false);
+        }
+        BuiltinControllerChoice::GarbageCollectorForegroundFinalizer => {
+            assert(resources.contains_key(key));
+            assert(resources[key].metadata.finalizers.get_Some_0().contains(api_server_state_machine::foreground_deletion_finalizer()));
+            assert(false);
+        }
+        _ => {}
+    }
+}
+
+pub open spec fn each_scheduled_object_has_consistent_key_and_valid_metadata()-> StatePred<Self> {
     |s: Self| {
         forall |key: ObjectRef|
             #[trigger] s.scheduled_reconciles().contains_key(key)
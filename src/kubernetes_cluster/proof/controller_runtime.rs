@@ -25,7 +25,7 @@ pub open spec fn reconciler_init_and_no_pending_req(cr_key: ObjectRef) -> StateP
 pub open spec fn reconciler_reconcile_init(cr_key: ObjectRef)
     -> StatePred<Self>
     recommends
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
 {
     |s: Self| {
         &&& s.ongoing_reconciles().contains_key(cr_key)
@@ -36,7 +36,7 @@ pub open spec fn reconciler_reconcile_init(cr_key: ObjectRef)
 pub open spec fn reconciler_reconcile_done(cr_key: ObjectRef)
     -> StatePred<Self>
     recommends
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
 {
     |s: Self| {
         &&& s.ongoing_reconciles().contains_key(cr_key)
@@ -47,7 +47,7 @@ pub open spec fn reconciler_reconcile_done(cr_key: ObjectRef)
 pub open spec fn reconciler_reconcile_error(cr_key: ObjectRef)
     -> StatePred<Self>
     recommends
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
 {
     |s: Self| {
         &&& s.ongoing_reconciles().contains_key(cr_key)
@@ -57,7 +57,7 @@ pub open spec fn reconciler_reconcile_error(cr_key: ObjectRef)
 
 pub open spec fn at_reconcile_state(key: ObjectRef, state: R::T) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == K::kind()
 {
     |s: Self| {
         &&& s.ongoing_reconciles().contains_key(key)
@@ -67,7 +67,7 @@ pub open spec fn at_reconcile_state(key: ObjectRef, state: R::T) -> StatePred<Se
 
 pub open spec fn at_expected_reconcile_states(key: ObjectRef, expected_states: spec_fn(R::T) -> bool) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind()
+        key.kind == K::kind()
 {
     |s: Self| {
         &&& s.ongoing_reconciles().contains_key(key)
@@ -96,7 +96,7 @@ pub open spec fn no_pending_req_msg(s: Self, key: ObjectRef) -> bool {
 
 pub open spec fn pending_req_in_flight_at_reconcile_state(key: ObjectRef, state: spec_fn(R::T) -> bool) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         Self::at_expected_reconcile_states(key, state)(s)
@@ -135,7 +135,7 @@ pub open spec fn pending_req_in_flight_or_resp_in_flight_at_reconcile_state(
     key: ObjectRef, state: spec_fn(R::T) -> bool
 ) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         Self::at_expected_reconcile_states(key, state)(s)
@@ -155,7 +155,7 @@ pub open spec fn no_pending_req_msg_at_reconcile_state(
     key: ObjectRef, state: spec_fn(R::T) -> bool
 ) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         Self::at_expected_reconcile_states(key, state)(s)
@@ -167,7 +167,7 @@ pub open spec fn resp_in_flight_matches_pending_req_at_reconcile_state(
     key: ObjectRef, state: spec_fn(R::T) -> bool
 ) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         Self::at_expected_reconcile_states(key, state)(s)
@@ -84,7 +84,7 @@ pub proof fn lemma_pre_leads_to_post_by_schedule_controller_reconcile_borrow_fro
 pub proof fn lemma_reconcile_done_leads_to_reconcile_idle(spec: TempPred<Self>, cr_key: ObjectRef)
     requires
         K::kind().is_CustomResourceKind(),
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
         spec.entails(always(lift_action(Self::next()))),
         spec.entails(tla_forall(|i| Self::controller_next().weak_fairness(i))),
     ensures spec.entails(lift_state(Self::reconciler_reconcile_done(cr_key)).leads_to(lift_state(|s: Self| { !s.ongoing_reconciles().contains_key(cr_key)}))),
@@ -104,7 +104,7 @@ pub proof fn lemma_reconcile_error_leads_to_reconcile_idle
 (spec: TempPred<Self>, cr_key: ObjectRef)
     requires
         K::kind().is_CustomResourceKind(),
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
         spec.entails(always(lift_action(Self::next()))),
         spec.entails(tla_forall(|i| Self::controller_next().weak_fairness(i))),
     ensures spec.entails(lift_state(Self::reconciler_reconcile_error(cr_key)).leads_to(lift_state(|s: Self| { !s.ongoing_reconciles().contains_key(cr_key) }))),
@@ -122,7 +122,7 @@ pub proof fn lemma_reconcile_idle_and_scheduled_leads_to_reconcile_init
 (spec: TempPred<Self>, cr_key: ObjectRef)
     requires
         K::kind().is_CustomResourceKind(),
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
         spec.entails(always(lift_action(Self::next()))),
         spec.entails(always(lift_state(Self::crash_disabled()))),
         spec.entails(tla_forall(|i| Self::controller_next().weak_fairness(i))),
@@ -155,7 +155,7 @@ pub proof fn lemma_true_leads_to_reconcile_scheduled_by_assumption(
 )
     requires
         K::kind().is_CustomResourceKind(),
-        cr_key.kind.is_CustomResourceKind(),
+        cr_key.kind == K::kind(),
         spec.entails(always(lift_state(|s: Self| {
             &&& s.resources().contains_key(cr_key)
             &&& K::unmarshal(s.resources()[cr_key]).is_Ok()
@@ -181,15 +181,15 @@ pub proof fn lemma_true_leads_to_reconcile_scheduled_by_assumption(
     entails_and_temp(spec, always(lift_state(|s: Self| {
         &&& s.resources().contains_key(cr_key)
         &&& K::unmarshal(s.resources()[cr_key]).is_Ok()
-    })), always(lift_state(|s: Self| { cr_key.kind.is_CustomResourceKind() })));
+    })), always(lift_state(|s: Self| { cr_key.kind == K::kind() })));
     always_and_equality(lift_state(|s: Self| {
         &&& s.resources().contains_key(cr_key)
         &&& K::unmarshal(s.resources()[cr_key]).is_Ok()
-    }), lift_state(|s: Self| { cr_key.kind.is_CustomResourceKind() }));
+    }), lift_state(|s: Self| { cr_key.kind == K::kind() }));
     temp_pred_equality(lift_state(|s: Self| {
         &&& s.resources().contains_key(cr_key)
         &&& K::unmarshal(s.resources()[cr_key]).is_Ok()
-    }).and(lift_state(|s: Self| { cr_key.kind.is_CustomResourceKind() })), lift_state(pre).implies(lift_state(Self::schedule_controller_reconcile().pre(cr_key))));
+    }).and(lift_state(|s: Self| { cr_key.kind == K::kind() })), lift_state(pre).implies(lift_state(Self::schedule_controller_reconcile().pre(cr_key))));
 
     Self::schedule_controller_reconcile().wf1(cr_key, spec, next_and_cr_exists, pre, post);
 }
@@ -458,6 +458,14 @@ pub proof fn lemma_from_pending_req_in_flight_at_some_state_to_in_flight_resp_ma
                             assert(pre_1(s_prime));
                         }
                     }
+                    Step::ServeReadFromCacheStep(input) => {
+                        // A (possibly stale) response served by the cache also matches the pending request.
+                        if input == req_msg {
+                            assert(post_1(s_prime));
+                        } else {
+                            assert(pre_1(s_prime));
+                        }
+                    }
                     Step::ControllerStep(input) => { assert(pre_1(s_prime)); },
                     _ => { assert(pre_1(s_prime)); }
                 }
@@ -4,6 +4,7 @@
 use crate::external_api::spec::ExternalAPI;
 use crate::kubernetes_api_objects::spec::{api_method::*, common::*, resource::*};
 use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::{handle_get_request, handle_list_request},
     cluster::*,
     cluster_state_machine::Step,
     controller::types::{ControllerAction, ControllerActionInput},
@@ -22,7 +23,7 @@ pub open spec fn scheduled_cr_has_lower_uid_than_uid_counter() -> StatePred<Self
     |s: Self| {
         forall |key: ObjectRef|
         #[trigger] s.controller_state.scheduled_reconciles.contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == K::kind()
         ==> s.controller_state.scheduled_reconciles[key].metadata().uid.is_Some()
         && s.controller_state.scheduled_reconciles[key].metadata().uid.get_Some_0() < s.kubernetes_api_state.uid_counter
     }
@@ -52,7 +53,7 @@ pub open spec fn triggering_cr_has_lower_uid_than_uid_counter() -> StatePred<Sel
     |s: Self| {
         forall |key: ObjectRef|
         #[trigger] s.ongoing_reconciles().contains_key(key)
-        && key.kind.is_CustomResourceKind()
+        && key.kind == K::kind()
         ==> s.ongoing_reconciles()[key].triggering_cr.metadata().uid.is_Some()
         && s.ongoing_reconciles()[key].triggering_cr.metadata().uid.get_Some_0() < s.kubernetes_api_state.uid_counter
     }
@@ -137,6 +138,21 @@ pub proof fn lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_s
                         }
                     }
                 }
+                Step::ServeReadFromCacheStep(input) => {
+                    if input == s.ongoing_reconciles()[key].pending_req_msg.get_Some_0() {
+                        let cached_api_server_state = s.cache_state.as_api_server_state(s.kubernetes_api_state);
+                        let resp_msg = if input.content.is_get_request() {
+                            Message::form_get_resp_msg(input, handle_get_request(input.content.get_get_request(), cached_api_server_state))
+                        } else {
+                            Message::form_list_resp_msg(input, handle_list_request(input.content.get_list_request(), cached_api_server_state))
+                        };
+                        assert(s_prime.in_flight().contains(resp_msg));
+                    } else {
+                        if !s.in_flight().contains(s.ongoing_reconciles()[key].pending_req_msg.get_Some_0()) {
+                            assert(s_prime.in_flight().contains(resp));
+                        }
+                    }
+                }
                 Step::ControllerStep(input) => {
                     let cr_key = input.1.get_Some_0();
                     if cr_key != key {
@@ -149,7 +165,7 @@ pub proof fn lemma_always_pending_req_in_flight_or_resp_in_flight_at_reconcile_s
                         assert(s_prime.in_flight().contains(s_prime.ongoing_reconciles()[key].pending_req_msg.get_Some_0()));
                     }
                 }
-                Step::ClientStep() => {
+                Step::ClientStep(_) => {
                     if s.in_flight().contains(s.ongoing_reconciles()[key].pending_req_msg.get_Some_0()) {
                         assert(s_prime.in_flight().contains(s_prime.ongoing_reconciles()[key].pending_req_msg.get_Some_0()));
                     } else {
@@ -200,6 +216,55 @@ pub proof fn lemma_always_no_pending_req_msg_at_reconcile_state(spec: TempPred<S
     init_invariant(spec, Self::init(), Self::next(), invariant);
 }
 
+// The controller runs reconciles of different keys concurrently, and ongoing_reconciles holds at most one
+// ongoing reconcile for each key. This predicate says that the ongoing reconcile of key is never preempted
+// by another reconcile of the same key: a reconcile of key starts from the initial state and, as long as it is ongoing,
+// it keeps the triggering cr it started with, so at most one reconcile of key is in flight at any time.
+// The shim layer provides the same guarantee: reconcile_with claims the key in InFlightReconciles
+// (see crate::shim_layer::worker_pool) before it reconciles the cr and releases the key when it returns,
+// and defers any other reconcile of the key in the meantime, no matter how many reconciles run at the same time.
+pub open spec fn ongoing_reconcile_of_key_is_not_preempted(key: ObjectRef) -> ActionPred<Self> {
+    |s: Self, s_prime: Self| {
+        &&& !s.ongoing_reconciles().contains_key(key) && s_prime.ongoing_reconciles().contains_key(key)
+            ==> s_prime.ongoing_reconciles()[key].local_state == R::reconcile_init_state()
+                && s_prime.ongoing_reconciles()[key].pending_req_msg.is_None()
+        &&& s.ongoing_reconciles().contains_key(key) && s_prime.ongoing_reconciles().contains_key(key)
+            ==> s_prime.ongoing_reconciles()[key].triggering_cr == s.ongoing_reconciles()[key].triggering_cr
+    }
+}
+
+pub proof fn lemma_always_ongoing_reconcile_of_key_is_not_preempted(spec: TempPred<Self>, key: ObjectRef)
+    requires spec.entails(always(lift_action(Self::next()))),
+    ensures spec.entails(always(lift_action(Self::ongoing_reconcile_of_key_is_not_preempted(key)))),
+{
+    let not_preempted = Self::ongoing_reconcile_of_key_is_not_preempted(key);
+    assert forall |s, s_prime: Self| #[trigger] Self::next()(s, s_prime) implies not_preempted(s, s_prime) by {
+        let next_step = choose |step| Self::next_step(s, s_prime, step);
+        match next_step {
+            Step::ControllerStep(input) => {
+                let cr_key = input.1.get_Some_0();
+                if cr_key == key && s.ongoing_reconciles().contains_key(key) && s_prime.ongoing_reconciles().contains_key(key) {
+                    // Neither run_scheduled_reconcile nor end_reconcile can lead to this case,
+                    // and continue_reconcile keeps the triggering cr
+                    assert(s_prime.ongoing_reconciles()[key].triggering_cr == s.ongoing_reconciles()[key].triggering_cr);
+                }
+            }
+            Step::RestartController() => {
+                assert(!s_prime.ongoing_reconciles().contains_key(key));
+            }
+            _ => {
+                assert(s.ongoing_reconciles() == s_prime.ongoing_reconciles());
+            }
+        }
+    }
+    assert forall |ex| #[trigger] lift_action(Self::next()).implies(lift_action(not_preempted)).satisfied_by(ex) by {
+        if lift_action(Self::next()).satisfied_by(ex) {
+            assert(Self::next()(ex.head(), ex.head_next()));
+        }
+    }
+    always_weaken_temp(spec, lift_action(Self::next()), lift_action(not_preempted));
+}
+
 }
 
 }
@@ -400,12 +400,15 @@ proof fn daemon_set_not_exist_or_updated_or_pending_update_status_requests_num_d
             Step::ControllerStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
-            Step::ClientStep() => {
+            Step::ClientStep(_) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
             Step::ExternalAPIStep(input) => {
                 assert(pending_req_multiset =~= pending_req_multiset_prime);
             },
+            Step::ServeReadFromCacheStep(input) => {
+                assert(pending_req_multiset =~= pending_req_multiset_prime);
+            },
             _ => {}
         }
     }
@@ -51,6 +51,10 @@ pub proof fn lemma_always_every_in_flight_msg_has_lower_id_than_allocator(spec:
                         assert(s.in_flight().contains(input.0));
                         assert(id == input.0.content.get_rest_id());
                     },
+                    Step::ServeReadFromCacheStep(input) => {
+                        assert(s.in_flight().contains(input));
+                        assert(id == input.content.get_rest_id());
+                    },
                     Step::ExternalAPIStep(_) => {},
                     _ => {},
                 }
@@ -284,6 +288,16 @@ pub proof fn lemma_always_every_in_flight_msg_has_unique_id(spec: TempPred<Self>
                                 assert(s_prime.in_flight().count(msg) == 1);
                             }
                         },
+                        Step::ServeReadFromCacheStep(input) => {
+                            let req = input;
+                            assert(s.in_flight().contains(req));
+                            if s.in_flight().contains(msg) {
+                                assert(s.in_flight().count(msg) == 1);
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            } else {
+                                assert(s_prime.in_flight().count(msg) == 1);
+                            }
+                        },
                         _ => {
                             if (s.in_flight().contains(msg)) {
                                 assert(s.in_flight().count(msg) == 1);
@@ -337,6 +351,11 @@ proof fn newly_added_msg_have_different_id_from_existing_ones(
                 assert(s.network_state.in_flight.count(req_msg) <= 1);
                 assert(msg_1.content.get_rest_id() != msg_2.content.get_rest_id());
             }
+            Step::ServeReadFromCacheStep(input) => {
+                let req_msg = input;
+                assert(s.network_state.in_flight.count(req_msg) <= 1);
+                assert(msg_1.content.get_rest_id() != msg_2.content.get_rest_id());
+            }
             _ => assert(false),
         }
     } else if msg_2.content.is_ExternalAPIResponse() {
@@ -418,11 +437,14 @@ pub proof fn lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spe
     let next = |s, s_prime| {
         &&& Self::next()(s, s_prime)
         &&& Self::each_object_in_etcd_is_well_formed()(s)
+        &&& Self::stale_read_disabled()(s)
     };
     Self::lemma_always_each_object_in_etcd_is_well_formed(spec);
+    Self::lemma_always_stale_read_disabled(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(Self::next()),
-        lift_state(Self::each_object_in_etcd_is_well_formed())
+        lift_state(Self::each_object_in_etcd_is_well_formed()),
+        lift_state(Self::stale_read_disabled())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| s_prime.in_flight().contains(msg) && #[trigger] Self::is_ok_get_response_msg()(msg) implies
@@ -432,6 +454,9 @@ pub proof fn lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spe
             if s.in_flight().contains(msg) {
                 assert(s.kubernetes_api_state.resource_version_counter <= s_prime.kubernetes_api_state.resource_version_counter);
             } else {
+                // The cache never answers the request since stale reads are disabled,
+                // so the new ok get response must come from the Kubernetes API.
+                assert(step.is_ApiServerStep());
                 let input = step.get_ApiServerStep_0().get_Some_0();
                 match input.content.get_APIRequest_0() {
                     APIRequest::GetRequest(req) => {
@@ -473,12 +498,15 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spe
         &&& Self::next()(s, s_prime)
         &&& Self::each_object_in_etcd_is_well_formed()(s)
         &&& Self::object_in_ok_get_response_has_smaller_rv_than_etcd()(s)
+        &&& Self::stale_read_disabled()(s)
     };
     Self::lemma_always_each_object_in_etcd_is_well_formed(spec);
     Self::lemma_always_object_in_ok_get_response_has_smaller_rv_than_etcd(spec);
+    Self::lemma_always_stale_read_disabled(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(Self::next()), lift_state(Self::each_object_in_etcd_is_well_formed()),
-        lift_state(Self::object_in_ok_get_response_has_smaller_rv_than_etcd())
+        lift_state(Self::object_in_ok_get_response_has_smaller_rv_than_etcd()),
+        lift_state(Self::stale_read_disabled())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| #[trigger] s_prime.in_flight().contains(msg) && Self::is_ok_get_response_msg_and_matches_key(key)(msg) && s_prime.resources().contains_key(key)
@@ -499,6 +527,8 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spe
                     APIRequest::DeleteRequest(_) => {}
                     APIRequest::UpdateRequest(_) => {}
                     APIRequest::UpdateStatusRequest(_) => {}
+                    APIRequest::ApplyRequest(_) => {}
+                    APIRequest::PatchRequest(_) => {}
                 }
                 assert(msg == Self::handle_get_request_msg(req, s.kubernetes_api_state).1);
                 assert(s.resources().contains_key(req.content.get_get_request().key));
@@ -514,7 +544,7 @@ pub proof fn lemma_always_object_in_ok_get_resp_is_same_as_etcd_with_same_rv(spe
 
 pub open spec fn key_of_object_in_matched_ok_get_resp_message_is_same_as_key_of_pending_req(key: ObjectRef) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         forall |msg: MsgType<E>|
@@ -529,7 +559,7 @@ pub open spec fn key_of_object_in_matched_ok_get_resp_message_is_same_as_key_of_
 
 pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_as_key_of_pending_req(spec: TempPred<Self>, key: ObjectRef)
     requires
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
         spec.entails(lift_state(Self::init())),
         spec.entails(always(lift_action(Self::next()))),
     ensures spec.entails(always(lift_state(Self::key_of_object_in_matched_ok_get_resp_message_is_same_as_key_of_pending_req(key)))),
@@ -541,16 +571,19 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_a
         &&& Self::every_in_flight_msg_has_lower_id_than_allocator()(s)
         &&& Self::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(key)(s)
         &&& Self::every_in_flight_msg_has_unique_id()(s)
+        &&& Self::stale_read_disabled()(s)
     };
     Self::lemma_always_each_object_in_etcd_is_well_formed(spec);
     Self::lemma_always_every_in_flight_msg_has_lower_id_than_allocator(spec);
     Self::lemma_always_every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(spec, key);
     Self::lemma_always_every_in_flight_msg_has_unique_id(spec);
+    Self::lemma_always_stale_read_disabled(spec);
     combine_spec_entails_always_n!(
         spec, lift_action(next), lift_action(Self::next()), lift_state(Self::each_object_in_etcd_is_well_formed()),
         lift_state(Self::every_in_flight_msg_has_lower_id_than_allocator()),
         lift_state(Self::every_in_flight_req_msg_has_different_id_from_pending_req_msg_of(key)),
-        lift_state(Self::every_in_flight_msg_has_unique_id())
+        lift_state(Self::every_in_flight_msg_has_unique_id()),
+        lift_state(Self::stale_read_disabled())
     );
     assert forall |s, s_prime| inv(s) && #[trigger] next(s, s_prime) implies inv(s_prime) by {
         assert forall |msg| #[trigger] s_prime.in_flight().contains(msg) && Self::is_ok_get_response_msg()(msg) && s_prime.ongoing_reconciles().contains_key(key)
@@ -609,7 +642,7 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_get_resp_message_is_same_a
 
 pub open spec fn key_of_object_in_matched_ok_update_resp_message_is_same_as_key_of_pending_req(key: ObjectRef) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         forall |msg: MsgType<E>|
@@ -624,7 +657,7 @@ pub open spec fn key_of_object_in_matched_ok_update_resp_message_is_same_as_key_
 
 pub proof fn lemma_always_key_of_object_in_matched_ok_update_resp_message_is_same_as_key_of_pending_req(spec: TempPred<Self>, key: ObjectRef)
     requires
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
         spec.entails(lift_state(Self::init())),
         spec.entails(always(lift_action(Self::next()))),
     ensures spec.entails(always(lift_state(Self::key_of_object_in_matched_ok_update_resp_message_is_same_as_key_of_pending_req(key)))),
@@ -688,6 +721,14 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_update_resp_message_is_sam
                     }
                     assert(Self::is_ok_update_response_msg_and_matches_key(req_key)(msg));
                 },
+                Step::ServeReadFromCacheStep(input) => {
+                    assert(s.ongoing_reconciles()[key] == s_prime.ongoing_reconciles()[key]);
+                    if !s.in_flight().contains(msg) {
+                        assert(msg.content.is_get_response() || msg.content.is_list_response());
+                        assert(!msg.content.is_update_response());
+                    }
+                    assert(s.in_flight().contains(msg));
+                },
                 Step::ExternalAPIStep(input) => {
                     assert(input.get_Some_0() != msg);
                     assert(s.in_flight().contains(msg));
@@ -704,7 +745,7 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_update_resp_message_is_sam
 
 pub open spec fn key_of_object_in_matched_ok_create_resp_message_is_same_as_key_of_pending_req(key: ObjectRef) -> StatePred<Self>
     recommends
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
 {
     |s: Self| {
         let pending_req = s.ongoing_reconciles()[key].pending_req_msg.get_Some_0();
@@ -722,7 +763,7 @@ pub open spec fn key_of_object_in_matched_ok_create_resp_message_is_same_as_key_
 
 pub proof fn lemma_always_key_of_object_in_matched_ok_create_resp_message_is_same_as_key_of_pending_req(spec: TempPred<Self>, key: ObjectRef)
     requires
-        key.kind.is_CustomResourceKind(),
+        key.kind == K::kind(),
         spec.entails(lift_state(Self::init())),
         spec.entails(always(lift_action(Self::next()))),
     ensures spec.entails(always(lift_state(Self::key_of_object_in_matched_ok_create_resp_message_is_same_as_key_of_pending_req(key)))),
@@ -791,6 +832,14 @@ pub proof fn lemma_always_key_of_object_in_matched_ok_create_resp_message_is_sam
                     }
                     assert(Self::is_ok_create_response_msg_and_matches_key(req_key)(msg));
                 },
+                Step::ServeReadFromCacheStep(input) => {
+                    assert(s.ongoing_reconciles()[key] == s_prime.ongoing_reconciles()[key]);
+                    if !s.in_flight().contains(msg) {
+                        assert(msg.content.is_get_response() || msg.content.is_list_response());
+                        assert(!msg.content.is_create_response());
+                    }
+                    assert(s.in_flight().contains(msg));
+                },
                 Step::ExternalAPIStep(input) => {
                     assert(input.get_Some_0() != msg);
                     assert(s.in_flight().contains(msg));
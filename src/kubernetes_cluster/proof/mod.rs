@@ -12,6 +12,7 @@ pub mod controller_runtime_safety;
 pub mod daemon_set_controller;
 pub mod external_api_liveness;
 pub mod message;
+pub mod multi_cluster;
 pub mod stateful_set_controller;
 pub mod validation_rule;
 pub mod wf1_assistant;
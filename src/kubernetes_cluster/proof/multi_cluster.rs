@@ -0,0 +1,1327 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::ExternalAPI;
+use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, marshal::*, resource::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::*,
+    builtin_controllers::types::BuiltinControllerChoice,
+    cache::types::CacheState,
+    client::{state_machine::*, types::*},
+    cluster::*,
+    cluster_state_machine::Step,
+    controller::types::{ControllerActionInput, ControllerActionOutput, ControllerState},
+    message::*,
+    multi_cluster::{state_machine::*, types::*},
+    network::types::NetworkState,
+};
+use crate::reconciler::spec::{io::*, reconciler::Reconciler};
+use crate::state_machine::action::*;
+use crate::state_machine::state_machine::*;
+use crate::temporal_logic::defs::*;
+use crate::temporal_logic::rules::*;
+use crate::vstd_ext::multiset_lib::*;
+use vstd::{multiset::*, prelude::*};
+
+verus! {
+
+// The API server validates the objects of every custom resource T against the schema in the CRD of T,
+// which is generated from T. So, if the CRD of T is installed in the cluster, the uninterpreted validation
+// functions of custom resources agree with the unmarshal and validation functions of T for the objects of T.
+//
+// This is a fact about the CRDs installed in the cluster, not something that holds for every T,
+// so it is not an axiom: it is required by lemma_lift_to_multi_cluster for each custom resource hosted in the model.
+pub open spec fn custom_resource_validation_agrees_with<T: CustomResourceView>() -> bool {
+    &&& T::kind().is_CustomResourceKind()
+    &&& forall |v: Value| #[trigger] unmarshallable_custom_resource_spec(T::kind().get_CustomResourceKind_0(), v) == T::unmarshal_spec(v).is_Ok()
+    &&& forall |v: Value| #[trigger] unmarshallable_custom_resource_status(T::kind().get_CustomResourceKind_0(), v) == T::unmarshal_status(v).is_Ok()
+    &&& forall |obj: DynamicObjectView| obj.kind == T::kind()
+        ==> #[trigger] valid_custom_resource(T::kind().get_CustomResourceKind_0(), obj) == T::unmarshal(obj).get_Ok_0().state_validation()
+    &&& forall |obj: DynamicObjectView, old_obj: DynamicObjectView| obj.kind == T::kind()
+        ==> #[trigger] valid_custom_resource_transition(T::kind().get_CustomResourceKind_0(), obj, old_obj) == T::unmarshal(obj).get_Ok_0().transition_validation(T::unmarshal(old_obj).get_Ok_0())
+    &&& marshalled_default_custom_resource_status(T::kind().get_CustomResourceKind_0()) == T::marshal_status(T::default().status())
+}
+
+pub open spec fn swap_client_and_controller(host: HostId) -> HostId {
+    match host {
+        HostId::CustomController => HostId::Client,
+        HostId::Client => HostId::CustomController,
+        _ => host,
+    }
+}
+
+pub open spec fn owned_by_other<I, O>(owners: Map<RestId, ControllerId>, id: ControllerId, msg: Message<I, O>) -> bool {
+    &&& owners.contains_key(msg.content.get_rest_id())
+    &&& owners[msg.content.get_rest_id()] != id
+}
+
+/// relabel turns the messages of the other controllers (whose RestIds are owned by a controller other than id)
+/// into the messages of the client, and the other way around. Each step of the other controllers
+/// looks like a step of the client to controller id (see Cluster::is_client_like).
+///
+/// relabel is an involution (see lemma_relabel_is_involution), so projecting the in-flight messages
+/// with it loses nothing.
+pub open spec fn relabel<I, O>(owners: Map<RestId, ControllerId>, id: ControllerId, msg: Message<I, O>) -> Message<I, O> {
+    if owned_by_other(owners, id, msg) {
+        Message {
+            src: swap_client_and_controller(msg.src),
+            dst: swap_client_and_controller(msg.dst),
+            content: msg.content,
+        }
+    } else {
+        msg
+    }
+}
+
+pub proof fn lemma_relabel_is_involution<I, O>(owners: Map<RestId, ControllerId>, id: ControllerId)
+    ensures forall |msg: Message<I, O>| #[trigger] relabel(owners, id, relabel(owners, id, msg)) == msg,
+{
+    assert forall |msg: Message<I, O>| #[trigger] relabel(owners, id, relabel(owners, id, msg)) == msg by {
+        if owned_by_other(owners, id, msg) {
+            assert(relabel(owners, id, msg).content == msg.content);
+        }
+    }
+}
+
+impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+
+/// The responses received by the other controllers. The client of Cluster never receives any message,
+/// so these responses stay in flight when seen by controller id.
+pub open spec fn received_by_others(s: MultiCluster<E>, id: ControllerId) -> Multiset<MsgType<E>> {
+    s.received.filter(|msg: MsgType<E>| owned_by_other(s.rest_id_owners, id, msg))
+}
+
+pub open spec fn projected_in_flight(s: MultiCluster<E>, id: ControllerId) -> Multiset<MsgType<E>> {
+    map_by_involution(
+        s.in_flight().add(Self::received_by_others(s, id)),
+        |msg: MsgType<E>| relabel(s.rest_id_owners, id, msg)
+    )
+}
+
+/// project returns the Cluster seen by the hosted controller id: the controller state is the one of controller id,
+/// the other controllers are seen as the client, and everything else is shared.
+pub open spec fn project(s: MultiCluster<E>, id: ControllerId) -> Self {
+    Cluster {
+        kubernetes_api_state: s.kubernetes_api_state,
+        controller_state: Self::unmarshal_controller_state(s.controller_states[id]),
+        client_state: s.client_state,
+        network_state: NetworkState {
+            in_flight: Self::projected_in_flight(s, id),
+        },
+        external_api_state: s.external_api_state,
+        rest_id_allocator: s.rest_id_allocator,
+        crash_enabled: s.crash_enabled,
+        transient_failure_enabled: s.transient_failure_enabled,
+        cache_state: CacheState {
+            resources: Map::empty(),
+        },
+        stale_read_enabled: false,
+    }
+}
+
+pub open spec fn project_ex(ex: Execution<MultiCluster<E>>, id: ControllerId) -> Execution<Self> {
+    Execution {
+        nat_to_state: |i: nat| Self::project((ex.nat_to_state)(i), id),
+    }
+}
+
+/// lift_to_multi_cluster turns a temporal predicate of Cluster into a temporal predicate of MultiCluster
+/// which holds if the projected execution satisfies the original predicate.
+pub open spec fn lift_to_multi_cluster(p: TempPred<Self>, id: ControllerId) -> TempPred<MultiCluster<E>> {
+    TempPred::new(|ex: Execution<MultiCluster<E>>| p.satisfied_by(Self::project_ex(ex, id)))
+}
+
+/// The controller of this Cluster is hosted as controller id in the MultiCluster,
+/// and the MultiCluster runs the same API server, built-in controllers, external system and network.
+pub open spec fn is_hosted_by(model: MultiClusterModel<E>, id: ControllerId) -> bool {
+    &&& model.controllers.contains_key(id)
+    &&& model.controllers[id] == Self::controller_model()
+    &&& model.kubernetes_api == Self::kubernetes_api()
+    &&& model.builtin_controllers == Self::builtin_controllers()
+    &&& model.external_api == Self::external_api()
+    &&& model.network == Self::network()
+}
+
+/// A hosted controller is client-like if each of its steps either sends nothing without allocating any RestId,
+/// or sends one request that the client of this Cluster could send (see client_can_send_request)
+/// with the next RestId.
+pub open spec fn is_client_like(controller: ControllerModel<E>) -> bool {
+    forall |input: ControllerActionInput<E>, s: ErasedControllerState|
+        #[trigger] (controller.next)(input, s).is_Some()
+        ==> {
+            let output = (controller.next)(input, s).get_Some_0().1;
+            ||| {
+                &&& output.send == Multiset::<MsgType<E>>::empty()
+                &&& output.rest_id_allocator == input.rest_id_allocator
+            }
+            ||| exists |req: APIRequest| {
+                &&& #[trigger] client_can_send_request(|obj: DynamicObjectView| Self::client_expects(obj), req)
+                &&& output.send == Multiset::singleton(Message::controller_req_msg(req, input.rest_id_allocator.allocate().1))
+                &&& output.rest_id_allocator == input.rest_id_allocator.allocate().0
+            }
+        }
+}
+
+/// The other hosted controllers (and the users of the MultiCluster) do not interfere with controller id:
+/// to controller id, they look like the client of this Cluster. That is, the users only manage the objects
+/// that the client of this Cluster manages, and every other controller is client-like.
+///
+/// This is the proof obligation for running controller id together with other controllers,
+/// which corresponds to the no_one_interferes_producer invariant in soundness::compositionality.
+/// A controller proves it for another by checking the requests returned by the reconcile_core
+/// of the other controller (see lemma_controller_model_is_client_like).
+pub open spec fn other_controllers_do_not_interfere(model: MultiClusterModel<E>, id: ControllerId) -> bool {
+    &&& forall |obj: DynamicObjectView| #[trigger] (model.client_expects)(obj) ==> Self::client_expects(obj)
+    &&& forall |other_id: ControllerId|
+        #[trigger] model.controllers.contains_key(other_id) && other_id != id
+        ==> Self::is_client_like(model.controllers[other_id])
+}
+
+/// A controller of another Cluster is client-like if its reconcile_core only sends the requests
+/// that the client of this Cluster can send.
+pub proof fn lemma_controller_model_is_client_like<K2: CustomResourceView, R2: Reconciler<K2, E>>()
+    requires
+        forall |cr: K2, resp_o: Option<ResponseView<E::Output>>, state: R2::T|
+            #![trigger R2::reconcile_core(cr, resp_o, state)]
+            R2::reconcile_core(cr, resp_o, state).1.is_Some()
+            ==> R2::reconcile_core(cr, resp_o, state).1.get_Some_0().is_KRequest()
+                && client_can_send_request(|obj: DynamicObjectView| Self::client_expects(obj), R2::reconcile_core(cr, resp_o, state).1.get_Some_0().get_KRequest_0()),
+    ensures Self::is_client_like(Cluster::<K2, E, R2>::controller_model()),
+{
+    let controller = Cluster::<K2, E, R2>::controller();
+    assert forall |input: ControllerActionInput<E>, s: ErasedControllerState|
+        #[trigger] (Cluster::<K2, E, R2>::controller_model().next)(input, s).is_Some()
+    implies {
+        let output = (Cluster::<K2, E, R2>::controller_model().next)(input, s).get_Some_0().1;
+        ||| {
+            &&& output.send == Multiset::<MsgType<E>>::empty()
+            &&& output.rest_id_allocator == input.rest_id_allocator
+        }
+        ||| exists |req: APIRequest| {
+            &&& #[trigger] client_can_send_request(|obj: DynamicObjectView| Self::client_expects(obj), req)
+            &&& output.send == Multiset::singleton(Message::controller_req_msg(req, input.rest_id_allocator.allocate().1))
+            &&& output.rest_id_allocator == input.rest_id_allocator.allocate().0
+        }
+    } by {
+        let controller_state = Cluster::<K2, E, R2>::unmarshal_controller_state(s);
+        let step = choose |step| (#[trigger] (controller.step_to_action)(step).precondition)((controller.action_input)(step, input), controller_state);
+        let output = controller.next_result(input, controller_state).get_Enabled_1();
+        if step.is_ContinueReconcile() {
+            let cr_key = input.scheduled_cr_key.get_Some_0();
+            let reconcile_state = controller_state.ongoing_reconciles[cr_key];
+            let resp_o = if input.recv.is_Some() {
+                if input.recv.get_Some_0().content.is_APIResponse() {
+                    Some(ResponseView::KResponse(input.recv.get_Some_0().content.get_APIResponse_0()))
+                } else {
+                    Some(ResponseView::ExternalResponse(input.recv.get_Some_0().content.get_ExternalAPIResponse_0()))
+                }
+            } else {
+                None
+            };
+            let req_o = R2::reconcile_core(reconcile_state.triggering_cr, resp_o, reconcile_state.local_state).1;
+            if req_o.is_Some() {
+                let req = req_o.get_Some_0().get_KRequest_0();
+                assert(client_can_send_request(|obj: DynamicObjectView| Self::client_expects(obj), req));
+                assert(output.send == Multiset::singleton(Message::controller_req_msg(req, input.rest_id_allocator.allocate().1)));
+            }
+        }
+    }
+}
+
+// The API server and the built-in controllers behave the same no matter which custom controller
+// the Cluster is instantiated with, as long as the objects of the custom resources of both Clusters are
+// validated by their CRDs (see custom_resource_validation_agrees_with). The built-in controllers, the external system
+// and the network never look into the custom resources. So the same MultiClusterModel can host controllers
+// of different Clusters.
+pub proof fn lemma_shared_components_do_not_depend_on_controller<K2: CustomResourceView, R2: Reconciler<K2, E>>()
+    requires
+        custom_resource_validation_agrees_with::<K>(),
+        custom_resource_validation_agrees_with::<K2>(),
+    ensures
+        Self::kubernetes_api() == Cluster::<K2, E, R2>::kubernetes_api(),
+        Self::builtin_controllers() == Cluster::<K2, E, R2>::builtin_controllers(),
+        Self::external_api() == Cluster::<K2, E, R2>::external_api(),
+        Self::network() == Cluster::<K2, E, R2>::network(),
+{
+    lemma_object_checks_do_not_depend_on_custom_resource::<K, K2>();
+    assert forall |msg: MsgType<E>, s: ApiServerState|
+        #[trigger] Self::transition_by_etcd(msg, s) == Cluster::<K2, E, R2>::transition_by_etcd(msg, s) by {
+        if msg.content.is_APIRequest() {
+            match msg.content.get_APIRequest_0() {
+                APIRequest::CreateRequest(req) => {
+                    lemma_handle_create_request_does_not_depend_on_custom_resource::<K, K2>(req, s);
+                }
+                APIRequest::UpdateRequest(req) => {
+                    lemma_handle_update_request_does_not_depend_on_custom_resource::<K, K2>(req, s);
+                }
+                APIRequest::UpdateStatusRequest(req) => {
+                    assert(update_status_request_admission_check::<K>(req, s) == update_status_request_admission_check::<K2>(req, s));
+                }
+                APIRequest::ApplyRequest(req) => {
+                    assert(apply_request_admission_check::<K>(req, s) == apply_request_admission_check::<K2>(req, s));
+                    lemma_handle_create_request_does_not_depend_on_custom_resource::<K, K2>(create_request_from_apply(req), s);
+                    if s.resources.contains_key(req.key) {
+                        lemma_handle_update_request_does_not_depend_on_custom_resource::<K, K2>(update_request_from_apply(req, s.resources[req.key]), s);
+                    }
+                }
+                APIRequest::PatchRequest(req) => {
+                    assert(patch_request_admission_check::<K>(req, s) == patch_request_admission_check::<K2>(req, s));
+                    if s.resources.contains_key(req.key) {
+                        lemma_handle_update_request_does_not_depend_on_custom_resource::<K, K2>(update_request_from_patch(req, s.resources[req.key]), s);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    assert(Self::handle_request().precondition =~= Cluster::<K2, E, R2>::handle_request().precondition);
+    assert(Self::handle_request().transition =~= Cluster::<K2, E, R2>::handle_request().transition);
+    assert(Self::kubernetes_api().actions =~= Cluster::<K2, E, R2>::kubernetes_api().actions);
+    assert(Self::kubernetes_api().init =~= Cluster::<K2, E, R2>::kubernetes_api().init);
+    assert(Self::kubernetes_api().step_to_action =~= Cluster::<K2, E, R2>::kubernetes_api().step_to_action);
+    assert(Self::kubernetes_api().action_input =~= Cluster::<K2, E, R2>::kubernetes_api().action_input);
+
+    // None of the built-in controllers depends on the custom resource.
+    assert(Self::run_garbage_collector() == Cluster::<K2, E, R2>::run_garbage_collector());
+    assert(Self::run_garbage_collector_orphan_dependent() == Cluster::<K2, E, R2>::run_garbage_collector_orphan_dependent());
+    assert(Self::run_garbage_collector_remove_orphan_finalizer() == Cluster::<K2, E, R2>::run_garbage_collector_remove_orphan_finalizer());
+    assert(Self::run_garbage_collector_foreground_dependent() == Cluster::<K2, E, R2>::run_garbage_collector_foreground_dependent());
+    assert(Self::run_garbage_collector_remove_foreground_finalizer() == Cluster::<K2, E, R2>::run_garbage_collector_remove_foreground_finalizer());
+    assert(Self::run_kubelet() == Cluster::<K2, E, R2>::run_kubelet());
+    assert(Self::run_stateful_set_controller() == Cluster::<K2, E, R2>::run_stateful_set_controller());
+    assert(Self::run_daemon_set_controller() == Cluster::<K2, E, R2>::run_daemon_set_controller());
+    assert(Self::run_stabilizer() == Cluster::<K2, E, R2>::run_stabilizer());
+    assert(Self::builtin_controllers().actions =~= Cluster::<K2, E, R2>::builtin_controllers().actions);
+    assert(Self::builtin_controllers().init =~= Cluster::<K2, E, R2>::builtin_controllers().init);
+    assert(Self::builtin_controllers().step_to_action =~= Cluster::<K2, E, R2>::builtin_controllers().step_to_action);
+    assert(Self::builtin_controllers().action_input =~= Cluster::<K2, E, R2>::builtin_controllers().action_input);
+
+    assert(Self::handle_external_request().precondition =~= Cluster::<K2, E, R2>::handle_external_request().precondition);
+    assert(Self::handle_external_request().transition =~= Cluster::<K2, E, R2>::handle_external_request().transition);
+    assert(Self::external_api().actions =~= Cluster::<K2, E, R2>::external_api().actions);
+    assert(Self::external_api().init =~= Cluster::<K2, E, R2>::external_api().init);
+    assert(Self::external_api().step_to_action =~= Cluster::<K2, E, R2>::external_api().step_to_action);
+    assert(Self::external_api().action_input =~= Cluster::<K2, E, R2>::external_api().action_input);
+
+    assert(Self::deliver().precondition =~= Cluster::<K2, E, R2>::deliver().precondition);
+    assert(Self::deliver().transition =~= Cluster::<K2, E, R2>::deliver().transition);
+    assert(Self::network().init =~= Cluster::<K2, E, R2>::network().init);
+}
+
+/// rest_ids_are_consistent is the invariant of the MultiCluster that makes the projection a Cluster execution:
+/// every RestId in use or owned by a controller has been allocated, every received message is a message
+/// to a controller that owns its RestId, and controller id owns the RestIds of its pending requests.
+pub open spec fn rest_ids_are_consistent(id: ControllerId) -> StatePred<MultiCluster<E>> {
+    |s: MultiCluster<E>| {
+        &&& forall |msg: MsgType<E>| #[trigger] s.in_flight().contains(msg)
+            ==> msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter
+        &&& forall |msg: MsgType<E>| #[trigger] s.received.contains(msg)
+            ==> {
+                &&& msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter
+                &&& msg.dst == HostId::CustomController
+                &&& s.rest_id_owners.contains_key(msg.content.get_rest_id())
+            }
+        &&& forall |rest_id: RestId| #[trigger] s.rest_id_owners.contains_key(rest_id)
+            ==> rest_id < s.rest_id_allocator.rest_id_counter
+        &&& forall |key: ObjectRef|
+            #[trigger] Self::unmarshal_controller_state(s.controller_states[id]).ongoing_reconciles.contains_key(key)
+            && Self::unmarshal_controller_state(s.controller_states[id]).ongoing_reconciles[key].pending_req_msg.is_Some()
+            ==> {
+                let rest_id = Self::unmarshal_controller_state(s.controller_states[id]).ongoing_reconciles[key].pending_req_msg.get_Some_0().content.get_rest_id();
+                &&& s.rest_id_owners.contains_key(rest_id)
+                &&& s.rest_id_owners[rest_id] == id
+            }
+    }
+}
+
+pub proof fn lemma_projected_in_flight_count(s: MultiCluster<E>, id: ControllerId)
+    ensures
+        forall |msg: MsgType<E>| #[trigger] Self::projected_in_flight(s, id).count(msg)
+            == s.in_flight().count(relabel(s.rest_id_owners, id, msg)) + Self::received_by_others(s, id).count(relabel(s.rest_id_owners, id, msg)),
+{
+    lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    map_by_involution_count(
+        s.in_flight().add(Self::received_by_others(s, id)),
+        |msg: MsgType<E>| relabel(s.rest_id_owners, id, msg)
+    );
+}
+
+/// The message seen by controller id is in flight if it is sent to the API server or the external system,
+/// since the received responses are all sent to the controllers.
+pub proof fn lemma_projected_request_is_in_flight(s: MultiCluster<E>, id: ControllerId, msg: MsgType<E>)
+    requires
+        Self::rest_ids_are_consistent(id)(s),
+        Self::projected_in_flight(s, id).contains(msg),
+        msg.dst == HostId::ApiServer || msg.dst == HostId::ExternalAPI,
+    ensures
+        s.in_flight().contains(relabel(s.rest_id_owners, id, msg)),
+        relabel(s.rest_id_owners, id, msg).dst == msg.dst,
+        msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter,
+{
+    Self::lemma_projected_in_flight_count(s, id);
+    let relabeled = relabel(s.rest_id_owners, id, msg);
+    if Self::received_by_others(s, id).count(relabeled) > 0 {
+        assert(s.received.contains(relabeled));
+        assert(false);
+    }
+    assert(s.in_flight().contains(relabeled));
+}
+
+pub proof fn lemma_rest_ids_are_consistent_at_init(model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>)
+    requires
+        Self::is_hosted_by(model, id),
+        model.init()(s),
+    ensures Self::rest_ids_are_consistent(id)(s),
+{
+    Self::marshal_controller_state_preserves_integrity();
+    assert((model.controllers[id].init)(s.controller_states[id]));
+    assert(Self::unmarshal_controller_state(s.controller_states[id]) == Self::init_controller_state());
+}
+
+/// Each step of controller id, or of a client-like controller, allocates the RestIds of the messages it sends.
+pub proof fn lemma_controller_step_sends_allocated_rest_ids(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        model.next_step(s, s_prime, MultiClusterStep::ControllerStep(input)),
+    ensures
+        s.rest_id_allocator.rest_id_counter <= s_prime.rest_id_allocator.rest_id_counter,
+        forall |msg: MsgType<E>| #[trigger] s_prime.in_flight().contains(msg) && !s.in_flight().contains(msg)
+            ==> s.rest_id_allocator.rest_id_counter <= msg.content.get_rest_id() < s_prime.rest_id_allocator.rest_id_counter,
+        input.0 != id ==> s_prime.rest_id_allocator.rest_id_counter <= s.rest_id_allocator.rest_id_counter + 1,
+{
+    let controller_input = ControllerActionInput{recv: input.1, scheduled_cr_key: input.2, rest_id_allocator: s.rest_id_allocator};
+    let output = (model.controllers[input.0].next)(controller_input, s.controller_states[input.0]).get_Some_0().1;
+    assert(s_prime.in_flight() == (if input.1.is_Some() { s.in_flight().remove(input.1.get_Some_0()) } else { s.in_flight() }).add(output.send));
+    if input.0 != id {
+        assert(Self::is_client_like(model.controllers[input.0]));
+        assert((model.controllers[input.0].next)(controller_input, s.controller_states[input.0]).is_Some());
+    } else {
+        // The step of controller id is a step of Self::controller(), which sends at most one request with the next RestId.
+        let controller_state = Self::unmarshal_controller_state(s.controller_states[id]);
+        let controller = Self::controller();
+        let step = choose |step| (#[trigger] (controller.step_to_action)(step).precondition)((controller.action_input)(step, controller_input), controller_state);
+        if step.is_ContinueReconcile() {
+            assert forall |msg: MsgType<E>| #[trigger] output.send.contains(msg)
+            implies msg.content.get_rest_id() == s.rest_id_allocator.rest_id_counter by {}
+        }
+    }
+}
+
+pub proof fn lemma_rest_ids_are_consistent_is_preserved(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.next()(s, s_prime),
+    ensures
+        Self::rest_ids_are_consistent(id)(s_prime),
+        s.rest_id_allocator.rest_id_counter <= s_prime.rest_id_allocator.rest_id_counter,
+        forall |rest_id: RestId| rest_id < s.rest_id_allocator.rest_id_counter
+            ==> (#[trigger] s_prime.rest_id_owners.contains_key(rest_id) == s.rest_id_owners.contains_key(rest_id)
+                && s_prime.rest_id_owners[rest_id] == s.rest_id_owners[rest_id]),
+{
+    Self::marshal_controller_state_preserves_integrity();
+    let step = choose |step: MultiClusterStep<MsgType<E>>| model.next_step(s, s_prime, step);
+    let counter = s.rest_id_allocator.rest_id_counter;
+    match step {
+        MultiClusterStep::ControllerStep(input) => {
+            Self::lemma_controller_step_sends_allocated_rest_ids(model, id, s, s_prime, input);
+            if input.0 == id {
+                // A new pending request of controller id carries the RestId allocated in this step,
+                // which is owned by controller id now.
+                let controller_state = Self::unmarshal_controller_state(s.controller_states[id]);
+                let controller_state_prime = Self::unmarshal_controller_state(s_prime.controller_states[id]);
+                assert forall |key: ObjectRef|
+                    #[trigger] controller_state_prime.ongoing_reconciles.contains_key(key)
+                    && controller_state_prime.ongoing_reconciles[key].pending_req_msg.is_Some()
+                implies {
+                    let rest_id = controller_state_prime.ongoing_reconciles[key].pending_req_msg.get_Some_0().content.get_rest_id();
+                    &&& s_prime.rest_id_owners.contains_key(rest_id)
+                    &&& s_prime.rest_id_owners[rest_id] == id
+                } by {
+                    if controller_state.ongoing_reconciles.contains_key(key)
+                    && controller_state_prime.ongoing_reconciles[key] == controller_state.ongoing_reconciles[key] {
+                        let rest_id = controller_state.ongoing_reconciles[key].pending_req_msg.get_Some_0().content.get_rest_id();
+                        assert(rest_id < counter);
+                    } else {
+                        let rest_id = controller_state_prime.ongoing_reconciles[key].pending_req_msg.get_Some_0().content.get_rest_id();
+                        assert(rest_id == counter);
+                    }
+                }
+            } else {
+                assert(s_prime.controller_states[id] == s.controller_states[id]);
+            }
+            if input.1.is_Some() {
+                assert(s.in_flight().contains(input.1.get_Some_0()));
+            }
+        }
+        MultiClusterStep::ScheduleControllerReconcileStep(input) => {
+            if input.0 == id {
+                assert(Self::unmarshal_controller_state(s_prime.controller_states[id]).ongoing_reconciles
+                    == Self::unmarshal_controller_state(s.controller_states[id]).ongoing_reconciles);
+            } else {
+                assert(s_prime.controller_states[id] == s.controller_states[id]);
+            }
+        }
+        MultiClusterStep::RestartController(input) => {
+            if input == id {
+                let init_state = Self::marshal_controller_state(Self::init_controller_state());
+                assert((model.controllers[id].init)(init_state));
+                assert(Self::unmarshal_controller_state(s_prime.controller_states[id]) == Self::init_controller_state());
+            } else {
+                assert(s_prime.controller_states[id] == s.controller_states[id]);
+            }
+        }
+        MultiClusterStep::ApiServerStep(input) => {
+            assert(s.in_flight().contains(input.get_Some_0()));
+        }
+        MultiClusterStep::ExternalAPIStep(input) => {
+            assert(s.in_flight().contains(input.get_Some_0()));
+        }
+        MultiClusterStep::FailTransientlyStep(input) => {
+            assert(s.in_flight().contains(input.0));
+        }
+        _ => {}
+    }
+}
+
+pub proof fn lemma_always_rest_ids_are_consistent(model: MultiClusterModel<E>, id: ControllerId, ex: Execution<MultiCluster<E>>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        model.sm_spec().satisfied_by(ex),
+    ensures always(lift_state(Self::rest_ids_are_consistent(id))).satisfied_by(ex),
+{
+    let spec = lift_state(model.init()).and(always(lift_action(model.next())));
+    assert forall |s: MultiCluster<E>| #[trigger] model.init()(s) implies Self::rest_ids_are_consistent(id)(s) by {
+        Self::lemma_rest_ids_are_consistent_at_init(model, id, s);
+    }
+    assert forall |s, s_prime: MultiCluster<E>| Self::rest_ids_are_consistent(id)(s) && #[trigger] model.next()(s, s_prime)
+    implies Self::rest_ids_are_consistent(id)(s_prime) by {
+        Self::lemma_rest_ids_are_consistent_is_preserved(model, id, s, s_prime);
+    }
+    init_invariant(spec, model.init(), model.next(), Self::rest_ids_are_consistent(id));
+    assert(spec.satisfied_by(ex));
+    implies_apply(ex, spec, always(lift_state(Self::rest_ids_are_consistent(id))));
+}
+
+/// Once a RestId is allocated, its owner never changes, so neither does the relabeling of the messages carrying it.
+pub proof fn lemma_relabel_is_stable(model: MultiClusterModel<E>, id: ControllerId, ex: Execution<MultiCluster<E>>, i: nat, j: nat, msg: MsgType<E>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        always(lift_action(model.next())).satisfied_by(ex),
+        always(lift_state(Self::rest_ids_are_consistent(id))).satisfied_by(ex),
+        i <= j,
+        msg.content.get_rest_id() < (ex.nat_to_state)(i).rest_id_allocator.rest_id_counter,
+    ensures
+        msg.content.get_rest_id() < (ex.nat_to_state)(j).rest_id_allocator.rest_id_counter,
+        relabel((ex.nat_to_state)(j).rest_id_owners, id, msg) == relabel((ex.nat_to_state)(i).rest_id_owners, id, msg),
+    decreases j - i,
+{
+    if i < j {
+        Self::lemma_relabel_is_stable(model, id, ex, i, (j - 1) as nat, msg);
+        let s = (ex.nat_to_state)((j - 1) as nat);
+        let s_prime = (ex.nat_to_state)(j);
+        assert(lift_action(model.next()).satisfied_by(ex.suffix((j - 1) as nat)));
+        assert(lift_state(Self::rest_ids_are_consistent(id)).satisfied_by(ex.suffix((j - 1) as nat)));
+        assert(ex.suffix((j - 1) as nat).head() == s);
+        assert(ex.suffix((j - 1) as nat).head_next() == s_prime);
+        Self::lemma_rest_ids_are_consistent_is_preserved(model, id, s, s_prime);
+        assert(s_prime.rest_id_owners.contains_key(msg.content.get_rest_id()) == s.rest_id_owners.contains_key(msg.content.get_rest_id()));
+    }
+}
+
+/// transition_by_etcd does not look at the sender of the request; it only replies to the sender.
+pub proof fn lemma_transition_by_etcd_commutes_with_relabel(owners: Map<RestId, ControllerId>, id: ControllerId, msg: MsgType<E>, s: ApiServerState)
+    requires msg.content.is_APIRequest(),
+    ensures
+        Self::transition_by_etcd(relabel(owners, id, msg), s).0 == Self::transition_by_etcd(msg, s).0,
+        Self::transition_by_etcd(relabel(owners, id, msg), s).1 == relabel(owners, id, Self::transition_by_etcd(msg, s).1),
+        Self::transition_by_etcd(msg, s).1.content.get_rest_id() == msg.content.get_rest_id(),
+{
+    let relabeled = relabel(owners, id, msg);
+    assert(relabeled.content == msg.content);
+    match msg.content.get_APIRequest_0() {
+        APIRequest::GetRequest(_) => {}
+        APIRequest::ListRequest(_) => {}
+        APIRequest::CreateRequest(_) => {}
+        APIRequest::DeleteRequest(_) => {}
+        APIRequest::UpdateRequest(_) => {}
+        APIRequest::UpdateStatusRequest(_) => {}
+        APIRequest::ApplyRequest(_) => {}
+        APIRequest::PatchRequest(_) => {}
+    }
+}
+
+pub proof fn lemma_form_matched_err_resp_msg_commutes_with_relabel(owners: Map<RestId, ControllerId>, id: ControllerId, msg: MsgType<E>, err: APIError)
+    requires msg.content.is_APIRequest(),
+    ensures
+        Message::form_matched_err_resp_msg(relabel(owners, id, msg), err) == relabel(owners, id, Message::form_matched_err_resp_msg(msg, err)),
+{
+    assert(relabel(owners, id, msg).content == msg.content);
+    match msg.content.get_APIRequest_0() {
+        APIRequest::GetRequest(_) => {}
+        APIRequest::ListRequest(_) => {}
+        APIRequest::CreateRequest(_) => {}
+        APIRequest::DeleteRequest(_) => {}
+        APIRequest::UpdateRequest(_) => {}
+        APIRequest::UpdateStatusRequest(_) => {}
+        APIRequest::ApplyRequest(_) => {}
+        APIRequest::PatchRequest(_) => {}
+    }
+}
+
+/// When the owners of the RestIds do not change (or only new RestIds get owners), removing a message
+/// and adding new ones in the MultiCluster removes and adds their relabeled versions in the projection.
+pub proof fn lemma_projected_in_flight_after_deliver(
+    s: MultiCluster<E>, s_prime: MultiCluster<E>, id: ControllerId, recv: Option<MsgType<E>>, send: Multiset<MsgType<E>>, projected_send: Multiset<MsgType<E>>
+)
+    requires
+        Self::rest_ids_are_consistent(id)(s),
+        recv.is_Some() ==> s.in_flight().contains(recv.get_Some_0()),
+        s_prime.in_flight() == (if recv.is_Some() { s.in_flight().remove(recv.get_Some_0()) } else { s.in_flight() }).add(send),
+        // The owners of the allocated RestIds do not change.
+        forall |rest_id: RestId| rest_id < s.rest_id_allocator.rest_id_counter
+            ==> (#[trigger] s_prime.rest_id_owners.contains_key(rest_id) == s.rest_id_owners.contains_key(rest_id)
+                && s_prime.rest_id_owners[rest_id] == s.rest_id_owners[rest_id]),
+        // The received message is either recorded as received by another controller (and stays in the projection),
+        // or has gone from the projection.
+        Self::received_by_others(s_prime, id) == Self::received_by_others(s, id)
+        || (recv.is_Some() && owned_by_other(s.rest_id_owners, id, recv.get_Some_0())
+            && Self::received_by_others(s_prime, id) == Self::received_by_others(s, id).insert(recv.get_Some_0())),
+        forall |msg: MsgType<E>| #[trigger] projected_send.count(msg) == send.count(relabel(s_prime.rest_id_owners, id, msg)),
+    ensures
+        Self::received_by_others(s_prime, id) == Self::received_by_others(s, id)
+        ==> Self::projected_in_flight(s_prime, id) == (
+            if recv.is_Some() {
+                Self::projected_in_flight(s, id).remove(relabel(s.rest_id_owners, id, recv.get_Some_0()))
+            } else {
+                Self::projected_in_flight(s, id)
+            }
+        ).add(projected_send),
+        Self::received_by_others(s_prime, id) != Self::received_by_others(s, id)
+        ==> Self::projected_in_flight(s_prime, id) == Self::projected_in_flight(s, id).add(projected_send),
+{
+    Self::lemma_projected_in_flight_count(s, id);
+    Self::lemma_projected_in_flight_count(s_prime, id);
+    lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    lemma_relabel_is_involution::<E::Input, E::Output>(s_prime.rest_id_owners, id);
+    let counter = s.rest_id_allocator.rest_id_counter;
+    // The old messages are relabeled in the same way.
+    assert forall |msg: MsgType<E>| msg.content.get_rest_id() < counter
+    implies #[trigger] relabel(s_prime.rest_id_owners, id, msg) == relabel(s.rest_id_owners, id, msg) by {
+        assert(s_prime.rest_id_owners.contains_key(msg.content.get_rest_id()) == s.rest_id_owners.contains_key(msg.content.get_rest_id()));
+    }
+    // The messages in the projection of s carry allocated RestIds.
+    assert forall |msg: MsgType<E>| #[trigger] Self::projected_in_flight(s, id).contains(msg)
+    implies msg.content.get_rest_id() < counter by {
+        let relabeled = relabel(s.rest_id_owners, id, msg);
+        if s.in_flight().contains(relabeled) {
+        } else {
+            assert(s.received.contains(relabeled));
+        }
+    }
+    if Self::received_by_others(s_prime, id) == Self::received_by_others(s, id) {
+        assert(Self::projected_in_flight(s_prime, id) =~= (
+            if recv.is_Some() {
+                Self::projected_in_flight(s, id).remove(relabel(s.rest_id_owners, id, recv.get_Some_0()))
+            } else {
+                Self::projected_in_flight(s, id)
+            }
+        ).add(projected_send));
+    } else {
+        assert(Self::projected_in_flight(s_prime, id) =~= Self::projected_in_flight(s, id).add(projected_send));
+    }
+}
+
+pub proof fn lemma_projected_api_server_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: Option<MsgType<E>>
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.kubernetes_api_next().forward(input)(s, s_prime),
+    ensures
+        Self::kubernetes_api_next().forward(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let req_msg = input.get_Some_0();
+    let relabeled = relabel(s.rest_id_owners, id, req_msg);
+    Self::lemma_transition_by_etcd_commutes_with_relabel(s.rest_id_owners, id, req_msg, s.kubernetes_api_state);
+    let resp_msg = Self::transition_by_etcd(req_msg, s.kubernetes_api_state).1;
+    lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    assert(s.received == s_prime.received && s.rest_id_owners == s_prime.rest_id_owners);
+    assert(Self::received_by_others(s_prime, id) == Self::received_by_others(s, id));
+    Self::lemma_projected_in_flight_after_deliver(
+        s, s_prime, id, input, Multiset::singleton(resp_msg), Multiset::singleton(relabel(s.rest_id_owners, id, resp_msg))
+    );
+    Self::lemma_projected_in_flight_count(s, id);
+    assert(Self::projected_in_flight(s, id).contains(relabeled));
+}
+
+pub proof fn lemma_projected_external_api_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: Option<MsgType<E>>
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.external_api_next().forward(input)(s, s_prime),
+    ensures
+        Self::external_api_next().forward(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let req_msg = input.get_Some_0();
+    let relabeled = relabel(s.rest_id_owners, id, req_msg);
+    let resp_msg = Self::handle_external_request_helper(req_msg, s.external_api_state, s.kubernetes_api_state.resources).1;
+    assert(relabeled.content == req_msg.content);
+    assert(Self::handle_external_request_helper(relabeled, s.external_api_state, s.kubernetes_api_state.resources).1 == relabel(s.rest_id_owners, id, resp_msg));
+    lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    assert(Self::received_by_others(s_prime, id) == Self::received_by_others(s, id));
+    Self::lemma_projected_in_flight_after_deliver(
+        s, s_prime, id, input, Multiset::singleton(resp_msg), Multiset::singleton(relabel(s.rest_id_owners, id, resp_msg))
+    );
+    Self::lemma_projected_in_flight_count(s, id);
+    assert(Self::projected_in_flight(s, id).contains(relabeled));
+}
+
+pub proof fn lemma_projected_builtin_controllers_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (BuiltinControllerChoice, ObjectRef)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.builtin_controllers_next().forward(input)(s, s_prime),
+    ensures Self::builtin_controllers_next().forward(input)(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let host_result = Self::builtin_controllers().next_result(
+        BuiltinControllersActionInput {
+            choice: input.0,
+            key: input.1,
+            rest_id_allocator: s.rest_id_allocator,
+        },
+        s.kubernetes_api_state
+    );
+    let send = host_result.get_Enabled_1().send;
+    // The built-in controllers send their requests with the next RestId, which is not owned by any controller.
+    assert forall |msg: MsgType<E>| #[trigger] relabel(s.rest_id_owners, id, msg) != msg implies send.count(msg) == 0 by {
+        if send.count(msg) > 0 {
+            assert(msg.content.get_rest_id() == s.rest_id_allocator.rest_id_counter);
+        }
+    }
+    assert forall |msg: MsgType<E>| #[trigger] send.count(msg) == send.count(relabel(s.rest_id_owners, id, msg)) by {
+        lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    }
+    Self::lemma_projected_in_flight_after_deliver(s, s_prime, id, None, send, send);
+}
+
+pub proof fn lemma_projected_fail_transiently_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (MsgType<E>, APIError)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.fail_request_transiently().forward(input)(s, s_prime),
+    ensures
+        Self::fail_request_transiently().forward((relabel(s.rest_id_owners, id, input.0), input.1))(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let req_msg = input.0;
+    let relabeled = relabel(s.rest_id_owners, id, req_msg);
+    let resp_msg = Message::form_matched_err_resp_msg(req_msg, input.1);
+    Self::lemma_form_matched_err_resp_msg_commutes_with_relabel(s.rest_id_owners, id, req_msg, input.1);
+    lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+    assert(Self::received_by_others(s_prime, id) == Self::received_by_others(s, id));
+    Self::lemma_projected_in_flight_after_deliver(
+        s, s_prime, id, Some(req_msg), Multiset::singleton(resp_msg), Multiset::singleton(relabel(s.rest_id_owners, id, resp_msg))
+    );
+    Self::lemma_projected_in_flight_count(s, id);
+    assert(Self::projected_in_flight(s, id).contains(relabeled));
+}
+
+pub proof fn lemma_projected_client_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: ClientStep
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.client_next().forward(input)(s, s_prime),
+    ensures Self::client_next().forward(input)(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let host_result = model.client().next_action_result(
+        (model.client().step_to_action)(input),
+        (model.client().action_input)(input, s.rest_id_allocator),
+        s.client_state
+    );
+    let send = host_result.get_Enabled_1().send;
+    // The users of the MultiCluster only manage the objects that the client of this Cluster manages.
+    match input {
+        ClientStep::CreateCustomResource(obj) => { assert(Self::client_expects(obj)); }
+        ClientStep::UpdateCustomResource(obj) => { assert(Self::client_expects(obj)); }
+        ClientStep::DeleteCustomResource(obj) => { assert(Self::client_expects(obj)); }
+        ClientStep::SendRequest(req) => {
+            if req.is_CreateRequest() { assert(Self::client_expects(req.get_CreateRequest_0().obj)); }
+            if req.is_UpdateRequest() { assert(Self::client_expects(req.get_UpdateRequest_0().obj)); }
+        }
+    }
+    assert(Self::client().next_action_result(
+        (Self::client().step_to_action)(input),
+        (Self::client().action_input)(input, s.rest_id_allocator),
+        s.client_state
+    ) == host_result);
+    // The request carries the next RestId, which is not owned by any controller.
+    assert forall |msg: MsgType<E>| #[trigger] send.count(msg) == send.count(relabel(s.rest_id_owners, id, msg)) by {
+        lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+        if send.count(msg) > 0 || send.count(relabel(s.rest_id_owners, id, msg)) > 0 {
+            assert(!s.rest_id_owners.contains_key(s.rest_id_allocator.rest_id_counter));
+        }
+    }
+    Self::lemma_projected_in_flight_after_deliver(s, s_prime, id, None, send, send);
+}
+
+pub proof fn lemma_projected_controller_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        input.0 == id,
+        model.controller_next().forward(input)(s, s_prime),
+    ensures Self::controller_next().forward((input.1, input.2))(Self::project(s, id), Self::project(s_prime, id)),
+{
+    Self::marshal_controller_state_preserves_integrity();
+    let controller_input = ControllerActionInput{recv: input.1, scheduled_cr_key: input.2, rest_id_allocator: s.rest_id_allocator};
+    let host_result = Self::controller().next_result(controller_input, Self::unmarshal_controller_state(s.controller_states[id]));
+    let send = host_result.get_Enabled_1().send;
+    Self::lemma_controller_step_sends_allocated_rest_ids(model, id, s, s_prime, input);
+    // Everything received or sent by controller id carries the RestIds owned by controller id.
+    if input.1.is_Some() {
+        assert(!owned_by_other(s.rest_id_owners, id, input.1.get_Some_0()));
+        assert(s_prime.received == s.received.insert(input.1.get_Some_0()));
+        assert(Self::received_by_others(s_prime, id) =~= Self::received_by_others(s, id));
+    } else {
+        assert(Self::received_by_others(s_prime, id) =~= Self::received_by_others(s, id));
+    }
+    assert forall |msg: MsgType<E>| #[trigger] send.count(msg) == send.count(relabel(s_prime.rest_id_owners, id, msg)) by {
+        lemma_relabel_is_involution::<E::Input, E::Output>(s_prime.rest_id_owners, id);
+        if send.count(msg) > 0 {
+            assert(s_prime.rest_id_owners[msg.content.get_rest_id()] == id);
+        }
+        if send.count(relabel(s_prime.rest_id_owners, id, msg)) > 0 {
+            assert(s_prime.rest_id_owners[msg.content.get_rest_id()] == id);
+        }
+    }
+    Self::lemma_projected_in_flight_after_deliver(s, s_prime, id, input.1, send, send);
+    if input.1.is_Some() {
+        Self::lemma_projected_in_flight_count(s, id);
+        assert(Self::projected_in_flight(s, id).contains(input.1.get_Some_0()));
+    }
+}
+
+/// A step of another controller is a step of the client (if it sends a request) or a stutter step
+/// when seen by controller id.
+pub proof fn lemma_projected_other_controller_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        input.0 != id,
+        model.controller_next().forward(input)(s, s_prime),
+    ensures Self::next()(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let (p, p_prime) = (Self::project(s, id), Self::project(s_prime, id));
+    let controller_input = ControllerActionInput{recv: input.1, scheduled_cr_key: input.2, rest_id_allocator: s.rest_id_allocator};
+    let output = (model.controllers[input.0].next)(controller_input, s.controller_states[input.0]).get_Some_0().1;
+    assert(Self::is_client_like(model.controllers[input.0]));
+    assert((model.controllers[input.0].next)(controller_input, s.controller_states[input.0]).is_Some());
+    assert(s_prime.controller_states[id] == s.controller_states[id]);
+    Self::lemma_controller_step_sends_allocated_rest_ids(model, id, s, s_prime, input);
+    // The response received by the other controller stays in the projection as a response to the client.
+    if input.1.is_Some() {
+        let recv = input.1.get_Some_0();
+        assert(owned_by_other(s.rest_id_owners, id, recv));
+        assert(Self::received_by_others(s_prime, id) =~= Self::received_by_others(s, id).insert(recv));
+    } else {
+        assert(Self::received_by_others(s_prime, id) =~= Self::received_by_others(s, id));
+    }
+    if output.send == Multiset::<MsgType<E>>::empty() {
+        Self::lemma_projected_in_flight_after_deliver(s, s_prime, id, input.1, output.send, output.send);
+        if input.1.is_Some() {
+            Self::lemma_projected_in_flight_count(s, id);
+            Self::lemma_projected_in_flight_count(s_prime, id);
+            assert(Self::projected_in_flight(s_prime, id) =~= Self::projected_in_flight(s, id));
+        }
+        assert(p_prime == p);
+        assert(Self::next_step(p, p_prime, Step::StutterStep()));
+    } else {
+        let req = choose |req: APIRequest| {
+            &&& #[trigger] client_can_send_request(|obj: DynamicObjectView| Self::client_expects(obj), req)
+            &&& output.send == Multiset::singleton(Message::controller_req_msg(req, s.rest_id_allocator.allocate().1))
+            &&& output.rest_id_allocator == s.rest_id_allocator.allocate().0
+        };
+        let req_msg = Message::controller_req_msg(req, s.rest_id_allocator.allocate().1);
+        let client_req_msg = Message::client_req_msg(MessageContent::APIRequest(req, s.rest_id_allocator.allocate().1));
+        // The RestId of the request is owned by the other controller, so the request is seen as sent by the client.
+        assert(s_prime.rest_id_owners[s.rest_id_allocator.rest_id_counter] == input.0);
+        assert(relabel(s_prime.rest_id_owners, id, client_req_msg) == req_msg);
+        assert(relabel(s_prime.rest_id_owners, id, req_msg) == client_req_msg);
+        lemma_relabel_is_involution::<E::Input, E::Output>(s_prime.rest_id_owners, id);
+        assert forall |msg: MsgType<E>| #[trigger] Multiset::singleton(client_req_msg).count(msg) == output.send.count(relabel(s_prime.rest_id_owners, id, msg)) by {
+            if msg == client_req_msg {} else {
+                assert(relabel(s_prime.rest_id_owners, id, msg) != req_msg);
+            }
+        }
+        Self::lemma_projected_in_flight_after_deliver(s, s_prime, id, input.1, output.send, Multiset::singleton(client_req_msg));
+        if input.1.is_Some() {
+            Self::lemma_projected_in_flight_count(s, id);
+            Self::lemma_projected_in_flight_count(s_prime, id);
+            assert(Self::projected_in_flight(s_prime, id) =~= Self::projected_in_flight(s, id).add(Multiset::singleton(client_req_msg)));
+        }
+        assert(Self::client_next().forward(ClientStep::SendRequest(req))(p, p_prime));
+        assert(Self::next_step(p, p_prime, Step::ClientStep(ClientStep::SendRequest(req))));
+    }
+}
+
+pub proof fn lemma_projected_schedule_controller_reconcile_step(
+    model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>, input: (ControllerId, ObjectRef)
+)
+    requires
+        Self::is_hosted_by(model, id),
+        input.0 == id,
+        model.schedule_controller_reconcile().forward(input)(s, s_prime),
+    ensures Self::schedule_controller_reconcile().forward(input.1)(Self::project(s, id), Self::project(s_prime, id)),
+{
+    Self::marshal_controller_state_preserves_integrity();
+    assert(Self::project(s_prime, id).network_state == Self::project(s, id).network_state);
+}
+
+pub proof fn lemma_projected_next(model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>, s_prime: MultiCluster<E>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        Self::rest_ids_are_consistent(id)(s),
+        model.next()(s, s_prime),
+    ensures Self::next()(Self::project(s, id), Self::project(s_prime, id)),
+{
+    let step = choose |step: MultiClusterStep<MsgType<E>>| model.next_step(s, s_prime, step);
+    let (p, p_prime) = (Self::project(s, id), Self::project(s_prime, id));
+    Self::marshal_controller_state_preserves_integrity();
+    match step {
+        MultiClusterStep::ApiServerStep(input) => {
+            Self::lemma_projected_api_server_step(model, id, s, s_prime, input);
+            assert(Self::next_step(p, p_prime, Step::ApiServerStep(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))));
+        },
+        MultiClusterStep::BuiltinControllersStep(input) => {
+            Self::lemma_projected_builtin_controllers_step(model, id, s, s_prime, input);
+            assert(Self::next_step(p, p_prime, Step::BuiltinControllersStep(input)));
+        },
+        MultiClusterStep::ControllerStep(input) => {
+            if input.0 == id {
+                Self::lemma_projected_controller_step(model, id, s, s_prime, input);
+                assert(Self::next_step(p, p_prime, Step::ControllerStep((input.1, input.2))));
+            } else {
+                Self::lemma_projected_other_controller_step(model, id, s, s_prime, input);
+            }
+        },
+        MultiClusterStep::ClientStep(input) => {
+            Self::lemma_projected_client_step(model, id, s, s_prime, input);
+            assert(Self::next_step(p, p_prime, Step::ClientStep(input)));
+        },
+        MultiClusterStep::ExternalAPIStep(input) => {
+            Self::lemma_projected_external_api_step(model, id, s, s_prime, input);
+            assert(Self::next_step(p, p_prime, Step::ExternalAPIStep(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))));
+        },
+        MultiClusterStep::ScheduleControllerReconcileStep(input) => {
+            if input.0 == id {
+                Self::lemma_projected_schedule_controller_reconcile_step(model, id, s, s_prime, input);
+                assert(Self::next_step(p, p_prime, Step::ScheduleControllerReconcileStep(input.1)));
+            } else {
+                assert(s_prime.controller_states[id] == s.controller_states[id]);
+                assert(p_prime == p);
+                assert(Self::next_step(p, p_prime, Step::StutterStep()));
+            }
+        },
+        MultiClusterStep::RestartController(input) => {
+            if input == id {
+                let init_state = Self::marshal_controller_state(Self::init_controller_state());
+                assert((model.controllers[id].init)(init_state));
+                assert(Self::next_step(p, p_prime, Step::RestartController()));
+            } else {
+                assert(s_prime.controller_states[id] == s.controller_states[id]);
+                assert(p_prime == p);
+                assert(Self::next_step(p, p_prime, Step::StutterStep()));
+            }
+        },
+        MultiClusterStep::DisableCrash() => {
+            assert(Self::next_step(p, p_prime, Step::DisableCrash()));
+        },
+        MultiClusterStep::FailTransientlyStep(input) => {
+            Self::lemma_projected_fail_transiently_step(model, id, s, s_prime, input);
+            assert(Self::next_step(p, p_prime, Step::FailTransientlyStep((relabel(s.rest_id_owners, id, input.0), input.1))));
+        },
+        MultiClusterStep::DisableTransientFailure() => {
+            assert(Self::next_step(p, p_prime, Step::DisableTransientFailure()));
+        },
+        MultiClusterStep::StutterStep() => {
+            assert(Self::next_step(p, p_prime, Step::StutterStep()));
+        },
+    }
+}
+
+pub proof fn lemma_projected_init(model: MultiClusterModel<E>, id: ControllerId, s: MultiCluster<E>)
+    requires
+        Self::is_hosted_by(model, id),
+        model.init()(s),
+    ensures Self::init()(Self::project(s, id)),
+{
+    assert((model.controllers[id].init)(s.controller_states[id]));
+    Self::lemma_projected_in_flight_count(s, id);
+    assert(Self::projected_in_flight(s, id) =~= Multiset::<MsgType<E>>::empty());
+}
+
+/// The weak fairness of multi_action (with the input j_at(i) chosen at each position i) carries over
+/// to cluster_action with input a on the projected execution, if the enabling condition of cluster_action
+/// (since position i) implies the one of multi_action, and the step of multi_action is a step of cluster_action.
+proof fn lemma_weak_fairness_is_projected<A, J>(
+    id: ControllerId, ex: Execution<MultiCluster<E>>, cluster_action: Action<Self, A, ()>, a: A,
+    multi_action: Action<MultiCluster<E>, J, ()>, j_at: spec_fn(nat) -> J
+)
+    requires
+        forall |i: nat| #[trigger] multi_action.weak_fairness(j_at(i)).satisfied_by(ex),
+        forall |i: nat, k: nat|
+            i <= k
+            && cluster_action.pre(a)(Self::project((ex.nat_to_state)(i), id))
+            && cluster_action.pre(a)(Self::project((ex.nat_to_state)(k), id))
+            ==> #[trigger] multi_action.pre(j_at(i))((ex.nat_to_state)(k)),
+        forall |i: nat, k: nat|
+            i <= k
+            && cluster_action.pre(a)(Self::project((ex.nat_to_state)(i), id))
+            && #[trigger] multi_action.forward(j_at(i))((ex.nat_to_state)(k), (ex.nat_to_state)(k + 1))
+            ==> cluster_action.forward(a)(Self::project((ex.nat_to_state)(k), id), Self::project((ex.nat_to_state)(k + 1), id)),
+    ensures cluster_action.weak_fairness(a).satisfied_by(Self::project_ex(ex, id)),
+{
+    let pex = Self::project_ex(ex, id);
+    let pre = lift_state(cluster_action.pre(a));
+    let forward = lift_action(cluster_action.forward(a));
+    assert forall |i: nat| #[trigger] always(pre).implies(eventually(forward)).satisfied_by(pex.suffix(i)) by {
+        if always(pre).satisfied_by(pex.suffix(i)) {
+            let j = j_at(i);
+            assert(pre.satisfied_by(pex.suffix(i).suffix(0)));
+            assert(cluster_action.pre(a)(Self::project((ex.nat_to_state)(i), id)));
+            assert forall |k: nat| #[trigger] lift_state(multi_action.pre(j)).satisfied_by(ex.suffix(i).suffix(k)) by {
+                assert(pre.satisfied_by(pex.suffix(i).suffix(k)));
+                assert(cluster_action.pre(a)(Self::project((ex.nat_to_state)(k + i), id)));
+                assert(multi_action.pre(j)((ex.nat_to_state)(k + i)));
+            }
+            assert(multi_action.weak_fairness(j).satisfied_by(ex));
+            assert(always(lift_state(multi_action.pre(j))).implies(eventually(lift_action(multi_action.forward(j)))).satisfied_by(ex.suffix(i)));
+            assert(always(lift_state(multi_action.pre(j))).satisfied_by(ex.suffix(i)));
+            let k = choose |k: nat| #[trigger] lift_action(multi_action.forward(j)).satisfied_by(ex.suffix(i).suffix(k));
+            assert(multi_action.forward(j)((ex.nat_to_state)(k + i), (ex.nat_to_state)(k + i + 1)));
+            assert(cluster_action.forward(a)(Self::project((ex.nat_to_state)(k + i), id), Self::project((ex.nat_to_state)(k + i + 1), id)));
+            assert(forward.satisfied_by(pex.suffix(i).suffix(k)));
+        }
+    }
+}
+
+/// The multi_action taking a message as input is fair for the relabeled message: once the message is seen by
+/// controller id, its RestId has been allocated, so it is relabeled in the same way from then on.
+proof fn lemma_weak_fairness_of_message_is_projected(
+    model: MultiClusterModel<E>, id: ControllerId, ex: Execution<MultiCluster<E>>,
+    cluster_action: Action<Self, Option<MsgType<E>>, ()>, msg: MsgType<E>, multi_action: Action<MultiCluster<E>, Option<MsgType<E>>, ()>
+)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        always(lift_action(model.next())).satisfied_by(ex),
+        always(lift_state(Self::rest_ids_are_consistent(id))).satisfied_by(ex),
+        forall |input: Option<MsgType<E>>| #[trigger] multi_action.weak_fairness(input).satisfied_by(ex),
+        forall |s: MultiCluster<E>| #[trigger] cluster_action.pre(Some(msg))(Self::project(s, id)) && Self::rest_ids_are_consistent(id)(s)
+            ==> msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter
+                && multi_action.pre(Some(relabel(s.rest_id_owners, id, msg)))(s),
+        forall |s: MultiCluster<E>, s_prime: MultiCluster<E>, input: Option<MsgType<E>>|
+            Self::rest_ids_are_consistent(id)(s) && #[trigger] multi_action.forward(input)(s, s_prime)
+            ==> cluster_action.forward(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))(Self::project(s, id), Self::project(s_prime, id)),
+    ensures cluster_action.weak_fairness(Some(msg)).satisfied_by(Self::project_ex(ex, id)),
+{
+    let a = Some(msg);
+    let j_at = |i: nat| Some(relabel((ex.nat_to_state)(i).rest_id_owners, id, msg));
+    assert forall |i: nat| #[trigger] (ex.nat_to_state)(i) == ex.suffix(i).head() && Self::rest_ids_are_consistent(id)((ex.nat_to_state)(i)) by {
+        assert(lift_state(Self::rest_ids_are_consistent(id)).satisfied_by(ex.suffix(i)));
+    }
+    assert forall |i: nat, k: nat|
+        i <= k
+        && cluster_action.pre(a)(Self::project((ex.nat_to_state)(i), id))
+        && cluster_action.pre(a)(Self::project((ex.nat_to_state)(k), id))
+    implies #[trigger] multi_action.pre(j_at(i))((ex.nat_to_state)(k)) by {
+        assert(Self::rest_ids_are_consistent(id)((ex.nat_to_state)(i)));
+        assert(Self::rest_ids_are_consistent(id)((ex.nat_to_state)(k)));
+        Self::lemma_relabel_is_stable(model, id, ex, i, k, msg);
+    }
+    assert forall |i: nat, k: nat|
+        i <= k
+        && cluster_action.pre(a)(Self::project((ex.nat_to_state)(i), id))
+        && #[trigger] multi_action.forward(j_at(i))((ex.nat_to_state)(k), (ex.nat_to_state)(k + 1))
+    implies cluster_action.forward(a)(Self::project((ex.nat_to_state)(k), id), Self::project((ex.nat_to_state)(k + 1), id)) by {
+        let s = (ex.nat_to_state)(k);
+        assert(Self::rest_ids_are_consistent(id)((ex.nat_to_state)(i)));
+        assert(Self::rest_ids_are_consistent(id)(s));
+        Self::lemma_relabel_is_stable(model, id, ex, i, k, msg);
+        lemma_relabel_is_involution::<E::Input, E::Output>(s.rest_id_owners, id);
+        assert(relabel(s.rest_id_owners, id, j_at(i).get_Some_0()) == msg);
+    }
+    Self::lemma_weak_fairness_is_projected(id, ex, cluster_action, a, multi_action, j_at);
+}
+
+// Each fair action of Cluster is enabled in the projected state only if the corresponding action of controller id
+// (or the shared component) is enabled in the MultiCluster, and the step of the latter is a step of the former,
+// so the weak fairness of the MultiCluster actions carries over to the projected execution.
+pub proof fn lemma_projected_wf(model: MultiClusterModel<E>, id: ControllerId, ex: Execution<MultiCluster<E>>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        model.sm_spec().satisfied_by(ex),
+    ensures Self::sm_wf_spec().satisfied_by(Self::project_ex(ex, id)),
+{
+    let pex = Self::project_ex(ex, id);
+    Self::lemma_always_rest_ids_are_consistent(model, id, ex);
+    let inv = Self::rest_ids_are_consistent(id);
+    assert forall |i: nat| #[trigger] inv((ex.nat_to_state)(i)) by {
+        assert(lift_state(inv).satisfied_by(ex.suffix(i)));
+    }
+    assert(tla_forall(|input| model.kubernetes_api_next().weak_fairness(input)).satisfied_by(ex));
+    assert(tla_forall(|input| model.builtin_controllers_next().weak_fairness(input)).satisfied_by(ex));
+    assert(tla_forall(|input| model.external_api_next().weak_fairness(input)).satisfied_by(ex));
+    assert(model.controller_wf_spec(id).satisfied_by(ex));
+    assert(model.disable_crash().weak_fairness(()).satisfied_by(ex));
+    assert(model.disable_transient_failure().weak_fairness(()).satisfied_by(ex));
+
+    assert forall |input: Option<MsgType<E>>| #[trigger] Self::kubernetes_api_next().weak_fairness(input).satisfied_by(pex) by {
+        if input.is_Some() {
+            let msg = input.get_Some_0();
+            assert forall |s: MultiCluster<E>| #[trigger] Self::kubernetes_api_next().pre(Some(msg))(Self::project(s, id)) && inv(s)
+            implies msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter
+                && model.kubernetes_api_next().pre(Some(relabel(s.rest_id_owners, id, msg)))(s) by {
+                Self::lemma_projected_request_is_in_flight(s, id, msg);
+                assert(relabel(s.rest_id_owners, id, msg).content == msg.content);
+            }
+            assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>, input: Option<MsgType<E>>|
+                inv(s) && #[trigger] model.kubernetes_api_next().forward(input)(s, s_prime)
+            implies Self::kubernetes_api_next().forward(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))(Self::project(s, id), Self::project(s_prime, id)) by {
+                Self::lemma_projected_api_server_step(model, id, s, s_prime, input);
+            }
+            assert forall |input: Option<MsgType<E>>| #[trigger] model.kubernetes_api_next().weak_fairness(input).satisfied_by(ex) by {
+                assert(tla_forall(|input| model.kubernetes_api_next().weak_fairness(input)).satisfied_by(ex));
+            }
+            Self::lemma_weak_fairness_of_message_is_projected(model, id, ex, Self::kubernetes_api_next(), msg, model.kubernetes_api_next());
+        } else {
+            // The API server does nothing without a request, so the action is never enabled.
+            assert forall |i: nat| #[trigger] always(lift_state(Self::kubernetes_api_next().pre(input))).implies(eventually(lift_action(Self::kubernetes_api_next().forward(input)))).satisfied_by(pex.suffix(i)) by {
+                assert(!lift_state(Self::kubernetes_api_next().pre(input)).satisfied_by(pex.suffix(i).suffix(0)));
+            }
+        }
+    }
+    assert forall |input: Option<MsgType<E>>| #[trigger] Self::external_api_next().weak_fairness(input).satisfied_by(pex) by {
+        if input.is_Some() {
+            let msg = input.get_Some_0();
+            assert forall |s: MultiCluster<E>| #[trigger] Self::external_api_next().pre(Some(msg))(Self::project(s, id)) && inv(s)
+            implies msg.content.get_rest_id() < s.rest_id_allocator.rest_id_counter
+                && model.external_api_next().pre(Some(relabel(s.rest_id_owners, id, msg)))(s) by {
+                Self::lemma_projected_request_is_in_flight(s, id, msg);
+                assert(relabel(s.rest_id_owners, id, msg).content == msg.content);
+            }
+            assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>, input: Option<MsgType<E>>|
+                inv(s) && #[trigger] model.external_api_next().forward(input)(s, s_prime)
+            implies Self::external_api_next().forward(Some(relabel(s.rest_id_owners, id, input.get_Some_0())))(Self::project(s, id), Self::project(s_prime, id)) by {
+                Self::lemma_projected_external_api_step(model, id, s, s_prime, input);
+            }
+            assert forall |input: Option<MsgType<E>>| #[trigger] model.external_api_next().weak_fairness(input).satisfied_by(ex) by {
+                assert(tla_forall(|input| model.external_api_next().weak_fairness(input)).satisfied_by(ex));
+            }
+            Self::lemma_weak_fairness_of_message_is_projected(model, id, ex, Self::external_api_next(), msg, model.external_api_next());
+        } else {
+            // The external system does nothing without a request, so the action is never enabled.
+            assert forall |i: nat| #[trigger] always(lift_state(Self::external_api_next().pre(input))).implies(eventually(lift_action(Self::external_api_next().forward(input)))).satisfied_by(pex.suffix(i)) by {
+                assert(!lift_state(Self::external_api_next().pre(input)).satisfied_by(pex.suffix(i).suffix(0)));
+            }
+        }
+    }
+    assert forall |input: (BuiltinControllerChoice, ObjectRef)| #[trigger] Self::builtin_controllers_next().weak_fairness(input).satisfied_by(pex) by {
+        let j_at = |i: nat| input;
+        assert(model.builtin_controllers_next().weak_fairness(input).satisfied_by(ex));
+        assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>|
+            inv(s) && #[trigger] model.builtin_controllers_next().forward(input)(s, s_prime)
+        implies Self::builtin_controllers_next().forward(input)(Self::project(s, id), Self::project(s_prime, id)) by {
+            Self::lemma_projected_builtin_controllers_step(model, id, s, s_prime, input);
+        }
+        Self::lemma_weak_fairness_is_projected(id, ex, Self::builtin_controllers_next(), input, model.builtin_controllers_next(), j_at);
+    }
+    assert forall |input: (Option<MsgType<E>>, Option<ObjectRef>)| #[trigger] Self::controller_next().weak_fairness(input).satisfied_by(pex) by {
+        let j_at = |i: nat| (id, input.0, input.1);
+        assert(model.controller_next().weak_fairness((id, input.0, input.1)).satisfied_by(ex));
+        // The message received by controller id is the response to its pending request,
+        // whose RestId is owned by controller id, so it is not relabeled and is in flight.
+        assert forall |s: MultiCluster<E>| #[trigger] Self::controller_next().pre(input)(Self::project(s, id)) && inv(s)
+        implies model.controller_next().pre((id, input.0, input.1))(s) by {
+            Self::marshal_controller_state_preserves_integrity();
+            if input.0.is_Some() {
+                let msg = input.0.get_Some_0();
+                let cr_key = input.1.get_Some_0();
+                let controller_state = Self::unmarshal_controller_state(s.controller_states[id]);
+                assert(controller_state.ongoing_reconciles.contains_key(cr_key));
+                assert(controller_state.ongoing_reconciles[cr_key].pending_req_msg.is_Some());
+                assert(msg.content.get_rest_id() == controller_state.ongoing_reconciles[cr_key].pending_req_msg.get_Some_0().content.get_rest_id());
+                assert(!owned_by_other(s.rest_id_owners, id, msg));
+                Self::lemma_projected_in_flight_count(s, id);
+                assert(Self::received_by_others(s, id).count(msg) == 0);
+                assert(s.in_flight().contains(msg));
+            }
+        }
+        assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>|
+            inv(s) && #[trigger] model.controller_next().forward((id, input.0, input.1))(s, s_prime)
+        implies Self::controller_next().forward(input)(Self::project(s, id), Self::project(s_prime, id)) by {
+            Self::lemma_projected_controller_step(model, id, s, s_prime, (id, input.0, input.1));
+        }
+        Self::lemma_weak_fairness_is_projected(id, ex, Self::controller_next(), input, model.controller_next(), j_at);
+    }
+    assert forall |input: ObjectRef| #[trigger] Self::schedule_controller_reconcile().weak_fairness(input).satisfied_by(pex) by {
+        let j_at = |i: nat| (id, input);
+        assert(model.schedule_controller_reconcile().weak_fairness((id, input)).satisfied_by(ex));
+        assert forall |s: MultiCluster<E>| #[trigger] Self::schedule_controller_reconcile().pre(input)(Self::project(s, id))
+        implies model.schedule_controller_reconcile().pre((id, input))(s) by {}
+        assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>|
+            #[trigger] model.schedule_controller_reconcile().forward((id, input))(s, s_prime)
+        implies Self::schedule_controller_reconcile().forward(input)(Self::project(s, id), Self::project(s_prime, id)) by {
+            Self::lemma_projected_schedule_controller_reconcile_step(model, id, s, s_prime, (id, input));
+        }
+        Self::lemma_weak_fairness_is_projected(id, ex, Self::schedule_controller_reconcile(), input, model.schedule_controller_reconcile(), j_at);
+    }
+    let j_at = |i: nat| ();
+    assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>| #[trigger] model.disable_crash().forward(())(s, s_prime)
+    implies Self::disable_crash().forward(())(Self::project(s, id), Self::project(s_prime, id)) by {
+        assert(Self::projected_in_flight(s_prime, id) == Self::projected_in_flight(s, id));
+    }
+    Self::lemma_weak_fairness_is_projected(id, ex, Self::disable_crash(), (), model.disable_crash(), j_at);
+    assert forall |s: MultiCluster<E>, s_prime: MultiCluster<E>| #[trigger] model.disable_transient_failure().forward(())(s, s_prime)
+    implies Self::disable_transient_failure().forward(())(Self::project(s, id), Self::project(s_prime, id)) by {
+        assert(Self::projected_in_flight(s_prime, id) == Self::projected_in_flight(s, id));
+    }
+    Self::lemma_weak_fairness_is_projected(id, ex, Self::disable_transient_failure(), (), model.disable_transient_failure(), j_at);
+}
+
+pub proof fn lemma_projected_execution_satisfies_sm_spec(model: MultiClusterModel<E>, id: ControllerId, ex: Execution<MultiCluster<E>>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        model.sm_spec().satisfied_by(ex),
+    ensures Self::sm_spec().satisfied_by(Self::project_ex(ex, id)),
+{
+    let projected_ex = Self::project_ex(ex, id);
+    Self::lemma_projected_init(model, id, ex.head());
+    Self::lemma_always_rest_ids_are_consistent(model, id, ex);
+    assert forall |i: nat| #[trigger] lift_action(Self::next()).satisfied_by(projected_ex.suffix(i)) by {
+        assert(lift_action(model.next()).satisfied_by(ex.suffix(i)));
+        assert(lift_state(Self::rest_ids_are_consistent(id)).satisfied_by(ex.suffix(i)));
+        Self::lemma_projected_next(model, id, ex.suffix(i).head(), ex.suffix(i).head_next());
+    }
+    Self::lemma_projected_wf(model, id, ex);
+}
+
+/// lemma_lift_to_multi_cluster is the bridge from a Cluster to a MultiCluster:
+/// any property proven for the Cluster (e.g., ESR of its controller) holds for the controller
+/// when it runs in a MultiCluster with other controllers, as long as the other controllers do not interfere with it.
+pub proof fn lemma_lift_to_multi_cluster(model: MultiClusterModel<E>, id: ControllerId, spec: TempPred<MultiCluster<E>>, p: TempPred<Self>)
+    requires
+        Self::is_hosted_by(model, id),
+        Self::other_controllers_do_not_interfere(model, id),
+        Self::sm_spec().entails(p),
+        spec.entails(model.sm_spec()),
+    ensures spec.entails(Self::lift_to_multi_cluster(p, id)),
+{
+    assert forall |ex: Execution<MultiCluster<E>>| #[trigger] spec.satisfied_by(ex)
+    implies Self::lift_to_multi_cluster(p, id).satisfied_by(ex) by {
+        assert(spec.implies(model.sm_spec()).satisfied_by(ex));
+        Self::lemma_projected_execution_satisfies_sm_spec(model, id, ex);
+        assert(Self::sm_spec().implies(p).satisfied_by(Self::project_ex(ex, id)));
+    }
+}
+
+}
+
+// The checks of the API server on the objects do not depend on the custom resource of the Cluster
+// as long as the CRDs of both custom resources are installed.
+proof fn lemma_object_checks_do_not_depend_on_custom_resource<K: CustomResourceView, K2: CustomResourceView>()
+    requires
+        custom_resource_validation_agrees_with::<K>(),
+        custom_resource_validation_agrees_with::<K2>(),
+    ensures
+        forall |obj: DynamicObjectView| #[trigger] unmarshallable_object::<K>(obj) == unmarshallable_object::<K2>(obj),
+        forall |obj: DynamicObjectView| #[trigger] valid_object::<K>(obj) == valid_object::<K2>(obj),
+        forall |obj: DynamicObjectView, old_obj: DynamicObjectView| #[trigger] valid_transition::<K>(obj, old_obj) == valid_transition::<K2>(obj, old_obj),
+        forall |kind: Kind| #[trigger] marshalled_default_status::<K>(kind) == marshalled_default_status::<K2>(kind),
+{
+    assert forall |obj: DynamicObjectView| #[trigger] unmarshallable_object::<K>(obj) == unmarshallable_object::<K2>(obj) by {
+        if obj.kind.is_CustomResourceKind() {
+            let kind = obj.kind.get_CustomResourceKind_0();
+            assert(unmarshallable_custom_resource_spec(kind, obj.spec) == unmarshallable_spec::<K>(obj));
+            assert(unmarshallable_custom_resource_status(kind, obj.status) == unmarshallable_status::<K>(obj));
+            assert(unmarshallable_custom_resource_spec(kind, obj.spec) == unmarshallable_spec::<K2>(obj));
+            assert(unmarshallable_custom_resource_status(kind, obj.status) == unmarshallable_status::<K2>(obj));
+        }
+    }
+    assert forall |obj: DynamicObjectView| #[trigger] valid_object::<K>(obj) == valid_object::<K2>(obj) by {
+        if obj.kind.is_CustomResourceKind() {
+            let kind = obj.kind.get_CustomResourceKind_0();
+            assert(valid_custom_resource(kind, obj) == valid_object::<K>(obj));
+            assert(valid_custom_resource(kind, obj) == valid_object::<K2>(obj));
+        }
+    }
+    assert forall |obj: DynamicObjectView, old_obj: DynamicObjectView| #[trigger] valid_transition::<K>(obj, old_obj) == valid_transition::<K2>(obj, old_obj) by {
+        if obj.kind.is_CustomResourceKind() {
+            let kind = obj.kind.get_CustomResourceKind_0();
+            assert(valid_custom_resource_transition(kind, obj, old_obj) == valid_transition::<K>(obj, old_obj));
+            assert(valid_custom_resource_transition(kind, obj, old_obj) == valid_transition::<K2>(obj, old_obj));
+        }
+    }
+    assert forall |kind: Kind| #[trigger] marshalled_default_status::<K>(kind) == marshalled_default_status::<K2>(kind) by {
+        if kind.is_CustomResourceKind() {
+            let crd_kind = kind.get_CustomResourceKind_0();
+            assert(marshalled_default_custom_resource_status(crd_kind) == marshalled_default_status::<K>(kind));
+            assert(marshalled_default_custom_resource_status(crd_kind) == marshalled_default_status::<K2>(kind));
+        }
+    }
+}
+
+proof fn lemma_handle_create_request_does_not_depend_on_custom_resource<K: CustomResourceView, K2: CustomResourceView>(req: CreateRequest, s: ApiServerState)
+    requires
+        forall |obj: DynamicObjectView| #[trigger] unmarshallable_object::<K>(obj) == unmarshallable_object::<K2>(obj),
+        forall |obj: DynamicObjectView| #[trigger] valid_object::<K>(obj) == valid_object::<K2>(obj),
+        forall |kind: Kind| #[trigger] marshalled_default_status::<K>(kind) == marshalled_default_status::<K2>(kind),
+    ensures handle_create_request::<K>(req, s) == handle_create_request::<K2>(req, s),
+{
+    assert(create_request_admission_check::<K>(req, s) == create_request_admission_check::<K2>(req, s));
+    assert forall |obj: DynamicObjectView| #[trigger] created_object_validity_check::<K>(obj) == created_object_validity_check::<K2>(obj) by {
+        assert(object_validity_check::<K>(obj) == object_validity_check::<K2>(obj));
+    }
+}
+
+proof fn lemma_handle_update_request_does_not_depend_on_custom_resource<K: CustomResourceView, K2: CustomResourceView>(req: UpdateRequest, s: ApiServerState)
+    requires
+        forall |obj: DynamicObjectView| #[trigger] unmarshallable_object::<K>(obj) == unmarshallable_object::<K2>(obj),
+        forall |obj: DynamicObjectView| #[trigger] valid_object::<K>(obj) == valid_object::<K2>(obj),
+        forall |obj: DynamicObjectView, old_obj: DynamicObjectView| #[trigger] valid_transition::<K>(obj, old_obj) == valid_transition::<K2>(obj, old_obj),
+    ensures handle_update_request::<K>(req, s) == handle_update_request::<K2>(req, s),
+{
+    assert(update_request_admission_check::<K>(req, s) == update_request_admission_check::<K2>(req, s));
+    assert forall |obj: DynamicObjectView, old_obj: DynamicObjectView| #[trigger] updated_object_validity_check::<K>(obj, old_obj) == updated_object_validity_check::<K2>(obj, old_obj) by {
+        assert(object_validity_check::<K>(obj) == object_validity_check::<K2>(obj));
+        assert(object_transition_validity_check::<K>(obj, old_obj) == object_transition_validity_check::<K2>(obj, old_obj));
+    }
+}
+
+}
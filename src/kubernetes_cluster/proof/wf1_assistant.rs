@@ -121,6 +121,21 @@ pub proof fn exists_next_builtin_controllers_step(action: BuiltinControllersActi
     if action == Self::run_garbage_collector() {
         let step = BuiltinControllersStep::RunGarbageCollector;
         assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
+    } else if action == Self::run_garbage_collector_orphan_dependent() {
+        let step = BuiltinControllersStep::RunGarbageCollectorOrphanDependent;
+        assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
+    } else if action == Self::run_garbage_collector_remove_orphan_finalizer() {
+        let step = BuiltinControllersStep::RunGarbageCollectorRemoveOrphanFinalizer;
+        assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
+    } else if action == Self::run_garbage_collector_foreground_dependent() {
+        let step = BuiltinControllersStep::RunGarbageCollectorForegroundDependent;
+        assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
+    } else if action == Self::run_garbage_collector_remove_foreground_finalizer() {
+        let step = BuiltinControllersStep::RunGarbageCollectorRemoveForegroundFinalizer;
+        assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
+    } else if action == Self::run_kubelet() {
+        let step = BuiltinControllersStep::RunKubelet;
+        assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
     } else if action == Self::run_stateful_set_controller() {
         let step = BuiltinControllersStep::RunStatefulSetController;
         assert(((Self::builtin_controllers().step_to_action)(step).precondition)(input, s));
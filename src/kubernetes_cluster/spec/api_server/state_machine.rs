@@ -19,8 +19,19 @@ verus! {
 //   For example, RoleBinding's roleRef is immutable: https://kubernetes.io/docs/reference/access-authn-authz/rbac/#clusterrolebinding-example
 //
 // + Check kind-specific strategy like AllowCreateOnUpdate()
-//
-// + Support graceful deletion
+
+// The cluster can hold objects of several custom resources at once, but the model only knows the schema
+// of the custom resource K managed by the controller. Objects of the other custom resources are validated
+// by the API server against the schema in their CRDs, which we leave uninterpreted here (keyed by the kind).
+pub closed spec fn unmarshallable_custom_resource_spec(kind: StringView, spec: Value) -> bool;
+
+pub closed spec fn unmarshallable_custom_resource_status(kind: StringView, status: Value) -> bool;
+
+pub closed spec fn valid_custom_resource(kind: StringView, obj: DynamicObjectView) -> bool;
+
+pub closed spec fn valid_custom_resource_transition(kind: StringView, obj: DynamicObjectView, old_obj: DynamicObjectView) -> bool;
+
+pub closed spec fn marshalled_default_custom_resource_status(kind: StringView) -> Value;
 
 #[verifier(inline)]
 pub open spec fn unmarshallable_spec<K: CustomResourceView>(obj: DynamicObjectView) -> bool {
@@ -35,7 +46,11 @@ pub open spec fn unmarshallable_spec<K: CustomResourceView>(obj: DynamicObjectVi
         Kind::ServiceKind => ServiceView::unmarshal_spec(obj.spec).is_Ok(),
         Kind::StatefulSetKind => StatefulSetView::unmarshal_spec(obj.spec).is_Ok(),
         Kind::ServiceAccountKind => ServiceAccountView::unmarshal_spec(obj.spec).is_Ok(),
-        Kind::CustomResourceKind => K::unmarshal_spec(obj.spec).is_Ok(),
+        Kind::CustomResourceKind(kind) => if obj.kind == K::kind() {
+            K::unmarshal_spec(obj.spec).is_Ok()
+        } else {
+            unmarshallable_custom_resource_spec(kind, obj.spec)
+        },
     }
 }
 
@@ -52,7 +67,11 @@ pub open spec fn unmarshallable_status<K: CustomResourceView>(obj: DynamicObject
         Kind::ServiceKind => ServiceView::unmarshal_status(obj.status).is_Ok(),
         Kind::StatefulSetKind => StatefulSetView::unmarshal_status(obj.status).is_Ok(),
         Kind::ServiceAccountKind => ServiceAccountView::unmarshal_status(obj.status).is_Ok(),
-        Kind::CustomResourceKind => K::unmarshal_status(obj.status).is_Ok(),
+        Kind::CustomResourceKind(kind) => if obj.kind == K::kind() {
+            K::unmarshal_status(obj.status).is_Ok()
+        } else {
+            unmarshallable_custom_resource_status(kind, obj.status)
+        },
     }
 }
 
@@ -74,7 +93,8 @@ pub open spec fn metadata_transition_validity_check(obj: DynamicObjectView, old_
     if old_obj.metadata.deletion_timestamp.is_Some()
     && obj.metadata.finalizers.is_Some() // Short circuit: we don't need to reason about the set difference if the finalizers is None
     && !obj.metadata.finalizers_as_set().subset_of(old_obj.metadata.finalizers_as_set()) {
-        Some(APIError::Forbidden)
+        // Kubernetes reports the forbidden finalizers as a validation error, so the reason is Invalid
+        Some(APIError::Invalid)
     } else {
         None
     }
@@ -92,7 +112,11 @@ pub open spec fn valid_object<K: CustomResourceView>(obj: DynamicObjectView) ->
         Kind::ServiceKind => ServiceView::unmarshal(obj).get_Ok_0().state_validation(),
         Kind::StatefulSetKind => StatefulSetView::unmarshal(obj).get_Ok_0().state_validation(),
         Kind::ServiceAccountKind => ServiceAccountView::unmarshal(obj).get_Ok_0().state_validation(),
-        Kind::CustomResourceKind => K::unmarshal(obj).get_Ok_0().state_validation(),
+        Kind::CustomResourceKind(kind) => if obj.kind == K::kind() {
+            K::unmarshal(obj).get_Ok_0().state_validation()
+        } else {
+            valid_custom_resource(kind, obj)
+        },
     }
 }
 
@@ -116,7 +140,11 @@ pub open spec fn valid_transition<K: CustomResourceView>(obj: DynamicObjectView,
         Kind::ServiceKind => ServiceView::unmarshal(obj).get_Ok_0().transition_validation(ServiceView::unmarshal(old_obj).get_Ok_0()),
         Kind::StatefulSetKind => StatefulSetView::unmarshal(obj).get_Ok_0().transition_validation(StatefulSetView::unmarshal(old_obj).get_Ok_0()),
         Kind::ServiceAccountKind => ServiceAccountView::unmarshal(obj).get_Ok_0().transition_validation(ServiceAccountView::unmarshal(old_obj).get_Ok_0()),
-        Kind::CustomResourceKind => K::unmarshal(obj).get_Ok_0().transition_validation(K::unmarshal(old_obj).get_Ok_0()),
+        Kind::CustomResourceKind(kind) => if obj.kind == K::kind() {
+            K::unmarshal(obj).get_Ok_0().transition_validation(K::unmarshal(old_obj).get_Ok_0())
+        } else {
+            valid_custom_resource_transition(kind, obj, old_obj)
+        },
     }
 }
 
@@ -140,7 +168,11 @@ pub open spec fn marshalled_default_status<K: CustomResourceView>(kind: Kind) ->
         Kind::ServiceKind => ServiceView::marshal_status(ServiceView::default().status()),
         Kind::StatefulSetKind => StatefulSetView::marshal_status(StatefulSetView::default().status()),
         Kind::ServiceAccountKind => ServiceAccountView::marshal_status(ServiceAccountView::default().status()),
-        Kind::CustomResourceKind => K::marshal_status(K::default().status()),
+        Kind::CustomResourceKind(crd_kind) => if kind == K::kind() {
+            K::marshal_status(K::default().status())
+        } else {
+            marshalled_default_custom_resource_status(crd_kind)
+        },
     }
 }
 
@@ -157,12 +189,13 @@ pub open spec fn handle_get_request(req: GetRequest, s: ApiServerState) -> GetRe
 
 #[verifier(inline)]
 pub open spec fn handle_list_request(req: ListRequest, s: ApiServerState) -> ListResponse {
-    // TODO: List should consider other fields
-    let selector = |o: DynamicObjectView| {
-        &&& o.object_ref().namespace == req.namespace
-        &&& o.object_ref().kind == req.kind
-    };
-    ListResponse{res: Ok(map_to_seq(s.resources, selector))}
+    if req.label_selector.is_Some() && !req.label_selector.get_Some_0().is_valid() {
+        // List fails because the label selector cannot be parsed
+        ListResponse{res: Err(APIError::BadRequest)}
+    } else {
+        let selector = |o: DynamicObjectView| req.selects(o);
+        ListResponse{res: Ok(map_to_seq(s.resources, object_ref_order(), selector))}
+    }
 }
 
 pub open spec fn create_request_admission_check<K: CustomResourceView>(req: CreateRequest, s: ApiServerState) -> Option<APIError> {
@@ -193,12 +226,28 @@ pub open spec fn created_object_validity_check<K: CustomResourceView>(created_ob
     }
 }
 
-pub closed spec fn generate_name(s: ApiServerState) -> StringView;
+// For an object created without a name, the API server appends a random suffix to metadata.generateName
+// and rejects the creation with AlreadyExists if an object with that name already exists (the client is expected
+// to retry). We model the random suffix with the uid counter, which changes after each successful creation,
+// so a colliding create request is rejected by handle_create_request and a retried one gets another name.
+pub open spec fn generated_name(s: ApiServerState, generate_name: StringView) -> StringView {
+    generate_name + int_to_string_view(s.uid_counter)
+}
 
-// TODO: This should be a spec ensures of the generate_name above
-#[verifier(external_body)]
-pub proof fn generated_name_is_unique(s: ApiServerState)
-    ensures forall |key| #[trigger] s.resources.contains_key(key) ==> key.name != generate_name(s)
+// No object of the kind in the namespace takes the name generated from generate_name with the current or any later uid counter.
+// It holds if nobody else creates objects of that kind in that namespace with names of that form.
+// Objects of other kinds or in other namespaces do not matter since the collision is checked by the object key.
+pub open spec fn generated_names_are_not_taken(s: ApiServerState, kind: Kind, namespace: StringView, generate_name: StringView) -> bool {
+    forall |key: ObjectRef, uid: Uid| #![trigger s.resources.contains_key(key), int_to_string_view(uid)]
+        s.resources.contains_key(key) && key.kind == kind && key.namespace == namespace && s.uid_counter <= uid
+        ==> key.name != generate_name + int_to_string_view(uid)
+}
+
+pub proof fn lemma_generated_name_is_fresh(s: ApiServerState, kind: Kind, namespace: StringView, generate_name: StringView)
+    requires generated_names_are_not_taken(s, kind, namespace, generate_name),
+    ensures
+        forall |key: ObjectRef| #[trigger] s.resources.contains_key(key) && key.kind == kind && key.namespace == namespace
+            ==> key.name != generated_name(s, generate_name),
 {}
 
 #[verifier(inline)]
@@ -211,34 +260,32 @@ pub open spec fn handle_create_request<K: CustomResourceView>(req: CreateRequest
             kind: req.obj.kind,
             metadata: ObjectMetaView {
                 // Set name for new object if name is not provided, here we generate
-                // a unique name. The uniqueness is guaranteed by generated_name_is_unique.
+                // a name from generate_name, which might collide with an existing object (see generated_name).
                 name: if req.obj.metadata.name.is_Some() {
                     req.obj.metadata.name
                 } else {
-                    Some(generate_name(s))
+                    Some(generated_name(s, req.obj.metadata.generate_name.get_Some_0()))
                 },
                 namespace: Some(req.namespace), // Set namespace for new object
                 resource_version: Some(s.resource_version_counter), // Set rv for new object
                 uid: Some(s.uid_counter), // Set uid for new object
                 deletion_timestamp: None, // Unset deletion timestamp for new object
+                deletion_grace_period_seconds: None, // Unset deletion grace period for new object
                 ..req.obj.metadata
             },
             spec: req.obj.spec,
             status: marshalled_default_status::<K>(req.obj.kind), // Overwrite the status with the default one
         };
         if s.resources.contains_key(created_obj.object_ref()) {
-            // Note 1: You might find this branch redundant since we already have
-            // generated_name_is_unique which guarantees that the created_obj's
-            // key is different from any existing keys even if name was not provided.
-            // But we still add this branch just to avoid calling generated_name_is_unique
-            // when we want to show that create without a provided name does not override
-            // an existing object when writing proofs.
-            //
-            // Note 2: Adding this branch also means that if we want to prove the object
-            // is eventually created by a create request without the name provided,
-            // we need to explicitly call generated_name_is_unique to show that
-            // we do not fall into this branch.
-            (s, CreateResponse{res: Err(APIError::ObjectAlreadyExists)})
+            // The object has a generated name that collides with an existing object.
+            // To show that a create request without a name succeeds, call lemma_generated_name_is_fresh
+            // to rule out this branch.
+            // The real API server generates a random suffix so a retry gets a different name;
+            // here the uid is consumed so that the next generated name is different.
+            (ApiServerState {
+                uid_counter: if req.obj.metadata.name.is_Some() { s.uid_counter } else { s.uid_counter + 1 },
+                ..s
+            }, CreateResponse{res: Err(APIError::ObjectAlreadyExists)})
         } else if created_object_validity_check::<K>(created_obj).is_Some() {
             // Creation fails.
             (s, CreateResponse{res: Err(created_object_validity_check::<K>(created_obj).get_Some_0())})
@@ -266,21 +313,156 @@ pub open spec fn handle_create_request<K: CustomResourceView>(req: CreateRequest
 // modeling and proof much easier compared to modelling the real clock.
 pub closed spec fn deletion_timestamp() -> StringView;
 
-pub open spec fn handle_delete_request(req: DeleteRequest, s: ApiServerState) -> (ApiServerState, DeleteResponse) {
+pub open spec fn delete_request_admission_check(req: DeleteRequest, s: ApiServerState) -> Option<APIError> {
     if !s.resources.contains_key(req.key) {
+        Some(APIError::ObjectNotFound)
+    } else if req.preconditions.is_Some() && !req.preconditions.get_Some_0().satisfied_by(s.resources[req.key].metadata) {
+        // The object with the key has been deleted and recreated (different uid),
+        // or has been updated (different resource version) since the requester read it.
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+pub open spec fn orphan_finalizer() -> StringView { "orphan"@ }
+
+pub open spec fn foreground_deletion_finalizer() -> StringView { "foregroundDeletion"@ }
+
+// The finalizer added to the object deleted with the propagation policy.
+// Background deletion (the default) does not add any finalizer.
+pub open spec fn propagation_finalizer(propagation_policy: Option<PropagationPolicy>) -> Option<StringView> {
+    match propagation_policy {
+        Some(PropagationPolicy::Orphan) => Some(orphan_finalizer()),
+        Some(PropagationPolicy::Foreground) => Some(foreground_deletion_finalizer()),
+        _ => None,
+    }
+}
+
+pub open spec fn finalizers_with(finalizers: Option<Seq<StringView>>, finalizer: StringView) -> Seq<StringView> {
+    let old_finalizers = finalizers.unwrap_or(Seq::empty());
+    if old_finalizers.contains(finalizer) {
+        old_finalizers
+    } else {
+        old_finalizers.push(finalizer)
+    }
+}
+
+// The grace period of a pod (if not specified by the delete request) is its terminationGracePeriodSeconds (30 by default).
+// The real API server uses 0 for a pod that is not scheduled yet or has already terminated,
+// but our pod model has neither spec.nodeName nor status.phase, so we treat every pod as scheduled and running.
+// This is the case where a deleted pod lingers the longest, so controllers cannot rely on pods disappearing immediately.
+pub open spec fn default_pod_deletion_grace_period_seconds(obj: DynamicObjectView) -> int {
+    let pod = PodView::unmarshal(obj);
+    if pod.is_Ok() && pod.get_Ok_0().spec.is_Some() && pod.get_Ok_0().spec.get_Some_0().termination_grace_period_seconds.is_Some() {
+        pod.get_Ok_0().spec.get_Some_0().termination_grace_period_seconds.get_Some_0()
+    } else {
+        30
+    }
+}
+
+// The grace period for deleting obj, which is None for the kinds that do not support graceful deletion.
+// For now only pods support graceful deletion.
+pub open spec fn deletion_grace_period_seconds(req: DeleteRequest, obj: DynamicObjectView) -> Option<int> {
+    if obj.kind == Kind::PodKind {
+        if req.grace_period_seconds.is_Some() {
+            req.grace_period_seconds
+        } else {
+            Some(default_pod_deletion_grace_period_seconds(obj))
+        }
+    } else {
+        None
+    }
+}
+
+// The object is deleted gracefully if the grace period is positive
+// and shorter than the one set by a previous delete request (if any).
+// A later delete request can only shorten the grace period but not extend it.
+pub open spec fn graceful_deletion(req: DeleteRequest, obj: DynamicObjectView) -> bool {
+    let grace_period_seconds = deletion_grace_period_seconds(req, obj);
+    &&& grace_period_seconds.is_Some()
+    &&& grace_period_seconds.get_Some_0() > 0
+    &&& (obj.metadata.deletion_grace_period_seconds.is_None()
+        || grace_period_seconds.get_Some_0() < obj.metadata.deletion_grace_period_seconds.get_Some_0())
+}
+
+// A graceful deletion is pending when the object is already gracefully terminating
+// and the request does not shorten its grace period.
+pub open spec fn pending_graceful_deletion(req: DeleteRequest, obj: DynamicObjectView) -> bool {
+    let grace_period_seconds = deletion_grace_period_seconds(req, obj);
+    &&& obj.metadata.deletion_timestamp.is_Some()
+    &&& obj.metadata.deletion_grace_period_seconds.is_Some()
+    &&& obj.metadata.deletion_grace_period_seconds.get_Some_0() > 0
+    &&& grace_period_seconds.is_Some()
+    &&& grace_period_seconds.get_Some_0() >= obj.metadata.deletion_grace_period_seconds.get_Some_0()
+}
+
+pub open spec fn handle_delete_request(req: DeleteRequest, s: ApiServerState) -> (ApiServerState, DeleteResponse) {
+    if delete_request_admission_check(req, s).is_Some() {
         // Deletion fails.
-        (s, DeleteResponse{res: Err(APIError::ObjectNotFound)})
+        (s, DeleteResponse{res: Err(delete_request_admission_check(req, s).get_Some_0())})
     } else {
         // Deletion succeeds.
         let obj = s.resources[req.key];
-        if obj.metadata.finalizers.is_Some() && obj.metadata.finalizers.get_Some_0().len() > 0 {
+        if graceful_deletion(req, obj) {
+            // With a positive grace period, the object cannot be immediately deleted.
+            // Instead, we set the deletion timestamp and the grace period,
+            // and the object is deleted later (by the kubelet) with a zero grace period.
+            // The finalizer of the propagation policy is also added if this is the first delete request.
+            let finalizers = if obj.metadata.deletion_timestamp.is_None() && propagation_finalizer(req.propagation_policy).is_Some() {
+                Some(finalizers_with(obj.metadata.finalizers, propagation_finalizer(req.propagation_policy).get_Some_0()))
+            } else {
+                obj.metadata.finalizers
+            };
+            let stamped_obj_with_new_rv = obj.set_metadata(ObjectMetaView {
+                                                    finalizers: finalizers,
+                                                    ..obj.metadata.set_deletion_grace_period_seconds(deletion_grace_period_seconds(req, obj).get_Some_0())
+                                                })
+                                                .set_deletion_timestamp(deletion_timestamp())
+                                                .set_resource_version(s.resource_version_counter);
+            (ApiServerState {
+                resources: s.resources.insert(req.key, stamped_obj_with_new_rv),
+                resource_version_counter: s.resource_version_counter + 1,
+                ..s
+            }, DeleteResponse{res: Ok(())})
+        } else if pending_graceful_deletion(req, obj) {
+            // The object is already being deleted gracefully and the request does not shorten the grace period,
+            // so the request changes nothing. The object is still deleted later (by the kubelet).
+            (s, DeleteResponse{res: Ok(())})
+        } else if obj.metadata.deletion_timestamp.is_None() && propagation_finalizer(req.propagation_policy).is_Some() {
+            // With the orphan or foreground propagation policy, the object cannot be immediately deleted.
+            // Instead, we add the corresponding finalizer and set the deletion timestamp,
+            // so the garbage collector handles the dependents before the object is removed.
+            let finalizers = finalizers_with(obj.metadata.finalizers, propagation_finalizer(req.propagation_policy).get_Some_0());
+            let stamped_obj_with_new_rv = obj.set_metadata(obj.metadata.set_finalizers(finalizers).set_deletion_grace_period_seconds(0))
+                                                .set_deletion_timestamp(deletion_timestamp())
+                                                .set_resource_version(s.resource_version_counter);
+            (ApiServerState {
+                resources: s.resources.insert(req.key, stamped_obj_with_new_rv),
+                resource_version_counter: s.resource_version_counter + 1,
+                ..s
+            }, DeleteResponse{res: Ok(())})
+        } else if obj.metadata.finalizers.is_Some() && obj.metadata.finalizers.get_Some_0().len() > 0 {
             // With the finalizer(s) in the object, we cannot immediately delete it from the key-value store.
             // Instead, we set the deletion timestamp of this object.
-            // If the object already has a deletion timestamp, then skip.
+            // If the object already has a deletion timestamp, then skip,
+            // unless the request shortens the grace period of a graceful deletion to zero.
             if obj.metadata.deletion_timestamp.is_Some() {
-                (s, DeleteResponse{res: Ok(())})
+                if obj.metadata.deletion_grace_period_seconds.is_Some() && obj.metadata.deletion_grace_period_seconds.get_Some_0() > 0
+                && deletion_grace_period_seconds(req, obj) == Some(0int) {
+                    let obj_with_new_rv = obj.set_metadata(obj.metadata.set_deletion_grace_period_seconds(0))
+                                            .set_resource_version(s.resource_version_counter);
+                    (ApiServerState {
+                        resources: s.resources.insert(req.key, obj_with_new_rv),
+                        resource_version_counter: s.resource_version_counter + 1,
+                        ..s
+                    }, DeleteResponse{res: Ok(())})
+                } else {
+                    (s, DeleteResponse{res: Ok(())})
+                }
             } else {
-                let stamped_obj_with_new_rv = obj.set_deletion_timestamp(deletion_timestamp())
+                let stamped_obj_with_new_rv = obj.set_metadata(obj.metadata.set_deletion_grace_period_seconds(0))
+                                                    .set_deletion_timestamp(deletion_timestamp())
                                                     .set_resource_version(s.resource_version_counter);
                 (ApiServerState {
                     // Here we use req.key, instead of stamped_obj.object_ref(), to insert to the map.
@@ -309,7 +491,7 @@ pub open spec fn handle_delete_request(req: DeleteRequest, s: ApiServerState) ->
 // Note that if the resource version is provided, it has to be the correct one.
 pub open spec fn allow_unconditional_update(kind: Kind) -> bool {
     match kind {
-        Kind::CustomResourceKind => false,
+        Kind::CustomResourceKind(_) => false,
         _ => true,
     }
 }
@@ -370,6 +552,7 @@ pub open spec fn updated_object(req: UpdateRequest, old_obj: DynamicObjectView)
             resource_version: old_obj.metadata.resource_version, // Overwrite rv since it might not be provided
             uid: old_obj.metadata.uid, // Overwrite uid since it might not be provided
             deletion_timestamp: old_obj.metadata.deletion_timestamp, // Ignore any change to deletion_timestamp
+            deletion_grace_period_seconds: old_obj.metadata.deletion_grace_period_seconds, // Ignore any change to deletion_grace_period_seconds
             ..req.obj.metadata
         },
         spec: req.obj.spec,
@@ -452,8 +635,26 @@ pub open spec fn handle_update_request<K: CustomResourceView>(req: UpdateRequest
     }
 }
 
+// Status is a subresource only for the kinds that have a status field.
+// For the other kinds, Kubernetes does not serve the status endpoint at all.
+pub open spec fn has_status_subresource(kind: Kind) -> bool {
+    match kind {
+        Kind::ConfigMapKind => false,
+        Kind::RoleKind => false,
+        Kind::RoleBindingKind => false,
+        Kind::SecretKind => false,
+        Kind::ServiceAccountKind => false,
+        _ => true,
+    }
+}
+
 pub open spec fn update_status_request_admission_check<K: CustomResourceView>(req: UpdateStatusRequest, s: ApiServerState) -> Option<APIError> {
-    update_request_admission_check_helper::<K>(req.name, req.namespace, req.obj, s)
+    if !has_status_subresource(req.obj.kind) {
+        // Kubernetes returns NotFound since the status endpoint does not exist
+        Some(APIError::ObjectNotFound)
+    } else {
+        update_request_admission_check_helper::<K>(req.name, req.namespace, req.obj, s)
+    }
 }
 
 pub open spec fn status_updated_object(req: UpdateStatusRequest, old_obj: DynamicObjectView) -> DynamicObjectView {
@@ -498,6 +699,389 @@ pub open spec fn handle_update_status_request<K: CustomResourceView>(req: Update
     }
 }
 
+// Server-side apply merges the applied object into the existing one by field ownership:
+// each field manager owns the fields it applies (see FieldPathView), applying a field owned by another manager
+// with a different value conflicts unless forced, and a field that the applier stops applying is removed
+// unless another manager still owns it.
+// We only track the ownership recorded by apply, so the other requests keep the managed fields of the object as they are
+// (Kubernetes also records the fields set by create, update and patch under their own managers).
+pub open spec fn apply_request_admission_check<K: CustomResourceView>(req: ApplyRequest, s: ApiServerState) -> Option<APIError> {
+    if req.field_manager.len() == 0 {
+        // Apply fails because the field manager is required for apply
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata.name.is_None() {
+        // Apply fails because the name of the object is not provided
+        Some(APIError::BadRequest)
+    } else if req.name != req.obj.metadata.name.get_Some_0() {
+        // Apply fails because the name of the provided object
+        // does not match the name sent on the request
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata.namespace.is_Some()
+        && req.namespace != req.obj.metadata.namespace.get_Some_0() {
+        // Apply fails because the namespace of the provided object
+        // does not match the namespace sent on the request
+        Some(APIError::BadRequest)
+    } else if !unmarshallable_object::<K>(req.obj) {
+        // Apply fails because the provided object is not well formed
+        Some(APIError::BadRequest)
+    } else if req.obj.metadata.resource_version.is_Some()
+        && (!s.resources.contains_key(req.key())
+            || req.obj.metadata.resource_version != s.resources[req.key()].metadata.resource_version) {
+        // Apply fails because the object provides a rv as the precondition but the rv does not match
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+// value_fields is the set of paths to the leaf values set in value (e.g., the spec of the applied object),
+// value_at is the value at the path (null if the path is not set),
+// and applied_value merges value into old_value after removing the fields in removed_fields.
+// They are left opaque because spec is an opaque value in our model,
+// and they are validated by the executable model (crate::executable_model::apply) and the conformance tests.
+pub closed spec fn value_fields(value: Value) -> Set<Seq<StringView>>;
+
+pub closed spec fn value_at(value: Value, path: Seq<StringView>) -> Value;
+
+pub closed spec fn applied_value(old_value: Value, value: Value, removed_fields: Set<Seq<StringView>>) -> Value;
+
+// The fields that obj sets, which the applier owns after applying obj.
+pub open spec fn applied_fields(obj: DynamicObjectView) -> Set<FieldPathView> {
+    Set::new(|f: FieldPathView| match f {
+        FieldPathView::Label(key) => obj.metadata.labels.is_Some() && obj.metadata.labels.get_Some_0().contains_key(key),
+        FieldPathView::Annotation(key) => obj.metadata.annotations.is_Some() && obj.metadata.annotations.get_Some_0().contains_key(key),
+        FieldPathView::Finalizer(finalizer) => obj.metadata.finalizers.unwrap_or(Seq::empty()).contains(finalizer),
+        FieldPathView::OwnerReference(uid) => owner_references_with_uid(obj.metadata.owner_references, uid).len() > 0,
+        FieldPathView::Spec(path) => value_fields(obj.spec).contains(path),
+    })
+}
+
+pub open spec fn owner_references_with_uid(owner_references: Option<Seq<OwnerReferenceView>>, uid: Uid) -> Seq<OwnerReferenceView> {
+    owner_references.unwrap_or(Seq::empty()).filter(|owner_reference: OwnerReferenceView| owner_reference.uid == uid)
+}
+
+pub open spec fn string_map_value_changed(obj_map: Option<Map<StringView, StringView>>, old_map: Option<Map<StringView, StringView>>, key: StringView) -> bool {
+    let map = obj_map.unwrap_or(Map::empty());
+    let old = old_map.unwrap_or(Map::empty());
+    if map.contains_key(key) {
+        !old.contains_key(key) || map[key] != old[key]
+    } else {
+        old.contains_key(key)
+    }
+}
+
+// Whether obj sets the field f to a different value than old_obj.
+pub open spec fn field_changed(obj: DynamicObjectView, old_obj: DynamicObjectView, f: FieldPathView) -> bool {
+    match f {
+        FieldPathView::Label(key) => string_map_value_changed(obj.metadata.labels, old_obj.metadata.labels, key),
+        FieldPathView::Annotation(key) => string_map_value_changed(obj.metadata.annotations, old_obj.metadata.annotations, key),
+        // A finalizer is the value of its own item, so applying it never changes it.
+        FieldPathView::Finalizer(_) => false,
+        FieldPathView::OwnerReference(uid) => owner_references_with_uid(obj.metadata.owner_references, uid)
+            != owner_references_with_uid(old_obj.metadata.owner_references, uid),
+        FieldPathView::Spec(path) => value_at(obj.spec, path) != value_at(old_obj.spec, path),
+    }
+}
+
+pub open spec fn managed_fields_of(obj: DynamicObjectView) -> Seq<ManagedFieldsEntryView> {
+    obj.metadata.managed_fields.unwrap_or(Seq::empty())
+}
+
+// The fields owned by the applier of req in old_obj.
+pub open spec fn fields_of_applier(req: ApplyRequest, old_obj: DynamicObjectView) -> Set<FieldPathView> {
+    Set::new(|f: FieldPathView| exists |i| 0 <= i < managed_fields_of(old_obj).len()
+        && #[trigger] managed_fields_of(old_obj)[i].manager == Some(req.field_manager)
+        && managed_fields_of(old_obj)[i].fields.contains(f))
+}
+
+// The fields owned by the managers other than the applier of req in old_obj.
+pub open spec fn fields_of_other_managers(req: ApplyRequest, old_obj: DynamicObjectView) -> Set<FieldPathView> {
+    Set::new(|f: FieldPathView| exists |i| 0 <= i < managed_fields_of(old_obj).len()
+        && #[trigger] managed_fields_of(old_obj)[i].manager != Some(req.field_manager)
+        && managed_fields_of(old_obj)[i].fields.contains(f))
+}
+
+// The fields that req changes but are owned by other managers.
+pub open spec fn conflicting_fields(req: ApplyRequest, old_obj: DynamicObjectView) -> Set<FieldPathView> {
+    Set::new(|f: FieldPathView| applied_fields(req.obj).contains(f)
+        && fields_of_other_managers(req, old_obj).contains(f)
+        && field_changed(req.obj, old_obj, f))
+}
+
+// Apply conflicts if it changes any field owned by other managers and is not forced.
+pub open spec fn apply_conflicts(req: ApplyRequest, old_obj: DynamicObjectView) -> bool {
+    &&& !req.force
+    &&& exists |f: FieldPathView| #[trigger] conflicting_fields(req, old_obj).contains(f)
+}
+
+// The fields that the applier owned but no longer applies, and no one else owns.
+// These fields are removed from the object.
+pub open spec fn removed_fields(req: ApplyRequest, old_obj: DynamicObjectView) -> Set<FieldPathView> {
+    Set::new(|f: FieldPathView| fields_of_applier(req, old_obj).contains(f)
+        && !applied_fields(req.obj).contains(f)
+        && !fields_of_other_managers(req, old_obj).contains(f))
+}
+
+pub open spec fn applier_managed_fields_entry(req: ApplyRequest) -> ManagedFieldsEntryView {
+    ManagedFieldsEntryView::default()
+        .set_manager(req.field_manager)
+        .set_operation(apply_operation())
+        .set_fields(applied_fields(req.obj))
+}
+
+// The other managers keep their fields except the conflicting ones, which are taken over by a forced apply
+// (there is no conflicting field if the apply is not forced since it fails otherwise).
+// The managers that no longer own any field are dropped, and the applier's entry is replaced by the new one.
+pub open spec fn managed_fields_after_apply(req: ApplyRequest, old_obj: DynamicObjectView) -> Seq<ManagedFieldsEntryView> {
+    managed_fields_of(old_obj)
+        .filter(|entry: ManagedFieldsEntryView| entry.manager != Some(req.field_manager))
+        .map_values(|entry: ManagedFieldsEntryView| entry.set_fields(entry.fields.difference(conflicting_fields(req, old_obj))))
+        .filter(|entry: ManagedFieldsEntryView| entry.fields != Set::<FieldPathView>::empty())
+        .push(applier_managed_fields_entry(req))
+}
+
+// Labels and annotations are merged key by key.
+pub open spec fn applied_string_map(old_map: Option<Map<StringView, StringView>>, applied_map: Option<Map<StringView, StringView>>, removed_keys: Set<StringView>) -> Option<Map<StringView, StringView>> {
+    if old_map.is_None() && applied_map.is_None() {
+        None
+    } else {
+        Some(old_map.unwrap_or(Map::empty()).remove_keys(removed_keys).union_prefer_right(applied_map.unwrap_or(Map::empty())))
+    }
+}
+
+// Finalizers are merged as a set and owner references as a map keyed by uid, i.e., item by item:
+// the items that the applier stops applying are removed (unless another manager owns them),
+// and each applied item replaces the old item with the same key.
+// The old items that are not applied stay where they are, and the applied items follow the applied order,
+// which is how Kubernetes (structured-merge-diff) orders the merged list.
+pub open spec fn merged_list<T, Key>(old_list: Seq<T>, list: Seq<T>, key: spec_fn(T) -> Key) -> Seq<T>
+    decreases list.len(), old_list.len(),
+{
+    if list.len() == 0 {
+        old_list
+    } else if old_list.len() == 0 {
+        list
+    } else if !list.map_values(key).contains(key(old_list[0])) {
+        seq![old_list[0]] + merged_list(old_list.drop_first(), list, key)
+    } else {
+        seq![list[0]] + merged_list(old_list.filter(|item: T| key(item) != key(list[0])), list.drop_first(), key)
+    }
+}
+
+// An empty list is omitted from the metadata, same as the Kubernetes API.
+pub open spec fn applied_list<T, Key>(old_list: Option<Seq<T>>, list: Option<Seq<T>>, key: spec_fn(T) -> Key, removed_keys: Set<Key>) -> Option<Seq<T>> {
+    let merged = merged_list(
+        old_list.unwrap_or(Seq::empty()).filter(|item: T| !removed_keys.contains(key(item))),
+        list.unwrap_or(Seq::empty()),
+        key
+    );
+    if merged.len() == 0 {
+        None
+    } else {
+        Some(merged)
+    }
+}
+
+// applied_object merges the applied object into old_obj.
+// All the other fields (e.g., name, namespace, rv, uid, deletion timestamp and status) are kept as they are.
+pub open spec fn applied_object(req: ApplyRequest, old_obj: DynamicObjectView) -> DynamicObjectView {
+    let removed = removed_fields(req, old_obj);
+    DynamicObjectView {
+        kind: req.obj.kind,
+        metadata: ObjectMetaView {
+            labels: applied_string_map(old_obj.metadata.labels, req.obj.metadata.labels,
+                Set::new(|key: StringView| removed.contains(FieldPathView::Label(key)))),
+            annotations: applied_string_map(old_obj.metadata.annotations, req.obj.metadata.annotations,
+                Set::new(|key: StringView| removed.contains(FieldPathView::Annotation(key)))),
+            finalizers: applied_list(old_obj.metadata.finalizers, req.obj.metadata.finalizers, |finalizer: StringView| finalizer,
+                Set::new(|finalizer: StringView| removed.contains(FieldPathView::Finalizer(finalizer)))),
+            owner_references: applied_list(old_obj.metadata.owner_references, req.obj.metadata.owner_references,
+                |owner_reference: OwnerReferenceView| owner_reference.uid,
+                Set::new(|uid: Uid| removed.contains(FieldPathView::OwnerReference(uid)))),
+            managed_fields: Some(managed_fields_after_apply(req, old_obj)),
+            ..old_obj.metadata
+        },
+        spec: applied_value(old_obj.spec, req.obj.spec,
+            Set::new(|path: Seq<StringView>| removed.contains(FieldPathView::Spec(path)))),
+        status: old_obj.status, // Ignore any change to status
+    }
+}
+
+// After the applied object is merged into the old object, the API server handles the merged object
+// in the same way as an update request (validation, rv, and deletion if the last finalizer is removed).
+pub open spec fn update_request_from_apply(req: ApplyRequest, old_obj: DynamicObjectView) -> UpdateRequest {
+    UpdateRequest {
+        namespace: req.namespace,
+        name: req.name,
+        obj: applied_object(req, old_obj),
+    }
+}
+
+// The create request that apply turns into when the object does not exist.
+pub open spec fn create_request_from_apply(req: ApplyRequest) -> CreateRequest {
+    CreateRequest {
+        namespace: req.namespace,
+        obj: DynamicObjectView {
+            metadata: ObjectMetaView {
+                managed_fields: Some(seq![applier_managed_fields_entry(req)]),
+                ..req.obj.metadata
+            },
+            ..req.obj
+        },
+    }
+}
+
+#[verifier(inline)]
+pub open spec fn handle_apply_request<K: CustomResourceView>(req: ApplyRequest, s: ApiServerState) -> (ApiServerState, ApplyResponse) {
+    if apply_request_admission_check::<K>(req, s).is_Some() {
+        // Apply fails.
+        (s, ApplyResponse{res: Err(apply_request_admission_check::<K>(req, s).get_Some_0())})
+    } else if !s.resources.contains_key(req.key()) {
+        // Apply creates the object because it does not exist.
+        let (s_prime, create_resp) = handle_create_request::<K>(create_request_from_apply(req), s);
+        (s_prime, ApplyResponse{res: create_resp.res})
+    } else {
+        let old_obj = s.resources[req.key()];
+        if apply_conflicts(req, old_obj) {
+            // Apply fails because it changes the fields owned by other managers.
+            (s, ApplyResponse{res: Err(APIError::Conflict)})
+        } else if !unmarshallable_object::<K>(applied_object(req, old_obj)) {
+            // Apply fails because the merged object is not well formed
+            (s, ApplyResponse{res: Err(APIError::BadRequest)})
+        } else {
+            let (s_prime, update_resp) = handle_update_request::<K>(update_request_from_apply(req, old_obj), s);
+            (s_prime, ApplyResponse{res: update_resp.res})
+        }
+    }
+}
+
+pub open spec fn patch_request_admission_check<K: CustomResourceView>(req: PatchRequest, s: ApiServerState) -> Option<APIError> {
+    if req.patch.metadata.name.is_Some() && req.key.name != req.patch.metadata.name.get_Some_0() {
+        // Patch fails because the name of the patch does not match the name sent on the request
+        Some(APIError::BadRequest)
+    } else if req.patch.metadata.namespace.is_Some() && req.key.namespace != req.patch.metadata.namespace.get_Some_0() {
+        // Patch fails because the namespace of the patch does not match the namespace sent on the request
+        Some(APIError::BadRequest)
+    } else if req.patch_type.is_StrategicMergePatch() && req.key.kind.is_CustomResourceKind() {
+        // Patch fails because strategic merge patch is not supported for custom resources
+        Some(APIError::NotSupported)
+    } else if !s.resources.contains_key(req.key) {
+        // Patch fails because the object does not exist
+        Some(APIError::ObjectNotFound)
+    } else if req.patch.metadata.resource_version.is_Some()
+        && req.patch.metadata.resource_version != s.resources[req.key].metadata.resource_version {
+        // Patch fails because the patch provides a rv as the precondition but the rv does not match
+        Some(APIError::Conflict)
+    } else if req.patch.metadata.uid.is_Some()
+        && req.patch.metadata.uid != s.resources[req.key].metadata.uid {
+        // Patch fails because the patch provides a uid as the precondition but the uid does not match
+        Some(APIError::Conflict)
+    } else {
+        None
+    }
+}
+
+// patched_value is how the patch merges a value (e.g., spec) into the old one.
+// It depends on the kind since strategic merge patch merges some lists by keys declared per kind.
+// It is left opaque because spec and status are opaque values in our model,
+// and it is validated by the executable model and the conformance tests.
+pub closed spec fn patched_value(patch_type: PatchType, kind: Kind, old_value: Value, patch_value: Value) -> Value;
+
+// Both merge patch and strategic merge patch merge maps key by key.
+pub open spec fn patched_string_map(old_map: Option<Map<StringView, StringView>>, patch_map: Option<Map<StringView, StringView>>) -> Option<Map<StringView, StringView>> {
+    if patch_map.is_None() {
+        old_map
+    } else {
+        Some(old_map.unwrap_or(Map::empty()).union_prefer_right(patch_map.get_Some_0()))
+    }
+}
+
+// Merge patch replaces the entire list,
+// while strategic merge patch keeps the old finalizers and appends the new ones (finalizers has patchStrategy=merge).
+pub open spec fn patched_finalizers(patch_type: PatchType, old_finalizers: Option<Seq<StringView>>, patch_finalizers: Option<Seq<StringView>>) -> Option<Seq<StringView>> {
+    if patch_finalizers.is_None() {
+        old_finalizers
+    } else {
+        match patch_type {
+            PatchType::MergePatch => patch_finalizers,
+            PatchType::StrategicMergePatch => {
+                let old_list = old_finalizers.unwrap_or(Seq::empty());
+                Some(old_list + patch_finalizers.get_Some_0().filter(|f: StringView| !old_list.contains(f)))
+            },
+        }
+    }
+}
+
+// Merge patch replaces the entire list,
+// while strategic merge patch merges the owner references by uid (ownerReferences has patchStrategy=merge and patchMergeKey=uid):
+// each old owner reference is replaced by the one in the patch with the same uid (if any),
+// and the owner references in the patch with new uids are appended.
+pub open spec fn patched_owner_references(patch_type: PatchType, old_owner_references: Option<Seq<OwnerReferenceView>>, patch_owner_references: Option<Seq<OwnerReferenceView>>) -> Option<Seq<OwnerReferenceView>> {
+    if patch_owner_references.is_None() {
+        old_owner_references
+    } else {
+        match patch_type {
+            PatchType::MergePatch => patch_owner_references,
+            PatchType::StrategicMergePatch => {
+                let old_list = old_owner_references.unwrap_or(Seq::empty());
+                let patch_list = patch_owner_references.get_Some_0();
+                let merged_old_list = old_list.map_values(|o: OwnerReferenceView|
+                    if exists |p: OwnerReferenceView| #[trigger] patch_list.contains(p) && p.uid == o.uid {
+                        choose |p: OwnerReferenceView| #[trigger] patch_list.contains(p) && p.uid == o.uid
+                    } else {
+                        o
+                    }
+                );
+                let new_list = patch_list.filter(|p: OwnerReferenceView| !old_list.map_values(|o: OwnerReferenceView| o.uid).contains(p.uid));
+                Some(merged_old_list + new_list)
+            },
+        }
+    }
+}
+
+// patched_object states which fields a patch can change:
+// labels, annotations, finalizers, owner references and spec.
+// All the other fields (e.g., name, namespace, rv, uid, deletion timestamp and status) are kept as they are.
+pub open spec fn patched_object(req: PatchRequest, old_obj: DynamicObjectView) -> DynamicObjectView {
+    let patched_obj = DynamicObjectView {
+        kind: req.key.kind,
+        metadata: ObjectMetaView {
+            labels: patched_string_map(old_obj.metadata.labels, req.patch.metadata.labels),
+            annotations: patched_string_map(old_obj.metadata.annotations, req.patch.metadata.annotations),
+            finalizers: patched_finalizers(req.patch_type, old_obj.metadata.finalizers, req.patch.metadata.finalizers),
+            owner_references: patched_owner_references(req.patch_type, old_obj.metadata.owner_references, req.patch.metadata.owner_references),
+            ..old_obj.metadata
+        },
+        spec: patched_value(req.patch_type, req.key.kind, old_obj.spec, req.patch.spec),
+        status: old_obj.status, // Ignore any change to status
+    };
+    patched_obj
+}
+
+// After the patch is merged into the old object, the API server handles the patched object
+// in the same way as an update request (validation, rv, and deletion if the last finalizer is removed).
+pub open spec fn update_request_from_patch(req: PatchRequest, old_obj: DynamicObjectView) -> UpdateRequest {
+    UpdateRequest {
+        namespace: req.key.namespace,
+        name: req.key.name,
+        obj: patched_object(req, old_obj),
+    }
+}
+
+#[verifier(inline)]
+pub open spec fn handle_patch_request<K: CustomResourceView>(req: PatchRequest, s: ApiServerState) -> (ApiServerState, PatchResponse) {
+    if patch_request_admission_check::<K>(req, s).is_Some() {
+        // Patch fails.
+        (s, PatchResponse{res: Err(patch_request_admission_check::<K>(req, s).get_Some_0())})
+    } else if !unmarshallable_object::<K>(patched_object(req, s.resources[req.key])) {
+        // Patch fails because the patched object is not well formed
+        (s, PatchResponse{res: Err(APIError::BadRequest)})
+    } else {
+        let (s_prime, update_resp) = handle_update_request::<K>(update_request_from_patch(req, s.resources[req.key]), s);
+        (s_prime, PatchResponse{res: update_resp.res})
+    }
+}
+
 impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
 
 pub open spec fn handle_get_request_msg(msg: MsgType<E>, s: ApiServerState) -> (ApiServerState, MsgType<E>)
@@ -552,7 +1136,25 @@ pub open spec fn handle_update_status_request_msg(msg: MsgType<E>, s: ApiServerS
     (s_prime, Message::form_update_status_resp_msg(msg, resp))
 }
 
-// etcd is modeled as a centralized map that handles get/list/create/delete/update
+pub open spec fn handle_apply_request_msg(msg: MsgType<E>, s: ApiServerState) -> (ApiServerState, MsgType<E>)
+    recommends
+        msg.content.is_apply_request(),
+{
+    let req = msg.content.get_apply_request();
+    let (s_prime, resp) = handle_apply_request::<K>(req, s);
+    (s_prime, Message::form_apply_resp_msg(msg, resp))
+}
+
+pub open spec fn handle_patch_request_msg(msg: MsgType<E>, s: ApiServerState) -> (ApiServerState, MsgType<E>)
+    recommends
+        msg.content.is_patch_request(),
+{
+    let req = msg.content.get_patch_request();
+    let (s_prime, resp) = handle_patch_request::<K>(req, s);
+    (s_prime, Message::form_patch_resp_msg(msg, resp))
+}
+
+// etcd is modeled as a centralized map that handles get/list/create/delete/update/apply/patch
 pub open spec fn transition_by_etcd(msg: MsgType<E>, s: ApiServerState) -> (ApiServerState, MsgType<E>)
     recommends
         msg.content.is_APIRequest(),
@@ -564,6 +1166,8 @@ pub open spec fn transition_by_etcd(msg: MsgType<E>, s: ApiServerState) -> (ApiS
         APIRequest::DeleteRequest(_) => Self::handle_delete_request_msg(msg, s),
         APIRequest::UpdateRequest(_) => Self::handle_update_request_msg(msg, s),
         APIRequest::UpdateStatusRequest(_) => Self::handle_update_status_request_msg(msg, s),
+        APIRequest::ApplyRequest(_) => Self::handle_apply_request_msg(msg, s),
+        APIRequest::PatchRequest(_) => Self::handle_patch_request_msg(msg, s),
     }
 }
 
@@ -608,6 +1212,9 @@ pub open spec fn kubernetes_api() -> ApiServerStateMachine<E::Input, E::Output>
         init: |s: ApiServerState| {
             &&& s.resources == Map::<ObjectRef, DynamicObjectView>::empty()
             &&& s.stable_resources == Set::<ObjectRef>::empty()
+            // Uids are allocated from a non-negative counter, so a generated name ends with the digits of the uid
+            // (see generated_name).
+            &&& s.uid_counter >= 0
         },
         actions: set![Self::handle_request()],
         step_to_action: |step: ApiServerStep| {
@@ -4,21 +4,20 @@
 use crate::external_api::spec::*;
 use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
-    api_server::types::ApiServerState, builtin_controllers::types::*, cluster::Cluster, message::*,
+    api_server::{state_machine::{foreground_deletion_finalizer, orphan_finalizer, propagation_finalizer}, types::ApiServerState},
+    builtin_controllers::types::*,
+    cluster::Cluster,
+    message::*,
 };
 use crate::reconciler::spec::reconciler::Reconciler;
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
 use crate::temporal_logic::defs::*;
+use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
 verus! {
 
-// TODO:
-// + Specify foreground deletion
-//
-// + Specify orphan dependents
-
 impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
 
 pub open spec fn garbage_collector_deletion_enabled(key: ObjectRef) -> StatePred<Self> {
@@ -56,8 +55,11 @@ pub open spec fn run_garbage_collector() -> BuiltinControllersAction<E::Input, E
             }
         },
         transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
-            let delete_req_msg = Message::built_in_controller_req_msg(Message::delete_req_msg_content(
-                input.key, input.rest_id_allocator.allocate().1
+            // The delete request carries the uid of the object as a precondition,
+            // so it does not delete another object that is created with the same name after the check above.
+            let preconditions = PreconditionsView::default().set_uid_from_object_meta(s.resources[input.key].metadata);
+            let delete_req_msg = Message::built_in_controller_req_msg(Message::delete_req_msg_content_with_options(
+                input.key, Some(preconditions), None, None, input.rest_id_allocator.allocate().1
             ));
             let s_prime = s;
             let output = BuiltinControllersActionOutput {
@@ -71,4 +73,244 @@ pub open spec fn run_garbage_collector() -> BuiltinControllersAction<E::Input, E
 
 }
 
+// The owner (referred by owner_reference from an object in namespace) is being deleted with the orphan propagation policy,
+// that is, it has a deletion timestamp and the orphan finalizer.
+pub open spec fn owner_is_orphaning(resources: StoredState, owner_reference: OwnerReferenceView, namespace: StringView) -> bool {
+    let owner_key = owner_reference_to_object_reference(owner_reference, namespace);
+    &&& resources.contains_key(owner_key)
+    &&& resources[owner_key].metadata.uid == Some(owner_reference.uid)
+    &&& resources[owner_key].metadata.deletion_timestamp.is_Some()
+    &&& resources[owner_key].metadata.finalizers.is_Some()
+    &&& resources[owner_key].metadata.finalizers.get_Some_0().contains(orphan_finalizer())
+}
+
+// The owner (referred by owner_reference from an object in namespace) is being deleted with the foreground propagation policy,
+// that is, it has a deletion timestamp and the foregroundDeletion finalizer.
+pub open spec fn owner_is_deleting_in_foreground(resources: StoredState, owner_reference: OwnerReferenceView, namespace: StringView) -> bool {
+    let owner_key = owner_reference_to_object_reference(owner_reference, namespace);
+    &&& resources.contains_key(owner_key)
+    &&& resources[owner_key].metadata.uid == Some(owner_reference.uid)
+    &&& resources[owner_key].metadata.deletion_timestamp.is_Some()
+    &&& resources[owner_key].metadata.finalizers.is_Some()
+    &&& resources[owner_key].metadata.finalizers.get_Some_0().contains(foreground_deletion_finalizer())
+}
+
+// The owner does not exist any more, or it has been deleted and recreated with a different uid.
+pub open spec fn owner_is_dangling(resources: StoredState, owner_reference: OwnerReferenceView, namespace: StringView) -> bool {
+    let owner_key = owner_reference_to_object_reference(owner_reference, namespace);
+    ||| !resources.contains_key(owner_key)
+    ||| resources[owner_key].metadata.uid != Some(owner_reference.uid)
+}
+
+// The finalizers contain the finalizer of the orphan or foreground propagation policy,
+// so the garbage collector orphans (or deletes) the dependents before the object is removed.
+pub open spec fn has_propagation_finalizer(finalizers: Option<Seq<StringView>>) -> bool {
+    &&& finalizers.is_Some()
+    &&& (finalizers.get_Some_0().contains(orphan_finalizer()) || finalizers.get_Some_0().contains(foreground_deletion_finalizer()))
+}
+
+// The request makes the API server add the finalizer of the orphan or foreground propagation policy to an object,
+// either by deleting the object with that propagation policy or by setting the finalizer directly.
+pub open spec fn request_uses_propagation_policy(req: APIRequest) -> bool {
+    match req {
+        APIRequest::CreateRequest(req) => has_propagation_finalizer(req.obj.metadata.finalizers),
+        APIRequest::DeleteRequest(req) => propagation_finalizer(req.propagation_policy).is_Some(),
+        APIRequest::UpdateRequest(req) => has_propagation_finalizer(req.obj.metadata.finalizers),
+        APIRequest::ApplyRequest(req) => has_propagation_finalizer(req.obj.metadata.finalizers),
+        APIRequest::PatchRequest(req) => has_propagation_finalizer(req.patch.metadata.finalizers),
+        _ => false,
+    }
+}
+
+// Some object in the cluster state still refers to the owner (with the key) by its uid.
+pub open spec fn has_dependents(resources: StoredState, key: ObjectRef) -> bool {
+    exists |other_key: ObjectRef| {
+        &&& #[trigger] resources.contains_key(other_key)
+        &&& other_key.namespace == key.namespace
+        &&& resources[other_key].metadata.owner_references.is_Some()
+        &&& exists |i| #![trigger resources[other_key].metadata.owner_references.get_Some_0()[i]]
+            0 <= i < resources[other_key].metadata.owner_references.get_Some_0().len()
+            && Some(resources[other_key].metadata.owner_references.get_Some_0()[i].uid) == resources[key].metadata.uid
+    }
+}
+
+// Some object in the cluster state still refers to the owner (with the key) by its uid with blockOwnerDeletion set,
+// which blocks the foreground deletion of the owner.
+pub open spec fn has_blocking_dependents(resources: StoredState, key: ObjectRef) -> bool {
+    exists |other_key: ObjectRef| {
+        &&& #[trigger] resources.contains_key(other_key)
+        &&& other_key.namespace == key.namespace
+        &&& resources[other_key].metadata.owner_references.is_Some()
+        &&& exists |i| #![trigger resources[other_key].metadata.owner_references.get_Some_0()[i]]
+            0 <= i < resources[other_key].metadata.owner_references.get_Some_0().len()
+            && Some(resources[other_key].metadata.owner_references.get_Some_0()[i].uid) == resources[key].metadata.uid
+            && resources[other_key].metadata.owner_references.get_Some_0()[i].block_owner_deletion == Some(true)
+    }
+}
+
+impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+
+// run_garbage_collector_orphan_dependent removes the owner references to the orphaning owners from the object with the key,
+// so the object outlives its owners.
+pub open spec fn run_garbage_collector_orphan_dependent() -> BuiltinControllersAction<E::Input, E::Output> {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let resources = s.resources;
+            let key = input.key;
+            let owner_references = resources[key].metadata.owner_references.get_Some_0();
+            &&& input.choice.is_GarbageCollectorOrphan()
+            &&& resources.contains_key(key)
+            &&& resources[key].metadata.owner_references.is_Some()
+            // At least one owner of the object is being deleted with the orphan propagation policy
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_orphaning(resources, owner_references[i], key.namespace)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let obj = s.resources[input.key];
+            let remaining_owner_references = obj.metadata.owner_references.get_Some_0().filter(
+                |o: OwnerReferenceView| !owner_is_orphaning(s.resources, o, input.key.namespace)
+            );
+            // The update request carries the resource version of obj,
+            // so it fails if the object has been changed since the check above.
+            let update_req_msg = Message::built_in_controller_req_msg(Message::update_req_msg_content(
+                input.key.namespace, input.key.name,
+                obj.set_metadata(obj.metadata.set_owner_references(remaining_owner_references)),
+                input.rest_id_allocator.allocate().1
+            ));
+            let s_prime = s;
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+// run_garbage_collector_remove_orphan_finalizer removes the orphan finalizer from the object with the key
+// after all its dependents are orphaned, so the object can be deleted.
+pub open spec fn run_garbage_collector_remove_orphan_finalizer() -> BuiltinControllersAction<E::Input, E::Output> {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let resources = s.resources;
+            let key = input.key;
+            &&& input.choice.is_GarbageCollectorFinalizer()
+            &&& resources.contains_key(key)
+            &&& resources[key].metadata.uid.is_Some()
+            &&& resources[key].metadata.deletion_timestamp.is_Some()
+            &&& resources[key].metadata.finalizers.is_Some()
+            &&& resources[key].metadata.finalizers.get_Some_0().contains(orphan_finalizer())
+            // No object refers to it as an owner any more
+            &&& !has_dependents(resources, key)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let obj = s.resources[input.key];
+            let remaining_finalizers = obj.metadata.finalizers.get_Some_0().filter(|f: StringView| f != orphan_finalizer());
+            let update_req_msg = Message::built_in_controller_req_msg(Message::update_req_msg_content(
+                input.key.namespace, input.key.name,
+                obj.set_metadata(obj.metadata.set_finalizers(remaining_finalizers)),
+                input.rest_id_allocator.allocate().1
+            ));
+            let s_prime = s;
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+// run_garbage_collector_foreground_dependent handles the object with the key whose owner is being deleted in the foreground.
+// If each owner of the object is either gone or being deleted in the foreground, the object is deleted
+// (also in the foreground if it has dependents itself, so the deletion cascades down).
+// Otherwise, the object still has an owner that is not being deleted, so only the references to the owners
+// being deleted in the foreground are removed.
+pub open spec fn run_garbage_collector_foreground_dependent() -> BuiltinControllersAction<E::Input, E::Output> {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let resources = s.resources;
+            let key = input.key;
+            let owner_references = resources[key].metadata.owner_references.get_Some_0();
+            &&& input.choice.is_GarbageCollectorForeground()
+            &&& resources.contains_key(key)
+            // The object is not being deleted yet
+            &&& resources[key].metadata.deletion_timestamp.is_None()
+            &&& resources[key].metadata.owner_references.is_Some()
+            // At least one owner of the object is being deleted with the foreground propagation policy
+            &&& exists |i| #![trigger owner_references[i]] 0 <= i < owner_references.len()
+                && owner_is_deleting_in_foreground(resources, owner_references[i], key.namespace)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let obj = s.resources[input.key];
+            let owner_references = obj.metadata.owner_references.get_Some_0();
+            let req_msg = if forall |i| #![trigger owner_references[i]] 0 <= i < owner_references.len() ==> {
+                ||| owner_is_dangling(s.resources, owner_references[i], input.key.namespace)
+                ||| owner_is_deleting_in_foreground(s.resources, owner_references[i], input.key.namespace)
+            } {
+                let preconditions = PreconditionsView::default().set_uid_from_object_meta(obj.metadata);
+                let propagation_policy = if has_dependents(s.resources, input.key) {
+                    Some(PropagationPolicy::Foreground)
+                } else {
+                    None
+                };
+                Message::built_in_controller_req_msg(Message::delete_req_msg_content_with_options(
+                    input.key, Some(preconditions), propagation_policy, None, input.rest_id_allocator.allocate().1
+                ))
+            } else {
+                let remaining_owner_references = owner_references.filter(
+                    |o: OwnerReferenceView| !owner_is_deleting_in_foreground(s.resources, o, input.key.namespace)
+                );
+                Message::built_in_controller_req_msg(Message::update_req_msg_content(
+                    input.key.namespace, input.key.name,
+                    obj.set_metadata(obj.metadata.set_owner_references(remaining_owner_references)),
+                    input.rest_id_allocator.allocate().1
+                ))
+            };
+            let s_prime = s;
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+// run_garbage_collector_remove_foreground_finalizer removes the foregroundDeletion finalizer from the object with the key
+// after all the dependents that block its deletion are gone, so the object can be deleted.
+// The dependents without blockOwnerDeletion do not block the removal of the object.
+pub open spec fn run_garbage_collector_remove_foreground_finalizer() -> BuiltinControllersAction<E::Input, E::Output> {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let resources = s.resources;
+            let key = input.key;
+            &&& input.choice.is_GarbageCollectorForegroundFinalizer()
+            &&& resources.contains_key(key)
+            &&& resources[key].metadata.uid.is_Some()
+            &&& resources[key].metadata.deletion_timestamp.is_Some()
+            &&& resources[key].metadata.finalizers.is_Some()
+            &&& resources[key].metadata.finalizers.get_Some_0().contains(foreground_deletion_finalizer())
+            &&& !has_blocking_dependents(resources, key)
+        },
+        transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let obj = s.resources[input.key];
+            let remaining_finalizers = obj.metadata.finalizers.get_Some_0().filter(|f: StringView| f != foreground_deletion_finalizer());
+            let update_req_msg = Message::built_in_controller_req_msg(Message::update_req_msg_content(
+                input.key.namespace, input.key.name,
+                obj.set_metadata(obj.metadata.set_finalizers(remaining_finalizers)),
+                input.rest_id_allocator.allocate().1
+            ));
+            let s_prime = s;
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(update_req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+}
+
 }
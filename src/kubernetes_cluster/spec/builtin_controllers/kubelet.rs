@@ -0,0 +1,53 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::spec::prelude::*;
+use crate::kubernetes_cluster::spec::{
+    api_server::types::ApiServerState, builtin_controllers::types::*, cluster::Cluster, message::*,
+};
+use crate::reconciler::spec::reconciler::Reconciler;
+use crate::state_machine::action::*;
+use crate::state_machine::state_machine::*;
+use crate::temporal_logic::defs::*;
+use vstd::{multiset::*, prelude::*};
+
+verus! {
+
+impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+
+// run_kubelet finishes the graceful deletion of a pod.
+// In the real world, the kubelet stops the containers of the pod within the grace period,
+// and then deletes the pod again with a zero grace period.
+// We do not model the containers or the clock, so the kubelet can finish the graceful deletion at any time.
+pub open spec fn run_kubelet() -> BuiltinControllersAction<E::Input, E::Output> {
+    Action {
+        precondition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let resources = s.resources;
+            let key = input.key;
+            &&& input.choice.is_Kubelet()
+            &&& key.kind == Kind::PodKind
+            &&& resources.contains_key(key)
+            // The pod is being deleted gracefully
+            &&& resources[key].metadata.deletion_timestamp.is_Some()
+            &&& resources[key].metadata.deletion_grace_period_seconds.is_Some()
+            &&& resources[key].metadata.deletion_grace_period_seconds.get_Some_0() > 0
+        },
+        transition: |input: BuiltinControllersActionInput, s: ApiServerState| {
+            let preconditions = PreconditionsView::default().set_uid_from_object_meta(s.resources[input.key].metadata);
+            let delete_req_msg = Message::built_in_controller_req_msg(Message::delete_req_msg_content_with_options(
+                input.key, Some(preconditions), None, Some(0), input.rest_id_allocator.allocate().1
+            ));
+            let s_prime = s;
+            let output = BuiltinControllersActionOutput {
+                send: Multiset::singleton(delete_req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+}
+
+}
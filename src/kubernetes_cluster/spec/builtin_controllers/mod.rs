@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 pub mod daemon_set_controller;
 pub mod garbage_collector;
+pub mod kubelet;
 pub mod stabilizer;
 pub mod state_machine;
 pub mod stateful_set_controller;
@@ -6,7 +6,7 @@ use crate::kubernetes_api_objects::spec::prelude::*;
 use crate::kubernetes_cluster::spec::{
     api_server::types::ApiServerState,
     builtin_controllers::{
-        garbage_collector::*, stabilizer::*, stateful_set_controller::*, types::*,
+        garbage_collector::*, kubelet::*, stabilizer::*, stateful_set_controller::*, types::*,
     },
     cluster::Cluster,
     message::*,
@@ -28,6 +28,11 @@ pub open spec fn builtin_controllers() -> BuiltinControllersStateMachine<E::Inpu
         },
         actions: set![
             Self::run_garbage_collector(),
+            Self::run_garbage_collector_orphan_dependent(),
+            Self::run_garbage_collector_remove_orphan_finalizer(),
+            Self::run_garbage_collector_foreground_dependent(),
+            Self::run_garbage_collector_remove_foreground_finalizer(),
+            Self::run_kubelet(),
             Self::run_stateful_set_controller(),
             Self::run_daemon_set_controller(),
             Self::run_stabilizer()
@@ -35,6 +40,11 @@ pub open spec fn builtin_controllers() -> BuiltinControllersStateMachine<E::Inpu
         step_to_action: |step: BuiltinControllersStep| {
             match step {
                 BuiltinControllersStep::RunGarbageCollector => Self::run_garbage_collector(),
+                BuiltinControllersStep::RunGarbageCollectorOrphanDependent => Self::run_garbage_collector_orphan_dependent(),
+                BuiltinControllersStep::RunGarbageCollectorRemoveOrphanFinalizer => Self::run_garbage_collector_remove_orphan_finalizer(),
+                BuiltinControllersStep::RunGarbageCollectorForegroundDependent => Self::run_garbage_collector_foreground_dependent(),
+                BuiltinControllersStep::RunGarbageCollectorRemoveForegroundFinalizer => Self::run_garbage_collector_remove_foreground_finalizer(),
+                BuiltinControllersStep::RunKubelet => Self::run_kubelet(),
                 BuiltinControllersStep::RunStatefulSetController => Self::run_stateful_set_controller(),
                 BuiltinControllersStep::RunDaemonSetController => Self::run_daemon_set_controller(),
                 BuiltinControllersStep::RunStabilizer => Self::run_stabilizer(),
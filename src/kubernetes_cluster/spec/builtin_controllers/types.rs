@@ -16,6 +16,11 @@ pub type StoredState = Map<ObjectRef, DynamicObjectView>;
 #[is_variant]
 pub enum BuiltinControllersStep {
     RunGarbageCollector,
+    RunGarbageCollectorOrphanDependent,
+    RunGarbageCollectorRemoveOrphanFinalizer,
+    RunGarbageCollectorForegroundDependent,
+    RunGarbageCollectorRemoveForegroundFinalizer,
+    RunKubelet,
     RunStatefulSetController,
     RunDaemonSetController,
     RunStabilizer,
@@ -24,6 +29,11 @@ pub enum BuiltinControllersStep {
 #[is_variant]
 pub enum BuiltinControllerChoice {
     GarbageCollector,
+    GarbageCollectorOrphan,
+    GarbageCollectorFinalizer,
+    GarbageCollectorForeground,
+    GarbageCollectorForegroundFinalizer,
+    Kubelet,
     StatefulSetController{ready_replicas: int},
     DaemonSetController{number_ready: int},
     Stabilizer,
@@ -0,0 +1,32 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::kubernetes_api_objects::spec::{common::*, dynamic::*};
+use crate::kubernetes_cluster::spec::api_server::types::ApiServerState;
+use vstd::prelude::*;
+
+verus! {
+
+/// CacheState models the local cache (i.e., the reflector stores) that the shim layer
+/// uses to serve get and list requests when cached reads are enabled.
+///
+/// The cache is populated by watching the Kubernetes API, so each entry reflects the
+/// state of the corresponding object in the API server at some earlier point.
+/// Different entries can be stale by different amounts since watch events for different
+/// objects are delivered independently.
+pub struct CacheState {
+    pub resources: StoredState,
+}
+
+impl CacheState {
+    /// The API server state seen by a read request that is answered by the cache.
+    /// Only the stored objects are replaced; the counters are irrelevant to get and list.
+    pub open spec fn as_api_server_state(self, s: ApiServerState) -> ApiServerState {
+        ApiServerState {
+            resources: self.resources,
+            ..s
+        }
+    }
+}
+
+}
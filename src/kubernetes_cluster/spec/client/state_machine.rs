@@ -3,8 +3,10 @@
 #![allow(unused_imports)]
 use super::types::*;
 use crate::external_api::spec::*;
-use crate::kubernetes_api_objects::spec::resource::*;
-use crate::kubernetes_cluster::spec::{cluster::Cluster, message::*};
+use crate::kubernetes_api_objects::spec::{api_method::*, dynamic::*, resource::*};
+use crate::kubernetes_cluster::spec::{
+    builtin_controllers::garbage_collector::has_propagation_finalizer, cluster::Cluster, message::*,
+};
 use crate::reconciler::spec::reconciler::Reconciler;
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
@@ -13,14 +15,20 @@ use vstd::{multiset::*, prelude::*};
 
 verus! {
 
-impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+// The client stands for the users of the cluster. It creates, updates and deletes the objects that it is expected to
+// manage (decided by expects), and reads any object. The client of a Cluster manages the custom resource objects and
+// the objects that the reconciler expects from the user (see Cluster::client_expects), while the client of a
+// MultiCluster manages the objects that all the hosted controllers expect from their users.
 
-pub open spec fn create_custom_resource() -> ClientAction<E::Input, E::Output> {
+pub open spec fn create_custom_resource<I, O>(expects: spec_fn(DynamicObjectView) -> bool) -> ClientAction<I, O> {
     Action {
         precondition: |input: ClientActionInput, s: ClientState| {
             &&& input.obj.metadata.name.is_Some()
             &&& input.obj.metadata.namespace.is_Some()
-            &&& (input.obj.kind == K::kind() || R::expect_from_user(input.obj))
+            &&& expects(input.obj)
+            // The orphan and foregroundDeletion finalizers are added by the API server when the object is deleted
+            // with the corresponding propagation policy, so the client does not set them by itself.
+            &&& !has_propagation_finalizer(input.obj.metadata.finalizers)
         },
         transition: |input: ClientActionInput, s: ClientState| {
             let create_req_msg = Message::client_req_msg(Message::create_req_msg_content(
@@ -39,12 +47,12 @@ pub open spec fn create_custom_resource() -> ClientAction<E::Input, E::Output> {
     }
 }
 
-pub open spec fn delete_custom_resource() -> ClientAction<E::Input, E::Output> {
+pub open spec fn delete_custom_resource<I, O>(expects: spec_fn(DynamicObjectView) -> bool) -> ClientAction<I, O> {
     Action {
         precondition: |input: ClientActionInput, s: ClientState| {
             &&& input.obj.metadata.name.is_Some()
             &&& input.obj.metadata.namespace.is_Some()
-            &&& (input.obj.kind == K::kind() || R::expect_from_user(input.obj))
+            &&& expects(input.obj)
         },
         transition: |input: ClientActionInput, s: ClientState| {
             let delete_req_msg = Message::client_req_msg(Message::delete_req_msg_content(
@@ -61,12 +69,13 @@ pub open spec fn delete_custom_resource() -> ClientAction<E::Input, E::Output> {
     }
 }
 
-pub open spec fn update_custom_resource() -> ClientAction<E::Input, E::Output> {
+pub open spec fn update_custom_resource<I, O>(expects: spec_fn(DynamicObjectView) -> bool) -> ClientAction<I, O> {
     Action {
         precondition: |input: ClientActionInput, s: ClientState| {
             &&& input.obj.metadata.name.is_Some()
             &&& input.obj.metadata.namespace.is_Some()
-            &&& (input.obj.kind == K::kind() || R::expect_from_user(input.obj))
+            &&& expects(input.obj)
+            &&& !has_propagation_finalizer(input.obj.metadata.finalizers)
         },
         transition: |input: ClientActionInput, s: ClientState| {
             let update_req_msg = Message::client_req_msg(Message::update_req_msg_content(
@@ -83,28 +92,78 @@ pub open spec fn update_custom_resource() -> ClientAction<E::Input, E::Output> {
     }
 }
 
-pub open spec fn client() -> ClientStateMachine<E::Input, E::Output> {
+/// The requests that the client can send by send_request: it reads any object, and creates or updates
+/// the objects it manages without the finalizers of the propagation policies.
+/// It never deletes objects this way, nor updates the status, nor applies or patches objects.
+pub open spec fn client_can_send_request(expects: spec_fn(DynamicObjectView) -> bool, req: APIRequest) -> bool {
+    match req {
+        APIRequest::GetRequest(_) => true,
+        APIRequest::ListRequest(_) => true,
+        APIRequest::CreateRequest(req) => expects(req.obj) && !has_propagation_finalizer(req.obj.metadata.finalizers),
+        APIRequest::UpdateRequest(req) => expects(req.obj) && !has_propagation_finalizer(req.obj.metadata.finalizers),
+        _ => false,
+    }
+}
+
+/// send_request sends the request as it is. Besides the users, it also stands for the other controllers
+/// running in the same cluster (see MultiCluster), whose requests are not necessarily formed as above.
+pub open spec fn send_request<I, O>(expects: spec_fn(DynamicObjectView) -> bool) -> ClientAction<I, O> {
+    Action {
+        precondition: |input: ClientActionInput, s: ClientState| {
+            &&& input.req.is_Some()
+            &&& client_can_send_request(expects, input.req.get_Some_0())
+        },
+        transition: |input: ClientActionInput, s: ClientState| {
+            let req_msg = Message::client_req_msg(MessageContent::APIRequest(
+                input.req.get_Some_0(), input.rest_id_allocator.allocate().1
+            ));
+
+            let s_prime = s;
+            let output = ClientActionOutput {
+                send: Multiset::singleton(req_msg),
+                rest_id_allocator: input.rest_id_allocator.allocate().0,
+            };
+            (s_prime, output)
+        },
+    }
+}
+
+pub open spec fn client_state_machine<I, O>(expects: spec_fn(DynamicObjectView) -> bool) -> ClientStateMachine<I, O> {
     StateMachine {
         init: |s: ClientState| {
             true
         },
-        actions: set![Self::create_custom_resource(), Self::delete_custom_resource(), Self::update_custom_resource()],
-        step_to_action: |step: Step| {
+        actions: set![create_custom_resource(expects), delete_custom_resource(expects), update_custom_resource(expects), send_request(expects)],
+        step_to_action: |step: ClientStep| {
             match step {
-                Step::CreateCustomResource(_) => Self::create_custom_resource(),
-                Step::UpdateCustomResource(_) => Self::update_custom_resource(),
-                Step::DeleteCustomResource(_) => Self::delete_custom_resource(),
+                ClientStep::CreateCustomResource(_) => create_custom_resource(expects),
+                ClientStep::UpdateCustomResource(_) => update_custom_resource(expects),
+                ClientStep::DeleteCustomResource(_) => delete_custom_resource(expects),
+                ClientStep::SendRequest(_) => send_request(expects),
             }
         },
-        action_input: |step: Step, input: RestIdAllocator| {
+        action_input: |step: ClientStep, input: RestIdAllocator| {
             match step {
-                Step::CreateCustomResource(obj) => ClientActionInput{ obj: obj, rest_id_allocator: input },
-                Step::UpdateCustomResource(obj) => ClientActionInput{ obj: obj, rest_id_allocator: input },
-                Step::DeleteCustomResource(obj) => ClientActionInput{ obj: obj, rest_id_allocator: input },
+                ClientStep::CreateCustomResource(obj) => ClientActionInput{ obj: obj, req: None, rest_id_allocator: input },
+                ClientStep::UpdateCustomResource(obj) => ClientActionInput{ obj: obj, req: None, rest_id_allocator: input },
+                ClientStep::DeleteCustomResource(obj) => ClientActionInput{ obj: obj, req: None, rest_id_allocator: input },
+                ClientStep::SendRequest(req) => ClientActionInput{ obj: arbitrary(), req: Some(req), rest_id_allocator: input },
             }
         }
     }
 }
 
+impl <K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+
+/// The client of the Cluster manages the custom resource objects and the objects that the reconciler expects from the user.
+pub open spec fn client_expects(obj: DynamicObjectView) -> bool {
+    obj.kind == K::kind() || R::expect_from_user(obj)
+}
+
+pub open spec fn client() -> ClientStateMachine<E::Input, E::Output> {
+    client_state_machine(|obj: DynamicObjectView| Self::client_expects(obj))
 }
+
+}
+
 }
@@ -1,7 +1,7 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
-use crate::kubernetes_api_objects::spec::{dynamic::*, resource::*};
+use crate::kubernetes_api_objects::spec::{api_method::*, dynamic::*, resource::*};
 use crate::kubernetes_cluster::spec::message::*;
 use crate::state_machine::action::*;
 use crate::state_machine::state_machine::*;
@@ -12,14 +12,18 @@ verus! {
 
 pub struct ClientState {}
 
-pub enum Step {
+#[is_variant]
+pub enum ClientStep {
     CreateCustomResource(DynamicObjectView),
     UpdateCustomResource(DynamicObjectView),
     DeleteCustomResource(DynamicObjectView),
+    SendRequest(APIRequest),
 }
 
 pub struct ClientActionInput {
     pub obj: DynamicObjectView,
+    /// The request sent by send_request, which is None for the other actions.
+    pub req: Option<APIRequest>,
     pub rest_id_allocator: RestIdAllocator,
 }
 
@@ -28,7 +32,7 @@ pub struct ClientActionOutput<I, O> {
     pub rest_id_allocator: RestIdAllocator,
 }
 
-pub type ClientStateMachine<I, O> = StateMachine<ClientState, RestIdAllocator, ClientActionInput, ClientActionOutput<I, O>, Step>;
+pub type ClientStateMachine<I, O> = StateMachine<ClientState, RestIdAllocator, ClientActionInput, ClientActionOutput<I, O>, ClientStep>;
 
 pub type ClientAction<I, O> = Action<ClientState, ClientActionInput, ClientActionOutput<I, O>>;
 
@@ -5,6 +5,7 @@ use crate::external_api::spec::*;
 use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, resource::*};
 use crate::kubernetes_cluster::spec::{
     api_server::types::ApiServerState,
+    cache::types::CacheState,
     client::types::ClientState,
     controller::types::{ControllerState, OngoingReconcile},
     external_api::types::ExternalAPIState,
@@ -34,6 +35,8 @@ pub struct Cluster<K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> {
     pub rest_id_allocator: RestIdAllocator,
     pub crash_enabled: bool,
     pub transient_failure_enabled: bool,
+    pub cache_state: CacheState,
+    pub stale_read_enabled: bool,
 }
 
 impl<K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
@@ -67,6 +70,11 @@ impl<K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R
         self.controller_state.scheduled_reconciles
     }
 
+    #[verifier(inline)]
+    pub open spec fn cached_resources(self) -> StoredState {
+        self.cache_state.resources
+    }
+
     pub open spec fn has_rest_id_counter_no_smaller_than(self, rest_id: nat) -> bool {
         self.rest_id_allocator.rest_id_counter >= rest_id
     }
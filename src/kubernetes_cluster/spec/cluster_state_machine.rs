@@ -5,11 +5,13 @@ use crate::external_api::spec::*;
 use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, resource::*};
 use crate::kubernetes_cluster::spec::{
+    api_server::state_machine::{handle_get_request, handle_list_request},
     api_server::types::{ApiServerAction, ApiServerActionInput},
     builtin_controllers::types::{
         BuiltinControllerChoice, BuiltinControllersAction, BuiltinControllersActionInput,
     },
-    client::types::ClientActionInput,
+    cache::types::CacheState,
+    client::types::{ClientActionInput, ClientStep},
     cluster::Cluster,
     controller::types::{
         ControllerAction, ControllerActionInput, ControllerState, OngoingReconcile,
@@ -29,13 +31,15 @@ pub enum Step<Msg> {
     ApiServerStep(Option<Msg>),
     BuiltinControllersStep((BuiltinControllerChoice, ObjectRef)),
     ControllerStep((Option<Msg>, Option<ObjectRef>)),
-    ClientStep(),
+    ClientStep(ClientStep),
     ExternalAPIStep(Option<Msg>),
     ScheduleControllerReconcileStep(ObjectRef),
     RestartController(),
     DisableCrash(),
     FailTransientlyStep((Msg, APIError)),
     DisableTransientFailure(),
+    SyncCacheStep(ObjectRef),
+    ServeReadFromCacheStep(Msg),
     StutterStep(),
 }
 
@@ -51,6 +55,25 @@ pub open spec fn init() -> StatePred<Self> {
         &&& (Self::external_api().init)(s.external_api_state)
         &&& s.crash_enabled
         &&& s.transient_failure_enabled
+        &&& !s.stale_read_enabled
+    }
+}
+
+/// init_with_stale_read is the same as init except that the controller's get and list requests
+/// can be answered by the local cache (see sync_cache and serve_read_from_cache below).
+/// This corresponds to running the shim layer with cached reads enabled.
+pub open spec fn init_with_stale_read() -> StatePred<Self> {
+    |s: Self| {
+        &&& (Self::kubernetes_api().init)(s.kubernetes_api_state)
+        &&& (Self::builtin_controllers().init)(s.kubernetes_api_state)
+        &&& (Self::controller().init)(s.controller_state)
+        &&& (Self::client().init)(s.client_state)
+        &&& (Self::network().init)(s.network_state)
+        &&& (Self::external_api().init)(s.external_api_state)
+        &&& s.crash_enabled
+        &&& s.transient_failure_enabled
+        &&& s.stale_read_enabled
+        &&& s.cache_state.resources == Map::<ObjectRef, DynamicObjectView>::empty()
     }
 }
 
@@ -212,7 +235,12 @@ pub open spec fn controller_next() -> Action<Self, (Option<MsgType<E>>, Option<O
 ///   -- so the first creation event will schedule a reconcile
 /// (2) The shim layer always re-queues `reconcile_with` unless the corresponding cr object does not exist,
 /// and the kube library always eventually invokes the re-queued `reconcile_with`
-///   -- so as long as the cr still exists, the reconcile will still be scheduled over and over again
+///   -- so as long as the cr still exists, the reconcile will still be scheduled over and over again.
+///   The requeue policy of the shim layer (see `RequeuePolicy`) only decides how long it takes: a reconcile that is
+///   done is re-queued after its `RequeueHint`, which is bounded by the resync period even for `AwaitChange`,
+///   and a reconcile that fails (or fails to get the cr object) is re-queued with exponential backoff bounded by the
+///   maximum backoff. Weak fairness only requires the reconcile to be scheduled eventually, not within any bound,
+///   so it still holds as long as the requeue policy never drops a re-queue, which it does not.
 /// (3) The shim layer always performs a quorum read to etcd to get the cr object and passes it to `reconcile_core`
 ///   -- so the reconcile is scheduled with the most recent view of the cr object when this action happens
 /// (4) The shim layer never invokes `reconcile_core` if the cr object does not exist
@@ -223,7 +251,7 @@ pub open spec fn schedule_controller_reconcile() -> Action<Self, ObjectRef, ()>
     Action {
         precondition: |input: ObjectRef, s: Self| {
             &&& s.resources().contains_key(input)
-            &&& input.kind.is_CustomResourceKind()
+            &&& input.kind == K::kind()
             &&& K::unmarshal(s.resources()[input]).is_Ok()
         },
         transition: |input: ObjectRef, s: Self| {
@@ -253,6 +281,29 @@ pub open spec fn restart_controller() -> Action<Self, (), ()> {
     }
 }
 
+/// This action models a leader failover when the controller runs as several replicas with leader election
+/// (see crate::shim_layer::leader_election): the leader performs its last controller step and then stops,
+/// and another replica acquires the lease and starts with the initial controller state.
+/// The requests sent by the old leader stay in flight and can be handled after the new leader starts.
+///
+/// This action is not a separate Step because it is exactly a controller step followed by restart_controller
+/// (see lemma_failover_controller_is_controller_step_then_restart), so every property proven for the Cluster
+/// also holds when the controller fails over to another replica.
+pub open spec fn failover_controller() -> Action<Self, (Option<MsgType<E>>, Option<ObjectRef>), ()> {
+    Action {
+        precondition: |input: (Option<MsgType<E>>, Option<ObjectRef>), s: Self| {
+            &&& s.crash_enabled
+            &&& (Self::controller_next().precondition)(input, s)
+        },
+        transition: |input: (Option<MsgType<E>>, Option<ObjectRef>), s: Self| {
+            (Self {
+                controller_state: Self::init_controller_state(),
+                ..(Self::controller_next().transition)(input, s).0
+            }, ())
+        },
+    }
+}
+
 /// This action disallows the controller to crash from this point.
 /// This is used to constraint the crash behavior for liveness proof:
 /// the controller eventually stops crashing.
@@ -271,7 +322,8 @@ pub open spec fn disable_crash() -> Action<Self, (), ()> {
 }
 
 /// This action fails a request sent to the Kubernetes API in a transient way:
-/// the request fails with timeout error or conflict error (caused by resource version conflicts).
+/// the request fails with any transient error (see APIError::is_transient), e.g., timeout error,
+/// conflict error (caused by resource version conflicts), throttling or expired resource versions.
 pub open spec fn fail_request_transiently() -> Action<Self, (MsgType<E>, APIError), ()> {
     let result = |input: (MsgType<E>, APIError), s: Self| {
         let req_msg = input.0;
@@ -291,7 +343,7 @@ pub open spec fn fail_request_transiently() -> Action<Self, (MsgType<E>, APIErro
             &&& s.transient_failure_enabled
             &&& req_msg.dst.is_ApiServer()
             &&& req_msg.content.is_APIRequest()
-            &&& (api_err.is_Timeout() || api_err.is_ServerTimeout() || api_err.is_Conflict())
+            &&& api_err.is_transient()
             &&& result(input, s).is_Enabled()
         },
         transition: |input: (MsgType<E>, APIError), s: Self| {
@@ -320,10 +372,72 @@ pub open spec fn disable_transient_failure() -> Action<Self, (), ()> {
     }
 }
 
-pub open spec fn client_next() -> Action<Self, (), ()> {
-    let result = |input: (), s: Self| {
-        let host_result = Self::client().next_result(
-            s.rest_id_allocator,
+/// This action delivers the latest watch event of the object identified by the key to the local cache:
+/// the cache entry is overwritten by the object stored in the Kubernetes API, or removed if the object no longer exists.
+/// Since each key is synced independently, different cache entries can lag behind by different amounts,
+/// but each entry only ever holds a version of the object that was once stored in the Kubernetes API.
+pub open spec fn sync_cache() -> Action<Self, ObjectRef, ()> {
+    Action {
+        precondition: |input: ObjectRef, s: Self| {
+            s.stale_read_enabled
+        },
+        transition: |input: ObjectRef, s: Self| {
+            let cached_resources_prime = if s.resources().contains_key(input) {
+                s.cache_state.resources.insert(input, s.resources()[input])
+            } else {
+                s.cache_state.resources.remove(input)
+            };
+            (Self {
+                cache_state: CacheState {
+                    resources: cached_resources_prime,
+                },
+                ..s
+            }, ())
+        },
+    }
+}
+
+/// This action answers a get or list request sent by the controller using the local cache
+/// instead of the objects stored in the Kubernetes API, which models the (potentially) stale reads
+/// served by the shim layer when cached reads are enabled.
+/// The response is computed by the same handle_get_request and handle_list_request used by the Kubernetes API,
+/// so the only difference is the snapshot of the objects that the request observes.
+pub open spec fn serve_read_from_cache() -> Action<Self, MsgType<E>, ()> {
+    let result = |input: MsgType<E>, s: Self| {
+        let cached_api_server_state = s.cache_state.as_api_server_state(s.kubernetes_api_state);
+        let resp = if input.content.is_get_request() {
+            Message::form_get_resp_msg(input, handle_get_request(input.content.get_get_request(), cached_api_server_state))
+        } else {
+            Message::form_list_resp_msg(input, handle_list_request(input.content.get_list_request(), cached_api_server_state))
+        };
+        let msg_ops = MessageOps {
+            recv: Some(input),
+            send: Multiset::singleton(resp),
+        };
+        Self::network().next_result(msg_ops, s.network_state)
+    };
+    Action {
+        precondition: |input: MsgType<E>, s: Self| {
+            &&& s.stale_read_enabled
+            &&& input.src.is_CustomController()
+            &&& input.dst.is_ApiServer()
+            &&& (input.content.is_get_request() || input.content.is_list_request())
+            &&& result(input, s).is_Enabled()
+        },
+        transition: |input: MsgType<E>, s: Self| {
+            (Self {
+                network_state: result(input, s).get_Enabled_0(),
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn client_next() -> Action<Self, ClientStep, ()> {
+    let result = |input: ClientStep, s: Self| {
+        let host_result = Self::client().next_action_result(
+            (Self::client().step_to_action)(input),
+            (Self::client().action_input)(input, s.rest_id_allocator),
             s.client_state
         );
         let msg_ops = MessageOps {
@@ -335,11 +449,11 @@ pub open spec fn client_next() -> Action<Self, (), ()> {
         (host_result, network_result)
     };
     Action {
-        precondition: |input: (), s: Self| {
+        precondition: |input: ClientStep, s: Self| {
             &&& result(input, s).0.is_Enabled()
             &&& result(input, s).1.is_Enabled()
         },
-        transition: |input: (), s: Self| {
+        transition: |input: ClientStep, s: Self| {
             let (host_result, network_result) = result(input, s);
             (Self {
                 client_state: host_result.get_Enabled_0(),
@@ -367,13 +481,15 @@ pub open spec fn next_step(s: Self, s_prime: Self, step: Step<MsgType<E>>) -> bo
         Step::ApiServerStep(input) => Self::kubernetes_api_next().forward(input)(s, s_prime),
         Step::BuiltinControllersStep(input) => Self::builtin_controllers_next().forward(input)(s, s_prime),
         Step::ControllerStep(input) => Self::controller_next().forward(input)(s, s_prime),
-        Step::ClientStep() => Self::client_next().forward(())(s, s_prime),
+        Step::ClientStep(input) => Self::client_next().forward(input)(s, s_prime),
         Step::ExternalAPIStep(input) => Self::external_api_next().forward(input)(s, s_prime),
         Step::ScheduleControllerReconcileStep(input) => Self::schedule_controller_reconcile().forward(input)(s, s_prime),
         Step::RestartController() => Self::restart_controller().forward(())(s, s_prime),
         Step::DisableCrash() => Self::disable_crash().forward(())(s, s_prime),
         Step::FailTransientlyStep(input) => Self::fail_request_transiently().forward(input)(s, s_prime),
         Step::DisableTransientFailure() => Self::disable_transient_failure().forward(())(s, s_prime),
+        Step::SyncCacheStep(input) => Self::sync_cache().forward(input)(s, s_prime),
+        Step::ServeReadFromCacheStep(input) => Self::serve_read_from_cache().forward(input)(s, s_prime),
         Step::StutterStep() => Self::stutter().forward(())(s, s_prime),
     }
 }
@@ -399,6 +515,12 @@ pub open spec fn sm_wf_spec() -> TempPred<Self> {
     .and(Self::disable_transient_failure().weak_fairness(()))
 }
 
+/// stale_read_wf_spec is the fairness assumption used together with init_with_stale_read:
+/// every object's latest state is always eventually delivered to the local cache.
+pub open spec fn stale_read_wf_spec() -> TempPred<Self> {
+    tla_forall(|input| Self::sync_cache().weak_fairness(input))
+}
+
 pub open spec fn kubernetes_api_action_pre(action: ApiServerAction<E::Input, E::Output>, input: Option<MsgType<E>>) -> StatePred<Self> {
     |s: Self| {
         let host_result = Self::kubernetes_api().next_action_result(
@@ -478,6 +600,10 @@ pub open spec fn controller_action_pre(action: ControllerAction<K, E, R>, input:
     }
 }
 
+pub open spec fn stale_read_disabled() -> StatePred<Self> {
+    |s: Self| !s.stale_read_enabled
+}
+
 pub open spec fn crash_disabled() -> StatePred<Self> {
     |s: Self| !s.crash_enabled
 }
@@ -3,7 +3,7 @@
 #![allow(unused_imports)]
 use crate::external_api::spec::*;
 use crate::kubernetes_api_objects::error::*;
-use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*};
+use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, preconditions::*};
 use crate::vstd_ext::string_view::*;
 use vstd::{multiset::*, prelude::*};
 
@@ -160,6 +160,42 @@ impl<I, O> MessageContent<I, O> {
         self.get_APIRequest_0().get_UpdateStatusRequest_0()
     }
 
+    pub open spec fn is_apply_request(self) -> bool {
+        &&& self.is_APIRequest()
+        &&& self.get_APIRequest_0().is_ApplyRequest()
+    }
+
+    pub open spec fn is_apply_request_with_key(self, key: ObjectRef) -> bool {
+        &&& self.is_APIRequest()
+        &&& self.get_APIRequest_0().is_ApplyRequest()
+        &&& self.get_APIRequest_0().get_ApplyRequest_0().key() == key
+    }
+
+    pub open spec fn get_apply_request(self) -> ApplyRequest
+        recommends
+            self.is_apply_request()
+    {
+        self.get_APIRequest_0().get_ApplyRequest_0()
+    }
+
+    pub open spec fn is_patch_request(self) -> bool {
+        &&& self.is_APIRequest()
+        &&& self.get_APIRequest_0().is_PatchRequest()
+    }
+
+    pub open spec fn is_patch_request_with_key(self, key: ObjectRef) -> bool {
+        &&& self.is_APIRequest()
+        &&& self.get_APIRequest_0().is_PatchRequest()
+        &&& self.get_APIRequest_0().get_PatchRequest_0().key == key
+    }
+
+    pub open spec fn get_patch_request(self) -> PatchRequest
+        recommends
+            self.is_patch_request()
+    {
+        self.get_APIRequest_0().get_PatchRequest_0()
+    }
+
     pub open spec fn is_get_response(self) -> bool {
         &&& self.is_APIResponse()
         &&& self.get_APIResponse_0().is_GetResponse()
@@ -220,6 +256,30 @@ impl<I, O> MessageContent<I, O> {
         self.get_APIResponse_0().get_ListResponse_0()
     }
 
+    pub open spec fn is_apply_response(self) -> bool {
+        &&& self.is_APIResponse()
+        &&& self.get_APIResponse_0().is_ApplyResponse()
+    }
+
+    pub open spec fn get_apply_response(self) -> ApplyResponse
+        recommends
+            self.is_apply_response()
+    {
+        self.get_APIResponse_0().get_ApplyResponse_0()
+    }
+
+    pub open spec fn is_patch_response(self) -> bool {
+        &&& self.is_APIResponse()
+        &&& self.get_APIResponse_0().is_PatchResponse()
+    }
+
+    pub open spec fn get_patch_response(self) -> PatchResponse
+        recommends
+            self.is_patch_response()
+    {
+        self.get_APIResponse_0().get_PatchResponse_0()
+    }
+
     pub open spec fn get_rest_id(self) -> RestId
     {
         match self {
@@ -239,6 +299,8 @@ pub open spec fn is_ok_resp(resp: APIResponse) -> bool {
         APIResponse::DeleteResponse(delete_resp) => delete_resp.res.is_Ok(),
         APIResponse::UpdateResponse(update_resp) => update_resp.res.is_Ok(),
         APIResponse::UpdateStatusResponse(update_status_resp) => update_status_resp.res.is_Ok(),
+        APIResponse::ApplyResponse(apply_resp) => apply_resp.res.is_Ok(),
+        APIResponse::PatchResponse(patch_resp) => patch_resp.res.is_Ok(),
     }
 }
 
@@ -274,6 +336,8 @@ pub open spec fn resp_msg_matches_req_msg(resp_msg: Message<I, O>, req_msg: Mess
             APIResponse::DeleteResponse(_) => req_msg.content.get_APIRequest_0().is_DeleteRequest(),
             APIResponse::UpdateResponse(_) => req_msg.content.get_APIRequest_0().is_UpdateRequest(),
             APIResponse::UpdateStatusResponse(_) => req_msg.content.get_APIRequest_0().is_UpdateStatusRequest(),
+            APIResponse::ApplyResponse(_) => req_msg.content.get_APIRequest_0().is_ApplyRequest(),
+            APIResponse::PatchResponse(_) => req_msg.content.get_APIRequest_0().is_PatchRequest(),
         }
     }
     ||| {
@@ -295,6 +359,8 @@ pub open spec fn form_matched_err_resp_msg(req_msg: Message<I, O>, err: APIError
         APIRequest::DeleteRequest(_) => Self::form_delete_resp_msg(req_msg, DeleteResponse{res: Err(err)}),
         APIRequest::UpdateRequest(_) => Self::form_update_resp_msg(req_msg, UpdateResponse{res: Err(err)}),
         APIRequest::UpdateStatusRequest(_) => Self::form_update_status_resp_msg(req_msg, UpdateStatusResponse{res: Err(err)}),
+        APIRequest::ApplyRequest(_) => Self::form_apply_resp_msg(req_msg, ApplyResponse{res: Err(err)}),
+        APIRequest::PatchRequest(_) => Self::form_patch_resp_msg(req_msg, PatchResponse{res: Err(err)}),
     }
 }
 
@@ -342,6 +408,18 @@ pub open spec fn form_update_status_resp_msg(req_msg: Message<I, O>, resp: Updat
     Self::form_msg(req_msg.dst, req_msg.src, Self::update_status_resp_msg_content(resp, req_msg.content.get_rest_id()))
 }
 
+pub open spec fn form_apply_resp_msg(req_msg: Message<I, O>, resp: ApplyResponse) -> Message<I, O>
+    recommends req_msg.content.is_apply_request(),
+{
+    Self::form_msg(req_msg.dst, req_msg.src, Self::apply_resp_msg_content(resp, req_msg.content.get_rest_id()))
+}
+
+pub open spec fn form_patch_resp_msg(req_msg: Message<I, O>, resp: PatchResponse) -> Message<I, O>
+    recommends req_msg.content.is_patch_request(),
+{
+    Self::form_msg(req_msg.dst, req_msg.src, Self::patch_resp_msg_content(resp, req_msg.content.get_rest_id()))
+}
+
 pub open spec fn form_external_resp_msg(req_msg: Message<I, O>, resp: O) -> Message<I, O>
     recommends req_msg.content.is_ExternalAPIRequest(),
 {
@@ -358,6 +436,8 @@ pub open spec fn list_req_msg_content(kind: Kind, namespace: StringView, req_id:
     MessageContent::APIRequest(APIRequest::ListRequest(ListRequest{
         kind: kind,
         namespace: namespace,
+        label_selector: None,
+        field_selector: None,
     }), req_id)
 }
 
@@ -371,6 +451,21 @@ pub open spec fn create_req_msg_content(namespace: StringView, obj: DynamicObjec
 pub open spec fn delete_req_msg_content(key: ObjectRef, req_id: RestId) -> MessageContent<I, O> {
     MessageContent::APIRequest(APIRequest::DeleteRequest(DeleteRequest{
         key: key,
+        preconditions: None,
+        propagation_policy: None,
+        grace_period_seconds: None,
+    }), req_id)
+}
+
+pub open spec fn delete_req_msg_content_with_options(
+    key: ObjectRef, preconditions: Option<PreconditionsView>, propagation_policy: Option<PropagationPolicy>,
+    grace_period_seconds: Option<int>, req_id: RestId
+) -> MessageContent<I, O> {
+    MessageContent::APIRequest(APIRequest::DeleteRequest(DeleteRequest{
+        key: key,
+        preconditions: preconditions,
+        propagation_policy: propagation_policy,
+        grace_period_seconds: grace_period_seconds,
     }), req_id)
 }
 
@@ -390,6 +485,24 @@ pub open spec fn update_status_req_msg_content(namespace: StringView, name: Stri
     }), req_id)
 }
 
+pub open spec fn apply_req_msg_content(namespace: StringView, name: StringView, obj: DynamicObjectView, field_manager: StringView, force: bool, req_id: RestId) -> MessageContent<I, O> {
+    MessageContent::APIRequest(APIRequest::ApplyRequest(ApplyRequest{
+        namespace: namespace,
+        name: name,
+        obj: obj,
+        field_manager: field_manager,
+        force: force,
+    }), req_id)
+}
+
+pub open spec fn patch_req_msg_content(key: ObjectRef, patch: DynamicObjectView, patch_type: PatchType, req_id: RestId) -> MessageContent<I, O> {
+    MessageContent::APIRequest(APIRequest::PatchRequest(PatchRequest{
+        key: key,
+        patch: patch,
+        patch_type: patch_type,
+    }), req_id)
+}
+
 pub open spec fn get_resp_msg_content(resp: GetResponse, resp_id: RestId) -> MessageContent<I, O> {
     MessageContent::APIResponse(APIResponse::GetResponse(resp), resp_id)
 }
@@ -414,6 +527,14 @@ pub open spec fn update_status_resp_msg_content(resp: UpdateStatusResponse, resp
     MessageContent::APIResponse(APIResponse::UpdateStatusResponse(resp), resp_id)
 }
 
+pub open spec fn apply_resp_msg_content(resp: ApplyResponse, resp_id: RestId) -> MessageContent<I, O> {
+    MessageContent::APIResponse(APIResponse::ApplyResponse(resp), resp_id)
+}
+
+pub open spec fn patch_resp_msg_content(resp: PatchResponse, resp_id: RestId) -> MessageContent<I, O> {
+    MessageContent::APIResponse(APIResponse::PatchResponse(resp), resp_id)
+}
+
 pub open spec fn external_resp_msg_content(resp: O, resp_id: RestId) -> MessageContent<I, O> {
     MessageContent::ExternalAPIResponse(resp, resp_id)
 }
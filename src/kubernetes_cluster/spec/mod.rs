@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: MIT
 pub mod api_server;
 pub mod builtin_controllers;
+pub mod cache;
 pub mod client;
 pub mod cluster;
 pub mod cluster_state_machine;
 pub mod controller;
 pub mod external_api;
 pub mod message;
+pub mod multi_cluster;
 pub mod network;
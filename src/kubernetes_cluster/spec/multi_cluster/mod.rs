@@ -0,0 +1,4 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+pub mod state_machine;
+pub mod types;
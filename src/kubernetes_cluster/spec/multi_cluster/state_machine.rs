@@ -0,0 +1,447 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::spec::{api_method::*, common::*, dynamic::*, resource::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::types::ApiServerActionInput,
+    builtin_controllers::types::{BuiltinControllerChoice, BuiltinControllersActionInput},
+    client::{state_machine::client_state_machine, types::{ClientState, ClientStateMachine, ClientStep}},
+    cluster::Cluster,
+    controller::types::{ControllerActionInput, ControllerActionOutput, ControllerState},
+    external_api::types::ExternalAPIActionInput,
+    message::*,
+    multi_cluster::types::*,
+};
+use crate::reconciler::spec::reconciler::Reconciler;
+use crate::state_machine::{action::*, state_machine::*};
+use crate::temporal_logic::defs::*;
+use vstd::{multiset::*, prelude::*};
+
+verus! {
+
+impl<K: CustomResourceView, E: ExternalAPI, R: Reconciler<K, E>> Cluster<K, E, R> {
+
+pub closed spec fn marshal_controller_state(s: ControllerState<K, E, R>) -> ErasedControllerState;
+
+pub closed spec fn unmarshal_controller_state(s: ErasedControllerState) -> ControllerState<K, E, R>;
+
+/// This is a trusted assumption: marshal_controller_state is injective for each instantiation of K, E and R.
+/// See doc/trusted_assumptions.md for why it is consistent.
+#[verifier(external_body)]
+pub proof fn marshal_controller_state_preserves_integrity()
+    ensures forall |s: ControllerState<K, E, R>| Self::unmarshal_controller_state(#[trigger] Self::marshal_controller_state(s)) == s,
+{}
+
+/// controller_model packs up the controller of this Cluster so that it can be hosted in a MultiCluster.
+/// Each step of the hosted controller is exactly a step of Self::controller() (or Self::schedule_controller_reconcile)
+/// on the unmarshalled controller state.
+pub open spec fn controller_model() -> ControllerModel<E> {
+    ControllerModel {
+        kind: K::kind(),
+        init: |s: ErasedControllerState| {
+            (Self::controller().init)(Self::unmarshal_controller_state(s))
+        },
+        next: |input: ControllerActionInput<E>, s: ErasedControllerState| {
+            let result = Self::controller().next_result(input, Self::unmarshal_controller_state(s));
+            if result.is_Enabled() {
+                Some((Self::marshal_controller_state(result.get_Enabled_0()), result.get_Enabled_1()))
+            } else {
+                None
+            }
+        },
+        schedule: |key: ObjectRef, obj: DynamicObjectView, s: ErasedControllerState| {
+            let controller_state = Self::unmarshal_controller_state(s);
+            if K::unmarshal(obj).is_Ok() {
+                Some(Self::marshal_controller_state(ControllerState {
+                    scheduled_reconciles: controller_state.scheduled_reconciles.insert(key, K::unmarshal(obj).get_Ok_0()),
+                    ..controller_state
+                }))
+            } else {
+                None
+            }
+        },
+    }
+}
+
+}
+
+#[is_variant]
+pub enum MultiClusterStep<Msg> {
+    ApiServerStep(Option<Msg>),
+    BuiltinControllersStep((BuiltinControllerChoice, ObjectRef)),
+    ControllerStep((ControllerId, Option<Msg>, Option<ObjectRef>)),
+    ClientStep(ClientStep),
+    ExternalAPIStep(Option<Msg>),
+    ScheduleControllerReconcileStep((ControllerId, ObjectRef)),
+    RestartController(ControllerId),
+    DisableCrash(),
+    FailTransientlyStep((Msg, APIError)),
+    DisableTransientFailure(),
+    StutterStep(),
+}
+
+/// The state machine of the MultiCluster is the same as the one of Cluster (see cluster_state_machine.rs)
+/// except that the controller related actions (controller_next, schedule_controller_reconcile and restart_controller)
+/// take the id of the hosted controller that performs the action, and the client is shared by all the controllers.
+impl<E: ExternalAPI> MultiClusterModel<E> {
+
+pub open spec fn init(self) -> StatePred<MultiCluster<E>> {
+    |s: MultiCluster<E>| {
+        &&& (self.kubernetes_api.init)(s.kubernetes_api_state)
+        &&& (self.builtin_controllers.init)(s.kubernetes_api_state)
+        &&& s.controller_states.dom() == self.controllers.dom()
+        &&& forall |id: ControllerId| #[trigger] self.controllers.contains_key(id)
+            ==> (self.controllers[id].init)(s.controller_states[id])
+        &&& (self.client().init)(s.client_state)
+        &&& (self.network.init)(s.network_state)
+        &&& s.rest_id_owners == Map::<RestId, ControllerId>::empty()
+        &&& s.received == Multiset::<MsgType<E>>::empty()
+        &&& (self.external_api.init)(s.external_api_state)
+        &&& s.crash_enabled
+        &&& s.transient_failure_enabled
+    }
+}
+
+pub open spec fn kubernetes_api_next(self) -> Action<MultiCluster<E>, Option<MsgType<E>>, ()> {
+    let result = |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+        let host_result = self.kubernetes_api.next_result(
+            ApiServerActionInput{ recv: input },
+            s.kubernetes_api_state
+        );
+        let msg_ops = MessageOps {
+            recv: input,
+            send: host_result.get_Enabled_1().send,
+        };
+        let network_result = self.network.next_result(msg_ops, s.network_state);
+
+        (host_result, network_result)
+    };
+    Action {
+        precondition: |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+            &&& received_msg_destined_for(input, HostId::ApiServer)
+            &&& result(input, s).0.is_Enabled()
+            &&& result(input, s).1.is_Enabled()
+        },
+        transition: |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+            let (host_result, network_result) = result(input, s);
+            (MultiCluster {
+                kubernetes_api_state: host_result.get_Enabled_0(),
+                network_state: network_result.get_Enabled_0(),
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn builtin_controllers_next(self) -> Action<MultiCluster<E>, (BuiltinControllerChoice, ObjectRef), ()> {
+    let result = |input: (BuiltinControllerChoice, ObjectRef), s: MultiCluster<E>| {
+        let host_result = self.builtin_controllers.next_result(
+            BuiltinControllersActionInput {
+                choice: input.0,
+                key: input.1,
+                rest_id_allocator: s.rest_id_allocator,
+            },
+            s.kubernetes_api_state
+        );
+        let msg_ops = MessageOps {
+            recv: None,
+            send: host_result.get_Enabled_1().send,
+        };
+        let network_result = self.network.next_result(msg_ops, s.network_state);
+
+        (host_result, network_result)
+    };
+    Action {
+        precondition: |input: (BuiltinControllerChoice, ObjectRef), s: MultiCluster<E>| {
+            &&& result(input, s).0.is_Enabled()
+            &&& result(input, s).1.is_Enabled()
+        },
+        transition: |input: (BuiltinControllerChoice, ObjectRef), s: MultiCluster<E>| {
+            let (host_result, network_result) = result(input, s);
+            (MultiCluster {
+                kubernetes_api_state: host_result.get_Enabled_0(),
+                network_state: network_result.get_Enabled_0(),
+                rest_id_allocator: host_result.get_Enabled_1().rest_id_allocator,
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn external_api_next(self) -> Action<MultiCluster<E>, Option<MsgType<E>>, ()> {
+    let result = |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+        let host_result = self.external_api.next_result(
+            ExternalAPIActionInput {
+                recv: input,
+                resources: s.kubernetes_api_state.resources,
+            },
+            s.external_api_state
+        );
+        let msg_ops = MessageOps {
+            recv: input,
+            send: host_result.get_Enabled_1().send,
+        };
+        let network_result = self.network.next_result(msg_ops, s.network_state);
+
+        (host_result, network_result)
+    };
+    Action {
+        precondition: |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+            &&& received_msg_destined_for(input, HostId::ExternalAPI)
+            &&& result(input, s).0.is_Enabled()
+            &&& result(input, s).1.is_Enabled()
+        },
+        transition: |input: Option<MsgType<E>>, s: MultiCluster<E>| {
+            let (host_result, network_result) = result(input, s);
+            (MultiCluster {
+                external_api_state: host_result.get_Enabled_0(),
+                network_state: network_result.get_Enabled_0(),
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn controller_next(self) -> Action<MultiCluster<E>, (ControllerId, Option<MsgType<E>>, Option<ObjectRef>), ()> {
+    let result = |input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>), s: MultiCluster<E>| {
+        let host_result = (self.controllers[input.0].next)(
+            ControllerActionInput{recv: input.1, scheduled_cr_key: input.2, rest_id_allocator: s.rest_id_allocator},
+            s.controller_states[input.0]
+        );
+        let msg_ops = MessageOps {
+            recv: input.1,
+            send: host_result.get_Some_0().1.send,
+        };
+        let network_result = self.network.next_result(msg_ops, s.network_state);
+
+        (host_result, network_result)
+    };
+    Action {
+        precondition: |input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>), s: MultiCluster<E>| {
+            &&& self.controllers.contains_key(input.0)
+            &&& received_msg_destined_for(input.1, HostId::CustomController)
+            // The controller only receives the responses to its own requests.
+            &&& input.1.is_Some() ==> {
+                let rest_id = input.1.get_Some_0().content.get_rest_id();
+                &&& s.rest_id_owners.contains_key(rest_id)
+                &&& s.rest_id_owners[rest_id] == input.0
+            }
+            &&& result(input, s).0.is_Some()
+            &&& result(input, s).1.is_Enabled()
+        },
+        transition: |input: (ControllerId, Option<MsgType<E>>, Option<ObjectRef>), s: MultiCluster<E>| {
+            let (host_result, network_result) = result(input, s);
+            let rest_id_allocator_prime = host_result.get_Some_0().1.rest_id_allocator;
+            // The RestIds allocated in this step are owned by the controller.
+            let allocated = Map::new(
+                |rest_id: RestId| s.rest_id_allocator.rest_id_counter <= rest_id < rest_id_allocator_prime.rest_id_counter,
+                |rest_id: RestId| input.0
+            );
+            (MultiCluster {
+                controller_states: s.controller_states.insert(input.0, host_result.get_Some_0().0),
+                network_state: network_result.get_Enabled_0(),
+                rest_id_allocator: rest_id_allocator_prime,
+                rest_id_owners: s.rest_id_owners.union_prefer_right(allocated),
+                received: if input.1.is_Some() { s.received.insert(input.1.get_Some_0()) } else { s.received },
+                ..s
+            }, ())
+        },
+    }
+}
+
+/// See Cluster::schedule_controller_reconcile for the assumptions behind this action.
+/// The only difference is that the custom resource object is delivered to the controller managing its kind.
+pub open spec fn schedule_controller_reconcile(self) -> Action<MultiCluster<E>, (ControllerId, ObjectRef), ()> {
+    Action {
+        precondition: |input: (ControllerId, ObjectRef), s: MultiCluster<E>| {
+            &&& self.controllers.contains_key(input.0)
+            &&& s.resources().contains_key(input.1)
+            &&& input.1.kind == self.controllers[input.0].kind
+            &&& (self.controllers[input.0].schedule)(input.1, s.resources()[input.1], s.controller_states[input.0]).is_Some()
+        },
+        transition: |input: (ControllerId, ObjectRef), s: MultiCluster<E>| {
+            let controller_state_prime = (self.controllers[input.0].schedule)(input.1, s.resources()[input.1], s.controller_states[input.0]).get_Some_0();
+            (MultiCluster {
+                controller_states: s.controller_states.insert(input.0, controller_state_prime),
+                ..s
+            }, ())
+        }
+    }
+}
+
+/// This action restarts one crashed controller. The other controllers keep running.
+pub open spec fn restart_controller(self) -> Action<MultiCluster<E>, ControllerId, ()> {
+    Action {
+        precondition: |input: ControllerId, s: MultiCluster<E>| {
+            &&& self.controllers.contains_key(input)
+            &&& s.crash_enabled
+        },
+        transition: |input: ControllerId, s: MultiCluster<E>| {
+            let controller_state_prime = choose |controller_state: ErasedControllerState| (self.controllers[input].init)(controller_state);
+            (MultiCluster {
+                controller_states: s.controller_states.insert(input, controller_state_prime),
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn disable_crash(self) -> Action<MultiCluster<E>, (), ()> {
+    Action {
+        precondition: |input: (), s: MultiCluster<E>| {
+            true
+        },
+        transition: |input: (), s: MultiCluster<E>| {
+            (MultiCluster {
+                crash_enabled: false,
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn fail_request_transiently(self) -> Action<MultiCluster<E>, (MsgType<E>, APIError), ()> {
+    let result = |input: (MsgType<E>, APIError), s: MultiCluster<E>| {
+        let req_msg = input.0;
+        let api_err = input.1;
+        let resp = Message::form_matched_err_resp_msg(req_msg, api_err);
+        let msg_ops = MessageOps {
+            recv: Some(req_msg),
+            send: Multiset::singleton(resp),
+        };
+        self.network.next_result(msg_ops, s.network_state)
+    };
+    Action {
+        precondition: |input: (MsgType<E>, APIError), s: MultiCluster<E>| {
+            let req_msg = input.0;
+            let api_err = input.1;
+            &&& s.transient_failure_enabled
+            &&& req_msg.dst.is_ApiServer()
+            &&& req_msg.content.is_APIRequest()
+            &&& api_err.is_transient()
+            &&& result(input, s).is_Enabled()
+        },
+        transition: |input: (MsgType<E>, APIError), s: MultiCluster<E>| {
+            (MultiCluster {
+                network_state: result(input, s).get_Enabled_0(),
+                ..s
+            }, ())
+        }
+    }
+}
+
+pub open spec fn disable_transient_failure(self) -> Action<MultiCluster<E>, (), ()> {
+    Action {
+        precondition: |input:(), s: MultiCluster<E>| {
+            true
+        },
+        transition: |input: (), s: MultiCluster<E>| {
+            (MultiCluster {
+                transient_failure_enabled: false,
+                ..s
+            }, ())
+        }
+    }
+}
+
+/// The client of the MultiCluster, which creates, updates and deletes the objects in self.client_expects.
+pub open spec fn client(self) -> ClientStateMachine<E::Input, E::Output> {
+    client_state_machine(self.client_expects)
+}
+
+pub open spec fn client_next(self) -> Action<MultiCluster<E>, ClientStep, ()> {
+    let result = |input: ClientStep, s: MultiCluster<E>| {
+        let host_result = self.client().next_action_result(
+            (self.client().step_to_action)(input),
+            (self.client().action_input)(input, s.rest_id_allocator),
+            s.client_state
+        );
+        let msg_ops = MessageOps {
+            recv: None,
+            send: host_result.get_Enabled_1().send,
+        };
+        let network_result = self.network.next_result(msg_ops, s.network_state);
+
+        (host_result, network_result)
+    };
+    Action {
+        precondition: |input: ClientStep, s: MultiCluster<E>| {
+            &&& result(input, s).0.is_Enabled()
+            &&& result(input, s).1.is_Enabled()
+        },
+        transition: |input: ClientStep, s: MultiCluster<E>| {
+            let (host_result, network_result) = result(input, s);
+            (MultiCluster {
+                client_state: host_result.get_Enabled_0(),
+                network_state: network_result.get_Enabled_0(),
+                rest_id_allocator: host_result.get_Enabled_1().rest_id_allocator,
+                ..s
+            }, ())
+        },
+    }
+}
+
+pub open spec fn stutter(self) -> Action<MultiCluster<E>, (), ()> {
+    Action {
+        precondition: |input: (), s: MultiCluster<E>| {
+            true
+        },
+        transition: |input: (), s: MultiCluster<E>| {
+            (s, ())
+        },
+    }
+}
+
+pub open spec fn next_step(self, s: MultiCluster<E>, s_prime: MultiCluster<E>, step: MultiClusterStep<MsgType<E>>) -> bool {
+    match step {
+        MultiClusterStep::ApiServerStep(input) => self.kubernetes_api_next().forward(input)(s, s_prime),
+        MultiClusterStep::BuiltinControllersStep(input) => self.builtin_controllers_next().forward(input)(s, s_prime),
+        MultiClusterStep::ControllerStep(input) => self.controller_next().forward(input)(s, s_prime),
+        MultiClusterStep::ClientStep(input) => self.client_next().forward(input)(s, s_prime),
+        MultiClusterStep::ExternalAPIStep(input) => self.external_api_next().forward(input)(s, s_prime),
+        MultiClusterStep::ScheduleControllerReconcileStep(input) => self.schedule_controller_reconcile().forward(input)(s, s_prime),
+        MultiClusterStep::RestartController(input) => self.restart_controller().forward(input)(s, s_prime),
+        MultiClusterStep::DisableCrash() => self.disable_crash().forward(())(s, s_prime),
+        MultiClusterStep::FailTransientlyStep(input) => self.fail_request_transiently().forward(input)(s, s_prime),
+        MultiClusterStep::DisableTransientFailure() => self.disable_transient_failure().forward(())(s, s_prime),
+        MultiClusterStep::StutterStep() => self.stutter().forward(())(s, s_prime),
+    }
+}
+
+pub open spec fn next(self) -> ActionPred<MultiCluster<E>> {
+    |s: MultiCluster<E>, s_prime: MultiCluster<E>| exists |step: MultiClusterStep<MsgType<E>>| self.next_step(s, s_prime, step)
+}
+
+pub open spec fn sm_spec(self) -> TempPred<MultiCluster<E>> {
+    lift_state(self.init()).and(always(lift_action(self.next()))).and(self.sm_wf_spec())
+}
+
+/// The fairness assumptions are the same as Cluster::sm_wf_spec, and they are made for every hosted controller:
+/// each controller (and its reconcile scheduling) is weakly fair on its own.
+pub open spec fn sm_wf_spec(self) -> TempPred<MultiCluster<E>> {
+    tla_forall(|input| self.kubernetes_api_next().weak_fairness(input))
+    .and(tla_forall(|input| self.builtin_controllers_next().weak_fairness(input)))
+    .and(tla_forall(|input| self.external_api_next().weak_fairness(input)))
+    .and(tla_forall(|id: ControllerId| self.controller_wf_spec(id)))
+    .and(self.disable_crash().weak_fairness(()))
+    .and(self.disable_transient_failure().weak_fairness(()))
+}
+
+pub open spec fn controller_wf_spec(self, id: ControllerId) -> TempPred<MultiCluster<E>> {
+    tla_forall(|input: (Option<MsgType<E>>, Option<ObjectRef>)| self.controller_next().weak_fairness((id, input.0, input.1)))
+    .and(tla_forall(|key: ObjectRef| self.schedule_controller_reconcile().weak_fairness((id, key))))
+}
+
+pub open spec fn crash_disabled(self) -> StatePred<MultiCluster<E>> {
+    |s: MultiCluster<E>| !s.crash_enabled
+}
+
+pub open spec fn busy_disabled(self) -> StatePred<MultiCluster<E>> {
+    |s: MultiCluster<E>| !s.transient_failure_enabled
+}
+
+}
+
+}
@@ -0,0 +1,106 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::spec::*;
+use crate::kubernetes_api_objects::spec::{common::*, dynamic::*};
+use crate::kubernetes_cluster::spec::{
+    api_server::types::{ApiServerState, ApiServerStateMachine},
+    builtin_controllers::types::BuiltinControllersStateMachine,
+    client::types::ClientState,
+    controller::types::{ControllerActionInput, ControllerActionOutput},
+    external_api::types::{ExternalAPIState, ExternalAPIStateMachine},
+    message::*,
+    network::types::NetworkState,
+};
+use crate::state_machine::state_machine::*;
+use vstd::{multiset::*, prelude::*};
+
+verus! {
+
+pub type ControllerId = int;
+
+/// ErasedControllerState is the ControllerState<K, E, R> of one hosted controller with K and R erased,
+/// so that controllers managing different custom resources (with different reconcilers) can be hosted
+/// in the same MultiCluster. Each Cluster<K, E, R> marshals its ControllerState into an ErasedControllerState
+/// (see Cluster::controller_model).
+///
+/// ErasedControllerState is opaque, and the only fact about it is the trusted marshal_controller_state_preserves_integrity
+/// (see doc/trusted_assumptions.md).
+#[verifier(external_body)]
+pub struct ErasedControllerState {}
+
+/// ControllerModel packs up one custom controller and installs it to the MultiCluster.
+/// It is supposed to be built by Cluster::controller_model instead of by hand.
+#[verifier(reject_recursive_types(E))]
+pub struct ControllerModel<E: ExternalAPI> {
+    /// The kind of the custom resource managed by the controller.
+    pub kind: Kind,
+
+    /// Check if it is the initial state of the controller.
+    pub init: spec_fn(ErasedControllerState) -> bool,
+
+    /// Run one step of the controller state machine, or return None if no step is enabled.
+    pub next: spec_fn(ControllerActionInput<E>, ErasedControllerState) -> Option<(ErasedControllerState, ControllerActionOutput<E>)>,
+
+    /// Schedule a reconcile for the custom resource object, or return None if the object cannot be unmarshalled.
+    pub schedule: spec_fn(ObjectRef, DynamicObjectView, ErasedControllerState) -> Option<ErasedControllerState>,
+}
+
+/// MultiClusterModel describes what runs in a MultiCluster: the components shared by all the controllers
+/// (the Kubernetes API, the built-in controllers, the external system and the network) and the hosted controllers.
+///
+/// All the hosted controllers have to talk to the same external system E.
+#[verifier(reject_recursive_types(E))]
+pub struct MultiClusterModel<E: ExternalAPI> {
+    pub kubernetes_api: ApiServerStateMachine<E::Input, E::Output>,
+    pub builtin_controllers: BuiltinControllersStateMachine<E::Input, E::Output>,
+    pub external_api: ExternalAPIStateMachine<E>,
+    pub network: NetworkStateMachine<NetworkState<E::Input, E::Output>, MessageOps<E::Input, E::Output>>,
+    pub controllers: Map<ControllerId, ControllerModel<E>>,
+
+    /// The objects that the users create, update and delete (see client_state_machine), which usually are
+    /// the custom resource objects of all the hosted controllers and the objects they expect from the user.
+    pub client_expects: spec_fn(DynamicObjectView) -> bool,
+}
+
+/// MultiCluster is the state of a cluster running several custom controllers at the same time.
+/// It is the same as Cluster except that each hosted controller has its own controller state.
+///
+/// The rest_id_allocator is shared by all the hosts, and rest_id_owners partitions the RestIds it allocates
+/// among the hosted controllers: each RestId allocated by a step of controller id is owned by id,
+/// and a controller only takes the responses whose RestIds it owns. So the RestIds allocated by different
+/// controllers are disjoint, and a controller never takes the response to another controller's request.
+/// We do not give each controller a RestIdAllocator of its own because, as seen by one controller,
+/// the others are clients of the same cluster, which share one RestIdAllocator with it in Cluster
+/// (see Cluster::project).
+///
+/// rest_id_owners and received are ghost: they only record what has happened, and no host reads them
+/// except that the controller can only receive the responses whose RestIds it owns.
+pub struct MultiCluster<E: ExternalAPI> {
+    pub kubernetes_api_state: ApiServerState,
+    pub controller_states: Map<ControllerId, ErasedControllerState>,
+    pub client_state: ClientState,
+    pub network_state: NetworkState<E::Input, E::Output>,
+    pub external_api_state: ExternalAPIState<E>,
+    pub rest_id_allocator: RestIdAllocator,
+    /// The controller that allocated each RestId, for the RestIds allocated by the hosted controllers.
+    pub rest_id_owners: Map<RestId, ControllerId>,
+    /// The messages received by the hosted controllers.
+    pub received: Multiset<MsgType<E>>,
+    pub crash_enabled: bool,
+    pub transient_failure_enabled: bool,
+}
+
+impl<E: ExternalAPI> MultiCluster<E> {
+    #[verifier(inline)]
+    pub open spec fn in_flight(self) -> Multiset<MsgType<E>> {
+        self.network_state.in_flight
+    }
+
+    #[verifier(inline)]
+    pub open spec fn resources(self) -> StoredState {
+        self.kubernetes_api_state.resources
+    }
+}
+
+}
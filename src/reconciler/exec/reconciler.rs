@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 use crate::external_api::exec::*;
-use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*};
 use crate::reconciler::exec::io::*;
 use crate::reconciler::spec::io::*;
 use vstd::prelude::*;
@@ -19,6 +19,10 @@ pub trait Reconciler{
         requires Self::well_formed(cr);
     fn reconcile_done(state: &Self::T) -> bool;
     fn reconcile_error(state: &Self::T) -> bool;
+    // owned_resources lists the kinds of the objects that reconcile_core creates and owns
+    // (i.e., the objects carrying a controller owner reference pointing to the cr object).
+    // The shim layer watches these kinds and maps each event back to the owner cr to trigger a reconcile.
+    fn owned_resources() -> Vec<ApiResource>;
 }
 
 // pub open spec fn resource_version_check<I, O>(prev_resp_opt: Option<ResponseView<O>>, cur_req_opt: Option<RequestView<I>>) -> bool {
@@ -0,0 +1,214 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::{common::*, object_map::ObjectMap};
+use crate::kubernetes_api_objects::exec::{dynamic::*, resource::*};
+use crate::reconciler::exec::reconciler::*;
+use core::fmt::Debug;
+use deps_hack::futures::Future;
+use deps_hack::k8s_openapi::api::{apps, core, rbac};
+use deps_hack::kube::{
+    api::{Api, ApiResource, ListParams, Resource, TypeMeta},
+    Client,
+};
+use deps_hack::kube_core::NamespaceResourceScope;
+use deps_hack::serde::de::DeserializeOwned;
+use deps_hack::serde_json::Value;
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+// A cluster snapshot holds the objects of some kinds in one namespace of a real cluster, keyed the same way
+// as the exec API server model keys its objects, so that the exec checkers of the liveness theorems
+// (e.g., crate::zookeeper_controller::exec::state_matches) can check a real cluster, e.g., in e2e tests or health probes.
+//
+// The snapshot is taken by listing one kind after another, so it is not atomic:
+// a check on it can fail (or pass) on a state that the cluster never was in as a whole
+// if the objects change in the meantime, so a caller that needs a stable answer should check it again.
+//
+// Unlike the API server model, a real API server fills in the fields that the written object leaves unset
+// (e.g., the pod template of a StatefulSet gets a restart policy), so a stored object never equals the one
+// the controller made. To check the same property as the liveness theorem, which is stated over the objects
+// as the model stores them, the snapshot puts back what the controller wrote (see WrittenObjects).
+
+// StateMatchesReconciler is implemented by every reconciler run by the shim layer.
+// A reconciler with an exec checker of the current_state_matches of its liveness theorem
+// (e.g., crate::zookeeper_controller::exec::state_matches) overrides state_matches,
+// which the shim layer serves as a health probe (see check_state_matches).
+pub trait StateMatchesReconciler: Reconciler {
+    // state_matches returns whether the objects in the snapshot match the desired state of cr,
+    // or None if the reconciler has no checker
+    fn state_matches(_cr: &Self::R, _snapshot: &ObjectMap) -> Option<bool> {
+        None
+    }
+}
+
+// StateMatches is the result of check_state_matches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateMatches {
+    Matches,
+    DoesNotMatch,
+    // The custom resource does not exist
+    NotFound,
+    // The reconciler has no checker
+    Unchecked,
+}
+
+// WrittenObjects keeps, for each object that the controller creates or updates, the object it writes
+// and the object the API server returns, which is the written one with the unset fields filled in.
+// If the stored object still has the content (everything but the metadata and the status) of the returned one,
+// restore gives it back the content of the written one, i.e., the content the API server model would store.
+//
+// Only the objects written since the controller started are kept, and with leader election only the leader
+// writes, so the snapshot taken by a replica that has not written an object yet keeps the stored content,
+// which makes a check comparing the content fail until the next reconcile writes the object again.
+pub struct WrittenObjects {
+    objects: Mutex<BTreeMap<(String, String, String), (Value, Value)>>,
+}
+
+impl WrittenObjects {
+    pub fn new() -> WrittenObjects {
+        WrittenObjects {
+            objects: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    // record records the object of the kind that the controller writes and the one the API server returns for it
+    pub fn record(
+        &self,
+        kind: &str,
+        written: &deps_hack::kube::api::DynamicObject,
+        returned: &deps_hack::kube::api::DynamicObject,
+    ) {
+        let key = (
+            kind.to_string(),
+            returned.metadata.namespace.clone().unwrap_or_default(),
+            returned.metadata.name.clone().unwrap_or_default(),
+        );
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(key, (written.data.clone(), returned.data.clone()));
+    }
+
+    // restore replaces the content of the stored object of the kind with the written one if it is still the returned one
+    pub fn restore(&self, kind: &str, obj: &mut deps_hack::kube::api::DynamicObject) {
+        let key = (
+            kind.to_string(),
+            obj.metadata.namespace.clone().unwrap_or_default(),
+            obj.metadata.name.clone().unwrap_or_default(),
+        );
+        if let Some((written, returned)) = self.objects.lock().unwrap().get(&key) {
+            if content(&obj.data) == content(returned) {
+                let mut data = content(written);
+                if let (Value::Object(data), Some(status)) = (&mut data, obj.data.get("status")) {
+                    data.insert("status".to_string(), status.clone());
+                }
+                obj.data = data;
+            }
+        }
+    }
+}
+
+// content returns the data of a DynamicObject without the status
+fn content(data: &Value) -> Value {
+    let mut data = data.clone();
+    if let Value::Object(fields) = &mut data {
+        fields.remove("status");
+    }
+    data
+}
+
+// builtin_api_resources returns the ApiResource of each builtin kind that KubeKind knows.
+pub fn builtin_api_resources() -> Vec<ApiResource> {
+    vec![
+        ApiResource::erase::<core::v1::ConfigMap>(&()),
+        ApiResource::erase::<apps::v1::DaemonSet>(&()),
+        ApiResource::erase::<core::v1::PersistentVolumeClaim>(&()),
+        ApiResource::erase::<core::v1::Pod>(&()),
+        ApiResource::erase::<rbac::v1::Role>(&()),
+        ApiResource::erase::<rbac::v1::RoleBinding>(&()),
+        ApiResource::erase::<apps::v1::StatefulSet>(&()),
+        ApiResource::erase::<core::v1::Service>(&()),
+        ApiResource::erase::<core::v1::ServiceAccount>(&()),
+        ApiResource::erase::<core::v1::Secret>(&()),
+    ]
+}
+
+// take_snapshot lists the objects of each of the api_resources in the namespace,
+// with the content the controller wrote if written is given (see WrittenObjects).
+pub async fn take_snapshot(
+    client: Client,
+    namespace: &str,
+    api_resources: &[ApiResource],
+    written: Option<&WrittenObjects>,
+) -> Result<ObjectMap, deps_hack::kube::Error> {
+    let mut objects = BTreeMap::new();
+    for api_resource in api_resources {
+        let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
+            client.clone(),
+            namespace,
+            api_resource,
+        );
+        for mut obj in api.list(&ListParams::default()).await?.items {
+            // The items of a list might not carry their apiVersion and kind,
+            // which DynamicObject::kind needs to tell the kind of the object
+            obj.types = Some(TypeMeta {
+                api_version: api_resource.api_version.clone(),
+                kind: api_resource.kind.clone(),
+            });
+            if let Some(written) = written {
+                written.restore(&api_resource.kind, &mut obj);
+            }
+            let key = ExternalObjectRef {
+                kind: KubeKind::from_kind_name(&api_resource.kind),
+                name: obj.metadata.name.clone().unwrap_or_default(),
+                namespace: namespace.to_string(),
+            };
+            objects.insert(key, DynamicObject::from_kube(obj));
+        }
+    }
+    Ok(ObjectMap::from_rust_map(objects))
+}
+
+// check_state_matches checks the current state of the cluster against the custom resource namespace/name
+// with the checker of ReconcilerType, on a snapshot of the objects of all the kinds that KubeKind knows.
+pub async fn check_state_matches<K, ReconcilerType>(
+    client: Client,
+    written: &WrittenObjects,
+    namespace: &str,
+    name: &str,
+) -> Result<StateMatches, deps_hack::kube::Error>
+where
+    K: Clone + Resource<Scope = NamespaceResourceScope> + DeserializeOwned + Debug,
+    K::DynamicType: Default,
+    ReconcilerType: StateMatchesReconciler,
+    ReconcilerType::R: ResourceWrapper<K>,
+{
+    let cr = match Api::<K>::namespaced(client.clone(), namespace)
+        .get_opt(name)
+        .await?
+    {
+        Some(cr) => ReconcilerType::R::from_kube(cr),
+        None => return Ok(StateMatches::NotFound),
+    };
+    let snapshot =
+        take_snapshot(client, namespace, &builtin_api_resources(), Some(written)).await?;
+    Ok(match ReconcilerType::state_matches(&cr, &snapshot) {
+        Some(true) => StateMatches::Matches,
+        Some(false) => StateMatches::DoesNotMatch,
+        None => StateMatches::Unchecked,
+    })
+}
+
+// StateMatchesProbe runs check_state_matches for the namespace and the name of a custom resource,
+// and is served by serve_metrics at /state-matches/<namespace>/<name>.
+pub type StateMatchesProbe = Arc<
+    dyn Fn(
+            String,
+            String,
+        )
+            -> Pin<Box<dyn Future<Output = Result<StateMatches, deps_hack::kube::Error>> + Send>>
+        + Send
+        + Sync,
+>;
@@ -3,9 +3,18 @@
 #![allow(unused_imports)]
 use crate::external_api::exec::*;
 use crate::kubernetes_api_objects::error::*;
-use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::*, resource::*};
+use crate::kubernetes_api_objects::exec::{api_method::*, api_resource::*, dynamic::*, resource::*};
+use crate::kubernetes_api_objects::spec::api_method::PatchType;
 use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::cluster_snapshot::*;
 use crate::shim_layer::fault_injection::*;
+use crate::shim_layer::leader_election::*;
+use crate::shim_layer::metrics::*;
+use crate::shim_layer::read_cache::*;
+use crate::shim_layer::reconcile_trace::*;
+use crate::shim_layer::refinement_monitor::*;
+use crate::shim_layer::requeue::*;
+use crate::shim_layer::worker_pool::*;
 use builtin::*;
 use builtin_macros::*;
 use core::fmt::Debug;
@@ -13,7 +22,7 @@ use core::hash::Hash;
 use deps_hack::anyhow::Result;
 use deps_hack::futures::{Future, Stream, StreamExt, TryFuture};
 use deps_hack::kube::{
-    api::{Api, DeleteParams, ListParams, ObjectMeta, PostParams, Resource},
+    api::{Api, ListParams, ObjectMeta, Patch, PatchParams, PostParams, Resource},
     runtime::{
         controller::{self, Action, Controller},
         reflector, watcher,
@@ -22,10 +31,11 @@ use deps_hack::kube::{
 };
 use deps_hack::kube_core::{ErrorResponse, NamespaceResourceScope};
 use deps_hack::serde::{de::DeserializeOwned, Serialize};
+use deps_hack::serde_json::Value;
 use deps_hack::tracing::{error, info, warn};
 use deps_hack::Error;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use vstd::{string::*, view::*};
 
 // The shim layer connects the verified reconciler to the trusted kube-rs APIs.
@@ -33,6 +43,36 @@ use vstd::{string::*, view::*};
 // which is required by the kube-rs framework to build a controller,
 // on top of reconcile_core, which is provided by the developer.
 
+// ControllerConfig carries the options for running the controller.
+#[derive(Clone, Default)]
+pub struct ControllerConfig {
+    // Whether to inject the faults configured by the fault-injection-config ConfigMap (see FaultInjectionSpec)
+    pub fault_injection: bool,
+    // Whether to answer the get and list requests issued by reconcile_core from the local cache (see ReadCache).
+    // Note that cached reads can be stale and no controller in controller_examples is proved against stale reads:
+    // all their proofs assume Cluster::stale_read_disabled, so a controller running with cached reads is unverified
+    // unless its proof is conducted with Cluster::init_with_stale_read.
+    pub cached_read: bool,
+    // Whether to run reconciles only when this replica holds the lease (see LeaderElector).
+    // This allows running several replicas of the controller so that another replica takes over when the leader crashes.
+    pub leader_election: Option<LeaderElectionConfig>,
+    // The file to record the trace of each reconcile to (see ReconcileTrace), if any.
+    // The recorded traces can be replayed against reconcile_core with replay_reconcile.
+    pub reconcile_trace: Option<String>,
+    // Whether to check each reconcile_core step against the executable model of the spec reconcile_core
+    // (see ExecutableModelReconciler), and what to do when the check fails.
+    pub refinement_monitor: Option<RefinementMonitorMode>,
+    // When to requeue the reconcile after it is done (see RequeueHint) or fails (with per-resource exponential backoff)
+    pub requeue_policy: RequeuePolicy,
+    // The maximum number of reconciles (of different custom resources) running at the same time.
+    // 0 means no limit, which is the kube-rs default. Reconciles of the same custom resource never overlap
+    // regardless of this limit (see InFlightReconciles).
+    pub max_concurrent_reconciles: u16,
+    // The address (e.g., 0.0.0.0:8080) to serve the metrics of the controller at /metrics, if any (see Metrics),
+    // and the health probe of each custom resource at /state-matches/<namespace>/<name> (see check_state_matches)
+    pub metrics_addr: Option<String>,
+}
+
 // run_controller prepares and runs the controller. It requires:
 // K: the custom resource type
 // ReconcilerType: the reconciler type
@@ -48,25 +88,174 @@ where
         + Sync
         + 'static,
     K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
-    ReconcilerType: Reconciler + Send + Sync,
+    ReconcilerType: ExecutableModelReconciler
+        + RequeueHintReconciler
+        + StateMatchesReconciler
+        + Send
+        + Sync
+        + 'static,
     ReconcilerType::R: ResourceWrapper<K> + Send,
     ReconcilerType::T: Send,
+    ReconcilerType::ExternalAPIType: TracedExternalAPI,
+    <ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Input: Send,
+    <ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Output: Send,
+{
+    run_controller_with_config::<K, ReconcilerType>(ControllerConfig {
+        fault_injection: fault_injection,
+        // The following options can be turned on for a deployed controller without rebuilding it
+        cached_read: std::env::var("CACHED_READ").map_or(false, |cached_read| cached_read == "true"),
+        // Each custom resource type has its own lease, named after its CRD, in the namespace given by LEADER_ELECTION_NAMESPACE
+        leader_election: std::env::var("LEADER_ELECTION_NAMESPACE")
+            .ok()
+            .map(|namespace| LeaderElectionConfig::new(K::crd_name(), &namespace)),
+        reconcile_trace: std::env::var("RECONCILE_TRACE_FILE").ok(),
+        refinement_monitor: std::env::var("REFINEMENT_MONITOR")
+            .ok()
+            .and_then(|mode| RefinementMonitorMode::from_str(&mode)),
+        max_concurrent_reconciles: std::env::var("MAX_CONCURRENT_RECONCILES")
+            .ok()
+            .and_then(|max| max.parse().ok())
+            .unwrap_or(0),
+        metrics_addr: std::env::var("METRICS_ADDR").ok(),
+        ..ControllerConfig::default()
+    })
+    .await
+}
+
+// run_controller_with_config is the same as run_controller but takes all the options in config.
+pub async fn run_controller_with_config<K, ReconcilerType>(config: ControllerConfig) -> Result<()>
+where
+    K: Clone
+        + Resource<Scope = NamespaceResourceScope>
+        + CustomResourceExt
+        + DeserializeOwned
+        + Debug
+        + Send
+        + Serialize
+        + Sync
+        + 'static,
+    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
+    ReconcilerType: ExecutableModelReconciler
+        + RequeueHintReconciler
+        + StateMatchesReconciler
+        + Send
+        + Sync
+        + 'static,
+    ReconcilerType::R: ResourceWrapper<K> + Send,
+    ReconcilerType::T: Send,
+    ReconcilerType::ExternalAPIType: TracedExternalAPI,
     <ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Input: Send,
     <ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Output: Send,
 {
     let client = Client::try_default().await?;
     let crs = Api::<K>::all(client.clone());
+    if config.fault_injection {
+        info!("injecting the faults configured by the fault-injection-config ConfigMap");
+    }
+    let read_cache = if config.cached_read {
+        warn!("serving get and list requests from the local cache, which the controller is not verified against");
+        Some(ReadCache::new(client.clone()))
+    } else {
+        None
+    };
+    let leader_elector = match config.leader_election {
+        Some(leader_election_config) => {
+            let leader_elector = LeaderElector::start(client.clone(), leader_election_config);
+            info!("waiting to become the leader");
+            leader_elector.wait_until_leader().await;
+            Some(leader_elector)
+        }
+        None => None,
+    };
+    let trace_recorder = match config.reconcile_trace {
+        Some(path) => {
+            info!("recording reconcile traces to {}", path);
+            Some(Arc::new(ReconcileTraceRecorder::new(&path)?))
+        }
+        None => None,
+    };
+    let refinement_monitor = match config.refinement_monitor {
+        Some(mode) => {
+            if ReconcilerType::model_init_state().is_none() {
+                warn!("the reconciler has no executable model, so the refinement monitor checks nothing");
+            } else {
+                info!(
+                    "checking reconcile_core against the executable model ({:?} on violation)",
+                    mode
+                );
+            }
+            Some(RefinementMonitor { mode: mode })
+        }
+        None => None,
+    };
+    let requeuer = Requeuer::new(config.requeue_policy);
+    let in_flight = InFlightReconciles::new();
+    let metrics = Arc::new(Metrics::new());
+    let written_objects = Arc::new(WrittenObjects::new());
+    if let Some(addr) = config.metrics_addr {
+        let metrics = metrics.clone();
+        let probe: StateMatchesProbe = {
+            let client = client.clone();
+            let written_objects = written_objects.clone();
+            Arc::new(move |namespace: String, name: String| {
+                let client = client.clone();
+                let written_objects = written_objects.clone();
+                Box::pin(async move {
+                    check_state_matches::<K, ReconcilerType>(client, &written_objects, &namespace, &name).await
+                })
+            })
+        };
+        deps_hack::tokio::spawn(async move {
+            if let Err(err) = serve_metrics(metrics, probe, addr).await {
+                error!("metrics endpoint failed: {}", err);
+            }
+        });
+    }
+    if config.max_concurrent_reconciles > 0 {
+        info!(
+            "running at most {} reconciles at the same time",
+            config.max_concurrent_reconciles
+        );
+    }
 
     // Build the async closure on top of reconcile_with
     let reconcile = |cr: Arc<K>, ctx: Arc<Data>| async move {
-        return reconcile_with::<K, ReconcilerType>(cr, ctx, fault_injection).await;
+        return reconcile_with::<K, ReconcilerType>(cr, ctx).await;
+    };
+
+    // The controller's reconcile is triggered when a CR is created/updated
+    let mut controller = Controller::new(crs, watcher::Config::default()).with_config(
+        controller::Config::default().concurrency(config.max_concurrent_reconciles),
+    );
+    // The controller's reconcile is also triggered when any object owned by a CR is created/updated/deleted.
+    // owns_with maps the event on the owned object back to the CR referred to by its controller owner reference.
+    for api_resource in ReconcilerType::owned_resources() {
+        let kube_api_resource = api_resource.into_kube();
+        info!("watching owned resource {}", kube_api_resource.kind);
+        controller = controller.owns_with(
+            Api::<deps_hack::kube::api::DynamicObject>::all_with(client.clone(), &kube_api_resource),
+            kube_api_resource,
+            watcher::Config::default(),
+        );
+    }
+
+    let data = Data {
+        client,
+        read_cache,
+        leader_elector,
+        trace_recorder,
+        refinement_monitor,
+        requeuer,
+        in_flight,
+        metrics,
+        written_objects,
+        fault_injection: config.fault_injection,
     };
 
     info!("starting controller");
-    // TODO: the controller should also listen to the owned resources
-    Controller::new(crs, watcher::Config::default()) // The controller's reconcile is triggered when a CR is created/updated
+    controller
         .shutdown_on_signal()
-        .run(reconcile, error_policy, Arc::new(Data { client })) // The reconcile function is registered
+        .run(reconcile, error_policy, Arc::new(data)) // The reconcile function is registered
         .for_each(|res| async move {
             match res {
                 Ok(o) => info!("reconciled {:?}", o),
@@ -89,7 +278,6 @@ where
 pub async fn reconcile_with<K, ReconcilerType>(
     cr: Arc<K>,
     ctx: Arc<Data>,
-    fault_injection: bool,
 ) -> Result<Action, Error>
 where
     K: Clone
@@ -99,8 +287,9 @@ where
         + Debug
         + Serialize,
     K::DynamicType: Default + Clone + Debug,
-    ReconcilerType: Reconciler,
+    ReconcilerType: ExecutableModelReconciler + RequeueHintReconciler,
     ReconcilerType::R: ResourceWrapper<K>,
+    ReconcilerType::ExternalAPIType: TracedExternalAPI,
 {
     let client = &ctx.client;
 
@@ -115,6 +304,20 @@ where
     let cr_key = format!("{}/{}/{}", cr_kind, cr_namespace, cr_name);
     let log_header = format!("Reconciling {}:", cr_key);
 
+    // Never run two reconciles of the same custom resource at the same time.
+    // The guard releases the key when reconcile_with returns, however it returns.
+    let _in_flight_guard = match ctx.in_flight.try_claim(&cr_key) {
+        Some(guard) => guard,
+        None => {
+            info!("{} another reconcile is in flight, defer", log_header);
+            return Ok(Action::requeue(DEFER_PERIOD));
+        }
+    };
+
+    if let Some(hold_off) = hold_off_if_not_leader(&ctx, &log_header) {
+        return Ok(hold_off);
+    }
+
     let cr_api = Api::<K>::namespaced(client.clone(), &cr_namespace);
     // Get the custom resource by a quorum read to Kubernetes' storage (etcd) to get the most updated custom resource
     let get_cr_resp = cr_api.get(&cr_name).await;
@@ -126,6 +329,7 @@ where
                 "{} Custom resource {} not found, end reconcile",
                 log_header, cr_name
             );
+            ctx.requeuer.forget(&cr_key);
             return Ok(Action::await_change());
         }
         Err(err) => {
@@ -133,7 +337,7 @@ where
                 "{} Get custom resource {} failed with error: {}, will retry reconcile",
                 log_header, cr_name, err
             );
-            return Ok(Action::requeue(Duration::from_secs(60)));
+            return Ok(ctx.requeuer.on_failure(&cr_key));
         }
         _ => {}
     }
@@ -145,36 +349,147 @@ where
         deps_hack::k8s_openapi::serde_json::to_string(&cr).unwrap()
     );
 
+    // Only build the trace if the trace recorder is set
+    let mut trace = ctx
+        .trace_recorder
+        .as_ref()
+        .map(|_| ReconcileTrace::new(cr_key.clone(), &cr));
+    // The refinement monitor runs the model on the cr object and the responses as served by Kubernetes
+    let mut model = ctx.refinement_monitor.as_ref().and_then(|_| {
+        ModelReconcile::<ReconcilerType>::new(&deps_hack::serde_json::to_value(&cr).unwrap())
+    });
+    // The response fed to the current step in the json form built for the monitor
+    let mut model_resp_json: Option<Value> = None;
+
+    // Read the fault injection config once per reconcile
+    let fault_plan = if ctx.fault_injection {
+        FaultPlan::load(client, &cr_key, &log_header).await
+    } else {
+        None
+    };
+
     let cr_wrapper = ReconcilerType::R::from_kube(cr);
     let mut state = ReconcilerType::reconcile_init_state();
     let mut resp_option: Option<
         Response<<ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Output>,
     > = None;
-    // check_fault_timing is only set to true right after the controller issues any create, update or delete request,
-    // or external request
-    let mut check_fault_timing: bool;
+    // error_details keeps the status code and message of the failed request (if any) that reconcile_core does not see
+    let mut error_details: Option<APIErrorDetails>;
+    let reconcile_start = Instant::now();
+    let mut iterations: u64 = 0;
 
     // Call reconcile_core in a loop
     loop {
-        check_fault_timing = false;
-        // If reconcile core is done, then breaks the loop
+        error_details = None;
+        // If reconcile core is done, then returns the action to requeue it
         if ReconcilerType::reconcile_done(&state) {
             info!("{} done", log_header);
-            break;
+            record_trace(&ctx, trace, ReconcileTraceOutcome::Done);
+            ctx.metrics.reconcile_finished(&cr_kind, "done", reconcile_start.elapsed(), iterations);
+            return Ok(ctx.requeuer.on_done(&cr_key, ReconcilerType::requeue_hint(&state)));
         }
         if ReconcilerType::reconcile_error(&state) {
             warn!("{} error", log_header);
+            record_trace(&ctx, trace, ReconcileTraceOutcome::Error);
+            ctx.metrics.reconcile_finished(&cr_kind, "error", reconcile_start.elapsed(), iterations);
+            ctx.metrics.reconcile_core_error(&cr_kind);
             return Err(Error::ReconcileCoreError);
         }
+        // Stop issuing requests once this replica loses the lease, the new leader will start the reconcile over
+        if let Some(hold_off) = hold_off_if_not_leader(&ctx, &log_header) {
+            record_trace(&ctx, trace, ReconcileTraceOutcome::Interrupted);
+            ctx.metrics.reconcile_finished(&cr_kind, "interrupted", reconcile_start.elapsed(), iterations);
+            return Ok(hold_off);
+        }
         // Feed the current reconcile state and get the new state and the pending request
         let (state_prime, request_option) =
             ReconcilerType::reconcile_core(&cr_wrapper, resp_option, state);
+        iterations += 1;
+        // The request is consumed below, so convert it for the trace and the monitor beforehand
+        let request_json = match (trace.is_some() || model.is_some(), &request_option) {
+            (true, Some(request)) => {
+                Some(request_to_json::<ReconcilerType::ExternalAPIType>(request))
+            }
+            _ => None,
+        };
+        if let (Some(monitor), Some(model)) = (&ctx.refinement_monitor, model.as_mut()) {
+            monitor.report(
+                &log_header,
+                model.check_step(
+                    model_resp_json.as_ref(),
+                    request_json.as_ref(),
+                    outcome::<ReconcilerType>(&state_prime),
+                ),
+            );
+        }
+        // Decide whether to inject a fault into the request before the request is consumed below
+        let fault = match (&fault_plan, &request_option) {
+            (Some(plan), Some(request)) => {
+                let fault = plan.next_fault(request);
+                match plan.record_progress(client).await {
+                    Ok(()) => fault,
+                    Err(err) => {
+                        // Without the progress recorded, the fault would fire again after a restart, so skip it
+                        warn!(
+                            "{} Update fault injection progress failed with error: {}; skip fault {:?}",
+                            log_header, err, fault
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        if let (Some(plan), Some(fault)) = (&fault_plan, &fault) {
+            info!("{} Inject fault {:?}", log_header, fault);
+            if let Fault::CrashBeforeSend = fault {
+                plan.crash(&log_header);
+            }
+        }
+        // The response in json built right from what kube-rs returns, for the monitor (see raw_kube_response_json)
+        let mut raw_resp_json: Option<Value> = None;
+        let monitored = model.is_some();
         // Pattern match the request and send requests to the Kubernetes API via kube-rs methods
         match request_option {
             Some(request) => match request {
                 Request::KRequest(req) => {
                     let kube_resp: KubeAPIResponse;
+                    let (verb, resource_kind) = kube_request_verb_and_kind(&req);
+                    let mut injected_error = match &fault {
+                        Some(Fault::Error(err)) => Some(err.to_api_error()),
+                        _ => None,
+                    };
                     match req {
+                        // Answer the request with the injected error without sending it
+                        _ if injected_error.is_some() => {
+                            kube_resp =
+                                injected_error_response(&req, injected_error.take().unwrap());
+                            info!("{} Request failed with injected error", log_header);
+                        }
+                        // A label selector with an invalid requirement has no string form to send,
+                        // so the list request is rejected the same way as the Kubernetes API rejects it
+                        KubeAPIRequest::ListRequest(list_req) if list_req.has_invalid_selector() => {
+                            kube_resp = KubeAPIResponse::ListResponse(KubeListResponse {
+                                res: Err(APIError::BadRequest),
+                            });
+                            warn!(
+                                "{} List {} rejected: the label selector is invalid",
+                                log_header,
+                                list_req.key()
+                            );
+                        }
+                        KubeAPIRequest::GetRequest(get_req) if ctx.read_cache.is_some() => {
+                            let key = get_req.key();
+                            let res = ctx.read_cache.as_ref().unwrap().get(&get_req).await;
+                            match &res {
+                                Err(err) => info!(
+                                    "{} Get {} from cache failed with error: {:?}",
+                                    log_header, key, err
+                                ),
+                                Ok(_) => info!("{} Get {} from cache done", log_header, key),
+                            }
+                            kube_resp = KubeAPIResponse::GetResponse(KubeGetResponse { res: res });
+                        }
                         KubeAPIRequest::GetRequest(get_req) => {
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
@@ -182,8 +497,13 @@ where
                                 get_req.api_resource.as_kube_ref(),
                             );
                             let key = get_req.key();
-                            match api.get(&get_req.name).await {
+                            let res = api.get(&get_req.name).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Get", res.as_ref().map(to_json)));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp = KubeAPIResponse::GetResponse(KubeGetResponse {
                                         res: Err(kube_error_to_ghost(&err)),
                                     });
@@ -197,6 +517,18 @@ where
                                 }
                             }
                         }
+                        KubeAPIRequest::ListRequest(list_req) if ctx.read_cache.is_some() => {
+                            let key = list_req.key();
+                            let res = ctx.read_cache.as_ref().unwrap().list(&list_req).await;
+                            match &res {
+                                Err(err) => info!(
+                                    "{} List {} from cache failed with error: {:?}",
+                                    log_header, key, err
+                                ),
+                                Ok(_) => info!("{} List {} from cache done", log_header, key),
+                            }
+                            kube_resp = KubeAPIResponse::ListResponse(KubeListResponse { res: res });
+                        }
                         KubeAPIRequest::ListRequest(list_req) => {
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
@@ -204,9 +536,17 @@ where
                                 list_req.api_resource.as_kube_ref(),
                             );
                             let key = list_req.key();
-                            let lp = ListParams::default();
-                            match api.list(&lp).await {
+                            let lp = list_req.list_params();
+                            let res = api.list(&lp).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json(
+                                    "List",
+                                    res.as_ref().map(|obj_list| Value::Array(obj_list.items.iter().map(to_json).collect())),
+                                ));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp = KubeAPIResponse::ListResponse(KubeListResponse {
                                         res: Err(kube_error_to_ghost(&err)),
                                     });
@@ -225,7 +565,6 @@ where
                             }
                         }
                         KubeAPIRequest::CreateRequest(create_req) => {
-                            check_fault_timing = true;
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
                                 &create_req.namespace,
@@ -234,8 +573,13 @@ where
                             let pp = PostParams::default();
                             let key = create_req.key();
                             let obj_to_create = create_req.obj.into_kube();
-                            match api.create(&pp, &obj_to_create).await {
+                            let res = api.create(&pp, &obj_to_create).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Create", res.as_ref().map(to_json)));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp =
                                         KubeAPIResponse::CreateResponse(KubeCreateResponse {
                                             res: Err(kube_error_to_ghost(&err)),
@@ -246,6 +590,7 @@ where
                                     );
                                 }
                                 Ok(obj) => {
+                                    ctx.written_objects.record(&create_req.api_resource.as_kube_ref().kind, &obj_to_create, &obj);
                                     kube_resp =
                                         KubeAPIResponse::CreateResponse(KubeCreateResponse {
                                             res: Ok(DynamicObject::from_kube(obj)),
@@ -255,16 +600,20 @@ where
                             }
                         }
                         KubeAPIRequest::DeleteRequest(delete_req) => {
-                            check_fault_timing = true;
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
                                 &delete_req.namespace,
                                 delete_req.api_resource.as_kube_ref(),
                             );
-                            let dp = DeleteParams::default();
+                            let dp = delete_req.delete_params();
                             let key = delete_req.key();
-                            match api.delete(&delete_req.name, &dp).await {
+                            let res = api.delete(&delete_req.name, &dp).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Delete", res.as_ref().map(|_| Value::Null)));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp =
                                         KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
                                             res: Err(kube_error_to_ghost(&err)),
@@ -284,7 +633,6 @@ where
                             }
                         }
                         KubeAPIRequest::UpdateRequest(update_req) => {
-                            check_fault_timing = true;
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
                                 &update_req.namespace,
@@ -293,8 +641,13 @@ where
                             let pp = PostParams::default();
                             let key = update_req.key();
                             let obj_to_update = update_req.obj.into_kube();
-                            match api.replace(&update_req.name, &pp, &obj_to_update).await {
+                            let res = api.replace(&update_req.name, &pp, &obj_to_update).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Update", res.as_ref().map(to_json)));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp =
                                         KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
                                             res: Err(kube_error_to_ghost(&err)),
@@ -305,6 +658,7 @@ where
                                     );
                                 }
                                 Ok(obj) => {
+                                    ctx.written_objects.record(&update_req.api_resource.as_kube_ref().kind, &obj_to_update, &obj);
                                     kube_resp =
                                         KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
                                             res: Ok(DynamicObject::from_kube(obj)),
@@ -314,7 +668,6 @@ where
                             }
                         }
                         KubeAPIRequest::UpdateStatusRequest(update_status_req) => {
-                            check_fault_timing = true;
                             let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
                                 client.clone(),
                                 &update_status_req.namespace,
@@ -324,16 +677,20 @@ where
                             let key = update_status_req.key();
                             let obj_to_update = update_status_req.obj.into_kube();
                             // Here we assume serde_json always succeed
-                            match api
+                            let res = api
                                 .replace_status(
                                     &update_status_req.name,
                                     &pp,
                                     deps_hack::k8s_openapi::serde_json::to_vec(&obj_to_update)
                                         .unwrap(),
                                 )
-                                .await
-                            {
+                                .await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("UpdateStatus", res.as_ref().map(to_json)));
+                            }
+                            match res {
                                 Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
                                     kube_resp = KubeAPIResponse::UpdateStatusResponse(
                                         KubeUpdateStatusResponse {
                                             res: Err(kube_error_to_ghost(&err)),
@@ -354,77 +711,265 @@ where
                                 }
                             }
                         }
+                        KubeAPIRequest::ApplyRequest(apply_req) => {
+                            let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
+                                client.clone(),
+                                &apply_req.namespace,
+                                apply_req.api_resource.as_kube_ref(),
+                            );
+                            let pp = apply_req.patch_params();
+                            let key = apply_req.key();
+                            let obj_to_apply = apply_req.obj.into_kube();
+                            let res = api
+                                .patch(&apply_req.name, &pp, &Patch::Apply(&obj_to_apply))
+                                .await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Apply", res.as_ref().map(to_json)));
+                            }
+                            match res {
+                                Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
+                                    kube_resp = KubeAPIResponse::ApplyResponse(KubeApplyResponse {
+                                        res: Err(kube_error_to_ghost(&err)),
+                                    });
+                                    info!(
+                                        "{} Apply {} failed with error: {}",
+                                        log_header, key, err
+                                    );
+                                }
+                                Ok(obj) => {
+                                    kube_resp = KubeAPIResponse::ApplyResponse(KubeApplyResponse {
+                                        res: Ok(DynamicObject::from_kube(obj)),
+                                    });
+                                    info!("{} Apply {} done", log_header, key);
+                                }
+                            }
+                        }
+                        KubeAPIRequest::PatchRequest(patch_req) => {
+                            let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
+                                client.clone(),
+                                &patch_req.namespace,
+                                patch_req.api_resource.as_kube_ref(),
+                            );
+                            let pp = PatchParams::default();
+                            let key = patch_req.key();
+                            let patch_obj = patch_req.patch.into_kube();
+                            let patch = match patch_req.patch_type {
+                                PatchType::MergePatch => Patch::Merge(&patch_obj),
+                                PatchType::StrategicMergePatch => Patch::Strategic(&patch_obj),
+                            };
+                            let res = api.patch(&patch_req.name, &pp, &patch).await;
+                            if monitored {
+                                raw_resp_json = Some(raw_kube_response_json("Patch", res.as_ref().map(to_json)));
+                            }
+                            match res {
+                                Err(err) => {
+                                    error_details = Some(kube_error_details(&err));
+                                    kube_resp = KubeAPIResponse::PatchResponse(KubePatchResponse {
+                                        res: Err(kube_error_to_ghost(&err)),
+                                    });
+                                    info!(
+                                        "{} Patch {} failed with error: {}",
+                                        log_header, key, err
+                                    );
+                                }
+                                Ok(obj) => {
+                                    kube_resp = KubeAPIResponse::PatchResponse(KubePatchResponse {
+                                        res: Ok(DynamicObject::from_kube(obj)),
+                                    });
+                                    info!("{} Patch {} done", log_header, key);
+                                }
+                            }
+                        }
                     }
+                    ctx.metrics.api_request(&cr_kind, verb, &resource_kind, &kube_resp);
                     resp_option = Some(Response::KResponse(kube_resp));
                 }
                 Request::ExternalRequest(req) => {
-                    check_fault_timing = true;
-                    let external_resp = ReconcilerType::ExternalAPIType::call_external_api(req);
-                    resp_option = Some(Response::ExternalResponse(external_resp));
+                    if matches!(fault, Some(Fault::Drop)) {
+                        info!("{} Drop external request", log_header);
+                        resp_option = None;
+                    } else {
+                        let call_start = Instant::now();
+                        let external_resp = ReconcilerType::ExternalAPIType::call_external_api(req);
+                        ctx.metrics.external_api_call(&cr_kind, call_start.elapsed());
+                        resp_option = Some(Response::ExternalResponse(external_resp));
+                    }
                 }
             },
             _ => resp_option = None,
         }
-        if check_fault_timing && fault_injection {
-            // If the controller just issues create, update, delete or external request,
-            // and fault injection option is on, then check whether to crash at this point
-            let result = crash_or_continue(client, &cr_key, &log_header).await;
-            if result.is_err() {
-                error!(
-                    "{} crash_or_continue fails due to {}",
-                    log_header,
-                    result.unwrap_err()
-                );
+        // The responses that the shim layer makes up (injected errors, rejected requests and cached reads)
+        // and the external responses do not come from kube-rs, so the monitor takes them as they are
+        if monitored {
+            model_resp_json = raw_resp_json.or_else(|| {
+                resp_option
+                    .as_ref()
+                    .map(|resp| response_to_json::<ReconcilerType::ExternalAPIType>(resp))
+            });
+        }
+        if let Some(trace) = trace.as_mut() {
+            trace.push_step::<ReconcilerType::ExternalAPIType>(
+                request_json,
+                &resp_option,
+                error_details,
+            );
+        }
+        match (&fault_plan, &fault) {
+            (Some(plan), Some(Fault::CrashAfterSend)) => plan.crash(&log_header),
+            (Some(_), Some(Fault::Delay { millis })) => {
+                deps_hack::tokio::time::sleep(Duration::from_millis(*millis)).await
             }
+            _ => {}
         }
         state = state_prime;
     }
+}
+
+// to_json returns the json form of the object that kube-rs returns
+fn to_json(obj: &deps_hack::kube::api::DynamicObject) -> Value {
+    deps_hack::serde_json::to_value(obj).unwrap()
+}
 
-    return Ok(Action::requeue(Duration::from_secs(60)));
+// record_trace records the trace of the reconcile, which ends with outcome, if the trace recorder is set.
+fn record_trace(ctx: &Data, trace: Option<ReconcileTrace>, outcome: ReconcileTraceOutcome) {
+    if let (Some(trace_recorder), Some(mut trace)) = (&ctx.trace_recorder, trace) {
+        trace.outcome = outcome;
+        trace_recorder.record(&trace);
+    }
 }
 
 // error_policy defines the controller's behavior when the reconcile ends with an error.
-pub fn error_policy<K>(_object: Arc<K>, _error: &Error, _ctx: Arc<Data>) -> Action
+// The reconcile is retried with exponential backoff per custom resource, which is reset once a reconcile is done.
+pub fn error_policy<K>(object: Arc<K>, _error: &Error, ctx: Arc<Data>) -> Action
 where
     K: Clone + Resource + DeserializeOwned + Debug + Send + Sync + 'static,
-    K::DynamicType: Eq + Hash + Clone + Debug + Unpin,
+    K::DynamicType: Default + Eq + Hash + Clone + Debug + Unpin,
 {
-    Action::requeue(Duration::from_secs(10))
+    // Use the same key as reconcile_with so that the failures of the custom resource are counted together
+    let cr_key = format!(
+        "{}/{}/{}",
+        K::kind(&K::DynamicType::default()),
+        object.meta().namespace.as_deref().unwrap_or_default(),
+        object.meta().name.as_deref().unwrap_or_default()
+    );
+    ctx.requeuer.on_failure(&cr_key)
+}
+
+// hold_off_if_not_leader returns the action to requeue the reconcile
+// if leader election is enabled and this replica does not hold the lease.
+fn hold_off_if_not_leader(ctx: &Data, log_header: &String) -> Option<Action> {
+    match &ctx.leader_elector {
+        Some(leader_elector) if !leader_elector.is_leader() => {
+            info!("{} not the leader, hold off reconcile", log_header);
+            Some(Action::requeue(leader_elector.retry_period()))
+        }
+        _ => None,
+    }
 }
 
 // Data is passed to reconcile_with.
-// It carries the client that communicates with Kubernetes API.
+// It carries the client that communicates with Kubernetes API,
+// the local cache that answers get and list requests if cached reads are enabled,
+// the leader elector that tells whether this replica can reconcile if leader election is enabled,
+// the recorder that records the trace of each reconcile if reconcile traces are enabled,
+// the monitor that checks each reconcile_core step if the refinement monitor is enabled,
+// the requeuer that decides when to reconcile each custom resource again,
+// the keys of the custom resources being reconciled, the metrics of the reconciles,
+// the objects written by reconcile_core for the health probe (see WrittenObjects),
+// and whether to inject the faults configured by the fault injection config into the requests.
 pub struct Data {
     pub client: Client,
+    pub read_cache: Option<ReadCache>,
+    pub leader_elector: Option<Arc<LeaderElector>>,
+    pub trace_recorder: Option<Arc<ReconcileTraceRecorder>>,
+    pub refinement_monitor: Option<RefinementMonitor>,
+    pub requeuer: Requeuer,
+    pub in_flight: InFlightReconciles,
+    pub metrics: Arc<Metrics>,
+    pub written_objects: Arc<WrittenObjects>,
+    pub fault_injection: bool,
 }
 
 // kube_error_to_ghost translates the API error from kube-rs APIs
 // to the form that can be processed by reconcile_core.
-
-// TODO: match more error types.
+// The errors returned by the API server are translated by their reasons, and by their status codes
+// if the reasons are unknown. The other errors are translated by what happens to the connection:
+// a request that times out becomes Timeout and a request that cannot reach the API server becomes ServiceUnavailable.
 pub fn kube_error_to_ghost(error: &deps_hack::kube::Error) -> APIError {
     match error {
-        deps_hack::kube::Error::Api(error_resp) => {
-            if &error_resp.reason == "NotFound" {
-                APIError::ObjectNotFound
-            } else if &error_resp.reason == "AlreadyExists" {
-                APIError::ObjectAlreadyExists
-            } else if &error_resp.reason == "BadRequest" {
-                APIError::BadRequest
-            } else if &error_resp.reason == "Conflict" {
-                APIError::Conflict
-            } else if &error_resp.reason == "Invalid" {
-                APIError::Invalid
-            } else if &error_resp.reason == "InternalError" {
-                APIError::InternalError
-            } else if &error_resp.reason == "Timeout" {
-                APIError::Timeout
-            } else if &error_resp.reason == "ServerTimeout" {
-                APIError::ServerTimeout
-            } else {
-                APIError::Other
-            }
+        deps_hack::kube::Error::Api(error_resp) => match error_resp.reason.as_str() {
+            "NotFound" => APIError::ObjectNotFound,
+            "AlreadyExists" => APIError::ObjectAlreadyExists,
+            "BadRequest" => APIError::BadRequest,
+            "Conflict" => APIError::Conflict,
+            "Forbidden" => APIError::Forbidden,
+            "Invalid" => APIError::Invalid,
+            "InternalError" => APIError::InternalError,
+            "Timeout" => APIError::Timeout,
+            "ServerTimeout" => APIError::ServerTimeout,
+            "Unauthorized" => APIError::Unauthorized,
+            "TooManyRequests" => APIError::TooManyRequests,
+            "Gone" => APIError::Gone,
+            "Expired" => APIError::Expired,
+            "MethodNotAllowed" => APIError::MethodNotAllowed,
+            "RequestEntityTooLarge" => APIError::RequestEntityTooLarge,
+            "ServiceUnavailable" => APIError::ServiceUnavailable,
+            // Returned for patch types that the resource does not support, e.g., strategic merge patch of custom resources
+            "UnsupportedMediaType" => APIError::NotSupported,
+            _ => match error_resp.code {
+                400 => APIError::BadRequest,
+                401 => APIError::Unauthorized,
+                403 => APIError::Forbidden,
+                404 => APIError::ObjectNotFound,
+                405 => APIError::MethodNotAllowed,
+                409 => APIError::Conflict,
+                410 => APIError::Gone,
+                413 => APIError::RequestEntityTooLarge,
+                415 => APIError::NotSupported,
+                422 => APIError::Invalid,
+                429 => APIError::TooManyRequests,
+                500 => APIError::InternalError,
+                503 => APIError::ServiceUnavailable,
+                504 => APIError::Timeout,
+                _ => APIError::Other,
+            },
+        },
+        _ => match io_error_kind(error) {
+            Some(std::io::ErrorKind::TimedOut) => APIError::Timeout,
+            Some(std::io::ErrorKind::ConnectionRefused)
+            | Some(std::io::ErrorKind::ConnectionReset)
+            | Some(std::io::ErrorKind::ConnectionAborted)
+            | Some(std::io::ErrorKind::NotConnected) => APIError::ServiceUnavailable,
+            _ => APIError::Other,
+        },
+    }
+}
+
+// kube_error_details keeps the status code and the message of the error,
+// which are dropped by kube_error_to_ghost, for logging and for the reconcile traces.
+pub fn kube_error_details(error: &deps_hack::kube::Error) -> APIErrorDetails {
+    match error {
+        deps_hack::kube::Error::Api(error_resp) => APIErrorDetails {
+            code: Some(error_resp.code),
+            message: error_resp.message.clone(),
+        },
+        _ => APIErrorDetails {
+            code: None,
+            message: error.to_string(),
+        },
+    }
+}
+
+// io_error_kind finds the io error (if any) that causes the error, e.g., a refused connection,
+// which is wrapped by hyper and tower in different ways.
+fn io_error_kind(error: &(dyn std::error::Error + 'static)) -> Option<std::io::ErrorKind> {
+    let mut source = Some(error);
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            return Some(io_err.kind());
         }
-        _ => APIError::Other,
+        source = err.source();
     }
+    None
 }
@@ -1,6 +1,9 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::reconciler::exec::io::*;
 use builtin::*;
 use builtin_macros::*;
 use core::fmt::Debug;
@@ -11,69 +14,306 @@ use deps_hack::kube::{
     api::{Api, ObjectMeta, PostParams, Resource},
     Client,
 };
-use deps_hack::tracing::info;
+use deps_hack::serde::{Deserialize, Serialize};
+use deps_hack::tracing::{info, warn};
 use deps_hack::Error;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use vstd::view::*;
 
-pub async fn crash_or_continue(
-    client: &Client,
-    cr_key: &String,
-    log_header: &String,
-) -> Result<(), String> {
-    // We require the fault injection configuration is stored by a ConfigMap object
-    // in the default namespace called "fault-injection-config"
-    let config_map_name = "fault-injection-config";
-    let config_map_api = Api::<ConfigMap>::namespaced(client.clone(), "default");
-    let mut config_map = config_map_api
-        .get(&config_map_name)
-        .await
-        .map_err(|_e| "Fail to get fault injection config".to_string())?;
-    info!(
-        "{} Get {}: {}",
-        log_header,
-        config_map_name,
-        deps_hack::k8s_openapi::serde_json::to_string(&config_map).unwrap()
-    );
+// Fault injection reproduces, against a real cluster, the failures that the cluster model allows
+// and that the proofs have to tolerate:
+// - Crash: Cluster::restart_controller, which can happen right after a controller step (CrashAfterSend)
+//   or right before it, when reconcile_core has returned a request that is not sent yet (CrashBeforeSend).
+//   Both are the same to the model because a controller step runs reconcile_core and sends the request atomically.
+// - Error: Cluster::fail_request_transiently, which answers a request with a transient APIError
+//   (see APIError::is_transient) without handling it. Non-transient errors (e.g., InternalError) can be injected
+//   as well to test the controller beyond what the model allows.
+// - Delay and Drop: the network of the model delivers messages at any time, so a response can be delayed
+//   indefinitely; Drop stands for a response to the external API call that never arrives.
+//
+// The faults are configured by a FaultInjectionSpec stored in the "spec" entry (in JSON or YAML) of
+// the ConfigMap "fault-injection-config" in the default namespace, which is read at the start of each reconcile
+// of the chosen custom resource. Each fault fires at most once (see FaultProgress).
+
+const CONFIG_MAP_NAME: &str = "fault-injection-config";
+const CONFIG_MAP_NAMESPACE: &str = "default";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde", rename_all = "camelCase")]
+pub struct FaultInjectionSpec {
+    // The key (kind/namespace/name) of the custom resource whose reconciles are faulted,
+    // which makes the fault injection deterministic when the controller manages several custom resources
+    pub cr_key: String,
+    #[serde(default)]
+    pub faults: Vec<FaultSpec>,
+    // The request kinds to count besides the ones the faults are on, e.g., for a run that only counts the requests
+    #[serde(default)]
+    pub count: Vec<FaultRequestKind>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde", rename_all = "camelCase")]
+pub struct FaultSpec {
+    // The fault fires at the index-th request (counting from 1) matching the request kind
+    // that the controller issues for the custom resource since the config is written
+    pub request: FaultRequestKind,
+    pub index: u64,
+    pub fault: Fault,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub enum FaultRequestKind {
+    Get,
+    List,
+    Create,
+    Delete,
+    Update,
+    UpdateStatus,
+    Apply,
+    Patch,
+    External,
+    // Any request that changes the cluster state or calls the external API,
+    // i.e., the requests after which the controller used to crash
+    Write,
+    Any,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub enum Fault {
+    // Crash the controller after reconcile_core returns the request and before the request is sent
+    CrashBeforeSend,
+    // Crash the controller after the request is sent and the response is received
+    CrashAfterSend,
+    // Answer the Kubernetes API request with the error instead of sending it
+    Error(InjectedError),
+    // Pass the response to reconcile_core after the delay
+    Delay { millis: u64 },
+    // Do not call the external API and pass no response to reconcile_core
+    Drop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub enum InjectedError {
+    Conflict,
+    Timeout,
+    ServerTimeout,
+    TooManyRequests,
+    ServiceUnavailable,
+    InternalError,
+}
+
+impl InjectedError {
+    pub fn to_api_error(self) -> APIError {
+        match self {
+            InjectedError::Conflict => APIError::Conflict,
+            InjectedError::Timeout => APIError::Timeout,
+            InjectedError::ServerTimeout => APIError::ServerTimeout,
+            InjectedError::TooManyRequests => APIError::TooManyRequests,
+            InjectedError::ServiceUnavailable => APIError::ServiceUnavailable,
+            InjectedError::InternalError => APIError::InternalError,
+        }
+    }
+}
+
+// fault_request_kinds returns all the request kinds that the request matches
+pub fn fault_request_kinds<I: View>(request: &Request<I>) -> Vec<FaultRequestKind> {
+    let kind = match request {
+        Request::KRequest(KubeAPIRequest::GetRequest(_)) => FaultRequestKind::Get,
+        Request::KRequest(KubeAPIRequest::ListRequest(_)) => FaultRequestKind::List,
+        Request::KRequest(KubeAPIRequest::CreateRequest(_)) => FaultRequestKind::Create,
+        Request::KRequest(KubeAPIRequest::DeleteRequest(_)) => FaultRequestKind::Delete,
+        Request::KRequest(KubeAPIRequest::UpdateRequest(_)) => FaultRequestKind::Update,
+        Request::KRequest(KubeAPIRequest::UpdateStatusRequest(_)) => FaultRequestKind::UpdateStatus,
+        Request::KRequest(KubeAPIRequest::ApplyRequest(_)) => FaultRequestKind::Apply,
+        Request::KRequest(KubeAPIRequest::PatchRequest(_)) => FaultRequestKind::Patch,
+        Request::ExternalRequest(_) => FaultRequestKind::External,
+    };
+    match kind {
+        FaultRequestKind::Get | FaultRequestKind::List => vec![kind, FaultRequestKind::Any],
+        _ => vec![kind, FaultRequestKind::Write, FaultRequestKind::Any],
+    }
+}
+
+// injected_error_response returns the response to the request that fails with the error
+pub fn injected_error_response(request: &KubeAPIRequest, error: APIError) -> KubeAPIResponse {
+    match request {
+        KubeAPIRequest::GetRequest(_) => {
+            KubeAPIResponse::GetResponse(KubeGetResponse { res: Err(error) })
+        }
+        KubeAPIRequest::ListRequest(_) => {
+            KubeAPIResponse::ListResponse(KubeListResponse { res: Err(error) })
+        }
+        KubeAPIRequest::CreateRequest(_) => {
+            KubeAPIResponse::CreateResponse(KubeCreateResponse { res: Err(error) })
+        }
+        KubeAPIRequest::DeleteRequest(_) => {
+            KubeAPIResponse::DeleteResponse(KubeDeleteResponse { res: Err(error) })
+        }
+        KubeAPIRequest::UpdateRequest(_) => {
+            KubeAPIResponse::UpdateResponse(KubeUpdateResponse { res: Err(error) })
+        }
+        KubeAPIRequest::UpdateStatusRequest(_) => {
+            KubeAPIResponse::UpdateStatusResponse(KubeUpdateStatusResponse { res: Err(error) })
+        }
+        KubeAPIRequest::ApplyRequest(_) => {
+            KubeAPIResponse::ApplyResponse(KubeApplyResponse { res: Err(error) })
+        }
+        KubeAPIRequest::PatchRequest(_) => {
+            KubeAPIResponse::PatchResponse(KubePatchResponse { res: Err(error) })
+        }
+    }
+}
+
+// FaultProgress is how far the faulted custom resource is in the fault injection config:
+// the number of requests of each counted kind (see FaultPlan::counted_kind) issued for it since the config was written
+// and the indices of the fired faults.
+// It is stored in the "progress" entry of the ConfigMap and updated after every request that changes it,
+// so the counting carries over across reconciles and controller restarts, and a crash does not fire again
+// at the same point after the restart. It also tells tools like the crash-point enumeration driver in e2e
+// how many requests a workload issues.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub struct FaultProgress {
+    #[serde(default)]
+    pub counts: BTreeMap<FaultRequestKind, u64>,
+    #[serde(default)]
+    pub fired: BTreeSet<usize>,
+}
+
+// FaultPlan is the faults of one reconcile, i.e., the spec and the progress read at the start of the reconcile.
+pub struct FaultPlan {
+    spec: FaultInjectionSpec,
+    progress: Mutex<FaultProgress>,
+    // The progress last read from or written to the config
+    recorded: Mutex<FaultProgress>,
+}
+
+impl FaultPlan {
+    pub fn new(spec: FaultInjectionSpec, progress: FaultProgress) -> FaultPlan {
+        FaultPlan {
+            spec: spec,
+            progress: Mutex::new(progress.clone()),
+            recorded: Mutex::new(progress),
+        }
+    }
+
+    // counted_kind returns whether the requests of the kind are counted,
+    // which is the case if a fault is on the kind or the spec asks to count it
+    fn counted_kind(&self, kind: &FaultRequestKind) -> bool {
+        self.spec.faults.iter().any(|fault| &fault.request == kind)
+            || self.spec.count.contains(kind)
+    }
+
+    // load reads the fault injection config and returns the faults for the reconcile of cr_key, if any
+    pub async fn load(client: &Client, cr_key: &String, log_header: &String) -> Option<FaultPlan> {
+        let config_map_api = Api::<ConfigMap>::namespaced(client.clone(), CONFIG_MAP_NAMESPACE);
+        let config_map = match config_map_api.get_opt(CONFIG_MAP_NAME).await {
+            Ok(Some(config_map)) => config_map,
+            Ok(None) => return None,
+            Err(err) => {
+                warn!(
+                    "{} Get {} failed with error: {}",
+                    log_header, CONFIG_MAP_NAME, err
+                );
+                return None;
+            }
+        };
+        match parse_fault_injection_config(&config_map) {
+            Ok((spec, progress)) if &spec.cr_key == cr_key => Some(FaultPlan::new(spec, progress)),
+            Ok(_) => None,
+            Err(err) => {
+                warn!("{} Invalid {}: {}", log_header, CONFIG_MAP_NAME, err);
+                None
+            }
+        }
+    }
+
+    // next_fault counts the request and returns the fault to inject into it, if any
+    pub fn next_fault<I: View>(&self, request: &Request<I>) -> Option<Fault> {
+        let kinds = fault_request_kinds(request);
+        let mut progress = self.progress.lock().unwrap();
+        for kind in kinds.iter().filter(|kind| self.counted_kind(kind)) {
+            *progress.counts.entry(*kind).or_insert(0) += 1;
+        }
+        let fault = self
+            .spec
+            .faults
+            .iter()
+            .enumerate()
+            .find(|(i, fault)| {
+                !progress.fired.contains(i)
+                    && kinds.contains(&fault.request)
+                    && progress.counts.get(&fault.request) == Some(&fault.index)
+            })
+            .map(|(i, fault)| (i, fault.fault.clone()));
+        match fault {
+            Some((i, fault)) => {
+                progress.fired.insert(i);
+                Some(fault)
+            }
+            None => None,
+        }
+    }
+
+    pub fn progress(&self) -> FaultProgress {
+        self.progress.lock().unwrap().clone()
+    }
+
+    // record_progress writes the progress to the config before the request is sent (or the controller crashes),
+    // unless the progress has not changed since it was last read or written, e.g., when the request is of no counted kind.
+    // If it fails, the caller must not inject the fault returned by next_fault, otherwise the fault fires again
+    // at the same point after a restart, e.g., a crash would crash the controller over and over.
+    pub async fn record_progress(&self, client: &Client) -> Result<(), deps_hack::kube::Error> {
+        let progress = self.progress();
+        if *self.recorded.lock().unwrap() == progress {
+            return Ok(());
+        }
+        let config_map_api = Api::<ConfigMap>::namespaced(client.clone(), CONFIG_MAP_NAMESPACE);
+        let mut config_map = config_map_api.get(CONFIG_MAP_NAME).await?;
+        config_map.data.get_or_insert_with(Default::default).insert(
+            "progress".to_string(),
+            deps_hack::serde_json::to_string(&progress).unwrap(),
+        );
+        config_map_api
+            .replace(CONFIG_MAP_NAME, &PostParams::default(), &config_map)
+            .await?;
+        *self.recorded.lock().unwrap() = progress;
+        Ok(())
+    }
+
+    // crash crashes the controller; the progress (including the crash) must have been recorded
+    pub fn crash(&self, log_header: &String) {
+        info!(
+            "{} Crash the controller as the fault injection config asks",
+            log_header
+        );
+        panic!();
+    }
+}
+
+// parse_fault_injection_config returns the spec and the progress in the config
+pub fn parse_fault_injection_config(
+    config_map: &ConfigMap,
+) -> Result<(FaultInjectionSpec, FaultProgress), String> {
     let data = config_map
         .data
         .as_ref()
         .ok_or_else(|| "Fail to unwrap data".to_string())?;
-    // The configuration should tell us a cr_key and we will crash the controller when it is managing that object
-    // This is to make the fault injection more deterministic when the controller manages multiple cr objects of different types
-    let cr_key_val = data
-        .get("cr_key")
-        .ok_or_else(|| "Fail to get cr_key".to_string())?;
-    // We only want to crash when the controller is managing the object identified by cr_key
-    if cr_key_val.to_string() != cr_key.to_string() {
-        return Ok(());
-    }
-    // The configuration should have the two entries:
-    // 1. the current number of requests that the controller has issued, and
-    // 2. the expected number of requests after which the controller should crash
-    let current_val = data
-        .get("current")
-        .ok_or_else(|| "Fail to get current".to_string())?;
-    let current = current_val
-        .parse::<i32>()
-        .map_err(|_e| "Fail to parse current value to i32".to_string())?;
-    let expected_val = data
-        .get("expected")
-        .ok_or_else(|| "Fail to get expected".to_string())?;
-    let expected = expected_val
-        .parse::<i32>()
-        .map_err(|_e| "Fail to parse expected value to i32".to_string())?;
-    // We increment current entry here before panic, otherwise we might end up crashing at the same point forever
-    config_map
-        .data
-        .as_mut()
-        .unwrap()
-        .insert("current".to_string(), (current + 1).to_string());
-    config_map_api
-        .replace(config_map_name, &PostParams::default(), &config_map)
-        .await
-        .map_err(|_e| "Fail to update fault injection config".to_string())?;
-    if current == expected {
-        // Now it is time to crash according to fault-injection-config
-        panic!();
-    }
-    return Ok(());
+    let spec_val = data
+        .get("spec")
+        .ok_or_else(|| "Fail to get spec".to_string())?;
+    // YAML is a superset of JSON, so both work
+    let spec = deps_hack::serde_yaml::from_str::<FaultInjectionSpec>(spec_val)
+        .map_err(|e| format!("Fail to parse spec: {}", e))?;
+    let progress = match data.get("progress") {
+        Some(progress_val) => deps_hack::serde_json::from_str::<FaultProgress>(progress_val)
+            .map_err(|e| format!("Fail to parse progress: {}", e))?,
+        None => FaultProgress::default(),
+    };
+    Ok((spec, progress))
 }
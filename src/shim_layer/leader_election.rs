@@ -0,0 +1,284 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use deps_hack::chrono::Utc;
+use deps_hack::k8s_openapi::api::coordination::v1::{Lease, LeaseSpec};
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::MicroTime;
+use deps_hack::kube::{
+    api::{Api, ObjectMeta, PostParams},
+    Client,
+};
+use deps_hack::kube_core::ErrorResponse;
+use deps_hack::tokio;
+use deps_hack::tracing::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// LeaderElector lets only one of several replicas of the same controller run reconciles at a time.
+// The replicas compete for a coordination.k8s.io/v1 Lease object:
+// (1) A replica acquires the lease if nobody holds it or the holder has not renewed it for lease_duration.
+// (2) The holder renews the lease every retry_period by updating its renewTime.
+// (3) Every update to the lease carries the resource version that the replica read,
+// so when two replicas race for the same lease only one of them wins.
+//
+// Like client-go, a replica never compares the renewTime written by another replica with its own wall clock.
+// Instead, it records when it (locally) observed the lease record change, and considers the lease expired
+// only if the record stays unchanged for lease_duration since then. So the replicas only need their clocks
+// to tick at roughly the same rate, not to agree on the time.
+//
+// A replica considers itself the leader only within lease_duration after it started the last successful
+// acquire or renew request. Since the other replicas observe the record written by that request no earlier than
+// the request starts, and then wait for lease_duration, the old leader stops reconciling before any other replica
+// takes over (assuming bounded clock drift). The requests that the old leader has already sent can still be
+// handled after that, which is modeled by Cluster::failover_controller
+// (see crate::kubernetes_cluster::spec::cluster_state_machine).
+pub struct LeaderElector {
+    client: Client,
+    config: LeaderElectionConfig,
+    state: Mutex<LeaderElectionState>,
+}
+
+// LeaderElectionConfig carries the options of leader election.
+#[derive(Clone, Debug)]
+pub struct LeaderElectionConfig {
+    // The name and namespace of the Lease object that the replicas compete for
+    pub lease_name: String,
+    pub lease_namespace: String,
+    // The identity of this replica written to the holderIdentity of the Lease object
+    pub identity: String,
+    // How long the lease is valid after it is acquired or renewed
+    pub lease_duration: Duration,
+    // How often the replica tries to acquire or renew the lease; it should be much shorter than lease_duration
+    pub retry_period: Duration,
+}
+
+impl LeaderElectionConfig {
+    // new uses the pod name (or the host name) as the identity of this replica,
+    // and the same durations as the default ones of client-go.
+    pub fn new(lease_name: &str, lease_namespace: &str) -> LeaderElectionConfig {
+        let identity = std::env::var("POD_NAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| format!("{}-{}", lease_name, std::process::id()));
+        LeaderElectionConfig {
+            lease_name: lease_name.to_string(),
+            lease_namespace: lease_namespace.to_string(),
+            identity: identity,
+            lease_duration: Duration::from_secs(15),
+            retry_period: Duration::from_secs(2),
+        }
+    }
+}
+
+// LeaderElectionState decides what a replica does with the lease it reads, without talking to the API server.
+// All the times are taken from the local monotonic clock, except the acquireTime and renewTime written
+// to the lease, which are only informational.
+#[derive(Clone, Debug)]
+pub struct LeaderElectionState {
+    identity: String,
+    lease_duration: Duration,
+    // The last lease record read or written by this replica and when this replica observed it changing
+    observed: Option<(LeaseSpec, Instant)>,
+    // When the last successful acquire or renew request started
+    last_renewed: Option<Instant>,
+}
+
+impl LeaderElectionState {
+    pub fn new(config: &LeaderElectionConfig) -> LeaderElectionState {
+        LeaderElectionState {
+            identity: config.identity.clone(),
+            lease_duration: config.lease_duration,
+            observed: None,
+            last_renewed: None,
+        }
+    }
+
+    pub fn is_leader(&self, now: Instant) -> bool {
+        match self.last_renewed {
+            Some(last_renewed) => now.saturating_duration_since(last_renewed) < self.lease_duration,
+            None => false,
+        }
+    }
+
+    // observe records the lease record read at now. The expiry of the lease restarts whenever the record changes.
+    pub fn observe(&mut self, record: &LeaseSpec, now: Instant) {
+        match &self.observed {
+            Some((observed_record, _)) if observed_record == record => {}
+            _ => self.observed = Some((record.clone(), now)),
+        }
+    }
+
+    // lease_expired checks whether the holder of the observed lease has failed to renew it
+    // within the lease duration since this replica observed the record.
+    // A lease without a holder is considered expired.
+    pub fn lease_expired(&self, now: Instant) -> bool {
+        let (record, observed_at) = match &self.observed {
+            Some(observed) => observed,
+            None => return true,
+        };
+        if record
+            .holder_identity
+            .as_ref()
+            .map_or(true, |holder| holder.is_empty())
+        {
+            return true;
+        }
+        let lease_duration =
+            Duration::from_secs(record.lease_duration_seconds.unwrap_or(0).max(0) as u64);
+        now.saturating_duration_since(*observed_at) >= lease_duration
+    }
+
+    // next_record returns the lease record that this replica should write at now, after reading current
+    // (None if the lease does not exist), or None if the lease is held by another replica.
+    // wall_clock_now is only used for the acquireTime and renewTime written to the lease.
+    pub fn next_record(
+        &mut self,
+        current: Option<&LeaseSpec>,
+        now: Instant,
+        wall_clock_now: MicroTime,
+    ) -> Option<LeaseSpec> {
+        let lease_duration_seconds = self.lease_duration.as_secs() as i32;
+        match current {
+            None => Some(LeaseSpec {
+                holder_identity: Some(self.identity.clone()),
+                lease_duration_seconds: Some(lease_duration_seconds),
+                acquire_time: Some(wall_clock_now.clone()),
+                renew_time: Some(wall_clock_now),
+                lease_transitions: Some(0),
+                ..LeaseSpec::default()
+            }),
+            Some(record) => {
+                self.observe(record, now);
+                let held_by_self = record.holder_identity.as_ref() == Some(&self.identity);
+                if !held_by_self && !self.lease_expired(now) {
+                    return None;
+                }
+                let mut record_prime = record.clone();
+                if !held_by_self {
+                    record_prime.holder_identity = Some(self.identity.clone());
+                    record_prime.acquire_time = Some(wall_clock_now.clone());
+                    record_prime.lease_transitions = Some(record.lease_transitions.unwrap_or(0) + 1);
+                }
+                record_prime.lease_duration_seconds = Some(lease_duration_seconds);
+                record_prime.renew_time = Some(wall_clock_now);
+                Some(record_prime)
+            }
+        }
+    }
+
+    // written records that this replica has written the record by a request started at attempt_start.
+    pub fn written(&mut self, record: LeaseSpec, attempt_start: Instant) {
+        self.observed = Some((record, attempt_start));
+        self.last_renewed = Some(attempt_start);
+    }
+}
+
+impl LeaderElector {
+    // start spawns a task that keeps acquiring or renewing the lease in the background.
+    pub fn start(client: Client, config: LeaderElectionConfig) -> Arc<LeaderElector> {
+        info!(
+            "{} competing for lease {}/{}",
+            config.identity, config.lease_namespace, config.lease_name
+        );
+        let elector = Arc::new(LeaderElector {
+            client: client,
+            state: Mutex::new(LeaderElectionState::new(&config)),
+            config: config,
+        });
+        let elector_clone = elector.clone();
+        tokio::spawn(async move {
+            loop {
+                let was_leader = elector_clone.is_leader();
+                if let Err(err) = elector_clone.try_acquire_or_renew().await {
+                    warn!(
+                        "{} fails to acquire or renew lease due to {}",
+                        elector_clone.config.identity, err
+                    );
+                }
+                let is_leader = elector_clone.is_leader();
+                if !was_leader && is_leader {
+                    info!("{} becomes the leader", elector_clone.config.identity);
+                } else if was_leader && !is_leader {
+                    warn!("{} is no longer the leader", elector_clone.config.identity);
+                }
+                tokio::time::sleep(elector_clone.config.retry_period).await;
+            }
+        });
+        elector
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.state.lock().unwrap().is_leader(Instant::now())
+    }
+
+    pub fn retry_period(&self) -> Duration {
+        self.config.retry_period
+    }
+
+    // wait_until_leader returns once this replica holds the lease.
+    pub async fn wait_until_leader(&self) {
+        while !self.is_leader() {
+            tokio::time::sleep(self.config.retry_period).await;
+        }
+    }
+
+    // try_acquire_or_renew tries to acquire the lease, or renew it if this replica already holds it.
+    // It returns Ok(true) if this replica holds the lease after the attempt.
+    async fn try_acquire_or_renew(&self) -> Result<bool, deps_hack::kube::Error> {
+        let lease_api = Api::<Lease>::namespaced(self.client.clone(), &self.config.lease_namespace);
+        // The lease expires no earlier than lease_duration after attempt_start from the view of other replicas,
+        // because they observe the record we write after attempt_start
+        let attempt_start = Instant::now();
+        let lease_o = lease_api.get_opt(&self.config.lease_name).await?;
+        let current = lease_o
+            .as_ref()
+            .map(|lease| lease.spec.clone().unwrap_or_default());
+        let record = match self.state.lock().unwrap().next_record(
+            current.as_ref(),
+            Instant::now(),
+            MicroTime(Utc::now()),
+        ) {
+            Some(record) => record,
+            None => return Ok(false),
+        };
+        match lease_o {
+            None => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.config.lease_name.clone()),
+                        namespace: Some(self.config.lease_namespace.clone()),
+                        ..ObjectMeta::default()
+                    },
+                    spec: Some(record.clone()),
+                };
+                match lease_api.create(&PostParams::default(), &lease).await {
+                    Err(deps_hack::kube::Error::Api(ErrorResponse { reason, .. }))
+                        if &reason == "AlreadyExists" =>
+                    {
+                        return Ok(false);
+                    }
+                    Err(err) => return Err(err),
+                    Ok(_) => {}
+                }
+            }
+            Some(mut lease) => {
+                lease.spec = Some(record.clone());
+                // The lease carries the resource version we got,
+                // so replace fails with Conflict if another replica updated the lease after our get
+                match lease_api
+                    .replace(&self.config.lease_name, &PostParams::default(), &lease)
+                    .await
+                {
+                    Err(deps_hack::kube::Error::Api(ErrorResponse { reason, .. }))
+                        if &reason == "Conflict" =>
+                    {
+                        return Ok(false);
+                    }
+                    Err(err) => return Err(err),
+                    Ok(_) => {}
+                }
+            }
+        }
+        self.state.lock().unwrap().written(record, attempt_start);
+        Ok(true)
+    }
+}
@@ -0,0 +1,392 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::shim_layer::cluster_snapshot::*;
+use deps_hack::anyhow::Result;
+use deps_hack::tokio;
+use deps_hack::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use deps_hack::tracing::{info, warn};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Metrics collects the metrics of the shim layer and renders them in the Prometheus text format,
+// which is served at /metrics if ControllerConfig::metrics_addr is set (see serve_metrics).
+// Every metric is labeled by the kind of the custom resource so that the controllers that run
+// several reconcilers in one binary (e.g., the fluent controller) can be told apart.
+//
+// The metrics are kept in memory with no dependency on a metrics library;
+// the number of series is small because all the labels come from small sets:
+// the kinds, the verbs of KubeAPIRequest, the variants of APIError and the outcomes of reconciles.
+
+const RECONCILE_TOTAL: &str = "controller_reconcile_total";
+const RECONCILE_DURATION_SECONDS: &str = "controller_reconcile_duration_seconds";
+const RECONCILE_CORE_ITERATIONS: &str = "controller_reconcile_core_iterations";
+const RECONCILE_CORE_ERRORS_TOTAL: &str = "controller_reconcile_core_errors_total";
+const API_REQUESTS_TOTAL: &str = "controller_api_requests_total";
+const EXTERNAL_API_CALL_DURATION_SECONDS: &str = "controller_external_api_call_duration_seconds";
+
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+const ITERATION_BUCKETS: &[f64] = &[1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0];
+
+type Labels = Vec<(&'static str, String)>;
+
+struct Histogram {
+    buckets: &'static [f64],
+    // counts[i] is the number of observations in (buckets[i - 1], buckets[i]],
+    // and the last one is the number of observations above the largest bucket
+    counts: Vec<u64>,
+    sum: f64,
+}
+
+enum Series {
+    Counter(u64),
+    Histogram(Histogram),
+}
+
+struct Family {
+    help: &'static str,
+    series: BTreeMap<Labels, Series>,
+}
+
+pub struct Metrics {
+    families: Mutex<BTreeMap<&'static str, Family>>,
+}
+
+impl Metrics {
+    pub fn new() -> Metrics {
+        Metrics {
+            families: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    // reconcile_finished records a reconcile of the kind that ends with the outcome (done, error or interrupted)
+    // after running reconcile_core for the given number of iterations
+    pub fn reconcile_finished(
+        &self,
+        kind: &str,
+        outcome: &str,
+        duration: Duration,
+        iterations: u64,
+    ) {
+        self.inc(
+            RECONCILE_TOTAL,
+            "Number of reconciles by outcome",
+            vec![("kind", kind.to_string()), ("outcome", outcome.to_string())],
+        );
+        self.observe(
+            RECONCILE_DURATION_SECONDS,
+            "Duration of reconciles in seconds",
+            DURATION_BUCKETS,
+            vec![("kind", kind.to_string())],
+            duration.as_secs_f64(),
+        );
+        self.observe(
+            RECONCILE_CORE_ITERATIONS,
+            "Number of reconcile_core invocations per reconcile",
+            ITERATION_BUCKETS,
+            vec![("kind", kind.to_string())],
+            iterations as f64,
+        );
+    }
+
+    // reconcile_core_error records a reconcile that ends because reconcile_core reports an error
+    pub fn reconcile_core_error(&self, kind: &str) {
+        self.inc(
+            RECONCILE_CORE_ERRORS_TOTAL,
+            "Number of reconciles ended by reconcile_core reporting an error",
+            vec![("kind", kind.to_string())],
+        );
+    }
+
+    // api_request records a request to the Kubernetes API sent on behalf of reconcile_core,
+    // where verb and resource_kind come from kube_request_verb_and_kind
+    pub fn api_request(
+        &self,
+        kind: &str,
+        verb: &str,
+        resource_kind: &str,
+        response: &KubeAPIResponse,
+    ) {
+        self.inc(
+            API_REQUESTS_TOTAL,
+            "Number of requests to the Kubernetes API by verb, resource kind and outcome",
+            vec![
+                ("kind", kind.to_string()),
+                ("verb", verb.to_string()),
+                ("resource_kind", resource_kind.to_string()),
+                ("outcome", kube_response_outcome(response)),
+            ],
+        );
+    }
+
+    // external_api_call records a call to the external API sent on behalf of reconcile_core
+    pub fn external_api_call(&self, kind: &str, duration: Duration) {
+        self.observe(
+            EXTERNAL_API_CALL_DURATION_SECONDS,
+            "Latency of external API calls in seconds",
+            DURATION_BUCKETS,
+            vec![("kind", kind.to_string())],
+            duration.as_secs_f64(),
+        );
+    }
+
+    fn inc(&self, name: &'static str, help: &'static str, labels: Labels) {
+        let mut families = self.families.lock().unwrap();
+        let family = families.entry(name).or_insert_with(|| Family {
+            help: help,
+            series: BTreeMap::new(),
+        });
+        if let Series::Counter(count) = family.series.entry(labels).or_insert(Series::Counter(0)) {
+            *count += 1;
+        }
+    }
+
+    fn observe(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        buckets: &'static [f64],
+        labels: Labels,
+        value: f64,
+    ) {
+        let mut families = self.families.lock().unwrap();
+        let family = families.entry(name).or_insert_with(|| Family {
+            help: help,
+            series: BTreeMap::new(),
+        });
+        let series = family.series.entry(labels).or_insert_with(|| {
+            Series::Histogram(Histogram {
+                buckets: buckets,
+                counts: vec![0; buckets.len() + 1],
+                sum: 0.0,
+            })
+        });
+        if let Series::Histogram(histogram) = series {
+            let i = histogram
+                .buckets
+                .iter()
+                .position(|bound| value <= *bound)
+                .unwrap_or(histogram.buckets.len());
+            histogram.counts[i] += 1;
+            histogram.sum += value;
+        }
+    }
+
+    // render renders all the metrics in the Prometheus text format (version 0.0.4)
+    pub fn render(&self) -> String {
+        let families = self.families.lock().unwrap();
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let is_counter = matches!(family.series.values().next(), Some(Series::Counter(_)));
+            writeln!(out, "# HELP {} {}", name, family.help).unwrap();
+            writeln!(
+                out,
+                "# TYPE {} {}",
+                name,
+                if is_counter { "counter" } else { "histogram" }
+            )
+            .unwrap();
+            for (labels, series) in family.series.iter() {
+                match series {
+                    Series::Counter(count) => {
+                        writeln!(out, "{}{} {}", name, render_labels(labels, None), count).unwrap();
+                    }
+                    Series::Histogram(histogram) => {
+                        let mut cumulative = 0;
+                        for (bound, count) in histogram.buckets.iter().zip(histogram.counts.iter())
+                        {
+                            cumulative += count;
+                            writeln!(
+                                out,
+                                "{}_bucket{} {}",
+                                name,
+                                render_labels(labels, Some(&bound.to_string())),
+                                cumulative
+                            )
+                            .unwrap();
+                        }
+                        let total: u64 = histogram.counts.iter().sum();
+                        writeln!(
+                            out,
+                            "{}_bucket{} {}",
+                            name,
+                            render_labels(labels, Some("+Inf")),
+                            total
+                        )
+                        .unwrap();
+                        writeln!(
+                            out,
+                            "{}_sum{} {}",
+                            name,
+                            render_labels(labels, None),
+                            histogram.sum
+                        )
+                        .unwrap();
+                        writeln!(
+                            out,
+                            "{}_count{} {}",
+                            name,
+                            render_labels(labels, None),
+                            total
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn render_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub fn kube_request_verb_and_kind(request: &KubeAPIRequest) -> (&'static str, String) {
+    match request {
+        KubeAPIRequest::GetRequest(req) => ("get", req.api_resource.as_kube_ref().kind.clone()),
+        KubeAPIRequest::ListRequest(req) => ("list", req.api_resource.as_kube_ref().kind.clone()),
+        KubeAPIRequest::CreateRequest(req) => {
+            ("create", req.api_resource.as_kube_ref().kind.clone())
+        }
+        KubeAPIRequest::DeleteRequest(req) => {
+            ("delete", req.api_resource.as_kube_ref().kind.clone())
+        }
+        KubeAPIRequest::UpdateRequest(req) => {
+            ("update", req.api_resource.as_kube_ref().kind.clone())
+        }
+        KubeAPIRequest::UpdateStatusRequest(req) => {
+            ("update_status", req.api_resource.as_kube_ref().kind.clone())
+        }
+        KubeAPIRequest::ApplyRequest(req) => ("apply", req.api_resource.as_kube_ref().kind.clone()),
+        KubeAPIRequest::PatchRequest(req) => ("patch", req.api_resource.as_kube_ref().kind.clone()),
+    }
+}
+
+// kube_response_outcome returns "ok" if the request succeeds, or the APIError it fails with
+pub fn kube_response_outcome(response: &KubeAPIResponse) -> String {
+    let err = match response {
+        KubeAPIResponse::GetResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::ListResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::CreateResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::DeleteResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::UpdateResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::UpdateStatusResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::ApplyResponse(resp) => resp.res.as_ref().err(),
+        KubeAPIResponse::PatchResponse(resp) => resp.res.as_ref().err(),
+    };
+    match err {
+        Some(err) => format!("{:?}", err),
+        None => "ok".to_string(),
+    }
+}
+
+// serve_metrics serves the metrics at http://<addr>/metrics until the controller exits,
+// and the result of the probe (see check_state_matches) for each custom resource at http://<addr>/state-matches/<namespace>/<name>:
+// 200 if the current state matches the custom resource, 503 if it does not, 404 if the custom resource does not exist
+// and 501 if the reconciler has no checker.
+// It speaks just enough HTTP/1.1 for Prometheus and curl: each connection gets one response and is then closed.
+pub async fn serve_metrics(
+    metrics: Arc<Metrics>,
+    probe: StateMatchesProbe,
+    addr: String,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    info!("serving metrics at http://{}/metrics", addr);
+    loop {
+        let (mut stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!("failed to accept metrics connection: {}", err);
+                continue;
+            }
+        };
+        let metrics = metrics.clone();
+        let probe = probe.clone();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 4096];
+            let n = match stream.read(&mut buf).await {
+                Ok(n) => n,
+                Err(_) => return,
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = match request.lines().next() {
+                Some(line) if line.starts_with("GET ") => line["GET ".len()..]
+                    .split(' ')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                _ => String::new(),
+            };
+            let response = if path == "/metrics" {
+                http_response("200 OK", "text/plain; version=0.0.4", metrics.render())
+            } else if let Some((namespace, name)) = path
+                .strip_prefix("/state-matches/")
+                .and_then(|key| key.split_once('/'))
+            {
+                match probe(namespace.to_string(), name.to_string()).await {
+                    Ok(StateMatches::Matches) => {
+                        http_response("200 OK", "text/plain", "matches\n".to_string())
+                    }
+                    Ok(StateMatches::DoesNotMatch) => http_response(
+                        "503 Service Unavailable",
+                        "text/plain",
+                        "does not match\n".to_string(),
+                    ),
+                    Ok(StateMatches::NotFound) => {
+                        http_response("404 Not Found", "text/plain", "not found\n".to_string())
+                    }
+                    Ok(StateMatches::Unchecked) => http_response(
+                        "501 Not Implemented",
+                        "text/plain",
+                        "no checker\n".to_string(),
+                    ),
+                    Err(err) => http_response(
+                        "500 Internal Server Error",
+                        "text/plain",
+                        format!("{}\n", err),
+                    ),
+                }
+            } else {
+                http_response("404 Not Found", "text/plain", "not found\n".to_string())
+            };
+            if let Err(err) = stream.write_all(response.as_bytes()).await {
+                warn!("failed to write metrics response: {}", err);
+            }
+            let _ = stream.shutdown().await;
+        });
+    }
+}
+
+fn http_response(status: &str, content_type: &str, body: String) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
@@ -1,4 +1,13 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+pub mod cluster_snapshot;
 pub mod controller_runtime;
 pub mod fault_injection;
+pub mod leader_election;
+pub mod metrics;
+pub mod read_cache;
+pub mod reconcile_trace;
+pub mod refinement_monitor;
+pub mod requeue;
+pub mod simulation;
+pub mod worker_pool;
@@ -0,0 +1,212 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::*, resource::*};
+use crate::vstd_ext::string_map::StringMap;
+use deps_hack::futures::{future, StreamExt};
+use deps_hack::kube::{
+    api::{Api, ApiResource},
+    runtime::{
+        reflector::{self, store::Writer, ObjectRef, Store},
+        watcher, WatchStreamExt,
+    },
+    Client,
+};
+use deps_hack::tokio;
+use deps_hack::tracing::{info, warn};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// ReadCache answers the get and list requests issued by reconcile_core from local stores,
+// instead of sending every read to the Kubernetes API.
+// Each store is populated by a reflector that watches one kind of objects in one namespace,
+// and is created lazily when the first read to that (kind, namespace) arrives.
+//
+// Staleness contract:
+// (1) A store only serves reads after its initial list completes, so it never answers from an empty store
+// that has not synced yet. If the initial list does not complete within STORE_READY_TIMEOUT, the read fails
+// with APIError::Timeout and the store is dropped, so the next read starts a fresh reflector.
+// (2) Each object returned by the cache was stored in the Kubernetes API at some point,
+// but it can be older than the latest version (including the writes issued by the same reconcile).
+// Likewise, an object that is missing from the cache might exist in the Kubernetes API.
+// (3) Different objects can be stale by different amounts because their watch events are delivered independently.
+// This contract is modeled by Cluster::sync_cache and Cluster::serve_read_from_cache
+// (see crate::kubernetes_cluster::spec::cluster_state_machine).
+// Reconcilers that need a quorum read should not run with the cache enabled.
+// Note that the proofs of the controllers in controller_examples all assume Cluster::stale_read_disabled,
+// so none of them is verified to run with the cache enabled.
+pub struct ReadCache {
+    client: Client,
+    stores: Mutex<HashMap<StoreKey, CachedStore>>,
+    next_generation: Mutex<u64>,
+}
+
+// STORE_READY_TIMEOUT bounds how long a read waits for the initial list of a new store.
+const STORE_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+// CachedStore is a store with the reflector that populates it.
+// The generation tells apart the stores created for the same key after an eviction.
+struct CachedStore {
+    store: Store<deps_hack::kube::api::DynamicObject>,
+    reflector: tokio::task::JoinHandle<()>,
+    generation: u64,
+}
+
+// StoreKey identifies the store for one kind of objects in one namespace.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct StoreKey {
+    group: String,
+    version: String,
+    kind: String,
+    namespace: String,
+}
+
+impl StoreKey {
+    fn new(api_resource: &ApiResource, namespace: &str) -> StoreKey {
+        StoreKey {
+            group: api_resource.group.clone(),
+            version: api_resource.version.clone(),
+            kind: api_resource.kind.clone(),
+            namespace: namespace.to_string(),
+        }
+    }
+}
+
+impl ReadCache {
+    pub fn new(client: Client) -> ReadCache {
+        ReadCache {
+            client: client,
+            stores: Mutex::new(HashMap::new()),
+            next_generation: Mutex::new(0),
+        }
+    }
+
+    pub async fn get(&self, get_req: &KubeGetRequest) -> Result<DynamicObject, APIError> {
+        let api_resource = get_req.api_resource.as_kube_ref();
+        let store = self.ready_store(api_resource, &get_req.namespace).await?;
+        let obj_ref = ObjectRef::new_with(&get_req.name, api_resource.clone()).within(&get_req.namespace);
+        match store.get(&obj_ref) {
+            Some(obj) => Ok(DynamicObject::from_kube(obj.as_ref().clone())),
+            None => Err(APIError::ObjectNotFound),
+        }
+    }
+
+    pub async fn list(&self, list_req: &KubeListRequest) -> Result<Vec<DynamicObject>, APIError> {
+        if list_req.has_invalid_selector() {
+            return Err(APIError::BadRequest);
+        }
+        let store = self.ready_store(list_req.api_resource.as_kube_ref(), &list_req.namespace).await?;
+        // The store holds all the objects of the kind in the namespace,
+        // so the selectors are applied here (as what the Kubernetes API does for the uncached list).
+        let mut objs = store
+            .state()
+            .into_iter()
+            .filter(|obj| list_req_selects(list_req, obj))
+            .collect::<Vec<_>>();
+        // The Kubernetes API returns the list ordered by name, so we do the same here
+        // to make the cached list indistinguishable from the uncached one (modulo staleness).
+        objs.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        Ok(objs
+            .into_iter()
+            .map(|obj| DynamicObject::from_kube(obj.as_ref().clone()))
+            .collect())
+    }
+
+    // ready_store returns the store for the (kind, namespace) after the store finishes its initial list.
+    // If the store does not exist yet, it starts a reflector to populate it.
+    // If the store does not become ready within STORE_READY_TIMEOUT, it evicts the store
+    // (and stops its reflector) so that the next read starts over, and returns an error.
+    async fn ready_store(
+        &self,
+        api_resource: &ApiResource,
+        namespace: &str,
+    ) -> Result<Store<deps_hack::kube::api::DynamicObject>, APIError> {
+        let key = StoreKey::new(api_resource, namespace);
+        let (store, generation) = {
+            let mut stores = self.stores.lock().unwrap();
+            let cached_store = stores
+                .entry(key.clone())
+                .or_insert_with(|| self.start_reflector(api_resource, namespace));
+            (cached_store.store.clone(), cached_store.generation)
+        };
+        let err = match tokio::time::timeout(STORE_READY_TIMEOUT, store.wait_until_ready()).await {
+            Ok(Ok(())) => return Ok(store),
+            Ok(Err(err)) => {
+                warn!(
+                    "Store for {}/{} is not ready: {}",
+                    api_resource.kind, namespace, err
+                );
+                APIError::Other
+            }
+            Err(_) => {
+                warn!(
+                    "Store for {}/{} is not ready after {:?}",
+                    api_resource.kind, namespace, STORE_READY_TIMEOUT
+                );
+                APIError::Timeout
+            }
+        };
+        self.evict(&key, generation);
+        Err(err)
+    }
+
+    // evict drops the store of the key and stops its reflector,
+    // unless the store has already been replaced by another read.
+    fn evict(&self, key: &StoreKey, generation: u64) {
+        let mut stores = self.stores.lock().unwrap();
+        if stores.get(key).map(|cached_store| cached_store.generation) == Some(generation) {
+            if let Some(cached_store) = stores.remove(key) {
+                cached_store.reflector.abort();
+            }
+        }
+    }
+
+    fn start_reflector(&self, api_resource: &ApiResource, namespace: &str) -> CachedStore {
+        info!("Starting reflector for {}/{}", api_resource.kind, namespace);
+        let writer = Writer::new(api_resource.clone());
+        let store = writer.as_reader();
+        let api = Api::<deps_hack::kube::api::DynamicObject>::namespaced_with(
+            self.client.clone(),
+            namespace,
+            api_resource,
+        );
+        // The reflector keeps running (and retrying with backoff on watch errors)
+        // for as long as the controller is alive, or until the store is evicted.
+        let reflector = tokio::spawn(
+            reflector::reflector(writer, watcher(api, watcher::Config::default()))
+                .default_backoff()
+                .applied_objects()
+                .for_each(|_| future::ready(())),
+        );
+        let generation = {
+            let mut next_generation = self.next_generation.lock().unwrap();
+            *next_generation += 1;
+            *next_generation
+        };
+        CachedStore {
+            store: store,
+            reflector: reflector,
+            generation: generation,
+        }
+    }
+}
+
+// list_req_selects checks whether the cached obj matches the label selector and the field selector of list_req.
+fn list_req_selects(list_req: &KubeListRequest, obj: &deps_hack::kube::api::DynamicObject) -> bool {
+    let label_selector_matches = match &list_req.label_selector {
+        Some(label_selector) => label_selector.matches(StringMap::from_rust_map(
+            obj.metadata.labels.clone().unwrap_or_default(),
+        )),
+        None => true,
+    };
+    let field_selector_matches = match &list_req.field_selector {
+        Some(field_selector) => match &field_selector.name {
+            Some(name) => obj.metadata.name.as_ref() == Some(name),
+            None => true,
+        },
+        None => true,
+    };
+    label_selector_matches && field_selector_matches
+}
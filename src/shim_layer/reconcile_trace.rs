@@ -0,0 +1,473 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::exec::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, dynamic::*, resource::*,
+};
+use crate::kubernetes_api_objects::spec::api_method::PatchType;
+use crate::reconciler::exec::{io::*, reconciler::*};
+use deps_hack::kube::api::Resource;
+use deps_hack::serde::{de::DeserializeOwned, Deserialize, Serialize};
+use deps_hack::serde_json::{json, Value};
+use deps_hack::tracing::warn;
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+
+// A reconcile trace records one reconcile done by reconcile_with (or SimulatedCluster::reconcile):
+// the cr object that reconcile_core gets, each request issued by reconcile_core
+// together with the response fed back to it, and how the reconcile ends.
+// The requests and responses are recorded in the form that reconcile_core sees them,
+// i.e., after the shim layer converts them from or to kube-rs types,
+// so replay_reconcile can feed the same inputs to reconcile_core again.
+//
+// Comparing a replay with the recorded trace tells where a misbehaving reconcile goes wrong:
+// (1) If reconcile_core issues the same requests in the replay, it did exactly what the verified code is supposed to do
+// on the recorded inputs, so the problem is in the recorded inputs (i.e., the cluster or the shim layer that
+// produces them) or in the assumptions of the proof.
+// (2) If reconcile_core issues a different request in the replay, the shim layer did not send or record
+// what reconcile_core issued, or reconcile_core is not deterministic; either way the trusted code is to blame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub struct ReconcileTrace {
+    // kind/namespace/name of the cr object
+    pub cr_key: String,
+    // The cr object in json
+    pub cr: Value,
+    pub steps: Vec<ReconcileTraceStep>,
+    pub outcome: ReconcileTraceOutcome,
+}
+
+// Each step is one invocation of reconcile_core.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub struct ReconcileTraceStep {
+    // The request returned by reconcile_core, if any
+    pub request: Option<Value>,
+    // The response fed to the next invocation of reconcile_core, if any
+    pub response: Option<Value>,
+    // The status code and message of the failed request, if any, which are not in the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_details: Option<APIErrorDetails>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "deps_hack::serde")]
+pub enum ReconcileTraceOutcome {
+    // reconcile_done holds after the last step
+    Done,
+    // reconcile_error holds after the last step
+    Error,
+    // The shim layer stops the reconcile before it is done or fails, e.g., when losing the lease
+    Interrupted,
+}
+
+// TracedExternalAPI tells how to record the requests to and the responses from the external api in a trace,
+// and how to read the recorded responses back in replay_reconcile.
+// Each ExternalAPIShimLayer used by a controller implements it.
+pub trait TracedExternalAPI: ExternalAPIShimLayer {
+    fn input_to_json(input: &Self::Input) -> Value;
+    fn output_to_json(output: &Self::Output) -> Value;
+    fn output_from_json(output: &Value) -> Option<Self::Output>;
+}
+
+impl TracedExternalAPI for EmptyAPIShimLayer {
+    fn input_to_json(_input: &EmptyType) -> Value {
+        Value::Null
+    }
+
+    fn output_to_json(_output: &EmptyType) -> Value {
+        Value::Null
+    }
+
+    fn output_from_json(_output: &Value) -> Option<EmptyType> {
+        Some(EmptyType {})
+    }
+}
+
+impl ReconcileTrace {
+    pub fn new<K: Serialize>(cr_key: String, cr: &K) -> ReconcileTrace {
+        ReconcileTrace {
+            cr_key: cr_key,
+            cr: deps_hack::serde_json::to_value(cr).unwrap(),
+            steps: Vec::new(),
+            outcome: ReconcileTraceOutcome::Interrupted,
+        }
+    }
+
+    pub fn push_step<E: TracedExternalAPI>(
+        &mut self,
+        request: Option<Value>,
+        response: &Option<Response<E::Output>>,
+        error_details: Option<APIErrorDetails>,
+    ) {
+        self.steps.push(ReconcileTraceStep {
+            request: request,
+            response: response.as_ref().map(|resp| response_to_json::<E>(resp)),
+            error_details: error_details,
+        });
+    }
+}
+
+// ReconcileTraceRecorder writes each reconcile trace as one line of json to a file.
+pub struct ReconcileTraceRecorder {
+    file: Mutex<File>,
+}
+
+impl ReconcileTraceRecorder {
+    pub fn new(path: &str) -> std::io::Result<ReconcileTraceRecorder> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(ReconcileTraceRecorder {
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn record(&self, trace: &ReconcileTrace) {
+        let line = deps_hack::serde_json::to_string(trace).unwrap();
+        if let Err(err) = writeln!(self.file.lock().unwrap(), "{}", line) {
+            warn!(
+                "Fail to record the reconcile trace of {} due to {}",
+                trace.cr_key, err
+            );
+        }
+    }
+}
+
+// read_reconcile_traces reads the traces written by ReconcileTraceRecorder.
+pub fn read_reconcile_traces(path: &str) -> std::io::Result<Vec<ReconcileTrace>> {
+    let content = std::fs::read_to_string(path)?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            deps_hack::serde_json::from_str(line)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayResult {
+    // reconcile_core issues the same requests and ends in the same way as recorded
+    Consistent,
+    // reconcile_core issues a different request at the step from the recorded one
+    RequestMismatch {
+        step: usize,
+        recorded: Option<Value>,
+        replayed: Option<Value>,
+    },
+    // reconcile_core ends differently from the recorded trace
+    // (the number of steps is the number of steps that reconcile_core takes in the replay)
+    OutcomeMismatch {
+        steps: usize,
+        recorded: ReconcileTraceOutcome,
+        replayed: Option<ReconcileTraceOutcome>,
+    },
+    // The recorded response at the step cannot be read back
+    UnreadableResponse {
+        step: usize,
+    },
+}
+
+// replay_reconcile re-runs reconcile_core on the cr object and the responses recorded in the trace,
+// without talking to Kubernetes or the external system, and reports the first step where it diverges from the trace.
+pub fn replay_reconcile<K, ReconcilerType>(trace: &ReconcileTrace) -> ReplayResult
+where
+    K: Resource + DeserializeOwned,
+    ReconcilerType: Reconciler,
+    ReconcilerType::R: ResourceWrapper<K>,
+    ReconcilerType::ExternalAPIType: TracedExternalAPI,
+{
+    let cr = deps_hack::serde_json::from_value::<K>(trace.cr.clone()).unwrap();
+    let cr_wrapper = ReconcilerType::R::from_kube(cr);
+    let mut state = ReconcilerType::reconcile_init_state();
+    let mut resp_option: Option<
+        Response<<ReconcilerType::ExternalAPIType as ExternalAPIShimLayer>::Output>,
+    > = None;
+    for (i, step) in trace.steps.iter().enumerate() {
+        if ReconcilerType::reconcile_done(&state) || ReconcilerType::reconcile_error(&state) {
+            return ReplayResult::OutcomeMismatch {
+                steps: i,
+                recorded: trace.outcome.clone(),
+                replayed: Some(outcome_of::<ReconcilerType>(&state)),
+            };
+        }
+        let (state_prime, request_option) =
+            ReconcilerType::reconcile_core(&cr_wrapper, resp_option, state);
+        let replayed = request_option
+            .as_ref()
+            .map(|req| request_to_json::<ReconcilerType::ExternalAPIType>(req));
+        if replayed != step.request {
+            return ReplayResult::RequestMismatch {
+                step: i,
+                recorded: step.request.clone(),
+                replayed: replayed,
+            };
+        }
+        resp_option = match &step.response {
+            Some(resp) => match response_from_json::<ReconcilerType::ExternalAPIType>(resp) {
+                Some(resp) => Some(resp),
+                None => return ReplayResult::UnreadableResponse { step: i },
+            },
+            None => None,
+        };
+        state = state_prime;
+    }
+    let replayed =
+        if ReconcilerType::reconcile_done(&state) || ReconcilerType::reconcile_error(&state) {
+            Some(outcome_of::<ReconcilerType>(&state))
+        } else {
+            None
+        };
+    // An interrupted reconcile can stop at any point, so any outcome of the replay is consistent with it
+    if trace.outcome == ReconcileTraceOutcome::Interrupted
+        || replayed.as_ref() == Some(&trace.outcome)
+    {
+        ReplayResult::Consistent
+    } else {
+        ReplayResult::OutcomeMismatch {
+            steps: trace.steps.len(),
+            recorded: trace.outcome.clone(),
+            replayed: replayed,
+        }
+    }
+}
+
+// outcome_of is also used by the refinement monitor (see crate::shim_layer::refinement_monitor)
+// to compare how reconcile_core ends.
+pub fn outcome_of<ReconcilerType: Reconciler>(state: &ReconcilerType::T) -> ReconcileTraceOutcome {
+    if ReconcilerType::reconcile_done(state) {
+        ReconcileTraceOutcome::Done
+    } else {
+        ReconcileTraceOutcome::Error
+    }
+}
+
+fn api_resource_to_json(api_resource: &ApiResource) -> Value {
+    kube_api_resource_to_json(api_resource.as_kube_ref())
+}
+
+// kube_api_resource_to_json is also used by the executable models of reconcilers
+// (see crate::shim_layer::refinement_monitor) to build the requests they expect.
+pub fn kube_api_resource_to_json(api_resource: &deps_hack::kube::api::ApiResource) -> Value {
+    json!({
+        "group": api_resource.group,
+        "version": api_resource.version,
+        "kind": api_resource.kind,
+        "plural": api_resource.plural,
+    })
+}
+
+fn object_to_json(obj: &DynamicObject) -> Value {
+    deps_hack::serde_json::to_value(obj.as_kube_ref()).unwrap()
+}
+
+fn object_from_json(obj: &Value) -> Option<DynamicObject> {
+    deps_hack::serde_json::from_value::<deps_hack::kube::api::DynamicObject>(obj.clone())
+        .ok()
+        .map(|obj| DynamicObject::from_kube(obj))
+}
+
+fn api_error_from_str(err: &str) -> Option<APIError> {
+    match err {
+        "BadRequest" => Some(APIError::BadRequest),
+        "Conflict" => Some(APIError::Conflict),
+        "Forbidden" => Some(APIError::Forbidden),
+        "Invalid" => Some(APIError::Invalid),
+        "ObjectNotFound" => Some(APIError::ObjectNotFound),
+        "ObjectAlreadyExists" => Some(APIError::ObjectAlreadyExists),
+        "NotSupported" => Some(APIError::NotSupported),
+        "InternalError" => Some(APIError::InternalError),
+        "Timeout" => Some(APIError::Timeout),
+        "ServerTimeout" => Some(APIError::ServerTimeout),
+        "Unauthorized" => Some(APIError::Unauthorized),
+        "TooManyRequests" => Some(APIError::TooManyRequests),
+        "Gone" => Some(APIError::Gone),
+        "Expired" => Some(APIError::Expired),
+        "MethodNotAllowed" => Some(APIError::MethodNotAllowed),
+        "RequestEntityTooLarge" => Some(APIError::RequestEntityTooLarge),
+        "ServiceUnavailable" => Some(APIError::ServiceUnavailable),
+        "Other" => Some(APIError::Other),
+        _ => None,
+    }
+}
+
+// A result is recorded as {"Ok": ...} or {"Err": "<APIError>"}
+fn result_to_json<T>(res: &Result<T, APIError>, value_to_json: impl Fn(&T) -> Value) -> Value {
+    match res {
+        Ok(value) => json!({ "Ok": value_to_json(value) }),
+        Err(err) => json!({ "Err": format!("{:?}", err) }),
+    }
+}
+
+fn result_from_json<T>(
+    res: &Value,
+    value_from_json: impl Fn(&Value) -> Option<T>,
+) -> Option<Result<T, APIError>> {
+    if let Some(value) = res.get("Ok") {
+        value_from_json(value).map(|value| Ok(value))
+    } else {
+        api_error_from_str(res.get("Err")?.as_str()?).map(|err| Err(err))
+    }
+}
+
+fn object_result_to_json(res: &Result<DynamicObject, APIError>) -> Value {
+    result_to_json(res, object_to_json)
+}
+
+fn object_result_from_json(res: &Value) -> Option<Result<DynamicObject, APIError>> {
+    result_from_json(res, object_from_json)
+}
+
+pub fn request_to_json<E: TracedExternalAPI>(request: &Request<E::Input>) -> Value {
+    match request {
+        Request::KRequest(req) => json!({ "KRequest": kube_request_to_json(req) }),
+        Request::ExternalRequest(req) => json!({ "ExternalRequest": E::input_to_json(req) }),
+    }
+}
+
+pub fn kube_request_to_json(request: &KubeAPIRequest) -> Value {
+    match request {
+        KubeAPIRequest::GetRequest(req) => json!({ "Get": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+        }}),
+        KubeAPIRequest::ListRequest(req) => {
+            let lp = req.list_params();
+            json!({ "List": {
+                "api_resource": api_resource_to_json(&req.api_resource),
+                "namespace": req.namespace,
+                "label_selector": lp.label_selector,
+                "field_selector": lp.field_selector,
+            }})
+        }
+        KubeAPIRequest::CreateRequest(req) => json!({ "Create": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "obj": object_to_json(&req.obj),
+        }}),
+        KubeAPIRequest::DeleteRequest(req) => json!({ "Delete": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "delete_params": deps_hack::serde_json::to_value(&req.delete_params()).unwrap(),
+        }}),
+        KubeAPIRequest::UpdateRequest(req) => json!({ "Update": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": object_to_json(&req.obj),
+        }}),
+        KubeAPIRequest::UpdateStatusRequest(req) => json!({ "UpdateStatus": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": object_to_json(&req.obj),
+        }}),
+        KubeAPIRequest::ApplyRequest(req) => json!({ "Apply": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "obj": object_to_json(&req.obj),
+            "field_manager": req.field_manager,
+            "force": req.force,
+        }}),
+        KubeAPIRequest::PatchRequest(req) => json!({ "Patch": {
+            "api_resource": api_resource_to_json(&req.api_resource),
+            "namespace": req.namespace,
+            "name": req.name,
+            "patch": object_to_json(&req.patch),
+            "patch_type": match req.patch_type {
+                PatchType::MergePatch => "MergePatch",
+                PatchType::StrategicMergePatch => "StrategicMergePatch",
+            },
+        }}),
+    }
+}
+
+pub fn response_to_json<E: TracedExternalAPI>(response: &Response<E::Output>) -> Value {
+    match response {
+        Response::KResponse(resp) => json!({ "KResponse": kube_response_to_json(resp) }),
+        Response::ExternalResponse(resp) => json!({ "ExternalResponse": E::output_to_json(resp) }),
+    }
+}
+
+pub fn response_from_json<E: TracedExternalAPI>(response: &Value) -> Option<Response<E::Output>> {
+    if let Some(resp) = response.get("KResponse") {
+        kube_response_from_json(resp).map(|resp| Response::KResponse(resp))
+    } else {
+        E::output_from_json(response.get("ExternalResponse")?)
+            .map(|resp| Response::ExternalResponse(resp))
+    }
+}
+
+pub fn kube_response_to_json(response: &KubeAPIResponse) -> Value {
+    match response {
+        KubeAPIResponse::GetResponse(resp) => json!({ "Get": object_result_to_json(&resp.res) }),
+        KubeAPIResponse::ListResponse(resp) => json!({ "List": result_to_json(&resp.res, |objs| {
+            Value::Array(objs.iter().map(object_to_json).collect())
+        })}),
+        KubeAPIResponse::CreateResponse(resp) => {
+            json!({ "Create": object_result_to_json(&resp.res) })
+        }
+        KubeAPIResponse::DeleteResponse(resp) => {
+            json!({ "Delete": result_to_json(&resp.res, |_| Value::Null) })
+        }
+        KubeAPIResponse::UpdateResponse(resp) => {
+            json!({ "Update": object_result_to_json(&resp.res) })
+        }
+        KubeAPIResponse::UpdateStatusResponse(resp) => {
+            json!({ "UpdateStatus": object_result_to_json(&resp.res) })
+        }
+        KubeAPIResponse::ApplyResponse(resp) => {
+            json!({ "Apply": object_result_to_json(&resp.res) })
+        }
+        KubeAPIResponse::PatchResponse(resp) => {
+            json!({ "Patch": object_result_to_json(&resp.res) })
+        }
+    }
+}
+
+pub fn kube_response_from_json(response: &Value) -> Option<KubeAPIResponse> {
+    let (name, res) = response.as_object()?.iter().next()?;
+    match name.as_str() {
+        "Get" => Some(KubeAPIResponse::GetResponse(KubeGetResponse {
+            res: object_result_from_json(res)?,
+        })),
+        "List" => Some(KubeAPIResponse::ListResponse(KubeListResponse {
+            res: result_from_json(res, |objs| {
+                objs.as_array()?
+                    .iter()
+                    .map(object_from_json)
+                    .collect::<Option<Vec<DynamicObject>>>()
+            })?,
+        })),
+        "Create" => Some(KubeAPIResponse::CreateResponse(KubeCreateResponse {
+            res: object_result_from_json(res)?,
+        })),
+        "Delete" => Some(KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+            res: result_from_json(res, |_| Some(()))?,
+        })),
+        "Update" => Some(KubeAPIResponse::UpdateResponse(KubeUpdateResponse {
+            res: object_result_from_json(res)?,
+        })),
+        "UpdateStatus" => Some(KubeAPIResponse::UpdateStatusResponse(
+            KubeUpdateStatusResponse {
+                res: object_result_from_json(res)?,
+            },
+        )),
+        "Apply" => Some(KubeAPIResponse::ApplyResponse(KubeApplyResponse {
+            res: object_result_from_json(res)?,
+        })),
+        "Patch" => Some(KubeAPIResponse::PatchResponse(KubePatchResponse {
+            res: object_result_from_json(res)?,
+        })),
+        _ => None,
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::external_api::exec::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::controller_runtime::kube_error_to_ghost;
+use crate::shim_layer::reconcile_trace::*;
+use deps_hack::kube::api::Resource;
+use deps_hack::serde::de::DeserializeOwned;
+use deps_hack::serde_json::{json, Value};
+use deps_hack::tracing::error;
+
+// Each exec reconcile_core is proved to refine the spec-level reconcile_core on the views of its inputs and outputs.
+// The proof does not cover the trusted code around it: the View impls of the exec types,
+// the external_body functions (e.g., marshal and unmarshal) and the conversion between the exec types and kube-rs types.
+// The refinement monitor checks at runtime that the verified reconciler, together with all the trusted code,
+// behaves as the spec says on the requests actually sent to and the responses actually received from Kubernetes.
+//
+// Since spec functions cannot run, each reconciler provides an executable model (see ExecutableModelReconciler),
+// which transcribes its spec reconcile_core to plain Rust over kube-rs types.
+// The executable model takes the cr object, the response and its own reconcile state in json,
+// and returns its new reconcile state and the request in json,
+// where the requests and the responses are in the form recorded in reconcile traces.
+// The inputs of the model never go through the exec types: the cr object is the one served by Kubernetes,
+// and each response is built right from what kube-rs returns (see raw_kube_response_json),
+// so a bug in the conversion to the exec types or in their View impls makes the model and reconcile_core diverge.
+// The monitor runs the model alongside each reconcile (see ModelReconcile)
+// and reports a violation if the request sent by the shim layer or how the reconcile ends differs from the model.
+//
+// Note that the executable model is trusted as well, so a violation means either the trusted code
+// around reconcile_core or the executable model diverges from the spec, and both are worth a look.
+
+// ExecutableModelReconciler is implemented by every reconciler run by the shim layer.
+// A reconciler without an executable model keeps the default methods, and the monitor skips it.
+pub trait ExecutableModelReconciler: Reconciler {
+    // model_init_state returns the spec reconcile_init_state in the json form used by model_reconcile_core
+    fn model_init_state() -> Option<Value> {
+        None
+    }
+
+    // model_reconcile_core returns the new reconcile state and the request (if any) that the spec reconcile_core returns,
+    // or None if the inputs cannot be interpreted, e.g., the cr object is not in the expected form.
+    fn model_reconcile_core(
+        _cr: &Value,
+        _resp_o: Option<&Value>,
+        _state: &Value,
+    ) -> Option<(Value, Option<Value>)> {
+        None
+    }
+
+    // model_outcome returns how the reconcile ends in the state by the spec reconcile_done and reconcile_error,
+    // or None if it goes on
+    fn model_outcome(_state: &Value) -> Option<ReconcileTraceOutcome> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefinementMonitorMode {
+    // Log the violation and go on with the reconcile
+    Log,
+    // Log the violation and abort the controller
+    Abort,
+}
+
+impl RefinementMonitorMode {
+    // from_str parses the value of the REFINEMENT_MONITOR environment variable
+    pub fn from_str(mode: &str) -> Option<RefinementMonitorMode> {
+        match mode {
+            "log" => Some(RefinementMonitorMode::Log),
+            "abort" => Some(RefinementMonitorMode::Abort),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefinementViolation {
+    pub response: Option<Value>,
+    pub expected_request: Option<Value>,
+    pub expected_outcome: Option<ReconcileTraceOutcome>,
+    pub actual_request: Option<Value>,
+    pub actual_outcome: Option<ReconcileTraceOutcome>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefinementCheck {
+    // The step matches the executable model
+    Refines,
+    // The reconciler has no executable model, the model cannot interpret the inputs of the step,
+    // or it has stopped checking the reconcile after an earlier violation, since its state has diverged
+    Unchecked,
+    Violated(RefinementViolation),
+}
+
+// ModelReconcile runs the executable model of one reconcile alongside the shim layer.
+// It threads its own reconcile state, so it does not rely on the state carried by the shim layer.
+pub struct ModelReconcile<ReconcilerType: ExecutableModelReconciler> {
+    cr: Value,
+    state: Option<Value>,
+    _reconciler: std::marker::PhantomData<ReconcilerType>,
+}
+
+impl<ReconcilerType: ExecutableModelReconciler> ModelReconcile<ReconcilerType> {
+    // new returns None if the reconciler has no executable model.
+    pub fn new(cr: &Value) -> Option<Self> {
+        Some(ModelReconcile {
+            cr: cr.clone(),
+            state: Some(ReconcilerType::model_init_state()?),
+            _reconciler: std::marker::PhantomData,
+        })
+    }
+
+    // check_step runs the model on the response fed to the step (in json),
+    // and compares its outputs with the request returned by the step (in json)
+    // and how the reconcile ends after the step (None if it goes on).
+    pub fn check_step(
+        &mut self,
+        resp_o: Option<&Value>,
+        actual_request: Option<&Value>,
+        actual_outcome: Option<ReconcileTraceOutcome>,
+    ) -> RefinementCheck {
+        let state = match self.state.take() {
+            Some(state) => state,
+            None => return RefinementCheck::Unchecked,
+        };
+        // The shim layer only runs a step if the reconcile has not ended
+        if ReconcilerType::model_outcome(&state).is_some() {
+            return RefinementCheck::Unchecked;
+        }
+        let (state_prime, expected_request) =
+            match ReconcilerType::model_reconcile_core(&self.cr, resp_o, &state) {
+                Some(outputs) => outputs,
+                None => return RefinementCheck::Unchecked,
+            };
+        let expected_outcome = ReconcilerType::model_outcome(&state_prime);
+        // Fields set to null and absent fields are the same to Kubernetes
+        if expected_request.as_ref().map(strip_nulls) == actual_request.map(strip_nulls)
+            && expected_outcome == actual_outcome
+        {
+            self.state = Some(state_prime);
+            RefinementCheck::Refines
+        } else {
+            RefinementCheck::Violated(RefinementViolation {
+                response: resp_o.cloned(),
+                expected_request: expected_request,
+                expected_outcome: expected_outcome,
+                actual_request: actual_request.cloned(),
+                actual_outcome: actual_outcome,
+            })
+        }
+    }
+}
+
+// outcome returns how the reconcile ends in the state, or None if it goes on
+pub fn outcome<ReconcilerType: Reconciler>(
+    state: &ReconcilerType::T,
+) -> Option<ReconcileTraceOutcome> {
+    if ReconcilerType::reconcile_done(state) || ReconcilerType::reconcile_error(state) {
+        Some(outcome_of::<ReconcilerType>(state))
+    } else {
+        None
+    }
+}
+
+// raw_kube_response_json builds the json form (as in reconcile traces) of the response to a kube request,
+// e.g., "Get", from what kube-rs returns, where an object (or a list of objects) is already in json.
+pub fn raw_kube_response_json(method: &str, res: Result<Value, &deps_hack::kube::Error>) -> Value {
+    let res = match res {
+        Ok(value) => json!({ "Ok": value }),
+        Err(err) => json!({ "Err": format!("{:?}", kube_error_to_ghost(err)) }),
+    };
+    json!({ "KResponse": { method: res } })
+}
+
+// check_reconcile_trace runs the monitor over a recorded reconcile trace,
+// so that the traces recorded in production can also be checked offline.
+// Note that a trace records the responses as converted by the shim layer (see ReconcileTrace),
+// so unlike the runtime monitor, this check does not cover the conversion of the responses.
+// It returns the index and the violation of the first step that violates the refinement, if any.
+pub fn check_reconcile_trace<K, ReconcilerType>(
+    trace: &ReconcileTrace,
+) -> Result<(), (usize, RefinementViolation)>
+where
+    K: Resource + DeserializeOwned,
+    ReconcilerType: ExecutableModelReconciler,
+    ReconcilerType::R: ResourceWrapper<K>,
+    ReconcilerType::ExternalAPIType: TracedExternalAPI,
+{
+    let mut model = match ModelReconcile::<ReconcilerType>::new(&trace.cr) {
+        Some(model) => model,
+        None => return Ok(()),
+    };
+    let mut resp_json: Option<Value> = None;
+    for (i, step) in trace.steps.iter().enumerate() {
+        // The trace only records how the reconcile ends after the last step
+        let actual_outcome = if i + 1 == trace.steps.len()
+            && trace.outcome != ReconcileTraceOutcome::Interrupted
+        {
+            Some(trace.outcome.clone())
+        } else {
+            None
+        };
+        if let RefinementCheck::Violated(violation) =
+            model.check_step(resp_json.as_ref(), step.request.as_ref(), actual_outcome)
+        {
+            return Err((i, violation));
+        }
+        resp_json = step.response.clone();
+    }
+    Ok(())
+}
+
+// RefinementMonitor is the runtime monitor used by reconcile_with.
+pub struct RefinementMonitor {
+    pub mode: RefinementMonitorMode,
+}
+
+impl RefinementMonitor {
+    pub fn report(&self, log_header: &String, check: RefinementCheck) {
+        match check {
+            RefinementCheck::Violated(violation) => {
+                error!(
+                    "{} reconcile_core does not refine the spec: {}",
+                    log_header,
+                    deps_hack::serde_json::json!({
+                        "response": violation.response,
+                        "expected_request": violation.expected_request,
+                        "expected_outcome": violation.expected_outcome,
+                        "actual_request": violation.actual_request,
+                        "actual_outcome": violation.actual_outcome,
+                    })
+                );
+                if self.mode == RefinementMonitorMode::Abort {
+                    std::process::abort();
+                }
+            }
+            // Reconcilers without an executable model are not checked at all, so do not flood the log for them
+            RefinementCheck::Unchecked | RefinementCheck::Refines => {}
+        }
+    }
+}
+
+fn strip_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), strip_nulls(v)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(strip_nulls).collect()),
+        _ => value.clone(),
+    }
+}
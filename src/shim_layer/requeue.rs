@@ -0,0 +1,131 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::reconciler::exec::reconciler::*;
+use deps_hack::kube::runtime::controller::Action;
+use deps_hack::rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// The liveness proofs assume that the shim layer keeps reconciling each custom resource as long as it exists
+// (see Cluster::schedule_controller_reconcile). The requeue policy decides how soon the next reconcile happens,
+// but never whether it happens: every action returned here is a requeue after a finite delay bounded by
+// the resync period (or the maximum backoff), so a reconcile is always eventually scheduled again
+// even if no watch event arrives.
+
+// RequeueHint is what the reconciler asks the shim layer to do after a reconcile finishes successfully.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequeueHint {
+    // Requeue after the default requeue_after of the policy
+    Done,
+    // Requeue after the given duration (at most the resync period)
+    RequeueAfter(Duration),
+    // Nothing to do until the custom resource or an object it owns changes.
+    // The reconcile is still requeued after the resync period in case a watch event is missed.
+    AwaitChange,
+}
+
+// RequeueHintReconciler is implemented by every reconciler run by the shim layer.
+// A reconciler that does not override requeue_hint is requeued after the default requeue_after,
+// as the shim layer always did.
+pub trait RequeueHintReconciler: Reconciler {
+    // requeue_hint returns the hint from the final state of a reconcile that is done
+    fn requeue_hint(_state: &Self::T) -> RequeueHint {
+        RequeueHint::Done
+    }
+}
+
+// RequeuePolicy configures when reconcile_with is requeued.
+#[derive(Clone, Debug)]
+pub struct RequeuePolicy {
+    // The delay for RequeueHint::Done
+    pub requeue_after: Duration,
+    // The longest delay after a successful reconcile, which is also the delay for RequeueHint::AwaitChange
+    pub resync_period: Duration,
+    // The delay after the first failure, doubled on each consecutive failure of the same custom resource
+    pub backoff_base: Duration,
+    // The longest delay after a failure (before jitter)
+    pub backoff_max: Duration,
+    // Each backoff delay is scaled by a random factor in [1 - backoff_jitter, 1 + backoff_jitter]
+    // so that the reconciles failing at the same time (e.g., when the API server is down) do not retry in lockstep
+    pub backoff_jitter: f64,
+}
+
+impl Default for RequeuePolicy {
+    fn default() -> RequeuePolicy {
+        RequeuePolicy {
+            requeue_after: Duration::from_secs(60),
+            resync_period: Duration::from_secs(600),
+            backoff_base: Duration::from_secs(10),
+            backoff_max: Duration::from_secs(300),
+            backoff_jitter: 0.1,
+        }
+    }
+}
+
+impl RequeuePolicy {
+    // delay_after_done returns the delay to requeue a successful reconcile with the hint
+    pub fn delay_after_done(&self, hint: RequeueHint) -> Duration {
+        match hint {
+            RequeueHint::Done => self.requeue_after.min(self.resync_period),
+            RequeueHint::RequeueAfter(delay) => delay.min(self.resync_period),
+            RequeueHint::AwaitChange => self.resync_period,
+        }
+    }
+
+    // delay_after_failures returns the delay to retry after the given number of consecutive failures (at least one),
+    // where jitter is a sample from [-1, 1] scaled by backoff_jitter.
+    pub fn delay_after_failures(&self, failures: u32, jitter: f64) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        let delay = self
+            .backoff_base
+            .saturating_mul(1u32 << exponent)
+            .min(self.backoff_max);
+        let factor = 1.0 + self.backoff_jitter * jitter.clamp(-1.0, 1.0);
+        delay.mul_f64(factor.max(0.0))
+    }
+}
+
+// Requeuer applies the requeue policy and tracks the consecutive failures of each custom resource,
+// keyed by the cr_key used by reconcile_with.
+pub struct Requeuer {
+    pub policy: RequeuePolicy,
+    failures: Mutex<HashMap<String, u32>>,
+}
+
+impl Requeuer {
+    pub fn new(policy: RequeuePolicy) -> Requeuer {
+        Requeuer {
+            policy: policy,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // on_done resets the backoff of the custom resource and requeues it as the hint asks
+    pub fn on_done(&self, cr_key: &String, hint: RequeueHint) -> Action {
+        self.forget(cr_key);
+        Action::requeue(self.policy.delay_after_done(hint))
+    }
+
+    // on_failure records one more failure of the custom resource and requeues it with exponential backoff
+    pub fn on_failure(&self, cr_key: &String) -> Action {
+        let failures = {
+            let mut failures = self.failures.lock().unwrap();
+            let count = failures.entry(cr_key.clone()).or_insert(0);
+            *count = count.saturating_add(1);
+            *count
+        };
+        let jitter = deps_hack::rand::thread_rng().gen_range(-1.0..=1.0);
+        Action::requeue(self.policy.delay_after_failures(failures, jitter))
+    }
+
+    // forget drops the backoff of the custom resource, e.g., when it is deleted
+    pub fn forget(&self, cr_key: &String) {
+        self.failures.lock().unwrap().remove(cr_key);
+    }
+
+    pub fn failures(&self, cr_key: &String) -> u32 {
+        *self.failures.lock().unwrap().get(cr_key).unwrap_or(&0)
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use crate::executable_model::prelude::*;
+use crate::external_api::exec::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{
+    api_method::*, api_resource::*, dynamic::*, resource::*,
+};
+use crate::reconciler::exec::{io::*, reconciler::*};
+use crate::shim_layer::reconcile_trace::*;
+use core::fmt::Debug;
+use deps_hack::kube::api::Resource;
+use deps_hack::serde::{de::DeserializeOwned, Serialize};
+use deps_hack::tracing::{info, warn};
+use deps_hack::Error;
+
+type ExternalInput<ReconcilerType> =
+    <<ReconcilerType as Reconciler>::ExternalAPIType as ExternalAPIShimLayer>::Input;
+type ExternalOutput<ReconcilerType> =
+    <<ReconcilerType as Reconciler>::ExternalAPIType as ExternalAPIShimLayer>::Output;
+
+// SimulatedCluster runs a reconciler in the same process against the executable API server model
+// (see crate::executable_model::api_server) instead of a Kubernetes cluster.
+// reconcile follows reconcile_with in controller_runtime: it gets the cr object,
+// invokes reconcile_core in a loop until the reconcile is done or fails,
+// and answers each request from reconcile_core by the corresponding handler of ExecutableApiServerModel.
+// Everything happens in one thread without any timing, so the tests built on it are deterministic.
+//
+// Note that SimulatedCluster only simulates the API server:
+// (1) There are no built-in controllers, e.g., no pod is created for a stateful set
+// and no object is garbage collected after its owner is deleted.
+// (2) There is no watch, so the test decides when to reconcile which cr object.
+// (3) External requests are answered by the stand-in set by set_external_api, which defaults to
+// ReconcilerType::ExternalAPIType::call_external_api.
+pub struct SimulatedCluster<ReconcilerType>
+where
+    ReconcilerType: Reconciler,
+{
+    pub api_server_state: ApiServerState,
+    external_api: Box<dyn FnMut(ExternalInput<ReconcilerType>) -> ExternalOutput<ReconcilerType>>,
+    // The maximum number of reconcile_core invocations in one reconcile before the reconcile is considered stuck
+    pub max_steps: usize,
+    // The trace of each reconcile that invokes reconcile_core, in the same form as recorded by reconcile_with
+    pub traces: Vec<ReconcileTrace>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SimulatedReconcileOutcome {
+    // The reconcile is done after reconcile_core is invoked steps times
+    Done { steps: usize },
+    // The cr object does not exist so reconcile_core is never invoked
+    CrNotFound,
+}
+
+impl<ReconcilerType> SimulatedCluster<ReconcilerType>
+where
+    ReconcilerType: Reconciler,
+{
+    pub fn new() -> SimulatedCluster<ReconcilerType> {
+        SimulatedCluster {
+            api_server_state: ApiServerState::new(),
+            external_api: Box::new(|input| {
+                ReconcilerType::ExternalAPIType::call_external_api(input)
+            }),
+            max_steps: 1000,
+            traces: Vec::new(),
+        }
+    }
+
+    // set_external_api replaces the external system with a stand-in, e.g., an in-memory ZooKeeper.
+    pub fn set_external_api<F>(&mut self, external_api: F)
+    where
+        F: FnMut(ExternalInput<ReconcilerType>) -> ExternalOutput<ReconcilerType> + 'static,
+    {
+        self.external_api = Box::new(external_api);
+    }
+
+    // create stores obj as if it is created by a client, e.g., kubectl creating the cr object.
+    pub fn create<K>(&mut self, obj: &K) -> Result<DynamicObject, APIError>
+    where
+        K: Resource + Serialize,
+        K::DynamicType: Default,
+    {
+        let api_resource = ApiResource::from_kube(deps_hack::kube::api::ApiResource::erase::<K>(
+            &K::DynamicType::default(),
+        ));
+        let namespace = obj
+            .meta()
+            .namespace
+            .clone()
+            .unwrap_or("default".to_string());
+        let obj = DynamicObject::from_kube(
+            deps_hack::serde_json::from_value(deps_hack::serde_json::to_value(obj).unwrap())
+                .unwrap(),
+        );
+        match self.handle_request(KubeAPIRequest::CreateRequest(KubeCreateRequest {
+            api_resource: api_resource,
+            namespace: namespace,
+            obj: obj,
+        })) {
+            KubeAPIResponse::CreateResponse(resp) => resp.res,
+            _ => panic!(),
+        }
+    }
+
+    pub fn get(
+        &self,
+        api_resource: ApiResource,
+        namespace: &str,
+        name: &str,
+    ) -> Result<DynamicObject, APIError> {
+        let req = KubeGetRequest {
+            api_resource: api_resource,
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        };
+        SimpleExecutableApiServerModel::handle_get_request(&req, &self.api_server_state).res
+    }
+
+    pub fn list(&self, api_resource: ApiResource, namespace: &str) -> Vec<DynamicObject> {
+        let req = KubeListRequest {
+            api_resource: api_resource,
+            namespace: namespace.to_string(),
+            label_selector: None,
+            field_selector: None,
+        };
+        SimpleExecutableApiServerModel::handle_list_request(&req, &self.api_server_state)
+            .res
+            .unwrap()
+    }
+
+    // handle_request answers one request from reconcile_core in the same way as the API server.
+    pub fn handle_request(&mut self, req: KubeAPIRequest) -> KubeAPIResponse {
+        match req {
+            KubeAPIRequest::GetRequest(get_req) => {
+                KubeAPIResponse::GetResponse(SimpleExecutableApiServerModel::handle_get_request(
+                    &get_req,
+                    &self.api_server_state,
+                ))
+            }
+            KubeAPIRequest::ListRequest(list_req) => {
+                KubeAPIResponse::ListResponse(SimpleExecutableApiServerModel::handle_list_request(
+                    &list_req,
+                    &self.api_server_state,
+                ))
+            }
+            KubeAPIRequest::CreateRequest(create_req) => KubeAPIResponse::CreateResponse(
+                SimpleExecutableApiServerModel::handle_create_request(
+                    &create_req,
+                    &mut self.api_server_state,
+                ),
+            ),
+            KubeAPIRequest::DeleteRequest(delete_req) => KubeAPIResponse::DeleteResponse(
+                SimpleExecutableApiServerModel::handle_delete_request(
+                    &delete_req,
+                    &mut self.api_server_state,
+                ),
+            ),
+            KubeAPIRequest::UpdateRequest(update_req) => KubeAPIResponse::UpdateResponse(
+                SimpleExecutableApiServerModel::handle_update_request(
+                    &update_req,
+                    &mut self.api_server_state,
+                ),
+            ),
+            KubeAPIRequest::UpdateStatusRequest(update_status_req) => {
+                KubeAPIResponse::UpdateStatusResponse(
+                    SimpleExecutableApiServerModel::handle_update_status_request(
+                        &update_status_req,
+                        &mut self.api_server_state,
+                    ),
+                )
+            }
+            KubeAPIRequest::ApplyRequest(apply_req) => KubeAPIResponse::ApplyResponse(
+                SimpleExecutableApiServerModel::handle_apply_request(
+                    &apply_req,
+                    &mut self.api_server_state,
+                ),
+            ),
+            KubeAPIRequest::PatchRequest(patch_req) => KubeAPIResponse::PatchResponse(
+                SimpleExecutableApiServerModel::handle_patch_request(
+                    &patch_req,
+                    &mut self.api_server_state,
+                ),
+            ),
+        }
+    }
+
+    // reconcile runs one reconcile for the cr object identified by namespace and name.
+    pub fn reconcile<K>(
+        &mut self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<SimulatedReconcileOutcome, Error>
+    where
+        K: Resource + DeserializeOwned + Serialize,
+        K::DynamicType: Default,
+        ReconcilerType::R: ResourceWrapper<K>,
+        ReconcilerType::ExternalAPIType: TracedExternalAPI,
+    {
+        let cr_api_resource = ApiResource::from_kube(
+            deps_hack::kube::api::ApiResource::erase::<K>(&K::DynamicType::default()),
+        );
+        let log_header = format!(
+            "Reconciling {}/{}/{}:",
+            cr_api_resource.as_kube_ref().kind,
+            namespace,
+            name
+        );
+        let cr = match self.get(cr_api_resource, namespace, name) {
+            Ok(obj) => obj.into_kube().try_parse::<K>().map_err(|err| {
+                Error::ShimLayerError(format!("Fail to parse custom resource: {}", err))
+            })?,
+            Err(APIError::ObjectNotFound) => {
+                warn!(
+                    "{} Custom resource {} not found, end reconcile",
+                    log_header, name
+                );
+                return Ok(SimulatedReconcileOutcome::CrNotFound);
+            }
+            Err(err) => {
+                return Err(Error::ShimLayerError(format!(
+                    "Fail to get custom resource: {:?}",
+                    err
+                )));
+            }
+        };
+
+        let mut trace = ReconcileTrace::new(
+            format!(
+                "{}/{}/{}",
+                cr_api_resource.as_kube_ref().kind,
+                namespace,
+                name
+            ),
+            &cr,
+        );
+        let cr_wrapper = ReconcilerType::R::from_kube(cr);
+        let mut state = ReconcilerType::reconcile_init_state();
+        let mut resp_option: Option<Response<ExternalOutput<ReconcilerType>>> = None;
+        let mut steps = 0;
+        loop {
+            if ReconcilerType::reconcile_done(&state) {
+                info!("{} done", log_header);
+                trace.outcome = ReconcileTraceOutcome::Done;
+                self.traces.push(trace);
+                return Ok(SimulatedReconcileOutcome::Done { steps: steps });
+            }
+            if ReconcilerType::reconcile_error(&state) {
+                warn!("{} error", log_header);
+                trace.outcome = ReconcileTraceOutcome::Error;
+                self.traces.push(trace);
+                return Err(Error::ReconcileCoreError);
+            }
+            if steps >= self.max_steps {
+                self.traces.push(trace);
+                return Err(Error::ShimLayerError(format!(
+                    "Reconcile is not done after {} steps",
+                    steps
+                )));
+            }
+            let (state_prime, request_option) =
+                ReconcilerType::reconcile_core(&cr_wrapper, resp_option, state);
+            steps = steps + 1;
+            let request_json = request_option
+                .as_ref()
+                .map(|req| request_to_json::<ReconcilerType::ExternalAPIType>(req));
+            resp_option = match request_option {
+                Some(Request::KRequest(req)) => Some(Response::KResponse(self.handle_request(req))),
+                Some(Request::ExternalRequest(req)) => {
+                    Some(Response::ExternalResponse((self.external_api)(req)))
+                }
+                None => None,
+            };
+            trace.push_step::<ReconcilerType::ExternalAPIType>(request_json, &resp_option, None);
+            state = state_prime;
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+#![allow(unused_imports)]
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// The controller runs reconciles of different custom resources concurrently on a pool of workers
+// (the size of the pool is ControllerConfig::max_concurrent_reconciles), which matches the controller model:
+// ControllerState::ongoing_reconciles can hold one ongoing reconcile for each custom resource,
+// and the steps of the ongoing reconciles interleave with each other.
+//
+// The model never starts a reconcile of a custom resource while another reconcile of it is ongoing
+// (see Cluster::run_scheduled_reconcile and Cluster::lemma_always_ongoing_reconcile_of_key_is_not_preempted).
+// kube-rs already runs at most one reconcile of each object at a time, but the proofs should not depend on
+// the internals of its scheduler, so reconcile_with enforces the same per-key exclusion with InFlightReconciles.
+
+// The delay to requeue a reconcile that finds another reconcile of the same custom resource in flight
+pub const DEFER_PERIOD: Duration = Duration::from_secs(1);
+
+// InFlightReconciles tracks the keys of the custom resources being reconciled.
+pub struct InFlightReconciles {
+    keys: Mutex<HashSet<String>>,
+}
+
+impl InFlightReconciles {
+    pub fn new() -> InFlightReconciles {
+        InFlightReconciles {
+            keys: Mutex::new(HashSet::new()),
+        }
+    }
+
+    // try_claim claims the key for a reconcile, or returns None if another reconcile holds the key.
+    // The key is released when the returned guard is dropped, i.e., when the reconcile ends in any way.
+    pub fn try_claim(&self, cr_key: &String) -> Option<InFlightGuard> {
+        if self.keys.lock().unwrap().insert(cr_key.clone()) {
+            Some(InFlightGuard {
+                in_flight: self,
+                cr_key: cr_key.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, cr_key: &String) -> bool {
+        self.keys.lock().unwrap().contains(cr_key)
+    }
+}
+
+pub struct InFlightGuard<'a> {
+    in_flight: &'a InFlightReconciles,
+    cr_key: String,
+}
+
+impl<'a> Drop for InFlightGuard<'a> {
+    fn drop(&mut self) {
+        self.in_flight.keys.lock().unwrap().remove(&self.cr_key);
+    }
+}
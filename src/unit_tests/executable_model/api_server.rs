@@ -0,0 +1,60 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::executable_model::prelude::*;
+use crate::kubernetes_api_objects::{error::*, exec::prelude::*};
+use deps_hack::serde_json::json;
+
+fn custom_resource(kind: &str, name: &str) -> (ApiResource, DynamicObject) {
+    let api_resource = ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+        group: "anvil.dev".to_string(),
+        version: "v1".to_string(),
+        api_version: "anvil.dev/v1".to_string(),
+        kind: kind.to_string(),
+        plural: format!("{}s", kind.to_lowercase()),
+    });
+    let obj = DynamicObject::from_kube(
+        deps_hack::serde_json::from_value(json!({
+            "apiVersion": "anvil.dev/v1",
+            "kind": kind,
+            "metadata": {
+                "name": name,
+            },
+            "spec": {},
+        }))
+        .unwrap(),
+    );
+    (api_resource, obj)
+}
+
+#[test]
+pub fn test_create_custom_resource_of_registered_kind() {
+    let mut api_server_state = ApiServerState::new();
+    let (api_resource, obj) = custom_resource("SimpleCR", "foo");
+    let create_request = KubeCreateRequest {
+        api_resource: api_resource,
+        namespace: "default".to_string(),
+        obj: obj,
+    };
+    let res = SimpleExecutableApiServerModel::handle_create_request(&create_request, &mut api_server_state).res;
+    assert!(res.is_ok());
+}
+
+#[test]
+pub fn test_create_custom_resource_of_unregistered_kind() {
+    let mut api_server_state = ApiServerState::new();
+    let (api_resource, obj) = custom_resource("OtherCR", "foo");
+    let create_request = KubeCreateRequest {
+        api_resource: api_resource.clone(),
+        namespace: "default".to_string(),
+        obj: obj,
+    };
+    let res = SimpleExecutableApiServerModel::handle_create_request(&create_request, &mut api_server_state).res;
+    assert!(matches!(res, Err(APIError::ObjectNotFound)));
+    let get_request = KubeGetRequest {
+        api_resource: api_resource,
+        namespace: "default".to_string(),
+        name: "foo".to_string(),
+    };
+    let res = SimpleExecutableApiServerModel::handle_get_request(&get_request, &api_server_state).res;
+    assert!(matches!(res, Err(APIError::ObjectNotFound)));
+}
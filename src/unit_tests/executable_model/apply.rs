@@ -0,0 +1,228 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::executable_model::apply::*;
+use deps_hack::kube::api::DynamicObject;
+use deps_hack::serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+fn config_map(labels: Value, data: Value) -> DynamicObject {
+    deps_hack::serde_json::from_value(json!({
+        "apiVersion": "v1",
+        "kind": "ConfigMap",
+        "metadata": {
+            "name": "foo",
+            "labels": labels,
+        },
+        "data": data,
+    }))
+    .unwrap()
+}
+
+fn fields_of_manager(obj: &DynamicObject, manager: &str) -> BTreeSet<FieldPath> {
+    obj.metadata
+        .managed_fields
+        .clone()
+        .unwrap_or_default()
+        .iter()
+        .filter(|entry| entry.manager.as_deref() == Some(manager))
+        .flat_map(entry_fields)
+        .collect()
+}
+
+fn spec_field(path: &[&str]) -> FieldPath {
+    FieldPath::Spec(path.iter().map(|key| key.to_string()).collect())
+}
+
+#[test]
+pub fn test_value_fields() {
+    let data = json!({
+        "spec": {"replicas": 1, "template": {"spec": {"containers": [{"name": "c"}]}}, "empty": {}},
+        "status": {"readyReplicas": 1},
+    });
+    let fields = value_fields(&data);
+    assert_eq!(fields.len(), 3);
+    assert!(fields.contains(&vec!["spec".to_string(), "replicas".to_string()]));
+    // Lists and empty objects are leaves
+    assert!(fields.contains(&vec!["spec".to_string(), "template".to_string(), "spec".to_string(), "containers".to_string()]));
+    assert!(fields.contains(&vec!["spec".to_string(), "empty".to_string()]));
+}
+
+#[test]
+pub fn test_value_at() {
+    let data = json!({"spec": {"replicas": 1}});
+    assert_eq!(value_at(&data, &vec!["spec".to_string(), "replicas".to_string()]), json!(1));
+    assert_eq!(value_at(&data, &vec!["spec".to_string(), "paused".to_string()]), Value::Null);
+}
+
+#[test]
+pub fn test_applied_value() {
+    let old_value = json!({"data": {"a": "x", "b": "y"}, "status": {"s": 1}});
+    let removed = vec![vec!["data".to_string(), "b".to_string()]].into_iter().collect();
+    assert_eq!(
+        applied_value(old_value, &json!({"data": {"c": "z"}}), &removed),
+        json!({"data": {"a": "x", "c": "z"}, "status": {"s": 1}})
+    );
+    // The objects that become empty are removed together with the removed field
+    let old_value = json!({"data": {"a": "x"}});
+    let removed = vec![vec!["data".to_string(), "a".to_string()]].into_iter().collect();
+    assert_eq!(applied_value(old_value, &json!({}), &removed), json!({}));
+}
+
+#[test]
+pub fn test_fields_v1_round_trip() {
+    let fields: BTreeSet<FieldPath> = vec![
+        FieldPath::Label("app".to_string()),
+        FieldPath::Annotation("note".to_string()),
+        FieldPath::Finalizer("example.com/foo".to_string()),
+        FieldPath::OwnerReference("1".to_string()),
+        spec_field(&["data", "key"]),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(fields_from_fields_v1(&fields_v1_from_fields(&fields)), fields);
+}
+
+#[test]
+pub fn test_fields_from_kubernetes_fields_v1() {
+    // The fieldsV1 written by Kubernetes marks the owned maps with "." and the items of associative lists with "k:"
+    // (map items) and "v:" (set items)
+    let fields_v1 = json!({
+        "f:metadata": {
+            "f:labels": {".": {}, "f:app": {}},
+            "f:finalizers": {".": {}, "v:\"example.com/foo\"": {}},
+            "f:ownerReferences": {".": {}, "k:{\"uid\":\"1\"}": {".": {}, "f:kind": {}, "f:uid": {}}},
+        },
+        "f:data": {".": {}, "f:key": {}},
+    });
+    let fields: BTreeSet<FieldPath> = vec![
+        FieldPath::Label("app".to_string()),
+        FieldPath::Finalizer("example.com/foo".to_string()),
+        FieldPath::OwnerReference("1".to_string()),
+        spec_field(&["data", "key"]),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(fields_from_fields_v1(&fields_v1), fields);
+}
+
+#[test]
+pub fn test_apply_creates_owner() {
+    let obj = config_map(json!({"app": "foo"}), json!({"key": "value"}));
+    let created = created_kube_object_from_apply("manager-a", &obj);
+    assert_eq!(
+        fields_of_manager(&created, "manager-a"),
+        applied_fields(&obj)
+    );
+    assert_eq!(created.data, obj.data);
+}
+
+#[test]
+pub fn test_apply_shares_fields_with_same_value() {
+    let old_obj = created_kube_object_from_apply("manager-a", &config_map(json!({"app": "foo"}), json!({"a": "x"})));
+    let obj = config_map(json!({"app": "foo"}), json!({"b": "y"}));
+    assert!(!apply_conflicts("manager-b", false, &obj, &old_obj));
+    let applied = applied_kube_object("manager-b", &obj, &old_obj);
+    assert!(fields_of_manager(&applied, "manager-a").contains(&FieldPath::Label("app".to_string())));
+    assert!(fields_of_manager(&applied, "manager-b").contains(&FieldPath::Label("app".to_string())));
+    assert_eq!(applied.data, json!({"a": "x", "b": "y"}));
+}
+
+#[test]
+pub fn test_apply_conflicts_with_other_manager() {
+    let old_obj = created_kube_object_from_apply("manager-a", &config_map(Value::Null, json!({"a": "x"})));
+    let obj = config_map(Value::Null, json!({"a": "y"}));
+    assert!(apply_conflicts("manager-b", false, &obj, &old_obj));
+    assert!(!apply_conflicts("manager-a", false, &obj, &old_obj));
+}
+
+#[test]
+pub fn test_forced_apply_takes_over_conflicting_fields() {
+    let old_obj = created_kube_object_from_apply("manager-a", &config_map(Value::Null, json!({"a": "x", "b": "y"})));
+    let obj = config_map(Value::Null, json!({"a": "z"}));
+    assert!(!apply_conflicts("manager-b", true, &obj, &old_obj));
+    let applied = applied_kube_object("manager-b", &obj, &old_obj);
+    assert_eq!(fields_of_manager(&applied, "manager-a"), vec![spec_field(&["data", "b"])].into_iter().collect());
+    assert_eq!(fields_of_manager(&applied, "manager-b"), vec![spec_field(&["data", "a"])].into_iter().collect());
+    assert_eq!(applied.data, json!({"a": "z", "b": "y"}));
+}
+
+#[test]
+pub fn test_apply_removes_fields_no_longer_applied() {
+    let old_obj = created_kube_object_from_apply("manager-a", &config_map(json!({"app": "foo", "tier": "db"}), json!({"a": "x"})));
+    let shared = applied_kube_object("manager-b", &config_map(json!({"tier": "db"}), json!({"a": "x"})), &old_obj);
+    // manager-a stops applying both labels, but manager-b still owns tier and a
+    let applied = applied_kube_object("manager-a", &config_map(Value::Null, json!({"b": "y"})), &shared);
+    assert_eq!(
+        applied.metadata.labels.clone().unwrap_or_default(),
+        vec![("tier".to_string(), "db".to_string())].into_iter().collect()
+    );
+    assert_eq!(applied.data, json!({"a": "x", "b": "y"}));
+}
+
+fn with_finalizers(mut obj: DynamicObject, finalizers: &[&str]) -> DynamicObject {
+    obj.metadata.finalizers = Some(finalizers.iter().map(|finalizer| finalizer.to_string()).collect());
+    obj
+}
+
+fn owner_reference(uid: &str, name: &str) -> deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+    deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+        api_version: "v1".to_string(),
+        kind: "ConfigMap".to_string(),
+        name: name.to_string(),
+        uid: uid.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+pub fn test_merged_list() {
+    let key = |item: &String| item.clone();
+    let strings = |items: &[&str]| items.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+    // The old items that are not applied stay where they are, and the applied items follow the applied order
+    assert_eq!(merged_list(&strings(&["a", "b", "c"]), &strings(&["c", "d"]), &key), strings(&["a", "b", "c", "d"]));
+    assert_eq!(merged_list(&strings(&["a", "b"]), &strings(&["b", "a"]), &key), strings(&["b", "a"]));
+    assert_eq!(merged_list(&strings(&["x", "a"]), &strings(&["b"]), &key), strings(&["x", "a", "b"]));
+}
+
+#[test]
+pub fn test_apply_merges_finalizers_of_managers() {
+    let old_obj = created_kube_object_from_apply(
+        "manager-a",
+        &with_finalizers(config_map(Value::Null, json!({"a": "x"})), &["example.com/a", "example.com/shared"]),
+    );
+    // Applying a finalizer that another manager owns does not conflict, and the finalizers of manager-a are kept
+    let obj = with_finalizers(config_map(Value::Null, json!({"b": "y"})), &["example.com/shared", "example.com/b"]);
+    assert!(!apply_conflicts("manager-b", false, &obj, &old_obj));
+    let applied = applied_kube_object("manager-b", &obj, &old_obj);
+    assert_eq!(
+        applied.metadata.finalizers.clone().unwrap(),
+        vec!["example.com/a".to_string(), "example.com/shared".to_string(), "example.com/b".to_string()]
+    );
+    assert!(fields_of_manager(&applied, "manager-a").contains(&FieldPath::Finalizer("example.com/shared".to_string())));
+    assert!(fields_of_manager(&applied, "manager-b").contains(&FieldPath::Finalizer("example.com/shared".to_string())));
+    // manager-a stops applying its finalizers: only the one that manager-b still owns is kept
+    let applied = applied_kube_object("manager-a", &config_map(Value::Null, json!({"a": "x"})), &applied);
+    assert_eq!(
+        applied.metadata.finalizers.clone().unwrap(),
+        vec!["example.com/shared".to_string(), "example.com/b".to_string()]
+    );
+}
+
+#[test]
+pub fn test_apply_merges_owner_references_by_uid() {
+    let mut obj_a = config_map(Value::Null, json!({"a": "x"}));
+    obj_a.metadata.owner_references = Some(vec![owner_reference("1", "owner-1")]);
+    let old_obj = created_kube_object_from_apply("manager-a", &obj_a);
+    // manager-b adds another owner reference, which does not take the one of manager-a away
+    let mut obj_b = config_map(Value::Null, json!({"b": "y"}));
+    obj_b.metadata.owner_references = Some(vec![owner_reference("2", "owner-2")]);
+    assert!(!apply_conflicts("manager-b", false, &obj_b, &old_obj));
+    let applied = applied_kube_object("manager-b", &obj_b, &old_obj);
+    assert_eq!(
+        applied.metadata.owner_references.clone().unwrap(),
+        vec![owner_reference("1", "owner-1"), owner_reference("2", "owner-2")]
+    );
+    // Changing the owner reference with the uid owned by manager-a conflicts
+    obj_b.metadata.owner_references = Some(vec![owner_reference("1", "owner-renamed")]);
+    assert!(apply_conflicts("manager-b", false, &obj_b, &applied));
+}
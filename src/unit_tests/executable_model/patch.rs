@@ -0,0 +1,102 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::executable_model::patch::*;
+use crate::kubernetes_api_objects::spec::api_method::PatchType;
+use deps_hack::serde_json::json;
+
+#[test]
+pub fn test_merge_patch_merges_objects() {
+    let old_value = json!({"data": {"a": "x", "b": "y"}});
+    let patch = json!({"data": {"a": "z", "b": null, "c": "w"}});
+    assert_eq!(
+        patched_value(&PatchType::MergePatch, "ConfigMap", old_value, &patch),
+        json!({"data": {"a": "z", "c": "w"}})
+    );
+}
+
+#[test]
+pub fn test_merge_patch_replaces_lists() {
+    let old_value = json!({"spec": {"ports": [{"name": "http", "port": 80}]}});
+    let patch = json!({"spec": {"ports": [{"name": "https", "port": 443}]}});
+    assert_eq!(
+        patched_value(&PatchType::MergePatch, "Service", old_value, &patch),
+        json!({"spec": {"ports": [{"name": "https", "port": 443}]}})
+    );
+}
+
+#[test]
+pub fn test_strategic_merge_patch_merges_service_ports_by_port() {
+    let old_value = json!({"spec": {"ports": [{"name": "http", "port": 80}]}});
+    let patch = json!({"spec": {"ports": [{"name": "web", "port": 80}, {"name": "https", "port": 443}]}});
+    assert_eq!(
+        patched_value(&PatchType::StrategicMergePatch, "Service", old_value, &patch),
+        json!({"spec": {"ports": [{"name": "web", "port": 80}, {"name": "https", "port": 443}]}})
+    );
+}
+
+#[test]
+pub fn test_strategic_merge_patch_merges_container_lists_by_their_keys() {
+    let old_value = json!({"spec": {"template": {"spec": {"containers": [{
+        "name": "main",
+        "image": "old",
+        "ports": [{"name": "http", "containerPort": 80}],
+        "volumeMounts": [{"name": "data", "mountPath": "/data"}],
+    }]}}}});
+    let patch = json!({"spec": {"template": {"spec": {"containers": [{
+        "name": "main",
+        "image": "new",
+        "ports": [{"name": "http", "containerPort": 8080}],
+        "volumeMounts": [{"name": "data", "mountPath": "/cache"}],
+    }]}}}});
+    // Container ports are merged by containerPort and volume mounts by mountPath, not by name
+    assert_eq!(
+        patched_value(&PatchType::StrategicMergePatch, "StatefulSet", old_value, &patch),
+        json!({"spec": {"template": {"spec": {"containers": [{
+            "name": "main",
+            "image": "new",
+            "ports": [{"name": "http", "containerPort": 80}, {"name": "http", "containerPort": 8080}],
+            "volumeMounts": [{"name": "data", "mountPath": "/data"}, {"name": "data", "mountPath": "/cache"}],
+        }]}}}})
+    );
+}
+
+#[test]
+pub fn test_strategic_merge_patch_replaces_lists_without_merge_key() {
+    // subjects of a RoleBinding are named but have no patchStrategy, so they are replaced
+    let old_value = json!({"subjects": [{"kind": "ServiceAccount", "name": "a"}]});
+    let patch = json!({"subjects": [{"kind": "ServiceAccount", "name": "b"}]});
+    assert_eq!(
+        patched_value(&PatchType::StrategicMergePatch, "RoleBinding", old_value, &patch),
+        json!({"subjects": [{"kind": "ServiceAccount", "name": "b"}]})
+    );
+}
+
+#[test]
+pub fn test_strategic_merge_patch_with_empty_list() {
+    // An empty list replaces a list without merge key
+    let old_value = json!({"subjects": [{"kind": "ServiceAccount", "name": "a"}]});
+    assert_eq!(
+        patched_value(&PatchType::StrategicMergePatch, "RoleBinding", old_value, &json!({"subjects": []})),
+        json!({"subjects": []})
+    );
+    // but merges nothing into a list with merge key
+    let old_value = json!({"secrets": [{"name": "a"}]});
+    assert_eq!(
+        patched_value(&PatchType::StrategicMergePatch, "ServiceAccount", old_value, &json!({"secrets": []})),
+        json!({"secrets": [{"name": "a"}]})
+    );
+    // while a merge patch always replaces the list
+    let old_value = json!({"secrets": [{"name": "a"}]});
+    assert_eq!(
+        patched_value(&PatchType::MergePatch, "ServiceAccount", old_value, &json!({"secrets": []})),
+        json!({"secrets": []})
+    );
+}
+
+#[test]
+pub fn test_merge_key() {
+    assert_eq!(merge_key("Service", "ports"), Some("port"));
+    assert_eq!(merge_key("StatefulSet", "ports"), Some("containerPort"));
+    assert_eq!(merge_key("StatefulSet", "volumeMounts"), Some("mountPath"));
+    assert_eq!(merge_key("RoleBinding", "subjects"), None);
+}
@@ -3,8 +3,11 @@
 use crate::kubernetes_api_objects::exec::api_method::*;
 use crate::kubernetes_api_objects::exec::api_resource::*;
 use crate::kubernetes_api_objects::exec::dynamic::*;
+use crate::kubernetes_api_objects::exec::label_selector::*;
 use crate::kubernetes_api_objects::exec::object_meta::*;
+use crate::kubernetes_api_objects::exec::preconditions::*;
 use crate::kubernetes_api_objects::exec::resource::*;
+use crate::kubernetes_api_objects::spec::api_method::{PatchType, PropagationPolicy};
 use crate::vstd_ext::string_map::*;
 use deps_hack::chrono::{DateTime, Utc};
 use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
@@ -38,10 +41,63 @@ pub fn test_listrequest_key() {
             plural: "plural".to_string(),
         }),
         namespace: "namespace".to_string(),
+        label_selector: None,
+        field_selector: None,
     };
     assert_eq!(api_method.key(), "kind/namespace");
 }
 
+#[test]
+pub fn test_listrequest_list_params() {
+    let mut label_selector = LabelSelector::default();
+    let mut match_labels = StringMap::new();
+    match_labels.insert("key".to_string(), "value".to_string());
+    match_labels.insert("key_2".to_string(), "value_2".to_string());
+    label_selector.set_match_labels(match_labels);
+    let mut field_selector = FieldSelector::default();
+    field_selector.set_name("name".to_string());
+    let api_method = KubeListRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        namespace: "namespace".to_string(),
+        label_selector: Some(label_selector),
+        field_selector: Some(field_selector),
+    };
+    let list_params = api_method.list_params();
+    assert_eq!(list_params.label_selector, Some("key=value,key_2=value_2".to_string()));
+    assert_eq!(list_params.field_selector, Some("metadata.name=name".to_string()));
+
+    let api_method_without_selectors = KubeListRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        namespace: "namespace".to_string(),
+        label_selector: None,
+        field_selector: None,
+    };
+    let list_params = api_method_without_selectors.list_params();
+    assert_eq!(list_params.label_selector, None);
+    assert_eq!(list_params.field_selector, None);
+}
+
+#[test]
+pub fn test_fieldselector_to_selector_string() {
+    let field_selector = FieldSelector::default();
+    assert_eq!(field_selector.to_selector_string(), "".to_string());
+    let mut field_selector = FieldSelector::default();
+    field_selector.set_name("name".to_string());
+    assert_eq!(field_selector.to_selector_string(), "metadata.name=name".to_string());
+}
+
 #[test]
 pub fn test_createquest_key() {
     let api_method = KubeCreateRequest {
@@ -83,10 +139,48 @@ pub fn test_deleterequest_key() {
         }),
         name: "name".to_string(),
         namespace: "namespace".to_string(),
+        preconditions: None,
+        propagation_policy: None,
+        grace_period_seconds: None,
     };
     assert_eq!(api_method.key(), "kind/namespace/name");
 }
 
+#[test]
+pub fn test_deleterequest_delete_params() {
+    let mut preconditions = Preconditions::default();
+    preconditions.set_uid_from_object_meta(&ObjectMeta::from_kube(deps_hack::kube::api::ObjectMeta {
+        uid: Some("uid".to_string()),
+        ..Default::default()
+    }));
+    let mut api_method = KubeDeleteRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        name: "name".to_string(),
+        namespace: "namespace".to_string(),
+        preconditions: None,
+        propagation_policy: None,
+        grace_period_seconds: None,
+    };
+    assert_eq!(api_method.delete_params(), deps_hack::kube::api::DeleteParams::default());
+
+    api_method.preconditions = Some(preconditions);
+    api_method.propagation_policy = Some(PropagationPolicy::Orphan);
+    api_method.grace_period_seconds = Some(0);
+    let delete_params = api_method.delete_params();
+    assert_eq!(delete_params.preconditions, Some(deps_hack::kube::api::Preconditions {
+        uid: Some("uid".to_string()),
+        resource_version: None,
+    }));
+    assert_eq!(delete_params.propagation_policy, Some(deps_hack::kube::api::PropagationPolicy::Orphan));
+    assert_eq!(delete_params.grace_period_seconds, Some(0));
+}
+
 #[test]
 pub fn test_updaterequest_key() {
     let api_method = KubeUpdateRequest {
@@ -146,3 +240,99 @@ pub fn test_updatestatusrequest_key() {
     };
     assert_eq!(api_method.key(), "kind/namespace/name");
 }
+
+#[test]
+pub fn test_applyrequest_key() {
+    let api_method = KubeApplyRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        name: "name".to_string(),
+        namespace: "namespace".to_string(),
+        obj: DynamicObject::from_kube(deps_hack::kube::api::DynamicObject {
+            metadata: deps_hack::kube::api::ObjectMeta {
+                name: Some("dyn_name".to_string()),
+                namespace: Some("namespace".to_string()),
+                ..Default::default()
+            },
+            types: Some(deps_hack::kube::api::TypeMeta {
+                api_version: "api_version".to_string(),
+                kind: "kind".to_string(),
+            }),
+            data: deps_hack::serde_json::json!({
+                "key": "value",
+            }),
+        }),
+        field_manager: "field_manager".to_string(),
+        force: false,
+    };
+    assert_eq!(api_method.key(), "kind/namespace/name");
+}
+
+#[test]
+pub fn test_applyrequest_patch_params() {
+    let mut api_method = KubeApplyRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        name: "name".to_string(),
+        namespace: "namespace".to_string(),
+        obj: DynamicObject::from_kube(deps_hack::kube::api::DynamicObject {
+            metadata: deps_hack::kube::api::ObjectMeta {
+                name: Some("name".to_string()),
+                namespace: Some("namespace".to_string()),
+                ..Default::default()
+            },
+            types: Some(deps_hack::kube::api::TypeMeta {
+                api_version: "api_version".to_string(),
+                kind: "kind".to_string(),
+            }),
+            data: deps_hack::serde_json::json!({
+                "key": "value",
+            }),
+        }),
+        field_manager: "field_manager".to_string(),
+        force: false,
+    };
+    let patch_params = api_method.patch_params();
+    assert_eq!(patch_params.field_manager, Some("field_manager".to_string()));
+    assert_eq!(patch_params.force, false);
+
+    api_method.force = true;
+    let patch_params = api_method.patch_params();
+    assert_eq!(patch_params.field_manager, Some("field_manager".to_string()));
+    assert_eq!(patch_params.force, true);
+}
+
+#[test]
+pub fn test_patchrequest_key() {
+    let api_method = KubePatchRequest {
+        api_resource: ApiResource::from_kube(deps_hack::kube::api::ApiResource {
+            group: "group".to_string(),
+            version: "version".to_string(),
+            kind: "kind".to_string(),
+            api_version: "api_version".to_string(),
+            plural: "plural".to_string(),
+        }),
+        name: "name".to_string(),
+        namespace: "namespace".to_string(),
+        patch: DynamicObject::from_kube(deps_hack::kube::api::DynamicObject {
+            metadata: deps_hack::kube::api::ObjectMeta {
+                labels: Some(vec![("key".to_string(), "value".to_string())].into_iter().collect()),
+                ..Default::default()
+            },
+            types: None,
+            data: deps_hack::serde_json::json!({}),
+        }),
+        patch_type: PatchType::MergePatch,
+    };
+    assert_eq!(api_method.key(), "kind/namespace/name");
+}
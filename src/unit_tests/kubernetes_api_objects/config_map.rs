@@ -68,6 +68,22 @@ pub fn test_data() {
     );
 }
 
+#[test]
+pub fn test_data_eq() {
+    let mut config_map = ConfigMap::default();
+    let mut other = ConfigMap::default();
+    assert!(config_map.data_eq(&other));
+    let mut data = StringMap::new();
+    data.insert("key".to_string(), "value".to_string());
+    config_map.set_data(data.clone());
+    assert!(!config_map.data_eq(&other));
+    let mut object_meta = ObjectMeta::default();
+    object_meta.set_name("name".to_string());
+    other.set_metadata(object_meta);
+    other.set_data(data);
+    assert!(config_map.data_eq(&other));
+}
+
 #[test]
 pub fn test_api_resource() {
     let api_resource = ConfigMap::api_resource();
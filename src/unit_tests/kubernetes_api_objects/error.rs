@@ -4,9 +4,11 @@ use crate::kubernetes_api_objects::error::*;
 use crate::kubernetes_api_objects::exec::dynamic::*;
 use crate::kubernetes_api_objects::exec::object_meta::*;
 use crate::kubernetes_api_objects::exec::resource::*;
+use crate::shim_layer::controller_runtime::{kube_error_details, kube_error_to_ghost};
 use crate::vstd_ext::string_map::*;
 use deps_hack::chrono::{DateTime, Utc};
 use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use deps_hack::kube_core::ErrorResponse;
 use vstd::prelude::*;
 use vstd::string::*;
 
@@ -32,10 +34,60 @@ pub fn test_apierror_fmt() {
     assert_eq!(format!("{:?}", error), "Timeout");
     let error = APIError::ServerTimeout;
     assert_eq!(format!("{:?}", error), "ServerTimeout");
+    let error = APIError::Unauthorized;
+    assert_eq!(format!("{:?}", error), "Unauthorized");
+    let error = APIError::TooManyRequests;
+    assert_eq!(format!("{:?}", error), "TooManyRequests");
+    let error = APIError::Gone;
+    assert_eq!(format!("{:?}", error), "Gone");
+    let error = APIError::Expired;
+    assert_eq!(format!("{:?}", error), "Expired");
+    let error = APIError::MethodNotAllowed;
+    assert_eq!(format!("{:?}", error), "MethodNotAllowed");
+    let error = APIError::RequestEntityTooLarge;
+    assert_eq!(format!("{:?}", error), "RequestEntityTooLarge");
+    let error = APIError::ServiceUnavailable;
+    assert_eq!(format!("{:?}", error), "ServiceUnavailable");
     let error = APIError::Other;
     assert_eq!(format!("{:?}", error), "Other");
 }
 
+fn api_error(reason: &str, code: u16) -> deps_hack::kube::Error {
+    deps_hack::kube::Error::Api(ErrorResponse {
+        status: "Failure".to_string(),
+        message: format!("{} from the API server", reason),
+        reason: reason.to_string(),
+        code: code,
+    })
+}
+
+#[test]
+pub fn test_kube_error_to_ghost() {
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("NotFound", 404))), "ObjectNotFound");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("TooManyRequests", 429))), "TooManyRequests");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("Expired", 410))), "Expired");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("Gone", 410))), "Gone");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("ServiceUnavailable", 503))), "ServiceUnavailable");
+    // Unknown reasons fall back to the status code
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("", 401))), "Unauthorized");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("", 413))), "RequestEntityTooLarge");
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&api_error("", 418))), "Other");
+    // Errors without a response are translated by the io error causing them
+    let timed_out = deps_hack::kube::Error::ReadEvents(std::io::Error::from(std::io::ErrorKind::TimedOut));
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&timed_out)), "Timeout");
+    let refused = deps_hack::kube::Error::ReadEvents(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+    assert_eq!(format!("{:?}", kube_error_to_ghost(&refused)), "ServiceUnavailable");
+}
+
+#[test]
+pub fn test_kube_error_details() {
+    let details = kube_error_details(&api_error("TooManyRequests", 429));
+    assert_eq!(details.code, Some(429));
+    assert_eq!(details.message, "TooManyRequests from the API server");
+    let refused = deps_hack::kube::Error::ReadEvents(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+    assert_eq!(kube_error_details(&refused).code, None);
+}
+
 #[test]
 pub fn test_parse_dyn_error_fmt() {
     let error = ParseDynamicObjectError::MissingField;
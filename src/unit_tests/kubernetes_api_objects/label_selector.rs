@@ -62,3 +62,91 @@ pub fn test_kube() {
 
     assert_eq!(label_selector.into_kube(), kube_label_selector);
 }
+
+#[test]
+pub fn test_to_selector_string() {
+    let mut label_selector = LabelSelector::default();
+    assert_eq!(label_selector.to_selector_string(), "".to_string());
+    let mut match_labels = StringMap::new();
+    match_labels.insert("key".to_string(), "value".to_string());
+    match_labels.insert("key_2".to_string(), "value_2".to_string());
+    label_selector.set_match_labels(match_labels);
+    assert_eq!(
+        label_selector.to_selector_string(),
+        "key=value,key_2=value_2".to_string()
+    );
+}
+
+fn label_selector_with_expressions(exprs: Vec<(&str, &str, Option<Vec<&str>>)>) -> LabelSelector {
+    LabelSelector::from_kube(
+        deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector {
+            match_expressions: Some(
+                exprs
+                    .into_iter()
+                    .map(|(key, operator, values)| {
+                        deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelectorRequirement {
+                            key: key.to_string(),
+                            operator: operator.to_string(),
+                            values: values.map(|values| values.into_iter().map(|v| v.to_string()).collect()),
+                        }
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+pub fn test_is_valid() {
+    assert!(LabelSelector::default().is_valid());
+    assert!(label_selector_with_expressions(vec![
+        ("key", "In", Some(vec!["value"])),
+        ("key", "NotIn", Some(vec!["value"])),
+        ("key", "Exists", None),
+        ("key", "DoesNotExist", Some(vec![])),
+    ])
+    .is_valid());
+    assert!(!label_selector_with_expressions(vec![("key", "In", None)]).is_valid());
+    assert!(!label_selector_with_expressions(vec![("key", "Exists", Some(vec!["value"]))]).is_valid());
+    assert!(!label_selector_with_expressions(vec![("key", "Equals", Some(vec!["value"]))]).is_valid());
+}
+
+#[test]
+pub fn test_matches_with_expressions() {
+    let mut labels = StringMap::new();
+    labels.insert("key".to_string(), "value".to_string());
+
+    let label_selector = label_selector_with_expressions(vec![
+        ("key", "In", Some(vec!["value", "value_2"])),
+        ("key_2", "DoesNotExist", None),
+    ]);
+    assert!(label_selector.matches(labels.clone()));
+
+    let label_selector = label_selector_with_expressions(vec![("key", "NotIn", Some(vec!["value"]))]);
+    assert!(!label_selector.matches(labels.clone()));
+
+    let label_selector = label_selector_with_expressions(vec![("key_2", "Exists", None)]);
+    assert!(!label_selector.matches(labels.clone()));
+
+    // match_labels and match_expressions are ANDed
+    let mut label_selector = label_selector_with_expressions(vec![("key", "Exists", None)]);
+    let mut match_labels = StringMap::new();
+    match_labels.insert("key".to_string(), "value_2".to_string());
+    label_selector.set_match_labels(match_labels);
+    assert!(!label_selector.matches(labels));
+}
+
+#[test]
+pub fn test_to_selector_string_with_expressions() {
+    let label_selector = label_selector_with_expressions(vec![
+        ("key", "In", Some(vec!["value", "value_2"])),
+        ("key_2", "NotIn", Some(vec!["value"])),
+        ("key_3", "Exists", None),
+        ("key_4", "DoesNotExist", None),
+    ]);
+    assert_eq!(
+        label_selector.to_selector_string(),
+        "key in (value,value_2),key_2 notin (value),key_3,!key_4".to_string()
+    );
+}
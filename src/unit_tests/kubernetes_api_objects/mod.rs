@@ -35,6 +35,7 @@ pub mod pod_security_context;
 pub mod pod_spec;
 pub mod pod_template_spec;
 pub mod policy_rule;
+pub mod preconditions;
 pub mod probe;
 pub mod projected_volume_source;
 pub mod resource_requirements;
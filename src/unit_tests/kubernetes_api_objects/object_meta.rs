@@ -77,6 +77,25 @@ pub fn test_labels() {
     );
 }
 
+#[test]
+pub fn test_labels_eq() {
+    let mut object_meta = ObjectMeta::default();
+    let mut other = ObjectMeta::default();
+    assert!(object_meta.labels_eq(&other));
+    let mut labels = StringMap::new();
+    labels.insert("key".to_string(), "value".to_string());
+    object_meta.set_labels(labels.clone());
+    assert!(!object_meta.labels_eq(&other));
+    other.set_labels(labels);
+    other.set_name("name".to_string());
+    assert!(object_meta.labels_eq(&other));
+    assert!(object_meta.annotations_eq(&other));
+    let mut annotations = StringMap::new();
+    annotations.insert("key".to_string(), "value".to_string());
+    other.set_annotations(annotations);
+    assert!(!object_meta.annotations_eq(&other));
+}
+
 #[test]
 pub fn test_owner_references_only_contains() {
     let mut object_meta = ObjectMeta::default();
@@ -135,6 +154,19 @@ pub fn test_has_deletion_timestamp() {
     assert_eq!(true, object_meta.has_deletion_timestamp());
 }
 
+#[test]
+pub fn test_deletion_grace_period_seconds() {
+    let object_meta = ObjectMeta::default();
+    assert_eq!(None, object_meta.deletion_grace_period_seconds());
+    let object_meta = ObjectMeta::from_kube(
+        deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            deletion_grace_period_seconds: Some(30),
+            ..Default::default()
+        },
+    );
+    assert_eq!(Some(30), object_meta.deletion_grace_period_seconds());
+}
+
 #[test]
 pub fn test_set_annotations() {
     let mut object_meta = ObjectMeta::default();
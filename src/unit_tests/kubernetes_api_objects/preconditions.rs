@@ -0,0 +1,72 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::exec::object_meta::*;
+use crate::kubernetes_api_objects::exec::preconditions::*;
+use crate::kubernetes_api_objects::exec::resource::*;
+use vstd::prelude::*;
+use vstd::string::*;
+
+#[test]
+pub fn test_default() {
+    let preconditions = Preconditions::default();
+    assert_eq!(preconditions.into_kube(), deps_hack::kube::api::Preconditions::default());
+}
+
+#[test]
+pub fn test_set_uid_from_object_meta() {
+    let mut preconditions = Preconditions::default();
+    let object_meta = ObjectMeta::from_kube(deps_hack::kube::api::ObjectMeta {
+        uid: Some("uid".to_string()),
+        resource_version: Some("rv".to_string()),
+        ..Default::default()
+    });
+    preconditions.set_uid_from_object_meta(&object_meta);
+    assert_eq!(preconditions.into_kube(), deps_hack::kube::api::Preconditions {
+        uid: Some("uid".to_string()),
+        resource_version: None,
+    });
+}
+
+#[test]
+pub fn test_set_resource_version_from_object_meta() {
+    let mut preconditions = Preconditions::default();
+    let object_meta = ObjectMeta::from_kube(deps_hack::kube::api::ObjectMeta {
+        uid: Some("uid".to_string()),
+        resource_version: Some("rv".to_string()),
+        ..Default::default()
+    });
+    preconditions.set_resource_version_from_object_meta(&object_meta);
+    assert_eq!(preconditions.into_kube(), deps_hack::kube::api::Preconditions {
+        uid: None,
+        resource_version: Some("rv".to_string()),
+    });
+}
+
+#[test]
+pub fn test_satisfied_by() {
+    let mut preconditions = Preconditions::default();
+    let object_meta = ObjectMeta::from_kube(deps_hack::kube::api::ObjectMeta {
+        uid: Some("uid".to_string()),
+        resource_version: Some("rv".to_string()),
+        ..Default::default()
+    });
+    let other_object_meta = ObjectMeta::from_kube(deps_hack::kube::api::ObjectMeta {
+        uid: Some("other_uid".to_string()),
+        resource_version: Some("rv".to_string()),
+        ..Default::default()
+    });
+    assert!(preconditions.satisfied_by(&other_object_meta));
+    preconditions.set_uid_from_object_meta(&object_meta);
+    assert!(preconditions.satisfied_by(&object_meta));
+    assert!(!preconditions.satisfied_by(&other_object_meta));
+}
+
+#[test]
+pub fn test_kube() {
+    let kube_preconditions = deps_hack::kube::api::Preconditions {
+        uid: Some("uid".to_string()),
+        resource_version: Some("rv".to_string()),
+    };
+    let preconditions = Preconditions::from_kube(kube_preconditions.clone());
+    assert_eq!(preconditions.into_kube(), kube_preconditions);
+}
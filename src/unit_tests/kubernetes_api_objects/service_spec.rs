@@ -26,6 +26,25 @@ pub fn test_set_cluster_ip() {
     );
 }
 
+#[test]
+pub fn test_unset_cluster_ip() {
+    let mut service_spec = ServiceSpec::default();
+    service_spec.set_cluster_ip("ip".to_string());
+    service_spec.unset_cluster_ip();
+    assert_eq!(None, service_spec.into_kube().cluster_ip);
+}
+
+#[test]
+pub fn test_eq() {
+    let mut service_spec = ServiceSpec::default();
+    let mut other = ServiceSpec::default();
+    assert!(service_spec.eq(&other));
+    service_spec.set_cluster_ip("ip".to_string());
+    assert!(!service_spec.eq(&other));
+    other.set_cluster_ip("ip".to_string());
+    assert!(service_spec.eq(&other));
+}
+
 #[test]
 pub fn test_set_ports() {
     let mut service_spec = ServiceSpec::default();
@@ -65,6 +65,19 @@ pub fn test_spec() {
     );
 }
 
+#[test]
+pub fn test_spec_eq() {
+    let mut stateful_set = StatefulSet::default();
+    let mut other = StatefulSet::default();
+    assert!(stateful_set.spec_eq(&other));
+    let mut stateful_set_spec = StatefulSetSpec::default();
+    stateful_set_spec.set_replicas(1024);
+    stateful_set.set_spec(stateful_set_spec.clone());
+    assert!(!stateful_set.spec_eq(&other));
+    other.set_spec(stateful_set_spec);
+    assert!(stateful_set.spec_eq(&other));
+}
+
 #[test]
 pub fn test_api_resource() {
     let api_resource = StatefulSet::api_resource();
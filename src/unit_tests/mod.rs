@@ -1,4 +1,6 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
+pub mod executable_model;
 pub mod kubernetes_api_objects;
+pub mod shim_layer;
 pub mod vstd_ext;
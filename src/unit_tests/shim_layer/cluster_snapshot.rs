@@ -0,0 +1,52 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::cluster_snapshot::*;
+use deps_hack::kube::api::DynamicObject;
+use deps_hack::serde_json::{self, json};
+
+fn stateful_set(data: serde_json::Value) -> DynamicObject {
+    let mut obj: DynamicObject = serde_json::from_value(json!({
+        "apiVersion": "apps/v1",
+        "kind": "StatefulSet",
+        "metadata": {"name": "zk", "namespace": "default"},
+    }))
+    .unwrap();
+    obj.data = data;
+    obj
+}
+
+#[test]
+pub fn test_restore_written_object() {
+    let written_objects = WrittenObjects::new();
+    let written = stateful_set(json!({"spec": {"replicas": 3}}));
+    let returned = stateful_set(json!({"spec": {"replicas": 3, "revisionHistoryLimit": 10}}));
+    written_objects.record("StatefulSet", &written, &returned);
+
+    // The stored object still has the content the API server returned, so it gets back the written content
+    // and keeps its status
+    let mut stored = stateful_set(json!({
+        "spec": {"replicas": 3, "revisionHistoryLimit": 10},
+        "status": {"readyReplicas": 1},
+    }));
+    written_objects.restore("StatefulSet", &mut stored);
+    assert_eq!(
+        stored.data,
+        json!({"spec": {"replicas": 3}, "status": {"readyReplicas": 1}})
+    );
+
+    // The stored object is changed by someone else, so it is left as it is
+    let mut stored = stateful_set(json!({"spec": {"replicas": 5, "revisionHistoryLimit": 10}}));
+    written_objects.restore("StatefulSet", &mut stored);
+    assert_eq!(
+        stored.data,
+        json!({"spec": {"replicas": 5, "revisionHistoryLimit": 10}})
+    );
+
+    // The controller never wrote an object of this kind
+    let mut stored = stateful_set(json!({"spec": {"replicas": 3, "revisionHistoryLimit": 10}}));
+    written_objects.restore("DaemonSet", &mut stored);
+    assert_eq!(
+        stored.data,
+        json!({"spec": {"replicas": 3, "revisionHistoryLimit": 10}})
+    );
+}
@@ -0,0 +1,242 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::external_api::exec::*;
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::{api_method::*, dynamic::*, pod::*};
+use crate::reconciler::exec::io::*;
+use crate::shim_layer::fault_injection::*;
+use deps_hack::k8s_openapi::api::core::v1::ConfigMap;
+use std::collections::{BTreeMap, BTreeSet};
+use vstd::string::*;
+
+fn get_request() -> Request<EmptyType> {
+    Request::KRequest(KubeAPIRequest::GetRequest(KubeGetRequest {
+        api_resource: Pod::api_resource(),
+        name: "foo".to_string(),
+        namespace: "default".to_string(),
+    }))
+}
+
+fn delete_request() -> Request<EmptyType> {
+    Request::KRequest(KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
+        api_resource: Pod::api_resource(),
+        name: "foo".to_string(),
+        namespace: "default".to_string(),
+        preconditions: None,
+        propagation_policy: None,
+        grace_period_seconds: None,
+    }))
+}
+
+#[test]
+pub fn test_parse_fault_injection_config() {
+    let mut config_map = ConfigMap::default();
+    config_map.data = Some(BTreeMap::from([
+        (
+            "spec".to_string(),
+            "crKey: VReplicaSet/default/foo
+faults:
+- request: Get
+  index: 2
+  fault: !Error Conflict
+- request: Write
+  index: 1
+  fault: CrashBeforeSend
+- request: External
+  index: 1
+  fault: !Delay
+    millis: 100
+"
+            .to_string(),
+        ),
+        (
+            "progress".to_string(),
+            r#"{"counts": {"Get": 2, "Any": 2}, "fired": [1]}"#.to_string(),
+        ),
+    ]));
+    let (spec, progress) = parse_fault_injection_config(&config_map).unwrap();
+    assert_eq!(
+        spec,
+        FaultInjectionSpec {
+            cr_key: "VReplicaSet/default/foo".to_string(),
+            faults: vec![
+                FaultSpec {
+                    request: FaultRequestKind::Get,
+                    index: 2,
+                    fault: Fault::Error(InjectedError::Conflict),
+                },
+                FaultSpec {
+                    request: FaultRequestKind::Write,
+                    index: 1,
+                    fault: Fault::CrashBeforeSend,
+                },
+                FaultSpec {
+                    request: FaultRequestKind::External,
+                    index: 1,
+                    fault: Fault::Delay { millis: 100 },
+                },
+            ],
+            count: vec![],
+        }
+    );
+    assert_eq!(
+        progress,
+        FaultProgress {
+            counts: BTreeMap::from([(FaultRequestKind::Get, 2), (FaultRequestKind::Any, 2)]),
+            fired: BTreeSet::from([1]),
+        }
+    );
+
+    // JSON works as well
+    config_map.data = Some(BTreeMap::from([(
+        "spec".to_string(),
+        r#"{"crKey": "VReplicaSet/default/foo", "faults": [{"request": "Any", "index": 3, "fault": "Drop"}]}"#
+            .to_string(),
+    )]));
+    let (spec, progress) = parse_fault_injection_config(&config_map).unwrap();
+    assert_eq!(spec.faults[0].request, FaultRequestKind::Any);
+    assert_eq!(spec.faults[0].fault, Fault::Drop);
+    assert_eq!(progress, FaultProgress::default());
+
+    config_map.data = Some(BTreeMap::from([(
+        "spec".to_string(),
+        "faults: []".to_string(),
+    )]));
+    assert!(parse_fault_injection_config(&config_map).is_err());
+}
+
+#[test]
+pub fn test_next_fault() {
+    let spec = FaultInjectionSpec {
+        cr_key: "VReplicaSet/default/foo".to_string(),
+        faults: vec![
+            FaultSpec {
+                request: FaultRequestKind::Get,
+                index: 2,
+                fault: Fault::Error(InjectedError::Timeout),
+            },
+            FaultSpec {
+                request: FaultRequestKind::Write,
+                index: 1,
+                fault: Fault::CrashAfterSend,
+            },
+            FaultSpec {
+                request: FaultRequestKind::Any,
+                index: 4,
+                fault: Fault::Delay { millis: 10 },
+            },
+        ],
+        count: vec![],
+    };
+    let plan = FaultPlan::new(spec.clone(), FaultProgress::default());
+    assert_eq!(plan.next_fault(&get_request()), None);
+    assert_eq!(
+        plan.next_fault(&get_request()),
+        Some(Fault::Error(InjectedError::Timeout))
+    );
+    assert_eq!(
+        plan.next_fault(&delete_request()),
+        Some(Fault::CrashAfterSend)
+    );
+    // The get request is the fourth request of any kind
+    assert_eq!(
+        plan.next_fault(&get_request()),
+        Some(Fault::Delay { millis: 10 })
+    );
+    assert_eq!(plan.next_fault(&delete_request()), None);
+    assert_eq!(
+        plan.progress(),
+        FaultProgress {
+            // No fault is on Delete, so the delete requests are only counted as Write and Any
+            counts: BTreeMap::from([
+                (FaultRequestKind::Get, 3),
+                (FaultRequestKind::Write, 2),
+                (FaultRequestKind::Any, 5),
+            ]),
+            fired: BTreeSet::from([0, 1, 2]),
+        }
+    );
+
+    // The progress carries over to the next reconcile (or restart), and the fired faults do not fire again
+    let plan = FaultPlan::new(
+        spec.clone(),
+        FaultProgress {
+            counts: BTreeMap::from([(FaultRequestKind::Get, 1), (FaultRequestKind::Any, 1)]),
+            fired: BTreeSet::new(),
+        },
+    );
+    assert_eq!(
+        plan.next_fault(&get_request()),
+        Some(Fault::Error(InjectedError::Timeout))
+    );
+    let plan = FaultPlan::new(
+        spec.clone(),
+        FaultProgress {
+            counts: BTreeMap::from([(FaultRequestKind::Get, 1), (FaultRequestKind::Any, 1)]),
+            fired: BTreeSet::from([0]),
+        },
+    );
+    assert_eq!(plan.next_fault(&get_request()), None);
+}
+
+#[test]
+pub fn test_next_fault_counts_only_configured_kinds() {
+    // A spec with no faults counts nothing unless it asks to
+    let plan = FaultPlan::new(
+        FaultInjectionSpec {
+            cr_key: "VReplicaSet/default/foo".to_string(),
+            faults: vec![],
+            count: vec![],
+        },
+        FaultProgress::default(),
+    );
+    assert_eq!(plan.next_fault(&get_request()), None);
+    assert_eq!(plan.next_fault(&delete_request()), None);
+    assert_eq!(plan.progress(), FaultProgress::default());
+
+    let plan = FaultPlan::new(
+        FaultInjectionSpec {
+            cr_key: "VReplicaSet/default/foo".to_string(),
+            faults: vec![FaultSpec {
+                request: FaultRequestKind::Delete,
+                index: 2,
+                fault: Fault::CrashBeforeSend,
+            }],
+            count: vec![FaultRequestKind::Write],
+        },
+        FaultProgress::default(),
+    );
+    // The get request changes no count, so the progress need not be recorded
+    assert_eq!(plan.next_fault(&get_request()), None);
+    assert_eq!(plan.progress(), FaultProgress::default());
+    assert_eq!(plan.next_fault(&delete_request()), None);
+    assert_eq!(
+        plan.next_fault(&delete_request()),
+        Some(Fault::CrashBeforeSend)
+    );
+    assert_eq!(
+        plan.progress(),
+        FaultProgress {
+            counts: BTreeMap::from([(FaultRequestKind::Delete, 2), (FaultRequestKind::Write, 2)]),
+            fired: BTreeSet::from([0]),
+        }
+    );
+}
+
+#[test]
+pub fn test_injected_error_response() {
+    let req = KubeAPIRequest::DeleteRequest(KubeDeleteRequest {
+        api_resource: Pod::api_resource(),
+        name: "foo".to_string(),
+        namespace: "default".to_string(),
+        preconditions: None,
+        propagation_policy: None,
+        grace_period_seconds: None,
+    });
+    match injected_error_response(&req, InjectedError::Conflict.to_api_error()) {
+        KubeAPIResponse::DeleteResponse(resp) => {
+            assert_eq!(format!("{:?}", resp.res), "Err(Conflict)")
+        }
+        _ => panic!("unexpected response"),
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::leader_election::*;
+use deps_hack::chrono::{TimeZone, Utc};
+use deps_hack::k8s_openapi::api::coordination::v1::LeaseSpec;
+use deps_hack::k8s_openapi::apimachinery::pkg::apis::meta::v1::MicroTime;
+use std::time::{Duration, Instant};
+
+// LeaseStore stands for the Lease object in the API server: a write succeeds only if it carries
+// the resource version of the current lease.
+struct LeaseStore {
+    lease: Option<LeaseSpec>,
+    resource_version: u64,
+}
+
+impl LeaseStore {
+    fn read(&self) -> (Option<LeaseSpec>, u64) {
+        (self.lease.clone(), self.resource_version)
+    }
+
+    fn write(&mut self, record: LeaseSpec, resource_version: u64) -> bool {
+        if resource_version != self.resource_version {
+            return false;
+        }
+        self.lease = Some(record);
+        self.resource_version += 1;
+        true
+    }
+}
+
+fn config(identity: &str) -> LeaderElectionConfig {
+    LeaderElectionConfig {
+        lease_name: "lease".to_string(),
+        lease_namespace: "default".to_string(),
+        identity: identity.to_string(),
+        lease_duration: Duration::from_secs(15),
+        retry_period: Duration::from_secs(2),
+    }
+}
+
+fn wall_clock(secs: i64) -> MicroTime {
+    MicroTime(Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap())
+}
+
+// attempt runs one acquire-or-renew attempt of the replica against the store and returns whether it holds the lease.
+fn attempt(state: &mut LeaderElectionState, store: &mut LeaseStore, now: Instant, wall_clock_now: MicroTime) -> bool {
+    let (current, resource_version) = store.read();
+    match state.next_record(current.as_ref(), now, wall_clock_now) {
+        Some(record) => {
+            if store.write(record.clone(), resource_version) {
+                state.written(record, now);
+                true
+            } else {
+                false
+            }
+        }
+        None => false,
+    }
+}
+
+#[test]
+pub fn test_acquire() {
+    let start = Instant::now();
+    let mut store = LeaseStore { lease: None, resource_version: 0 };
+    let mut a = LeaderElectionState::new(&config("a"));
+    assert!(!a.is_leader(start));
+    assert!(attempt(&mut a, &mut store, start, wall_clock(0)));
+    assert!(a.is_leader(start));
+    let lease = store.lease.clone().unwrap();
+    assert_eq!(lease.holder_identity, Some("a".to_string()));
+    assert_eq!(lease.lease_duration_seconds, Some(15));
+    assert_eq!(lease.lease_transitions, Some(0));
+    assert_eq!(lease.acquire_time, Some(wall_clock(0)));
+
+    // A lease without a holder can be acquired at once
+    let mut store = LeaseStore {
+        lease: Some(LeaseSpec { holder_identity: Some("".to_string()), lease_transitions: Some(3), ..LeaseSpec::default() }),
+        resource_version: 7,
+    };
+    let mut b = LeaderElectionState::new(&config("b"));
+    assert!(attempt(&mut b, &mut store, start, wall_clock(0)));
+    let lease = store.lease.clone().unwrap();
+    assert_eq!(lease.holder_identity, Some("b".to_string()));
+    assert_eq!(lease.lease_transitions, Some(4));
+}
+
+#[test]
+pub fn test_renew() {
+    let start = Instant::now();
+    let mut store = LeaseStore { lease: None, resource_version: 0 };
+    let mut a = LeaderElectionState::new(&config("a"));
+    assert!(attempt(&mut a, &mut store, start, wall_clock(0)));
+    // The leadership runs out lease_duration after the last successful attempt started, unless renewed
+    assert!(!a.is_leader(start + Duration::from_secs(15)));
+    assert!(attempt(&mut a, &mut store, start + Duration::from_secs(10), wall_clock(10)));
+    assert!(a.is_leader(start + Duration::from_secs(20)));
+    let lease = store.lease.clone().unwrap();
+    assert_eq!(lease.holder_identity, Some("a".to_string()));
+    assert_eq!(lease.acquire_time, Some(wall_clock(0)));
+    assert_eq!(lease.renew_time, Some(wall_clock(10)));
+    assert_eq!(lease.lease_transitions, Some(0));
+}
+
+#[test]
+pub fn test_takeover() {
+    let start = Instant::now();
+    let mut store = LeaseStore { lease: None, resource_version: 0 };
+    let mut a = LeaderElectionState::new(&config("a"));
+    let mut b = LeaderElectionState::new(&config("b"));
+    assert!(attempt(&mut a, &mut store, start, wall_clock(0)));
+
+    // The renewTime written by a is far in the past from the wall clock of b, but b still waits for
+    // lease_duration since it observed the record, because the clocks of the replicas need not agree
+    assert!(!attempt(&mut b, &mut store, start + Duration::from_secs(1), wall_clock(3600)));
+    assert!(!attempt(&mut b, &mut store, start + Duration::from_secs(15), wall_clock(3614)));
+
+    // a renews the lease, which restarts the expiry observed by b
+    assert!(attempt(&mut a, &mut store, start + Duration::from_secs(10), wall_clock(10)));
+    assert!(!attempt(&mut b, &mut store, start + Duration::from_secs(17), wall_clock(3617)));
+    assert!(!attempt(&mut b, &mut store, start + Duration::from_secs(31), wall_clock(3631)));
+
+    // a stops renewing, and b takes over once the record stays unchanged for lease_duration
+    assert!(!a.is_leader(start + Duration::from_secs(25)));
+    assert!(attempt(&mut b, &mut store, start + Duration::from_secs(32), wall_clock(3632)));
+    assert!(b.is_leader(start + Duration::from_secs(32)));
+    let lease = store.lease.clone().unwrap();
+    assert_eq!(lease.holder_identity, Some("b".to_string()));
+    assert_eq!(lease.acquire_time, Some(wall_clock(3632)));
+    assert_eq!(lease.lease_transitions, Some(1));
+
+    // a comes back and finds the lease held by b
+    assert!(!attempt(&mut a, &mut store, start + Duration::from_secs(33), wall_clock(33)));
+    assert!(!a.is_leader(start + Duration::from_secs(33)));
+}
+
+#[test]
+pub fn test_conflict() {
+    let start = Instant::now();
+    let mut store = LeaseStore {
+        lease: Some(LeaseSpec {
+            holder_identity: Some("c".to_string()),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(wall_clock(0)),
+            lease_transitions: Some(0),
+            ..LeaseSpec::default()
+        }),
+        resource_version: 1,
+    };
+    let mut a = LeaderElectionState::new(&config("a"));
+    let mut b = LeaderElectionState::new(&config("b"));
+    assert!(!attempt(&mut a, &mut store, start, wall_clock(0)));
+    assert!(!attempt(&mut b, &mut store, start, wall_clock(0)));
+
+    // Both a and b find the lease of c expired and race for it with the same resource version
+    let later = start + Duration::from_secs(16);
+    let (current, resource_version) = store.read();
+    let record_a = a.next_record(current.as_ref(), later, wall_clock(16)).unwrap();
+    let record_b = b.next_record(current.as_ref(), later, wall_clock(16)).unwrap();
+    assert!(store.write(record_a.clone(), resource_version));
+    a.written(record_a, later);
+    assert!(!store.write(record_b, resource_version));
+    assert!(a.is_leader(later));
+    assert!(!b.is_leader(later));
+
+    // b observes the lease of a as a new record and waits for lease_duration again
+    assert!(!attempt(&mut b, &mut store, later + Duration::from_secs(1), wall_clock(17)));
+    assert!(!attempt(&mut b, &mut store, later + Duration::from_secs(15), wall_clock(31)));
+    assert!(attempt(&mut b, &mut store, later + Duration::from_secs(16), wall_clock(32)));
+    assert_eq!(store.lease.clone().unwrap().lease_transitions, Some(2));
+}
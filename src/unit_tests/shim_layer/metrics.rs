@@ -0,0 +1,60 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::kubernetes_api_objects::error::*;
+use crate::kubernetes_api_objects::exec::api_method::*;
+use crate::shim_layer::metrics::*;
+use std::time::Duration;
+
+#[test]
+pub fn test_kube_response_outcome() {
+    let resp = KubeAPIResponse::DeleteResponse(KubeDeleteResponse { res: Ok(()) });
+    assert_eq!(kube_response_outcome(&resp), "ok");
+    let resp = KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+        res: Err(APIError::ObjectNotFound),
+    });
+    assert_eq!(kube_response_outcome(&resp), "ObjectNotFound");
+}
+
+#[test]
+pub fn test_render_metrics() {
+    let metrics = Metrics::new();
+    assert_eq!(metrics.render(), "");
+    metrics.reconcile_finished("VReplicaSet", "done", Duration::from_millis(20), 3);
+    metrics.reconcile_finished("VReplicaSet", "done", Duration::from_secs(120), 1);
+    metrics.reconcile_core_error("VReplicaSet");
+    metrics.api_request(
+        "VReplicaSet",
+        "delete",
+        "Pod",
+        &KubeAPIResponse::DeleteResponse(KubeDeleteResponse {
+            res: Err(APIError::Conflict),
+        }),
+    );
+    let rendered = metrics.render();
+    assert!(rendered.contains("# TYPE controller_reconcile_total counter\n"));
+    assert!(
+        rendered.contains("controller_reconcile_total{kind=\"VReplicaSet\",outcome=\"done\"} 2\n")
+    );
+    assert!(rendered.contains("# TYPE controller_reconcile_duration_seconds histogram\n"));
+    // Buckets are cumulative, and the reconcile that takes 120s only shows up in +Inf
+    assert!(rendered.contains(
+        "controller_reconcile_duration_seconds_bucket{kind=\"VReplicaSet\",le=\"0.01\"} 0\n"
+    ));
+    assert!(rendered.contains(
+        "controller_reconcile_duration_seconds_bucket{kind=\"VReplicaSet\",le=\"0.025\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "controller_reconcile_duration_seconds_bucket{kind=\"VReplicaSet\",le=\"60\"} 1\n"
+    ));
+    assert!(rendered.contains(
+        "controller_reconcile_duration_seconds_bucket{kind=\"VReplicaSet\",le=\"+Inf\"} 2\n"
+    ));
+    assert!(
+        rendered.contains("controller_reconcile_duration_seconds_count{kind=\"VReplicaSet\"} 2\n")
+    );
+    assert!(rendered.contains("controller_reconcile_core_iterations_sum{kind=\"VReplicaSet\"} 4\n"));
+    assert!(rendered.contains("controller_reconcile_core_errors_total{kind=\"VReplicaSet\"} 1\n"));
+    assert!(rendered.contains(
+        "controller_api_requests_total{kind=\"VReplicaSet\",verb=\"delete\",resource_kind=\"Pod\",outcome=\"Conflict\"} 1\n"
+    ));
+}
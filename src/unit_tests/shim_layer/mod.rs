@@ -0,0 +1,8 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+pub mod cluster_snapshot;
+pub mod fault_injection;
+pub mod leader_election;
+pub mod metrics;
+pub mod requeue;
+pub mod worker_pool;
@@ -0,0 +1,83 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::requeue::*;
+use deps_hack::kube::runtime::controller::Action;
+use std::time::Duration;
+
+#[test]
+pub fn test_delay_after_done() {
+    let policy = RequeuePolicy::default();
+    assert_eq!(
+        policy.delay_after_done(RequeueHint::Done),
+        Duration::from_secs(60)
+    );
+    assert_eq!(
+        policy.delay_after_done(RequeueHint::RequeueAfter(Duration::from_secs(5))),
+        Duration::from_secs(5)
+    );
+    // No hint can delay the next reconcile beyond the resync period
+    assert_eq!(
+        policy.delay_after_done(RequeueHint::RequeueAfter(Duration::from_secs(3600))),
+        Duration::from_secs(600)
+    );
+    assert_eq!(
+        policy.delay_after_done(RequeueHint::AwaitChange),
+        Duration::from_secs(600)
+    );
+}
+
+#[test]
+pub fn test_delay_after_failures() {
+    let policy = RequeuePolicy::default();
+    assert_eq!(policy.delay_after_failures(1, 0.0), Duration::from_secs(10));
+    assert_eq!(policy.delay_after_failures(2, 0.0), Duration::from_secs(20));
+    assert_eq!(policy.delay_after_failures(3, 0.0), Duration::from_secs(40));
+    assert_eq!(
+        policy.delay_after_failures(6, 0.0),
+        Duration::from_secs(300)
+    );
+    assert_eq!(
+        policy.delay_after_failures(u32::MAX, 0.0),
+        Duration::from_secs(300)
+    );
+    assert_eq!(policy.delay_after_failures(1, 1.0), Duration::from_secs(11));
+    assert_eq!(policy.delay_after_failures(1, -1.0), Duration::from_secs(9));
+    // The jitter sample is clamped to [-1, 1]
+    assert_eq!(policy.delay_after_failures(1, 5.0), Duration::from_secs(11));
+}
+
+#[test]
+pub fn test_requeuer() {
+    let requeuer = Requeuer::new(RequeuePolicy {
+        backoff_jitter: 0.0,
+        ..RequeuePolicy::default()
+    });
+    let key = "VReplicaSet/default/foo".to_string();
+    let other_key = "VReplicaSet/default/bar".to_string();
+    assert_eq!(
+        requeuer.on_failure(&key),
+        Action::requeue(Duration::from_secs(10))
+    );
+    assert_eq!(
+        requeuer.on_failure(&key),
+        Action::requeue(Duration::from_secs(20))
+    );
+    assert_eq!(requeuer.failures(&key), 2);
+    // The backoff is tracked per custom resource
+    assert_eq!(
+        requeuer.on_failure(&other_key),
+        Action::requeue(Duration::from_secs(10))
+    );
+    // A successful reconcile resets the backoff
+    assert_eq!(
+        requeuer.on_done(&key, RequeueHint::AwaitChange),
+        Action::requeue(Duration::from_secs(600))
+    );
+    assert_eq!(requeuer.failures(&key), 0);
+    assert_eq!(
+        requeuer.on_failure(&key),
+        Action::requeue(Duration::from_secs(10))
+    );
+    requeuer.forget(&other_key);
+    assert_eq!(requeuer.failures(&other_key), 0);
+}
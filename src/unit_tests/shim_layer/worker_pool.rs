@@ -0,0 +1,24 @@
+// Copyright 2022 VMware, Inc.
+// SPDX-License-Identifier: MIT
+use crate::shim_layer::worker_pool::*;
+
+#[test]
+pub fn test_in_flight_reconciles() {
+    let in_flight = InFlightReconciles::new();
+    let key = "VReplicaSet/default/foo".to_string();
+    let other_key = "VReplicaSet/default/bar".to_string();
+    let guard = in_flight.try_claim(&key);
+    assert!(guard.is_some());
+    assert!(in_flight.contains(&key));
+    // Another reconcile of the same key has to wait
+    assert!(in_flight.try_claim(&key).is_none());
+    // but a reconcile of another key does not
+    let other_guard = in_flight.try_claim(&other_key);
+    assert!(other_guard.is_some());
+    drop(guard);
+    assert!(!in_flight.contains(&key));
+    assert!(in_flight.try_claim(&key).is_some());
+    // The key is released when the guard above is dropped at the end of the statement
+    assert!(!in_flight.contains(&key));
+    assert!(in_flight.contains(&other_key));
+}
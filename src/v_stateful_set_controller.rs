@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
 
+pub mod executable_model;
 pub mod external_api;
 pub mod kubernetes_api_objects;
 pub mod kubernetes_cluster;
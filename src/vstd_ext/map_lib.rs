@@ -1,13 +1,236 @@
 // Copyright 2022 VMware, Inc.
 // SPDX-License-Identifier: MIT
 #![allow(unused_imports)]
+use crate::vstd_ext::seq_lib::*;
 use vstd::{map_lib::*, prelude::*, seq_lib::*, set::*, set_lib::*};
 
 verus! {
 
-// Return all the values in m, which satisfy f, as a seq
-pub open spec fn map_to_seq<K, V>(m: Map<K, V>, f: spec_fn(V) -> bool) -> Seq<V> {
-    m.values().filter(f).to_seq()
+pub open spec fn strict_total_order<K>(lt: spec_fn(K, K) -> bool) -> bool {
+    &&& forall |a: K| !#[trigger] lt(a, a)
+    &&& forall |a: K, b: K, c: K| #[trigger] lt(a, b) && #[trigger] lt(b, c) ==> lt(a, c)
+    &&& forall |a: K, b: K| #![trigger lt(a, b)] a != b ==> lt(a, b) || lt(b, a)
+}
+
+// keys lists each key of m exactly once, in the ascending order of lt
+pub open spec fn keys_are_sorted<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool, keys: Seq<K>) -> bool {
+    &&& keys.to_set() == m.dom()
+    &&& keys.no_duplicates()
+    &&& forall |i: int, j: int| 0 <= i < j < keys.len() ==> #[trigger] lt(keys[i], keys[j])
+}
+
+// sorted_keys is well defined when m is finite and lt is a strict total order (see lemma_sorted_keys_exists)
+pub open spec fn sorted_keys<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool) -> Seq<K> {
+    choose |keys: Seq<K>| keys_are_sorted(m, lt, keys)
+}
+
+// Return all the values in m, which satisfy f, as a seq ordered by their keys
+pub open spec fn map_to_seq<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool, f: spec_fn(V) -> bool) -> Seq<V> {
+    sorted_keys(m, lt).map_values(|k: K| m[k]).filter(f)
+}
+
+pub proof fn lemma_sorted_keys_exists<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool)
+    requires
+        m.dom().finite(),
+        strict_total_order(lt),
+    ensures keys_are_sorted(m, lt, sorted_keys(m, lt)),
+{
+    let keys = lemma_sorted_seq_of_set(m.dom(), lt);
+    assert(keys_are_sorted(m, lt, keys));
+}
+
+proof fn lemma_sorted_seq_of_set<K>(s: Set<K>, lt: spec_fn(K, K) -> bool) -> (keys: Seq<K>)
+    requires
+        s.finite(),
+        strict_total_order(lt),
+    ensures
+        keys.to_set() == s,
+        keys.no_duplicates(),
+        forall |i: int, j: int| 0 <= i < j < keys.len() ==> #[trigger] lt(keys[i], keys[j]),
+    decreases s.len(),
+{
+    if s.len() == 0 {
+        assert(s =~= Set::empty());
+        assert(Seq::<K>::empty().to_set() =~= s);
+        Seq::empty()
+    } else {
+        let max = lemma_max_of_set(s, lt);
+        let rest = lemma_sorted_seq_of_set(s.remove(max), lt);
+        let keys = rest.push(max);
+        assert forall |k: K| keys.contains(k) <==> s.contains(k) by {
+            if keys.contains(k) {
+                let i = choose |i: int| 0 <= i < keys.len() && keys[i] == k;
+                if i < rest.len() {
+                    assert(rest[i] == k);
+                    assert(rest.to_set().contains(k));
+                }
+            }
+            if s.contains(k) {
+                if k == max {
+                    assert(keys[rest.len() as int] == k);
+                } else {
+                    assert(rest.to_set().contains(k));
+                    let i = choose |i: int| 0 <= i < rest.len() && rest[i] == k;
+                    assert(keys[i] == k);
+                }
+            }
+        }
+        assert(keys.to_set() =~= s);
+        assert forall |i: int| 0 <= i < rest.len() implies #[trigger] lt(rest[i], max) by {
+            assert(rest.to_set().contains(rest[i]));
+        }
+        assert forall |i: int, j: int| 0 <= i < j < keys.len() implies #[trigger] lt(keys[i], keys[j]) by {
+            if j < rest.len() {
+                assert(lt(rest[i], rest[j]));
+            } else {
+                assert(lt(rest[i], max));
+            }
+        }
+        assert forall |i: int, j: int| 0 <= i < keys.len() && 0 <= j < keys.len() && i != j implies keys[i] != keys[j] by {
+            if i < j {
+                assert(lt(keys[i], keys[j]));
+            } else {
+                assert(lt(keys[j], keys[i]));
+            }
+        }
+        keys
+    }
+}
+
+// Each element of s other than the returned one is less than it
+proof fn lemma_max_of_set<K>(s: Set<K>, lt: spec_fn(K, K) -> bool) -> (max: K)
+    requires
+        s.finite(),
+        s.len() > 0,
+        strict_total_order(lt),
+    ensures
+        s.contains(max),
+        forall |k: K| #[trigger] s.contains(k) && k != max ==> lt(k, max),
+    decreases s.len(),
+{
+    let x = s.choose();
+    if s.remove(x).len() == 0 {
+        assert forall |k: K| #[trigger] s.contains(k) implies k == x by {
+            if k != x {
+                assert(s.remove(x).contains(k));
+            }
+        }
+        x
+    } else {
+        let max_of_rest = lemma_max_of_set(s.remove(x), lt);
+        if lt(x, max_of_rest) {
+            assert forall |k: K| #[trigger] s.contains(k) && k != max_of_rest implies lt(k, max_of_rest) by {
+                if k != x {
+                    assert(s.remove(x).contains(k));
+                }
+            }
+            max_of_rest
+        } else {
+            assert(lt(max_of_rest, x));
+            assert forall |k: K| #[trigger] s.contains(k) && k != x implies lt(k, x) by {
+                assert(s.remove(x).contains(k));
+                if k != max_of_rest {
+                    assert(lt(k, max_of_rest));
+                }
+            }
+            x
+        }
+    }
+}
+
+// The keys whose values satisfy f are as many as the values map_to_seq lists.
+pub proof fn lemma_map_to_seq_len_is_filtered_keys_len<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool, f: spec_fn(V) -> bool)
+    requires
+        m.dom().finite(),
+        strict_total_order(lt),
+    ensures
+        Set::new(|k: K| m.contains_key(k) && f(m[k])).finite(),
+        Set::new(|k: K| m.contains_key(k) && f(m[k])).len() == map_to_seq(m, lt, f).len(),
+{
+    lemma_sorted_keys_exists(m, lt);
+    let keys = sorted_keys(m, lt);
+    lemma_filtered_keys_len(keys, m, f);
+    assert forall |k: K| keys.contains(k) <==> m.contains_key(k) by {
+        assert(keys.to_set().contains(k) == keys.contains(k));
+    }
+    assert(Set::new(|k: K| m.contains_key(k) && f(m[k])) =~= Set::new(|k: K| keys.contains(k) && f(m[k])));
+}
+
+proof fn lemma_filtered_keys_len<K, V>(keys: Seq<K>, m: Map<K, V>, f: spec_fn(V) -> bool)
+    requires keys.no_duplicates(),
+    ensures
+        Set::new(|k: K| keys.contains(k) && f(m[k])).finite(),
+        Set::new(|k: K| keys.contains(k) && f(m[k])).len() == keys.map_values(|k: K| m[k]).filter(f).len(),
+    decreases keys.len(),
+{
+    reveal(Seq::filter);
+    let filtered = Set::new(|k: K| keys.contains(k) && f(m[k]));
+    if keys.len() == 0 {
+        assert(filtered =~= Set::empty());
+        assert(keys.map_values(|k: K| m[k]) =~= Seq::empty());
+    } else {
+        let rest = keys.drop_last();
+        let last = keys.last();
+        assert(keys =~= rest.push(last));
+        assert forall |i: int, j: int| 0 <= i < rest.len() && 0 <= j < rest.len() && i != j implies rest[i] != rest[j] by {
+            assert(keys[i] == rest[i] && keys[j] == rest[j]);
+        }
+        lemma_filtered_keys_len(rest, m, f);
+        let filtered_rest = Set::new(|k: K| rest.contains(k) && f(m[k]));
+        assert forall |k: K| keys.contains(k) <==> rest.contains(k) || k == last by {
+            if keys.contains(k) {
+                let i = choose |i: int| 0 <= i < keys.len() && keys[i] == k;
+                if i < rest.len() {
+                    assert(rest[i] == k);
+                }
+            }
+            if rest.contains(k) {
+                let i = choose |i: int| 0 <= i < rest.len() && rest[i] == k;
+                assert(keys[i] == k);
+            }
+            if k == last {
+                assert(keys[keys.len() - 1] == k);
+            }
+        }
+        assert(!rest.contains(last)) by {
+            if rest.contains(last) {
+                let i = choose |i: int| 0 <= i < rest.len() && rest[i] == last;
+                assert(keys[i] == keys[keys.len() - 1]);
+            }
+        }
+        let values = keys.map_values(|k: K| m[k]);
+        let rest_values = rest.map_values(|k: K| m[k]);
+        assert(values =~= rest_values.push(m[last]));
+        lemma_filter_maintained_after_add(rest_values, f, rest_values.filter(f), m[last]);
+        if f(m[last]) {
+            assert(filtered =~= filtered_rest.insert(last));
+        } else {
+            assert(filtered =~= filtered_rest);
+        }
+    }
+}
+
+// Each value listed by map_to_seq is the value of some key in m and satisfies f.
+pub proof fn lemma_map_to_seq_contains_implies_contains_value<K, V>(m: Map<K, V>, lt: spec_fn(K, K) -> bool, f: spec_fn(V) -> bool, i: int) -> (k: K)
+    requires
+        m.dom().finite(),
+        strict_total_order(lt),
+        0 <= i < map_to_seq(m, lt, f).len(),
+    ensures
+        m.contains_key(k),
+        m[k] == map_to_seq(m, lt, f)[i],
+        f(m[k]),
+{
+    lemma_sorted_keys_exists(m, lt);
+    let keys = sorted_keys(m, lt);
+    let values = keys.map_values(|k: K| m[k]);
+    let v = map_to_seq(m, lt, f)[i];
+    assert(values.filter(f).contains(v));
+    lemma_filter_contains_implies_contains(values, f, v);
+    let j = choose |j: int| 0 <= j < values.len() && values[j] == v;
+    assert(keys.contains(keys[j]));
+    assert(keys.to_set().contains(keys[j]));
+    keys[j]
 }
 
 }
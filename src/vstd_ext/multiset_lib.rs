@@ -83,4 +83,60 @@ pub proof fn filtered_size_is_one_means_only_one_such_value<V>(m: Multiset<V>, f
     }
 }
 
+/// The multiset obtained by renaming each value v in m to f(v), where f is an involution (f(f(v)) == v).
+/// Since f is its own inverse, the count of v in the result is the count of f(v) in m.
+pub open spec fn map_by_involution<V>(m: Multiset<V>, f: spec_fn(V) -> V) -> Multiset<V> {
+    Multiset::from_map(Map::new(|v: V| m.count(f(v)) > 0, |v: V| m.count(f(v))))
+}
+
+pub proof fn map_by_involution_count<V>(m: Multiset<V>, f: spec_fn(V) -> V)
+    requires forall |v: V| #[trigger] f(f(v)) == v,
+    ensures forall |v: V| #[trigger] map_by_involution(m, f).count(v) == m.count(f(v)),
+{
+    let counts = Map::new(|v: V| m.count(f(v)) > 0, |v: V| m.count(f(v)));
+    support_is_finite(m);
+    involution_preimage_is_finite(Set::new(|v: V| m.count(v) > 0), f);
+    assert(counts.dom() =~= Set::new(|v: V| Set::new(|u: V| m.count(u) > 0).contains(f(v))));
+    assert forall |v: V| #[trigger] map_by_involution(m, f).count(v) == m.count(f(v)) by {
+        if counts.dom().contains(v) {
+            assert(Multiset::from_map(counts).count(v) == counts[v]);
+        } else {
+            assert(Multiset::from_map(counts).count(v) == 0);
+        }
+    }
+}
+
+pub proof fn support_is_finite<V>(m: Multiset<V>)
+    ensures Set::new(|v: V| m.count(v) > 0).finite(),
+    decreases m.len(),
+{
+    if m.len() == 0 {
+        len_is_zero_means_count_for_each_value_is_zero(m);
+        assert(Set::new(|v: V| m.count(v) > 0) =~= Set::empty());
+    } else {
+        let v = m.choose();
+        assert(m.count(v) > 0);
+        assert(m.remove(v).len() == m.len() - 1);
+        support_is_finite(m.remove(v));
+        assert(Set::new(|u: V| m.count(u) > 0) =~= Set::new(|u: V| m.remove(v).count(u) > 0).insert(v));
+    }
+}
+
+pub proof fn involution_preimage_is_finite<V>(s: Set<V>, f: spec_fn(V) -> V)
+    requires
+        s.finite(),
+        forall |v: V| #[trigger] f(f(v)) == v,
+    ensures Set::new(|v: V| s.contains(f(v))).finite(),
+    decreases s.len(),
+{
+    if s.len() == 0 {
+        assert(s =~= Set::empty());
+        assert(Set::new(|v: V| s.contains(f(v))) =~= Set::empty());
+    } else {
+        let u = s.choose();
+        involution_preimage_is_finite(s.remove(u), f);
+        assert(Set::new(|v: V| s.contains(f(v))) =~= Set::new(|v: V| s.remove(u).contains(f(v))).insert(f(u)));
+    }
+}
+
 }
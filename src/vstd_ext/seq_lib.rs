@@ -103,4 +103,154 @@ pub proof fn seq_unequal_preserved_by_add_auto<A>(suffix: Seq<A>)
     };
 }
 
+pub proof fn lemma_filter_maintained_after_add<A>(s: Seq<A>, pred: spec_fn(A) -> bool, filtered_s: Seq<A>, new_elt: A)
+    requires filtered_s == s.filter(pred),
+    ensures
+        (pred(new_elt) ==> filtered_s.push(new_elt) == s.push(new_elt).filter(pred)),
+        (!pred(new_elt) ==> filtered_s == s.push(new_elt).filter(pred)),
+{
+    // Lemma follows from body of Seq::filter.
+    reveal(Seq::filter);
+    // For some reason, this law needs to be explicitly asserted.
+    assert(s.push(new_elt).drop_last() == s);
+}
+
+
+pub proof fn lemma_filter_contains<A>(s: Seq<A>, pred: spec_fn(A) -> bool, e: A)
+    requires
+        s.contains(e),
+        pred(e),
+    ensures s.filter(pred).contains(e),
+    decreases s.len(),
+{
+    reveal(Seq::filter);
+    if s.last() == e {
+        // The witness for .contains() is the last index of the filtered sequence.
+        assert(s.filter(pred).last() == e);
+    } else {
+        let i = choose |i| 0 <= i < s.len() && s[i] == e;
+        assert(s.drop_last()[i] == e);
+        lemma_filter_contains(s.drop_last(), pred, e);
+        let j = choose |j| 0 <= j < s.drop_last().filter(pred).len() && s.drop_last().filter(pred)[j] == e;
+        assert(s.filter(pred)[j] == e);
+    }
+}
+
+pub proof fn lemma_filter_contains_implies_contains<A>(s: Seq<A>, pred: spec_fn(A) -> bool, e: A)
+    requires s.filter(pred).contains(e),
+    ensures
+        s.contains(e),
+        pred(e),
+    decreases s.len(),
+{
+    reveal(Seq::filter);
+    if s.len() == 0 {
+        // Trivially true.
+    } else {
+        if pred(s.last()) && s.last() == e {
+            // The witness for .contains() is the last index.
+        } else {
+            let j = choose |j| 0 <= j < s.filter(pred).len() && s.filter(pred)[j] == e;
+            assert(s.drop_last().filter(pred)[j] == e);
+            lemma_filter_contains_implies_contains(s.drop_last(), pred, e);
+            let i = choose |i| 0 <= i < s.drop_last().len() && s.drop_last()[i] == e;
+            assert(s[i] == e);
+        }
+    }
+}
+
+// The elements of s.filter(pred) are the elements of s that satisfy pred, stated for any s and pred so that callers
+// do not need to name the closure of a filter unfolded from a spec function.
+pub proof fn lemma_filter_auto<A>()
+    ensures
+        forall |s: Seq<A>, pred: spec_fn(A) -> bool, i: int| 0 <= i < s.filter(pred).len()
+            ==> pred(#[trigger] s.filter(pred)[i]) && s.contains(s.filter(pred)[i]),
+        forall |s: Seq<A>, pred: spec_fn(A) -> bool, i: int| #![trigger s.filter(pred), s[i]]
+            0 <= i < s.len() && pred(s[i]) ==> s.filter(pred).contains(s[i]),
+{
+    assert forall |s: Seq<A>, pred: spec_fn(A) -> bool, i: int| 0 <= i < s.filter(pred).len()
+    implies pred(#[trigger] s.filter(pred)[i]) && s.contains(s.filter(pred)[i]) by {
+        // The witness for .contains() is i.
+        assert(s.filter(pred).contains(s.filter(pred)[i]));
+        lemma_filter_contains_implies_contains(s, pred, s.filter(pred)[i]);
+    }
+    assert forall |s: Seq<A>, pred: spec_fn(A) -> bool, i: int| #![trigger s.filter(pred), s[i]]
+        0 <= i < s.len() && pred(s[i]) implies s.filter(pred).contains(s[i]) by {
+        assert(s.contains(s[i]));
+        lemma_filter_contains(s, pred, s[i]);
+    }
+}
+
+pub proof fn lemma_filter_preserves_no_duplicates<A>(s: Seq<A>, pred: spec_fn(A) -> bool)
+    requires s.no_duplicates(),
+    ensures s.filter(pred).no_duplicates(),
+    decreases s.len(),
+{
+    reveal(Seq::filter);
+    if s.len() != 0 {
+        let rest = s.drop_last();
+        let filtered_rest = rest.filter(pred);
+        assert forall |i: int, j: int| 0 <= i < rest.len() && 0 <= j < rest.len() && i != j implies rest[i] != rest[j] by {
+            assert(s[i] == rest[i] && s[j] == rest[j]);
+        }
+        lemma_filter_preserves_no_duplicates(rest, pred);
+        if pred(s.last()) {
+            // The last element does not appear earlier in s, so it does not appear in filtered_rest either.
+            if filtered_rest.contains(s.last()) {
+                lemma_filter_contains_implies_contains(rest, pred, s.last());
+                let i = choose |i: int| 0 <= i < rest.len() && rest[i] == s.last();
+                assert(s[i] == s[s.len() - 1]);
+            }
+            assert forall |i: int, j: int| 0 <= i < s.filter(pred).len() && 0 <= j < s.filter(pred).len() && i != j
+            implies s.filter(pred)[i] != s.filter(pred)[j] by {
+                if i < filtered_rest.len() && j < filtered_rest.len() {
+                    assert(s.filter(pred)[i] == filtered_rest[i] && s.filter(pred)[j] == filtered_rest[j]);
+                } else if i < filtered_rest.len() {
+                    assert(filtered_rest.contains(filtered_rest[i]));
+                } else {
+                    assert(filtered_rest.contains(filtered_rest[j]));
+                }
+            }
+        }
+    }
+}
+
+pub proof fn lemma_filter_of_filter<A>(s: Seq<A>, p: spec_fn(A) -> bool, q: spec_fn(A) -> bool, r: spec_fn(A) -> bool)
+    requires forall |x: A| #[trigger] r(x) == (p(x) && q(x)),
+    ensures s.filter(p).filter(q) == s.filter(r),
+    decreases s.len(),
+{
+    reveal(Seq::filter);
+    if s.len() != 0 {
+        let rest = s.drop_last();
+        lemma_filter_of_filter(rest, p, q, r);
+        if p(s.last()) {
+            assert(s.filter(p).drop_last() =~= rest.filter(p));
+            assert(s.filter(p).last() == s.last());
+        } else {
+            assert(s.filter(p) == rest.filter(p));
+        }
+        assert(r(s.last()) == (p(s.last()) && q(s.last())));
+    } else {
+        assert(s.filter(p).len() == 0);
+    }
+}
+
+pub proof fn lemma_map_values_filter_len<A, B>(s: Seq<A>, f: spec_fn(A) -> B, p: spec_fn(B) -> bool, q: spec_fn(A) -> bool)
+    requires forall |x: A| #[trigger] q(x) == p(f(x)),
+    ensures s.map_values(f).filter(p).len() == s.filter(q).len(),
+    decreases s.len(),
+{
+    reveal(Seq::filter);
+    if s.len() != 0 {
+        let rest = s.drop_last();
+        lemma_map_values_filter_len(rest, f, p, q);
+        assert(s.map_values(f).drop_last() =~= rest.map_values(f));
+        assert(s.map_values(f).last() == f(s.last()));
+        assert(q(s.last()) == p(f(s.last())));
+    } else {
+        assert(s.map_values(f).len() == 0);
+    }
+}
+
 }
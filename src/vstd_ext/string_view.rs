@@ -15,6 +15,13 @@ pub fn i32_to_string(i: i32) -> (s: String)
     i.to_string()
 }
 
+#[verifier(external_body)]
+pub fn i64_to_string(i: i64) -> (s: String)
+    ensures s@ == int_to_string_view(i as int),
+{
+    i.to_string()
+}
+
 pub closed spec fn int_to_string_view(i: int) -> StringView;
 
 #[verifier(external_body)]
@@ -22,6 +29,50 @@ pub proof fn int_to_string_view_injectivity()
     ensures forall |i: int, j: int| int_to_string_view(i) == int_to_string_view(j) ==> i == j,
 {}
 
+pub open spec fn is_digit(c: char) -> bool {
+    ('0' as u32) <= (c as u32) && (c as u32) <= ('9' as u32)
+}
+
+// A non-negative integer is printed in decimal without a sign, same as i.to_string().
+#[verifier(external_body)]
+pub proof fn int_to_string_view_of_non_negative_int_has_only_digits()
+    ensures
+        forall |i: int, j: int| 0 <= i && 0 <= j < int_to_string_view(i).len()
+            ==> #[trigger] is_digit(int_to_string_view(i)[j]),
+{}
+
+// Since "-" never appears in a printed non-negative integer, a string of the form prefix + "-" + i
+// (e.g., a name generated from generate_name prefix + "-") determines both the prefix and i.
+pub proof fn lemma_dash_int_suffix_is_unique(a1: StringView, i1: int, a2: StringView, i2: int)
+    requires
+        0 <= i1,
+        0 <= i2,
+        a1 + "-"@ + int_to_string_view(i1) == a2 + "-"@ + int_to_string_view(i2),
+    ensures
+        a1 == a2,
+        i1 == i2,
+{
+    reveal_strlit("-");
+    int_to_string_view_of_non_negative_int_has_only_digits();
+    int_to_string_view_injectivity();
+    let b1 = int_to_string_view(i1);
+    let b2 = int_to_string_view(i2);
+    let s = a1 + "-"@ + b1;
+    assert(s[a1.len() as int] == '-');
+    assert(s[a2.len() as int] == '-');
+    if a1.len() < a2.len() {
+        assert(s[a2.len() as int] == b1[a2.len() - a1.len() - 1]);
+        assert(is_digit(b1[a2.len() - a1.len() - 1]));
+    } else if a2.len() < a1.len() {
+        assert(s[a1.len() as int] == b2[a1.len() - a2.len() - 1]);
+        assert(is_digit(b2[a1.len() - a2.len() - 1]));
+    }
+    assert(a1 =~= s.subrange(0, a1.len() as int));
+    assert(a2 =~= s.subrange(0, a2.len() as int));
+    assert(b1 =~= s.subrange(a1.len() + 1, s.len() as int));
+    assert(b2 =~= s.subrange(a2.len() + 1, s.len() as int));
+}
+
 #[verifier(external_body)]
 pub fn bool_to_string(b: bool) -> (s: String)
     ensures s@ == bool_to_string_view(b),
@@ -36,6 +87,57 @@ pub proof fn bool_to_string_view_injectivity()
     ensures forall |i: bool, j: bool| bool_to_string_view(i) == bool_to_string_view(j) ==> i == j,
 {}
 
+// string_view_lt is the lexicographic order of strings. It agrees with how std::string::String is ordered,
+// since comparing the UTF-8 encodings of two strings byte by byte is the same as comparing their chars.
+pub open spec fn string_view_lt(a: StringView, b: StringView) -> bool
+    decreases a.len(),
+{
+    if a.len() == 0 {
+        b.len() > 0
+    } else if b.len() == 0 {
+        false
+    } else if a[0] != b[0] {
+        (a[0] as u32) < (b[0] as u32)
+    } else {
+        string_view_lt(a.drop_first(), b.drop_first())
+    }
+}
+
+pub proof fn lemma_string_view_lt_is_irreflexive(a: StringView)
+    ensures !string_view_lt(a, a),
+    decreases a.len(),
+{
+    if a.len() > 0 {
+        lemma_string_view_lt_is_irreflexive(a.drop_first());
+    }
+}
+
+pub proof fn lemma_string_view_lt_is_transitive(a: StringView, b: StringView, c: StringView)
+    requires
+        string_view_lt(a, b),
+        string_view_lt(b, c),
+    ensures string_view_lt(a, c),
+    decreases a.len(),
+{
+    if a.len() > 0 && a[0] == b[0] && b[0] == c[0] {
+        lemma_string_view_lt_is_transitive(a.drop_first(), b.drop_first(), c.drop_first());
+    }
+}
+
+pub proof fn lemma_string_view_lt_is_total(a: StringView, b: StringView)
+    requires a != b,
+    ensures string_view_lt(a, b) || string_view_lt(b, a),
+    decreases a.len(),
+{
+    if a.len() == 0 && b.len() == 0 {
+        assert(a =~= b);
+    } else if a.len() > 0 && b.len() > 0 && a[0] == b[0] {
+        assert(a =~= seq![a[0]] + a.drop_first());
+        assert(b =~= seq![b[0]] + b.drop_first());
+        lemma_string_view_lt_is_total(a.drop_first(), b.drop_first());
+    }
+}
+
 pub open spec fn opt_string_to_view(s: &Option<String>) -> Option<StringView> {
     match s {
         Some(s1) => Some(s1@),